@@ -409,6 +409,7 @@ impl EventIntegrationTest {
       .payload(GroupByFieldPayloadPB {
         field_id: field_id.to_string(),
         view_id: view_id.to_string(),
+        setting_content: String::new(),
       })
       .async_send()
       .await
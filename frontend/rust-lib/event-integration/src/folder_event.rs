@@ -124,6 +124,22 @@ impl EventIntegrationTest {
       .parse::<ViewPB>()
   }
 
+  pub async fn import_directory(
+    &self,
+    parent_view_id: &str,
+    path: &str,
+  ) -> ImportDirectoryResultPB {
+    EventBuilder::new(self.clone())
+      .event(FolderEvent::ImportDirectory)
+      .payload(ImportDirectoryPB {
+        parent_view_id: parent_view_id.to_string(),
+        path: path.to_string(),
+      })
+      .async_send()
+      .await
+      .parse::<ImportDirectoryResultPB>()
+  }
+
   pub async fn get_view(&self, view_id: &str) -> ViewPB {
     EventBuilder::new(self.clone())
       .event(FolderEvent::GetView)
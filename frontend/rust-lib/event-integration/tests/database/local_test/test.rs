@@ -6,9 +6,13 @@ use event_integration::event_builder::EventBuilder;
 use event_integration::EventIntegrationTest;
 use flowy_database2::entities::{
   CellChangesetPB, CellIdPB, ChecklistCellDataChangesetPB, DatabaseLayoutPB,
-  DatabaseSettingChangesetPB, DatabaseViewIdPB, DateChangesetPB, FieldType, OrderObjectPositionPB,
-  SelectOptionCellDataPB, UpdateRowMetaChangesetPB,
+  DatabaseSettingChangesetPB, DatabaseViewIdPB, DateChangesetPB, FieldType, FieldValidationPB,
+  FieldValidationRulePB, OrderObjectPositionPB, SelectOptionCellDataPB,
+  UpdateFieldValidationPayloadPB, UpdateRowMetaChangesetPB,
 };
+use flowy_database2::event_map::DatabaseEvent;
+use flowy_folder::entities::{CreateViewPayloadPB, UpdateViewPayloadPB, ViewLayoutPB, ViewPB};
+use flowy_folder::event_map::FolderEvent;
 use lib_infra::util::timestamp;
 
 #[tokio::test]
@@ -209,6 +213,43 @@ async fn create_row_event_test() {
   assert_eq!(database.rows.len(), 4);
 }
 
+#[tokio::test]
+async fn create_row_without_required_cell_event_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "my grid view".to_owned(), vec![])
+    .await;
+  let text_field = test.get_primary_field(&grid_view.id).await;
+  let error = EventBuilder::new(test.clone())
+    .event(DatabaseEvent::UpdateFieldValidation)
+    .payload(UpdateFieldValidationPayloadPB {
+      view_id: grid_view.id.clone(),
+      field_id: text_field.id.clone(),
+      validation: FieldValidationPB {
+        required: true,
+        ..Default::default()
+      },
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+
+  // The blank row is created, and the missing cell is returned with it
+  let row_meta = test
+    .create_row(&grid_view.id, OrderObjectPositionPB::default(), None)
+    .await;
+  let database = test.get_database(&grid_view.id).await;
+  assert_eq!(database.rows.len(), 4);
+  assert_eq!(row_meta.validation_errors.len(), 1);
+  assert_eq!(row_meta.validation_errors[0].field_id, text_field.id);
+  assert_eq!(
+    row_meta.validation_errors[0].rule,
+    FieldValidationRulePB::Required
+  );
+}
+
 #[tokio::test]
 async fn delete_row_event_test() {
   let test = EventIntegrationTest::new_with_guest_user().await;
@@ -680,6 +721,77 @@ async fn update_database_layout_event_test() {
   assert_eq!(database.layout_type, DatabaseLayoutPB::Board);
 }
 
+// Update the database layout type from grid to timeline. A date field is created for the spans
+#[tokio::test]
+async fn update_database_layout_to_timeline_event_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "my grid view".to_owned(), vec![])
+    .await;
+
+  let error = test
+    .update_setting(DatabaseSettingChangesetPB {
+      view_id: grid_view.id.clone(),
+      layout_type: Some(DatabaseLayoutPB::Timeline),
+      ..Default::default()
+    })
+    .await;
+  assert!(error.is_none());
+
+  let database = test.get_database(&grid_view.id).await;
+  assert_eq!(database.layout_type, DatabaseLayoutPB::Timeline);
+  let fields = test.get_all_database_fields(&grid_view.id).await.items;
+  assert!(fields
+    .iter()
+    .any(|field| field.field_type == FieldType::DateTime));
+}
+
+#[tokio::test]
+async fn create_timeline_view_event_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+  let timeline_view = EventBuilder::new(test.clone())
+    .event(FolderEvent::CreateView)
+    .payload(CreateViewPayloadPB {
+      parent_view_id: current_workspace.id.clone(),
+      name: "my timeline view".to_owned(),
+      desc: "".to_string(),
+      thumbnail: None,
+      layout: ViewLayoutPB::Timeline,
+      initial_data: vec![],
+      meta: Default::default(),
+      set_as_current: true,
+      index: None,
+    })
+    .async_send()
+    .await
+    .parse::<ViewPB>();
+  assert_eq!(timeline_view.layout, ViewLayoutPB::Timeline);
+  assert_eq!(
+    test.get_view(&timeline_view.id).await.layout,
+    ViewLayoutPB::Timeline
+  );
+  let database = test.get_database(&timeline_view.id).await;
+  assert_eq!(database.layout_type, DatabaseLayoutPB::Timeline);
+
+  // Switch the view back to a grid through the folder
+  let error = test
+    .update_view(UpdateViewPayloadPB {
+      view_id: timeline_view.id.clone(),
+      layout: Some(ViewLayoutPB::Grid),
+      ..Default::default()
+    })
+    .await;
+  assert!(error.is_none());
+  assert_eq!(
+    test.get_view(&timeline_view.id).await.layout,
+    ViewLayoutPB::Grid
+  );
+  let database = test.get_database(&timeline_view.id).await;
+  assert_eq!(database.layout_type, DatabaseLayoutPB::Grid);
+}
+
 // Update the database layout type from grid to board. Set the checkbox field as the grouping field
 #[tokio::test]
 async fn update_database_layout_event_test2() {
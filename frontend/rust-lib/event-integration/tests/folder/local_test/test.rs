@@ -556,3 +556,90 @@ async fn move_folder_nested_view(
     .async_send()
     .await;
 }
+
+#[tokio::test]
+async fn import_directory_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+
+  // A directory that is exported from Notion
+  let dir = tempdir::TempDir::new("import_directory").unwrap();
+  let notes_stem = "Notes 0c9a1f3a1e6d4c2a9b8e7f6d5c4b3a21";
+  std::fs::write(
+    dir.path().join(format!("{}.md", notes_stem)),
+    "# Notes\n\nSee [Child](Notes%200c9a1f3a1e6d4c2a9b8e7f6d5c4b3a21/Child.md)\n",
+  )
+  .unwrap();
+  std::fs::create_dir(dir.path().join(notes_stem)).unwrap();
+  std::fs::write(dir.path().join(notes_stem).join("Child.md"), "Child page\n").unwrap();
+  std::fs::write(dir.path().join("Tasks.csv"), "Name,Done\nWrite docs,Yes\n").unwrap();
+  std::fs::write(
+    dir.path().join("Tasks_all.csv"),
+    "Name,Done\nWrite docs,Yes\n",
+  )
+  .unwrap();
+  std::fs::create_dir(dir.path().join(".obsidian")).unwrap();
+  std::fs::write(dir.path().join(".obsidian").join("config.md"), "").unwrap();
+  // The link to the directory itself is skipped instead of being scanned forever
+  #[cfg(unix)]
+  std::os::unix::fs::symlink(dir.path(), dir.path().join("Loop")).unwrap();
+
+  let result = test
+    .import_directory(&current_workspace.id, dir.path().to_str().unwrap())
+    .await;
+  assert!(result.failed_files.is_empty());
+  let views = result.views;
+  let names = views
+    .iter()
+    .map(|view| (view.name.as_str(), view.layout.clone()))
+    .collect::<Vec<_>>();
+  assert_eq!(
+    names,
+    vec![
+      ("Notes", ViewLayoutPB::Document),
+      ("Tasks", ViewLayoutPB::Grid),
+      ("Child", ViewLayoutPB::Document),
+    ]
+  );
+  assert_eq!(views[0].parent_view_id, current_workspace.id);
+  assert_eq!(views[2].parent_view_id, views[0].id);
+
+  // The link to the child page is rewritten to a mention of its view
+  let document = test.get_document_data(&views[0].id).await;
+  let text_map = document.meta.text_map.unwrap();
+  assert!(text_map
+    .values()
+    .any(|delta| delta.contains(&format!("\"page_id\":\"{}\"", views[2].id))));
+
+  let database = test.get_database(&views[1].id).await;
+  assert_eq!(database.rows.len(), 1);
+}
+
+#[tokio::test]
+async fn import_directory_with_invalid_file_test() {
+  let test = EventIntegrationTest::new_with_guest_user().await;
+  let current_workspace = test.get_current_workspace().await;
+
+  // The markdown file is not valid utf-8, the page nested in it is imported anyway
+  let dir = tempdir::TempDir::new("import_directory").unwrap();
+  std::fs::write(dir.path().join("Broken.md"), [0xff, 0xfe, 0xfd]).unwrap();
+  std::fs::create_dir(dir.path().join("Broken")).unwrap();
+  std::fs::write(dir.path().join("Broken").join("Child.md"), "Child page\n").unwrap();
+  std::fs::write(dir.path().join("Notes.md"), "Notes\n").unwrap();
+
+  let result = test
+    .import_directory(&current_workspace.id, dir.path().to_str().unwrap())
+    .await;
+  assert_eq!(result.failed_files.len(), 1);
+  assert_eq!(result.failed_files[0].file_path, "Broken.md");
+  let names = result
+    .views
+    .iter()
+    .map(|view| view.name.as_str())
+    .collect::<Vec<_>>();
+  assert_eq!(names, vec!["Notes", "Child"]);
+  assert!(result
+    .views
+    .iter()
+    .all(|view| view.parent_view_id == current_workspace.id));
+}
@@ -4,6 +4,7 @@ use std::path::Path;
 use base64::Engine;
 use tracing::{error, info};
 
+use flowy_server::FileSizeLimitPlan;
 use flowy_server_pub::af_cloud_config::AFCloudConfiguration;
 use flowy_server_pub::supabase_config::SupabaseConfiguration;
 use flowy_user::services::entities::URL_SAFE_ENGINE;
@@ -25,6 +26,8 @@ pub struct AppFlowyCoreConfig {
   /// the origin_application_path.
   pub application_path: String,
   pub(crate) log_filter: String,
+  /// The maximum size of each file uploaded to the servers that don't report their own limits.
+  pub(crate) maximum_file_size: u64,
   cloud_config: Option<AFCloudConfiguration>,
 }
 
@@ -98,6 +101,7 @@ impl AppFlowyCoreConfig {
       application_path,
       device_id,
       log_filter: create_log_filter("info".to_owned(), vec![]),
+      maximum_file_size: FileSizeLimitPlan::DEFAULT_MAXIMUM_FILE_SIZE,
       cloud_config,
     }
   }
@@ -106,4 +110,9 @@ impl AppFlowyCoreConfig {
     self.log_filter = create_log_filter(level.to_owned(), with_crates);
     self
   }
+
+  pub fn maximum_file_size(mut self, maximum_file_size: u64) -> Self {
+    self.maximum_file_size = maximum_file_size;
+    self
+  }
 }
@@ -3,7 +3,11 @@ use collab_integrate::CollabKVDB;
 use flowy_database2::{DatabaseManager, DatabaseUser};
 use flowy_database_pub::cloud::DatabaseCloudService;
 use flowy_error::FlowyError;
+use flowy_sqlite::DBConnection;
+use flowy_storage::{FileStoragePlan, ObjectStorageService};
 use flowy_user::services::authenticate_user::AuthenticateUser;
+use flowy_user_pub::cloud::UserCloudServiceProvider;
+use lib_infra::future::FutureResult;
 use lib_infra::priority_task::TaskDispatcher;
 use std::sync::{Arc, Weak};
 use tokio::sync::RwLock;
@@ -12,21 +16,26 @@ pub struct DatabaseDepsResolver();
 impl DatabaseDepsResolver {
   pub async fn resolve(
     authenticate_user: Weak<AuthenticateUser>,
+    user_cloud_service: Weak<dyn UserCloudServiceProvider>,
     task_scheduler: Arc<RwLock<TaskDispatcher>>,
     collab_builder: Arc<AppFlowyCollabBuilder>,
     cloud_service: Arc<dyn DatabaseCloudService>,
+    storage_service: Weak<dyn ObjectStorageService>,
+    storage_plan: Weak<dyn FileStoragePlan>,
   ) -> Arc<DatabaseManager> {
-    let user = Arc::new(DatabaseUserImpl(authenticate_user));
+    let user = Arc::new(DatabaseUserImpl(authenticate_user, user_cloud_service));
     Arc::new(DatabaseManager::new(
       user,
       task_scheduler,
       collab_builder,
       cloud_service,
+      storage_service,
+      storage_plan,
     ))
   }
 }
 
-struct DatabaseUserImpl(Weak<AuthenticateUser>);
+struct DatabaseUserImpl(Weak<AuthenticateUser>, Weak<dyn UserCloudServiceProvider>);
 impl DatabaseUser for DatabaseUserImpl {
   fn user_id(&self) -> Result<i64, FlowyError> {
     self
@@ -36,6 +45,59 @@ impl DatabaseUser for DatabaseUserImpl {
       .user_id()
   }
 
+  fn workspace_id(&self) -> Result<String, FlowyError> {
+    self
+      .0
+      .upgrade()
+      .ok_or(FlowyError::internal().with_context("Unexpected error: UserSession is None"))?
+      .workspace_id()
+  }
+
+  fn user_email(&self) -> Result<String, FlowyError> {
+    let profile = self
+      .0
+      .upgrade()
+      .ok_or(FlowyError::internal().with_context("Unexpected error: UserSession is None"))?
+      .get_user_profile()?;
+    Ok(profile.email)
+  }
+
+  fn workspace_member_emails(&self) -> FutureResult<Vec<String>, FlowyError> {
+    let authenticate_user = self.0.upgrade();
+    let user_cloud_service = self.1.upgrade();
+    FutureResult::new(async move {
+      let authenticate_user = authenticate_user
+        .ok_or(FlowyError::internal().with_context("Unexpected error: UserSession is None"))?;
+      let user_cloud_service = user_cloud_service
+        .ok_or(FlowyError::internal().with_context("Unexpected error: ServerProvider is None"))?;
+      let workspace_id = authenticate_user.workspace_id()?;
+      let mut emails = user_cloud_service
+        .get_user_service()?
+        .get_workspace_members(workspace_id)
+        .await?
+        .into_iter()
+        .map(|member| member.email)
+        .collect::<Vec<_>>();
+
+      // The local workspaces don't have a member list. The current user is always a member.
+      let email = authenticate_user.get_user_profile()?.email;
+      if !emails.contains(&email) {
+        emails.push(email);
+      }
+      Ok(emails)
+    })
+  }
+
+  fn device_id(&self) -> Result<String, FlowyError> {
+    Ok(
+      self
+        .0
+        .upgrade()
+        .ok_or(FlowyError::internal().with_context("Unexpected error: UserSession is None"))?
+        .device_id(),
+    )
+  }
+
   fn collab_db(&self, uid: i64) -> Result<Weak<CollabKVDB>, FlowyError> {
     self
       .0
@@ -43,4 +105,12 @@ impl DatabaseUser for DatabaseUserImpl {
       .ok_or(FlowyError::internal().with_context("Unexpected error: UserSession is None"))?
       .get_collab_db(uid)
   }
+
+  fn sqlite_connection(&self, uid: i64) -> Result<DBConnection, FlowyError> {
+    self
+      .0
+      .upgrade()
+      .ok_or(FlowyError::internal().with_context("Unexpected error: UserSession is None"))?
+      .get_sqlite_connection(uid)
+  }
 }
@@ -2,20 +2,29 @@ use bytes::Bytes;
 use collab_integrate::collab_builder::AppFlowyCollabBuilder;
 use collab_integrate::CollabKVDB;
 use flowy_database2::entities::DatabaseLayoutPB;
-use flowy_database2::services::share::csv::CSVFormat;
+use flowy_database2::services::share::csv::{csv_field_types_from_meta, CSVFormat};
 use flowy_database2::template::{make_default_board, make_default_calendar, make_default_grid};
 use flowy_database2::DatabaseManager;
 use flowy_document::entities::DocumentDataPB;
 use flowy_document::manager::DocumentManager;
+use flowy_document::parser::constant::PAGE;
+use flowy_document::parser::external::markdown::{
+  markdown_image_urls, resolve_markdown_link, rewrite_markdown_links,
+};
+use flowy_document::parser::external::parser::ExternalDataToNestedJSONParser;
 use flowy_document::parser::json::parser::JsonToDocumentParser;
-use flowy_error::FlowyError;
+use flowy_document::parser::parser_entities::{InputType, NestedBlock};
+use flowy_error::{FlowyError, FlowyResult};
 use flowy_folder::entities::ViewLayoutPB;
 use flowy_folder::manager::{FolderManager, FolderUser};
-use flowy_folder::share::ImportType;
-use flowy_folder::view_operation::{FolderOperationHandler, FolderOperationHandlers, View};
+use flowy_folder::share::{
+  ImportType, IMPORT_FILE_PATH, IMPORT_ROOT_DIR, IMPORT_VIEW_LINKS, IMPORT_WORKSPACE_ID,
+};
+use flowy_folder::view_operation::{FolderOperationHandler, FolderOperationHandlers};
 use flowy_folder::ViewLayout;
 use std::collections::HashMap;
 use std::convert::TryFrom;
+use std::path::Path;
 use std::sync::{Arc, Weak};
 use tokio::sync::RwLock;
 
@@ -164,10 +173,10 @@ impl FolderOperationHandler for DocumentFolderOperation {
     view_id: &str,
     _name: &str,
     data: Vec<u8>,
-    layout: ViewLayout,
+    layout: ViewLayoutPB,
     _meta: HashMap<String, String>,
   ) -> FutureResult<(), FlowyError> {
-    debug_assert_eq!(layout, ViewLayout::Document);
+    debug_assert_eq!(layout, ViewLayoutPB::Document);
     let view_id = view_id.to_string();
     let manager = self.0.clone();
     FutureResult::new(async move {
@@ -185,9 +194,9 @@ impl FolderOperationHandler for DocumentFolderOperation {
     user_id: i64,
     view_id: &str,
     _name: &str,
-    layout: ViewLayout,
+    layout: ViewLayoutPB,
   ) -> FutureResult<(), FlowyError> {
-    debug_assert_eq!(layout, ViewLayout::Document);
+    debug_assert_eq!(layout, ViewLayoutPB::Document);
     let view_id = view_id.to_string();
     let manager = self.0.clone();
     FutureResult::new(async move {
@@ -209,13 +218,25 @@ impl FolderOperationHandler for DocumentFolderOperation {
     uid: i64,
     view_id: &str,
     _name: &str,
-    _import_type: ImportType,
+    import_type: ImportType,
     bytes: Vec<u8>,
+    meta: HashMap<String, String>,
   ) -> FutureResult<(), FlowyError> {
     let view_id = view_id.to_string();
     let manager = self.0.clone();
     FutureResult::new(async move {
-      let data = DocumentDataPB::try_from(Bytes::from(bytes))?;
+      let data = match import_type {
+        ImportType::Markdown => {
+          let mut page = markdown_to_nested_block(bytes)?;
+          // The markdown file is a part of an imported directory
+          if let Some(file_path) = meta.get(IMPORT_FILE_PATH) {
+            rewrite_imported_markdown_links(&manager, &mut page, Path::new(file_path), &meta)
+              .await?;
+          }
+          nested_block_to_document(&page)?
+        },
+        _ => DocumentDataPB::try_from(Bytes::from(bytes))?,
+      };
       manager
         .create_document(uid, &view_id, Some(data.into()))
         .await?;
@@ -228,12 +249,83 @@ impl FolderOperationHandler for DocumentFolderOperation {
     &self,
     _view_id: &str,
     _name: &str,
+    _import_type: ImportType,
     _path: String,
   ) -> FutureResult<(), FlowyError> {
     FutureResult::new(async move { Ok(()) })
   }
 }
 
+/// Convert the content of a markdown file to the page block. An empty file is imported as an
+/// empty page.
+fn markdown_to_nested_block(bytes: Vec<u8>) -> FlowyResult<NestedBlock> {
+  let markdown = String::from_utf8(bytes).map_err(|_| FlowyError::invalid_data())?;
+  let page = ExternalDataToNestedJSONParser::new(markdown, InputType::Markdown)
+    .to_nested_block()
+    .unwrap_or_else(|| NestedBlock {
+      ty: PAGE.to_string(),
+      ..Default::default()
+    });
+  Ok(page)
+}
+
+fn nested_block_to_document(page: &NestedBlock) -> FlowyResult<DocumentDataPB> {
+  let json_str = serde_json::to_string(page)?;
+  JsonToDocumentParser::json_str_to_document(&json_str)
+}
+
+/// Rewrites the relative links of a markdown file that is imported with its directory. The links
+/// to the other imported files become mentions of their views, and the local images are uploaded.
+async fn rewrite_imported_markdown_links(
+  manager: &DocumentManager,
+  page: &mut NestedBlock,
+  file_path: &Path,
+  meta: &HashMap<String, String>,
+) -> FlowyResult<()> {
+  // Without the imported directory, none of the local links can be followed safely
+  let root_dir = match meta.get(IMPORT_ROOT_DIR) {
+    Some(root_dir) => Path::new(root_dir).canonicalize()?,
+    None => return Ok(()),
+  };
+  let base_dir = file_path.parent().unwrap_or_else(|| Path::new(""));
+  let view_links = match meta.get(IMPORT_VIEW_LINKS) {
+    Some(view_links) => serde_json::from_str::<HashMap<String, String>>(view_links)?,
+    None => HashMap::new(),
+  };
+
+  let mut image_urls = HashMap::new();
+  if let Some(workspace_id) = meta.get(IMPORT_WORKSPACE_ID) {
+    for url in markdown_image_urls(page) {
+      if image_urls.contains_key(&url) {
+        continue;
+      }
+      if let Some(image_path) = resolve_markdown_link(&root_dir, base_dir, &url) {
+        let local_file_path = image_path.to_string_lossy().to_string();
+        match manager
+          .upload_file(workspace_id.clone(), &local_file_path, false)
+          .await
+        {
+          Ok(object_url) => {
+            image_urls.insert(url, object_url);
+          },
+          // The image keeps its local url, so it can be fixed after the import
+          Err(err) => tracing::error!("Failed to upload the imported image: {}", err),
+        }
+      }
+    }
+  }
+
+  rewrite_markdown_links(
+    page,
+    &|link| {
+      let path = resolve_markdown_link(&root_dir, base_dir, link)?;
+      view_links.get(path.to_string_lossy().as_ref()).cloned()
+    },
+    &|url| image_urls.get(url).cloned(),
+  );
+  Ok(())
+}
+
 struct DatabaseFolderOperation(Arc<DatabaseManager>);
 impl FolderOperationHandler for DatabaseFolderOperation {
   fn close_view(&self, view_id: &str) -> FutureResult<(), FlowyError> {
@@ -275,7 +367,7 @@ impl FolderOperationHandler for DatabaseFolderOperation {
     view_id: &str,
     name: &str,
     data: Vec<u8>,
-    layout: ViewLayout,
+    layout: ViewLayoutPB,
     meta: HashMap<String, String>,
   ) -> FutureResult<(), FlowyError> {
     match CreateDatabaseExtParams::from_map(meta) {
@@ -291,7 +383,7 @@ impl FolderOperationHandler for DatabaseFolderOperation {
       },
       Some(params) => {
         let database_manager = self.0.clone();
-        let layout = layout_type_from_view_layout(layout.into());
+        let layout = layout_type_from_view_layout(layout);
         let name = name.to_string();
         let database_view_id = view_id.to_string();
 
@@ -314,32 +406,38 @@ impl FolderOperationHandler for DatabaseFolderOperation {
     _user_id: i64,
     view_id: &str,
     name: &str,
-    layout: ViewLayout,
+    layout: ViewLayoutPB,
   ) -> FutureResult<(), FlowyError> {
     let name = name.to_string();
     let database_manager = self.0.clone();
     let data = match layout {
-      ViewLayout::Grid => make_default_grid(view_id, &name),
-      ViewLayout::Board => make_default_board(view_id, &name),
-      ViewLayout::Calendar => make_default_calendar(view_id, &name),
-      ViewLayout::Document => {
+      // The timeline is a grid with the timeline layout setting
+      ViewLayoutPB::Grid | ViewLayoutPB::Timeline => make_default_grid(view_id, &name),
+      ViewLayoutPB::Board => make_default_board(view_id, &name),
+      ViewLayoutPB::Calendar => make_default_calendar(view_id, &name),
+      ViewLayoutPB::Document => {
         return FutureResult::new(async move {
           Err(FlowyError::internal().with_context(format!("Can't handle {:?} layout type", layout)))
         });
       },
     };
+    let view_id = view_id.to_string();
     FutureResult::new(async move {
       let result = database_manager.create_database_with_params(data).await;
       match result {
-        Ok(_) => Ok(()),
+        Ok(_) => {},
         Err(err) => {
-          if err.is_already_exists() {
-            Ok(())
-          } else {
-            Err(err)
+          if !err.is_already_exists() {
+            return Err(err);
           }
         },
       }
+      if layout == ViewLayoutPB::Timeline {
+        database_manager
+          .update_database_layout(&view_id, DatabaseLayoutPB::Timeline)
+          .await?;
+      }
+      Ok(())
     })
   }
 
@@ -350,6 +448,7 @@ impl FolderOperationHandler for DatabaseFolderOperation {
     _name: &str,
     import_type: ImportType,
     bytes: Vec<u8>,
+    meta: HashMap<String, String>,
   ) -> FutureResult<(), FlowyError> {
     let database_manager = self.0.clone();
     let view_id = view_id.to_string();
@@ -359,55 +458,85 @@ impl FolderOperationHandler for DatabaseFolderOperation {
       ImportType::RawDatabase => CSVFormat::META,
       _ => CSVFormat::Original,
     };
+    // The field types that the user chose after previewing the CSV.
+    let field_types = match import_type {
+      ImportType::CSV => csv_field_types_from_meta(&meta),
+      _ => None,
+    };
     FutureResult::new(async move {
       let content =
         String::from_utf8(bytes).map_err(|err| FlowyError::internal().with_context(err))?;
-      database_manager
-        .import_csv(view_id, content, format)
-        .await?;
+      match field_types {
+        None => {
+          database_manager
+            .import_csv(view_id, content, format)
+            .await?;
+        },
+        Some(field_types) => {
+          database_manager
+            .import_csv_with_field_types(view_id, content, field_types)
+            .await?;
+        },
+      }
       Ok(())
     })
   }
 
   fn import_from_file_path(
     &self,
-    _view_id: &str,
+    view_id: &str,
     _name: &str,
+    import_type: ImportType,
     path: String,
   ) -> FutureResult<(), FlowyError> {
     let database_manager = self.0.clone();
+    let view_id = view_id.to_string();
+    let format = match import_type {
+      ImportType::HistoryDatabase | ImportType::RawDatabase => CSVFormat::META,
+      _ => CSVFormat::Original,
+    };
     FutureResult::new(async move {
       database_manager
-        .import_csv_from_file(path, CSVFormat::META)
+        .import_csv_from_file(view_id, path, format)
         .await?;
       Ok(())
     })
   }
 
-  fn did_update_view(&self, old: &View, new: &View) -> FutureResult<(), FlowyError> {
-    let database_layout = match new.layout {
-      ViewLayout::Document => {
-        return FutureResult::new(async {
-          Err(FlowyError::internal().with_context("Can't handle document layout type"))
-        });
-      },
-      ViewLayout::Grid => DatabaseLayoutPB::Grid,
-      ViewLayout::Board => DatabaseLayoutPB::Board,
-      ViewLayout::Calendar => DatabaseLayoutPB::Calendar,
-    };
+  fn will_update_view_layout(
+    &self,
+    view_id: &str,
+    layout: ViewLayoutPB,
+  ) -> FutureResult<(), FlowyError> {
+    if layout == ViewLayoutPB::Document {
+      return FutureResult::new(async {
+        Err(FlowyError::internal().with_context("Can't handle document layout type"))
+      });
+    }
 
     let database_manager = self.0.clone();
-    let view_id = new.id.clone();
-    if old.layout != new.layout {
-      FutureResult::new(async move {
+    let view_id = view_id.to_string();
+    FutureResult::new(async move {
+      let database_layout = layout_type_from_view_layout(layout);
+      if database_manager.get_database_layout(&view_id).await? != database_layout {
         database_manager
           .update_database_layout(&view_id, database_layout)
           .await?;
-        Ok(())
-      })
-    } else {
-      FutureResult::new(async move { Ok(()) })
-    }
+      }
+      Ok(())
+    })
+  }
+
+  fn get_view_layout(&self, view_id: &str) -> FutureResult<Option<ViewLayoutPB>, FlowyError> {
+    let database_manager = self.0.clone();
+    let view_id = view_id.to_string();
+    FutureResult::new(async move {
+      let layout = match database_manager.get_database_layout(&view_id).await? {
+        DatabaseLayoutPB::Timeline => Some(ViewLayoutPB::Timeline),
+        _ => None,
+      };
+      Ok(layout)
+    })
   }
 }
 
@@ -428,6 +557,7 @@ pub fn layout_type_from_view_layout(layout: ViewLayoutPB) -> DatabaseLayoutPB {
     ViewLayoutPB::Grid => DatabaseLayoutPB::Grid,
     ViewLayoutPB::Board => DatabaseLayoutPB::Board,
     ViewLayoutPB::Calendar => DatabaseLayoutPB::Calendar,
+    ViewLayoutPB::Timeline => DatabaseLayoutPB::Timeline,
     ViewLayoutPB::Document => DatabaseLayoutPB::Grid,
   }
 }
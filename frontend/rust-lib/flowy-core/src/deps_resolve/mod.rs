@@ -2,6 +2,7 @@ pub use collab_deps::*;
 pub use database_deps::*;
 pub use document_deps::*;
 pub use folder_deps::*;
+pub use search_deps::*;
 pub use user_deps::*;
 
 mod collab_deps;
@@ -10,4 +11,5 @@ mod folder_deps;
 mod util;
 
 mod database_deps;
+mod search_deps;
 mod user_deps;
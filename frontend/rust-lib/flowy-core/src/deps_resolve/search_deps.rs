@@ -0,0 +1,456 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use collab_database::rows::RowId;
+use collab_document::blocks::DocumentData;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::broadcast::Receiver;
+use tracing::{error, warn};
+
+use flowy_database2::services::database::{DatabaseDataChanged, RowSearchText};
+use flowy_database2::DatabaseManager;
+use flowy_document::manager::DocumentManager;
+use flowy_document::parser::document_data_parser::DocumentDataParser;
+use flowy_error::{internal_error, FlowyError, FlowyResult};
+use flowy_folder::manager::FolderManager;
+use flowy_folder::view_operation::View;
+use flowy_folder::{ViewChange, ViewLayout};
+use flowy_search::entities::{SearchIndexItem, SearchItemTypePB};
+use flowy_search::manager::{SearchIndexSource, SearchManager, SearchUser};
+use flowy_sqlite::DBConnection;
+use flowy_user::services::authenticate_user::AuthenticateUser;
+use lib_dispatch::prelude::af_spawn;
+use lib_infra::future::FutureResult;
+
+/// A changed document or database row is indexed after it stays unchanged for this duration, so
+/// it's not indexed on every keystroke.
+const INDEX_DEBOUNCE: Duration = Duration::from_millis(500);
+
+pub struct SearchDepsResolver();
+impl SearchDepsResolver {
+  pub fn resolve(
+    authenticate_user: Weak<AuthenticateUser>,
+    folder_manager: &Arc<FolderManager>,
+    document_manager: &Arc<DocumentManager>,
+    database_manager: &Arc<DatabaseManager>,
+  ) -> Arc<SearchManager> {
+    let user = Arc::new(SearchUserImpl(authenticate_user));
+    let source = Arc::new(SearchIndexSourceImpl {
+      folder_manager: Arc::downgrade(folder_manager),
+      document_manager: Arc::downgrade(document_manager),
+      database_manager: Arc::downgrade(database_manager),
+    });
+    let search_manager = Arc::new(SearchManager::new(user, source));
+
+    // Keep the index up to date with the changes of the views, the documents and the databases.
+    subscribe_view_changed(
+      &search_manager,
+      folder_manager,
+      document_manager,
+      database_manager,
+    );
+    subscribe_document_changed(&search_manager, document_manager);
+    subscribe_database_changed(&search_manager, database_manager);
+    search_manager
+  }
+}
+
+struct SearchUserImpl(Weak<AuthenticateUser>);
+impl SearchUserImpl {
+  fn upgrade_user(&self) -> Result<Arc<AuthenticateUser>, FlowyError> {
+    self
+      .0
+      .upgrade()
+      .ok_or(FlowyError::internal().with_context("Unexpected error: UserSession is None"))
+  }
+}
+
+impl SearchUser for SearchUserImpl {
+  fn user_id(&self) -> Result<i64, FlowyError> {
+    self.upgrade_user()?.user_id()
+  }
+
+  fn workspace_id(&self) -> Result<String, FlowyError> {
+    self.upgrade_user()?.workspace_id()
+  }
+
+  fn sqlite_connection(&self, uid: i64) -> Result<DBConnection, FlowyError> {
+    self.upgrade_user()?.get_sqlite_connection(uid)
+  }
+}
+
+struct SearchIndexSourceImpl {
+  folder_manager: Weak<FolderManager>,
+  document_manager: Weak<DocumentManager>,
+  database_manager: Weak<DatabaseManager>,
+}
+
+impl SearchIndexSource for SearchIndexSourceImpl {
+  fn get_all_items(&self) -> FutureResult<Vec<SearchIndexItem>, FlowyError> {
+    let folder_manager = self.folder_manager.upgrade();
+    let document_manager = self.document_manager.upgrade();
+    let database_manager = self.database_manager.upgrade();
+    FutureResult::new(async move {
+      let (folder_manager, document_manager, database_manager) =
+        match (folder_manager, document_manager, database_manager) {
+          (Some(folder_manager), Some(document_manager), Some(database_manager)) => {
+            (folder_manager, document_manager, database_manager)
+          },
+          _ => return Err(FlowyError::internal().with_context("The managers are already dropped")),
+        };
+
+      let mut items = vec![];
+      // The rows of a database are indexed once, even if the database has many views.
+      let mut database_ids = HashSet::new();
+      for view in folder_manager.get_all_views().await? {
+        items.push(view_search_item(&view));
+        if view.layout != ViewLayout::Document {
+          let is_indexed = match database_manager
+            .get_database_id_with_view_id(&view.id)
+            .await
+          {
+            Ok(database_id) => !database_ids.insert(database_id),
+            Err(_) => true,
+          };
+          if is_indexed {
+            continue;
+          }
+        }
+        match view_content_search_items(&view, &document_manager, &database_manager).await {
+          Ok(content_items) => items.extend(content_items),
+          Err(err) => error!("Failed to index the content of view: {}, {}", view.id, err),
+        }
+      }
+      Ok(items)
+    })
+  }
+
+  fn get_trash_view_ids(&self) -> FutureResult<Vec<String>, FlowyError> {
+    let folder_manager = self.folder_manager.upgrade();
+    FutureResult::new(async move {
+      let folder_manager = folder_manager
+        .ok_or(FlowyError::internal().with_context("The folder manager is already dropped"))?;
+      Ok(
+        folder_manager
+          .get_all_trash()
+          .await
+          .into_iter()
+          .map(|trash| trash.id)
+          .collect(),
+      )
+    })
+  }
+}
+
+fn view_search_item(view: &View) -> SearchIndexItem {
+  SearchIndexItem {
+    item_type: SearchItemTypePB::View,
+    view_id: view.id.clone(),
+    object_id: view.id.clone(),
+    content: view.name.clone(),
+  }
+}
+
+fn document_search_items(document_id: &str, document_data: DocumentData) -> Vec<SearchIndexItem> {
+  DocumentDataParser::new(Arc::new(document_data), None)
+    .to_block_texts()
+    .into_iter()
+    .map(|(block_id, text)| SearchIndexItem {
+      item_type: SearchItemTypePB::Document,
+      view_id: document_id.to_string(),
+      object_id: block_id,
+      content: text,
+    })
+    .collect()
+}
+
+fn row_search_items(rows: Vec<RowSearchText>) -> Vec<SearchIndexItem> {
+  rows
+    .into_iter()
+    .map(|row| SearchIndexItem {
+      item_type: SearchItemTypePB::DatabaseRow,
+      view_id: row.view_id,
+      object_id: row.row_id,
+      content: row.text,
+    })
+    .collect()
+}
+
+/// Returns the blocks of the document or the rows of the database of the view.
+async fn view_content_search_items(
+  view: &View,
+  document_manager: &DocumentManager,
+  database_manager: &DatabaseManager,
+) -> FlowyResult<Vec<SearchIndexItem>> {
+  match view.layout {
+    ViewLayout::Document => {
+      let document_data = document_manager.get_document_data(&view.id).await?;
+      Ok(document_search_items(&view.id, document_data))
+    },
+    ViewLayout::Grid | ViewLayout::Board | ViewLayout::Calendar => {
+      let editor = database_manager.get_database_with_view_id(&view.id).await?;
+      Ok(row_search_items(editor.get_rows_search_text(&[])))
+    },
+  }
+}
+
+fn subscribe_view_changed(
+  search_manager: &Arc<SearchManager>,
+  folder_manager: &Arc<FolderManager>,
+  document_manager: &Arc<DocumentManager>,
+  database_manager: &Arc<DatabaseManager>,
+) {
+  let weak_search_manager = Arc::downgrade(search_manager);
+  let weak_document_manager = Arc::downgrade(document_manager);
+  let weak_database_manager = Arc::downgrade(database_manager);
+  let mut rx = folder_manager.subscribe_view_changed();
+  af_spawn(async move {
+    loop {
+      let changed = match recv_or_rebuild_index(&mut rx, &weak_search_manager).await {
+        Some(changed) => changed,
+        None => break,
+      };
+      let search_manager = match weak_search_manager.upgrade() {
+        None => break,
+        Some(search_manager) => search_manager,
+      };
+
+      let result = match changed {
+        ViewChange::DidCreateView { view } => {
+          // The created view may already have content, for example, if it was imported or
+          // duplicated.
+          let mut items = vec![view_search_item(&view)];
+          if let (Some(document_manager), Some(database_manager)) = (
+            weak_document_manager.upgrade(),
+            weak_database_manager.upgrade(),
+          ) {
+            match view_content_search_items(&view, &document_manager, &database_manager).await {
+              Ok(content_items) => items.extend(content_items),
+              Err(err) => error!("Failed to index the content of view: {}, {}", view.id, err),
+            }
+          }
+          search_manager.index_items(items)
+        },
+        ViewChange::DidUpdate { view } => search_manager.index_items(vec![view_search_item(&view)]),
+        ViewChange::DidDeleteView { views } => {
+          search_manager.remove_views(views.iter().map(|view| view.id.clone()).collect())
+        },
+      };
+      if let Err(err) = result {
+        error!("Failed to update the search index of the views: {}", err);
+      }
+    }
+  });
+}
+
+fn subscribe_document_changed(
+  search_manager: &Arc<SearchManager>,
+  document_manager: &Arc<DocumentManager>,
+) {
+  let weak_search_manager = Arc::downgrade(search_manager);
+  let weak_document_manager = Arc::downgrade(document_manager);
+  let mut rx = document_manager.subscribe_document_changed();
+  af_spawn(async move {
+    let mut is_closed = false;
+    while !is_closed {
+      let document_id = match recv_or_rebuild_index(&mut rx, &weak_search_manager).await {
+        Some(document_id) => document_id,
+        None => break,
+      };
+      let mut document_ids = HashSet::from([document_id]);
+      loop {
+        match tokio::time::timeout(
+          INDEX_DEBOUNCE,
+          recv_or_rebuild_index(&mut rx, &weak_search_manager),
+        )
+        .await
+        {
+          Ok(Some(document_id)) => {
+            document_ids.insert(document_id);
+          },
+          Ok(None) => {
+            is_closed = true;
+            break;
+          },
+          Err(_) => break,
+        }
+      }
+
+      let (search_manager, document_manager) = match (
+        weak_search_manager.upgrade(),
+        weak_document_manager.upgrade(),
+      ) {
+        (Some(search_manager), Some(document_manager)) => (search_manager, document_manager),
+        _ => break,
+      };
+      for document_id in document_ids {
+        if let Err(err) = index_document(&search_manager, &document_manager, &document_id).await {
+          error!(
+            "Failed to update the search index of document: {}, {}",
+            document_id, err
+          );
+        }
+      }
+    }
+  });
+}
+
+async fn index_document(
+  search_manager: &SearchManager,
+  document_manager: &DocumentManager,
+  document_id: &str,
+) -> FlowyResult<()> {
+  let document = document_manager.get_document(document_id).await?;
+  let document_data = document
+    .lock()
+    .get_document_data()
+    .map_err(internal_error)?;
+  search_manager.index_view_items(
+    document_id,
+    SearchItemTypePB::Document,
+    document_search_items(document_id, document_data),
+  )
+}
+
+/// The changes of the rows of a database that are waiting to be indexed.
+#[derive(Default)]
+struct PendingDatabaseChanges {
+  updated_row_ids: HashSet<String>,
+  deleted_row_ids: HashSet<String>,
+  /// True if a lookup field was updated, so the cells of all the rows might have changed.
+  is_all_rows_updated: bool,
+}
+
+impl PendingDatabaseChanges {
+  fn add(&mut self, changed: DatabaseDataChanged) {
+    match changed {
+      DatabaseDataChanged::DidCreateRow { row_id }
+      | DatabaseDataChanged::DidUpdateCell { row_id, .. } => {
+        self.updated_row_ids.insert(row_id);
+      },
+      DatabaseDataChanged::DidDeleteRow { row_id } => {
+        self.updated_row_ids.remove(&row_id);
+        self.deleted_row_ids.insert(row_id);
+      },
+      DatabaseDataChanged::DidUpdateLookupField { .. } => self.is_all_rows_updated = true,
+    }
+  }
+}
+
+fn subscribe_database_changed(
+  search_manager: &Arc<SearchManager>,
+  database_manager: &Arc<DatabaseManager>,
+) {
+  let weak_search_manager = Arc::downgrade(search_manager);
+  let weak_database_manager = Arc::downgrade(database_manager);
+  let mut rx = database_manager.subscribe_data_changed();
+  af_spawn(async move {
+    let mut is_closed = false;
+    while !is_closed {
+      let (database_id, changed) = match recv_or_rebuild_index(&mut rx, &weak_search_manager).await
+      {
+        Some(changed) => changed,
+        None => break,
+      };
+      let mut changes_by_database = HashMap::<String, PendingDatabaseChanges>::new();
+      changes_by_database
+        .entry(database_id)
+        .or_default()
+        .add(changed);
+      loop {
+        match tokio::time::timeout(
+          INDEX_DEBOUNCE,
+          recv_or_rebuild_index(&mut rx, &weak_search_manager),
+        )
+        .await
+        {
+          Ok(Some((database_id, changed))) => {
+            changes_by_database
+              .entry(database_id)
+              .or_default()
+              .add(changed);
+          },
+          Ok(None) => {
+            is_closed = true;
+            break;
+          },
+          Err(_) => break,
+        }
+      }
+
+      let (search_manager, database_manager) = match (
+        weak_search_manager.upgrade(),
+        weak_database_manager.upgrade(),
+      ) {
+        (Some(search_manager), Some(database_manager)) => (search_manager, database_manager),
+        _ => break,
+      };
+      for (database_id, changes) in changes_by_database {
+        if let Err(err) =
+          index_database_changes(&search_manager, &database_manager, &database_id, changes).await
+        {
+          error!(
+            "Failed to update the search index of database: {}, {}",
+            database_id, err
+          );
+        }
+      }
+    }
+  });
+}
+
+async fn index_database_changes(
+  search_manager: &SearchManager,
+  database_manager: &DatabaseManager,
+  database_id: &str,
+  changes: PendingDatabaseChanges,
+) -> FlowyResult<()> {
+  if !changes.deleted_row_ids.is_empty() {
+    search_manager.remove_items(
+      SearchItemTypePB::DatabaseRow,
+      changes.deleted_row_ids.into_iter().collect(),
+    )?;
+  }
+
+  // The rows are read when they are indexed, so a row that was deleted in the meantime is skipped.
+  let row_ids = if changes.is_all_rows_updated {
+    vec![]
+  } else if changes.updated_row_ids.is_empty() {
+    return Ok(());
+  } else {
+    changes
+      .updated_row_ids
+      .into_iter()
+      .map(RowId::from)
+      .collect::<Vec<RowId>>()
+  };
+  let editor = database_manager.get_database(database_id).await?;
+  let rows = editor.get_rows_search_text(&row_ids);
+  search_manager.index_items(row_search_items(rows))
+}
+
+/// Receives the next change. Returns None if the sender was dropped.
+///
+/// The changes that were dropped because the receiver lagged behind can't be recovered, so the
+/// index of the whole workspace is rebuilt.
+async fn recv_or_rebuild_index<T: Clone>(
+  rx: &mut Receiver<T>,
+  weak_search_manager: &Weak<SearchManager>,
+) -> Option<T> {
+  loop {
+    match rx.recv().await {
+      Ok(value) => return Some(value),
+      Err(RecvError::Lagged(skipped)) => {
+        warn!(
+          "The search index missed {} changes, rebuild the index",
+          skipped
+        );
+        if let Some(search_manager) = weak_search_manager.upgrade() {
+          search_manager.schedule_rebuild_index();
+        }
+      },
+      Err(RecvError::Closed) => return None,
+    }
+  }
+}
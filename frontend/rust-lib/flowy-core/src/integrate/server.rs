@@ -119,7 +119,7 @@ impl ServerProvider {
         let local_db = Arc::new(LocalServerDBImpl {
           storage_path: self.config.storage_path.clone(),
         });
-        let server = Arc::new(LocalServer::new(local_db));
+        let server = Arc::new(LocalServer::new(local_db, self.config.maximum_file_size));
         Ok::<Arc<dyn AppFlowyServer>, FlowyError>(server)
       },
       Server::AppFlowyCloud => {
@@ -129,6 +129,7 @@ impl ServerProvider {
           *self.user_enable_sync.read(),
           self.config.device_id.clone(),
           &self.config.app_version,
+          self.config.maximum_file_size,
         ));
 
         Ok::<Arc<dyn AppFlowyServer>, FlowyError>(server)
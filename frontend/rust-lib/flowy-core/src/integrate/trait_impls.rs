@@ -1,4 +1,4 @@
-use flowy_storage::{ObjectIdentity, ObjectStorageService};
+use flowy_storage::{FileStoragePlan, ObjectIdentity, ObjectStorageService, StorageObject};
 use std::sync::Arc;
 
 use anyhow::Error;
@@ -64,6 +64,47 @@ impl ObjectStorageService for ServerProvider {
   }
 }
 
+/// Uses the plan of the current server. The uploads are not limited on the client side if the
+/// server doesn't provide a plan.
+impl FileStoragePlan for ServerProvider {
+  fn storage_size(&self) -> FutureResult<u64, FlowyError> {
+    let plan = self
+      .get_server()
+      .ok()
+      .and_then(|server| server.file_storage_plan());
+    FutureResult::new(async move {
+      match plan {
+        None => Ok(u64::MAX),
+        Some(plan) => plan.storage_size().await,
+      }
+    })
+  }
+
+  fn maximum_file_size(&self) -> FutureResult<u64, FlowyError> {
+    let plan = self
+      .get_server()
+      .ok()
+      .and_then(|server| server.file_storage_plan());
+    FutureResult::new(async move {
+      match plan {
+        None => Ok(u64::MAX),
+        Some(plan) => plan.maximum_file_size().await,
+      }
+    })
+  }
+
+  fn check_upload_object(&self, object: &StorageObject) -> FutureResult<(), FlowyError> {
+    match self
+      .get_server()
+      .ok()
+      .and_then(|server| server.file_storage_plan())
+    {
+      None => FutureResult::new(async { Ok(()) }),
+      Some(plan) => plan.check_upload_object(object),
+    }
+  }
+}
+
 impl UserCloudServiceProvider for ServerProvider {
   fn set_token(&self, token: &str) -> Result<(), FlowyError> {
     let server = self.get_server()?;
@@ -9,6 +9,7 @@ use flowy_database2::DatabaseManager;
 use flowy_document::manager::DocumentManager;
 use flowy_error::{FlowyError, FlowyResult};
 use flowy_folder::manager::{FolderInitDataSource, FolderManager};
+use flowy_search::manager::SearchManager;
 use flowy_user::event_map::UserStatusCallback;
 use flowy_user_pub::cloud::{UserCloudConfig, UserCloudServiceProvider};
 use flowy_user_pub::entities::{Authenticator, UserProfile, UserWorkspace};
@@ -22,6 +23,7 @@ pub(crate) struct UserStatusCallbackImpl {
   pub(crate) folder_manager: Arc<FolderManager>,
   pub(crate) database_manager: Arc<DatabaseManager>,
   pub(crate) document_manager: Arc<DocumentManager>,
+  pub(crate) search_manager: Arc<SearchManager>,
   pub(crate) server_provider: Arc<ServerProvider>,
   #[allow(dead_code)]
   pub(crate) config: AppFlowyCoreConfig,
@@ -42,6 +44,7 @@ impl UserStatusCallback for UserStatusCallbackImpl {
     let folder_manager = self.folder_manager.clone();
     let database_manager = self.database_manager.clone();
     let document_manager = self.document_manager.clone();
+    let search_manager = self.search_manager.clone();
 
     self
       .server_provider
@@ -77,6 +80,9 @@ impl UserStatusCallback for UserStatusCallbackImpl {
         )
         .await?;
       document_manager
+        .initialize(user_id, user_workspace.id.clone())
+        .await?;
+      search_manager
         .initialize(user_id, user_workspace.id)
         .await?;
       Ok(())
@@ -95,6 +101,7 @@ impl UserStatusCallback for UserStatusCallbackImpl {
     let folder_manager = self.folder_manager.clone();
     let database_manager = self.database_manager.clone();
     let document_manager = self.document_manager.clone();
+    let search_manager = self.search_manager.clone();
 
     to_fut(async move {
       event!(
@@ -115,6 +122,9 @@ impl UserStatusCallback for UserStatusCallbackImpl {
         )
         .await?;
       document_manager
+        .initialize(user_id, user_workspace.id.clone())
+        .await?;
+      search_manager
         .initialize(user_id, user_workspace.id)
         .await?;
       Ok(())
@@ -134,6 +144,7 @@ impl UserStatusCallback for UserStatusCallbackImpl {
     let database_manager = self.database_manager.clone();
     let user_workspace = user_workspace.clone();
     let document_manager = self.document_manager.clone();
+    let search_manager = self.search_manager.clone();
     self
       .server_provider
       .set_user_authenticator(&user_profile.authenticator);
@@ -208,9 +219,14 @@ impl UserStatusCallback for UserStatusCallbackImpl {
         .context("DatabaseManager error")?;
 
       document_manager
-        .initialize_with_new_user(user_profile.uid, user_workspace.id)
+        .initialize_with_new_user(user_profile.uid, user_workspace.id.clone())
         .await
         .context("DocumentManager error")?;
+
+      search_manager
+        .initialize(user_profile.uid, user_workspace.id)
+        .await
+        .context("SearchManager error")?;
       Ok(())
     })
   }
@@ -229,6 +245,7 @@ impl UserStatusCallback for UserStatusCallbackImpl {
     let folder_manager = self.folder_manager.clone();
     let database_manager = self.database_manager.clone();
     let document_manager = self.document_manager.clone();
+    let search_manager = self.search_manager.clone();
 
     to_fut(async move {
       collab_builder.initialize(user_workspace.id.clone());
@@ -244,6 +261,9 @@ impl UserStatusCallback for UserStatusCallbackImpl {
         )
         .await?;
       document_manager
+        .initialize(user_id, user_workspace.id.clone())
+        .await?;
+      search_manager
         .initialize(user_id, user_workspace.id)
         .await?;
       Ok(())
@@ -1,6 +1,6 @@
 #![allow(unused_doc_comments)]
 
-use flowy_storage::ObjectStorageService;
+use flowy_storage::{FileStoragePlan, ObjectStorageService};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::RwLock;
@@ -10,10 +10,12 @@ use collab_integrate::collab_builder::{AppFlowyCollabBuilder, CollabPluginProvid
 use flowy_database2::DatabaseManager;
 use flowy_document::manager::DocumentManager;
 use flowy_folder::manager::FolderManager;
+use flowy_search::manager::SearchManager;
 use flowy_sqlite::kv::StorePreferences;
 use flowy_user::services::authenticate_user::AuthenticateUser;
 use flowy_user::services::entities::UserConfig;
 use flowy_user::user_manager::UserManager;
+use flowy_user_pub::cloud::UserCloudServiceProvider;
 
 use lib_dispatch::prelude::*;
 use lib_dispatch::runtime::AFPluginRuntime;
@@ -44,6 +46,7 @@ pub struct AppFlowyCore {
   pub document_manager: Arc<DocumentManager>,
   pub folder_manager: Arc<FolderManager>,
   pub database_manager: Arc<DatabaseManager>,
+  pub search_manager: Arc<SearchManager>,
   pub event_dispatcher: Arc<AFPluginDispatcher>,
   pub server_provider: Arc<ServerProvider>,
   pub task_dispatcher: Arc<RwLock<TaskDispatcher>>,
@@ -98,6 +101,7 @@ impl AppFlowyCore {
       server_provider,
       database_manager,
       document_manager,
+      search_manager,
       collab_builder,
     ) = async {
       /// The shared collab builder is used to build the [Collab] instance. The plugins will be loaded
@@ -124,9 +128,12 @@ impl AppFlowyCore {
 
       let database_manager = DatabaseDepsResolver::resolve(
         Arc::downgrade(&authenticate_user),
+        Arc::downgrade(&(server_provider.clone() as Arc<dyn UserCloudServiceProvider>)),
         task_dispatcher.clone(),
         collab_builder.clone(),
         server_provider.clone(),
+        Arc::downgrade(&(server_provider.clone() as Arc<dyn ObjectStorageService>)),
+        Arc::downgrade(&(server_provider.clone() as Arc<dyn FileStoragePlan>)),
       )
       .await;
 
@@ -147,6 +154,13 @@ impl AppFlowyCore {
       )
       .await;
 
+      let search_manager = SearchDepsResolver::resolve(
+        Arc::downgrade(&authenticate_user),
+        &folder_manager,
+        &document_manager,
+        &database_manager,
+      );
+
       let user_manager = UserDepsResolver::resolve(
         authenticate_user,
         collab_builder.clone(),
@@ -163,6 +177,7 @@ impl AppFlowyCore {
         server_provider,
         database_manager,
         document_manager,
+        search_manager,
         collab_builder,
       )
     }
@@ -173,6 +188,7 @@ impl AppFlowyCore {
       folder_manager: folder_manager.clone(),
       database_manager: database_manager.clone(),
       document_manager: document_manager.clone(),
+      search_manager: search_manager.clone(),
       server_provider: server_provider.clone(),
       config: config.clone(),
     };
@@ -198,6 +214,7 @@ impl AppFlowyCore {
         Arc::downgrade(&database_manager),
         Arc::downgrade(&user_manager),
         Arc::downgrade(&document_manager),
+        Arc::downgrade(&search_manager),
       ),
     ));
 
@@ -207,6 +224,7 @@ impl AppFlowyCore {
       document_manager,
       folder_manager,
       database_manager,
+      search_manager,
       event_dispatcher,
       server_provider,
       task_dispatcher,
@@ -3,6 +3,7 @@ use std::sync::Weak;
 use flowy_database2::DatabaseManager;
 use flowy_document::manager::DocumentManager as DocumentManager2;
 use flowy_folder::manager::FolderManager;
+use flowy_search::manager::SearchManager;
 use flowy_user::user_manager::UserManager;
 use lib_dispatch::prelude::AFPlugin;
 
@@ -11,6 +12,7 @@ pub fn make_plugins(
   database_manager: Weak<DatabaseManager>,
   user_session: Weak<UserManager>,
   document_manager2: Weak<DocumentManager2>,
+  search_manager: Weak<SearchManager>,
 ) -> Vec<AFPlugin> {
   let store_preferences = user_session
     .upgrade()
@@ -22,6 +24,7 @@ pub fn make_plugins(
   let document_plugin2 = flowy_document::event_map::init(document_manager2);
   let config_plugin = flowy_config::event_map::init(store_preferences);
   let date_plugin = flowy_date::event_map::init();
+  let search_plugin = flowy_search::event_map::init(search_manager);
   vec![
     user_plugin,
     folder_plugin,
@@ -29,5 +32,6 @@ pub fn make_plugins(
     document_plugin2,
     config_plugin,
     date_plugin,
+    search_plugin,
   ]
 }
@@ -6,7 +6,7 @@ use std::{
 use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
-use crate::{impl_into_calculation_type, services::calculations::Calculation};
+use crate::{entities::FieldType, impl_into_calculation_type, services::calculations::Calculation};
 
 #[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
 pub struct CalculationPB {
@@ -60,6 +60,14 @@ pub enum CalculationType {
   Median = 2, // Number
   Min = 3,    // Number
   Sum = 4,    // Number
+  Count = 5,
+  CountEmpty = 6,
+  CountNonEmpty = 7,
+  CountUnique = 8,
+  PercentChecked = 9, // Checkbox, Checklist
+  Earliest = 10,      // DateTime, LastEditedTime, CreatedTime
+  Latest = 11,        // DateTime, LastEditedTime, CreatedTime
+  DateRange = 12,     // DateTime, LastEditedTime, CreatedTime
 }
 
 impl Display for CalculationType {
@@ -85,6 +93,26 @@ impl CalculationType {
   pub fn value(&self) -> i64 {
     (*self).into()
   }
+
+  /// Returns true if the calculation can be applied to the cells of the given [FieldType]. The
+  /// count calculations are supported by every field type.
+  pub fn is_supported_by(&self, field_type: &FieldType) -> bool {
+    match self {
+      CalculationType::Average
+      | CalculationType::Max
+      | CalculationType::Median
+      | CalculationType::Min
+      | CalculationType::Sum => field_type.is_number(),
+      CalculationType::Count
+      | CalculationType::CountEmpty
+      | CalculationType::CountNonEmpty
+      | CalculationType::CountUnique => true,
+      CalculationType::PercentChecked => field_type.is_checkbox() || field_type.is_checklist(),
+      CalculationType::Earliest | CalculationType::Latest | CalculationType::DateRange => {
+        field_type.is_date() || field_type.is_last_edited_time() || field_type.is_created_time()
+      },
+    }
+  }
 }
 
 impl_into_calculation_type!(i64);
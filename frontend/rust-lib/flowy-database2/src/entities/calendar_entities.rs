@@ -113,6 +113,10 @@ pub struct CalendarEventPB {
 
   #[pb(index = 5)]
   pub is_scheduled: bool,
+
+  /// The end of the event if it spans multiple days, otherwise it's equal to the timestamp.
+  #[pb(index = 6)]
+  pub end_timestamp: i64,
 }
 
 #[derive(Debug, Clone, Default, ProtoBuf)]
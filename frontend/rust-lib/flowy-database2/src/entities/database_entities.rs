@@ -166,6 +166,10 @@ pub struct MoveGroupRowPayloadPB {
 
   #[pb(index = 5)]
   pub from_group_id: String,
+
+  /// The swimlane that the row is moved to. If it's None, the row stays in its swimlane.
+  #[pb(index = 6, one_of)]
+  pub to_swimlane_id: Option<String>,
 }
 
 pub struct MoveGroupRowParams {
@@ -174,6 +178,7 @@ pub struct MoveGroupRowParams {
   pub from_group_id: String,
   pub to_group_id: String,
   pub to_row_id: Option<RowId>,
+  pub to_swimlane_id: Option<String>,
 }
 
 impl TryInto<MoveGroupRowParams> for MoveGroupRowPayloadPB {
@@ -192,6 +197,7 @@ impl TryInto<MoveGroupRowParams> for MoveGroupRowPayloadPB {
       from_group_id: from_group_id.0,
       from_row_id: RowId::from(self.from_row_id),
       to_row_id: self.to_row_id.map(RowId::from),
+      to_swimlane_id: self.to_swimlane_id,
     })
   }
 }
@@ -14,7 +14,9 @@ use flowy_error::ErrorCode;
 use crate::entities::parser::NotEmptyStr;
 use crate::entities::position_entities::OrderObjectPositionPB;
 use crate::impl_into_field_type;
-use crate::services::field::{default_type_option_data_from_type, type_option_to_pb};
+use crate::services::field::{
+  default_type_option_data_from_type, type_option_to_pb, FormulaTypeOption,
+};
 
 /// [FieldPB] defines a Field's attributes. Such as the name, field_type, and width. etc.
 #[derive(Debug, Clone, Default, ProtoBuf)]
@@ -57,6 +59,21 @@ impl FieldPB {
       type_option_data: type_option_to_pb(type_option, &field_type).to_vec(),
     }
   }
+
+  /// Same as [FieldPB::new], except that the formula of a formula field refers to the other
+  /// fields by their names, which are looked up in `fields`.
+  pub fn new_with_fields(field: Field, fields: &[Field]) -> Self {
+    let field_type = FieldType::from(field.field_type);
+    let formula_type_option = field
+      .get_type_option::<FormulaTypeOption>(field_type)
+      .filter(|_| field_type.is_formula());
+    let mut field_pb = Self::new(field);
+    if let Some(type_option) = formula_type_option {
+      let type_option = type_option.display_field_references(fields);
+      field_pb.type_option_data = type_option_to_pb(type_option.into(), &field_type).to_vec();
+    }
+    field_pb
+  }
 }
 
 /// [FieldIdPB] id of the [Field]
@@ -473,6 +490,12 @@ pub enum FieldType {
   Checklist = 7,
   LastEditedTime = 8,
   CreatedTime = 9,
+  Relation = 10,
+  Lookup = 11,
+  Formula = 12,
+  Person = 13,
+  Files = 14,
+  AutoNumber = 15,
 }
 
 impl Display for FieldType {
@@ -511,6 +534,12 @@ impl FieldType {
       FieldType::Checklist => "Checklist",
       FieldType::LastEditedTime => "Last edited time",
       FieldType::CreatedTime => "Created time",
+      FieldType::Relation => "Relation",
+      FieldType::Lookup => "Lookup",
+      FieldType::Formula => "Formula",
+      FieldType::Person => "Person",
+      FieldType::Files => "Files",
+      FieldType::AutoNumber => "ID",
     };
     s.to_string()
   }
@@ -559,8 +588,37 @@ impl FieldType {
     matches!(self, FieldType::Checklist)
   }
 
+  pub fn is_relation(&self) -> bool {
+    matches!(self, FieldType::Relation)
+  }
+
+  pub fn is_lookup(&self) -> bool {
+    matches!(self, FieldType::Lookup)
+  }
+
+  pub fn is_formula(&self) -> bool {
+    matches!(self, FieldType::Formula)
+  }
+
+  pub fn is_person(&self) -> bool {
+    matches!(self, FieldType::Person)
+  }
+
+  pub fn is_files(&self) -> bool {
+    matches!(self, FieldType::Files)
+  }
+
+  pub fn is_auto_number(&self) -> bool {
+    matches!(self, FieldType::AutoNumber)
+  }
+
   pub fn can_be_group(&self) -> bool {
-    self.is_select_option() || self.is_checkbox() || self.is_url()
+    self.is_select_option() || self.is_checkbox() || self.is_url() || self.is_person()
+  }
+
+  /// Returns true if the quick search filter looks into the cells of this field type.
+  pub fn is_searchable(&self) -> bool {
+    self.is_text() || self.is_url() || self.is_select_option()
   }
 
   pub fn is_auto_update(&self) -> bool {
@@ -0,0 +1,147 @@
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use flowy_error::ErrorCode;
+
+use crate::entities::parser::NotEmptyStr;
+use crate::entities::FieldIdParams;
+use crate::services::field::FieldValidation;
+
+#[derive(Debug, Clone, Default, PartialEq, ProtoBuf)]
+pub struct FieldValidationPB {
+  #[pb(index = 1)]
+  pub required: bool,
+
+  #[pb(index = 2, one_of)]
+  pub min: Option<f64>,
+
+  #[pb(index = 3, one_of)]
+  pub max: Option<f64>,
+
+  #[pb(index = 4, one_of)]
+  pub max_length: Option<i32>,
+
+  #[pb(index = 5, one_of)]
+  pub pattern: Option<String>,
+
+  #[pb(index = 6)]
+  pub https_only: bool,
+
+  #[pb(index = 7)]
+  pub unique: bool,
+}
+
+impl From<FieldValidation> for FieldValidationPB {
+  fn from(data: FieldValidation) -> Self {
+    Self {
+      required: data.required,
+      min: data.min,
+      max: data.max,
+      max_length: data.max_length.map(|max_length| max_length as i32),
+      pattern: data.pattern,
+      https_only: data.https_only,
+      unique: data.unique,
+    }
+  }
+}
+
+impl From<FieldValidationPB> for FieldValidation {
+  fn from(data: FieldValidationPB) -> Self {
+    Self {
+      required: data.required,
+      min: data.min,
+      max: data.max,
+      max_length: data.max_length.map(|max_length| max_length.max(0) as usize),
+      pattern: data.pattern.filter(|pattern| !pattern.is_empty()),
+      https_only: data.https_only,
+      unique: data.unique,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct GetFieldValidationPayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub field_id: String,
+}
+
+impl TryInto<FieldIdParams> for GetFieldValidationPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<FieldIdParams, Self::Error> {
+    let view_id = NotEmptyStr::parse(self.view_id).map_err(|_| ErrorCode::DatabaseIdIsEmpty)?;
+    let field_id = NotEmptyStr::parse(self.field_id).map_err(|_| ErrorCode::FieldIdIsEmpty)?;
+    Ok(FieldIdParams {
+      view_id: view_id.0,
+      field_id: field_id.0,
+    })
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct UpdateFieldValidationPayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub field_id: String,
+
+  #[pb(index = 3)]
+  pub validation: FieldValidationPB,
+}
+
+pub struct UpdateFieldValidationParams {
+  pub view_id: String,
+  pub field_id: String,
+  pub validation: FieldValidation,
+}
+
+impl TryInto<UpdateFieldValidationParams> for UpdateFieldValidationPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<UpdateFieldValidationParams, Self::Error> {
+    let view_id = NotEmptyStr::parse(self.view_id).map_err(|_| ErrorCode::DatabaseIdIsEmpty)?;
+    let field_id = NotEmptyStr::parse(self.field_id).map_err(|_| ErrorCode::FieldIdIsEmpty)?;
+    Ok(UpdateFieldValidationParams {
+      view_id: view_id.0,
+      field_id: field_id.0,
+      validation: self.validation.into(),
+    })
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ProtoBuf_Enum)]
+#[repr(u8)]
+pub enum FieldValidationRulePB {
+  #[default]
+  Required = 0,
+  Min = 1,
+  Max = 2,
+  MaxLength = 3,
+  Pattern = 4,
+  HttpsOnly = 5,
+  Unique = 6,
+}
+
+/// A rule of a field that is broken by the cell of a row.
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct FieldValidationErrorPB {
+  #[pb(index = 1)]
+  pub row_id: String,
+
+  #[pb(index = 2)]
+  pub field_id: String,
+
+  #[pb(index = 3)]
+  pub rule: FieldValidationRulePB,
+
+  #[pb(index = 4)]
+  pub message: String,
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct RepeatedFieldValidationErrorPB {
+  #[pb(index = 1)]
+  pub items: Vec<FieldValidationErrorPB>,
+}
@@ -20,6 +20,24 @@ pub struct DateFilterPB {
 
   #[pb(index = 4, one_of)]
   pub timestamp: Option<i64>,
+
+  /// The N of the "in the last N days" and "in the next N days" conditions.
+  #[pb(index = 5, one_of)]
+  pub number_of_days: Option<i64>,
+}
+
+/// The largest N of the "in the last N days" and "in the next N days" conditions, about a hundred
+/// years.
+pub const MAX_NUMBER_OF_DAYS: i64 = 36_500;
+
+impl DateFilterPB {
+  /// Returns false if the N of the "in the last N days" and "in the next N days" conditions is
+  /// negative or larger than [MAX_NUMBER_OF_DAYS].
+  pub fn is_number_of_days_valid(&self) -> bool {
+    self
+      .number_of_days
+      .map_or(true, |days| (0..=MAX_NUMBER_OF_DAYS).contains(&days))
+  }
 }
 
 #[derive(Deserialize, Serialize, Default, Clone, Debug)]
@@ -27,6 +45,7 @@ pub struct DateFilterContentPB {
   pub start: Option<i64>,
   pub end: Option<i64>,
   pub timestamp: Option<i64>,
+  pub number_of_days: Option<i64>,
 }
 
 impl ToString for DateFilterContentPB {
@@ -56,6 +75,38 @@ pub enum DateFilterConditionPB {
   DateWithIn = 5,
   DateIsEmpty = 6,
   DateIsNotEmpty = 7,
+  // The relative conditions are evaluated against the current date, so a saved filter like
+  // "this week" keeps matching the current week.
+  DateIsToday = 8,
+  DateInLastDays = 9,
+  DateInNextDays = 10,
+  DateIsThisWeek = 11,
+  DateIsLastWeek = 12,
+  DateIsNextWeek = 13,
+  DateIsThisMonth = 14,
+  DateIsLastMonth = 15,
+  DateIsNextMonth = 16,
+  // The range conditions compare the whole range of a date range cell. The cells that aren't
+  // ranges are treated as ranges of a single day.
+  DateOverlaps = 17,
+  DateContains = 18,
+}
+
+impl DateFilterConditionPB {
+  pub fn is_relative(&self) -> bool {
+    matches!(
+      self,
+      DateFilterConditionPB::DateIsToday
+        | DateFilterConditionPB::DateInLastDays
+        | DateFilterConditionPB::DateInNextDays
+        | DateFilterConditionPB::DateIsThisWeek
+        | DateFilterConditionPB::DateIsLastWeek
+        | DateFilterConditionPB::DateIsNextWeek
+        | DateFilterConditionPB::DateIsThisMonth
+        | DateFilterConditionPB::DateIsLastMonth
+        | DateFilterConditionPB::DateIsNextMonth
+    )
+  }
 }
 
 impl std::convert::From<DateFilterConditionPB> for u32 {
@@ -76,6 +127,18 @@ impl std::convert::TryFrom<u8> for DateFilterConditionPB {
       4 => Ok(DateFilterConditionPB::DateOnOrAfter),
       5 => Ok(DateFilterConditionPB::DateWithIn),
       6 => Ok(DateFilterConditionPB::DateIsEmpty),
+      7 => Ok(DateFilterConditionPB::DateIsNotEmpty),
+      8 => Ok(DateFilterConditionPB::DateIsToday),
+      9 => Ok(DateFilterConditionPB::DateInLastDays),
+      10 => Ok(DateFilterConditionPB::DateInNextDays),
+      11 => Ok(DateFilterConditionPB::DateIsThisWeek),
+      12 => Ok(DateFilterConditionPB::DateIsLastWeek),
+      13 => Ok(DateFilterConditionPB::DateIsNextWeek),
+      14 => Ok(DateFilterConditionPB::DateIsThisMonth),
+      15 => Ok(DateFilterConditionPB::DateIsLastMonth),
+      16 => Ok(DateFilterConditionPB::DateIsNextMonth),
+      17 => Ok(DateFilterConditionPB::DateOverlaps),
+      18 => Ok(DateFilterConditionPB::DateContains),
       _ => Err(ErrorCode::InvalidParams),
     }
   }
@@ -96,6 +159,7 @@ impl FromFilterString for DateFilterPB {
       date_filter.start = content.start;
       date_filter.end = content.end;
       date_filter.timestamp = content.timestamp;
+      date_filter.number_of_days = content.number_of_days;
     };
 
     date_filter
@@ -114,6 +178,7 @@ impl std::convert::From<&Filter> for DateFilterPB {
       date_filter.start = content.start;
       date_filter.end = content.end;
       date_filter.timestamp = content.timestamp;
+      date_filter.number_of_days = content.number_of_days;
     };
 
     date_filter
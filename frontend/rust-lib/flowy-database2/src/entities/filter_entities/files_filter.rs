@@ -0,0 +1,62 @@
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use flowy_error::ErrorCode;
+
+use crate::services::filter::{Filter, FromFilterString};
+
+#[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
+pub struct FilesFilterPB {
+  #[pb(index = 1)]
+  pub condition: FilesFilterConditionPB,
+
+  /// Only used by [FilesFilterConditionPB::FileNameContains].
+  #[pb(index = 2)]
+  pub content: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ProtoBuf_Enum)]
+#[repr(u8)]
+#[derive(Default)]
+pub enum FilesFilterConditionPB {
+  #[default]
+  FilesIsEmpty = 0,
+  FilesIsNotEmpty = 1,
+  FileNameContains = 2,
+}
+
+impl std::convert::From<FilesFilterConditionPB> for u32 {
+  fn from(value: FilesFilterConditionPB) -> Self {
+    value as u32
+  }
+}
+
+impl std::convert::TryFrom<u8> for FilesFilterConditionPB {
+  type Error = ErrorCode;
+
+  fn try_from(value: u8) -> Result<Self, Self::Error> {
+    match value {
+      0 => Ok(FilesFilterConditionPB::FilesIsEmpty),
+      1 => Ok(FilesFilterConditionPB::FilesIsNotEmpty),
+      2 => Ok(FilesFilterConditionPB::FileNameContains),
+      _ => Err(ErrorCode::InvalidParams),
+    }
+  }
+}
+
+impl FromFilterString for FilesFilterPB {
+  fn from_filter(filter: &Filter) -> Self
+  where
+    Self: Sized,
+  {
+    FilesFilterPB::from(filter)
+  }
+}
+
+impl std::convert::From<&Filter> for FilesFilterPB {
+  fn from(filter: &Filter) -> Self {
+    FilesFilterPB {
+      condition: FilesFilterConditionPB::try_from(filter.condition as u8)
+        .unwrap_or(FilesFilterConditionPB::FilesIsEmpty),
+      content: filter.content.clone(),
+    }
+  }
+}
@@ -0,0 +1,131 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use flowy_derive::ProtoBuf;
+
+use crate::entities::{
+  CheckboxFilterPB, DateFilterContentPB, DateFilterPB, FieldType, FormulaResultTypePB,
+  NumberFilterPB, TextFilterPB,
+};
+use crate::services::filter::{Filter, FromFilterString};
+
+/// The filter of the formula field. The result of a formula is typed, so the filter is one of
+/// the filters of the corresponding field type, selected by the `result_type`.
+#[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
+pub struct FormulaFilterPB {
+  #[pb(index = 1)]
+  pub result_type: FormulaResultTypePB,
+
+  #[pb(index = 2, one_of)]
+  pub text_filter: Option<TextFilterPB>,
+
+  #[pb(index = 3, one_of)]
+  pub number_filter: Option<NumberFilterPB>,
+
+  #[pb(index = 4, one_of)]
+  pub date_filter: Option<DateFilterPB>,
+
+  #[pb(index = 5, one_of)]
+  pub checkbox_filter: Option<CheckboxFilterPB>,
+}
+
+/// The content of the formula filter that is saved in the [Filter]. The `content` is the
+/// content of the underlying filter.
+#[derive(Deserialize, Serialize, Default, Clone, Debug)]
+pub struct FormulaFilterContentPB {
+  pub result_type: i64,
+  pub content: String,
+}
+
+impl ToString for FormulaFilterContentPB {
+  fn to_string(&self) -> String {
+    serde_json::to_string(self).unwrap()
+  }
+}
+
+impl FromStr for FormulaFilterContentPB {
+  type Err = serde_json::Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    serde_json::from_str(s)
+  }
+}
+
+impl FormulaFilterPB {
+  /// Returns the condition and the content that will be saved in the [Filter].
+  pub fn to_condition_and_content(&self) -> (u8, String) {
+    let (condition, content) = match self.result_type {
+      FormulaResultTypePB::Text => self
+        .text_filter
+        .as_ref()
+        .map(|filter| (filter.condition.clone() as u8, filter.content.clone())),
+      FormulaResultTypePB::Number => self
+        .number_filter
+        .as_ref()
+        .map(|filter| (filter.condition.clone() as u8, filter.content.clone())),
+      FormulaResultTypePB::Date => self.date_filter.as_ref().map(|filter| {
+        let content = DateFilterContentPB {
+          start: filter.start,
+          end: filter.end,
+          timestamp: filter.timestamp,
+          number_of_days: filter.number_of_days,
+        };
+        (filter.condition.clone() as u8, content.to_string())
+      }),
+      FormulaResultTypePB::Boolean => self
+        .checkbox_filter
+        .as_ref()
+        .map(|filter| (filter.condition.clone() as u8, "".to_owned())),
+    }
+    .unwrap_or_default();
+
+    let content = FormulaFilterContentPB {
+      result_type: self.result_type.clone() as i64,
+      content,
+    };
+    (condition, content.to_string())
+  }
+}
+
+impl FromFilterString for FormulaFilterPB {
+  fn from_filter(filter: &Filter) -> Self
+  where
+    Self: Sized,
+  {
+    FormulaFilterPB::from(filter)
+  }
+}
+
+impl std::convert::From<&Filter> for FormulaFilterPB {
+  fn from(filter: &Filter) -> Self {
+    let content = FormulaFilterContentPB::from_str(&filter.content).unwrap_or_default();
+    let result_type = FormulaResultTypePB::from(content.result_type);
+    let inner_filter = |field_type: FieldType| Filter {
+      field_type,
+      content: content.content.clone(),
+      ..filter.clone()
+    };
+
+    let mut formula_filter = FormulaFilterPB {
+      result_type: result_type.clone(),
+      ..Default::default()
+    };
+    match result_type {
+      FormulaResultTypePB::Text => {
+        formula_filter.text_filter = Some(TextFilterPB::from(&inner_filter(FieldType::RichText)));
+      },
+      FormulaResultTypePB::Number => {
+        formula_filter.number_filter = Some(NumberFilterPB::from(&inner_filter(FieldType::Number)));
+      },
+      FormulaResultTypePB::Date => {
+        formula_filter.date_filter = Some(DateFilterPB::from(&inner_filter(FieldType::DateTime)));
+      },
+      FormulaResultTypePB::Boolean => {
+        formula_filter.checkbox_filter =
+          Some(CheckboxFilterPB::from(&inner_filter(FieldType::Checkbox)));
+      },
+    }
+    formula_filter
+  }
+}
@@ -1,8 +1,12 @@
 mod checkbox_filter;
 mod checklist_filter;
 mod date_filter;
+mod files_filter;
 mod filter_changeset;
+mod formula_filter;
 mod number_filter;
+mod person_filter;
+mod relation_filter;
 mod select_option_filter;
 mod text_filter;
 mod util;
@@ -10,8 +14,12 @@ mod util;
 pub use checkbox_filter::*;
 pub use checklist_filter::*;
 pub use date_filter::*;
+pub use files_filter::*;
 pub use filter_changeset::*;
+pub use formula_filter::*;
 pub use number_filter::*;
+pub use person_filter::*;
+pub use relation_filter::*;
 pub use select_option_filter::*;
 pub use text_filter::*;
 pub use util::*;
@@ -0,0 +1,68 @@
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use flowy_error::ErrorCode;
+
+use crate::services::field::PersonCellData;
+use crate::services::filter::{Filter, FromFilterString};
+
+#[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
+pub struct PersonFilterPB {
+  #[pb(index = 1)]
+  pub condition: PersonFilterConditionPB,
+
+  /// Not used by [PersonFilterConditionPB::PersonIsMe], which always compares with the current
+  /// user.
+  #[pb(index = 2)]
+  pub user_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ProtoBuf_Enum)]
+#[repr(u8)]
+#[derive(Default)]
+pub enum PersonFilterConditionPB {
+  #[default]
+  PersonIsMe = 0,
+  PersonContains = 1,
+  PersonDoesNotContain = 2,
+  PersonIsEmpty = 3,
+  PersonIsNotEmpty = 4,
+}
+
+impl std::convert::From<PersonFilterConditionPB> for u32 {
+  fn from(value: PersonFilterConditionPB) -> Self {
+    value as u32
+  }
+}
+
+impl std::convert::TryFrom<u8> for PersonFilterConditionPB {
+  type Error = ErrorCode;
+
+  fn try_from(value: u8) -> Result<Self, Self::Error> {
+    match value {
+      0 => Ok(PersonFilterConditionPB::PersonIsMe),
+      1 => Ok(PersonFilterConditionPB::PersonContains),
+      2 => Ok(PersonFilterConditionPB::PersonDoesNotContain),
+      3 => Ok(PersonFilterConditionPB::PersonIsEmpty),
+      4 => Ok(PersonFilterConditionPB::PersonIsNotEmpty),
+      _ => Err(ErrorCode::InvalidParams),
+    }
+  }
+}
+
+impl FromFilterString for PersonFilterPB {
+  fn from_filter(filter: &Filter) -> Self
+  where
+    Self: Sized,
+  {
+    PersonFilterPB::from(filter)
+  }
+}
+
+impl std::convert::From<&Filter> for PersonFilterPB {
+  fn from(filter: &Filter) -> Self {
+    PersonFilterPB {
+      condition: PersonFilterConditionPB::try_from(filter.condition as u8)
+        .unwrap_or(PersonFilterConditionPB::PersonIsMe),
+      user_ids: PersonCellData::from(filter.content.clone()).user_ids,
+    }
+  }
+}
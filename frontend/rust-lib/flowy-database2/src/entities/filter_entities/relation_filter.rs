@@ -0,0 +1,69 @@
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use flowy_error::ErrorCode;
+
+use crate::services::field::RelationCellData;
+use crate::services::filter::{Filter, FromFilterString};
+
+#[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
+pub struct RelationFilterPB {
+  #[pb(index = 1)]
+  pub condition: RelationFilterConditionPB,
+
+  #[pb(index = 2)]
+  pub row_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ProtoBuf_Enum)]
+#[repr(u8)]
+#[derive(Default)]
+pub enum RelationFilterConditionPB {
+  #[default]
+  RelationContains = 0,
+  RelationDoesNotContain = 1,
+  RelationIsEmpty = 2,
+  RelationIsNotEmpty = 3,
+}
+
+impl std::convert::From<RelationFilterConditionPB> for u32 {
+  fn from(value: RelationFilterConditionPB) -> Self {
+    value as u32
+  }
+}
+
+impl std::convert::TryFrom<u8> for RelationFilterConditionPB {
+  type Error = ErrorCode;
+
+  fn try_from(value: u8) -> Result<Self, Self::Error> {
+    match value {
+      0 => Ok(RelationFilterConditionPB::RelationContains),
+      1 => Ok(RelationFilterConditionPB::RelationDoesNotContain),
+      2 => Ok(RelationFilterConditionPB::RelationIsEmpty),
+      3 => Ok(RelationFilterConditionPB::RelationIsNotEmpty),
+      _ => Err(ErrorCode::InvalidParams),
+    }
+  }
+}
+
+impl FromFilterString for RelationFilterPB {
+  fn from_filter(filter: &Filter) -> Self
+  where
+    Self: Sized,
+  {
+    RelationFilterPB::from(filter)
+  }
+}
+
+impl std::convert::From<&Filter> for RelationFilterPB {
+  fn from(filter: &Filter) -> Self {
+    let row_ids = RelationCellData::from(filter.content.clone())
+      .row_ids
+      .into_iter()
+      .map(String::from)
+      .collect();
+    RelationFilterPB {
+      condition: RelationFilterConditionPB::try_from(filter.condition as u8)
+        .unwrap_or(RelationFilterConditionPB::RelationContains),
+      row_ids,
+    }
+  }
+}
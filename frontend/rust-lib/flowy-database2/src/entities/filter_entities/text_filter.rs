@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
 use flowy_error::ErrorCode;
 
@@ -10,6 +12,10 @@ pub struct TextFilterPB {
 
   #[pb(index = 2)]
   pub content: String,
+
+  /// The text is compared ignoring the case unless it's true.
+  #[pb(index = 3)]
+  pub case_sensitive: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, ProtoBuf_Enum)]
@@ -25,6 +31,10 @@ pub enum TextFilterConditionPB {
   EndsWith = 5,
   TextIsEmpty = 6,
   TextIsNotEmpty = 7,
+  /// The content is a regular expression.
+  MatchesRegex = 8,
+  /// The content is a word or a phrase that must not be a part of a longer word.
+  ContainsWord = 9,
 }
 
 impl std::convert::From<TextFilterConditionPB> for u32 {
@@ -46,30 +56,61 @@ impl std::convert::TryFrom<u8> for TextFilterConditionPB {
       5 => Ok(TextFilterConditionPB::EndsWith),
       6 => Ok(TextFilterConditionPB::TextIsEmpty),
       7 => Ok(TextFilterConditionPB::TextIsNotEmpty),
+      8 => Ok(TextFilterConditionPB::MatchesRegex),
+      9 => Ok(TextFilterConditionPB::ContainsWord),
       _ => Err(ErrorCode::InvalidParams),
     }
   }
 }
 
+/// The content saved in the [Filter] of a case sensitive text filter. The content of the other
+/// text filters is saved as is, which keeps the filters created before working.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CaseSensitiveContent {
+  content: String,
+  case_sensitive: bool,
+}
+
+impl TextFilterPB {
+  /// Returns the content that is saved in the [Filter].
+  pub fn to_filter_content(&self) -> String {
+    if self.case_sensitive {
+      serde_json::to_string(&CaseSensitiveContent {
+        content: self.content.clone(),
+        case_sensitive: true,
+      })
+      .unwrap_or_else(|_| self.content.clone())
+    } else {
+      self.content.clone()
+    }
+  }
+
+  fn parse_filter_content(content: &str) -> (String, bool) {
+    match serde_json::from_str::<CaseSensitiveContent>(content) {
+      Ok(content) => (content.content, content.case_sensitive),
+      Err(_) => (content.to_owned(), false),
+    }
+  }
+}
+
 impl FromFilterString for TextFilterPB {
   fn from_filter(filter: &Filter) -> Self
   where
     Self: Sized,
   {
-    TextFilterPB {
-      condition: TextFilterConditionPB::try_from(filter.condition as u8)
-        .unwrap_or(TextFilterConditionPB::Is),
-      content: filter.content.clone(),
-    }
+    TextFilterPB::from(filter)
   }
 }
 
 impl std::convert::From<&Filter> for TextFilterPB {
   fn from(filter: &Filter) -> Self {
+    let (content, case_sensitive) = TextFilterPB::parse_filter_content(&filter.content);
     TextFilterPB {
       condition: TextFilterConditionPB::try_from(filter.condition as u8)
         .unwrap_or(TextFilterConditionPB::Is),
-      content: filter.content.clone(),
+      content,
+      case_sensitive,
     }
   }
 }
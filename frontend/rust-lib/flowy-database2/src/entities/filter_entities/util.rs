@@ -4,17 +4,18 @@ use std::sync::Arc;
 use bytes::Bytes;
 use collab_database::fields::Field;
 
-use flowy_derive::ProtoBuf;
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
 use flowy_error::ErrorCode;
 use validator::Validate;
 
 use crate::entities::parser::NotEmptyStr;
 use crate::entities::{
-  CheckboxFilterPB, ChecklistFilterPB, DateFilterContentPB, DateFilterPB, FieldType,
-  NumberFilterPB, SelectOptionFilterPB, TextFilterPB,
+  CheckboxFilterPB, ChecklistFilterPB, DateFilterContentPB, DateFilterPB, FieldType, FilesFilterPB,
+  FormulaFilterPB, NumberFilterPB, PersonFilterPB, RelationFilterPB, SelectOptionFilterPB,
+  TextFilterPB,
 };
-use crate::services::field::SelectOptionIds;
-use crate::services::filter::Filter;
+use crate::services::field::{SelectOptionIds, PERSON_IDS_SEPARATOR, RELATION_ROW_IDS_SEPARATOR};
+use crate::services::filter::{Filter, FilterType};
 
 #[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
 pub struct FilterPB {
@@ -29,13 +30,79 @@ pub struct FilterPB {
 
   #[pb(index = 4)]
   pub data: Vec<u8>,
+
+  #[pb(index = 5)]
+  pub filter_type: FilterTypePB,
+
+  /// The id of the And/Or filter that contains this filter. None if it's a root filter.
+  #[pb(index = 6, one_of)]
+  pub parent_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ProtoBuf_Enum, Default)]
+#[repr(u8)]
+pub enum FilterTypePB {
+  #[default]
+  Data = 0,
+  And = 1,
+  Or = 2,
+  /// Matches the rows whose text, url or select option cells contain the content of its
+  /// [TextFilterPB].
+  Search = 3,
+}
+
+impl std::convert::From<FilterType> for FilterTypePB {
+  fn from(filter_type: FilterType) -> Self {
+    match filter_type {
+      FilterType::Data => FilterTypePB::Data,
+      FilterType::And => FilterTypePB::And,
+      FilterType::Or => FilterTypePB::Or,
+      FilterType::Search => FilterTypePB::Search,
+    }
+  }
+}
+
+impl std::convert::From<FilterTypePB> for FilterType {
+  fn from(filter_type: FilterTypePB) -> Self {
+    match filter_type {
+      FilterTypePB::Data => FilterType::Data,
+      FilterTypePB::And => FilterType::And,
+      FilterTypePB::Or => FilterType::Or,
+      FilterTypePB::Search => FilterType::Search,
+    }
+  }
 }
 
 impl std::convert::From<&Filter> for FilterPB {
   fn from(filter: &Filter) -> Self {
+    if filter.is_group() {
+      return Self {
+        id: filter.id.clone(),
+        field_id: filter.field_id.clone(),
+        field_type: filter.field_type,
+        data: vec![],
+        filter_type: filter.filter_type.into(),
+        parent_id: filter.parent_id.clone(),
+      };
+    }
+
+    if filter.filter_type == FilterType::Search {
+      let bytes: Bytes = TextFilterPB::from(filter).try_into().unwrap();
+      return Self {
+        id: filter.id.clone(),
+        field_id: filter.field_id.clone(),
+        field_type: filter.field_type,
+        data: bytes.to_vec(),
+        filter_type: filter.filter_type.into(),
+        parent_id: filter.parent_id.clone(),
+      };
+    }
+
     let bytes: Bytes = match filter.field_type {
       FieldType::RichText => TextFilterPB::from(filter).try_into().unwrap(),
-      FieldType::Number => NumberFilterPB::from(filter).try_into().unwrap(),
+      FieldType::Number | FieldType::Lookup | FieldType::AutoNumber => {
+        NumberFilterPB::from(filter).try_into().unwrap()
+      },
       FieldType::DateTime | FieldType::LastEditedTime | FieldType::CreatedTime => {
         DateFilterPB::from(filter).try_into().unwrap()
       },
@@ -44,12 +111,18 @@ impl std::convert::From<&Filter> for FilterPB {
       FieldType::Checklist => ChecklistFilterPB::from(filter).try_into().unwrap(),
       FieldType::Checkbox => CheckboxFilterPB::from(filter).try_into().unwrap(),
       FieldType::URL => TextFilterPB::from(filter).try_into().unwrap(),
+      FieldType::Relation => RelationFilterPB::from(filter).try_into().unwrap(),
+      FieldType::Formula => FormulaFilterPB::from(filter).try_into().unwrap(),
+      FieldType::Person => PersonFilterPB::from(filter).try_into().unwrap(),
+      FieldType::Files => FilesFilterPB::from(filter).try_into().unwrap(),
     };
     Self {
       id: filter.id.clone(),
       field_id: filter.field_id.clone(),
       field_type: filter.field_type,
       data: bytes.to_vec(),
+      filter_type: filter.filter_type.into(),
+      parent_id: filter.parent_id.clone(),
     }
   }
 }
@@ -76,8 +149,8 @@ impl std::convert::From<Vec<FilterPB>> for RepeatedFilterPB {
 
 #[derive(ProtoBuf, Debug, Default, Clone, Validate)]
 pub struct DeleteFilterPayloadPB {
+  /// Empty if the filter is an And/Or or a search filter
   #[pb(index = 1)]
-  #[validate(custom = "lib_infra::validator_fn::required_not_empty_str")]
   pub field_id: String,
 
   #[pb(index = 2)]
@@ -94,8 +167,8 @@ pub struct DeleteFilterPayloadPB {
 
 #[derive(ProtoBuf, Debug, Default, Clone, Validate)]
 pub struct UpdateFilterPayloadPB {
+  /// Required unless the filter is an And/Or or a search filter
   #[pb(index = 1)]
-  #[validate(custom = "lib_infra::validator_fn::required_not_empty_str")]
   pub field_id: String,
 
   #[pb(index = 2)]
@@ -112,6 +185,15 @@ pub struct UpdateFilterPayloadPB {
   #[pb(index = 5)]
   #[validate(custom = "lib_infra::validator_fn::required_not_empty_str")]
   pub view_id: String,
+
+  #[pb(index = 6)]
+  pub filter_type: FilterTypePB,
+
+  /// The id of the And/Or filter the filter is added to. The filter is added to the root of
+  /// the view's filters if it's None.
+  #[pb(index = 7, one_of)]
+  #[validate(custom = "crate::entities::utils::validate_filter_id")]
+  pub parent_id: Option<String>,
 }
 
 impl UpdateFilterPayloadPB {
@@ -129,8 +211,36 @@ impl UpdateFilterPayloadPB {
       field_type,
       filter_id: None,
       data: data.to_vec(),
+      filter_type: FilterTypePB::Data,
+      parent_id: None,
+    }
+  }
+
+  /// Creates a filter that searches the text of all the fields of the row.
+  pub fn new_search(view_id: &str, text_filter: TextFilterPB) -> Self {
+    let data: Bytes = text_filter.try_into().unwrap_or_else(|_| Bytes::new());
+    Self {
+      view_id: view_id.to_owned(),
+      data: data.to_vec(),
+      filter_type: FilterTypePB::Search,
+      ..Default::default()
+    }
+  }
+
+  /// Creates an And/Or filter that groups the filters whose parent_id is the id of this filter.
+  pub fn new_group(view_id: &str, filter_type: FilterTypePB, parent_id: Option<String>) -> Self {
+    Self {
+      view_id: view_id.to_owned(),
+      filter_type,
+      parent_id,
+      ..Default::default()
     }
   }
+
+  pub fn with_parent_id(mut self, parent_id: &str) -> Self {
+    self.parent_id = Some(parent_id.to_owned());
+    self
+  }
 }
 
 impl TryInto<UpdateFilterParams> for UpdateFilterPayloadPB {
@@ -141,9 +251,6 @@ impl TryInto<UpdateFilterParams> for UpdateFilterPayloadPB {
       .map_err(|_| ErrorCode::DatabaseViewIdIsEmpty)?
       .0;
 
-    let field_id = NotEmptyStr::parse(self.field_id)
-      .map_err(|_| ErrorCode::FieldIdIsEmpty)?
-      .0;
     let filter_id = match self.filter_id {
       None => None,
       Some(filter_id) => Some(
@@ -152,6 +259,46 @@ impl TryInto<UpdateFilterParams> for UpdateFilterPayloadPB {
           .0,
       ),
     };
+    let parent_id = match self.parent_id {
+      None => None,
+      Some(parent_id) => Some(
+        NotEmptyStr::parse(parent_id)
+          .map_err(|_| ErrorCode::FilterIdIsEmpty)?
+          .0,
+      ),
+    };
+    let filter_type = FilterType::from(self.filter_type);
+    if filter_type == FilterType::Search {
+      let bytes: &[u8] = self.data.as_ref();
+      let filter = TextFilterPB::try_from(bytes).map_err(|_| ErrorCode::ProtobufSerde)?;
+      return Ok(UpdateFilterParams {
+        view_id,
+        field_id: "".to_string(),
+        filter_id,
+        parent_id,
+        filter_type,
+        field_type: FieldType::RichText,
+        condition: filter.condition as i64,
+        content: filter.to_filter_content(),
+      });
+    }
+
+    if filter_type != FilterType::Data {
+      return Ok(UpdateFilterParams {
+        view_id,
+        field_id: "".to_string(),
+        filter_id,
+        parent_id,
+        filter_type,
+        field_type: self.field_type,
+        condition: 0,
+        content: "".to_string(),
+      });
+    }
+
+    let field_id = NotEmptyStr::parse(self.field_id)
+      .map_err(|_| ErrorCode::FieldIdIsEmpty)?
+      .0;
     let condition;
     let mut content = "".to_string();
     let bytes: &[u8] = self.data.as_ref();
@@ -160,24 +307,28 @@ impl TryInto<UpdateFilterParams> for UpdateFilterPayloadPB {
       FieldType::RichText | FieldType::URL => {
         let filter = TextFilterPB::try_from(bytes).map_err(|_| ErrorCode::ProtobufSerde)?;
         condition = filter.condition as u8;
-        content = filter.content;
+        content = filter.to_filter_content();
       },
       FieldType::Checkbox => {
         let filter = CheckboxFilterPB::try_from(bytes).map_err(|_| ErrorCode::ProtobufSerde)?;
         condition = filter.condition as u8;
       },
-      FieldType::Number => {
+      FieldType::Number | FieldType::Lookup | FieldType::AutoNumber => {
         let filter = NumberFilterPB::try_from(bytes).map_err(|_| ErrorCode::ProtobufSerde)?;
         condition = filter.condition as u8;
         content = filter.content;
       },
       FieldType::DateTime | FieldType::LastEditedTime | FieldType::CreatedTime => {
         let filter = DateFilterPB::try_from(bytes).map_err(|_| ErrorCode::ProtobufSerde)?;
+        if !filter.is_number_of_days_valid() {
+          return Err(ErrorCode::InvalidParams);
+        }
         condition = filter.condition as u8;
         content = DateFilterContentPB {
           start: filter.start,
           end: filter.end,
           timestamp: filter.timestamp,
+          number_of_days: filter.number_of_days,
         }
         .to_string();
       },
@@ -186,12 +337,42 @@ impl TryInto<UpdateFilterParams> for UpdateFilterPayloadPB {
         condition = filter.condition as u8;
         content = SelectOptionIds::from(filter.option_ids).to_string();
       },
+      FieldType::Relation => {
+        let filter = RelationFilterPB::try_from(bytes).map_err(|_| ErrorCode::ProtobufSerde)?;
+        condition = filter.condition as u8;
+        content = filter.row_ids.join(RELATION_ROW_IDS_SEPARATOR);
+      },
+      FieldType::Formula => {
+        let filter = FormulaFilterPB::try_from(bytes).map_err(|_| ErrorCode::ProtobufSerde)?;
+        let is_number_of_days_valid = filter
+          .date_filter
+          .as_ref()
+          .map_or(true, |date_filter| date_filter.is_number_of_days_valid());
+        if !is_number_of_days_valid {
+          return Err(ErrorCode::InvalidParams);
+        }
+        let (formula_condition, formula_content) = filter.to_condition_and_content();
+        condition = formula_condition;
+        content = formula_content;
+      },
+      FieldType::Person => {
+        let filter = PersonFilterPB::try_from(bytes).map_err(|_| ErrorCode::ProtobufSerde)?;
+        condition = filter.condition as u8;
+        content = filter.user_ids.join(PERSON_IDS_SEPARATOR);
+      },
+      FieldType::Files => {
+        let filter = FilesFilterPB::try_from(bytes).map_err(|_| ErrorCode::ProtobufSerde)?;
+        condition = filter.condition as u8;
+        content = filter.content;
+      },
     }
 
     Ok(UpdateFilterParams {
       view_id,
       field_id,
       filter_id,
+      parent_id,
+      filter_type,
       field_type: self.field_type,
       condition: condition as i64,
       content,
@@ -205,6 +386,9 @@ pub struct UpdateFilterParams {
   pub field_id: String,
   /// Create a new filter if the filter_id is None
   pub filter_id: Option<String>,
+  /// Add the filter to the root of the view's filters if the parent_id is None
+  pub parent_id: Option<String>,
+  pub filter_type: FilterType,
   pub field_type: FieldType,
   pub condition: i64,
   pub content: String,
@@ -15,6 +15,9 @@ pub struct GroupSettingPB {
 
   #[pb(index = 2)]
   pub field_id: String,
+
+  #[pb(index = 3, one_of)]
+  pub swimlane_field_id: Option<String>,
 }
 
 impl std::convert::From<&GroupSetting> for GroupSettingPB {
@@ -22,6 +25,10 @@ impl std::convert::From<&GroupSetting> for GroupSettingPB {
     GroupSettingPB {
       id: rev.id.clone(),
       field_id: rev.field_id.clone(),
+      swimlane_field_id: rev
+        .swimlane
+        .as_ref()
+        .map(|swimlane| swimlane.field_id.clone()),
     }
   }
 }
@@ -109,6 +116,11 @@ pub struct GroupByFieldPayloadPB {
 
   #[pb(index = 2)]
   pub view_id: String,
+
+  /// The JSON configuration of the groups, e.g. the bucket size of the number groups. The default
+  /// configuration of the field type is used if it's empty.
+  #[pb(index = 3)]
+  pub setting_content: String,
 }
 
 impl TryInto<GroupByFieldParams> for GroupByFieldPayloadPB {
@@ -122,13 +134,18 @@ impl TryInto<GroupByFieldParams> for GroupByFieldPayloadPB {
       .map_err(|_| ErrorCode::ViewIdIsInvalid)?
       .0;
 
-    Ok(GroupByFieldParams { field_id, view_id })
+    Ok(GroupByFieldParams {
+      field_id,
+      view_id,
+      setting_content: self.setting_content,
+    })
   }
 }
 
 pub struct GroupByFieldParams {
   pub field_id: String,
   pub view_id: String,
+  pub setting_content: String,
 }
 
 #[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone, Validate)]
@@ -1,7 +1,9 @@
 mod configuration;
 mod group;
 mod group_changeset;
+mod swimlane;
 
 pub use configuration::*;
 pub use group::*;
 pub use group_changeset::*;
+pub use swimlane::*;
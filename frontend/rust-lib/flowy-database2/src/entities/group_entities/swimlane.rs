@@ -0,0 +1,69 @@
+use flowy_derive::ProtoBuf;
+use flowy_error::ErrorCode;
+
+use crate::entities::parser::NotEmptyStr;
+use crate::entities::GroupPB;
+
+/// A swimlane is a group of the swimlane field. It's displayed as a row of the board that
+/// contains the groups of the board.
+#[derive(ProtoBuf, Debug, Default, Clone)]
+pub struct SwimlanePB {
+  #[pb(index = 1)]
+  pub field_id: String,
+
+  #[pb(index = 2)]
+  pub swimlane_id: String,
+
+  #[pb(index = 3)]
+  pub swimlane_name: String,
+
+  #[pb(index = 4)]
+  pub is_default: bool,
+
+  #[pb(index = 5)]
+  pub is_visible: bool,
+
+  /// The groups of the board. Each group only contains the rows of this swimlane.
+  #[pb(index = 6)]
+  pub groups: Vec<GroupPB>,
+}
+
+#[derive(ProtoBuf, Debug, Default, Clone)]
+pub struct RepeatedSwimlanePB {
+  #[pb(index = 1)]
+  pub items: Vec<SwimlanePB>,
+}
+
+#[derive(Debug, Default, ProtoBuf)]
+pub struct SetSwimlaneFieldPayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  /// The field that the board is sub-grouped by. Removes the swimlanes if it's None.
+  #[pb(index = 2, one_of)]
+  pub field_id: Option<String>,
+}
+
+pub struct SetSwimlaneFieldParams {
+  pub view_id: String,
+  pub field_id: Option<String>,
+}
+
+impl TryInto<SetSwimlaneFieldParams> for SetSwimlaneFieldPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<SetSwimlaneFieldParams, Self::Error> {
+    let view_id = NotEmptyStr::parse(self.view_id)
+      .map_err(|_| ErrorCode::ViewIdIsInvalid)?
+      .0;
+    let field_id = match self.field_id {
+      None => None,
+      Some(field_id) => Some(
+        NotEmptyStr::parse(field_id)
+          .map_err(|_| ErrorCode::FieldIdIsEmpty)?
+          .0,
+      ),
+    };
+    Ok(SetSwimlaneFieldParams { view_id, field_id })
+  }
+}
@@ -14,6 +14,12 @@ macro_rules! impl_into_field_type {
           7 => FieldType::Checklist,
           8 => FieldType::LastEditedTime,
           9 => FieldType::CreatedTime,
+          10 => FieldType::Relation,
+          11 => FieldType::Lookup,
+          12 => FieldType::Formula,
+          13 => FieldType::Person,
+          14 => FieldType::Files,
+          15 => FieldType::AutoNumber,
           _ => {
             tracing::error!("🔴Can't parser FieldType from value: {}", ty);
             FieldType::RichText
@@ -54,6 +60,14 @@ macro_rules! impl_into_calculation_type {
           2 => CalculationType::Median,
           3 => CalculationType::Min,
           4 => CalculationType::Sum,
+          5 => CalculationType::Count,
+          6 => CalculationType::CountEmpty,
+          7 => CalculationType::CountNonEmpty,
+          8 => CalculationType::CountUnique,
+          9 => CalculationType::PercentChecked,
+          10 => CalculationType::Earliest,
+          11 => CalculationType::Latest,
+          12 => CalculationType::DateRange,
           _ => {
             tracing::error!("🔴 Can't parse CalculationType from value: {}", ty);
             CalculationType::Average
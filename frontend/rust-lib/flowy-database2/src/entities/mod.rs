@@ -5,6 +5,7 @@ mod cell_entities;
 mod database_entities;
 mod field_entities;
 mod field_settings_entities;
+mod field_validation_entities;
 pub mod filter_entities;
 mod group_entities;
 pub mod parser;
@@ -13,6 +14,7 @@ mod row_entities;
 pub mod setting_entities;
 mod share_entities;
 mod sort_entities;
+mod timeline_entities;
 mod type_option_entities;
 mod view_entities;
 
@@ -26,6 +28,7 @@ pub use cell_entities::*;
 pub use database_entities::*;
 pub use field_entities::*;
 pub use field_settings_entities::*;
+pub use field_validation_entities::*;
 pub use filter_entities::*;
 pub use group_entities::*;
 pub use position_entities::*;
@@ -33,6 +36,7 @@ pub use row_entities::*;
 pub use setting_entities::*;
 pub use share_entities::*;
 pub use sort_entities::*;
+pub use timeline_entities::*;
 pub use type_option_entities::*;
 pub use view_entities::*;
 
@@ -8,6 +8,7 @@ use flowy_error::ErrorCode;
 
 use crate::entities::parser::NotEmptyStr;
 use crate::entities::position_entities::OrderObjectPositionPB;
+use crate::entities::FieldValidationErrorPB;
 use crate::services::database::{InsertedRow, UpdatedRow};
 
 /// [RowPB] Describes a row. Has the id of the parent Block. Has the metadata of the row.
@@ -63,6 +64,12 @@ pub struct RowMetaPB {
 
   #[pb(index = 5)]
   pub is_document_empty: bool,
+
+  /// The validation rules that the row breaks. Only set when the row is created: the row that
+  /// misses a required cell is created anyway, so that blank rows can be added, and the missing
+  /// cells are reported here.
+  #[pb(index = 6)]
+  pub validation_errors: Vec<FieldValidationErrorPB>,
 }
 
 impl std::convert::From<&RowDetail> for RowMetaPB {
@@ -73,6 +80,7 @@ impl std::convert::From<&RowDetail> for RowMetaPB {
       icon: row_detail.meta.icon_url.clone(),
       cover: row_detail.meta.cover_url.clone(),
       is_document_empty: row_detail.meta.is_document_empty,
+      validation_errors: vec![],
     }
   }
 }
@@ -84,6 +92,7 @@ impl std::convert::From<RowDetail> for RowMetaPB {
       icon: row_detail.meta.icon_url,
       cover: row_detail.meta.cover_url,
       is_document_empty: row_detail.meta.is_document_empty,
+      validation_errors: vec![],
     }
   }
 }
@@ -1,6 +1,6 @@
 use std::convert::TryInto;
 
-use collab_database::views::DatabaseLayout;
+use collab_database::views::{DatabaseLayout, LayoutSetting};
 use strum_macros::EnumIter;
 
 use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
@@ -10,10 +10,10 @@ use validator::Validate;
 use crate::entities::parser::NotEmptyStr;
 use crate::entities::{
   CalendarLayoutSettingPB, DeleteFilterPayloadPB, DeleteSortPayloadPB, RepeatedFieldSettingsPB,
-  RepeatedFilterPB, RepeatedGroupSettingPB, RepeatedSortPB, UpdateFilterPayloadPB, UpdateGroupPB,
-  UpdateSortPayloadPB,
+  RepeatedFilterPB, RepeatedGroupSettingPB, RepeatedSortPB, TimelineLayoutSettingPB,
+  UpdateFilterPayloadPB, UpdateGroupPB, UpdateSortPayloadPB,
 };
-use crate::services::setting::{BoardLayoutSetting, CalendarLayoutSetting};
+use crate::services::setting::{BoardLayoutSetting, CalendarLayoutSetting, TimelineLayoutSetting};
 
 use super::{BoardLayoutSettingPB, ReorderSortPayloadPB};
 
@@ -46,6 +46,22 @@ pub enum DatabaseLayoutPB {
   Grid = 0,
   Board = 1,
   Calendar = 2,
+  /// A timeline is saved as a [DatabaseLayout::Grid] view whose layout setting is a
+  /// [TimelineLayoutSetting], because the [DatabaseLayout] of the collab has no timeline.
+  Timeline = 3,
+}
+
+impl DatabaseLayoutPB {
+  /// Returns the layout of the view that is saved with the layout setting.
+  pub fn from_view_layout(layout: DatabaseLayout, layout_setting: Option<&LayoutSetting>) -> Self {
+    let is_timeline = layout_setting
+      .map(TimelineLayoutSetting::is_timeline)
+      .unwrap_or(false);
+    match layout {
+      DatabaseLayout::Grid if is_timeline => DatabaseLayoutPB::Timeline,
+      _ => layout.into(),
+    }
+  }
 }
 
 impl std::convert::From<DatabaseLayout> for DatabaseLayoutPB {
@@ -61,7 +77,7 @@ impl std::convert::From<DatabaseLayout> for DatabaseLayoutPB {
 impl std::convert::From<DatabaseLayoutPB> for DatabaseLayout {
   fn from(layout: DatabaseLayoutPB) -> Self {
     match layout {
-      DatabaseLayoutPB::Grid => DatabaseLayout::Grid,
+      DatabaseLayoutPB::Grid | DatabaseLayoutPB::Timeline => DatabaseLayout::Grid,
       DatabaseLayoutPB::Board => DatabaseLayout::Board,
       DatabaseLayoutPB::Calendar => DatabaseLayout::Calendar,
     }
@@ -112,6 +128,9 @@ pub struct DatabaseLayoutSettingPB {
 
   #[pb(index = 3, one_of)]
   pub calendar: Option<CalendarLayoutSettingPB>,
+
+  #[pb(index = 4, one_of)]
+  pub timeline: Option<TimelineLayoutSettingPB>,
 }
 
 impl DatabaseLayoutSettingPB {
@@ -120,6 +139,7 @@ impl DatabaseLayoutSettingPB {
       layout_type: DatabaseLayoutPB::Board,
       board: Some(layout_setting.into()),
       calendar: None,
+      timeline: None,
     }
   }
 
@@ -128,6 +148,16 @@ impl DatabaseLayoutSettingPB {
       layout_type: DatabaseLayoutPB::Calendar,
       calendar: Some(layout_setting.into()),
       board: None,
+      timeline: None,
+    }
+  }
+
+  pub fn from_timeline(layout_setting: TimelineLayoutSetting) -> Self {
+    Self {
+      layout_type: DatabaseLayoutPB::Timeline,
+      timeline: Some(layout_setting.into()),
+      board: None,
+      calendar: None,
     }
   }
 }
@@ -137,6 +167,7 @@ pub struct LayoutSettingParams {
   pub layout_type: DatabaseLayout,
   pub board: Option<BoardLayoutSetting>,
   pub calendar: Option<CalendarLayoutSetting>,
+  pub timeline: Option<TimelineLayoutSetting>,
 }
 
 impl LayoutSettingParams {
@@ -150,10 +181,15 @@ impl LayoutSettingParams {
 
 impl From<LayoutSettingParams> for DatabaseLayoutSettingPB {
   fn from(data: LayoutSettingParams) -> Self {
+    let layout_type = match data.timeline {
+      Some(_) => DatabaseLayoutPB::Timeline,
+      None => data.layout_type.into(),
+    };
     Self {
-      layout_type: data.layout_type.into(),
+      layout_type,
       board: data.board.map(|board| board.into()),
       calendar: data.calendar.map(|calendar| calendar.into()),
+      timeline: data.timeline.map(|timeline| timeline.into()),
     }
   }
 }
@@ -171,6 +207,9 @@ pub struct LayoutSettingChangesetPB {
 
   #[pb(index = 4, one_of)]
   pub calendar: Option<CalendarLayoutSettingPB>,
+
+  #[pb(index = 5, one_of)]
+  pub timeline: Option<TimelineLayoutSettingPB>,
 }
 
 #[derive(Debug)]
@@ -179,12 +218,14 @@ pub struct LayoutSettingChangeset {
   pub layout_type: DatabaseLayout,
   pub board: Option<BoardLayoutSetting>,
   pub calendar: Option<CalendarLayoutSetting>,
+  pub timeline: Option<TimelineLayoutSetting>,
 }
 
 impl LayoutSettingChangeset {
   pub fn is_valid(&self) -> bool {
     self.board.is_some() && self.layout_type == DatabaseLayout::Board
       || self.calendar.is_some() && self.layout_type == DatabaseLayout::Calendar
+      || self.timeline.is_some() && self.layout_type == DatabaseLayout::Grid
   }
 }
 
@@ -201,6 +242,7 @@ impl TryInto<LayoutSettingChangeset> for LayoutSettingChangesetPB {
       layout_type: self.layout_type.into(),
       board: self.board.map(Into::into),
       calendar: self.calendar.map(Into::into),
+      timeline: self.timeline.map(Into::into),
     })
   }
 }
@@ -1,9 +1,14 @@
 use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
 
+use crate::entities::FieldType;
+use crate::services::share::csv::{CSVColumn, CSVFormat, CSVImportPreview};
+
 #[derive(Debug, ProtoBuf_Enum, Clone, Default)]
 pub enum DatabaseExportDataType {
   #[default]
   CSV = 0,
+  JSON = 1,
+  Markdown = 2,
 }
 
 #[derive(Debug, ProtoBuf, Default, Clone)]
@@ -14,3 +19,126 @@ pub struct DatabaseExportDataPB {
   #[pb(index = 2)]
   pub data: String,
 }
+
+#[derive(Debug, ProtoBuf_Enum, Clone, Default)]
+pub enum CSVFormatPB {
+  #[default]
+  Original = 0,
+  META = 1,
+}
+
+impl From<CSVFormatPB> for CSVFormat {
+  fn from(format: CSVFormatPB) -> Self {
+    match format {
+      CSVFormatPB::Original => CSVFormat::Original,
+      CSVFormatPB::META => CSVFormat::META,
+    }
+  }
+}
+
+#[derive(Debug, ProtoBuf, Default, Clone)]
+pub struct ExportCSVToFilePayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub file_path: String,
+
+  #[pb(index = 3)]
+  pub format: CSVFormatPB,
+}
+
+/// Exports a view as the user sees it, with the filters, sorts and hidden fields of the view.
+#[derive(Debug, ProtoBuf, Default, Clone)]
+pub struct ExportViewPayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  /// Split the rows by the groups of the view, e.g. the columns of a board.
+  #[pb(index = 2)]
+  pub include_group_headers: bool,
+}
+
+#[derive(Debug, ProtoBuf, Default, Clone)]
+pub struct ExportViewCSVPayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub format: CSVFormatPB,
+
+  /// Add a column with the name of the group of each row, e.g. the column of a board.
+  #[pb(index = 3)]
+  pub include_group_headers: bool,
+}
+
+#[derive(Debug, ProtoBuf, Default, Clone)]
+pub struct ExportXLSXPayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub file_path: String,
+}
+
+/// The progress of importing a CSV file. It's sent after each batch of rows is imported.
+#[derive(Debug, ProtoBuf, Default, Clone)]
+pub struct CSVImportProgressPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  /// The proportion of the file that has been imported, between 0.0 and 1.0.
+  #[pb(index = 2)]
+  pub progress: f64,
+
+  #[pb(index = 3)]
+  pub num_of_rows: i64,
+}
+
+#[derive(Debug, ProtoBuf, Default, Clone)]
+pub struct CSVImportPreviewPayloadPB {
+  #[pb(index = 1)]
+  pub content: String,
+}
+
+/// The columns of the CSV with their inferred field types. The client shows them to the user,
+/// who can change the types before importing the CSV.
+#[derive(Debug, ProtoBuf, Default, Clone)]
+pub struct CSVImportPreviewPB {
+  #[pb(index = 1)]
+  pub columns: Vec<CSVColumnPB>,
+
+  #[pb(index = 2)]
+  pub num_of_rows: i64,
+}
+
+impl From<CSVImportPreview> for CSVImportPreviewPB {
+  fn from(preview: CSVImportPreview) -> Self {
+    Self {
+      columns: preview.columns.into_iter().map(CSVColumnPB::from).collect(),
+      num_of_rows: preview.num_of_rows as i64,
+    }
+  }
+}
+
+#[derive(Debug, ProtoBuf, Default, Clone)]
+pub struct CSVColumnPB {
+  #[pb(index = 1)]
+  pub name: String,
+
+  #[pb(index = 2)]
+  pub field_type: FieldType,
+
+  #[pb(index = 3)]
+  pub samples: Vec<String>,
+}
+
+impl From<CSVColumn> for CSVColumnPB {
+  fn from(column: CSVColumn) -> Self {
+    Self {
+      name: column.name,
+      field_type: column.field_type,
+      samples: column.samples,
+    }
+  }
+}
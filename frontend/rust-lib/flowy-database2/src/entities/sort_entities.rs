@@ -2,7 +2,7 @@ use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
 use validator::Validate;
 
 use crate::entities::FieldType;
-use crate::services::sort::{Sort, SortCondition};
+use crate::services::sort::{EmptyPlacement, SelectOptionOrder, Sort, SortCondition};
 
 #[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
 pub struct SortPB {
@@ -17,6 +17,12 @@ pub struct SortPB {
 
   #[pb(index = 4)]
   pub condition: SortConditionPB,
+
+  #[pb(index = 5)]
+  pub empty_placement: EmptyPlacementPB,
+
+  #[pb(index = 6)]
+  pub option_order: SelectOptionOrderPB,
 }
 
 impl std::convert::From<&Sort> for SortPB {
@@ -26,6 +32,8 @@ impl std::convert::From<&Sort> for SortPB {
       field_id: sort.field_id.clone(),
       field_type: sort.field_type,
       condition: sort.condition.into(),
+      empty_placement: sort.empty_placement.into(),
+      option_order: sort.option_order.into(),
     }
   }
 }
@@ -37,6 +45,8 @@ impl std::convert::From<Sort> for SortPB {
       field_id: sort.field_id,
       field_type: sort.field_type,
       condition: sort.condition.into(),
+      empty_placement: sort.empty_placement.into(),
+      option_order: sort.option_order.into(),
     }
   }
 }
@@ -99,6 +109,59 @@ impl std::convert::From<SortConditionPB> for SortCondition {
   }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ProtoBuf_Enum, Default)]
+#[repr(u8)]
+pub enum EmptyPlacementPB {
+  #[default]
+  EmptyLast = 0,
+  EmptyFirst = 1,
+}
+
+impl std::convert::From<EmptyPlacement> for EmptyPlacementPB {
+  fn from(placement: EmptyPlacement) -> Self {
+    match placement {
+      EmptyPlacement::Last => EmptyPlacementPB::EmptyLast,
+      EmptyPlacement::First => EmptyPlacementPB::EmptyFirst,
+    }
+  }
+}
+
+impl std::convert::From<EmptyPlacementPB> for EmptyPlacement {
+  fn from(placement: EmptyPlacementPB) -> Self {
+    match placement {
+      EmptyPlacementPB::EmptyLast => EmptyPlacement::Last,
+      EmptyPlacementPB::EmptyFirst => EmptyPlacement::First,
+    }
+  }
+}
+
+/// Only used by the select option fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ProtoBuf_Enum, Default)]
+#[repr(u8)]
+pub enum SelectOptionOrderPB {
+  #[default]
+  OptionName = 0,
+  OptionPosition = 1,
+}
+
+impl std::convert::From<SelectOptionOrder> for SelectOptionOrderPB {
+  fn from(order: SelectOptionOrder) -> Self {
+    match order {
+      SelectOptionOrder::Name => SelectOptionOrderPB::OptionName,
+      SelectOptionOrder::Position => SelectOptionOrderPB::OptionPosition,
+    }
+  }
+}
+
+impl std::convert::From<SelectOptionOrderPB> for SelectOptionOrder {
+  fn from(order: SelectOptionOrderPB) -> Self {
+    match order {
+      SelectOptionOrderPB::OptionName => SelectOptionOrder::Name,
+      SelectOptionOrderPB::OptionPosition => SelectOptionOrder::Position,
+    }
+  }
+}
+
 #[derive(ProtoBuf, Debug, Default, Clone, Validate)]
 pub struct UpdateSortPayloadPB {
   #[pb(index = 1)]
@@ -119,6 +182,12 @@ pub struct UpdateSortPayloadPB {
 
   #[pb(index = 5)]
   pub condition: SortConditionPB,
+
+  #[pb(index = 6)]
+  pub empty_placement: EmptyPlacementPB,
+
+  #[pb(index = 7)]
+  pub option_order: SelectOptionOrderPB,
 }
 
 #[derive(Debug, Default, Clone, Validate, ProtoBuf)]
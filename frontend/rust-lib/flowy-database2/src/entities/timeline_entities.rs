@@ -0,0 +1,157 @@
+use flowy_derive::ProtoBuf;
+use flowy_error::ErrorCode;
+
+use crate::entities::parser::NotEmptyStr;
+use crate::entities::RowMetaPB;
+use crate::services::setting::TimelineLayoutSetting;
+
+#[derive(Debug, Clone, Eq, PartialEq, Default, ProtoBuf)]
+pub struct TimelineLayoutSettingPB {
+  #[pb(index = 1)]
+  pub start_field_id: String,
+
+  /// If it's None, the end of each span is the end date of the start field's date range.
+  #[pb(index = 2, one_of)]
+  pub end_field_id: Option<String>,
+
+  #[pb(index = 3, one_of)]
+  pub dependency_field_id: Option<String>,
+}
+
+impl std::convert::From<TimelineLayoutSettingPB> for TimelineLayoutSetting {
+  fn from(pb: TimelineLayoutSettingPB) -> Self {
+    TimelineLayoutSetting {
+      start_field_id: pb.start_field_id,
+      end_field_id: pb.end_field_id,
+      dependency_field_id: pb.dependency_field_id,
+    }
+  }
+}
+
+impl std::convert::From<TimelineLayoutSetting> for TimelineLayoutSettingPB {
+  fn from(params: TimelineLayoutSetting) -> Self {
+    TimelineLayoutSettingPB {
+      start_field_id: params.start_field_id,
+      end_field_id: params.end_field_id,
+      dependency_field_id: params.dependency_field_id,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct TimelineSpanPB {
+  #[pb(index = 1)]
+  pub row_meta: RowMetaPB,
+
+  #[pb(index = 2)]
+  pub title: String,
+
+  /// Timestamp in seconds.
+  #[pb(index = 3)]
+  pub start: i64,
+
+  /// Timestamp in seconds. It's equal to the start if the row has no end date.
+  #[pb(index = 4)]
+  pub end: i64,
+
+  /// The ids of the rows that must be done before this one.
+  #[pb(index = 5)]
+  pub dependencies: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct RepeatedTimelineSpanPB {
+  #[pb(index = 1)]
+  pub items: Vec<TimelineSpanPB>,
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct NoDateTimelineRowPB {
+  #[pb(index = 1)]
+  pub row_id: String,
+
+  #[pb(index = 2)]
+  pub title: String,
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct RepeatedNoDateTimelineRowPB {
+  #[pb(index = 1)]
+  pub items: Vec<NoDateTimelineRowPB>,
+}
+
+/// Moves the span of the row to the `start`, keeping its duration.
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct MoveTimelineSpanPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub row_id: String,
+
+  #[pb(index = 3)]
+  pub start: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct MoveTimelineSpanParams {
+  pub view_id: String,
+  pub row_id: String,
+  pub start: i64,
+}
+
+impl TryInto<MoveTimelineSpanParams> for MoveTimelineSpanPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<MoveTimelineSpanParams, Self::Error> {
+    let view_id = NotEmptyStr::parse(self.view_id).map_err(|_| ErrorCode::ViewIdIsInvalid)?;
+    let row_id = NotEmptyStr::parse(self.row_id).map_err(|_| ErrorCode::RowIdIsEmpty)?;
+    Ok(MoveTimelineSpanParams {
+      view_id: view_id.0,
+      row_id: row_id.0,
+      start: self.start,
+    })
+  }
+}
+
+/// Sets the start and the end of the span of the row.
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct ResizeTimelineSpanPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub row_id: String,
+
+  #[pb(index = 3)]
+  pub start: i64,
+
+  #[pb(index = 4)]
+  pub end: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct ResizeTimelineSpanParams {
+  pub view_id: String,
+  pub row_id: String,
+  pub start: i64,
+  pub end: i64,
+}
+
+impl TryInto<ResizeTimelineSpanParams> for ResizeTimelineSpanPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<ResizeTimelineSpanParams, Self::Error> {
+    let view_id = NotEmptyStr::parse(self.view_id).map_err(|_| ErrorCode::ViewIdIsInvalid)?;
+    let row_id = NotEmptyStr::parse(self.row_id).map_err(|_| ErrorCode::RowIdIsEmpty)?;
+    if self.end < self.start {
+      return Err(ErrorCode::InvalidParams);
+    }
+    Ok(ResizeTimelineSpanParams {
+      view_id: view_id.0,
+      row_id: row_id.0,
+      start: self.start,
+      end: self.end,
+    })
+  }
+}
@@ -0,0 +1,33 @@
+use flowy_derive::ProtoBuf;
+
+use crate::services::field::AutoNumberTypeOption;
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct AutoNumberTypeOptionPB {
+  /// Displayed before the number, e.g. `BUG-`.
+  #[pb(index = 1)]
+  pub prefix: String,
+
+  /// The number of the next created row. Setting it only takes effect if it's greater than the
+  /// numbers of the existing rows.
+  #[pb(index = 2)]
+  pub next_number: i64,
+}
+
+impl From<AutoNumberTypeOption> for AutoNumberTypeOptionPB {
+  fn from(value: AutoNumberTypeOption) -> Self {
+    AutoNumberTypeOptionPB {
+      prefix: value.prefix,
+      next_number: value.next_number,
+    }
+  }
+}
+
+impl From<AutoNumberTypeOptionPB> for AutoNumberTypeOption {
+  fn from(value: AutoNumberTypeOptionPB) -> Self {
+    AutoNumberTypeOption {
+      prefix: value.prefix,
+      next_number: value.next_number.max(1),
+    }
+  }
+}
@@ -78,6 +78,9 @@ pub struct DateTypeOptionPB {
 
   #[pb(index = 3)]
   pub timezone_id: String,
+
+  #[pb(index = 4)]
+  pub is_range: bool,
 }
 
 impl From<DateTypeOption> for DateTypeOptionPB {
@@ -86,6 +89,7 @@ impl From<DateTypeOption> for DateTypeOptionPB {
       date_format: data.date_format.into(),
       time_format: data.time_format.into(),
       timezone_id: data.timezone_id,
+      is_range: data.is_range,
     }
   }
 }
@@ -96,6 +100,7 @@ impl From<DateTypeOptionPB> for DateTypeOption {
       date_format: data.date_format.into(),
       time_format: data.time_format.into(),
       timezone_id: data.timezone_id,
+      is_range: data.is_range,
     }
   }
 }
@@ -0,0 +1,104 @@
+use flowy_derive::ProtoBuf;
+use flowy_error::FlowyError;
+
+use crate::entities::{CellIdPB, CellIdParams};
+use crate::services::field::{FileEntry, FilesTypeOption};
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct FilePB {
+  #[pb(index = 1)]
+  pub id: String,
+
+  #[pb(index = 2)]
+  pub name: String,
+
+  /// The url of the object in the storage service.
+  #[pb(index = 3)]
+  pub url: String,
+
+  #[pb(index = 4)]
+  pub mime: String,
+
+  #[pb(index = 5)]
+  pub size: i64,
+
+  #[pb(index = 6)]
+  pub uploaded_at: i64,
+}
+
+impl From<FileEntry> for FilePB {
+  fn from(file: FileEntry) -> Self {
+    Self {
+      id: file.id,
+      name: file.name,
+      url: file.url,
+      mime: file.mime,
+      size: file.size,
+      uploaded_at: file.uploaded_at,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct FilesCellDataPB {
+  #[pb(index = 1)]
+  pub files: Vec<FilePB>,
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct FilesCellChangesetPB {
+  #[pb(index = 1)]
+  pub cell_id: CellIdPB,
+
+  /// The paths of the files on the user's disk. The files are uploaded to the storage service
+  /// and then inserted into the cell.
+  #[pb(index = 2)]
+  pub local_file_paths: Vec<String>,
+
+  #[pb(index = 3)]
+  pub removed_file_ids: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct FilesCellChangesetParams {
+  pub cell_id: CellIdParams,
+  pub local_file_paths: Vec<String>,
+  pub removed_file_ids: Vec<String>,
+}
+
+impl TryInto<FilesCellChangesetParams> for FilesCellChangesetPB {
+  type Error = FlowyError;
+
+  fn try_into(self) -> Result<FilesCellChangesetParams, Self::Error> {
+    let cell_id: CellIdParams = self.cell_id.try_into()?;
+    Ok(FilesCellChangesetParams {
+      cell_id,
+      local_file_paths: self
+        .local_file_paths
+        .into_iter()
+        .filter(|path| !path.is_empty())
+        .collect(),
+      removed_file_ids: self.removed_file_ids,
+    })
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct FilesTypeOptionPB {
+  #[pb(index = 1)]
+  pub config: String,
+}
+
+impl From<FilesTypeOption> for FilesTypeOptionPB {
+  fn from(_data: FilesTypeOption) -> Self {
+    Self {
+      config: "".to_string(),
+    }
+  }
+}
+
+impl From<FilesTypeOptionPB> for FilesTypeOption {
+  fn from(_data: FilesTypeOptionPB) -> Self {
+    Self
+  }
+}
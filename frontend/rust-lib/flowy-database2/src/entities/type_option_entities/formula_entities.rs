@@ -0,0 +1,66 @@
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+
+use crate::services::field::{FormulaCellData, FormulaTypeOption};
+
+#[derive(Debug, Clone, PartialEq, Eq, ProtoBuf_Enum)]
+#[repr(u8)]
+#[derive(Default)]
+pub enum FormulaResultTypePB {
+  #[default]
+  Text = 0,
+  Number = 1,
+  Date = 2,
+  Boolean = 3,
+}
+
+impl From<i64> for FormulaResultTypePB {
+  fn from(value: i64) -> Self {
+    match value {
+      1 => FormulaResultTypePB::Number,
+      2 => FormulaResultTypePB::Date,
+      3 => FormulaResultTypePB::Boolean,
+      _ => FormulaResultTypePB::Text,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct FormulaCellDataPB {
+  #[pb(index = 1)]
+  pub result_type: FormulaResultTypePB,
+
+  /// The formatted result of the formula.
+  #[pb(index = 2)]
+  pub value: String,
+}
+
+impl From<FormulaCellData> for FormulaCellDataPB {
+  fn from(data: FormulaCellData) -> Self {
+    Self {
+      result_type: data.value.result_type(),
+      value: data.value.to_string(),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct FormulaTypeOptionPB {
+  #[pb(index = 1)]
+  pub formula: String,
+}
+
+impl From<FormulaTypeOption> for FormulaTypeOptionPB {
+  fn from(value: FormulaTypeOption) -> Self {
+    FormulaTypeOptionPB {
+      formula: value.formula,
+    }
+  }
+}
+
+impl From<FormulaTypeOptionPB> for FormulaTypeOption {
+  fn from(value: FormulaTypeOptionPB) -> Self {
+    FormulaTypeOption {
+      formula: value.formula,
+    }
+  }
+}
@@ -0,0 +1,62 @@
+use flowy_derive::ProtoBuf;
+
+use crate::entities::CalculationType;
+use crate::services::field::{LookupCellData, LookupTypeOption};
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct LookupCellDataPB {
+  #[pb(index = 1)]
+  pub value: String,
+}
+
+impl From<LookupCellData> for LookupCellDataPB {
+  fn from(data: LookupCellData) -> Self {
+    Self { value: data.0 }
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct LookupTypeOptionPB {
+  /// The id of the database whose rows are aggregated.
+  #[pb(index = 1)]
+  pub database_id: String,
+
+  /// The field of the current database that is used to find the matching rows.
+  #[pb(index = 2)]
+  pub key_field_id: String,
+
+  /// The field of the related database that is compared with the key field.
+  #[pb(index = 3)]
+  pub related_key_field_id: String,
+
+  /// The field of the related database whose cells are aggregated.
+  #[pb(index = 4)]
+  pub target_field_id: String,
+
+  #[pb(index = 5)]
+  pub calculation_type: CalculationType,
+}
+
+impl From<LookupTypeOption> for LookupTypeOptionPB {
+  fn from(value: LookupTypeOption) -> Self {
+    LookupTypeOptionPB {
+      database_id: value.database_id,
+      key_field_id: value.key_field_id,
+      related_key_field_id: value.related_key_field_id,
+      target_field_id: value.target_field_id,
+      calculation_type: value.calculation_type.into(),
+    }
+  }
+}
+
+impl From<LookupTypeOptionPB> for LookupTypeOption {
+  fn from(value: LookupTypeOptionPB) -> Self {
+    LookupTypeOption {
+      database_id: value.database_id,
+      key_field_id: value.key_field_id,
+      related_key_field_id: value.related_key_field_id,
+      target_field_id: value.target_field_id,
+      calculation_type: value.calculation_type.value(),
+    }
+  }
+}
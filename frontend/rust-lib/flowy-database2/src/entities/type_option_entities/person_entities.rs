@@ -0,0 +1,115 @@
+use flowy_derive::ProtoBuf;
+use flowy_error::FlowyError;
+
+use crate::entities::{CellIdPB, CellIdParams};
+use crate::services::field::{Person, PersonTypeOption};
+
+/// A workspace member. The `id` is the email of the member, see [Person].
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct PersonPB {
+  #[pb(index = 1)]
+  pub id: String,
+
+  #[pb(index = 2)]
+  pub name: String,
+
+  #[pb(index = 3)]
+  pub email: String,
+}
+
+impl From<Person> for PersonPB {
+  fn from(person: Person) -> Self {
+    Self {
+      id: person.id,
+      name: person.name,
+      email: person.email,
+    }
+  }
+}
+
+impl From<PersonPB> for Person {
+  fn from(person: PersonPB) -> Self {
+    Self {
+      id: person.id,
+      name: person.name,
+      email: person.email,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct PersonCellDataPB {
+  #[pb(index = 1)]
+  pub user_ids: Vec<String>,
+
+  /// The persons assigned to the cell, in the same order as the `user_ids`.
+  #[pb(index = 2)]
+  pub persons: Vec<PersonPB>,
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct PersonCellChangesetPB {
+  #[pb(index = 1)]
+  pub cell_id: CellIdPB,
+
+  /// The members of the workspace, as returned by `GetWorkspaceMember`, that will be assigned to
+  /// the cell.
+  #[pb(index = 2)]
+  pub inserted_persons: Vec<PersonPB>,
+
+  #[pb(index = 3)]
+  pub removed_user_ids: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct PersonCellChangesetParams {
+  pub cell_id: CellIdParams,
+  pub inserted_persons: Vec<Person>,
+  pub removed_user_ids: Vec<String>,
+}
+
+impl TryInto<PersonCellChangesetParams> for PersonCellChangesetPB {
+  type Error = FlowyError;
+
+  fn try_into(self) -> Result<PersonCellChangesetParams, Self::Error> {
+    let cell_id: CellIdParams = self.cell_id.try_into()?;
+    Ok(PersonCellChangesetParams {
+      cell_id,
+      inserted_persons: self
+        .inserted_persons
+        .into_iter()
+        .filter(|person| !person.id.is_empty())
+        .map(Person::from)
+        .collect(),
+      removed_user_ids: self.removed_user_ids,
+    })
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct PersonTypeOptionPB {
+  /// Only one person can be assigned to the cell if it's true.
+  #[pb(index = 1)]
+  pub is_single: bool,
+
+  #[pb(index = 2)]
+  pub persons: Vec<PersonPB>,
+}
+
+impl From<PersonTypeOption> for PersonTypeOptionPB {
+  fn from(value: PersonTypeOption) -> Self {
+    PersonTypeOptionPB {
+      is_single: value.is_single,
+      persons: value.persons.into_iter().map(PersonPB::from).collect(),
+    }
+  }
+}
+
+impl From<PersonTypeOptionPB> for PersonTypeOption {
+  fn from(value: PersonTypeOptionPB) -> Self {
+    PersonTypeOption {
+      is_single: value.is_single,
+      persons: value.persons.into_iter().map(Person::from).collect(),
+    }
+  }
+}
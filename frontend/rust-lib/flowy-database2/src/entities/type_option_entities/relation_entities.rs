@@ -0,0 +1,122 @@
+use collab_database::rows::RowId;
+
+use flowy_derive::ProtoBuf;
+use flowy_error::{ErrorCode, FlowyError};
+
+use crate::entities::parser::NotEmptyStr;
+use crate::entities::{CellIdPB, CellIdParams};
+use crate::services::field::{RelationCellData, RelationTypeOption};
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct RelationCellDataPB {
+  #[pb(index = 1)]
+  pub row_ids: Vec<String>,
+}
+
+impl From<RelationCellData> for RelationCellDataPB {
+  fn from(data: RelationCellData) -> Self {
+    Self {
+      row_ids: data.row_ids.into_iter().map(String::from).collect(),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct RelationCellChangesetPB {
+  #[pb(index = 1)]
+  pub cell_id: CellIdPB,
+
+  #[pb(index = 2)]
+  pub inserted_row_ids: Vec<String>,
+
+  #[pb(index = 3)]
+  pub removed_row_ids: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct RelationCellChangesetParams {
+  pub cell_id: CellIdParams,
+  pub inserted_row_ids: Vec<RowId>,
+  pub removed_row_ids: Vec<RowId>,
+}
+
+impl TryInto<RelationCellChangesetParams> for RelationCellChangesetPB {
+  type Error = FlowyError;
+
+  fn try_into(self) -> Result<RelationCellChangesetParams, Self::Error> {
+    let cell_id: CellIdParams = self.cell_id.try_into()?;
+    Ok(RelationCellChangesetParams {
+      cell_id,
+      inserted_row_ids: self.inserted_row_ids.into_iter().map(RowId::from).collect(),
+      removed_row_ids: self.removed_row_ids.into_iter().map(RowId::from).collect(),
+    })
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct RelationTypeOptionPB {
+  /// The id of the database whose rows can be linked to this field.
+  #[pb(index = 1)]
+  pub database_id: String,
+}
+
+impl From<RelationTypeOption> for RelationTypeOptionPB {
+  fn from(value: RelationTypeOption) -> Self {
+    RelationTypeOptionPB {
+      database_id: value.database_id,
+    }
+  }
+}
+
+impl From<RelationTypeOptionPB> for RelationTypeOption {
+  fn from(value: RelationTypeOptionPB) -> Self {
+    RelationTypeOption {
+      database_id: value.database_id,
+    }
+  }
+}
+
+/// Used to look up the rows of the related database. If `row_ids` is empty, all the rows of
+/// the related database will be returned.
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct GetRelatedRowDataPB {
+  #[pb(index = 1)]
+  pub database_id: String,
+
+  #[pb(index = 2)]
+  pub row_ids: Vec<String>,
+}
+
+pub struct GetRelatedRowDataParams {
+  pub database_id: String,
+  pub row_ids: Vec<RowId>,
+}
+
+impl TryInto<GetRelatedRowDataParams> for GetRelatedRowDataPB {
+  type Error = FlowyError;
+
+  fn try_into(self) -> Result<GetRelatedRowDataParams, Self::Error> {
+    let database_id =
+      NotEmptyStr::parse(self.database_id).map_err(|_| ErrorCode::DatabaseIdIsEmpty)?;
+    Ok(GetRelatedRowDataParams {
+      database_id: database_id.0,
+      row_ids: self.row_ids.into_iter().map(RowId::from).collect(),
+    })
+  }
+}
+
+/// The id and the content of the primary cell of a row in the related database.
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct RelatedRowDataPB {
+  #[pb(index = 1)]
+  pub row_id: String,
+
+  #[pb(index = 2)]
+  pub name: String,
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct RepeatedRelatedRowDataPB {
+  #[pb(index = 1)]
+  pub rows: Vec<RelatedRowDataPB>,
+}
@@ -13,7 +13,7 @@ use crate::manager::DatabaseManager;
 use crate::services::cell::CellBuilder;
 use crate::services::field::checklist_type_option::ChecklistCellChangeset;
 use crate::services::field::{
-  type_option_data_from_pb, DateCellChangeset, SelectOptionCellChangeset,
+  type_option_data_from_pb, DateCellChangeset, RelationCellChangeset, SelectOptionCellChangeset,
 };
 use crate::services::field_settings::FieldSettingsChangesetParams;
 use crate::services::group::GroupChangeset;
@@ -114,7 +114,7 @@ pub(crate) async fn update_database_setting_handler(
 
   if let Some(layout_type) = params.layout_type {
     database_editor
-      .update_view_layout(&params.view_id, layout_type.into())
+      .update_view_layout(&params.view_id, layout_type)
       .await?;
   }
   Ok(())
@@ -164,10 +164,11 @@ pub(crate) async fn get_fields_handler(
   let manager = upgrade_manager(manager)?;
   let params: GetFieldParams = data.into_inner().try_into()?;
   let database_editor = manager.get_database_with_view_id(&params.view_id).await?;
+  let all_fields = database_editor.get_fields(&params.view_id, None);
   let fields = database_editor
     .get_fields(&params.view_id, params.field_ids)
     .into_iter()
-    .map(FieldPB::new)
+    .map(|field| FieldPB::new_with_fields(field, &all_fields))
     .collect::<Vec<FieldPB>>()
     .into();
   data_result_ok(fields)
@@ -289,6 +290,44 @@ pub(crate) async fn duplicate_field_handler(
   Ok(())
 }
 
+#[tracing::instrument(level = "trace", skip(data, manager), err)]
+pub(crate) async fn get_field_validation_handler(
+  data: AFPluginData<GetFieldValidationPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<FieldValidationPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params: FieldIdParams = data.into_inner().try_into()?;
+  let database_editor = manager.get_database_with_view_id(&params.view_id).await?;
+  let validation = database_editor.get_field_validation(&params.field_id)?;
+  data_result_ok(validation.into())
+}
+
+#[tracing::instrument(level = "trace", skip(data, manager), err)]
+pub(crate) async fn update_field_validation_handler(
+  data: AFPluginData<UpdateFieldValidationPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> Result<(), FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params: UpdateFieldValidationParams = data.into_inner().try_into()?;
+  let database_editor = manager.get_database_with_view_id(&params.view_id).await?;
+  database_editor
+    .update_field_validation(&params.field_id, params.validation)
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "trace", skip(data, manager), err)]
+pub(crate) async fn get_validation_violations_handler(
+  data: AFPluginData<DatabaseViewIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<RepeatedFieldValidationErrorPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let view_id = data.into_inner().value;
+  let database_editor = manager.get_database_with_view_id(&view_id).await?;
+  let errors = database_editor.get_validation_violations(&view_id).await;
+  data_result_ok(RepeatedFieldValidationErrorPB { items: errors })
+}
+
 /// Create a field and save it. Returns the [FieldPB] in the current view.
 #[tracing::instrument(level = "trace", skip(data, manager), err)]
 pub(crate) async fn create_field_handler(
@@ -422,7 +461,11 @@ pub(crate) async fn create_row_handler(
     .await?
   {
     None => Err(FlowyError::internal().with_context("Create row fail")),
-    Some(row) => data_result_ok(RowMetaPB::from(row)),
+    Some(row) => {
+      let mut row_meta = RowMetaPB::from(&row);
+      row_meta.validation_errors = database_editor.get_row_validation_errors(&row.row.id);
+      data_result_ok(row_meta)
+    },
   }
 }
 
@@ -608,6 +651,85 @@ pub(crate) async fn update_date_cell_handler(
   Ok(())
 }
 
+#[tracing::instrument(level = "trace", skip_all, err)]
+pub(crate) async fn update_relation_cell_handler(
+  data: AFPluginData<RelationCellChangesetPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> Result<(), FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params: RelationCellChangesetParams = data.into_inner().try_into()?;
+  let cell_id = params.cell_id;
+  let changeset = RelationCellChangeset {
+    inserted_row_ids: params.inserted_row_ids,
+    removed_row_ids: params.removed_row_ids,
+  };
+
+  let database_editor = manager.get_database_with_view_id(&cell_id.view_id).await?;
+  database_editor
+    .update_cell_with_changeset(
+      &cell_id.view_id,
+      cell_id.row_id,
+      &cell_id.field_id,
+      changeset,
+    )
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "trace", skip_all, err)]
+pub(crate) async fn update_person_cell_handler(
+  data: AFPluginData<PersonCellChangesetPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> Result<(), FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params: PersonCellChangesetParams = data.into_inner().try_into()?;
+  let cell_id = params.cell_id;
+  let database_editor = manager.get_database_with_view_id(&cell_id.view_id).await?;
+  database_editor
+    .update_person_cell(
+      &cell_id.view_id,
+      &cell_id.field_id,
+      cell_id.row_id,
+      params.inserted_persons,
+      params.removed_user_ids,
+    )
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "trace", skip_all, err)]
+pub(crate) async fn update_files_cell_handler(
+  data: AFPluginData<FilesCellChangesetPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> Result<(), FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params: FilesCellChangesetParams = data.into_inner().try_into()?;
+  let cell_id = params.cell_id;
+  let database_editor = manager.get_database_with_view_id(&cell_id.view_id).await?;
+  database_editor
+    .update_files_cell(
+      &cell_id.view_id,
+      &cell_id.field_id,
+      cell_id.row_id,
+      params.local_file_paths,
+      params.removed_file_ids,
+    )
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "trace", skip_all, err)]
+pub(crate) async fn get_related_row_datas_handler(
+  data: AFPluginData<GetRelatedRowDataPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<RepeatedRelatedRowDataPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params: GetRelatedRowDataParams = data.into_inner().try_into()?;
+  let database_editor = manager.get_database(&params.database_id).await?;
+  let rows = database_editor.get_related_rows(&params.row_ids).await?;
+  data_result_ok(RepeatedRelatedRowDataPB { rows })
+}
+
 #[tracing::instrument(level = "trace", skip_all, err)]
 pub(crate) async fn get_groups_handler(
   data: AFPluginData<DatabaseViewIdPB>,
@@ -643,7 +765,7 @@ pub(crate) async fn set_group_by_field_handler(
   let params: GroupByFieldParams = data.into_inner().try_into()?;
   let database_editor = manager.get_database_with_view_id(&params.view_id).await?;
   database_editor
-    .set_group_by_field(&params.view_id, &params.field_id)
+    .set_group_by_field(&params.view_id, &params.field_id, params.setting_content)
     .await?;
   Ok(())
 }
@@ -699,11 +821,38 @@ pub(crate) async fn move_group_row_handler(
       &params.to_group_id,
       params.from_row_id,
       params.to_row_id,
+      params.to_swimlane_id,
     )
     .await?;
   Ok(())
 }
 
+#[tracing::instrument(level = "debug", skip(data, manager), err)]
+pub(crate) async fn set_swimlane_field_handler(
+  data: AFPluginData<SetSwimlaneFieldPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> FlowyResult<()> {
+  let manager = upgrade_manager(manager)?;
+  let params: SetSwimlaneFieldParams = data.into_inner().try_into()?;
+  let database_editor = manager.get_database_with_view_id(&params.view_id).await?;
+  database_editor
+    .set_swimlane_field(&params.view_id, params.field_id.as_deref())
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, manager), err)]
+pub(crate) async fn get_swimlanes_handler(
+  data: AFPluginData<DatabaseViewIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<RepeatedSwimlanePB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.into_inner();
+  let database_editor = manager.get_database_with_view_id(params.as_ref()).await?;
+  let swimlanes = database_editor.get_swimlanes(params.as_ref()).await?;
+  data_result_ok(swimlanes)
+}
+
 #[tracing::instrument(level = "debug", skip(manager), err)]
 pub(crate) async fn create_group_handler(
   data: AFPluginData<CreateGroupPayloadPB>,
@@ -821,17 +970,74 @@ pub(crate) async fn move_calendar_event_handler(
   let manager = upgrade_manager(manager)?;
   let data = data.into_inner();
   let cell_id: CellIdParams = data.cell_path.try_into()?;
-  let cell_changeset = DateCellChangeset {
-    date: Some(data.timestamp),
-    ..Default::default()
-  };
   let database_editor = manager.get_database_with_view_id(&cell_id.view_id).await?;
   database_editor
-    .update_cell_with_changeset(
+    .move_calendar_event(
       &cell_id.view_id,
       cell_id.row_id,
       &cell_id.field_id,
-      cell_changeset,
+      data.timestamp,
+    )
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, manager), err)]
+pub(crate) async fn get_timeline_spans_handler(
+  data: AFPluginData<DatabaseViewIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<RepeatedTimelineSpanPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let view_id: DatabaseViewIdPB = data.into_inner();
+  let database_editor = manager.get_database_with_view_id(view_id.as_ref()).await?;
+  let spans = database_editor
+    .get_all_timeline_spans(view_id.as_ref())
+    .await;
+  data_result_ok(RepeatedTimelineSpanPB { items: spans })
+}
+
+#[tracing::instrument(level = "debug", skip(data, manager), err)]
+pub(crate) async fn get_no_date_timeline_rows_handler(
+  data: AFPluginData<DatabaseViewIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<RepeatedNoDateTimelineRowPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let view_id: DatabaseViewIdPB = data.into_inner();
+  let database_editor = manager.get_database_with_view_id(view_id.as_ref()).await?;
+  let rows = database_editor
+    .get_all_no_date_timeline_rows(view_id.as_ref())
+    .await;
+  data_result_ok(RepeatedNoDateTimelineRowPB { items: rows })
+}
+
+#[tracing::instrument(level = "debug", skip(data, manager), err)]
+pub(crate) async fn move_timeline_span_handler(
+  data: AFPluginData<MoveTimelineSpanPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> FlowyResult<()> {
+  let manager = upgrade_manager(manager)?;
+  let params: MoveTimelineSpanParams = data.into_inner().try_into()?;
+  let database_editor = manager.get_database_with_view_id(&params.view_id).await?;
+  database_editor
+    .move_timeline_span(&params.view_id, RowId::from(params.row_id), params.start)
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, manager), err)]
+pub(crate) async fn resize_timeline_span_handler(
+  data: AFPluginData<ResizeTimelineSpanPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> FlowyResult<()> {
+  let manager = upgrade_manager(manager)?;
+  let params: ResizeTimelineSpanParams = data.into_inner().try_into()?;
+  let database_editor = manager.get_database_with_view_id(&params.view_id).await?;
+  database_editor
+    .resize_timeline_span(
+      &params.view_id,
+      RowId::from(params.row_id),
+      params.start,
+      params.end,
     )
     .await?;
   Ok(())
@@ -861,6 +1067,106 @@ pub(crate) async fn export_csv_handler(
   })
 }
 
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn preview_csv_import_handler(
+  data: AFPluginData<CSVImportPreviewPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<CSVImportPreviewPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let content = data.into_inner().content;
+  let preview = manager.preview_csv(content).await?;
+  data_result_ok(CSVImportPreviewPB::from(preview))
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn export_csv_to_file_handler(
+  data: AFPluginData<ExportCSVToFilePayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> Result<(), FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.into_inner();
+  manager
+    .export_csv_to_file(&params.view_id, params.format.into(), params.file_path)
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn cancel_csv_import_handler(
+  data: AFPluginData<DatabaseViewIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> Result<(), FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let view_id = data.into_inner().value;
+  manager.cancel_csv_import(&view_id);
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn export_json_handler(
+  data: AFPluginData<DatabaseViewIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<DatabaseExportDataPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let view_id = data.into_inner().value;
+  let database = manager.get_database_with_view_id(&view_id).await?;
+  let data = database.export_json().await?;
+  data_result_ok(DatabaseExportDataPB {
+    export_type: DatabaseExportDataType::JSON,
+    data,
+  })
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn export_markdown_handler(
+  data: AFPluginData<ExportViewPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<DatabaseExportDataPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.into_inner();
+  let database = manager.get_database_with_view_id(&params.view_id).await?;
+  let data = database
+    .export_markdown(&params.view_id, params.include_group_headers)
+    .await?;
+  data_result_ok(DatabaseExportDataPB {
+    export_type: DatabaseExportDataType::Markdown,
+    data,
+  })
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn export_view_csv_handler(
+  data: AFPluginData<ExportViewCSVPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<DatabaseExportDataPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.into_inner();
+  let database = manager.get_database_with_view_id(&params.view_id).await?;
+  let data = database
+    .export_view_csv(
+      &params.view_id,
+      params.format.into(),
+      params.include_group_headers,
+    )
+    .await?;
+  data_result_ok(DatabaseExportDataPB {
+    export_type: DatabaseExportDataType::CSV,
+    data,
+  })
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn export_xlsx_handler(
+  data: AFPluginData<ExportXLSXPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> Result<(), FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.into_inner();
+  let database = manager.get_database_with_view_id(&params.view_id).await?;
+  database.export_xlsx_to_file(params.file_path).await?;
+  Ok(())
+}
+
 #[tracing::instrument(level = "debug", skip_all, err)]
 pub(crate) async fn get_snapshots_handler(
   data: AFPluginData<DatabaseViewIdPB>,
@@ -31,6 +31,9 @@ pub fn init(database_manager: Weak<DatabaseManager>) -> AFPlugin {
         .event(DatabaseEvent::DuplicateField, duplicate_field_handler)
         .event(DatabaseEvent::MoveField, move_field_handler)
         .event(DatabaseEvent::CreateField, create_field_handler)
+        .event(DatabaseEvent::GetFieldValidation, get_field_validation_handler)
+        .event(DatabaseEvent::UpdateFieldValidation, update_field_validation_handler)
+        .event(DatabaseEvent::GetValidationViolations, get_validation_violations_handler)
         // Row
         .event(DatabaseEvent::CreateRow, create_row_handler)
         .event(DatabaseEvent::GetRow, get_row_handler)
@@ -52,6 +55,13 @@ pub fn init(database_manager: Weak<DatabaseManager>) -> AFPlugin {
         .event(DatabaseEvent::UpdateChecklistCell, update_checklist_cell_handler)
         // Date
         .event(DatabaseEvent::UpdateDateCell, update_date_cell_handler)
+        // Relation
+        .event(DatabaseEvent::UpdateRelationCell, update_relation_cell_handler)
+        .event(DatabaseEvent::GetRelatedRowDatas, get_related_row_datas_handler)
+        // Person
+        .event(DatabaseEvent::UpdatePersonCell, update_person_cell_handler)
+        // Files
+        .event(DatabaseEvent::UpdateFilesCell, update_files_cell_handler)
         // Group
         .event(DatabaseEvent::SetGroupByField, set_group_by_field_handler)
         .event(DatabaseEvent::MoveGroup, move_group_handler)
@@ -61,6 +71,8 @@ pub fn init(database_manager: Weak<DatabaseManager>) -> AFPlugin {
         .event(DatabaseEvent::UpdateGroup, update_group_handler)
         .event(DatabaseEvent::CreateGroup, create_group_handler)
         .event(DatabaseEvent::DeleteGroup, delete_group_handler)
+        .event(DatabaseEvent::SetSwimlaneField, set_swimlane_field_handler)
+        .event(DatabaseEvent::GetSwimlanes, get_swimlanes_handler)
         // Database
         .event(DatabaseEvent::GetDatabases, get_databases_handler)
         // Calendar
@@ -68,12 +80,24 @@ pub fn init(database_manager: Weak<DatabaseManager>) -> AFPlugin {
         .event(DatabaseEvent::GetNoDateCalendarEvents, get_no_date_calendar_events_handler)
         .event(DatabaseEvent::GetCalendarEvent, get_calendar_event_handler)
         .event(DatabaseEvent::MoveCalendarEvent, move_calendar_event_handler)
+        // Timeline
+        .event(DatabaseEvent::GetAllTimelineSpans, get_timeline_spans_handler)
+        .event(DatabaseEvent::GetNoDateTimelineRows, get_no_date_timeline_rows_handler)
+        .event(DatabaseEvent::MoveTimelineSpan, move_timeline_span_handler)
+        .event(DatabaseEvent::ResizeTimelineSpan, resize_timeline_span_handler)
         // Layout setting
         .event(DatabaseEvent::SetLayoutSetting, set_layout_setting_handler)
         .event(DatabaseEvent::GetLayoutSetting, get_layout_setting_handler)
         .event(DatabaseEvent::CreateDatabaseView, create_database_view)
         // Export
         .event(DatabaseEvent::ExportCSV, export_csv_handler)
+        .event(DatabaseEvent::PreviewCSVImport, preview_csv_import_handler)
+        .event(DatabaseEvent::ExportCSVToFile, export_csv_to_file_handler)
+        .event(DatabaseEvent::CancelCSVImport, cancel_csv_import_handler)
+        .event(DatabaseEvent::ExportJSON, export_json_handler)
+        .event(DatabaseEvent::ExportMarkdown, export_markdown_handler)
+        .event(DatabaseEvent::ExportXLSX, export_xlsx_handler)
+        .event(DatabaseEvent::ExportViewCSV, export_view_csv_handler)
         .event(DatabaseEvent::GetDatabaseSnapshots, get_snapshots_handler)
         // Field settings
         .event(DatabaseEvent::GetFieldSettings, get_field_settings_handler)
@@ -185,6 +209,21 @@ pub enum DatabaseEvent {
   #[event(input = "DatabaseViewIdPB", output = "FieldPB")]
   GetPrimaryField = 25,
 
+  /// Returns the validation rules of the field. The field has no rule if it has never been
+  /// validated.
+  #[event(input = "GetFieldValidationPayloadPB", output = "FieldValidationPB")]
+  GetFieldValidation = 26,
+
+  /// Replaces the validation rules of the field. The existing cells are not checked, use
+  /// [GetValidationViolations] to find the ones that break the new rules.
+  #[event(input = "UpdateFieldValidationPayloadPB")]
+  UpdateFieldValidation = 27,
+
+  /// Returns the cells of the view that break the validation rules of their field, including the
+  /// rows that were created without a required cell.
+  #[event(input = "DatabaseViewIdPB", output = "RepeatedFieldValidationErrorPB")]
+  GetValidationViolations = 28,
+
   /// [CreateSelectOption] event is used to create a new select option. Returns a [SelectOptionPB] if
   /// there are no errors.
   #[event(input = "CreateSelectOptionPayloadPB", output = "SelectOptionPB")]
@@ -208,6 +247,8 @@ pub enum DatabaseEvent {
   #[event(input = "RepeatedSelectOptionPayload")]
   DeleteSelectOption = 33,
 
+  /// Creates the row. The row that misses a required cell is created anyway, the broken rules are
+  /// returned in the `validation_errors` of the [RowMetaPB].
   #[event(input = "CreateRowPayloadPB", output = "RowMetaPB")]
   CreateRow = 50,
 
@@ -288,6 +329,15 @@ pub enum DatabaseEvent {
   #[event(input = "DeleteGroupPayloadPB")]
   DeleteGroup = 115,
 
+  /// [SetSwimlaneField] event is used to sub-group a board by a second field. Each group of the
+  /// swimlane field is displayed as a swimlane across the groups of the board.
+  #[event(input = "SetSwimlaneFieldPayloadPB")]
+  SetSwimlaneField = 116,
+
+  /// Returns the swimlanes of the board. It's empty if the board has no swimlanes.
+  #[event(input = "DatabaseViewIdPB", output = "RepeatedSwimlanePB")]
+  GetSwimlanes = 117,
+
   /// Returns all the databases
   #[event(output = "RepeatedDatabaseDescriptionPB")]
   GetDatabases = 120,
@@ -316,9 +366,56 @@ pub enum DatabaseEvent {
   #[event(input = "CreateDatabaseViewPayloadPB")]
   CreateDatabaseView = 130,
 
+  /// Returns the spans of the timeline. A view becomes a timeline by updating its layout type to
+  /// [DatabaseLayoutPB::Timeline], see [DatabaseEvent::UpdateDatabaseSetting].
+  #[event(input = "DatabaseViewIdPB", output = "RepeatedTimelineSpanPB")]
+  GetAllTimelineSpans = 131,
+
+  /// Returns the rows of the timeline that have no start date.
+  #[event(input = "DatabaseViewIdPB", output = "RepeatedNoDateTimelineRowPB")]
+  GetNoDateTimelineRows = 132,
+
+  #[event(input = "MoveTimelineSpanPB")]
+  MoveTimelineSpan = 133,
+
+  #[event(input = "ResizeTimelineSpanPB")]
+  ResizeTimelineSpan = 134,
+
   #[event(input = "DatabaseViewIdPB", output = "DatabaseExportDataPB")]
   ExportCSV = 141,
 
+  /// Returns the columns of the CSV with their inferred field types. The chosen field types are
+  /// passed to the import through the import meta, see `CSV_FIELD_TYPES_META_KEY`.
+  #[event(input = "CSVImportPreviewPayloadPB", output = "CSVImportPreviewPB")]
+  PreviewCSVImport = 142,
+
+  /// Exports the database to a CSV file. Unlike [DatabaseEvent::ExportCSV], the CSV isn't
+  /// returned, so it works for databases that are too large to be kept in memory.
+  #[event(input = "ExportCSVToFilePayloadPB")]
+  ExportCSVToFile = 143,
+
+  /// Cancels the import of the CSV file into the view. The rows that were already imported are
+  /// removed along with the view.
+  #[event(input = "DatabaseViewIdPB")]
+  CancelCSVImport = 144,
+
+  /// Exports the database to JSON, keeping the types of the cells.
+  #[event(input = "DatabaseViewIdPB", output = "DatabaseExportDataPB")]
+  ExportJSON = 145,
+
+  /// Exports the view to a Markdown table, with the filters, sorts and hidden fields of the view
+  /// applied.
+  #[event(input = "ExportViewPayloadPB", output = "DatabaseExportDataPB")]
+  ExportMarkdown = 146,
+
+  /// Exports the database to an XLSX file.
+  #[event(input = "ExportXLSXPayloadPB")]
+  ExportXLSX = 147,
+
+  /// Exports the view to CSV, with the filters, sorts and hidden fields of the view applied.
+  #[event(input = "ExportViewCSVPayloadPB", output = "DatabaseExportDataPB")]
+  ExportViewCSV = 148,
+
   /// Returns all the snapshots of the database view.
   #[event(input = "DatabaseViewIdPB", output = "RepeatedDatabaseSnapshotPB")]
   GetDatabaseSnapshots = 150,
@@ -342,4 +439,24 @@ pub enum DatabaseEvent {
 
   #[event(input = "RemoveCalculationChangesetPB")]
   RemoveCalculation = 165,
+
+  /// [UpdateRelationCell] event is used to link or unlink rows of the related database.
+  /// [RelationCellChangesetPB] contains the row ids that will be inserted or removed.
+  #[event(input = "RelationCellChangesetPB")]
+  UpdateRelationCell = 170,
+
+  /// Returns the name, the content of the primary cell, of the rows in the related database.
+  /// It's used to display the linked rows of a relation cell.
+  #[event(input = "GetRelatedRowDataPB", output = "RepeatedRelatedRowDataPB")]
+  GetRelatedRowDatas = 171,
+
+  /// [UpdatePersonCell] event is used to assign or unassign workspace members to a person cell.
+  /// The inserted persons are also saved in the field's type option.
+  #[event(input = "PersonCellChangesetPB")]
+  UpdatePersonCell = 172,
+
+  /// [UpdateFilesCell] event is used to attach files to a files cell or remove them from it.
+  /// The files on the user's disk are uploaded to the storage service before they are attached.
+  #[event(input = "FilesCellChangesetPB")]
+  UpdateFilesCell = 173,
 }
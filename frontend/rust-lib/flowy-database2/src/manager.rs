@@ -1,51 +1,78 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Weak};
 
 use collab::core::collab::{CollabDocState, MutexCollab};
 use collab_database::blocks::BlockEvent;
 use collab_database::database::{DatabaseData, MutexDatabase};
 use collab_database::error::DatabaseError;
+use collab_database::fields::Field;
 use collab_database::user::{
   CollabDocStateByOid, CollabFuture, DatabaseCollabService, WorkspaceDatabase,
 };
 use collab_database::views::{CreateDatabaseParams, CreateViewParams, DatabaseLayout};
 use collab_entity::CollabType;
 use collab_plugins::local_storage::kv::KVTransactionDB;
+use dashmap::DashMap;
 use futures::executor::block_on;
 use lru::LruCache;
-use tokio::sync::{Mutex, RwLock};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{broadcast, Mutex, RwLock};
 use tracing::{event, instrument, trace};
 
 use collab_integrate::collab_builder::{AppFlowyCollabBuilder, CollabBuilderConfig};
 use collab_integrate::{CollabKVAction, CollabKVDB, CollabPersistenceConfig};
 use flowy_database_pub::cloud::DatabaseCloudService;
-use flowy_error::{internal_error, FlowyError, FlowyResult};
+use flowy_error::{internal_error, ErrorCode, FlowyError, FlowyResult};
+use flowy_sqlite::DBConnection;
+use flowy_storage::{FileStoragePlan, ObjectStorageService};
 use lib_dispatch::prelude::af_spawn;
+use lib_infra::future::FutureResult;
 use lib_infra::priority_task::TaskDispatcher;
 
 use crate::entities::{
-  DatabaseDescriptionPB, DatabaseLayoutPB, DatabaseSnapshotPB, DidFetchRowPB,
-  RepeatedDatabaseDescriptionPB,
+  CSVImportProgressPB, DatabaseDescriptionPB, DatabaseLayoutPB, DatabaseSnapshotPB, DidFetchRowPB,
+  FieldType, RepeatedDatabaseDescriptionPB,
 };
 use crate::notification::{send_notification, DatabaseNotification};
-use crate::services::database::DatabaseEditor;
-use crate::services::database_view::DatabaseLayoutDepsResolver;
+use crate::services::database::{DatabaseDataChanged, DatabaseEditor, DatabaseFileStorage};
+use crate::services::database_view::{duration_until_next_day, DatabaseLayoutDepsResolver};
+use crate::services::field::LookupTypeOption;
 use crate::services::field_settings::default_field_settings_by_layout_map;
-use crate::services::share::csv::{CSVFormat, CSVImporter, ImportResult};
+use crate::services::share::csv::{
+  CSVFileReader, CSVFormat, CSVImportPreview, CSVImporter, ImportResult,
+};
+
+/// The number of rows that are inserted into the database at a time when importing a CSV file.
+const CSV_IMPORT_BATCH_SIZE: usize = 1000;
 
 pub trait DatabaseUser: Send + Sync {
   fn user_id(&self) -> Result<i64, FlowyError>;
+  fn workspace_id(&self) -> Result<String, FlowyError>;
+  /// The email of the current user. The person cells identify the workspace members by email.
+  fn user_email(&self) -> Result<String, FlowyError>;
+  /// The emails of the members of the current workspace, including the current user. Only the
+  /// members can be assigned to the person cells.
+  fn workspace_member_emails(&self) -> FutureResult<Vec<String>, FlowyError>;
+  /// The id of the device. The auto numbers allocated by this device are resolved by it.
+  fn device_id(&self) -> Result<String, FlowyError>;
   fn collab_db(&self, uid: i64) -> Result<Weak<CollabKVDB>, FlowyError>;
+  fn sqlite_connection(&self, uid: i64) -> Result<DBConnection, FlowyError>;
 }
 
 pub struct DatabaseManager {
   user: Arc<dyn DatabaseUser>,
   workspace_database: Arc<RwLock<Option<Arc<WorkspaceDatabase>>>>,
   task_scheduler: Arc<RwLock<TaskDispatcher>>,
-  editors: Mutex<LruCache<String, Arc<DatabaseEditor>>>,
+  editors: Arc<Mutex<LruCache<String, Arc<DatabaseEditor>>>>,
   collab_builder: Arc<AppFlowyCollabBuilder>,
   cloud_service: Arc<dyn DatabaseCloudService>,
+  file_storage: DatabaseFileStorage,
+  /// The cancellation flags of the CSV files being imported, keyed by the id of the view.
+  csv_import_cancellations: Arc<DashMap<String, Arc<AtomicBool>>>,
+  /// Sends the id of the database and the [DatabaseDataChanged] of the opened databases.
+  data_changed_notifier: broadcast::Sender<(String, DatabaseDataChanged)>,
 }
 
 impl DatabaseManager {
@@ -54,18 +81,32 @@ impl DatabaseManager {
     task_scheduler: Arc<RwLock<TaskDispatcher>>,
     collab_builder: Arc<AppFlowyCollabBuilder>,
     cloud_service: Arc<dyn DatabaseCloudService>,
+    storage_service: Weak<dyn ObjectStorageService>,
+    storage_plan: Weak<dyn FileStoragePlan>,
   ) -> Self {
-    let editors = Mutex::new(LruCache::new(NonZeroUsize::new(5).unwrap()));
+    let editors = Arc::new(Mutex::new(LruCache::new(NonZeroUsize::new(5).unwrap())));
+    let file_storage =
+      DatabaseFileStorage::new(database_user.clone(), storage_service, storage_plan);
+    let (data_changed_notifier, _) = broadcast::channel(100);
     Self {
       user: database_user,
+      file_storage,
       workspace_database: Default::default(),
       task_scheduler,
       editors,
       collab_builder,
       cloud_service,
+      csv_import_cancellations: Default::default(),
+      data_changed_notifier,
     }
   }
 
+  /// Subscribe to the data changes of the opened databases. The receiver gets the id of the
+  /// database together with the change.
+  pub fn subscribe_data_changed(&self) -> broadcast::Receiver<(String, DatabaseDataChanged)> {
+    self.data_changed_notifier.subscribe()
+  }
+
   fn is_collab_exist(&self, uid: i64, collab_db: &Weak<CollabKVDB>, object_id: &str) -> bool {
     match collab_db.upgrade() {
       None => false,
@@ -221,7 +262,30 @@ impl DatabaseManager {
     // Subscribe the [BlockEvent]
     subscribe_block_event(&database);
 
-    let editor = Arc::new(DatabaseEditor::new(database, self.task_scheduler.clone()).await?);
+    let editor = Arc::new(
+      DatabaseEditor::new(
+        self.user.clone(),
+        database,
+        self.task_scheduler.clone(),
+        self.file_storage.clone(),
+        self.workspace_database.clone(),
+      )
+      .await?,
+    );
+    // Subscribe the [DatabaseDataChanged] to keep the lookup fields up to date
+    subscribe_data_changed(
+      database_id,
+      &editor,
+      self.data_changed_notifier.clone(),
+      self.editors.clone(),
+      self.workspace_database.clone(),
+    );
+    refresh_formulas_on_day_change(&editor);
+    // The lookup fields are only recomputed while the database is open. The related rows might
+    // have been changed in the meantime.
+    if let Err(err) = refresh_all_lookup_fields(&editor, &self.workspace_database).await {
+      tracing::error!("Failed to refresh the lookup fields: {}", err);
+    }
     self
       .editors
       .lock()
@@ -323,13 +387,134 @@ impl DatabaseManager {
     Ok(result)
   }
 
-  // will implement soon
+  /// Imports the CSV with the field types that the user chose after calling [Self::preview_csv].
+  pub async fn import_csv_with_field_types(
+    &self,
+    view_id: String,
+    content: String,
+    field_types: Vec<FieldType>,
+  ) -> FlowyResult<ImportResult> {
+    let params = tokio::task::spawn_blocking(move || {
+      CSVImporter.import_csv_from_string_with_field_types(view_id, content, field_types)
+    })
+    .await
+    .map_err(internal_error)??;
+    let result = ImportResult {
+      database_id: params.database_id.clone(),
+      view_id: params.view_id.clone(),
+    };
+    self.create_database_with_params(params).await?;
+    Ok(result)
+  }
+
+  /// Returns the columns of the CSV with their inferred field types.
+  pub async fn preview_csv(&self, content: String) -> FlowyResult<CSVImportPreview> {
+    tokio::task::spawn_blocking(move || CSVImporter.preview_csv_from_string(content))
+      .await
+      .map_err(internal_error)?
+  }
+
+  /// Imports the CSV file in batches of rows, so a large file is never loaded into memory at
+  /// once. The progress is sent with [DatabaseNotification::DidUpdateCSVImportProgress] after
+  /// each batch, and the import can be cancelled with [Self::cancel_csv_import].
   pub async fn import_csv_from_file(
     &self,
-    _file_path: String,
-    _format: CSVFormat,
-  ) -> FlowyResult<()> {
-    Ok(())
+    view_id: String,
+    file_path: String,
+    format: CSVFormat,
+  ) -> FlowyResult<ImportResult> {
+    let cancelled = Arc::new(AtomicBool::new(false));
+    self
+      .csv_import_cancellations
+      .insert(view_id.clone(), cancelled.clone());
+    let result = self
+      .import_csv_file_in_batches(&view_id, file_path, format, cancelled)
+      .await;
+    self.csv_import_cancellations.remove(&view_id);
+    result
+  }
+
+  /// Cancels the import of the CSV file into the view. Does nothing if the import was finished.
+  pub fn cancel_csv_import(&self, view_id: &str) {
+    if let Some(cancelled) = self.csv_import_cancellations.get(view_id) {
+      cancelled.store(true, Ordering::SeqCst);
+    }
+  }
+
+  async fn import_csv_file_in_batches(
+    &self,
+    view_id: &str,
+    file_path: String,
+    format: CSVFormat,
+    cancelled: Arc<AtomicBool>,
+  ) -> FlowyResult<ImportResult> {
+    let (mut reader, created_rows) = tokio::task::spawn_blocking(move || {
+      let mut reader = CSVFileReader::open(&file_path, format)?;
+      let created_rows = reader.next_batch(CSV_IMPORT_BATCH_SIZE)?;
+      Ok::<_, FlowyError>((reader, created_rows))
+    })
+    .await
+    .map_err(internal_error)??;
+
+    let params = reader.create_database_params(view_id, created_rows);
+    let result = ImportResult {
+      database_id: params.database_id.clone(),
+      view_id: params.view_id.clone(),
+    };
+    self.create_database_with_params(params).await?;
+    notify_csv_import_progress(view_id, &reader);
+
+    let wdb = self.get_workspace_database().await?;
+    let database = wdb
+      .get_database(&result.database_id)
+      .await
+      .ok_or_else(|| FlowyError::record_not_found().with_context("Can't find the database"))?;
+    loop {
+      if cancelled.load(Ordering::SeqCst) {
+        self.delete_database_view(view_id).await?;
+        return Err(FlowyError::new(
+          ErrorCode::ImportCancelled,
+          "The CSV import was cancelled",
+        ));
+      }
+
+      let (returned_reader, created_rows) = tokio::task::spawn_blocking(move || {
+        let created_rows = reader.next_batch(CSV_IMPORT_BATCH_SIZE);
+        (reader, created_rows)
+      })
+      .await
+      .map_err(internal_error)?;
+      reader = returned_reader;
+      let created_rows = created_rows?;
+      if created_rows.is_empty() {
+        break;
+      }
+
+      {
+        let database_guard = database.lock();
+        for params in created_rows {
+          database_guard.create_row_in_view(view_id, params);
+        }
+      }
+      notify_csv_import_progress(view_id, &reader);
+    }
+
+    // The rows after the sampled rows may contain new select options, or values that make a
+    // column fall back to text.
+    let updated_type_options = reader.updated_type_options();
+    if !updated_type_options.is_empty() {
+      let database_guard = database.lock();
+      for updated in updated_type_options {
+        database_guard
+          .fields
+          .update_field(&updated.field_id, |update| {
+            update
+              .set_field_type(updated.field_type.into())
+              .set_type_option(updated.field_type.into(), Some(updated.type_option_data));
+          });
+      }
+    }
+    Ok(result)
   }
 
   pub async fn export_csv(&self, view_id: &str, style: CSVFormat) -> FlowyResult<String> {
@@ -337,13 +522,28 @@ impl DatabaseManager {
     database.export_csv(style).await
   }
 
+  pub async fn export_csv_to_file(
+    &self,
+    view_id: &str,
+    style: CSVFormat,
+    file_path: String,
+  ) -> FlowyResult<()> {
+    let database = self.get_database_with_view_id(view_id).await?;
+    database.export_csv_to_file(style, file_path).await
+  }
+
+  pub async fn get_database_layout(&self, view_id: &str) -> FlowyResult<DatabaseLayoutPB> {
+    let database = self.get_database_with_view_id(view_id).await?;
+    database.get_layout_type_pb(view_id)
+  }
+
   pub async fn update_database_layout(
     &self,
     view_id: &str,
     layout: DatabaseLayoutPB,
   ) -> FlowyResult<()> {
     let database = self.get_database_with_view_id(view_id).await?;
-    database.update_view_layout(view_id, layout.into()).await
+    database.update_view_layout(view_id, layout).await
   }
 
   pub async fn get_database_snapshots(
@@ -383,6 +583,16 @@ impl DatabaseManager {
   }
 }
 
+fn notify_csv_import_progress(view_id: &str, reader: &CSVFileReader) {
+  send_notification(view_id, DatabaseNotification::DidUpdateCSVImportProgress)
+    .payload(CSVImportProgressPB {
+      view_id: view_id.to_string(),
+      progress: reader.progress(),
+      num_of_rows: reader.num_of_rows() as i64,
+    })
+    .send();
+}
+
 /// Send notification to all clients that are listening to the given object.
 fn subscribe_block_event(database: &Arc<MutexDatabase>) {
   let mut block_event_rx = database.lock().subscribe_block_event();
@@ -404,6 +614,199 @@ fn subscribe_block_event(database: &Arc<MutexDatabase>) {
   });
 }
 
+/// Recomputes the formulas that call `now()` or `today()` right away, because the day might have
+/// rolled over since the database was closed, and then at every local midnight.
+fn refresh_formulas_on_day_change(editor: &Arc<DatabaseEditor>) {
+  let weak_editor = Arc::downgrade(editor);
+  af_spawn(async move {
+    loop {
+      match weak_editor.upgrade() {
+        None => break,
+        Some(editor) => {
+          if let Err(err) = editor.refresh_time_dependent_formula_cells().await {
+            tracing::error!("Failed to refresh the formula cells: {}", err);
+          }
+        },
+      }
+      tokio::time::sleep(duration_until_next_day()).await;
+    }
+  });
+}
+
+/// Recomputes the lookup fields that depend on the given database whenever its data was changed,
+/// and forwards the changes to the subscribers of the [DatabaseManager].
+fn subscribe_data_changed(
+  database_id: &str,
+  editor: &Arc<DatabaseEditor>,
+  changed_notifier: broadcast::Sender<(String, DatabaseDataChanged)>,
+  editors: Arc<Mutex<LruCache<String, Arc<DatabaseEditor>>>>,
+  workspace_database: Arc<RwLock<Option<Arc<WorkspaceDatabase>>>>,
+) {
+  let database_id = database_id.to_string();
+  let weak_editor = Arc::downgrade(editor);
+  let mut data_changed_rx = editor.subscribe_data_changed();
+  af_spawn(async move {
+    loop {
+      let changed = match data_changed_rx.recv().await {
+        Ok(changed) => changed,
+        Err(RecvError::Lagged(_)) => continue,
+        Err(RecvError::Closed) => break,
+      };
+
+      // The lookup cells are written when recomputing the lookup fields, which already recomputes
+      // the lookup fields that aggregate them. Skip them, otherwise lookup fields that refer to
+      // each other would be recomputed forever.
+      let _ = changed_notifier.send((database_id.clone(), changed.clone()));
+
+      if let DatabaseDataChanged::DidUpdateCell { field_id, .. } = &changed {
+        let is_lookup = weak_editor
+          .upgrade()
+          .and_then(|editor| editor.get_field(field_id))
+          .map(|field| FieldType::from(field.field_type).is_lookup())
+          .unwrap_or(false);
+        if is_lookup {
+          continue;
+        }
+      }
+
+      if let Err(err) =
+        refresh_lookup_fields(&database_id, &changed, &editors, &workspace_database).await
+      {
+        tracing::error!("Failed to refresh the lookup fields: {}", err);
+      }
+    }
+  });
+}
+
+/// Recomputes the lookup fields of the opened databases that depend on the changed data.
+///
+/// When the cells of a lookup field were changed, the lookup fields that aggregate it are
+/// recomputed as well. Each lookup field is recomputed at most once, so lookup fields that refer
+/// to each other don't loop forever.
+async fn refresh_lookup_fields(
+  changed_database_id: &str,
+  changed: &DatabaseDataChanged,
+  editors: &Mutex<LruCache<String, Arc<DatabaseEditor>>>,
+  workspace_database: &RwLock<Option<Arc<WorkspaceDatabase>>>,
+) -> FlowyResult<()> {
+  let wdb = workspace_database
+    .read()
+    .await
+    .clone()
+    .ok_or_else(|| FlowyError::internal().with_context("Workspace database not initialized"))?;
+  let opened_editors = editors
+    .lock()
+    .await
+    .iter()
+    .map(|(database_id, editor)| (database_id.clone(), editor.clone()))
+    .collect::<Vec<_>>();
+
+  let mut refreshed_fields = HashSet::new();
+  let mut pending_changes = VecDeque::from([(changed_database_id.to_string(), changed.clone())]);
+  while let Some((changed_database_id, changed)) = pending_changes.pop_front() {
+    for (database_id, editor) in &opened_editors {
+      let mut field_ids_by_database = HashMap::<String, HashSet<String>>::new();
+      for (field, type_option) in editor.get_lookup_fields() {
+        let is_refreshed = refreshed_fields.contains(&(database_id.clone(), field.id.clone()));
+        if !is_refreshed
+          && is_lookup_field_affected(
+            database_id,
+            &field,
+            &type_option,
+            &changed_database_id,
+            &changed,
+          )
+        {
+          field_ids_by_database
+            .entry(type_option.database_id)
+            .or_default()
+            .insert(field.id);
+        }
+      }
+
+      for (related_database_id, field_ids) in field_ids_by_database {
+        let related_database = match wdb.get_database(&related_database_id).await {
+          None => continue,
+          Some(related_database) => related_database,
+        };
+        let changed_field_ids = editor
+          .refresh_lookup_fields(&related_database_id, &related_database, |field, _| {
+            field_ids.contains(&field.id)
+          })
+          .await?;
+        refreshed_fields.extend(
+          field_ids
+            .into_iter()
+            .map(|field_id| (database_id.clone(), field_id)),
+        );
+        pending_changes.extend(changed_field_ids.into_iter().map(|field_id| {
+          (
+            database_id.clone(),
+            DatabaseDataChanged::DidUpdateLookupField { field_id },
+          )
+        }));
+      }
+    }
+  }
+  Ok(())
+}
+
+/// Returns true if the lookup field of the database with `database_id` depends on the `changed`
+/// data of the database with `changed_database_id`.
+fn is_lookup_field_affected(
+  database_id: &str,
+  field: &Field,
+  type_option: &LookupTypeOption,
+  changed_database_id: &str,
+  changed: &DatabaseDataChanged,
+) -> bool {
+  let is_related = type_option.database_id == changed_database_id;
+  let is_local = database_id == changed_database_id;
+  match changed {
+    DatabaseDataChanged::DidUpdateCell { field_id, .. } => {
+      (is_related && type_option.depends_on_related_field(field_id))
+        || (is_local && type_option.depends_on_local_field(field_id))
+    },
+    DatabaseDataChanged::DidCreateRow { .. } => is_related || is_local,
+    DatabaseDataChanged::DidDeleteRow { .. } => is_related,
+    // The lookup field itself was updated, or the cells of a lookup field were recomputed. The
+    // latter changes the lookup fields that aggregate it.
+    DatabaseDataChanged::DidUpdateLookupField { field_id } => {
+      (is_local && &field.id == field_id)
+        || (is_related && type_option.depends_on_related_field(field_id))
+    },
+  }
+}
+
+/// Recomputes all the lookup fields of the given database.
+async fn refresh_all_lookup_fields(
+  editor: &DatabaseEditor,
+  workspace_database: &RwLock<Option<Arc<WorkspaceDatabase>>>,
+) -> FlowyResult<()> {
+  let related_database_ids = editor
+    .get_lookup_fields()
+    .into_iter()
+    .map(|(_, type_option)| type_option.database_id)
+    .collect::<HashSet<_>>();
+  if related_database_ids.is_empty() {
+    return Ok(());
+  }
+
+  let wdb = workspace_database
+    .read()
+    .await
+    .clone()
+    .ok_or_else(|| FlowyError::internal().with_context("Workspace database not initialized"))?;
+  for related_database_id in related_database_ids {
+    if let Some(related_database) = wdb.get_database(&related_database_id).await {
+      editor
+        .refresh_lookup_fields(&related_database_id, &related_database, |_, _| true)
+        .await?;
+    }
+  }
+  Ok(())
+}
+
 struct UserDatabaseCollabServiceImpl {
   workspace_id: String,
   collab_builder: Arc<AppFlowyCollabBuilder>,
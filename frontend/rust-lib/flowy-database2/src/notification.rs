@@ -52,6 +52,10 @@ pub enum DatabaseNotification {
   DidUpdateFieldSettings = 86,
   // Trigger when Calculation changed
   DidUpdateCalculation = 87,
+  // Trigger after each batch of rows of a CSV file is imported
+  DidUpdateCSVImportProgress = 88,
+  /// Trigger after setting the swimlane field or moving the rows between the swimlanes
+  DidUpdateSwimlanes = 89,
 }
 
 impl std::convert::From<DatabaseNotification> for i32 {
@@ -84,6 +88,8 @@ impl std::convert::From<i32> for DatabaseNotification {
       84 => DatabaseNotification::DidMoveDatabaseViewToTrash,
       86 => DatabaseNotification::DidUpdateFieldSettings,
       87 => DatabaseNotification::DidUpdateCalculation,
+      88 => DatabaseNotification::DidUpdateCSVImportProgress,
+      89 => DatabaseNotification::DidUpdateSwimlanes,
       _ => DatabaseNotification::Unknown,
     }
   }
@@ -128,20 +128,7 @@ impl CalculationsController {
       .await;
 
     if let Some(calculation) = calculation {
-      self
-        .delegate
-        .remove_calculation(&self.view_id, &calculation.id);
-
-      let notification = CalculationChangesetNotificationPB::from_delete(
-        &self.view_id,
-        vec![CalculationPB::from(&calculation)],
-      );
-
-      let _ = self
-        .notifier
-        .send(DatabaseViewChanged::CalculationValueNotification(
-          notification,
-        ));
+      self.remove_calculation(calculation);
     }
   }
 
@@ -161,21 +148,14 @@ impl CalculationsController {
       .await;
 
     if let Some(calculation) = calculation {
-      if new_field_type != FieldType::Number {
-        self
-          .delegate
-          .remove_calculation(&self.view_id, &calculation.id);
-
-        let notification = CalculationChangesetNotificationPB::from_delete(
-          &self.view_id,
-          vec![CalculationPB::from(&calculation)],
-        );
-
-        let _ = self
-          .notifier
-          .send(DatabaseViewChanged::CalculationValueNotification(
-            notification,
-          ));
+      let calculation_type = CalculationType::from(calculation.calculation_type);
+      if calculation_type.is_supported_by(&new_field_type) {
+        // The count calculations are kept, but the cells are decoded differently now.
+        if let Some(update) = self.get_updated_calculation(calculation).await {
+          self.update_calculations(vec![update]);
+        }
+      } else {
+        self.remove_calculation(calculation);
       }
     }
   }
@@ -196,22 +176,8 @@ impl CalculationsController {
       .await;
 
     if let Some(calculation) = calculation {
-      let update = self.get_updated_calculation(calculation).await;
-      if let Some(update) = update {
-        self
-          .delegate
-          .update_calculation(&self.view_id, update.clone());
-
-        let notification = CalculationChangesetNotificationPB::from_update(
-          &self.view_id,
-          vec![CalculationPB::from(&update)],
-        );
-
-        let _ = self
-          .notifier
-          .send(DatabaseViewChanged::CalculationValueNotification(
-            notification,
-          ));
+      if let Some(update) = self.get_updated_calculation(calculation).await {
+        self.update_calculations(vec![update]);
       }
     }
   }
@@ -225,39 +191,50 @@ impl CalculationsController {
       .await
   }
 
+  /// Inserting or deleting a row only changes the calculations of the fields that the row has a
+  /// cell for, except the calculations that count the rows without a cell too.
   async fn handle_row_changed(&self, row: Row) {
-    let cells = row.cells.iter();
+    let field_ids = self.calculations_by_field_cache.read().keys();
 
     let mut updates = vec![];
-
-    // Iterate each cell in the row
-    for cell in cells {
-      let field_id = cell.0;
-      let value = cell.1.value().get("data");
-
-      // Only continue if there is a value in the cell
-      if let Some(_cell_value) = value {
-        let calculation = self.delegate.get_calculation(&self.view_id, field_id).await;
-
-        if let Some(calculation) = calculation {
-          let update = self.get_updated_calculation(calculation.clone()).await;
-
-          if let Some(update) = update {
-            updates.push(CalculationPB::from(&update));
-            self.delegate.update_calculation(&self.view_id, update);
-          }
+    for field_id in field_ids {
+      let calculation = self
+        .delegate
+        .get_calculation(&self.view_id, &field_id)
+        .await;
+      if let Some(calculation) = calculation {
+        if !self.is_affected_by_row(&calculation, &row) {
+          continue;
+        }
+        if let Some(update) = self.get_updated_calculation(calculation).await {
+          updates.push(update);
         }
       }
     }
 
-    if !updates.is_empty() {
-      let notification = CalculationChangesetNotificationPB::from_update(&self.view_id, updates);
+    self.update_calculations(updates);
+  }
+
+  fn is_affected_by_row(&self, calculation: &Calculation, row: &Row) -> bool {
+    if row.cells.contains_key(&calculation.field_id) {
+      return true;
+    }
 
-      let _ = self
-        .notifier
-        .send(DatabaseViewChanged::CalculationValueNotification(
-          notification,
-        ));
+    match CalculationType::from(calculation.calculation_type) {
+      CalculationType::Count | CalculationType::CountEmpty | CalculationType::PercentChecked => {
+        true
+      },
+      // The created and last edited time are read from the row, not from a cell.
+      _ => self
+        .delegate
+        .get_field(&calculation.field_id)
+        .map(|field| {
+          matches!(
+            FieldType::from(field.field_type),
+            FieldType::CreatedTime | FieldType::LastEditedTime
+          )
+        })
+        .unwrap_or(false),
     }
   }
 
@@ -268,20 +245,65 @@ impl CalculationsController {
       .await;
     let field = self.delegate.get_field(&calculation.field_id)?;
 
-    if !row_cells.is_empty() {
-      let value =
-        self
-          .calculations_service
-          .calculate(&field, calculation.calculation_type, row_cells);
+    let value =
+      self
+        .calculations_service
+        .calculate(&field, calculation.calculation_type, row_cells);
 
-      if value != calculation.value {
-        return Some(calculation.with_value(value));
-      }
+    if value != calculation.value {
+      return Some(calculation.with_value(value));
     }
 
     None
   }
 
+  /// Saves the updated calculations, refreshes the cache and notifies the client.
+  fn update_calculations(&self, updates: Vec<Calculation>) {
+    if updates.is_empty() {
+      return;
+    }
+
+    let mut calculations = vec![];
+    for update in updates {
+      calculations.push(CalculationPB::from(&update));
+      self
+        .delegate
+        .update_calculation(&self.view_id, update.clone());
+      self
+        .calculations_by_field_cache
+        .write()
+        .insert(&update.field_id.clone(), Arc::new(update));
+    }
+
+    let notification = CalculationChangesetNotificationPB::from_update(&self.view_id, calculations);
+    let _ = self
+      .notifier
+      .send(DatabaseViewChanged::CalculationValueNotification(
+        notification,
+      ));
+  }
+
+  /// Deletes the calculation, removes it from the cache and notifies the client.
+  fn remove_calculation(&self, calculation: Arc<Calculation>) {
+    self
+      .delegate
+      .remove_calculation(&self.view_id, &calculation.id);
+    self
+      .calculations_by_field_cache
+      .write()
+      .remove(&calculation.field_id);
+
+    let notification = CalculationChangesetNotificationPB::from_delete(
+      &self.view_id,
+      vec![CalculationPB::from(&calculation)],
+    );
+    let _ = self
+      .notifier
+      .send(DatabaseViewChanged::CalculationValueNotification(
+        notification,
+      ));
+  }
+
   pub async fn did_receive_changes(
     &self,
     changeset: CalculationChangeset,
@@ -300,6 +322,11 @@ impl CalculationsController {
         .calculations_service
         .calculate(&field, insert.calculation_type, row_cells);
 
+      self
+        .calculations_by_field_cache
+        .write()
+        .insert(&insert.field_id, Arc::new(insert.with_value(value.clone())));
+
       notification = Some(CalculationChangesetNotificationPB::from_insert(
         &self.view_id,
         vec![CalculationPB {
@@ -312,6 +339,11 @@ impl CalculationsController {
     }
 
     if let Some(delete) = &changeset.delete_calculation {
+      self
+        .calculations_by_field_cache
+        .write()
+        .remove(&delete.field_id);
+
       notification = Some(CalculationChangesetNotificationPB::from_delete(
         &self.view_id,
         vec![CalculationPB {
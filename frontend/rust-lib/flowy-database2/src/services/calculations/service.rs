@@ -1,10 +1,16 @@
 use crate::entities::{CalculationType, FieldType};
 
-use crate::services::field::TypeOptionCellExt;
+use crate::services::field::checklist_type_option::ChecklistCellData;
+use crate::services::field::{
+  CheckboxCellData, DateCellData, TimestampCellData, TypeOptionCellDataHandler, TypeOptionCellExt,
+};
 use collab_database::fields::Field;
-use collab_database::rows::RowCell;
+use collab_database::rows::{Cell, RowCell};
+use std::collections::HashSet;
 use std::sync::Arc;
 
+const SECONDS_PER_DAY: i64 = 86_400;
+
 pub struct CalculationsService {}
 
 impl CalculationsService {
@@ -26,6 +32,14 @@ impl CalculationsService {
       CalculationType::Median => self.calculate_median(field, row_cells),
       CalculationType::Min => self.calculate_min(field, row_cells),
       CalculationType::Sum => self.calculate_sum(field, row_cells),
+      CalculationType::Count => self.calculate_count(row_cells),
+      CalculationType::CountEmpty => self.calculate_count_empty(field, row_cells),
+      CalculationType::CountNonEmpty => self.calculate_count_non_empty(field, row_cells),
+      CalculationType::CountUnique => self.calculate_count_unique(field, row_cells),
+      CalculationType::PercentChecked => self.calculate_percent_checked(field, row_cells),
+      CalculationType::Earliest => self.calculate_earliest(field, row_cells),
+      CalculationType::Latest => self.calculate_latest(field, row_cells),
+      CalculationType::DateRange => self.calculate_date_range(field, row_cells),
     }
   }
 
@@ -118,6 +132,184 @@ impl CalculationsService {
     }
   }
 
+  fn calculate_count(&self, row_cells: Vec<Arc<RowCell>>) -> String {
+    format!("{}", row_cells.len())
+  }
+
+  fn calculate_count_empty(&self, field: &Field, row_cells: Vec<Arc<RowCell>>) -> String {
+    let len = row_cells.len();
+    let non_empty = self.count_non_empty(field, row_cells);
+    format!("{}", len - non_empty)
+  }
+
+  fn calculate_count_non_empty(&self, field: &Field, row_cells: Vec<Arc<RowCell>>) -> String {
+    format!("{}", self.count_non_empty(field, row_cells))
+  }
+
+  fn count_non_empty(&self, field: &Field, row_cells: Vec<Arc<RowCell>>) -> usize {
+    let field_type = FieldType::from(field.field_type);
+    match TypeOptionCellExt::new_with_cell_data_cache(field, None)
+      .get_type_option_cell_data_handler(&field_type)
+    {
+      None => 0,
+      Some(handler) => row_cells
+        .iter()
+        .filter_map(|row_cell| row_cell.cell.as_ref())
+        .filter(|cell| !handler.handle_is_cell_empty(cell, field))
+        .count(),
+    }
+  }
+
+  /// Counts the distinct values of the non-empty cells. The cells are compared by their string
+  /// representation, so two select option cells are the same if they have the same options.
+  fn calculate_count_unique(&self, field: &Field, row_cells: Vec<Arc<RowCell>>) -> String {
+    let mut values = HashSet::new();
+    let field_type = FieldType::from(field.field_type);
+    if let Some(handler) = TypeOptionCellExt::new_with_cell_data_cache(field, None)
+      .get_type_option_cell_data_handler(&field_type)
+    {
+      for row_cell in row_cells {
+        if let Some(cell) = &row_cell.cell {
+          if !handler.handle_is_cell_empty(cell, field) {
+            values.insert(handler.handle_stringify_cell(cell, &field_type, field));
+          }
+        }
+      }
+    }
+
+    format!("{}", values.len())
+  }
+
+  /// For a checkbox field, it's the percentage of the checked rows. For a checklist field, it's
+  /// the percentage of the checked tasks of all the rows.
+  fn calculate_percent_checked(&self, field: &Field, row_cells: Vec<Arc<RowCell>>) -> String {
+    let field_type = FieldType::from(field.field_type);
+    let handler = match TypeOptionCellExt::new_with_cell_data_cache(field, None)
+      .get_type_option_cell_data_handler(&field_type)
+    {
+      None => return "".to_owned(),
+      Some(handler) => handler,
+    };
+
+    let mut checked = 0;
+    let mut total = 0;
+    match field_type {
+      FieldType::Checkbox => {
+        total = row_cells.len();
+        for cell in row_cells
+          .iter()
+          .filter_map(|row_cell| row_cell.cell.as_ref())
+        {
+          let cell_data = handler
+            .get_cell_data(cell, &field_type, field)
+            .ok()
+            .and_then(|cell_data| cell_data.unbox_or_none::<CheckboxCellData>());
+          if cell_data.map_or(false, |cell_data| cell_data.is_check()) {
+            checked += 1;
+          }
+        }
+      },
+      FieldType::Checklist => {
+        for cell in row_cells
+          .iter()
+          .filter_map(|row_cell| row_cell.cell.as_ref())
+        {
+          if let Some(cell_data) = handler
+            .get_cell_data(cell, &field_type, field)
+            .ok()
+            .and_then(|cell_data| cell_data.unbox_or_none::<ChecklistCellData>())
+          {
+            checked += cell_data.selected_option_ids.len();
+            total += cell_data.options.len();
+          }
+        }
+      },
+      _ => return "".to_owned(),
+    }
+
+    if total > 0 {
+      format!("{:.5}", checked as f64 / total as f64 * 100.0)
+    } else {
+      "0".to_owned()
+    }
+  }
+
+  fn calculate_earliest(&self, field: &Field, row_cells: Vec<Arc<RowCell>>) -> String {
+    self.stringify_date_cell(field, row_cells, |timestamped_cells| {
+      timestamped_cells
+        .iter()
+        .min_by_key(|(timestamp, _)| *timestamp)
+        .map(|(_, cell)| cell.clone())
+    })
+  }
+
+  fn calculate_latest(&self, field: &Field, row_cells: Vec<Arc<RowCell>>) -> String {
+    self.stringify_date_cell(field, row_cells, |timestamped_cells| {
+      timestamped_cells
+        .iter()
+        .max_by_key(|(timestamp, _)| *timestamp)
+        .map(|(_, cell)| cell.clone())
+    })
+  }
+
+  /// The number of days between the earliest and the latest date.
+  fn calculate_date_range(&self, field: &Field, row_cells: Vec<Arc<RowCell>>) -> String {
+    let field_type = FieldType::from(field.field_type);
+    let timestamps = match TypeOptionCellExt::new_with_cell_data_cache(field, None)
+      .get_type_option_cell_data_handler(&field_type)
+    {
+      None => vec![],
+      Some(handler) => Self::timestamped_cells(handler.as_ref(), field, &row_cells)
+        .into_iter()
+        .map(|(timestamp, _)| timestamp)
+        .collect(),
+    };
+
+    match (timestamps.iter().min(), timestamps.iter().max()) {
+      (Some(earliest), Some(latest)) => format!("{}", (latest - earliest) / SECONDS_PER_DAY),
+      _ => "".to_owned(),
+    }
+  }
+
+  /// Stringifies the date cell picked by `f` using the date format of the field.
+  fn stringify_date_cell<F>(&self, field: &Field, row_cells: Vec<Arc<RowCell>>, f: F) -> String
+  where
+    F: FnOnce(&[(i64, Cell)]) -> Option<Cell>,
+  {
+    let field_type = FieldType::from(field.field_type);
+    TypeOptionCellExt::new_with_cell_data_cache(field, None)
+      .get_type_option_cell_data_handler(&field_type)
+      .and_then(|handler| {
+        let timestamped_cells = Self::timestamped_cells(handler.as_ref(), field, &row_cells);
+        let cell = f(&timestamped_cells)?;
+        Some(handler.handle_stringify_cell(&cell, &field_type, field))
+      })
+      .unwrap_or_default()
+  }
+
+  fn timestamped_cells(
+    handler: &dyn TypeOptionCellDataHandler,
+    field: &Field,
+    row_cells: &[Arc<RowCell>],
+  ) -> Vec<(i64, Cell)> {
+    let field_type = FieldType::from(field.field_type);
+    row_cells
+      .iter()
+      .filter_map(|row_cell| {
+        let cell = row_cell.cell.as_ref()?;
+        let cell_data = handler.get_cell_data(cell, &field_type, field).ok()?;
+        let timestamp = match field_type {
+          FieldType::DateTime => cell_data.unbox_or_none::<DateCellData>()?.timestamp,
+          FieldType::LastEditedTime | FieldType::CreatedTime => {
+            cell_data.unbox_or_none::<TimestampCellData>()?.timestamp
+          },
+          _ => None,
+        }?;
+        Some((timestamp, cell.clone()))
+      })
+      .collect()
+  }
+
   fn reduce_values_f64<F, T>(&self, field: &Field, row_cells: Vec<Arc<RowCell>>, f: F) -> T
   where
     F: FnOnce(&mut Vec<f64>) -> T,
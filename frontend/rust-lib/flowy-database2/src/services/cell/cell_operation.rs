@@ -2,7 +2,7 @@ use std::collections::HashMap;
 use std::fmt::Debug;
 
 use collab_database::fields::Field;
-use collab_database::rows::{get_field_type_from_cell, Cell, Cells};
+use collab_database::rows::{get_field_type_from_cell, Cell, Cells, RowId};
 
 use flowy_error::{ErrorCode, FlowyError, FlowyResult};
 
@@ -237,6 +237,38 @@ pub fn insert_checklist_cell(insert_options: Vec<String>, field: &Field) -> Cell
   apply_cell_changeset(changeset, None, field, None).unwrap()
 }
 
+pub fn insert_relation_cell(row_ids: Vec<RowId>, field: &Field) -> Cell {
+  let changeset = RelationCellChangeset {
+    inserted_row_ids: row_ids,
+    ..Default::default()
+  }
+  .to_cell_changeset_str();
+  apply_cell_changeset(changeset, None, field, None).unwrap()
+}
+
+pub fn insert_person_cell(user_ids: Vec<String>, field: &Field) -> Cell {
+  let changeset = PersonCellChangeset {
+    inserted_user_ids: user_ids,
+    ..Default::default()
+  }
+  .to_cell_changeset_str();
+  apply_cell_changeset(changeset, None, field, None).unwrap()
+}
+
+pub fn insert_files_cell(files: Vec<FileEntry>, field: &Field) -> Cell {
+  let changeset = FilesCellChangeset {
+    inserted_files: files,
+    ..Default::default()
+  }
+  .to_cell_changeset_str();
+  apply_cell_changeset(changeset, None, field, None).unwrap()
+}
+
+/// The auto number cells don't accept any changeset, so the cell is built from the number.
+pub fn insert_auto_number_cell(number: i64) -> Cell {
+  Cell::from(AutoNumberCellData::new(number))
+}
+
 pub fn delete_select_option_cell(option_ids: Vec<String>, field: &Field) -> Cell {
   let changeset =
     SelectOptionCellChangeset::from_delete_options(option_ids).to_cell_changeset_str();
@@ -355,6 +387,30 @@ impl<'a> CellBuilder<'a> {
               cells.insert(field_id, insert_select_option_cell(ids.into_inner(), field));
             }
           },
+          FieldType::Relation => {
+            if let Ok(cell_data) = RelationCellData::from_cell_str(&cell_str) {
+              cells.insert(field_id, insert_relation_cell(cell_data.row_ids, field));
+            }
+          },
+          FieldType::Lookup => {
+            tracing::warn!("Shouldn't insert cell data to cell whose field type is Lookup, it's computed from the related database");
+          },
+          FieldType::Formula => {
+            tracing::warn!("Shouldn't insert cell data to cell whose field type is Formula, it's computed from the other cells");
+          },
+          FieldType::Person => {
+            if let Ok(cell_data) = PersonCellData::from_cell_str(&cell_str) {
+              cells.insert(field_id, insert_person_cell(cell_data.user_ids, field));
+            }
+          },
+          FieldType::Files => {
+            if let Ok(cell_data) = FilesCellData::from_cell_str(&cell_str) {
+              cells.insert(field_id, insert_files_cell(cell_data.files, field));
+            }
+          },
+          FieldType::AutoNumber => {
+            tracing::warn!("Shouldn't insert cell data to cell whose field type is AutoNumber, it's assigned when the row is created");
+          },
         }
       }
     }
@@ -444,4 +500,37 @@ impl<'a> CellBuilder<'a> {
       },
     }
   }
+
+  pub fn insert_relation_cell(&mut self, field_id: &str, row_ids: Vec<RowId>) {
+    match self.field_maps.get(&field_id.to_owned()) {
+      None => tracing::warn!("Can't find the relation field with id: {}", field_id),
+      Some(field) => {
+        self
+          .cells
+          .insert(field_id.to_owned(), insert_relation_cell(row_ids, field));
+      },
+    }
+  }
+
+  pub fn insert_person_cell(&mut self, field_id: &str, user_ids: Vec<String>) {
+    match self.field_maps.get(&field_id.to_owned()) {
+      None => tracing::warn!("Can't find the person field with id: {}", field_id),
+      Some(field) => {
+        self
+          .cells
+          .insert(field_id.to_owned(), insert_person_cell(user_ids, field));
+      },
+    }
+  }
+
+  pub fn insert_auto_number_cell(&mut self, field_id: &str, number: i64) {
+    match self.field_maps.get(&field_id.to_owned()) {
+      None => tracing::warn!("Can't find the auto number field with id: {}", field_id),
+      Some(_) => {
+        self
+          .cells
+          .insert(field_id.to_owned(), insert_auto_number_cell(number));
+      },
+    }
+  }
 }
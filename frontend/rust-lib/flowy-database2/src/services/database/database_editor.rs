@@ -1,13 +1,17 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::time::Duration;
 
-use collab_database::database::MutexDatabase;
+use collab_database::database::{Database, MutexDatabase};
 use collab_database::fields::{Field, TypeOptionData};
-use collab_database::rows::{Cell, Cells, CreateRowParams, Row, RowCell, RowDetail, RowId};
+use collab_database::rows::{
+  get_field_type_from_cell, Cell, Cells, CreateRowParams, Row, RowCell, RowDetail, RowId,
+};
+use collab_database::user::WorkspaceDatabase;
 use collab_database::views::{DatabaseLayout, DatabaseView, LayoutSetting, OrderObjectPosition};
 use futures::StreamExt;
 use tokio::sync::{broadcast, RwLock};
-use tracing::{event, warn};
+use tracing::{error, event, warn};
 
 use flowy_error::{internal_error, ErrorCode, FlowyError, FlowyResult};
 use lib_dispatch::prelude::af_spawn;
@@ -17,47 +21,81 @@ use lib_infra::priority_task::TaskDispatcher;
 use crate::entities::*;
 use crate::notification::{send_notification, DatabaseNotification};
 use crate::services::calculations::Calculation;
-use crate::services::cell::{apply_cell_changeset, get_cell_protobuf, CellCache, ToCellChangeset};
+use crate::services::cell::{
+  apply_cell_changeset, get_cell_protobuf, stringify_cell_data, CellCache, ToCellChangeset,
+};
 use crate::services::database::util::database_view_setting_pb_from_view;
-use crate::services::database::UpdatedRow;
+use crate::services::database::{
+  DatabaseDataChanged, DatabaseFileStorage, RowSearchText, UpdatedRow,
+};
 use crate::services::database_view::{
   DatabaseViewChanged, DatabaseViewEditor, DatabaseViewOperation, DatabaseViews, EditorByViewId,
 };
 use crate::services::field::checklist_type_option::ChecklistCellChangeset;
 use crate::services::field::{
-  default_type_option_data_from_type, select_type_option_from_field, transform_type_option,
-  type_option_data_from_pb, SelectOptionCellChangeset, SelectOptionIds, TimestampCellData,
-  TypeOptionCellDataHandler, TypeOptionCellExt,
+  can_validate, default_type_option_data_from_type, field_validation_error, find_duplicated_values,
+  resolve_auto_numbers, select_type_option_from_field, transform_type_option,
+  type_option_data_from_pb, unique_value, AutoNumberCellData, AutoNumberRow, AutoNumberTypeOption,
+  DateCellChangeset, DateCellData, FieldValidation, FilesCellChangeset, FilesCellData,
+  FormulaCellData, FormulaTypeOption, LookupCellData, LookupRelatedRows, LookupTypeOption, Person,
+  PersonCellChangeset, PersonTypeOption, RelationCellData, RelationTypeOption,
+  SelectOptionCellChangeset, SelectOptionIds, TimestampCellData, TypeOptionCellDataHandler,
+  TypeOptionCellExt, FIELD_VALIDATION_KEY,
 };
 use crate::services::field_settings::{
   default_field_settings_by_layout_map, FieldSettings, FieldSettingsChangesetParams,
 };
 use crate::services::filter::Filter;
 use crate::services::group::{default_group_setting, GroupChangesets, GroupSetting, RowChangeset};
-use crate::services::share::csv::{CSVExport, CSVFormat};
+use crate::services::share::csv::{CSVExport, CSVExportData, CSVFormat};
+use crate::services::share::json::JSONExport;
+use crate::services::share::markdown::MarkdownExport;
+use crate::services::share::xlsx::XLSXExport;
+use crate::services::share::{stringify_row_cell, ExportGroup, RelatedRowNames, ViewExportData};
 use crate::services::sort::Sort;
 use crate::utils::cache::AnyTypeCache;
+use crate::DatabaseUser;
 
 #[derive(Clone)]
 pub struct DatabaseEditor {
+  user: Arc<dyn DatabaseUser>,
   database: Arc<MutexDatabase>,
   pub cell_cache: CellCache,
   database_views: Arc<DatabaseViews>,
+  data_changed_notifier: broadcast::Sender<DatabaseDataChanged>,
+  file_storage: DatabaseFileStorage,
+  /// Used to read the rows of the related databases, e.g. to export the linked rows by name.
+  workspace_database: Arc<RwLock<Option<Arc<WorkspaceDatabase>>>>,
+  /// Identifies the auto numbers allocated by this device until they are synced.
+  device_id: String,
 }
 
 impl DatabaseEditor {
   pub async fn new(
+    user: Arc<dyn DatabaseUser>,
     database: Arc<MutexDatabase>,
     task_scheduler: Arc<RwLock<TaskDispatcher>>,
+    file_storage: DatabaseFileStorage,
+    workspace_database: Arc<RwLock<Option<Arc<WorkspaceDatabase>>>>,
   ) -> FlowyResult<Self> {
     let cell_cache = AnyTypeCache::<u64>::new();
     let database_id = database.lock().get_database_id();
+    let device_id = user.device_id()?;
 
     // Receive database sync state and send to frontend via the notification
     let mut sync_state = database.lock().subscribe_sync_state();
     let cloned_database_id = database_id.clone();
+    let weak_database = Arc::downgrade(&database);
+    let cloned_device_id = device_id.clone();
     af_spawn(async move {
       while let Some(sync_state) = sync_state.next().await {
+        // The rows created on other devices while offline might have taken the same numbers.
+        if sync_state.is_sync_finished() {
+          if let Some(database) = weak_database.upgrade() {
+            let changesets = resolve_auto_number_cells(&database.lock(), &cloned_device_id, true);
+            notify_did_update_cell(changesets).await;
+          }
+        }
         send_notification(
           &cloned_database_id,
           DatabaseNotification::DidUpdateDatabaseSyncUpdate,
@@ -90,6 +128,7 @@ impl DatabaseEditor {
     // Used to cache the view of the database for fast access.
     let editor_by_view_id = Arc::new(RwLock::new(EditorByViewId::default()));
     let view_operation = Arc::new(DatabaseViewOperationImpl {
+      user: user.clone(),
       database: database.clone(),
       task_scheduler: task_scheduler.clone(),
       cell_cache: cell_cache.clone(),
@@ -105,10 +144,17 @@ impl DatabaseEditor {
       )
       .await?,
     );
+    let (data_changed_notifier, _) = broadcast::channel(100);
+    sweep_orphaned_files(&database, &file_storage);
     Ok(Self {
+      user,
       database,
       cell_cache,
       database_views,
+      data_changed_notifier,
+      file_storage,
+      workspace_database,
+      device_id,
     })
   }
 
@@ -144,10 +190,22 @@ impl DatabaseEditor {
     }
   }
 
+  /// Returns the layout of the view, including the timeline that the collab stores as a grid.
+  pub fn get_layout_type_pb(&self, view_id: &str) -> FlowyResult<DatabaseLayoutPB> {
+    let view =
+      self.database.lock().get_view(view_id).ok_or_else(|| {
+        FlowyError::record_not_found().with_context("Can't find the database view")
+      })?;
+    Ok(DatabaseLayoutPB::from_view_layout(
+      view.layout,
+      view.layout_settings.get(&view.layout),
+    ))
+  }
+
   pub async fn update_view_layout(
     &self,
     view_id: &str,
-    layout_type: DatabaseLayout,
+    layout_type: DatabaseLayoutPB,
   ) -> FlowyResult<()> {
     let view_editor = self.database_views.get_view_editor(view_id).await?;
     view_editor.v_update_layout_type(layout_type).await?;
@@ -163,24 +221,57 @@ impl DatabaseEditor {
     Ok(view_editor.notifier.subscribe())
   }
 
+  pub fn subscribe_data_changed(&self) -> broadcast::Receiver<DatabaseDataChanged> {
+    self.data_changed_notifier.subscribe()
+  }
+
   pub fn get_field(&self, field_id: &str) -> Option<Field> {
     self.database.lock().fields.get_field(field_id)
   }
 
-  pub async fn set_group_by_field(&self, view_id: &str, field_id: &str) -> FlowyResult<()> {
-    {
+  /// Groups the view by the field. The `setting_content` is the JSON configuration of the groups,
+  /// the view is regrouped if it changes even if the field is already the grouping field.
+  pub async fn set_group_by_field(
+    &self,
+    view_id: &str,
+    field_id: &str,
+    setting_content: String,
+  ) -> FlowyResult<()> {
+    let is_setting_content_changed = {
       let database = self.database.lock();
       let field = database.fields.get_field(field_id);
-      if let Some(field) = field {
-        let group_setting = default_group_setting(&field);
-        database.views.update_database_view(view_id, |view| {
-          view.set_groups(vec![group_setting.into()]);
-        });
+      let old_setting: Option<GroupSetting> =
+        database.get_all_group_setting(view_id).into_iter().next();
+      match field {
+        None => false,
+        Some(field) => {
+          let is_setting_content_changed = old_setting
+            .as_ref()
+            .map(|old_setting| {
+              old_setting.field_id == field.id && old_setting.content != setting_content
+            })
+            .unwrap_or(false);
+          let mut group_setting = default_group_setting(&field);
+          group_setting.content = setting_content;
+          // Keep the swimlanes unless the board is now grouped by their field
+          group_setting.swimlane = old_setting
+            .and_then(|old_setting| old_setting.swimlane)
+            .filter(|swimlane| swimlane.field_id != field.id);
+          database.views.update_database_view(view_id, |view| {
+            view.set_groups(vec![group_setting.into()]);
+          });
+          is_setting_content_changed
+        },
       }
-    }
+    };
 
     let view_editor = self.database_views.get_view_editor(view_id).await?;
-    view_editor.v_initialize_new_group(field_id).await?;
+    if view_editor.is_swimlane_field(field_id).await {
+      view_editor.v_set_swimlane_field(None).await?;
+    }
+    view_editor
+      .v_initialize_new_group(field_id, is_setting_content_changed)
+      .await?;
     Ok(())
   }
 
@@ -310,6 +401,7 @@ impl DatabaseEditor {
   }
 
   pub async fn update_field(&self, params: FieldChangesetParams) -> FlowyResult<()> {
+    let is_renamed = params.name.is_some();
     self
       .database
       .lock()
@@ -321,6 +413,29 @@ impl DatabaseEditor {
           .set_visibility_if_not_none(params.visibility);
       });
     notify_did_update_database_field(&self.database, &params.field_id)?;
+
+    // The formulas that refer to the field display its new name.
+    if is_renamed {
+      let fields = self.database.lock().get_fields(None);
+      let formula_field_ids = fields
+        .iter()
+        .filter(|field| {
+          field
+            .get_type_option::<FormulaTypeOption>(FieldType::Formula)
+            .filter(|_| FieldType::from(field.field_type).is_formula())
+            .map(|type_option| {
+              type_option
+                .referenced_field_ids(&fields)
+                .contains(&params.field_id)
+            })
+            .unwrap_or(false)
+        })
+        .map(|field| field.id.clone())
+        .collect::<Vec<_>>();
+      for field_id in formula_field_ids {
+        notify_did_update_database_field(&self.database, &field_id)?;
+      }
+    }
     Ok(())
   }
 
@@ -365,7 +480,27 @@ impl DatabaseEditor {
     old_field: Field,
   ) -> FlowyResult<()> {
     let view_editors = self.database_views.editors().await;
+    let field_id = old_field.id.clone();
+    let old_field_type = FieldType::from(old_field.field_type);
+    let type_option_data = if old_field_type.is_formula() && !type_option_data.is_empty() {
+      let fields = self.database.lock().get_fields(None);
+      FormulaTypeOption::from(type_option_data)
+        .normalize_field_references(&fields)
+        .into()
+    } else {
+      type_option_data
+    };
     update_field_type_option_fn(&self.database, &view_editors, type_option_data, old_field).await?;
+    if old_field_type.is_lookup() {
+      let _ = self
+        .data_changed_notifier
+        .send(DatabaseDataChanged::DidUpdateLookupField { field_id });
+    } else if old_field_type.is_formula() {
+      self.refresh_all_formula_cells().await?;
+    } else if old_field_type.is_auto_number() {
+      // The next number might have been set to a number that is already taken
+      self.resolve_auto_numbers(false).await;
+    }
 
     Ok(())
   }
@@ -411,6 +546,18 @@ impl DatabaseEditor {
         for view in self.database_views.editors().await {
           view.v_did_update_field_type(field_id, new_field_type).await;
         }
+
+        if new_field_type.is_lookup() {
+          let _ = self
+            .data_changed_notifier
+            .send(DatabaseDataChanged::DidUpdateLookupField {
+              field_id: field_id.to_string(),
+            });
+        } else if new_field_type.is_formula() {
+          self.refresh_all_formula_cells().await?;
+        } else if new_field_type.is_auto_number() {
+          self.resolve_auto_numbers(false).await;
+        }
       },
     }
 
@@ -448,11 +595,25 @@ impl DatabaseEditor {
 
   // consider returning a result. But most of the time, it should be fine to just ignore the error.
   pub async fn duplicate_row(&self, view_id: &str, row_id: &RowId) {
-    let params = self.database.lock().duplicate_row(row_id);
+    let params = {
+      let database = self.database.lock();
+      database.duplicate_row(row_id).map(|mut params| {
+        // The values of the unique fields can't be copied
+        for field in database.get_fields(None) {
+          if FieldValidation::from_field(&field).map_or(false, |validation| validation.unique) {
+            params.cells.remove(&field.id);
+          }
+        }
+        params
+      })
+    };
     match params {
       None => warn!("Failed to duplicate row: {}", row_id),
       Some(params) => {
         let result = self.create_row(view_id, None, params).await;
+        if let Err(err) = &result {
+          warn!("Failed to duplicate row: {}, error: {}", row_id, err);
+        }
         if let Some(row_detail) = result.unwrap_or(None) {
           for view in self.database_views.editors().await {
             view.v_did_duplicate_row(&row_detail).await;
@@ -504,6 +665,25 @@ impl DatabaseEditor {
     for view in self.database_views.editors().await {
       view.v_will_create_row(&mut params.cells, &group_id).await;
     }
+    // Validate the cells before allocating the auto numbers, so a rejected row doesn't use a
+    // number. The rows that miss a required cell are still created, otherwise blank rows couldn't
+    // be added from the grid or the board. The missing cells are returned by the CreateRow event,
+    // see [Self::get_row_validation_errors].
+    {
+      let database = self.database.lock();
+      let errors = database
+        .get_fields(None)
+        .iter()
+        .flat_map(|field| {
+          validate_field_cell(&database, field, &params.id, params.cells.get(&field.id))
+        })
+        .filter(|error| error.rule != FieldValidationRulePB::Required)
+        .collect::<Vec<FieldValidationErrorPB>>();
+      if !errors.is_empty() {
+        return Err(field_validation_error(errors));
+      }
+      allocate_auto_numbers(&database, &mut params.cells, &self.device_id);
+    }
     let result = self.database.lock().create_row_in_view(view_id, params);
     if let Some((index, row_order)) = result {
       tracing::trace!("create row: {:?} at {}", row_order, index);
@@ -512,6 +692,14 @@ impl DatabaseEditor {
         for view in self.database_views.editors().await {
           view.v_did_create_row(&row_detail, index).await;
         }
+        let _ = self
+          .data_changed_notifier
+          .send(DatabaseDataChanged::DidCreateRow {
+            row_id: row_detail.row.id.clone().into_inner(),
+          });
+        self
+          .refresh_formula_cells(view_id, &row_detail.row.id, None)
+          .await?;
         return Ok(Some(row_detail));
       }
     }
@@ -532,6 +720,14 @@ impl DatabaseEditor {
       .type_option_data
       .and_then(|data| type_option_data_from_pb(data, &params.field_type).ok())
       .unwrap_or(default_type_option_data_from_type(&params.field_type));
+    let type_option_data = if params.field_type.is_formula() {
+      let fields = self.database.lock().get_fields(None);
+      FormulaTypeOption::from(type_option_data)
+        .normalize_field_references(&fields)
+        .into()
+    } else {
+      type_option_data
+    };
 
     let (index, field) = self.database.lock().create_field_with_mut(
       &params.view_id,
@@ -550,7 +746,100 @@ impl DatabaseEditor {
       .notify_did_insert_database_field(field.clone(), index)
       .await;
 
-    Ok(FieldPB::new(field))
+    if params.field_type.is_lookup() {
+      let _ = self
+        .data_changed_notifier
+        .send(DatabaseDataChanged::DidUpdateLookupField {
+          field_id: field.id.clone(),
+        });
+    } else if params.field_type.is_formula() {
+      self.refresh_all_formula_cells().await?;
+    } else if params.field_type.is_auto_number() {
+      self.resolve_auto_numbers(false).await;
+    }
+
+    let fields = self.database.lock().get_fields(None);
+    Ok(FieldPB::new_with_fields(field, &fields))
+  }
+
+  pub fn get_field_validation(&self, field_id: &str) -> FlowyResult<FieldValidation> {
+    let field = self.get_field(field_id).ok_or_else(|| {
+      FlowyError::record_not_found().with_context(format!("Field with id:{} not found", field_id))
+    })?;
+    Ok(FieldValidation::from_field(&field).unwrap_or_default())
+  }
+
+  /// Replaces the validation rules of the field. The existing cells are not checked, the cells
+  /// that break the rules are returned by [Self::get_validation_violations].
+  pub async fn update_field_validation(
+    &self,
+    field_id: &str,
+    validation: FieldValidation,
+  ) -> FlowyResult<()> {
+    validation.check()?;
+    if self.get_field(field_id).is_none() {
+      return Err(
+        FlowyError::record_not_found()
+          .with_context(format!("Field with id:{} not found", field_id)),
+      );
+    }
+
+    self
+      .database
+      .lock()
+      .fields
+      .update_field(field_id, |update| {
+        update.update_type_options(|type_options_update| {
+          type_options_update.insert(FIELD_VALIDATION_KEY, validation.into());
+        });
+      });
+    notify_did_update_database_field(&self.database, field_id)
+  }
+
+  /// Returns the rules that are broken by the rows of the view. All the rows that share the same
+  /// value of a unique field are returned, so they can be cleaned up together.
+  /// Returns the validation rules that the cells of the row break.
+  pub fn get_row_validation_errors(&self, row_id: &RowId) -> Vec<FieldValidationErrorPB> {
+    let database = self.database.lock();
+    let row = database.get_row(row_id);
+    database
+      .get_fields(None)
+      .iter()
+      .flat_map(|field| validate_field_cell(&database, field, row_id, row.cells.get(&field.id)))
+      .collect()
+  }
+
+  pub async fn get_validation_violations(&self, view_id: &str) -> Vec<FieldValidationErrorPB> {
+    let database = self.database.lock();
+    let inline_view_id = database.get_inline_view_id();
+    let fields = database
+      .get_fields_in_view(view_id, None)
+      .into_iter()
+      .flat_map(|field| {
+        let validation = FieldValidation::from_field(&field)?;
+        let duplicated_row_ids =
+          if validation.unique && can_validate(&FieldType::from(field.field_type)) {
+            let row_cells = get_cells_for_field(&database, &inline_view_id, &field.id);
+            find_duplicated_values(&field, &row_cells)
+              .into_iter()
+              .collect::<HashSet<RowId>>()
+          } else {
+            HashSet::new()
+          };
+        Some((field, validation, duplicated_row_ids))
+      })
+      .collect::<Vec<_>>();
+
+    let mut errors = vec![];
+    for row in database.get_rows_for_view(view_id) {
+      for (field, validation, duplicated_row_ids) in &fields {
+        errors.extend(validation.validate_cell(field, &row.id, row.cells.get(&field.id)));
+        if duplicated_row_ids.contains(&row.id) {
+          errors.push(unique_error(field, &row.id));
+        }
+      }
+    }
+    errors
   }
 
   pub async fn move_field(&self, params: MoveFieldParams) -> FlowyResult<()> {
@@ -572,6 +861,7 @@ impl DatabaseEditor {
         });
 
       let new_index = database.index_of_field(&params.view_id, &params.from_field_id);
+      let field = FieldPB::new_with_fields(field, &database.get_fields(None));
 
       (field, new_index)
     };
@@ -579,7 +869,7 @@ impl DatabaseEditor {
     if let Some(index) = new_index {
       let delete_field = FieldIdPB::from(params.from_field_id);
       let insert_field = IndexFieldPB {
-        field: FieldPB::new(field),
+        field,
         index: index as i32,
       };
       let notified_changeset = DatabaseFieldChangesetPB {
@@ -618,6 +908,7 @@ impl DatabaseEditor {
         icon: row_meta.icon_url,
         cover: row_meta.cover_url,
         is_document_empty: row_meta.is_document_empty,
+        validation_errors: vec![],
       })
     } else {
       warn!("the row:{} is exist in view:{}", row_id.as_str(), view_id);
@@ -641,6 +932,24 @@ impl DatabaseEditor {
       for view in self.database_views.editors().await {
         view.v_did_delete_row(&row).await;
       }
+      let _ = self
+        .data_changed_notifier
+        .send(DatabaseDataChanged::DidDeleteRow {
+          row_id: row.id.clone().into_inner(),
+        });
+
+      let file_urls = self
+        .get_files_field_ids()
+        .iter()
+        .filter_map(|field_id| row.cells.get(field_id))
+        .flat_map(|cell| {
+          FilesCellData::from(cell)
+            .files
+            .into_iter()
+            .map(|file| file.url)
+        })
+        .collect::<Vec<_>>();
+      self.delete_orphaned_files(file_urls);
     }
   }
 
@@ -704,30 +1013,7 @@ impl DatabaseEditor {
   }
 
   pub async fn get_cells_for_field(&self, view_id: &str, field_id: &str) -> Vec<RowCell> {
-    let database = self.database.lock();
-    if let Some(field) = database.fields.get_field(field_id) {
-      let field_type = FieldType::from(field.field_type);
-      match field_type {
-        FieldType::LastEditedTime | FieldType::CreatedTime => database
-          .get_rows_for_view(view_id)
-          .into_iter()
-          .map(|row| {
-            let data = if field_type.is_created_time() {
-              TimestampCellData::new(row.created_at)
-            } else {
-              TimestampCellData::new(row.modified_at)
-            };
-            RowCell {
-              row_id: row.id,
-              cell: Some(Cell::from(data)),
-            }
-          })
-          .collect(),
-        _ => database.get_cells_for_field(view_id, field_id),
-      }
-    } else {
-      vec![]
-    }
+    get_cells_for_field(&self.database.lock(), view_id, field_id)
   }
 
   pub async fn update_cell_with_changeset<T>(
@@ -737,6 +1023,21 @@ impl DatabaseEditor {
     field_id: &str,
     cell_changeset: T,
   ) -> FlowyResult<()>
+  where
+    T: ToCellChangeset,
+  {
+    let new_cell = self.apply_cell_changeset(&row_id, field_id, cell_changeset)?;
+    self.update_cell(view_id, row_id, field_id, new_cell).await
+  }
+
+  /// Applies the changeset to the current cell of the row and validates the new cell against the
+  /// field. Returns the new cell without writing it.
+  fn apply_cell_changeset<T>(
+    &self,
+    row_id: &RowId,
+    field_id: &str,
+    cell_changeset: T,
+  ) -> FlowyResult<Cell>
   where
     T: ToCellChangeset,
   {
@@ -749,52 +1050,247 @@ impl DatabaseEditor {
           Err(FlowyError::internal().with_context(msg))
         },
       }?;
-      (field, database.get_cell(field_id, &row_id).cell)
+      (field, database.get_cell(field_id, row_id).cell)
     };
     let new_cell =
       apply_cell_changeset(cell_changeset, cell, &field, Some(self.cell_cache.clone()))?;
-    self.update_cell(view_id, row_id, field_id, new_cell).await
+    let errors = validate_field_cell(&self.database.lock(), &field, row_id, Some(&new_cell));
+    if !errors.is_empty() {
+      return Err(field_validation_error(errors));
+    }
+    Ok(new_cell)
   }
 
   /// Update a cell in the database.
-  /// This will notify all views that the cell has been updated.
+  /// This will notify all views that the cell has been updated. The formula cells of the row
+  /// that depend on the cell will be recomputed.
   pub async fn update_cell(
     &self,
     view_id: &str,
     row_id: RowId,
     field_id: &str,
     new_cell: Cell,
+  ) -> FlowyResult<()> {
+    self
+      .update_cells(view_id, row_id, vec![(field_id.to_string(), new_cell)])
+      .await
+  }
+
+  /// Updates several cells of the row in one update, e.g. the start and the end of a timeline
+  /// span.
+  pub async fn update_cells(
+    &self,
+    view_id: &str,
+    row_id: RowId,
+    cells: Vec<(String, Cell)>,
+  ) -> FlowyResult<()> {
+    let field_ids = cells
+      .iter()
+      .map(|(field_id, _)| field_id.clone())
+      .collect::<Vec<_>>();
+    self.write_cells(view_id, row_id.clone(), cells).await?;
+    for field_id in field_ids {
+      self
+        .refresh_formula_cells(view_id, &row_id, Some(&field_id))
+        .await?;
+    }
+    Ok(())
+  }
+
+  async fn write_cells(
+    &self,
+    view_id: &str,
+    row_id: RowId,
+    cells: Vec<(String, Cell)>,
   ) -> FlowyResult<()> {
     // Get the old row before updating the cell. It would be better to get the old cell
     let old_row = { self.get_row_detail(view_id, &row_id) };
 
+    let field_ids = cells
+      .iter()
+      .map(|(field_id, _)| field_id.clone())
+      .collect::<Vec<_>>();
     self.database.lock().update_row(&row_id, |row_update| {
       row_update.update_cells(|cell_update| {
-        cell_update.insert(field_id, new_cell);
+        cells
+          .into_iter()
+          .fold(cell_update, |cell_update, (field_id, new_cell)| {
+            cell_update.insert(&field_id, new_cell)
+          });
       });
     });
 
     let option_row = self.get_row_detail(view_id, &row_id);
     if let Some(new_row_detail) = option_row {
       for view in self.database_views.editors().await {
-        view
-          .v_did_update_row(&old_row, &new_row_detail, field_id.to_owned())
-          .await;
+        for field_id in &field_ids {
+          view
+            .v_did_update_row(&old_row, &new_row_detail, field_id.clone())
+            .await;
+        }
       }
     }
 
-    let changeset = CellChangesetNotifyPB {
-      view_id: view_id.to_string(),
-      row_id: row_id.clone().into_inner(),
-      field_id: field_id.to_string(),
-    };
+    let changesets = field_ids
+      .iter()
+      .map(|field_id| CellChangesetNotifyPB {
+        view_id: view_id.to_string(),
+        row_id: row_id.clone().into_inner(),
+        field_id: field_id.clone(),
+      })
+      .collect();
     self
-      .notify_update_row(view_id, row_id, vec![changeset])
+      .notify_update_row(view_id, row_id.clone(), changesets)
       .await;
 
+    for field_id in field_ids {
+      let _ = self
+        .data_changed_notifier
+        .send(DatabaseDataChanged::DidUpdateCell {
+          field_id,
+          row_id: row_id.clone().into_inner(),
+        });
+    }
+    Ok(())
+  }
+
+  /// Recomputes the formula cells of the row. If the `changed_field_id` is not None, only the
+  /// formulas that depend on the changed field, directly or through other formulas, are
+  /// recomputed. Otherwise, all the formulas of the row are recomputed.
+  async fn refresh_formula_cells(
+    &self,
+    view_id: &str,
+    row_id: &RowId,
+    changed_field_id: Option<&str>,
+  ) -> FlowyResult<()> {
+    let fields = self.database.lock().get_fields(None);
+    let formulas = fields
+      .iter()
+      .filter(|field| FieldType::from(field.field_type).is_formula())
+      .flat_map(|field| {
+        let type_option = field.get_type_option::<FormulaTypeOption>(FieldType::Formula)?;
+        let referenced_field_ids = type_option.referenced_field_ids(&fields);
+        Some((field.id.clone(), type_option, referenced_field_ids))
+      })
+      .collect::<Vec<_>>();
+    if formulas.is_empty() {
+      return Ok(());
+    }
+
+    let mut affected_field_ids = match changed_field_id {
+      None => formulas.iter().map(|(id, _, _)| id.clone()).collect(),
+      Some(changed_field_id) => {
+        let mut dirty_field_ids = HashSet::from([changed_field_id.to_string()]);
+        let mut affected_field_ids = vec![];
+        loop {
+          let newly_affected = formulas
+            .iter()
+            .filter(|(id, _, referenced_field_ids)| {
+              !dirty_field_ids.contains(id)
+                && referenced_field_ids
+                  .iter()
+                  .any(|field_id| dirty_field_ids.contains(field_id))
+            })
+            .map(|(id, _, _)| id.clone())
+            .collect::<Vec<_>>();
+          if newly_affected.is_empty() {
+            break;
+          }
+          dirty_field_ids.extend(newly_affected.iter().cloned());
+          affected_field_ids.extend(newly_affected);
+        }
+        affected_field_ids
+      },
+    };
+    if affected_field_ids.is_empty() {
+      return Ok(());
+    }
+
+    // The formulas might refer to each other. Evaluate them repeatedly until the values are
+    // stable, the number of passes is bounded in case of circular references.
+    let mut row = self.database.lock().get_row(row_id);
+    let mut changed_cells: HashMap<String, Cell> = HashMap::new();
+    for _ in 0..affected_field_ids.len() {
+      let mut is_stable = true;
+      for (field_id, type_option, _) in &formulas {
+        if !affected_field_ids.contains(field_id) {
+          continue;
+        }
+        let cell_data = type_option.evaluate(&fields, &row);
+        let old_cell_data = row
+          .cells
+          .get(field_id)
+          .map(FormulaCellData::from)
+          .unwrap_or_default();
+        if old_cell_data != cell_data {
+          is_stable = false;
+          let cell = Cell::from(cell_data);
+          row.cells.insert(field_id.clone(), cell.clone());
+          changed_cells.insert(field_id.clone(), cell);
+        }
+      }
+      if is_stable {
+        break;
+      }
+    }
+    affected_field_ids.retain(|field_id| changed_cells.contains_key(field_id));
+
+    for field_id in affected_field_ids {
+      if let Some(cell) = changed_cells.remove(&field_id) {
+        self
+          .write_cells(view_id, row_id.clone(), vec![(field_id, cell)])
+          .await?;
+      }
+    }
+    Ok(())
+  }
+
+  /// Recomputes the formula cells of all the rows. It's called when a formula was changed.
+  async fn refresh_all_formula_cells(&self) -> FlowyResult<()> {
+    let (inline_view_id, row_ids) = {
+      let database = self.database.lock();
+      let inline_view_id = database.get_inline_view_id();
+      let row_ids = database
+        .get_rows_for_view(&inline_view_id)
+        .into_iter()
+        .map(|row| row.id)
+        .collect::<Vec<_>>();
+      (inline_view_id, row_ids)
+    };
+
+    for row_id in row_ids {
+      self
+        .refresh_formula_cells(&inline_view_id, &row_id, None)
+        .await?;
+    }
+    Ok(())
+  }
+
+  /// Recomputes the formula cells if any formula calls `now()` or `today()`. The cells are stored,
+  /// so it runs when the database is opened and whenever the day rolls over.
+  pub async fn refresh_time_dependent_formula_cells(&self) -> FlowyResult<()> {
+    let has_time_dependent_formula = self
+      .database
+      .lock()
+      .get_fields(None)
+      .iter()
+      .filter(|field| FieldType::from(field.field_type).is_formula())
+      .flat_map(|field| field.get_type_option::<FormulaTypeOption>(FieldType::Formula))
+      .any(|type_option| type_option.is_time_dependent());
+    if has_time_dependent_formula {
+      self.refresh_all_formula_cells().await?;
+    }
     Ok(())
   }
 
+  /// Numbers the rows that don't have an auto number yet, and renumbers the pending numbers of
+  /// this device that are already taken. It runs when an auto number field is created or updated,
+  /// and with `is_synced` whenever the database is synced, see [resolve_auto_numbers].
+  pub async fn resolve_auto_numbers(&self, is_synced: bool) {
+    let changesets = resolve_auto_number_cells(&self.database.lock(), &self.device_id, is_synced);
+    notify_did_update_cell(changesets).await;
+  }
+
   pub fn get_auto_updated_fields_changesets(
     &self,
     view_id: &str,
@@ -916,6 +1412,145 @@ impl DatabaseEditor {
     Ok(())
   }
 
+  /// Assigns the persons to the cell and unassigns the `removed_user_ids`. The name and the
+  /// email of the inserted persons are saved in the field's type option.
+  pub async fn update_person_cell(
+    &self,
+    view_id: &str,
+    field_id: &str,
+    row_id: RowId,
+    inserted_persons: Vec<Person>,
+    removed_user_ids: Vec<String>,
+  ) -> FlowyResult<()> {
+    let field = self
+      .database
+      .lock()
+      .fields
+      .get_field(field_id)
+      .ok_or_else(|| {
+        FlowyError::record_not_found()
+          .with_context(format!("Field with id:{} not found", &field_id))
+      })?;
+    let field_type = FieldType::from(field.field_type);
+    if !field_type.is_person() {
+      return Err(FlowyError::invalid_data().with_context("The field is not a person field"));
+    }
+
+    let cell_changeset = PersonCellChangeset {
+      inserted_user_ids: inserted_persons
+        .iter()
+        .map(|person| person.id.clone())
+        .collect(),
+      removed_user_ids,
+    };
+    // Validates the new cell before writing the inserted persons to the type option, so a
+    // rejected changeset doesn't leave the persons behind.
+    let new_cell = self.apply_cell_changeset(&row_id, field_id, cell_changeset)?;
+
+    if !inserted_persons.is_empty() {
+      let member_emails = self.user.workspace_member_emails().await?;
+      if let Some(person) = inserted_persons
+        .iter()
+        .find(|person| !member_emails.contains(&person.id))
+      {
+        return Err(
+          FlowyError::invalid_data()
+            .with_context(format!("{} is not a member of the workspace", person.id)),
+        );
+      }
+
+      let mut type_option = field
+        .get_type_option::<PersonTypeOption>(field_type)
+        .unwrap_or_default();
+      inserted_persons
+        .into_iter()
+        .for_each(|person| type_option.insert_person(person));
+
+      let view_editors = self.database_views.editors().await;
+      update_field_type_option_fn(
+        &self.database,
+        &view_editors,
+        type_option.into(),
+        field.clone(),
+      )
+      .await?;
+    }
+
+    self.update_cell(view_id, row_id, field_id, new_cell).await
+  }
+
+  /// Uploads the files on the user's disk and attaches them to the cell. The files removed from
+  /// the cell are deleted from the storage if no other cell references them.
+  pub async fn update_files_cell(
+    &self,
+    view_id: &str,
+    field_id: &str,
+    row_id: RowId,
+    local_file_paths: Vec<String>,
+    removed_file_ids: Vec<String>,
+  ) -> FlowyResult<()> {
+    let field = self.get_field(field_id).ok_or_else(|| {
+      FlowyError::record_not_found().with_context(format!("Field with id:{} not found", &field_id))
+    })?;
+    if !FieldType::from(field.field_type).is_files() {
+      return Err(FlowyError::invalid_data().with_context("The field is not a files field"));
+    }
+
+    let database_id = self.database.lock().get_database_id();
+    let mut inserted_files = Vec::with_capacity(local_file_paths.len());
+    for local_file_path in local_file_paths {
+      let file = self
+        .file_storage
+        .upload_file(&database_id, &local_file_path)
+        .await?;
+      inserted_files.push(file);
+    }
+
+    let removed_urls = match self.get_cell(field_id, &row_id).await {
+      None => vec![],
+      Some(cell) => FilesCellData::from(&cell)
+        .files
+        .into_iter()
+        .filter(|file| removed_file_ids.contains(&file.id))
+        .map(|file| file.url)
+        .collect(),
+    };
+
+    let cell_changeset = FilesCellChangeset {
+      inserted_files,
+      removed_file_ids,
+    };
+    self
+      .update_cell_with_changeset(view_id, row_id, field_id, cell_changeset)
+      .await?;
+    self.delete_orphaned_files(removed_urls);
+    Ok(())
+  }
+
+  fn get_files_field_ids(&self) -> Vec<String> {
+    get_files_field_ids(&self.database.lock())
+  }
+
+  /// Deletes the objects that are not referenced by any files cell of the database anymore.
+  ///
+  /// The deletion is deferred, because the row or the cell might come back by undo or by the
+  /// changes of other devices. The pending deletions are persisted and the references are
+  /// checked again right before deleting.
+  fn delete_orphaned_files(&self, mut urls: Vec<String>) {
+    urls.sort();
+    urls.dedup();
+    if urls.is_empty() {
+      return;
+    }
+
+    let database_id = self.database.lock().get_database_id();
+    if let Err(err) = self.file_storage.add_pending_deletions(&database_id, &urls) {
+      error!("Failed to record the orphaned files: {}", err);
+      return;
+    }
+    sweep_orphaned_files(&self.database, &self.file_storage);
+  }
+
   pub async fn get_select_options(&self, row_id: RowId, field_id: &str) -> SelectOptionCellDataPB {
     let field = self.database.lock().fields.get_field(field_id);
     match field {
@@ -958,10 +1593,204 @@ impl DatabaseEditor {
     Ok(())
   }
 
-  #[tracing::instrument(level = "trace", skip_all, err)]
-  pub async fn load_groups(&self, view_id: &str) -> FlowyResult<RepeatedGroupPB> {
-    let view = self.database_views.get_view_editor(view_id).await?;
-    let groups = view.v_load_groups().await.unwrap_or_default();
+  /// Returns the id and the primary cell content of the given rows. It's used by the relation
+  /// field of other databases to display the linked rows. All the rows will be returned if the
+  /// `row_ids` is empty.
+  pub async fn get_related_rows(&self, row_ids: &[RowId]) -> FlowyResult<Vec<RelatedRowDataPB>> {
+    get_related_rows(&self.database.lock(), row_ids)
+  }
+
+  /// Returns the primary cell text of the rows that the relation cells of the database link to.
+  async fn get_related_row_names(&self) -> RelatedRowNames {
+    let row_ids_by_database_id = {
+      let database = self.database.lock();
+      let relation_fields = database
+        .get_fields(None)
+        .into_iter()
+        .filter(|field| FieldType::from(field.field_type).is_relation())
+        .flat_map(|field| {
+          let type_option = field.get_type_option::<RelationTypeOption>(FieldType::Relation)?;
+          Some((field.id, type_option.database_id))
+        })
+        .collect::<Vec<_>>();
+
+      let mut row_ids_by_database_id: HashMap<String, HashSet<RowId>> = HashMap::new();
+      if !relation_fields.is_empty() {
+        let inline_view_id = database.get_inline_view_id();
+        for row in database.get_rows_for_view(&inline_view_id) {
+          for (field_id, database_id) in &relation_fields {
+            if let Some(cell) = row.cells.get(field_id) {
+              row_ids_by_database_id
+                .entry(database_id.clone())
+                .or_default()
+                .extend(RelationCellData::from(cell).row_ids);
+            }
+          }
+        }
+      }
+      row_ids_by_database_id
+    };
+    if row_ids_by_database_id.is_empty() {
+      return RelatedRowNames::default();
+    }
+
+    let workspace_database = self.workspace_database.read().await.clone();
+    let mut names = HashMap::new();
+    for (database_id, row_ids) in row_ids_by_database_id {
+      let related_database = match &workspace_database {
+        None => None,
+        Some(workspace_database) => workspace_database.get_database(&database_id).await,
+      };
+      let row_ids = row_ids.into_iter().collect::<Vec<_>>();
+      // The rows of a database that can't be opened are exported by their ids.
+      if let Some(Ok(related_rows)) =
+        related_database.map(|database| get_related_rows(&database.lock(), &row_ids))
+      {
+        names.extend(
+          related_rows
+            .into_iter()
+            .map(|related_row| (related_row.row_id, related_row.name)),
+        );
+      }
+    }
+    RelatedRowNames::new(names)
+  }
+
+  /// Returns the text of the cells of the given rows. The rows that don't exist are skipped. All
+  /// the rows will be returned if the `row_ids` is empty.
+  pub fn get_rows_search_text(&self, row_ids: &[RowId]) -> Vec<RowSearchText> {
+    let database = self.database.lock();
+    let inline_view_id = database.get_inline_view_id();
+    let fields = database.get_fields_in_view(&inline_view_id, None);
+    let rows = if row_ids.is_empty() {
+      database.get_rows_for_view(&inline_view_id)
+    } else {
+      row_ids
+        .iter()
+        .filter(|row_id| database.views.is_row_exist(&inline_view_id, row_id))
+        .map(|row_id| database.get_row(row_id))
+        .collect()
+    };
+
+    rows
+      .into_iter()
+      .map(|row| {
+        let text = fields
+          .iter()
+          .map(|field| stringify_row_cell(&row, field))
+          .filter(|cell| !cell.trim().is_empty())
+          .collect::<Vec<String>>()
+          .join("\n");
+        RowSearchText {
+          view_id: inline_view_id.clone(),
+          row_id: row.id.into_inner(),
+          text,
+        }
+      })
+      .collect()
+  }
+
+  /// Returns all the lookup fields of the database with their type options.
+  pub fn get_lookup_fields(&self) -> Vec<(Field, LookupTypeOption)> {
+    self
+      .database
+      .lock()
+      .get_fields(None)
+      .into_iter()
+      .filter(|field| FieldType::from(field.field_type).is_lookup())
+      .flat_map(|field| {
+        let type_option = field.get_type_option::<LookupTypeOption>(FieldType::Lookup)?;
+        Some((field, type_option))
+      })
+      .collect()
+  }
+
+  /// Recomputes the cells of the lookup fields that aggregate the rows of the related database
+  /// and match the `predicate`. Only the cells whose value was changed are written back.
+  ///
+  /// Returns the ids of the lookup fields that had at least one cell changed.
+  #[tracing::instrument(level = "trace", skip(self, related_database, predicate), err)]
+  pub async fn refresh_lookup_fields<P>(
+    &self,
+    related_database_id: &str,
+    related_database: &MutexDatabase,
+    predicate: P,
+  ) -> FlowyResult<Vec<String>>
+  where
+    P: Fn(&Field, &LookupTypeOption) -> bool,
+  {
+    let lookup_fields = self
+      .get_lookup_fields()
+      .into_iter()
+      .filter(|(field, type_option)| {
+        type_option.database_id == related_database_id && predicate(field, type_option)
+      });
+
+    let mut changed_field_ids = vec![];
+
+    for (field, type_option) in lookup_fields {
+      // Don't hold the locks of both databases at the same time. The related database might be
+      // the current database.
+      let related_rows = {
+        let related_database = related_database.lock();
+        let target_field = match related_database
+          .fields
+          .get_field(&type_option.target_field_id)
+        {
+          None => continue,
+          Some(target_field) => target_field,
+        };
+        let key_field = related_database
+          .fields
+          .get_field(&type_option.related_key_field_id);
+        let inline_view_id = related_database.get_inline_view_id();
+        let rows = related_database.get_rows_for_view(&inline_view_id);
+        LookupRelatedRows::new(key_field.as_ref(), target_field, rows)
+      };
+
+      let (inline_view_id, changed_cells) = {
+        let database = self.database.lock();
+        let key_field = match database.fields.get_field(&type_option.key_field_id) {
+          None => continue,
+          Some(key_field) => key_field,
+        };
+        let inline_view_id = database.get_inline_view_id();
+        let changed_cells = database
+          .get_rows_for_view(&inline_view_id)
+          .into_iter()
+          .flat_map(|row| {
+            let value = type_option.lookup(&row, &key_field, &related_rows);
+            let old_cell_data = row
+              .cells
+              .get(&field.id)
+              .map(LookupCellData::from)
+              .unwrap_or_default();
+            if old_cell_data.0 == value {
+              None
+            } else {
+              Some((row.id, Cell::from(LookupCellData::new(value))))
+            }
+          })
+          .collect::<Vec<_>>();
+        (inline_view_id, changed_cells)
+      };
+
+      if !changed_cells.is_empty() {
+        changed_field_ids.push(field.id.clone());
+      }
+      for (row_id, cell) in changed_cells {
+        self
+          .update_cell(&inline_view_id, row_id, &field.id, cell)
+          .await?;
+      }
+    }
+    Ok(changed_field_ids)
+  }
+
+  #[tracing::instrument(level = "trace", skip_all, err)]
+  pub async fn load_groups(&self, view_id: &str) -> FlowyResult<RepeatedGroupPB> {
+    let view = self.database_views.get_view_editor(view_id).await?;
+    let groups = view.v_load_groups().await.unwrap_or_default();
     Ok(RepeatedGroupPB { items: groups })
   }
 
@@ -997,6 +1826,7 @@ impl DatabaseEditor {
     to_group: &str,
     from_row: RowId,
     to_row: Option<RowId>,
+    to_swimlane: Option<String>,
   ) -> FlowyResult<()> {
     let row_detail = self.get_row_detail(view_id, &from_row);
     match row_detail {
@@ -1010,7 +1840,13 @@ impl DatabaseEditor {
         let view = self.database_views.get_view_editor(view_id).await?;
         let mut row_changeset = RowChangeset::new(row_detail.row.id.clone());
         view
-          .v_move_group_row(&row_detail, &mut row_changeset, to_group, to_row.clone())
+          .v_move_group_row(
+            &row_detail,
+            &mut row_changeset,
+            to_group,
+            to_swimlane.as_deref(),
+            to_row.clone(),
+          )
           .await;
 
         let to_row = if to_row.is_some() {
@@ -1025,7 +1861,7 @@ impl DatabaseEditor {
           self.move_row(view_id, from_row.clone(), row_id).await?;
         }
 
-        if from_group == to_group {
+        if from_group == to_group && to_swimlane.is_none() {
           return Ok(());
         }
 
@@ -1052,6 +1888,18 @@ impl DatabaseEditor {
     Ok(())
   }
 
+  pub async fn set_swimlane_field(&self, view_id: &str, field_id: Option<&str>) -> FlowyResult<()> {
+    let view = self.database_views.get_view_editor(view_id).await?;
+    view.v_set_swimlane_field(field_id).await?;
+    Ok(())
+  }
+
+  pub async fn get_swimlanes(&self, view_id: &str) -> FlowyResult<RepeatedSwimlanePB> {
+    let view = self.database_views.get_view_editor(view_id).await?;
+    let swimlanes = view.v_get_swimlanes().await.unwrap_or_default();
+    Ok(RepeatedSwimlanePB { items: swimlanes })
+  }
+
   pub async fn create_group(&self, view_id: &str, name: &str) -> FlowyResult<()> {
     let view_editor = self.database_views.get_view_editor(view_id).await?;
     view_editor.v_create_group(name).await?;
@@ -1105,11 +1953,145 @@ impl DatabaseEditor {
     view.v_get_calendar_event(row_id).await
   }
 
+  /// Moves the calendar event to the `timestamp`. The events that span multiple days keep their
+  /// duration.
+  pub async fn move_calendar_event(
+    &self,
+    view_id: &str,
+    row_id: RowId,
+    field_id: &str,
+    timestamp: i64,
+  ) -> FlowyResult<()> {
+    let cell_data = self
+      .get_cell(field_id, &row_id)
+      .await
+      .map(|cell| DateCellData::from(&cell))
+      .unwrap_or_default();
+    let end_date = match (cell_data.timestamp, cell_data.end_timestamp) {
+      (Some(start), Some(end)) if cell_data.is_range => Some(timestamp + end - start),
+      _ => None,
+    };
+    let cell_changeset = DateCellChangeset {
+      date: Some(timestamp),
+      end_date,
+      ..Default::default()
+    };
+    self
+      .update_cell_with_changeset(view_id, row_id, field_id, cell_changeset)
+      .await
+  }
+
+  #[tracing::instrument(level = "trace", skip_all)]
+  pub async fn get_all_timeline_spans(&self, view_id: &str) -> Vec<TimelineSpanPB> {
+    match self.database_views.get_view_editor(view_id).await {
+      Ok(view) => view.v_get_all_timeline_spans().await.unwrap_or_default(),
+      Err(_) => {
+        warn!("Can not find the view: {}", view_id);
+        vec![]
+      },
+    }
+  }
+
+  #[tracing::instrument(level = "trace", skip_all)]
+  pub async fn get_all_no_date_timeline_rows(&self, view_id: &str) -> Vec<NoDateTimelineRowPB> {
+    match self.database_views.get_view_editor(view_id).await {
+      Ok(view) => view.v_get_no_date_timeline_rows().await.unwrap_or_default(),
+      Err(_) => {
+        warn!("Can not find the view: {}", view_id);
+        vec![]
+      },
+    }
+  }
+
+  /// Moves the span of the row to the `start`, keeping its duration. A row without dates is
+  /// scheduled on the `start`.
+  pub async fn move_timeline_span(
+    &self,
+    view_id: &str,
+    row_id: RowId,
+    start: i64,
+  ) -> FlowyResult<()> {
+    let view = self.database_views.get_view_editor(view_id).await?;
+    let duration = view
+      .v_get_timeline_span(&row_id)
+      .await
+      .and_then(|span| span.end.checked_sub(span.start))
+      .unwrap_or_default();
+    let end = start.checked_add(duration).ok_or_else(|| {
+      FlowyError::invalid_data().with_context("The end of the span is out of range")
+    })?;
+    self.resize_timeline_span(view_id, row_id, start, end).await
+  }
+
+  /// Updates the date cells of the row's span. If the timeline has no end field, the end is saved
+  /// as the end date of the start field's date range.
+  pub async fn resize_timeline_span(
+    &self,
+    view_id: &str,
+    row_id: RowId,
+    start: i64,
+    end: i64,
+  ) -> FlowyResult<()> {
+    if end < start {
+      return Err(
+        FlowyError::invalid_data().with_context("The end of the span can't be before its start"),
+      );
+    }
+    let view = self.database_views.get_view_editor(view_id).await?;
+    let timeline_setting = view.v_get_timeline_setting().await.ok_or_else(|| {
+      FlowyError::invalid_data().with_context("The view isn't displayed as a timeline")
+    })?;
+
+    let cells = match timeline_setting.end_field_id {
+      Some(end_field_id) => {
+        let start_changeset = DateCellChangeset {
+          date: Some(start),
+          ..Default::default()
+        };
+        let end_changeset = DateCellChangeset {
+          date: Some(end),
+          ..Default::default()
+        };
+        vec![
+          (
+            timeline_setting.start_field_id.clone(),
+            self.apply_cell_changeset(
+              &row_id,
+              &timeline_setting.start_field_id,
+              start_changeset,
+            )?,
+          ),
+          (
+            end_field_id.clone(),
+            self.apply_cell_changeset(&row_id, &end_field_id, end_changeset)?,
+          ),
+        ]
+      },
+      None => {
+        let changeset = DateCellChangeset {
+          date: Some(start),
+          end_date: Some(end),
+          is_range: Some(true),
+          ..Default::default()
+        };
+        vec![(
+          timeline_setting.start_field_id.clone(),
+          self.apply_cell_changeset(&row_id, &timeline_setting.start_field_id, changeset)?,
+        )]
+      },
+    };
+    // Write the start and the end at once, so the span is never seen with only one of them
+    self.update_cells(view_id, row_id, cells).await
+  }
+
   #[tracing::instrument(level = "trace", skip_all, err)]
   async fn notify_did_insert_database_field(&self, field: Field, index: usize) -> FlowyResult<()> {
-    let database_id = self.database.lock().get_database_id();
+    let (database_id, fields) = {
+      let database = self.database.lock();
+      (database.get_database_id(), database.get_fields(None))
+    };
     let index_field = IndexFieldPB {
-      field: FieldPB::new(field),
+      field: FieldPB::new_with_fields(field, &fields),
       index: index as i32,
     };
     let notified_changeset = DatabaseFieldChangesetPB::insert(&database_id, vec![index_field]);
@@ -1170,16 +2152,20 @@ impl DatabaseEditor {
       id: database_id,
       fields,
       rows,
-      layout_type: view.layout.into(),
+      layout_type: DatabaseLayoutPB::from_view_layout(
+        view.layout,
+        view.layout_settings.get(&view.layout),
+      ),
       is_linked,
     })
   }
 
   pub async fn export_csv(&self, style: CSVFormat) -> FlowyResult<String> {
+    let related_row_names = self.get_csv_related_row_names(style).await;
     let database = self.database.clone();
     let csv = tokio::task::spawn_blocking(move || {
       let database_guard = database.lock();
-      let csv = CSVExport.export_database(&database_guard, style)?;
+      let csv = CSVExport.export_database(&database_guard, style, &related_row_names)?;
       Ok::<String, FlowyError>(csv)
     })
     .await
@@ -1187,6 +2173,140 @@ impl DatabaseEditor {
     Ok(csv)
   }
 
+  /// Exports the database to the CSV file at `path` without building the whole CSV in memory.
+  /// The database is only locked while the rows are read, not while the file is written.
+  pub async fn export_csv_to_file(&self, style: CSVFormat, path: String) -> FlowyResult<()> {
+    let related_row_names = self.get_csv_related_row_names(style).await;
+    let database = self.database.clone();
+    tokio::task::spawn_blocking(move || {
+      let data = CSVExportData::from_database(&database.lock());
+      CSVExport.export_data_to_file(data, style, &related_row_names, &path)
+    })
+    .await
+    .map_err(internal_error)??;
+    Ok(())
+  }
+
+  /// The [CSVFormat::META] format keeps the row ids of the relation cells, so the database can be
+  /// restored from it.
+  async fn get_csv_related_row_names(&self, style: CSVFormat) -> RelatedRowNames {
+    match style {
+      CSVFormat::Original => self.get_related_row_names().await,
+      CSVFormat::META => RelatedRowNames::default(),
+    }
+  }
+
+  pub async fn export_json(&self) -> FlowyResult<String> {
+    let related_row_names = self.get_related_row_names().await;
+    let database = self.database.clone();
+    let json = tokio::task::spawn_blocking(move || {
+      let database_guard = database.lock();
+      JSONExport.export_database(&database_guard, &related_row_names)
+    })
+    .await
+    .map_err(internal_error)??;
+    Ok(json)
+  }
+
+  /// Exports the rows of the view to Markdown tables, see [Self::get_view_export_data].
+  pub async fn export_markdown(
+    &self,
+    view_id: &str,
+    include_group_headers: bool,
+  ) -> FlowyResult<String> {
+    let data = self
+      .get_view_export_data(view_id, include_group_headers)
+      .await?;
+    Ok(MarkdownExport.export_view(&data))
+  }
+
+  /// Exports the rows of the view to CSV, see [Self::get_view_export_data]. Unlike
+  /// [Self::export_csv], which exports all the rows and fields of the database.
+  pub async fn export_view_csv(
+    &self,
+    view_id: &str,
+    style: CSVFormat,
+    include_group_headers: bool,
+  ) -> FlowyResult<String> {
+    let data = self
+      .get_view_export_data(view_id, include_group_headers)
+      .await?;
+    CSVExport.export_view(&data, style)
+  }
+
+  /// Returns the rows of the view as the user sees them: the rows are filtered and sorted as in
+  /// the view, and only the visible fields are exported, in the order of the view. If
+  /// `include_group_headers` is true and the view is grouped, e.g. a board, the rows are split by
+  /// the visible groups of the view.
+  async fn get_view_export_data(
+    &self,
+    view_id: &str,
+    include_group_headers: bool,
+  ) -> FlowyResult<ViewExportData> {
+    let hidden_field_ids = self
+      .get_all_field_settings(view_id)
+      .await?
+      .into_iter()
+      .filter(|field_settings| field_settings.visibility == FieldVisibility::AlwaysHidden)
+      .map(|field_settings| field_settings.field_id)
+      .collect::<HashSet<String>>();
+    let fields = self
+      .get_fields(view_id, None)
+      .into_iter()
+      .filter(|field| !hidden_field_ids.contains(&field.id))
+      .collect::<Vec<Field>>();
+    let rows = self.get_rows(view_id).await?;
+
+    let groups = if include_group_headers {
+      self.load_groups(view_id).await?.items
+    } else {
+      vec![]
+    };
+    let groups = if groups.is_empty() {
+      vec![ExportGroup { name: None, rows }]
+    } else {
+      groups
+        .into_iter()
+        .filter(|group| group.is_visible)
+        .map(|group| {
+          let row_ids = group
+            .rows
+            .iter()
+            .map(|row_meta| row_meta.id.as_str())
+            .collect::<HashSet<&str>>();
+          let rows = rows
+            .iter()
+            .filter(|row_detail| row_ids.contains(row_detail.row.id.as_str()))
+            .cloned()
+            .collect();
+          ExportGroup {
+            name: Some(group.group_name),
+            rows,
+          }
+        })
+        .collect()
+    };
+    let related_row_names = self.get_related_row_names().await;
+    Ok(ViewExportData {
+      fields,
+      groups,
+      related_row_names,
+    })
+  }
+
+  /// Exports the database to the XLSX file at `path`.
+  pub async fn export_xlsx_to_file(&self, path: String) -> FlowyResult<()> {
+    let related_row_names = self.get_related_row_names().await;
+    let database = self.database.clone();
+    tokio::task::spawn_blocking(move || {
+      let database_guard = database.lock();
+      XLSXExport.export_database_to_file(&database_guard, &related_row_names, &path)
+    })
+    .await
+    .map_err(internal_error)??;
+    Ok(())
+  }
+
   pub async fn get_field_settings(
     &self,
     view_id: &str,
@@ -1300,6 +2420,7 @@ fn cell_changesets_from_cell_by_field_id(
 }
 
 struct DatabaseViewOperationImpl {
+  user: Arc<dyn DatabaseUser>,
   database: Arc<MutexDatabase>,
   task_scheduler: Arc<RwLock<TaskDispatcher>>,
   cell_cache: CellCache,
@@ -1436,7 +2557,7 @@ impl DatabaseViewOperation for DatabaseViewOperationImpl {
   }
 
   fn get_cells_for_field(&self, view_id: &str, field_id: &str) -> Fut<Vec<Arc<RowCell>>> {
-    let cells = self.database.lock().get_cells_for_field(view_id, field_id);
+    let cells = get_cells_for_field(&self.database.lock(), view_id, field_id);
     to_fut(async move { cells.into_iter().map(Arc::new).collect() })
   }
 
@@ -1526,11 +2647,18 @@ impl DatabaseViewOperation for DatabaseViewOperationImpl {
       .get_filter::<Filter>(view_id, filter_id)
   }
 
-  fn get_filter_by_field_id(&self, view_id: &str, field_id: &str) -> Option<Filter> {
+  fn get_filters_by_field_id(&self, view_id: &str, field_id: &str) -> Vec<Filter> {
     self
       .database
       .lock()
-      .get_filter_by_field_id::<Filter>(view_id, field_id)
+      .get_all_filters::<Filter>(view_id)
+      .into_iter()
+      .filter(|filter| filter.field_id == field_id)
+      .collect()
+  }
+
+  fn get_current_user_email(&self) -> Option<String> {
+    self.user.user_email().ok()
   }
 
   fn get_layout_setting(&self, view_id: &str, layout_ty: &DatabaseLayout) -> Option<LayoutSetting> {
@@ -1700,23 +2828,61 @@ pub async fn update_field_type_option_fn(
   Ok(())
 }
 
+/// Returns the id and the primary cell content of the given rows, or of all the rows if the
+/// `row_ids` is empty. The rows that don't exist are skipped.
+fn get_related_rows(database: &Database, row_ids: &[RowId]) -> FlowyResult<Vec<RelatedRowDataPB>> {
+  let inline_view_id = database.get_inline_view_id();
+  let primary_field = database
+    .get_fields(None)
+    .into_iter()
+    .find(|field| field.is_primary)
+    .ok_or_else(|| FlowyError::record_not_found().with_context("Can't find the primary field"))?;
+  let field_type = FieldType::from(primary_field.field_type);
+
+  let rows = if row_ids.is_empty() {
+    database.get_rows_for_view(&inline_view_id)
+  } else {
+    row_ids
+      .iter()
+      .filter(|row_id| database.views.is_row_exist(&inline_view_id, row_id))
+      .map(|row_id| database.get_row(row_id))
+      .collect()
+  };
+
+  let related_rows = rows
+    .into_iter()
+    .map(|row| {
+      let name = row
+        .cells
+        .get(&primary_field.id)
+        .map(|cell| stringify_cell_data(cell, &field_type, &field_type, &primary_field))
+        .unwrap_or_default();
+      RelatedRowDataPB {
+        row_id: row.id.into_inner(),
+        name,
+      }
+    })
+    .collect();
+  Ok(related_rows)
+}
+
 #[tracing::instrument(level = "trace", skip_all, err)]
 fn notify_did_update_database_field(
   database: &Arc<MutexDatabase>,
   field_id: &str,
 ) -> FlowyResult<()> {
-  let (database_id, field, views) = {
+  let (database_id, fields, views) = {
     let database = database
       .try_lock()
       .ok_or(FlowyError::internal().with_context("fail to acquire the lock of database"))?;
     let database_id = database.get_database_id();
-    let field = database.fields.get_field(field_id);
+    let fields = database.get_fields(None);
     let views = database.get_all_database_views_meta();
-    (database_id, field, views)
+    (database_id, fields, views)
   };
 
-  if let Some(field) = field {
-    let updated_field = FieldPB::new(field);
+  if let Some(field) = fields.iter().find(|field| field.id == field_id) {
+    let updated_field = FieldPB::new_with_fields(field.clone(), &fields);
     let notified_changeset =
       DatabaseFieldChangesetPB::update(&database_id, vec![updated_field.clone()]);
 
@@ -1732,3 +2898,239 @@ fn notify_did_update_database_field(
   }
   Ok(())
 }
+
+/// Returns the validation rules of the field that are broken by the cell. The unique rule compares
+/// the cell with the cells of all the other rows of the database.
+fn validate_field_cell(
+  database: &Database,
+  field: &Field,
+  row_id: &RowId,
+  cell: Option<&Cell>,
+) -> Vec<FieldValidationErrorPB> {
+  let validation = match FieldValidation::from_field(field) {
+    None => return vec![],
+    Some(validation) => validation,
+  };
+
+  let mut errors = validation.validate_cell(field, row_id, cell);
+  if validation.unique && can_validate(&FieldType::from(field.field_type)) {
+    if let Some(value) = cell.and_then(|cell| unique_value(field, cell)) {
+      let inline_view_id = database.get_inline_view_id();
+      let is_duplicated = get_cells_for_field(database, &inline_view_id, &field.id)
+        .into_iter()
+        .filter(|row_cell| &row_cell.row_id != row_id)
+        .any(|row_cell| {
+          row_cell
+            .cell
+            .as_ref()
+            .and_then(|cell| unique_value(field, cell))
+            .as_ref()
+            == Some(&value)
+        });
+      if is_duplicated {
+        errors.push(unique_error(field, row_id));
+      }
+    }
+  }
+  errors
+}
+
+fn unique_error(field: &Field, row_id: &RowId) -> FieldValidationErrorPB {
+  FieldValidationErrorPB {
+    row_id: row_id.to_string(),
+    field_id: field.id.clone(),
+    rule: FieldValidationRulePB::Unique,
+    message: format!("{} must be unique", field.name),
+  }
+}
+
+fn get_auto_number_fields(database: &Database) -> Vec<(Field, AutoNumberTypeOption)> {
+  database
+    .get_fields(None)
+    .into_iter()
+    .filter(|field| FieldType::from(field.field_type).is_auto_number())
+    .map(|field| {
+      let type_option = field
+        .get_type_option::<AutoNumberTypeOption>(FieldType::AutoNumber)
+        .unwrap_or_default();
+      (field, type_option)
+    })
+    .collect()
+}
+
+/// Returns the auto numbers of the rows. The cells that were written by another field type, before
+/// the field was switched to [FieldType::AutoNumber], don't have a number.
+fn get_auto_number_rows(database: &Database, field_id: &str) -> Vec<AutoNumberRow> {
+  let inline_view_id = database.get_inline_view_id();
+  database
+    .get_rows_for_view(&inline_view_id)
+    .into_iter()
+    .map(|row| {
+      let cell_data = row
+        .cells
+        .get(field_id)
+        .filter(|cell| get_field_type_from_cell::<FieldType>(cell) == Some(FieldType::AutoNumber))
+        .map(AutoNumberCellData::from)
+        .unwrap_or_default();
+      AutoNumberRow {
+        row_id: row.id,
+        created_at: row.created_at,
+        cell_data,
+      }
+    })
+    .collect()
+}
+
+fn update_auto_number_type_option(
+  database: &Database,
+  field_id: &str,
+  type_option: AutoNumberTypeOption,
+) {
+  database.fields.update_field(field_id, |update| {
+    update.update_type_options(|type_options_update| {
+      type_options_update.insert(&FieldType::AutoNumber.to_string(), type_option.into());
+    });
+  });
+}
+
+/// Assigns the next number of each auto number field to the new row. The cells copied from the
+/// original row by `duplicate_row` are overwritten, so the duplicated row gets its own number.
+///
+/// The rows aren't scanned, the `next_number` of the type option is trusted. The number stays
+/// pending until the database is synced, the numbers taken by the rows of other devices are
+/// resolved by [resolve_auto_number_cells].
+fn allocate_auto_numbers(database: &Database, cells: &mut Cells, device_id: &str) {
+  for (field, mut type_option) in get_auto_number_fields(database) {
+    let number = type_option.allocate();
+    let cell_data = AutoNumberCellData::new_pending(number, device_id.to_owned());
+    cells.insert(field.id.clone(), Cell::from(cell_data));
+    update_auto_number_type_option(database, &field.id, type_option);
+  }
+}
+
+/// How long the objects that are no longer referenced by any files cell are kept before they
+/// are deleted.
+const ORPHANED_FILES_GRACE_PERIOD: Duration = Duration::from_secs(60 * 60);
+
+/// Deletes the orphaned objects of the database whose grace period is over, then waits for the
+/// grace period of the next ones as long as the database is open. The deletions that were left
+/// pending when the application was closed are swept when the database is opened again.
+fn sweep_orphaned_files(database: &Arc<MutexDatabase>, file_storage: &DatabaseFileStorage) {
+  let weak_database = Arc::downgrade(database);
+  let file_storage = file_storage.clone();
+  af_spawn(async move {
+    loop {
+      let next_expiration = {
+        let database = match weak_database.upgrade() {
+          None => break,
+          Some(database) => database,
+        };
+        let database_id = database.lock().get_database_id();
+        let (mut urls, next_expiration) = match file_storage
+          .take_expired_pending_deletions(&database_id, ORPHANED_FILES_GRACE_PERIOD)
+        {
+          Ok(result) => result,
+          Err(err) => {
+            error!("Failed to read the orphaned files: {}", err);
+            break;
+          },
+        };
+        retain_unreferenced_file_urls(&database.lock(), &mut urls);
+        file_storage.delete_files(urls);
+        next_expiration
+      };
+
+      match next_expiration {
+        None => break,
+        Some(duration) => tokio::time::sleep(duration).await,
+      }
+    }
+  });
+}
+
+fn get_files_field_ids(database: &Database) -> Vec<String> {
+  database
+    .get_fields(None)
+    .into_iter()
+    .filter(|field| FieldType::from(field.field_type).is_files())
+    .map(|field| field.id)
+    .collect()
+}
+
+/// Removes the urls that are referenced by a files cell of the database.
+fn retain_unreferenced_file_urls(database: &Database, urls: &mut Vec<String>) {
+  let files_field_ids = get_files_field_ids(database);
+  let inline_view_id = database.get_inline_view_id();
+  for row in database.get_rows_for_view(&inline_view_id) {
+    for cell in files_field_ids
+      .iter()
+      .filter_map(|field_id| row.cells.get(field_id))
+    {
+      let cell_data = FilesCellData::from(cell);
+      urls.retain(|url| cell_data.urls().all(|referenced_url| referenced_url != url));
+    }
+  }
+}
+
+/// Applies [resolve_auto_numbers] to every auto number field of the database and returns the
+/// cells that were changed.
+fn resolve_auto_number_cells(
+  database: &Database,
+  device_id: &str,
+  is_synced: bool,
+) -> Vec<CellChangesetNotifyPB> {
+  let inline_view_id = database.get_inline_view_id();
+  let mut changesets = vec![];
+  for (field, mut type_option) in get_auto_number_fields(database) {
+    let rows = get_auto_number_rows(database, &field.id);
+    let (changes, next_number) =
+      resolve_auto_numbers(type_option.next_number, rows, device_id, is_synced);
+    for (row_id, cell_data) in changes {
+      database.update_row(&row_id, |row_update| {
+        row_update.update_cells(|cell_update| {
+          cell_update.insert(&field.id, Cell::from(cell_data));
+        });
+      });
+      changesets.push(CellChangesetNotifyPB {
+        view_id: inline_view_id.clone(),
+        row_id: row_id.into_inner(),
+        field_id: field.id.clone(),
+      });
+    }
+
+    if next_number != type_option.next_number {
+      type_option.next_number = next_number;
+      update_auto_number_type_option(database, &field.id, type_option);
+    }
+  }
+  changesets
+}
+
+/// Returns the cells of the field in the view. The cells of the [FieldType::LastEditedTime] and
+/// [FieldType::CreatedTime] fields are not stored in the rows, they are generated from the
+/// timestamps of the rows.
+fn get_cells_for_field(database: &Database, view_id: &str, field_id: &str) -> Vec<RowCell> {
+  if let Some(field) = database.fields.get_field(field_id) {
+    let field_type = FieldType::from(field.field_type);
+    match field_type {
+      FieldType::LastEditedTime | FieldType::CreatedTime => database
+        .get_rows_for_view(view_id)
+        .into_iter()
+        .map(|row| {
+          let data = if field_type.is_created_time() {
+            TimestampCellData::new(row.created_at)
+          } else {
+            TimestampCellData::new(row.modified_at)
+          };
+          RowCell {
+            row_id: row.id,
+            cell: Some(Cell::from(data)),
+          }
+        })
+        .collect(),
+      _ => database.get_cells_for_field(view_id, field_id),
+    }
+  } else {
+    vec![]
+  }
+}
@@ -12,6 +12,27 @@ pub enum DatabaseRowEvent {
   },
 }
 
+/// Emitted by the [DatabaseEditor](crate::services::database::DatabaseEditor) after the data of
+/// the database was changed. It's used to recompute the lookup fields that depend on the database
+/// and to keep the search index up to date.
+#[derive(Debug, Clone)]
+pub enum DatabaseDataChanged {
+  DidUpdateCell {
+    field_id: String,
+    row_id: String,
+  },
+  DidCreateRow {
+    row_id: String,
+  },
+  DidDeleteRow {
+    row_id: String,
+  },
+  /// A lookup field was created, or its type option was updated.
+  DidUpdateLookupField {
+    field_id: String,
+  },
+}
+
 #[derive(Debug, Clone)]
 pub struct InsertedRow {
   pub row_detail: RowDetail,
@@ -64,3 +85,13 @@ pub struct CreateDatabaseViewParams {
   pub view_id: String,
   pub layout_type: DatabaseLayout,
 }
+
+/// The text of the cells of a row. It's used to index the row for searching.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowSearchText {
+  /// The inline view of the database that the row belongs to.
+  pub view_id: String,
+  pub row_id: String,
+  /// The non-empty cells of the row, one per line, in the order of the fields.
+  pub text: String,
+}
@@ -0,0 +1,130 @@
+use std::path::Path;
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use tracing::error;
+
+use flowy_error::{FlowyError, FlowyResult};
+use flowy_storage::{upload_file_from_disk, FileStoragePlan, ObjectStorageService, StorageObject};
+use lib_dispatch::prelude::af_spawn;
+
+use crate::services::database::file_storage_sql::PendingFileDeletionSql;
+use crate::services::field::FileEntry;
+use crate::DatabaseUser;
+
+/// Uploads the files of the files cells to the [ObjectStorageService] and deletes them once
+/// they are not referenced by any cell.
+#[derive(Clone)]
+pub struct DatabaseFileStorage {
+  user: Arc<dyn DatabaseUser>,
+  storage_service: Weak<dyn ObjectStorageService>,
+  storage_plan: Weak<dyn FileStoragePlan>,
+}
+
+impl DatabaseFileStorage {
+  pub fn new(
+    user: Arc<dyn DatabaseUser>,
+    storage_service: Weak<dyn ObjectStorageService>,
+    storage_plan: Weak<dyn FileStoragePlan>,
+  ) -> Self {
+    Self {
+      user,
+      storage_service,
+      storage_plan,
+    }
+  }
+
+  /// Uploads the file on the user's disk the same way the document uploads its images. The size
+  /// of the file is checked against the [FileStoragePlan] before the file is read.
+  pub async fn upload_file(
+    &self,
+    database_id: &str,
+    local_file_path: &str,
+  ) -> FlowyResult<FileEntry> {
+    let workspace_id = self.user.workspace_id()?;
+    let file_name = Path::new(local_file_path)
+      .file_name()
+      .and_then(std::ffi::OsStr::to_str)
+      .unwrap_or_default()
+      .to_owned();
+    let size = tokio::fs::metadata(local_file_path).await?.len();
+    if let Some(storage_plan) = self.storage_plan.upgrade() {
+      let object = StorageObject::from_file(&workspace_id, &file_name, local_file_path);
+      storage_plan.check_upload_object(&object).await?;
+    }
+
+    // The objects are identified by the hash of their content. Prefix the id with the database
+    // id so the cleanup of this database never deletes an object that a document or another
+    // database uploaded.
+    let url = upload_file_from_disk(
+      self.storage_service_upgrade()?,
+      &workspace_id,
+      local_file_path,
+      Some(database_id),
+      false,
+    )
+    .await?;
+    let mime = mime_guess::from_path(local_file_path)
+      .first_or_octet_stream()
+      .to_string();
+    Ok(FileEntry::new(file_name, url, mime, size as i64))
+  }
+
+  /// Records the objects as unreferenced, so they are deleted once the grace period is over even
+  /// if the application is closed in the meantime.
+  pub fn add_pending_deletions(&self, database_id: &str, urls: &[String]) -> FlowyResult<()> {
+    let uid = self.user.user_id()?;
+    let mut conn = self.user.sqlite_connection(uid)?;
+    PendingFileDeletionSql::insert(database_id, urls, timestamp(), &mut conn)
+  }
+
+  /// Takes the objects of the database whose grace period is over. Returns their urls and the
+  /// time until the grace period of the next object is over, if any.
+  pub fn take_expired_pending_deletions(
+    &self,
+    database_id: &str,
+    grace_period: Duration,
+  ) -> FlowyResult<(Vec<String>, Option<Duration>)> {
+    let uid = self.user.user_id()?;
+    let mut conn = self.user.sqlite_connection(uid)?;
+    let grace_period = grace_period.as_secs() as i64;
+    let now = timestamp();
+    let (urls, next_deleted_at) =
+      PendingFileDeletionSql::take_expired(database_id, now - grace_period, &mut conn)?;
+    let next_expiration = next_deleted_at.map(|deleted_at| {
+      let seconds = (deleted_at + grace_period - now).max(0);
+      Duration::from_secs(seconds as u64)
+    });
+    Ok((urls, next_expiration))
+  }
+
+  /// Deletes the objects in the background.
+  pub fn delete_files(&self, urls: Vec<String>) {
+    if urls.is_empty() {
+      return;
+    }
+
+    match self.storage_service.upgrade() {
+      None => error!("Can't delete the files, the storage service is dropped"),
+      Some(storage_service) => {
+        af_spawn(async move {
+          for url in urls {
+            if let Err(err) = storage_service.delete_object(url.clone()).await {
+              error!("Delete file:{} failed: {}", url, err);
+            }
+          }
+        });
+      },
+    }
+  }
+
+  fn storage_service_upgrade(&self) -> FlowyResult<Arc<dyn ObjectStorageService>> {
+    self.storage_service.upgrade().ok_or_else(|| {
+      FlowyError::internal().with_context("The file storage service is already dropped")
+    })
+  }
+}
+
+fn timestamp() -> i64 {
+  chrono::Utc::now().timestamp()
+}
@@ -0,0 +1,99 @@
+use diesel::dsl::min;
+use diesel::{delete, replace_into, SqliteConnection};
+use flowy_error::FlowyError;
+use flowy_sqlite::{
+  prelude::*,
+  schema::{pending_file_deletion, pending_file_deletion::dsl},
+  Error,
+};
+
+/// Persists the objects that are waiting to be deleted, so the deletions survive restarts.
+pub(crate) struct PendingFileDeletionSql;
+impl PendingFileDeletionSql {
+  /// Records the objects as unreferenced since `deleted_at`. The timestamp of the objects that
+  /// were already recorded is reset.
+  pub(crate) fn insert(
+    database_id: &str,
+    urls: &[String],
+    deleted_at: i64,
+    conn: &mut SqliteConnection,
+  ) -> Result<(), FlowyError> {
+    conn.immediate_transaction::<_, Error, _>(|conn| {
+      for url in urls {
+        replace_into(pending_file_deletion::table)
+          .values((
+            dsl::url.eq(url),
+            dsl::database_id.eq(database_id),
+            dsl::deleted_at.eq(deleted_at),
+          ))
+          .execute(conn)?;
+      }
+      Ok(())
+    })?;
+    Ok(())
+  }
+
+  /// Removes the objects of the database that were recorded at or before `deleted_before` and
+  /// returns their urls, together with the earliest timestamp of the objects that remain.
+  pub(crate) fn take_expired(
+    database_id: &str,
+    deleted_before: i64,
+    conn: &mut SqliteConnection,
+  ) -> Result<(Vec<String>, Option<i64>), FlowyError> {
+    let result = conn.immediate_transaction::<_, Error, _>(|conn| {
+      let expired = dsl::pending_file_deletion
+        .filter(dsl::database_id.eq(database_id))
+        .filter(dsl::deleted_at.le(deleted_before));
+      let urls = expired.clone().select(dsl::url).load::<String>(conn)?;
+      delete(expired).execute(conn)?;
+      let next_deleted_at = dsl::pending_file_deletion
+        .filter(dsl::database_id.eq(database_id))
+        .select(min(dsl::deleted_at))
+        .first::<Option<i64>>(conn)?;
+      Ok((urls, next_deleted_at))
+    })?;
+    Ok(result)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use tempfile::TempDir;
+
+  use crate::services::database::file_storage_sql::PendingFileDeletionSql;
+
+  #[test]
+  fn take_expired_pending_deletions_test() {
+    let dir = TempDir::new().unwrap();
+    let database = flowy_sqlite::init(dir.path()).unwrap();
+    let mut conn = database.get_connection().unwrap();
+    let urls = |urls: &[&str]| urls.iter().map(|url| url.to_string()).collect::<Vec<_>>();
+    PendingFileDeletionSql::insert("d1", &urls(&["a", "b"]), 100, &mut conn).unwrap();
+    PendingFileDeletionSql::insert("d1", &urls(&["c"]), 200, &mut conn).unwrap();
+    PendingFileDeletionSql::insert("d2", &urls(&["d"]), 100, &mut conn).unwrap();
+
+    // Nothing is expired yet
+    let (expired, next) = PendingFileDeletionSql::take_expired("d1", 50, &mut conn).unwrap();
+    assert!(expired.is_empty());
+    assert_eq!(next, Some(100));
+
+    // Only the objects of the database are taken
+    let (mut expired, next) = PendingFileDeletionSql::take_expired("d1", 150, &mut conn).unwrap();
+    expired.sort();
+    assert_eq!(expired, urls(&["a", "b"]));
+    assert_eq!(next, Some(200));
+
+    // Recording an object again resets its timestamp
+    PendingFileDeletionSql::insert("d1", &urls(&["c"]), 300, &mut conn).unwrap();
+    let (expired, next) = PendingFileDeletionSql::take_expired("d1", 250, &mut conn).unwrap();
+    assert!(expired.is_empty());
+    assert_eq!(next, Some(300));
+
+    let (expired, next) = PendingFileDeletionSql::take_expired("d1", 300, &mut conn).unwrap();
+    assert_eq!(expired, urls(&["c"]));
+    assert_eq!(next, None);
+
+    let (expired, _) = PendingFileDeletionSql::take_expired("d2", 300, &mut conn).unwrap();
+    assert_eq!(expired, urls(&["d"]));
+  }
+}
@@ -1,7 +1,10 @@
 mod database_editor;
 mod entities;
+mod file_storage;
+mod file_storage_sql;
 mod util;
 
 pub use database_editor::*;
 pub use entities::*;
+pub use file_storage::*;
 pub(crate) use util::database_view_setting_pb_from_view;
@@ -7,10 +7,12 @@ use crate::entities::{
 use crate::services::field_settings::FieldSettings;
 use crate::services::filter::Filter;
 use crate::services::group::GroupSetting;
+use crate::services::setting::TimelineLayoutSetting;
 use crate::services::sort::Sort;
 
 pub(crate) fn database_view_setting_pb_from_view(view: DatabaseView) -> DatabaseViewSettingPB {
-  let layout_type: DatabaseLayoutPB = view.layout.into();
+  let layout_type =
+    DatabaseLayoutPB::from_view_layout(view.layout, view.layout_settings.get(&view.layout));
   let layout_setting = if let Some(layout_setting) = view.layout_settings.get(&view.layout) {
     match view.layout {
       DatabaseLayout::Board => {
@@ -21,6 +23,10 @@ pub(crate) fn database_view_setting_pb_from_view(view: DatabaseView) -> Database
         let calendar_setting = layout_setting.clone().into();
         DatabaseLayoutSettingPB::from_calendar(calendar_setting)
       },
+      DatabaseLayout::Grid if TimelineLayoutSetting::is_timeline(layout_setting) => {
+        let timeline_setting = layout_setting.clone().into();
+        DatabaseLayoutSettingPB::from_timeline(timeline_setting)
+      },
       _ => DatabaseLayoutSettingPB::default(),
     }
   } else {
@@ -1,12 +1,14 @@
 use collab_database::database::{gen_field_id, MutexDatabase};
 use collab_database::fields::Field;
-use collab_database::views::{DatabaseLayout, LayoutSetting, OrderObjectPosition};
+use collab_database::views::{
+  DatabaseLayout, LayoutSetting, LayoutSettingBuilder, OrderObjectPosition,
+};
 use std::sync::Arc;
 
 use crate::entities::FieldType;
 use crate::services::field::{DateTypeOption, SingleSelectTypeOption};
 use crate::services::field_settings::default_field_settings_by_layout_map;
-use crate::services::setting::{BoardLayoutSetting, CalendarLayoutSetting};
+use crate::services::setting::{BoardLayoutSetting, CalendarLayoutSetting, TimelineLayoutSetting};
 
 /// When creating a database, we need to resolve the dependencies of the views.
 /// Different database views have different dependencies. For example, a board
@@ -71,7 +73,6 @@ impl DatabaseLayoutDepsResolver {
   /// If the new layout type is a calendar and there is not date field in the database, it will add
   /// a new date field to the database and create the corresponding layout setting.
   pub fn resolve_deps_when_update_layout_type(&self, view_id: &str) {
-    let fields = self.database.lock().get_fields(None);
     // Insert the layout setting if it's not exist
     match &self.database_layout {
       DatabaseLayout::Grid => {},
@@ -79,29 +80,70 @@ impl DatabaseLayoutDepsResolver {
         self.create_board_layout_setting_if_need(view_id);
       },
       DatabaseLayout::Calendar => {
-        let date_field_id = match fields
-          .into_iter()
-          .find(|field| FieldType::from(field.field_type) == FieldType::DateTime)
-        {
-          None => {
-            tracing::trace!("Create a new date field after layout type change");
-            let field = self.create_date_field();
-            let field_id = field.id.clone();
-            self.database.lock().create_field(
-              None,
-              field,
-              &OrderObjectPosition::End,
-              default_field_settings_by_layout_map(),
-            );
-            field_id
-          },
-          Some(date_field) => date_field.id,
-        };
+        let date_field_id = self.get_or_create_date_field_id();
         self.create_calendar_layout_setting_if_need(view_id, &date_field_id);
       },
     }
   }
 
+  /// A timeline is saved as a grid view with a [TimelineLayoutSetting]. The setting starts the
+  /// spans at the first date field, which is added if the database has no date field.
+  pub fn resolve_deps_when_update_to_timeline(&self, view_id: &str) {
+    if self.is_timeline(view_id) {
+      return;
+    }
+    let date_field_id = self.get_or_create_date_field_id();
+    self.database.lock().insert_layout_setting(
+      view_id,
+      &DatabaseLayout::Grid,
+      TimelineLayoutSetting::new(date_field_id),
+    );
+  }
+
+  /// Removes the [TimelineLayoutSetting] of the view, so it's displayed as a plain grid again.
+  pub fn remove_timeline_layout_setting(&self, view_id: &str) {
+    if self.is_timeline(view_id) {
+      self.database.lock().insert_layout_setting(
+        view_id,
+        &DatabaseLayout::Grid,
+        LayoutSettingBuilder::new().build(),
+      );
+    }
+  }
+
+  fn is_timeline(&self, view_id: &str) -> bool {
+    self
+      .database
+      .lock()
+      .get_layout_setting::<LayoutSetting>(view_id, &DatabaseLayout::Grid)
+      .map(|layout_setting| TimelineLayoutSetting::is_timeline(&layout_setting))
+      .unwrap_or(false)
+  }
+
+  fn get_or_create_date_field_id(&self) -> String {
+    let date_field = self
+      .database
+      .lock()
+      .get_fields(None)
+      .into_iter()
+      .find(|field| FieldType::from(field.field_type) == FieldType::DateTime);
+    match date_field {
+      None => {
+        tracing::trace!("Create a new date field after layout type change");
+        let field = self.create_date_field();
+        let field_id = field.id.clone();
+        self.database.lock().create_field(
+          None,
+          field,
+          &OrderObjectPosition::End,
+          default_field_settings_by_layout_map(),
+        );
+        field_id
+      },
+      Some(date_field) => date_field.id,
+    }
+  }
+
   fn create_board_layout_setting_if_need(&self, view_id: &str) {
     if self
       .database
@@ -4,6 +4,8 @@ pub use view_editor::*;
 pub use view_operation::*;
 pub use views::*;
 
+pub(crate) use view_filter::duration_until_next_day;
+
 mod layout_deps;
 mod notifier;
 mod view_calculations;
@@ -12,5 +14,6 @@ mod view_filter;
 mod view_group;
 mod view_operation;
 mod view_sort;
+mod view_timeline;
 mod views;
 // mod trait_impl;
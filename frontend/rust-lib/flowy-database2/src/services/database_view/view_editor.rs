@@ -1,5 +1,5 @@
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use collab_database::database::{
@@ -11,14 +11,15 @@ use collab_database::views::{DatabaseLayout, DatabaseView};
 use tokio::sync::{broadcast, RwLock};
 use tracing::instrument;
 
-use flowy_error::{FlowyError, FlowyResult};
+use flowy_error::{ErrorCode, FlowyError, FlowyResult};
 use lib_dispatch::prelude::af_spawn;
 
 use crate::entities::{
-  CalendarEventPB, DatabaseLayoutMetaPB, DatabaseLayoutSettingPB, DeleteFilterPayloadPB,
-  DeleteSortPayloadPB, FieldType, FieldVisibility, GroupChangesPB, GroupPB, InsertedRowPB,
-  LayoutSettingChangeset, LayoutSettingParams, RemoveCalculationChangesetPB, ReorderSortPayloadPB,
-  RowMetaPB, RowsChangePB, SortChangesetNotificationPB, SortPB, UpdateCalculationChangesetPB,
+  CalendarEventPB, DatabaseLayoutMetaPB, DatabaseLayoutPB, DatabaseLayoutSettingPB,
+  DeleteFilterPayloadPB, DeleteSortPayloadPB, FieldType, FieldVisibility, GroupChangesPB, GroupPB,
+  InsertedRowPB, LayoutSettingChangeset, LayoutSettingParams, NoDateTimelineRowPB,
+  RemoveCalculationChangesetPB, ReorderSortPayloadPB, RepeatedSwimlanePB, RowMetaPB, RowsChangePB,
+  SortChangesetNotificationPB, SortPB, SwimlanePB, TimelineSpanPB, UpdateCalculationChangesetPB,
   UpdateFilterParams, UpdateSortPayloadPB,
 };
 use crate::notification::{send_notification, DatabaseNotification};
@@ -28,20 +29,29 @@ use crate::services::database::{database_view_setting_pb_from_view, DatabaseRowE
 use crate::services::database_view::view_filter::make_filter_controller;
 use crate::services::database_view::view_group::{
   get_cell_for_row, get_cells_for_field, new_group_controller, new_group_controller_with_field,
+  new_swimlane_controller, new_swimlane_controller_with_field,
 };
 use crate::services::database_view::view_operation::DatabaseViewOperation;
 use crate::services::database_view::view_sort::make_sort_controller;
+use crate::services::database_view::view_timeline::{
+  timeline_span_dates, timeline_span_dependencies,
+};
 use crate::services::database_view::{
   notify_did_update_filter, notify_did_update_group_rows, notify_did_update_num_of_groups,
   notify_did_update_setting, notify_did_update_sort, DatabaseLayoutDepsResolver,
   DatabaseViewChangedNotifier, DatabaseViewChangedReceiverRunner,
 };
+use crate::services::field::DateCellData;
 use crate::services::field_settings::FieldSettings;
 use crate::services::filter::{
-  Filter, FilterChangeset, FilterContext, FilterController, UpdatedFilter,
+  Filter, FilterChangeset, FilterContext, FilterController, FilterTree, UpdatedFilter,
+};
+use crate::services::group::{
+  default_group_setting, GroupChangesets, GroupController, GroupData, MoveGroupRowContext,
+  RowChangeset,
 };
-use crate::services::group::{GroupChangesets, GroupController, MoveGroupRowContext, RowChangeset};
-use crate::services::setting::CalendarLayoutSetting;
+use crate::services::setting::{CalendarLayoutSetting, TimelineLayoutSetting};
+use crate::services::share::stringify_row_cell;
 use crate::services::sort::{Sort, SortChangeset, SortController};
 
 use super::notify_did_update_calculation;
@@ -51,6 +61,9 @@ pub struct DatabaseViewEditor {
   pub view_id: String,
   delegate: Arc<dyn DatabaseViewOperation>,
   group_controller: Arc<RwLock<Option<Box<dyn GroupController>>>>,
+  /// Groups the rows of the board by the swimlane field. The rows of a board with swimlanes are
+  /// grouped by both the [group_controller] and the [swimlane_controller].
+  swimlane_controller: Arc<RwLock<Option<Box<dyn GroupController>>>>,
   filter_controller: Arc<FilterController>,
   sort_controller: Arc<RwLock<SortController>>,
   calculations_controller: Arc<CalculationsController>,
@@ -75,6 +88,9 @@ impl DatabaseViewEditor {
     let group_controller = Arc::new(RwLock::new(
       new_group_controller(view_id.clone(), delegate.clone()).await?,
     ));
+    let swimlane_controller = Arc::new(RwLock::new(
+      new_swimlane_controller(view_id.clone(), delegate.clone()).await?,
+    ));
 
     // Filter
     let filter_controller = make_filter_controller(
@@ -103,6 +119,7 @@ impl DatabaseViewEditor {
       view_id,
       delegate,
       group_controller,
+      swimlane_controller,
       filter_controller,
       sort_controller,
       calculations_controller,
@@ -150,6 +167,13 @@ impl DatabaseViewEditor {
         notify_did_update_group_rows(changeset).await;
       }
     }
+    let is_swimlane_changed = match self.swimlane_controller.write().await.as_mut() {
+      Some(controller) => !controller.did_create_row(row_detail, index).is_empty(),
+      None => false,
+    };
+    if is_swimlane_changed {
+      self.notify_did_update_swimlanes().await;
+    }
 
     let inserted_row = InsertedRowPB {
       row_meta: RowMetaPB::from(row_detail),
@@ -163,6 +187,10 @@ impl DatabaseViewEditor {
     self
       .gen_view_tasks(row_detail.row.id.clone(), "".to_string())
       .await;
+    self
+      .calculations_controller
+      .did_receive_row_changed(row_detail.row.clone())
+      .await;
   }
 
   pub async fn v_did_duplicate_row(&self, row_detail: &RowDetail) {
@@ -195,6 +223,13 @@ impl DatabaseViewEditor {
         notify_did_update_num_of_groups(&self.view_id, payload).await;
       }
     }
+    if self
+      .mut_swimlane_controller(|swimlane_controller, _| swimlane_controller.did_delete_row(row))
+      .await
+      .is_some()
+    {
+      self.notify_did_update_swimlanes().await;
+    }
     let changes = RowsChangePB::from_delete(row.id.clone().into_inner());
 
     send_notification(&self.view_id, DatabaseNotification::DidUpdateViewRows)
@@ -228,7 +263,31 @@ impl DatabaseViewEditor {
       })
       .await;
 
+    let swimlane_result = self
+      .mut_swimlane_controller(|swimlane_controller, field| {
+        Ok(swimlane_controller.did_update_group_row(old_row, row_detail, &field))
+      })
+      .await;
+    let mut is_swimlane_changed = match swimlane_result {
+      Some(Ok(swimlane_result)) => {
+        swimlane_result.inserted_group.is_some()
+          || swimlane_result.deleted_group.is_some()
+          || swimlane_result
+            .row_changesets
+            .iter()
+            .any(|changeset| !changeset.is_empty())
+      },
+      _ => false,
+    };
+
     if let Some(Ok(result)) = result {
+      // The swimlanes contain the groups, so they change with the groups.
+      is_swimlane_changed |= result.inserted_group.is_some()
+        || result.deleted_group.is_some()
+        || result
+          .row_changesets
+          .iter()
+          .any(|changeset| !changeset.is_empty());
       let mut group_changes = GroupChangesPB {
         view_id: self.view_id.clone(),
         ..Default::default()
@@ -253,6 +312,9 @@ impl DatabaseViewEditor {
         }
       }
     }
+    if is_swimlane_changed && self.swimlane_controller.read().await.is_some() {
+      self.notify_did_update_swimlanes().await;
+    }
 
     // Each row update will trigger a calculations, filter and sort operation. We don't want
     // to block the main thread, so we spawn a new task to do the work.
@@ -282,11 +344,15 @@ impl DatabaseViewEditor {
     rows
   }
 
+  /// Moves the row to the group with `to_group_id`. If the board has swimlanes and the
+  /// `to_swimlane_id` is not None, the row is also moved to that swimlane. The cells of both the
+  /// grouping field and the swimlane field are written to the `row_changeset`.
   pub async fn v_move_group_row(
     &self,
     row_detail: &RowDetail,
     row_changeset: &mut RowChangeset,
     to_group_id: &str,
+    to_swimlane_id: Option<&str>,
     to_row_id: Option<RowId>,
   ) {
     let result = self
@@ -296,12 +362,33 @@ impl DatabaseViewEditor {
           row_changeset,
           field: &field,
           to_group_id,
-          to_row_id,
+          to_row_id: to_row_id.clone(),
         };
         group_controller.move_group_row(move_row_context)
       })
       .await;
 
+    if let Some(to_swimlane_id) = to_swimlane_id {
+      let swimlane_result = self
+        .mut_swimlane_controller(|swimlane_controller, field| {
+          let move_row_context = MoveGroupRowContext {
+            row_detail,
+            row_changeset,
+            field: &field,
+            to_group_id: to_swimlane_id,
+            to_row_id,
+          };
+          swimlane_controller.move_group_row(move_row_context)
+        })
+        .await;
+      if swimlane_result.is_none() {
+        tracing::warn!(
+          "Can't move the row to the swimlane:{}, the view has no swimlanes",
+          to_swimlane_id
+        );
+      }
+    }
+
     if let Some(result) = result {
       if let Some(delete_group) = result.deleted_group {
         tracing::trace!("Delete group after moving the row: {:?}", delete_group);
@@ -317,6 +404,9 @@ impl DatabaseViewEditor {
         notify_did_update_group_rows(changeset).await;
       }
     }
+    if self.swimlane_controller.read().await.is_some() {
+      self.notify_did_update_swimlanes().await;
+    }
   }
 
   /// Only call once after database view editor initialized
@@ -365,9 +455,15 @@ impl DatabaseViewEditor {
   }
 
   /// Called when the user changes the grouping field
-  pub async fn v_initialize_new_group(&self, field_id: &str) -> FlowyResult<()> {
+  /// Groups the view by the field, unless it's already the grouping field and the group setting
+  /// hasn't changed.
+  pub async fn v_initialize_new_group(
+    &self,
+    field_id: &str,
+    is_setting_changed: bool,
+  ) -> FlowyResult<()> {
     let is_grouping_field = self.is_grouping_field(field_id).await;
-    if !is_grouping_field {
+    if !is_grouping_field || is_setting_changed {
       self.v_grouping_by_field(field_id).await?;
 
       if let Some(view) = self.delegate.get_view(&self.view_id).await {
@@ -378,6 +474,93 @@ impl DatabaseViewEditor {
     Ok(())
   }
 
+  pub async fn is_swimlane_field(&self, field_id: &str) -> bool {
+    match self.swimlane_controller.read().await.as_ref() {
+      Some(swimlane_controller) => swimlane_controller.field_id() == field_id,
+      None => false,
+    }
+  }
+
+  /// Returns the swimlanes of the board, or None if the board has no swimlanes.
+  pub async fn v_get_swimlanes(&self) -> Option<Vec<SwimlanePB>> {
+    let group_controller = self.group_controller.read().await;
+    let swimlane_controller = self.swimlane_controller.read().await;
+    let groups = group_controller.as_ref()?.get_all_groups();
+    let swimlanes = swimlane_controller.as_ref()?.get_all_groups();
+    Some(swimlanes_from_groups(&groups, &swimlanes))
+  }
+
+  /// Sub-groups the board by the field with `field_id`, or removes the swimlanes if it's None.
+  /// The swimlane setting is saved in the group setting of the view.
+  pub async fn v_set_swimlane_field(&self, field_id: Option<&str>) -> FlowyResult<()> {
+    let mut setting = match self
+      .delegate
+      .get_group_setting(&self.view_id)
+      .into_iter()
+      .next()
+    {
+      Some(setting) => setting,
+      None => {
+        return Err(
+          FlowyError::record_not_found()
+            .with_context("The view must be grouped before adding the swimlanes"),
+        )
+      },
+    };
+
+    match field_id {
+      None => {
+        setting.swimlane = None;
+        self.delegate.insert_group_setting(&self.view_id, setting);
+        *self.swimlane_controller.write().await = None;
+      },
+      Some(field_id) => {
+        if !self.v_get_layout_type().await.is_board() {
+          return Err(FlowyError::invalid_data().with_context("Only the boards have swimlanes"));
+        }
+        let field = self
+          .delegate
+          .get_field(field_id)
+          .ok_or_else(FlowyError::field_record_not_found)?;
+        if self.is_grouping_field(field_id).await
+          || !FieldType::from(field.field_type).can_be_group()
+        {
+          return Err(FlowyError::invalid_data().with_context(format!(
+            "Can't use the field:{} as the swimlane field",
+            field_id
+          )));
+        }
+
+        // The groups of the previous swimlane field are dropped.
+        setting.swimlane = Some(Box::new(default_group_setting(&field)));
+        self.delegate.insert_group_setting(&self.view_id, setting);
+        let swimlane_controller = new_swimlane_controller_with_field(
+          self.view_id.clone(),
+          self.delegate.clone(),
+          Arc::new(field),
+        )
+        .await?;
+        *self.swimlane_controller.write().await = Some(swimlane_controller);
+      },
+    }
+
+    if let Some(view) = self.delegate.get_view(&self.view_id).await {
+      let setting = database_view_setting_pb_from_view(view);
+      notify_did_update_setting(&self.view_id, setting).await;
+    }
+    self.notify_did_update_swimlanes().await;
+    Ok(())
+  }
+
+  async fn notify_did_update_swimlanes(&self) {
+    let payload = RepeatedSwimlanePB {
+      items: self.v_get_swimlanes().await.unwrap_or_default(),
+    };
+    send_notification(&self.view_id, DatabaseNotification::DidUpdateSwimlanes)
+      .payload(payload)
+      .send();
+  }
+
   pub async fn v_create_group(&self, name: &str) -> FlowyResult<()> {
     let mut old_field: Option<Field> = None;
     let result = if let Some(controller) = self.group_controller.write().await.as_mut() {
@@ -493,6 +676,8 @@ impl DatabaseViewEditor {
       field_id: params.field_id.clone(),
       field_type: params.field_type,
       condition: params.condition.into(),
+      empty_placement: params.empty_placement.into(),
+      option_order: params.option_order.into(),
     };
 
     self.delegate.insert_sort(&self.view_id, sort.clone());
@@ -565,6 +750,18 @@ impl DatabaseViewEditor {
     &self,
     params: UpdateCalculationChangesetPB,
   ) -> FlowyResult<()> {
+    let field = self
+      .delegate
+      .get_field(&params.field_id)
+      .ok_or_else(|| FlowyError::record_not_found().with_context("Can't find the field"))?;
+    let field_type = FieldType::from(field.field_type);
+    if !params.calculation_type.is_supported_by(&field_type) {
+      return Err(FlowyError::invalid_data().with_context(format!(
+        "The {:?} calculation is not supported by the {:?} field",
+        params.calculation_type, field_type
+      )));
+    }
+
     let calculation_id = match params.calculation_id {
       None => gen_database_calculation_id(),
       Some(calculation_id) => calculation_id,
@@ -630,8 +827,27 @@ impl DatabaseViewEditor {
       None => gen_database_filter_id(),
       Some(filter_id) => filter_id,
     };
+    if let Some(parent_id) = &params.parent_id {
+      // The parent must be an AND/OR filter that isn't the filter itself or one of its children.
+      let filter_tree = FilterTree::new(self.delegate.get_all_filters(&self.view_id));
+      let is_valid_parent = filter_tree
+        .get(parent_id)
+        .map(|parent| parent.is_group())
+        .unwrap_or(false)
+        && parent_id != &filter_id
+        && !filter_tree.descendant_ids(&filter_id).contains(parent_id);
+      if !is_valid_parent {
+        return Err(FlowyError::new(
+          ErrorCode::InvalidParams,
+          "The parent of a filter must be an AND/OR filter",
+        ));
+      }
+    }
+
     let filter = Filter {
       id: filter_id.clone(),
+      parent_id: params.parent_id,
+      filter_type: params.filter_type,
       field_id: params.field_id.clone(),
       field_type: params.field_type,
       condition: params.condition,
@@ -673,6 +889,11 @@ impl DatabaseViewEditor {
       .did_receive_changes(FilterChangeset::from_delete(filter_context.clone()))
       .await;
 
+    // Deleting an AND/OR filter deletes all the filters in it as well
+    let filter_tree = FilterTree::new(self.delegate.get_all_filters(&self.view_id));
+    for filter_id in filter_tree.descendant_ids(&params.filter_id) {
+      self.delegate.delete_filter(&self.view_id, &filter_id);
+    }
     self
       .delegate
       .delete_filter(&self.view_id, &params.filter_id);
@@ -691,7 +912,17 @@ impl DatabaseViewEditor {
   pub async fn v_get_layout_settings(&self, layout_ty: &DatabaseLayout) -> LayoutSettingParams {
     let mut layout_setting = LayoutSettingParams::default();
     match layout_ty {
-      DatabaseLayout::Grid => {},
+      DatabaseLayout::Grid => {
+        if let Some(value) = self.delegate.get_layout_setting(&self.view_id, layout_ty) {
+          if TimelineLayoutSetting::is_timeline(&value) {
+            let timeline_setting = TimelineLayoutSetting::from(value);
+            match self.validate_timeline_setting(&timeline_setting) {
+              Ok(_) => layout_setting.timeline = Some(timeline_setting),
+              Err(err) => tracing::warn!("The timeline setting is invalid: {}", err),
+            }
+          }
+        }
+      },
       DatabaseLayout::Board => {
         if let Some(value) = self.delegate.get_layout_setting(&self.view_id, layout_ty) {
           layout_setting.board = Some(value.into());
@@ -757,7 +988,24 @@ impl DatabaseViewEditor {
           None
         }
       },
-      _ => None,
+      DatabaseLayout::Grid => {
+        // The grid becomes a timeline by updating its layout type, not by setting its layout
+        if !self.is_timeline() {
+          return Err(
+            FlowyError::invalid_data().with_context("The view isn't displayed as a timeline"),
+          );
+        }
+        let layout_setting = params.timeline.unwrap();
+        self.validate_timeline_setting(&layout_setting)?;
+
+        self.delegate.insert_layout_setting(
+          &self.view_id,
+          &params.layout_type,
+          layout_setting.clone().into(),
+        );
+
+        Some(DatabaseLayoutSettingPB::from_timeline(layout_setting))
+      },
     };
 
     if let Some(payload) = layout_setting_pb {
@@ -769,7 +1017,38 @@ impl DatabaseViewEditor {
     Ok(())
   }
 
+  /// The dates of the timeline must be date fields and its dependencies a relation field.
+  fn validate_timeline_setting(&self, setting: &TimelineLayoutSetting) -> FlowyResult<()> {
+    let date_field_ids = std::iter::once(&setting.start_field_id).chain(&setting.end_field_id);
+    for field_id in date_field_ids {
+      let field = self
+        .delegate
+        .get_field(field_id)
+        .ok_or_else(FlowyError::field_record_not_found)?;
+      if FieldType::from(field.field_type) != FieldType::DateTime {
+        return Err(FlowyError::unexpect_timeline_field_type());
+      }
+    }
+
+    if let Some(field_id) = &setting.dependency_field_id {
+      let field = self
+        .delegate
+        .get_field(field_id)
+        .ok_or_else(FlowyError::field_record_not_found)?;
+      if FieldType::from(field.field_type) != FieldType::Relation {
+        return Err(FlowyError::invalid_data().with_context("The dependencies must be a relation"));
+      }
+    }
+    Ok(())
+  }
+
   pub async fn v_did_delete_field(&self, deleted_field_id: &str) {
+    if self.is_swimlane_field(deleted_field_id).await {
+      if let Err(err) = self.v_set_swimlane_field(None).await {
+        tracing::error!("Remove the swimlanes of the deleted field failed: {}", err);
+      }
+    }
+
     let sorts = self.delegate.get_all_sorts(&self.view_id);
 
     if let Some(sort) = sorts.iter().find(|sort| sort.field_id == deleted_field_id) {
@@ -809,6 +1088,11 @@ impl DatabaseViewEditor {
     if self.is_grouping_field(field_id).await {
       self.v_grouping_by_field(field_id).await?;
     }
+    if self.is_swimlane_field(field_id).await {
+      *self.swimlane_controller.write().await =
+        new_swimlane_controller(self.view_id.clone(), self.delegate.clone()).await?;
+      self.notify_did_update_swimlanes().await;
+    }
 
     if let Some(field) = self.delegate.get_field(field_id) {
       self
@@ -825,23 +1109,26 @@ impl DatabaseViewEditor {
         })
         .await;
 
-      if let Some(filter) = self
+      let filters = self
         .delegate
-        .get_filter_by_field_id(&self.view_id, field_id)
-      {
-        let old = Filter {
-          field_type: FieldType::from(old_field.field_type),
-          ..filter.clone()
-        };
-        let updated_filter = UpdatedFilter::new(Some(old), filter);
-        let filter_changeset = FilterChangeset::from_update(updated_filter);
+        .get_filters_by_field_id(&self.view_id, field_id);
+      if !filters.is_empty() {
+        let old_field_type = FieldType::from(old_field.field_type);
         let filter_controller = self.filter_controller.clone();
         af_spawn(async move {
-          if let Some(notification) = filter_controller
-            .did_receive_changes(filter_changeset)
-            .await
-          {
-            notify_did_update_filter(notification).await;
+          for filter in filters {
+            let old = Filter {
+              field_type: old_field_type.clone(),
+              ..filter.clone()
+            };
+            let updated_filter = UpdatedFilter::new(Some(old), filter);
+            let filter_changeset = FilterChangeset::from_update(updated_filter);
+            if let Some(notification) = filter_controller
+              .did_receive_changes(filter_changeset)
+              .await
+            {
+              notify_did_update_filter(notification).await;
+            }
           }
         });
       }
@@ -900,11 +1187,8 @@ impl DatabaseViewEditor {
       .unwrap_or_default()
       .into();
 
-    let timestamp = date_cell
-      .into_date_field_cell_data()
-      .unwrap_or_default()
-      .timestamp
-      .unwrap_or_default();
+    let (timestamp, end_timestamp) =
+      calendar_event_timestamps(&date_cell.into_date_field_cell_data().unwrap_or_default());
 
     let (_, row_detail) = self.delegate.get_row(&self.view_id, &row_id).await?;
     Some(CalendarEventPB {
@@ -913,6 +1197,7 @@ impl DatabaseViewEditor {
       title,
       timestamp,
       is_scheduled: timestamp != 0,
+      end_timestamp,
     })
   }
 
@@ -947,19 +1232,19 @@ impl DatabaseViewEditor {
       let row_id = date_cell.row_id.clone();
 
       // timestamp
-      let timestamp = date_cell
+      let timestamps = date_cell
         .into_date_field_cell_data()
-        .map(|date_cell_data| date_cell_data.timestamp.unwrap_or_default())
+        .map(|date_cell_data| calendar_event_timestamps(&date_cell_data))
         .unwrap_or_default();
 
-      (row_id, timestamp)
+      (row_id, timestamps)
     })
-    .collect::<HashMap<RowId, i64>>();
+    .collect::<HashMap<RowId, (i64, i64)>>();
 
     let mut events: Vec<CalendarEventPB> = vec![];
     for text_cell in text_cells {
       let row_id = text_cell.row_id.clone();
-      let timestamp = timestamp_by_row_id
+      let (timestamp, end_timestamp) = timestamp_by_row_id
         .get(&row_id)
         .cloned()
         .unwrap_or_default();
@@ -976,36 +1261,101 @@ impl DatabaseViewEditor {
         title,
         timestamp,
         is_scheduled: timestamp != 0,
+        end_timestamp,
       };
       events.push(event);
     }
     Some(events)
   }
 
+  fn is_timeline(&self) -> bool {
+    self
+      .delegate
+      .get_layout_setting(&self.view_id, &DatabaseLayout::Grid)
+      .map(|layout_setting| TimelineLayoutSetting::is_timeline(&layout_setting))
+      .unwrap_or(false)
+  }
+
+  /// Returns the setting of the timeline, or None if the view isn't displayed as a timeline.
+  pub async fn v_get_timeline_setting(&self) -> Option<TimelineLayoutSetting> {
+    if self.v_get_layout_type().await != DatabaseLayout::Grid {
+      return None;
+    }
+    self
+      .v_get_layout_settings(&DatabaseLayout::Grid)
+      .await
+      .timeline
+  }
+
+  pub async fn v_get_timeline_span(&self, row_id: &RowId) -> Option<TimelineSpanPB> {
+    let timeline_setting = self.v_get_timeline_setting().await?;
+    let primary_field = self.delegate.get_primary_field().await?;
+    let (_, row_detail) = self.delegate.get_row(&self.view_id, row_id).await?;
+    timeline_span_from_row(&timeline_setting, &primary_field, &row_detail)
+  }
+
+  /// Returns the spans of the rows that have a start date, in the order of the view.
+  pub async fn v_get_all_timeline_spans(&self) -> Option<Vec<TimelineSpanPB>> {
+    let timeline_setting = self.v_get_timeline_setting().await?;
+    let primary_field = self.delegate.get_primary_field().await?;
+    let spans = self
+      .v_get_rows()
+      .await
+      .iter()
+      .flat_map(|row_detail| timeline_span_from_row(&timeline_setting, &primary_field, row_detail))
+      .collect();
+    Some(spans)
+  }
+
+  /// Returns the rows that have no start date, so they can't be displayed on the timeline.
+  pub async fn v_get_no_date_timeline_rows(&self) -> Option<Vec<NoDateTimelineRowPB>> {
+    let timeline_setting = self.v_get_timeline_setting().await?;
+    let primary_field = self.delegate.get_primary_field().await?;
+    let rows = self
+      .v_get_rows()
+      .await
+      .iter()
+      .filter(|row_detail| timeline_span_dates(&timeline_setting, &row_detail.row.cells).is_none())
+      .map(|row_detail| NoDateTimelineRowPB {
+        row_id: row_detail.row.id.to_string(),
+        title: stringify_row_cell(&row_detail.row, &primary_field),
+      })
+      .collect();
+    Some(rows)
+  }
+
   pub async fn v_get_layout_type(&self) -> DatabaseLayout {
     self.delegate.get_layout_for_view(&self.view_id)
   }
 
   #[tracing::instrument(level = "trace", skip_all)]
-  pub async fn v_update_layout_type(&self, new_layout_type: DatabaseLayout) -> FlowyResult<()> {
+  pub async fn v_update_layout_type(&self, new_layout_type: DatabaseLayoutPB) -> FlowyResult<()> {
+    let layout_type = DatabaseLayout::from(new_layout_type.clone());
     self
       .delegate
-      .update_layout_type(&self.view_id, &new_layout_type);
+      .update_layout_type(&self.view_id, &layout_type);
 
     // using the {} brackets to denote the lifetime of the resolver. Because the DatabaseLayoutDepsResolver
     // is not sync and send, so we can't pass it to the async block.
     {
-      let resolver = DatabaseLayoutDepsResolver::new(self.delegate.get_database(), new_layout_type);
+      let resolver = DatabaseLayoutDepsResolver::new(self.delegate.get_database(), layout_type);
       resolver.resolve_deps_when_update_layout_type(&self.view_id);
+      match new_layout_type {
+        DatabaseLayoutPB::Timeline => resolver.resolve_deps_when_update_to_timeline(&self.view_id),
+        DatabaseLayoutPB::Grid => resolver.remove_timeline_layout_setting(&self.view_id),
+        DatabaseLayoutPB::Board | DatabaseLayoutPB::Calendar => {},
+      }
     }
 
     // initialize the group controller if the current layout support grouping
     *self.group_controller.write().await =
       new_group_controller(self.view_id.clone(), self.delegate.clone()).await?;
+    *self.swimlane_controller.write().await =
+      new_swimlane_controller(self.view_id.clone(), self.delegate.clone()).await?;
 
     let payload = DatabaseLayoutMetaPB {
       view_id: self.view_id.clone(),
-      layout: new_layout_type.into(),
+      layout: new_layout_type,
     };
     send_notification(&self.view_id, DatabaseNotification::DidUpdateDatabaseLayout)
       .payload(payload)
@@ -1056,19 +1406,14 @@ impl DatabaseViewEditor {
   where
     F: FnOnce(&mut Box<dyn GroupController>, Field) -> FlowyResult<T>,
   {
-    let group_field_id = self
-      .group_controller
-      .read()
-      .await
-      .as_ref()
-      .map(|group| group.field_id().to_owned())?;
-    let field = self.delegate.get_field(&group_field_id)?;
-    let mut write_guard = self.group_controller.write().await;
-    if let Some(group_controller) = &mut *write_guard {
-      f(group_controller, field).ok()
-    } else {
-      None
-    }
+    mut_controller(&self.group_controller, self.delegate.as_ref(), f).await
+  }
+
+  async fn mut_swimlane_controller<F, T>(&self, f: F) -> Option<T>
+  where
+    F: FnOnce(&mut Box<dyn GroupController>, Field) -> FlowyResult<T>,
+  {
+    mut_controller(&self.swimlane_controller, self.delegate.as_ref(), f).await
   }
 
   async fn gen_view_tasks(&self, row_id: RowId, field_id: String) {
@@ -1096,3 +1441,83 @@ impl DatabaseViewEditor {
     });
   }
 }
+
+fn timeline_span_from_row(
+  setting: &TimelineLayoutSetting,
+  primary_field: &Field,
+  row_detail: &RowDetail,
+) -> Option<TimelineSpanPB> {
+  let (start, end) = timeline_span_dates(setting, &row_detail.row.cells)?;
+  Some(TimelineSpanPB {
+    row_meta: RowMetaPB::from(row_detail),
+    title: stringify_row_cell(&row_detail.row, primary_field),
+    start,
+    end,
+    dependencies: timeline_span_dependencies(setting, &row_detail.row.cells),
+  })
+}
+
+/// Returns the start and the end of the calendar event. The event of a date range spans from the
+/// start to the end of the range, the other events last a single day.
+fn calendar_event_timestamps(cell_data: &DateCellData) -> (i64, i64) {
+  let timestamp = cell_data.timestamp.unwrap_or_default();
+  match cell_data.end_timestamp {
+    Some(end_timestamp) if cell_data.is_range && timestamp != 0 => (timestamp, end_timestamp),
+    _ => (timestamp, timestamp),
+  }
+}
+
+async fn mut_controller<F, T>(
+  controller: &RwLock<Option<Box<dyn GroupController>>>,
+  delegate: &dyn DatabaseViewOperation,
+  f: F,
+) -> Option<T>
+where
+  F: FnOnce(&mut Box<dyn GroupController>, Field) -> FlowyResult<T>,
+{
+  let group_field_id = controller
+    .read()
+    .await
+    .as_ref()
+    .map(|group| group.field_id().to_owned())?;
+  let field = delegate.get_field(&group_field_id)?;
+  let mut write_guard = controller.write().await;
+  if let Some(group_controller) = &mut *write_guard {
+    f(group_controller, field).ok()
+  } else {
+    None
+  }
+}
+
+/// Returns a swimlane for each group of the swimlane field. Each swimlane contains all the
+/// `groups` of the board, keeping only the rows of the swimlane.
+fn swimlanes_from_groups(groups: &[&GroupData], swimlanes: &[&GroupData]) -> Vec<SwimlanePB> {
+  swimlanes
+    .iter()
+    .map(|swimlane| {
+      let row_ids = swimlane
+        .rows
+        .iter()
+        .map(|row_detail| &row_detail.row.id)
+        .collect::<HashSet<_>>();
+      let groups = groups
+        .iter()
+        .map(|group| {
+          let mut group = (*group).clone();
+          group
+            .rows
+            .retain(|row_detail| row_ids.contains(&row_detail.row.id));
+          GroupPB::from(group)
+        })
+        .collect();
+      SwimlanePB {
+        field_id: swimlane.field_id.clone(),
+        swimlane_id: swimlane.id.clone(),
+        swimlane_name: swimlane.name.clone(),
+        is_default: swimlane.is_default,
+        is_visible: swimlane.is_visible,
+        groups,
+      }
+    })
+    .collect()
+}
@@ -1,8 +1,12 @@
-use std::sync::Arc;
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use chrono::{DateTime, FixedOffset, Local, TimeZone, Utc};
 
 use collab_database::fields::Field;
 use collab_database::rows::{RowDetail, RowId};
 
+use lib_dispatch::prelude::af_spawn;
 use lib_infra::future::{to_fut, Fut};
 
 use crate::services::cell::CellCache;
@@ -40,9 +44,71 @@ pub async fn make_filter_controller(
       handler_id,
       filter_controller.clone(),
     ));
+
+  af_spawn(refilter_on_day_change(
+    Arc::downgrade(&filter_controller),
+    Utc::now,
+  ));
   filter_controller
 }
 
+/// The relative date filters, like "today", match other rows once the day rolls over in the
+/// timezone of their field. The filters might change in the meantime, so the next midnight is
+/// checked at least hourly. Stops when the filter controller is dropped.
+async fn refilter_on_day_change<F>(filter_controller: Weak<FilterController>, now: F)
+where
+  F: Fn() -> DateTime<Utc>,
+{
+  let max_duration = Duration::from_secs(60 * 60);
+  loop {
+    let duration = match filter_controller.upgrade() {
+      None => break,
+      Some(filter_controller) => {
+        let offsets = filter_controller.relative_date_filter_offsets();
+        duration_until_earliest_next_day(&offsets, now())
+      },
+    };
+    match duration {
+      Some(duration) if duration <= max_duration => {
+        tokio::time::sleep(duration).await;
+        match filter_controller.upgrade() {
+          None => break,
+          Some(filter_controller) => filter_controller.did_change_day().await,
+        }
+      },
+      _ => tokio::time::sleep(max_duration).await,
+    }
+  }
+}
+
+/// Returns the duration until the next local midnight.
+pub(crate) fn duration_until_next_day() -> Duration {
+  duration_until_next_day_in(&Local, Utc::now())
+}
+
+/// Returns the duration until the earliest next midnight among the given timezones, or None if
+/// there is no timezone.
+fn duration_until_earliest_next_day(
+  offsets: &[FixedOffset],
+  now: DateTime<Utc>,
+) -> Option<Duration> {
+  offsets
+    .iter()
+    .map(|offset| duration_until_next_day_in(offset, now))
+    .min()
+}
+
+fn duration_until_next_day_in<Tz: TimeZone>(timezone: &Tz, now: DateTime<Utc>) -> Duration {
+  let now = now.with_timezone(timezone);
+  now
+    .date_naive()
+    .succ_opt()
+    .and_then(|tomorrow| tomorrow.and_hms_opt(0, 0, 1))
+    .and_then(|midnight| timezone.from_local_datetime(&midnight).earliest())
+    .and_then(|midnight| (midnight - now).to_std().ok())
+    .unwrap_or(Duration::from_secs(60 * 60))
+}
+
 struct DatabaseViewFilterDelegateImpl(Arc<dyn DatabaseViewOperation>);
 
 impl FilterDelegate for DatabaseViewFilterDelegateImpl {
@@ -66,4 +132,145 @@ impl FilterDelegate for DatabaseViewFilterDelegateImpl {
   fn get_row(&self, view_id: &str, rows_id: &RowId) -> Fut<Option<(usize, Arc<RowDetail>)>> {
     self.0.get_row(view_id, rows_id)
   }
+
+  fn get_current_user_email(&self) -> Option<String> {
+    self.0.get_current_user_email()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+  use std::time::Duration;
+
+  use chrono::{FixedOffset, Timelike, Utc};
+  use collab_database::fields::Field;
+  use collab_database::rows::{RowDetail, RowId};
+  use tokio::sync::{broadcast, RwLock};
+
+  use lib_infra::future::{to_fut, Fut};
+  use lib_infra::priority_task::{TaskDispatcher, TaskRunner};
+
+  use crate::entities::{DateFilterConditionPB, DateFilterContentPB, FieldType};
+  use crate::services::database_view::view_filter::{
+    duration_until_earliest_next_day, duration_until_next_day_in, refilter_on_day_change,
+  };
+  use crate::services::database_view::DatabaseViewChanged;
+  use crate::services::field::{DateTypeOption, FieldBuilder};
+  use crate::services::filter::{
+    Filter, FilterController, FilterDelegate, FilterTaskHandler, FilterType,
+  };
+  use crate::utils::cache::AnyTypeCache;
+
+  #[test]
+  fn duration_until_earliest_next_day_test() {
+    let now = Utc::now();
+    assert_eq!(duration_until_earliest_next_day(&[], now), None);
+
+    // The day rolls over in UTC+14 before any other timezone.
+    let utc = FixedOffset::east_opt(0).unwrap();
+    let kiribati = FixedOffset::east_opt(14 * 3600).unwrap();
+    let duration = duration_until_earliest_next_day(&[utc, kiribati], now).unwrap();
+    assert_eq!(
+      duration,
+      duration_until_next_day_in(&utc, now).min(duration_until_next_day_in(&kiribati, now))
+    );
+
+    let seconds_until_midnight =
+      24 * 3600 - now.with_timezone(&kiribati).num_seconds_from_midnight() as u64;
+    let expected = duration_until_next_day_in(&kiribati, now).as_secs();
+    assert!(expected.abs_diff(seconds_until_midnight) <= 2);
+  }
+
+  struct DateFieldDelegate(Field);
+
+  impl FilterDelegate for DateFieldDelegate {
+    fn get_filter(&self, _view_id: &str, _filter_id: &str) -> Fut<Option<Arc<Filter>>> {
+      to_fut(async { None })
+    }
+
+    fn get_field(&self, field_id: &str) -> Option<Field> {
+      (self.0.id == field_id).then(|| self.0.clone())
+    }
+
+    fn get_fields(&self, _view_id: &str, _field_ids: Option<Vec<String>>) -> Fut<Vec<Arc<Field>>> {
+      let field = Arc::new(self.0.clone());
+      to_fut(async move { vec![field] })
+    }
+
+    fn get_rows(&self, _view_id: &str) -> Fut<Vec<Arc<RowDetail>>> {
+      to_fut(async { vec![] })
+    }
+
+    fn get_row(&self, _view_id: &str, _row_id: &RowId) -> Fut<Option<(usize, Arc<RowDetail>)>> {
+      to_fut(async { None })
+    }
+
+    fn get_current_user_email(&self) -> Option<String> {
+      None
+    }
+  }
+
+  // The time is paused and advanced by tokio, so the clock of the refilter loop is derived from
+  // the tokio clock instead of the wall clock.
+  #[tokio::test(start_paused = true)]
+  async fn refilter_at_next_midnight_in_field_timezone_test() {
+    // The field is in UTC+14, where the day rolls over before the local timezone.
+    let type_option = DateTypeOption {
+      timezone_id: "Pacific/Kiritimati".to_owned(),
+      ..DateTypeOption::new()
+    };
+    let field = FieldBuilder::new(FieldType::DateTime, type_option).build();
+    let filter = Filter {
+      id: "filter".to_owned(),
+      parent_id: None,
+      filter_type: FilterType::Data,
+      field_id: field.id.clone(),
+      field_type: FieldType::DateTime,
+      condition: DateFilterConditionPB::DateIsToday as i64,
+      content: DateFilterContentPB::default().to_string(),
+    };
+
+    let task_scheduler = Arc::new(RwLock::new(TaskDispatcher::new(Duration::from_secs(2))));
+    tokio::spawn(TaskRunner::run(task_scheduler.clone()));
+    let (notifier, mut receiver) = broadcast::channel(10);
+    let filter_controller = Arc::new(
+      FilterController::new(
+        "view",
+        "handler",
+        DateFieldDelegate(field),
+        task_scheduler.clone(),
+        vec![Arc::new(filter)],
+        AnyTypeCache::<u64>::new(),
+        notifier,
+      )
+      .await,
+    );
+    task_scheduler
+      .write()
+      .await
+      .register_handler(FilterTaskHandler::new(
+        "handler".to_owned(),
+        filter_controller.clone(),
+      ));
+
+    let start = Utc::now();
+    let start_instant = tokio::time::Instant::now();
+    let now = move || start + chrono::Duration::from_std(start_instant.elapsed()).unwrap();
+    tokio::spawn(refilter_on_day_change(
+      Arc::downgrade(&filter_controller),
+      now,
+    ));
+
+    let kiribati = FixedOffset::east_opt(14 * 3600).unwrap();
+    let until_midnight = duration_until_next_day_in(&kiribati, start);
+    tokio::time::sleep(until_midnight - Duration::from_secs(5)).await;
+    assert!(receiver.try_recv().is_err());
+
+    tokio::time::sleep(Duration::from_secs(10)).await;
+    assert!(matches!(
+      receiver.try_recv(),
+      Ok(DatabaseViewChanged::FilterNotification(_))
+    ));
+  }
 }
@@ -4,7 +4,7 @@ use async_trait::async_trait;
 use collab_database::fields::Field;
 use collab_database::rows::{Cell, RowId};
 
-use flowy_error::FlowyResult;
+use flowy_error::{FlowyError, FlowyResult};
 use lib_infra::future::{to_fut, Fut};
 
 use crate::entities::FieldType;
@@ -79,6 +79,48 @@ pub async fn new_group_controller(
   }
 }
 
+/// Returns the controller of the swimlanes of the board, or None if the view isn't a board or
+/// has no swimlanes.
+pub async fn new_swimlane_controller(
+  view_id: String,
+  delegate: Arc<dyn DatabaseViewOperation>,
+) -> FlowyResult<Option<Box<dyn GroupController>>> {
+  if !delegate.get_layout_for_view(&view_id).is_board() {
+    return Ok(None);
+  }
+
+  let swimlane_field = SwimlaneSettingReaderImpl(delegate.clone())
+    .get_group_setting(&view_id)
+    .await
+    .and_then(|setting| delegate.get_field(&setting.field_id));
+  match swimlane_field {
+    None => Ok(None),
+    Some(swimlane_field) => Ok(Some(
+      new_swimlane_controller_with_field(view_id, delegate, Arc::new(swimlane_field)).await?,
+    )),
+  }
+}
+
+pub async fn new_swimlane_controller_with_field(
+  view_id: String,
+  delegate: Arc<dyn DatabaseViewOperation>,
+  swimlane_field: Arc<Field>,
+) -> FlowyResult<Box<dyn GroupController>> {
+  let setting_reader = SwimlaneSettingReaderImpl(delegate.clone());
+  let rows = delegate.get_rows(&view_id).await;
+  let setting_writer = SwimlaneSettingWriterImpl(delegate.clone());
+  let type_option_writer = GroupTypeOptionCellWriterImpl(delegate.clone());
+  make_group_controller(
+    view_id,
+    swimlane_field,
+    rows,
+    setting_reader,
+    setting_writer,
+    type_option_writer,
+  )
+  .await
+}
+
 pub(crate) struct GroupSettingReaderImpl(pub Arc<dyn DatabaseViewOperation>);
 
 impl GroupSettingReader for GroupSettingReaderImpl {
@@ -156,12 +198,65 @@ pub(crate) async fn get_cells_for_field(
 
 struct GroupSettingWriterImpl(Arc<dyn DatabaseViewOperation>);
 impl GroupSettingWriter for GroupSettingWriterImpl {
-  fn save_configuration(&self, view_id: &str, group_setting: GroupSetting) -> Fut<FlowyResult<()>> {
+  fn save_configuration(
+    &self,
+    view_id: &str,
+    mut group_setting: GroupSetting,
+  ) -> Fut<FlowyResult<()>> {
+    // The swimlane setting is saved by the [SwimlaneSettingWriterImpl], so keep the stored one
+    // instead of the one cached when the group controller was created.
+    group_setting.swimlane = self
+      .0
+      .get_group_setting(view_id)
+      .into_iter()
+      .next()
+      .and_then(|setting| setting.swimlane);
     self.0.insert_group_setting(view_id, group_setting);
     to_fut(async move { Ok(()) })
   }
 }
 
+/// Reads the swimlane setting that is nested in the group setting of the view.
+pub(crate) struct SwimlaneSettingReaderImpl(pub Arc<dyn DatabaseViewOperation>);
+
+impl GroupSettingReader for SwimlaneSettingReaderImpl {
+  fn get_group_setting(&self, view_id: &str) -> Fut<Option<Arc<GroupSetting>>> {
+    let swimlane = self
+      .0
+      .get_group_setting(view_id)
+      .into_iter()
+      .next()
+      .and_then(|setting| setting.swimlane);
+    to_fut(async move { swimlane.map(|swimlane| Arc::new(*swimlane)) })
+  }
+
+  fn get_configuration_cells(&self, view_id: &str, field_id: &str) -> Fut<Vec<RowSingleCellData>> {
+    let field_id = field_id.to_owned();
+    let view_id = view_id.to_owned();
+    let delegate = self.0.clone();
+    to_fut(async move { get_cells_for_field(delegate, &view_id, &field_id).await })
+  }
+}
+
+/// Saves the swimlane setting in the group setting of the view.
+struct SwimlaneSettingWriterImpl(Arc<dyn DatabaseViewOperation>);
+impl GroupSettingWriter for SwimlaneSettingWriterImpl {
+  fn save_configuration(&self, view_id: &str, swimlane: GroupSetting) -> Fut<FlowyResult<()>> {
+    let result = match self.0.get_group_setting(view_id).into_iter().next() {
+      None => Err(
+        FlowyError::record_not_found()
+          .with_context("The view must be grouped before adding the swimlanes"),
+      ),
+      Some(mut setting) => {
+        setting.swimlane = Some(Box::new(swimlane));
+        self.0.insert_group_setting(view_id, setting);
+        Ok(())
+      },
+    };
+    to_fut(async move { result })
+  }
+}
+
 struct GroupTypeOptionCellWriterImpl(Arc<dyn DatabaseViewOperation>);
 
 #[async_trait]
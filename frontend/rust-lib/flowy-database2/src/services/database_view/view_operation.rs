@@ -99,7 +99,11 @@ pub trait DatabaseViewOperation: Send + Sync + 'static {
 
   fn get_filter(&self, view_id: &str, filter_id: &str) -> Option<Filter>;
 
-  fn get_filter_by_field_id(&self, view_id: &str, field_id: &str) -> Option<Filter>;
+  /// Returns all the filters of the view that reference the field, including the nested ones.
+  fn get_filters_by_field_id(&self, view_id: &str, field_id: &str) -> Vec<Filter>;
+
+  /// Returns the email of the current user, which identifies the user in the person cells.
+  fn get_current_user_email(&self) -> Option<String>;
 
   fn get_layout_setting(&self, view_id: &str, layout_ty: &DatabaseLayout) -> Option<LayoutSetting>;
 
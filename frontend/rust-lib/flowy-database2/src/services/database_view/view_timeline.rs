@@ -0,0 +1,81 @@
+use collab_database::rows::Cells;
+
+use crate::services::field::{DateCellData, RelationCellData};
+use crate::services::setting::TimelineLayoutSetting;
+
+/// Returns the start and the end of the span of the row with the `cells`, or None if the row has
+/// no start date. The span of a row without an end date starts and ends on the same date.
+pub(crate) fn timeline_span_dates(
+  setting: &TimelineLayoutSetting,
+  cells: &Cells,
+) -> Option<(i64, i64)> {
+  let start_cell_data = DateCellData::from(cells.get(&setting.start_field_id)?);
+  let start = start_cell_data.timestamp?;
+  let end = match &setting.end_field_id {
+    Some(end_field_id) => cells
+      .get(end_field_id)
+      .and_then(|cell| DateCellData::from(cell).timestamp),
+    None if start_cell_data.is_range => start_cell_data.end_timestamp,
+    None => None,
+  };
+  Some((start, end.unwrap_or(start).max(start)))
+}
+
+/// Returns the ids of the rows that the row with the `cells` depends on.
+pub(crate) fn timeline_span_dependencies(
+  setting: &TimelineLayoutSetting,
+  cells: &Cells,
+) -> Vec<String> {
+  setting
+    .dependency_field_id
+    .as_ref()
+    .and_then(|field_id| cells.get(field_id))
+    .map(|cell| {
+      RelationCellData::from(cell)
+        .row_ids
+        .into_iter()
+        .map(|row_id| row_id.into_inner())
+        .collect()
+    })
+    .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+  use collab_database::rows::{Cell, Cells};
+
+  use crate::services::database_view::view_timeline::timeline_span_dates;
+  use crate::services::field::DateCellData;
+  use crate::services::setting::TimelineLayoutSetting;
+
+  fn date_cell(timestamp: i64, end_timestamp: Option<i64>) -> Cell {
+    let cell_data = DateCellData {
+      timestamp: Some(timestamp),
+      end_timestamp,
+      include_time: false,
+      is_range: end_timestamp.is_some(),
+      reminder_id: String::new(),
+    };
+    Cell::from(&cell_data)
+  }
+
+  #[test]
+  fn timeline_span_dates_test() {
+    let mut cells = Cells::default();
+    cells.insert("start".to_string(), date_cell(100, None));
+    cells.insert("end".to_string(), date_cell(200, None));
+    cells.insert("range".to_string(), date_cell(300, Some(400)));
+
+    let mut setting = TimelineLayoutSetting::new("start".to_string());
+    assert_eq!(timeline_span_dates(&setting, &cells), Some((100, 100)));
+
+    setting.end_field_id = Some("end".to_string());
+    assert_eq!(timeline_span_dates(&setting, &cells), Some((100, 200)));
+
+    let setting = TimelineLayoutSetting::new("range".to_string());
+    assert_eq!(timeline_span_dates(&setting, &cells), Some((300, 400)));
+
+    let setting = TimelineLayoutSetting::new("missing".to_string());
+    assert_eq!(timeline_span_dates(&setting, &cells), None);
+  }
+}
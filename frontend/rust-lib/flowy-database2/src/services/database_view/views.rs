@@ -73,7 +73,13 @@ impl DatabaseViews {
     let view_editor = self.get_view_editor(view_id).await?;
     let mut row_changeset = RowChangeset::new(row_detail.row.id.clone());
     view_editor
-      .v_move_group_row(&row_detail, &mut row_changeset, &to_group_id, to_row_id)
+      .v_move_group_row(
+        &row_detail,
+        &mut row_changeset,
+        &to_group_id,
+        None,
+        to_row_id,
+      )
       .await;
 
     if !row_changeset.is_empty() {
@@ -0,0 +1,477 @@
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+
+use collab::core::any_map::AnyMapExtension;
+use collab_database::fields::{Field, TypeOptionData, TypeOptionDataBuilder};
+use collab_database::rows::{Cell, RowCell, RowId};
+use fancy_regex::Regex;
+use lazy_static::lazy_static;
+use lru::LruCache;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use flowy_error::{ErrorCode, FlowyError, FlowyResult};
+
+use crate::entities::{
+  FieldType, FieldValidationErrorPB, FieldValidationRulePB, RepeatedFieldValidationErrorPB,
+};
+use crate::services::cell::stringify_cell_data;
+use crate::services::field::{TypeOptionCellExt, URLCellData};
+
+/// The key of the validation rules in the type options of the field. The rules are stored next to
+/// the type options of each field type, so they are kept when the field type is switched.
+pub const FIELD_VALIDATION_KEY: &str = "validation";
+
+lazy_static! {
+  /// The pattern is checked against every edited cell of the field, so it's compiled once when the
+  /// rules are set and the compiled regex is cached. The error is cached for the invalid patterns,
+  /// e.g. the ones synced from an older version.
+  static ref PATTERN_CACHE: Mutex<LruCache<String, Result<Arc<Regex>, String>>> =
+    Mutex::new(LruCache::new(NonZeroUsize::new(64).unwrap()));
+}
+
+/// Returns the regex that matches the whole content against the pattern, or the reason why the
+/// pattern is invalid.
+fn get_pattern_regex(pattern: &str) -> Result<Arc<Regex>, String> {
+  let mut cache = PATTERN_CACHE.lock();
+  if let Some(regex) = cache.get(pattern) {
+    return regex.clone();
+  }
+
+  // The pattern is compiled on its own first, otherwise a pattern like `a)(b` would be accepted
+  // once wrapped.
+  let regex = Regex::new(pattern)
+    .and_then(|_| Regex::new(&format!("^(?:{})$", pattern)))
+    .map(Arc::new)
+    .map_err(|err| err.to_string());
+  cache.put(pattern.to_owned(), regex.clone());
+  regex
+}
+
+/// The validation rules of a field. Each rule is only checked for the field types it makes sense
+/// for, and the empty cells only break the `required` rule.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FieldValidation {
+  #[serde(default)]
+  pub required: bool,
+  /// The minimum value of a number cell.
+  #[serde(default)]
+  pub min: Option<f64>,
+  /// The maximum value of a number cell.
+  #[serde(default)]
+  pub max: Option<f64>,
+  /// The maximum number of characters of a text or url cell.
+  #[serde(default)]
+  pub max_length: Option<usize>,
+  /// The regex that the whole content of a text or url cell must match.
+  #[serde(default)]
+  pub pattern: Option<String>,
+  /// The url cells must use the https scheme.
+  #[serde(default)]
+  pub https_only: bool,
+  /// No two rows can have the same value in the field.
+  #[serde(default)]
+  pub unique: bool,
+}
+
+impl FieldValidation {
+  pub fn from_field(field: &Field) -> Option<Self> {
+    field
+      .type_options
+      .get(FIELD_VALIDATION_KEY)
+      .cloned()
+      .map(Self::from)
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self == &Self::default()
+  }
+
+  /// Returns an error if the rules can't be applied, for example if the pattern is not a valid
+  /// regex or the minimum is greater than the maximum.
+  pub fn check(&self) -> FlowyResult<()> {
+    if let Some(pattern) = &self.pattern {
+      get_pattern_regex(pattern).map_err(|err| {
+        FlowyError::new(
+          ErrorCode::InvalidParams,
+          format!("Invalid pattern: {}", err),
+        )
+      })?;
+    }
+
+    if let (Some(min), Some(max)) = (self.min, self.max) {
+      if min > max {
+        return Err(FlowyError::new(
+          ErrorCode::InvalidParams,
+          "The minimum is greater than the maximum",
+        ));
+      }
+    }
+    Ok(())
+  }
+
+  /// Returns the rules that the cell breaks. The `unique` rule is checked against the other rows
+  /// by [find_duplicated_values].
+  pub fn validate_cell(
+    &self,
+    field: &Field,
+    row_id: &RowId,
+    cell: Option<&Cell>,
+  ) -> Vec<FieldValidationErrorPB> {
+    let field_type = FieldType::from(field.field_type);
+    if !can_validate(&field_type) {
+      return vec![];
+    }
+
+    let handler = TypeOptionCellExt::new_with_cell_data_cache(field, None)
+      .get_type_option_cell_data_handler(&field_type);
+    let is_empty = match (cell, handler.as_ref()) {
+      (Some(cell), Some(handler)) => handler.handle_is_cell_empty(cell, field),
+      _ => true,
+    };
+
+    let error = |rule: FieldValidationRulePB, message: String| FieldValidationErrorPB {
+      row_id: row_id.to_string(),
+      field_id: field.id.clone(),
+      rule,
+      message,
+    };
+
+    let mut errors = vec![];
+    let cell = match cell {
+      Some(cell) if !is_empty => cell,
+      _ => {
+        if self.required {
+          errors.push(error(
+            FieldValidationRulePB::Required,
+            format!("{} is required", field.name),
+          ));
+        }
+        return errors;
+      },
+    };
+
+    if field_type.is_number() {
+      let number = handler.and_then(|handler| handler.handle_numeric_cell(cell));
+      if let (Some(min), Some(number)) = (self.min, number) {
+        if number < min {
+          errors.push(error(
+            FieldValidationRulePB::Min,
+            format!("{} must be at least {}", field.name, min),
+          ));
+        }
+      }
+
+      if let (Some(max), Some(number)) = (self.max, number) {
+        if number > max {
+          errors.push(error(
+            FieldValidationRulePB::Max,
+            format!("{} must be at most {}", field.name, max),
+          ));
+        }
+      }
+    }
+
+    if field_type.is_text() || field_type.is_url() {
+      let content = stringify_cell_data(cell, &field_type, &field_type, field);
+      if let Some(max_length) = self.max_length {
+        if content.chars().count() > max_length {
+          errors.push(error(
+            FieldValidationRulePB::MaxLength,
+            format!("{} must be at most {} characters", field.name, max_length),
+          ));
+        }
+      }
+
+      if let Some(pattern) = &self.pattern {
+        // The whole content must match, not only a part of it. The cell can't be checked against
+        // an invalid pattern, so it's reported instead of being accepted.
+        let message = match get_pattern_regex(pattern) {
+          Ok(regex) => match regex.is_match(&content) {
+            Ok(true) => None,
+            Ok(false) => Some(format!(
+              "{} doesn't match the pattern {}",
+              field.name, pattern
+            )),
+            Err(err) => Some(format!(
+              "{} can't be matched against the pattern {}: {}",
+              field.name, pattern, err
+            )),
+          },
+          Err(err) => Some(format!(
+            "The pattern {} of {} is invalid: {}",
+            pattern, field.name, err
+          )),
+        };
+        if let Some(message) = message {
+          errors.push(error(FieldValidationRulePB::Pattern, message));
+        }
+      }
+    }
+
+    if field_type.is_url() && self.https_only {
+      let url = URLCellData::from(cell).data;
+      if !url.trim().to_lowercase().starts_with("https://") {
+        errors.push(error(
+          FieldValidationRulePB::HttpsOnly,
+          format!("{} must be an https url", field.name),
+        ));
+      }
+    }
+
+    errors
+  }
+}
+
+impl From<TypeOptionData> for FieldValidation {
+  fn from(data: TypeOptionData) -> Self {
+    data
+      .get_str_value("content")
+      .and_then(|content| serde_json::from_str(&content).ok())
+      .unwrap_or_default()
+  }
+}
+
+impl From<FieldValidation> for TypeOptionData {
+  fn from(data: FieldValidation) -> Self {
+    let content = serde_json::to_string(&data).unwrap_or_default();
+    TypeOptionDataBuilder::new()
+      .insert_str_value("content", content)
+      .build()
+  }
+}
+
+/// The computed fields can't be edited, so their cells are never validated.
+pub fn can_validate(field_type: &FieldType) -> bool {
+  !matches!(
+    field_type,
+    FieldType::CreatedTime
+      | FieldType::LastEditedTime
+      | FieldType::Formula
+      | FieldType::Lookup
+      | FieldType::AutoNumber
+  )
+}
+
+/// Returns the value of the cell that is compared by the `unique` rule, or None if the cell is
+/// empty.
+pub fn unique_value(field: &Field, cell: &Cell) -> Option<String> {
+  let field_type = FieldType::from(field.field_type);
+  let value = stringify_cell_data(cell, &field_type, &field_type, field);
+  let value = value.trim();
+  if value.is_empty() {
+    None
+  } else {
+    Some(value.to_string())
+  }
+}
+
+/// Returns the ids of the rows whose value of the field is also used by another row.
+pub fn find_duplicated_values(field: &Field, row_cells: &[RowCell]) -> Vec<RowId> {
+  let mut rows_by_value: HashMap<String, Vec<usize>> = HashMap::new();
+  for (index, row_cell) in row_cells.iter().enumerate() {
+    if let Some(value) = row_cell
+      .cell
+      .as_ref()
+      .and_then(|cell| unique_value(field, cell))
+    {
+      rows_by_value.entry(value).or_default().push(index);
+    }
+  }
+
+  // Keep the order of the rows
+  let mut indexes = rows_by_value
+    .into_values()
+    .filter(|indexes| indexes.len() > 1)
+    .flatten()
+    .collect::<Vec<usize>>();
+  indexes.sort();
+  indexes
+    .into_iter()
+    .map(|index| row_cells[index].row_id.clone())
+    .collect()
+}
+
+/// Returns a [ErrorCode::FieldValidationFailed] error whose payload is the broken rules.
+pub fn field_validation_error(errors: Vec<FieldValidationErrorPB>) -> FlowyError {
+  let msg = errors
+    .iter()
+    .map(|error| error.message.clone())
+    .collect::<Vec<String>>()
+    .join(", ");
+  FlowyError::new(ErrorCode::FieldValidationFailed, msg)
+    .with_payload(RepeatedFieldValidationErrorPB { items: errors })
+}
+
+#[cfg(test)]
+mod tests {
+  use collab_database::fields::Field;
+  use collab_database::rows::{Cell, RowCell, RowId};
+
+  use crate::entities::{FieldType, FieldValidationRulePB};
+  use crate::services::cell::{insert_number_cell, insert_text_cell, insert_url_cell};
+  use crate::services::field::{
+    find_duplicated_values, FieldBuilder, FieldValidation, NumberTypeOption, RichTextTypeOption,
+    URLTypeOption,
+  };
+
+  fn rules(
+    validation: &FieldValidation,
+    field: &Field,
+    cell: Option<&Cell>,
+  ) -> Vec<FieldValidationRulePB> {
+    validation
+      .validate_cell(field, &RowId::from("row".to_string()), cell)
+      .into_iter()
+      .map(|error| error.rule)
+      .collect()
+  }
+
+  #[test]
+  fn required_rule_test() {
+    let field = FieldBuilder::new(FieldType::RichText, RichTextTypeOption::default()).build();
+    let validation = FieldValidation {
+      required: true,
+      ..Default::default()
+    };
+
+    let empty_cell = insert_text_cell("".to_string(), &field);
+    assert_eq!(
+      rules(&validation, &field, None),
+      vec![FieldValidationRulePB::Required]
+    );
+    assert_eq!(
+      rules(&validation, &field, Some(&empty_cell)),
+      vec![FieldValidationRulePB::Required]
+    );
+
+    let cell = insert_text_cell("hello".to_string(), &field);
+    assert!(rules(&validation, &field, Some(&cell)).is_empty());
+  }
+
+  #[test]
+  fn number_range_rule_test() {
+    let field = FieldBuilder::new(FieldType::Number, NumberTypeOption::default()).build();
+    let validation = FieldValidation {
+      min: Some(1.0),
+      max: Some(10.0),
+      ..Default::default()
+    };
+
+    let tests = vec![
+      (0, vec![FieldValidationRulePB::Min]),
+      (1, vec![]),
+      (10, vec![]),
+      (11, vec![FieldValidationRulePB::Max]),
+    ];
+    for (number, expected) in tests {
+      let cell = insert_number_cell(number, &field);
+      assert_eq!(
+        rules(&validation, &field, Some(&cell)),
+        expected,
+        "{}",
+        number
+      );
+    }
+
+    // The empty cells only break the required rule
+    assert!(rules(&validation, &field, None).is_empty());
+  }
+
+  #[test]
+  fn text_length_and_pattern_rule_test() {
+    let field = FieldBuilder::new(FieldType::RichText, RichTextTypeOption::default()).build();
+    let validation = FieldValidation {
+      max_length: Some(6),
+      pattern: Some("[A-Z]+-\\d+".to_string()),
+      ..Default::default()
+    };
+
+    let tests = vec![
+      ("BUG-12", vec![]),
+      ("BUG-123", vec![FieldValidationRulePB::MaxLength]),
+      ("bug-12", vec![FieldValidationRulePB::Pattern]),
+      ("xBUG-1", vec![FieldValidationRulePB::Pattern]),
+    ];
+    for (text, expected) in tests {
+      let cell = insert_text_cell(text.to_string(), &field);
+      assert_eq!(
+        rules(&validation, &field, Some(&cell)),
+        expected,
+        "{}",
+        text
+      );
+    }
+  }
+
+  #[test]
+  fn https_rule_test() {
+    let field = FieldBuilder::new(FieldType::URL, URLTypeOption::default()).build();
+    let validation = FieldValidation {
+      https_only: true,
+      ..Default::default()
+    };
+
+    let cell = insert_url_cell("https://appflowy.io".to_string(), &field);
+    assert!(rules(&validation, &field, Some(&cell)).is_empty());
+
+    let cell = insert_url_cell("http://appflowy.io".to_string(), &field);
+    assert_eq!(
+      rules(&validation, &field, Some(&cell)),
+      vec![FieldValidationRulePB::HttpsOnly]
+    );
+  }
+
+  #[test]
+  fn invalid_rules_test() {
+    let validation = FieldValidation {
+      pattern: Some("[a-z".to_string()),
+      ..Default::default()
+    };
+    assert!(validation.check().is_err());
+
+    // The invalid pattern that was stored anyway is reported instead of accepting every cell
+    let field = FieldBuilder::new(FieldType::RichText, RichTextTypeOption::default()).build();
+    let cell = insert_text_cell("abc".to_string(), &field);
+    assert_eq!(
+      rules(&validation, &field, Some(&cell)),
+      vec![FieldValidationRulePB::Pattern]
+    );
+
+    let validation = FieldValidation {
+      pattern: Some("a)(b".to_string()),
+      ..Default::default()
+    };
+    assert!(validation.check().is_err());
+
+    let validation = FieldValidation {
+      min: Some(2.0),
+      max: Some(1.0),
+      ..Default::default()
+    };
+    assert!(validation.check().is_err());
+  }
+
+  #[test]
+  fn find_duplicated_values_test() {
+    let field = FieldBuilder::new(FieldType::RichText, RichTextTypeOption::default()).build();
+    let row_cells = vec!["a", "b", " a ", "", ""]
+      .into_iter()
+      .enumerate()
+      .map(|(i, text)| RowCell {
+        row_id: RowId::from(i.to_string()),
+        cell: Some(insert_text_cell(text.to_string(), &field)),
+      })
+      .chain(vec![RowCell {
+        row_id: RowId::from("5".to_string()),
+        cell: None,
+      }])
+      .collect::<Vec<RowCell>>();
+
+    assert_eq!(
+      find_duplicated_values(&field, &row_cells),
+      vec![RowId::from("0".to_string()), RowId::from("2".to_string())]
+    );
+  }
+}
@@ -1,7 +1,9 @@
 mod field_builder;
 mod field_operation;
+mod field_validation;
 mod type_options;
 
 pub use field_builder::*;
 pub use field_operation::*;
+pub use field_validation::*;
 pub use type_options::*;
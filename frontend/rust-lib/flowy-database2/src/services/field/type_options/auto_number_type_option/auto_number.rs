@@ -0,0 +1,237 @@
+use std::cmp::Ordering;
+
+use collab::core::any_map::AnyMapExtension;
+use collab_database::fields::{Field, TypeOptionData, TypeOptionDataBuilder};
+use collab_database::rows::Cell;
+use serde::{Deserialize, Serialize};
+
+use flowy_error::FlowyResult;
+
+use crate::entities::{FieldType, NumberFilterPB};
+use crate::services::cell::{CellDataChangeset, CellDataDecoder};
+use crate::services::field::{
+  AutoNumberCellData, ProtobufStr, TypeOption, TypeOptionCellDataCompare, TypeOptionCellDataFilter,
+  TypeOptionCellDataSerde, TypeOptionTransform,
+};
+use crate::services::sort::SortCondition;
+
+/// The [AutoNumberTypeOption] gives each new row a unique, increasing number. The number is
+/// displayed with the `prefix`, e.g. `BUG-123`.
+///
+/// The numbers are allocated by the [DatabaseEditor](crate::services::database::DatabaseEditor)
+/// when a row is created. The number stays pending until the database is synced, the pending
+/// numbers that were taken by another device are resolved by [resolve_auto_numbers]. The synced
+/// numbers are never changed.
+///
+/// [resolve_auto_numbers]: crate::services::field::resolve_auto_numbers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutoNumberTypeOption {
+  pub prefix: String,
+  /// The number of the next created row. It's moved past the numbers of the existing rows when
+  /// the field is created or updated, and whenever the database is synced.
+  pub next_number: i64,
+}
+
+impl Default for AutoNumberTypeOption {
+  fn default() -> Self {
+    Self {
+      prefix: "".to_owned(),
+      next_number: 1,
+    }
+  }
+}
+
+impl AutoNumberTypeOption {
+  pub fn format(&self, number: i64) -> String {
+    format!("{}{}", self.prefix, number)
+  }
+
+  /// Returns the number of a new row and moves the `next_number` forward.
+  pub fn allocate(&mut self) -> i64 {
+    let number = self.next_number;
+    self.next_number += 1;
+    number
+  }
+}
+
+impl From<TypeOptionData> for AutoNumberTypeOption {
+  fn from(data: TypeOptionData) -> Self {
+    let prefix = data.get_str_value("prefix").unwrap_or_default();
+    let next_number = data.get_i64_value("next_number").unwrap_or(1).max(1);
+    Self {
+      prefix,
+      next_number,
+    }
+  }
+}
+
+impl From<AutoNumberTypeOption> for TypeOptionData {
+  fn from(data: AutoNumberTypeOption) -> Self {
+    TypeOptionDataBuilder::new()
+      .insert_str_value("prefix", data.prefix)
+      .insert_i64_value("next_number", data.next_number)
+      .build()
+  }
+}
+
+impl TypeOption for AutoNumberTypeOption {
+  type CellData = AutoNumberCellData;
+  type CellChangeset = String;
+  type CellProtobufType = ProtobufStr;
+  type CellFilter = NumberFilterPB;
+}
+
+impl CellDataChangeset for AutoNumberTypeOption {
+  /// The number is assigned when the row is created, so the changeset is ignored and the cell is
+  /// kept as it is.
+  fn apply_changeset(
+    &self,
+    _changeset: <Self as TypeOption>::CellChangeset,
+    cell: Option<Cell>,
+  ) -> FlowyResult<(Cell, <Self as TypeOption>::CellData)> {
+    let cell_data = cell
+      .as_ref()
+      .map(AutoNumberCellData::from)
+      .unwrap_or_default();
+    Ok((Cell::from(cell_data.clone()), cell_data))
+  }
+}
+
+impl CellDataDecoder for AutoNumberTypeOption {
+  fn decode_cell(
+    &self,
+    cell: &Cell,
+    decoded_field_type: &FieldType,
+    _field: &Field,
+  ) -> FlowyResult<<Self as TypeOption>::CellData> {
+    if !decoded_field_type.is_auto_number() {
+      return Ok(Default::default());
+    }
+
+    self.parse_cell(cell)
+  }
+
+  fn stringify_cell_data(&self, cell_data: <Self as TypeOption>::CellData) -> String {
+    cell_data
+      .number
+      .map(|number| self.format(number))
+      .unwrap_or_default()
+  }
+
+  fn stringify_cell(&self, cell: &Cell) -> String {
+    let cell_data = AutoNumberCellData::from(cell);
+    self.stringify_cell_data(cell_data)
+  }
+
+  fn numeric_cell(&self, cell: &Cell) -> Option<f64> {
+    AutoNumberCellData::from(cell)
+      .number
+      .map(|number| number as f64)
+  }
+}
+
+impl TypeOptionCellDataCompare for AutoNumberTypeOption {
+  fn apply_cmp(
+    &self,
+    cell_data: &<Self as TypeOption>::CellData,
+    other_cell_data: &<Self as TypeOption>::CellData,
+    sort_condition: SortCondition,
+  ) -> Ordering {
+    // The empty cells are always placed at the end.
+    match (cell_data.number, other_cell_data.number) {
+      (None, None) => Ordering::Equal,
+      (None, Some(_)) => Ordering::Greater,
+      (Some(_), None) => Ordering::Less,
+      (Some(left), Some(right)) => sort_condition.evaluate_order(left.cmp(&right)),
+    }
+  }
+}
+
+impl TypeOptionCellDataFilter for AutoNumberTypeOption {
+  fn apply_filter(
+    &self,
+    filter: &<Self as TypeOption>::CellFilter,
+    field_type: &FieldType,
+    cell_data: &<Self as TypeOption>::CellData,
+  ) -> bool {
+    if !field_type.is_auto_number() {
+      return true;
+    }
+
+    filter.is_auto_number_visible(cell_data, &self.prefix)
+  }
+}
+
+impl TypeOptionTransform for AutoNumberTypeOption {}
+
+impl TypeOptionCellDataSerde for AutoNumberTypeOption {
+  fn protobuf_encode(
+    &self,
+    cell_data: <Self as TypeOption>::CellData,
+  ) -> <Self as TypeOption>::CellProtobufType {
+    ProtobufStr::from(self.stringify_cell_data(cell_data))
+  }
+
+  fn parse_cell(&self, cell: &Cell) -> FlowyResult<<Self as TypeOption>::CellData> {
+    Ok(cell.into())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::cmp::Ordering;
+
+  use crate::services::cell::CellDataDecoder;
+  use crate::services::field::{
+    AutoNumberCellData, AutoNumberTypeOption, TypeOptionCellDataCompare,
+  };
+  use crate::services::sort::SortCondition;
+
+  #[test]
+  fn auto_number_allocate_test() {
+    let mut type_option = AutoNumberTypeOption::default();
+    assert_eq!(type_option.allocate(), 1);
+    assert_eq!(type_option.allocate(), 2);
+    assert_eq!(type_option.next_number, 3);
+  }
+
+  #[test]
+  fn auto_number_stringify_test() {
+    let type_option = AutoNumberTypeOption {
+      prefix: "BUG-".to_owned(),
+      next_number: 1,
+    };
+    assert_eq!(
+      type_option.stringify_cell_data(AutoNumberCellData::new(123)),
+      "BUG-123"
+    );
+    assert_eq!(
+      type_option.stringify_cell_data(AutoNumberCellData::default()),
+      ""
+    );
+  }
+
+  #[test]
+  fn auto_number_sort_test() {
+    let type_option = AutoNumberTypeOption::default();
+    let (one, two, empty) = (
+      AutoNumberCellData::new(1),
+      AutoNumberCellData::new(2),
+      AutoNumberCellData::default(),
+    );
+    // The numbers are compared as numbers, not as the formatted strings.
+    let ten = AutoNumberCellData::new(10);
+    assert_eq!(
+      type_option.apply_cmp(&two, &ten, SortCondition::Ascending),
+      Ordering::Less
+    );
+    assert_eq!(
+      type_option.apply_cmp(&one, &two, SortCondition::Descending),
+      Ordering::Greater
+    );
+    assert_eq!(
+      type_option.apply_cmp(&empty, &one, SortCondition::Descending),
+      Ordering::Greater
+    );
+  }
+}
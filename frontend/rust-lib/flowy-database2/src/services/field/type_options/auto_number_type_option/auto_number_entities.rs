@@ -0,0 +1,285 @@
+use std::collections::HashSet;
+
+use collab::core::any_map::AnyMapExtension;
+use collab_database::rows::{new_cell_builder, Cell, RowId};
+
+use crate::entities::FieldType;
+use crate::services::field::{TypeOptionCellData, CELL_DATA};
+
+/// The number that was assigned to the row when it was created. The prefix is not stored in the
+/// cell, so renaming the prefix applies to all the existing rows.
+///
+/// The number is pending until the device that allocated it is synced and finds it isn't taken
+/// by another row. The pending number might still change, the synced one never does.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AutoNumberCellData {
+  pub number: Option<i64>,
+  /// The id of the device that allocated the number, as long as the number is pending.
+  pub pending_device_id: Option<String>,
+}
+
+impl AutoNumberCellData {
+  pub fn new(number: i64) -> Self {
+    Self {
+      number: Some(number),
+      pending_device_id: None,
+    }
+  }
+
+  pub fn new_pending(number: i64, device_id: String) -> Self {
+    Self {
+      number: Some(number),
+      pending_device_id: Some(device_id),
+    }
+  }
+
+  pub fn is_pending(&self) -> bool {
+    self.pending_device_id.is_some()
+  }
+}
+
+impl TypeOptionCellData for AutoNumberCellData {
+  fn is_cell_empty(&self) -> bool {
+    self.number.is_none()
+  }
+}
+
+const PENDING_DEVICE_ID: &str = "pending_device_id";
+
+impl From<&Cell> for AutoNumberCellData {
+  fn from(cell: &Cell) -> Self {
+    let number = cell
+      .get_str_value(CELL_DATA)
+      .and_then(|s| s.trim().parse::<i64>().ok());
+    let pending_device_id = cell
+      .get_str_value(PENDING_DEVICE_ID)
+      .filter(|device_id| !device_id.is_empty());
+    Self {
+      number,
+      pending_device_id,
+    }
+  }
+}
+
+impl From<AutoNumberCellData> for Cell {
+  fn from(data: AutoNumberCellData) -> Self {
+    new_cell_builder(FieldType::AutoNumber)
+      .insert_str_value(CELL_DATA, data.to_string())
+      .insert_str_value(
+        PENDING_DEVICE_ID,
+        data.pending_device_id.unwrap_or_default(),
+      )
+      .build()
+  }
+}
+
+impl ToString for AutoNumberCellData {
+  fn to_string(&self) -> String {
+    self
+      .number
+      .map(|number| number.to_string())
+      .unwrap_or_default()
+  }
+}
+
+/// The auto number of a row, as read from the database.
+#[derive(Debug, Clone)]
+pub struct AutoNumberRow {
+  pub row_id: RowId,
+  pub created_at: i64,
+  pub cell_data: AutoNumberCellData,
+}
+
+/// Resolves the auto numbers that are still pending. This is needed when two devices create rows
+/// while offline: both of them allocate the same number, and the collision only shows up once
+/// they are synced.
+///
+/// The synced numbers are never changed, even if two of them are the same, because they might
+/// already be referenced elsewhere. The pending numbers of this device that are taken by a synced
+/// row, or by a pending row created before, are renumbered after the last number and stay
+/// pending. The rows without a number are numbered the same way. When `is_synced` is true, the
+/// pending numbers that are free are confirmed. The pending numbers of the other devices are left
+/// to their devices.
+///
+/// Returns the rows that have to be updated and the next number of the type option.
+pub fn resolve_auto_numbers(
+  next_number: i64,
+  mut rows: Vec<AutoNumberRow>,
+  device_id: &str,
+  is_synced: bool,
+) -> (Vec<(RowId, AutoNumberCellData)>, i64) {
+  rows.sort_by(|left, right| {
+    left
+      .created_at
+      .cmp(&right.created_at)
+      .then_with(|| left.row_id.as_str().cmp(right.row_id.as_str()))
+  });
+
+  let max_number = rows.iter().flat_map(|row| row.cell_data.number).max();
+  let mut next_number = next_number.max(max_number.map_or(1, |number| number + 1));
+  let mut used_numbers = rows
+    .iter()
+    .filter(|row| !row.cell_data.is_pending())
+    .flat_map(|row| row.cell_data.number)
+    .collect::<HashSet<i64>>();
+  let mut changes = vec![];
+  for row in rows {
+    let cell_data = row.cell_data;
+    if cell_data.number.is_some() && !cell_data.is_pending() {
+      continue;
+    }
+
+    let is_own =
+      cell_data.number.is_none() || cell_data.pending_device_id.as_deref() == Some(device_id);
+    match cell_data.number {
+      Some(number) if used_numbers.insert(number) => {
+        if is_own && is_synced {
+          changes.push((row.row_id, AutoNumberCellData::new(number)));
+        }
+      },
+      Some(_) if !is_own => {},
+      _ => {
+        used_numbers.insert(next_number);
+        changes.push((
+          row.row_id,
+          AutoNumberCellData::new_pending(next_number, device_id.to_owned()),
+        ));
+        next_number += 1;
+      },
+    }
+  }
+  (changes, next_number)
+}
+
+#[cfg(test)]
+mod tests {
+  use collab_database::rows::{Cell, RowId};
+
+  use crate::services::field::{resolve_auto_numbers, AutoNumberCellData, AutoNumberRow};
+
+  const DEVICE_ID: &str = "device-1";
+
+  fn row(id: &str, created_at: i64, number: Option<i64>) -> AutoNumberRow {
+    AutoNumberRow {
+      row_id: RowId::from(id.to_string()),
+      created_at,
+      cell_data: AutoNumberCellData {
+        number,
+        pending_device_id: None,
+      },
+    }
+  }
+
+  fn pending_row(id: &str, created_at: i64, number: i64, device_id: &str) -> AutoNumberRow {
+    AutoNumberRow {
+      row_id: RowId::from(id.to_string()),
+      created_at,
+      cell_data: AutoNumberCellData::new_pending(number, device_id.to_string()),
+    }
+  }
+
+  #[test]
+  fn auto_number_cell_round_trip_test() {
+    let cell = Cell::from(AutoNumberCellData::new(42));
+    assert_eq!(AutoNumberCellData::from(&cell), AutoNumberCellData::new(42));
+
+    let pending = AutoNumberCellData::new_pending(42, DEVICE_ID.to_string());
+    let cell = Cell::from(pending.clone());
+    assert_eq!(AutoNumberCellData::from(&cell), pending);
+
+    let cell = Cell::from(AutoNumberCellData::default());
+    assert_eq!(AutoNumberCellData::from(&cell).number, None);
+  }
+
+  #[test]
+  fn resolve_auto_numbers_without_conflict_test() {
+    let rows = vec![row("a", 1, Some(1)), row("b", 2, Some(2))];
+    let (changes, next_number) = resolve_auto_numbers(3, rows, DEVICE_ID, true);
+    assert!(changes.is_empty());
+    assert_eq!(next_number, 3);
+  }
+
+  #[test]
+  fn resolve_auto_numbers_conflict_test() {
+    // The row 3 was synced from another device while this device created its row 3 offline.
+    // The pending row is renumbered, even though it was created before.
+    let rows = vec![
+      pending_row("c", 3, 3, DEVICE_ID),
+      row("a", 1, Some(1)),
+      row("d", 4, Some(3)),
+      row("b", 2, Some(2)),
+    ];
+    let (changes, next_number) = resolve_auto_numbers(4, rows, DEVICE_ID, true);
+    assert_eq!(
+      changes,
+      vec![(
+        RowId::from("c".to_string()),
+        AutoNumberCellData::new_pending(4, DEVICE_ID.to_string())
+      )]
+    );
+    assert_eq!(next_number, 5);
+  }
+
+  #[test]
+  fn resolve_auto_numbers_keeps_synced_numbers_test() {
+    // The synced numbers are never changed, even when they are the same.
+    let rows = vec![row("a", 1, Some(1)), row("b", 2, Some(1))];
+    let (changes, next_number) = resolve_auto_numbers(2, rows, DEVICE_ID, true);
+    assert!(changes.is_empty());
+    assert_eq!(next_number, 2);
+  }
+
+  #[test]
+  fn resolve_auto_numbers_confirm_pending_test() {
+    let rows = vec![row("a", 1, Some(1)), pending_row("b", 2, 2, DEVICE_ID)];
+    let (changes, _) = resolve_auto_numbers(3, rows.clone(), DEVICE_ID, false);
+    assert!(changes.is_empty());
+
+    let (changes, _) = resolve_auto_numbers(3, rows, DEVICE_ID, true);
+    assert_eq!(
+      changes,
+      vec![(RowId::from("b".to_string()), AutoNumberCellData::new(2))]
+    );
+  }
+
+  #[test]
+  fn resolve_auto_numbers_of_other_device_test() {
+    // The pending numbers of the other devices are left to them. The pending row created before
+    // keeps its number, so both devices agree on the row to renumber.
+    let rows = vec![
+      pending_row("a", 1, 1, "device-2"),
+      pending_row("b", 2, 1, DEVICE_ID),
+      pending_row("c", 3, 1, "device-2"),
+    ];
+    let (changes, next_number) = resolve_auto_numbers(2, rows, DEVICE_ID, true);
+    assert_eq!(
+      changes,
+      vec![(
+        RowId::from("b".to_string()),
+        AutoNumberCellData::new_pending(2, DEVICE_ID.to_string())
+      )]
+    );
+    assert_eq!(next_number, 3);
+  }
+
+  #[test]
+  fn resolve_auto_numbers_fill_empty_test() {
+    // The next number of the type option might be behind the numbers that were synced.
+    let rows = vec![row("a", 1, None), row("b", 2, Some(7)), row("c", 3, None)];
+    let (changes, next_number) = resolve_auto_numbers(1, rows, DEVICE_ID, false);
+    assert_eq!(
+      changes,
+      vec![
+        (
+          RowId::from("a".to_string()),
+          AutoNumberCellData::new_pending(8, DEVICE_ID.to_string())
+        ),
+        (
+          RowId::from("c".to_string()),
+          AutoNumberCellData::new_pending(9, DEVICE_ID.to_string())
+        )
+      ]
+    );
+    assert_eq!(next_number, 10);
+  }
+}
@@ -0,0 +1,54 @@
+use rust_decimal::Decimal;
+
+use crate::entities::NumberFilterPB;
+use crate::services::field::{AutoNumberCellData, NumberCellFormat};
+
+impl NumberFilterPB {
+  /// The auto number cell is filtered by its number. The `prefix` of the type option is removed
+  /// from the content of the filter, so both `BUG-12` and `12` match the twelfth row.
+  pub fn is_auto_number_visible(&self, cell_data: &AutoNumberCellData, prefix: &str) -> bool {
+    let content = self.content.trim();
+    let filter = NumberFilterPB {
+      condition: self.condition.clone(),
+      content: content
+        .strip_prefix(prefix)
+        .unwrap_or(content)
+        .trim()
+        .to_owned(),
+    };
+    let num_cell_data = cell_data
+      .number
+      .map(|number| NumberCellFormat::from_decimal(Decimal::from(number)))
+      .unwrap_or_default();
+    filter.is_visible(&num_cell_data)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::entities::{NumberFilterConditionPB, NumberFilterPB};
+  use crate::services::field::AutoNumberCellData;
+
+  #[test]
+  fn auto_number_filter_with_prefix_test() {
+    for content in ["BUG-12", "12"] {
+      let filter = NumberFilterPB {
+        condition: NumberFilterConditionPB::Equal,
+        content: content.to_owned(),
+      };
+      assert!(filter.is_auto_number_visible(&AutoNumberCellData::new(12), "BUG-"));
+      assert!(!filter.is_auto_number_visible(&AutoNumberCellData::new(13), "BUG-"));
+      assert!(!filter.is_auto_number_visible(&AutoNumberCellData::default(), "BUG-"));
+    }
+  }
+
+  #[test]
+  fn auto_number_filter_is_empty_test() {
+    let filter = NumberFilterPB {
+      condition: NumberFilterConditionPB::NumberIsEmpty,
+      content: "".to_owned(),
+    };
+    assert!(filter.is_auto_number_visible(&AutoNumberCellData::default(), ""));
+    assert!(!filter.is_auto_number_visible(&AutoNumberCellData::new(1), ""));
+  }
+}
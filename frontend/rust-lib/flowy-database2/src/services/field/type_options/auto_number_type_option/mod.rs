@@ -0,0 +1,7 @@
+#![allow(clippy::module_inception)]
+mod auto_number;
+mod auto_number_entities;
+mod auto_number_filter;
+
+pub use auto_number::*;
+pub use auto_number_entities::*;
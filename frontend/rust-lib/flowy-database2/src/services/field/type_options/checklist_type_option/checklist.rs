@@ -1,14 +1,17 @@
 use crate::entities::{ChecklistCellDataPB, ChecklistFilterPB, FieldType, SelectOptionPB};
-use crate::services::cell::{CellDataChangeset, CellDataDecoder};
+use crate::services::cell::{
+  CellDataChangeset, CellDataDecoder, CellProtobufBlobParser, DecodedCellData,
+};
 use crate::services::field::checklist_type_option::{ChecklistCellChangeset, ChecklistCellData};
 use crate::services::field::{
   SelectOption, TypeOption, TypeOptionCellDataCompare, TypeOptionCellDataFilter,
   TypeOptionCellDataSerde, TypeOptionTransform, SELECTION_IDS_SEPARATOR,
 };
 use crate::services::sort::SortCondition;
+use bytes::Bytes;
 use collab_database::fields::{Field, TypeOptionData, TypeOptionDataBuilder};
 use collab_database::rows::Cell;
-use flowy_error::FlowyResult;
+use flowy_error::{internal_error, FlowyResult};
 use std::cmp::Ordering;
 
 #[derive(Debug, Clone, Default)]
@@ -191,18 +194,30 @@ impl TypeOptionCellDataCompare for ChecklistTypeOption {
     &self,
     cell_data: &<Self as TypeOption>::CellData,
     other_cell_data: &<Self as TypeOption>::CellData,
-    _sort_condition: SortCondition,
+    sort_condition: SortCondition,
   ) -> Ordering {
     let left = cell_data.percentage_complete();
     let right = other_cell_data.percentage_complete();
-    if left > right {
-      Ordering::Greater
-    } else if left < right {
-      Ordering::Less
-    } else {
-      Ordering::Equal
-    }
+    let order = left.partial_cmp(&right).unwrap_or(Ordering::Equal);
+    sort_condition.evaluate_order(order)
   }
 }
 
 impl TypeOptionTransform for ChecklistTypeOption {}
+
+impl DecodedCellData for ChecklistCellDataPB {
+  type Object = ChecklistCellDataPB;
+
+  fn is_empty(&self) -> bool {
+    self.selected_options.is_empty()
+  }
+}
+
+pub struct ChecklistCellDataParser();
+impl CellProtobufBlobParser for ChecklistCellDataParser {
+  type Object = ChecklistCellDataPB;
+
+  fn parser(bytes: &Bytes) -> FlowyResult<Self::Object> {
+    ChecklistCellDataPB::try_from(bytes.as_ref()).map_err(internal_error)
+  }
+}
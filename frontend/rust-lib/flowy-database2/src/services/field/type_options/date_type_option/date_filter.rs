@@ -1,9 +1,71 @@
+use chrono::{Datelike, Days, Duration, Local, Months, NaiveDate, TimeZone, Utc};
+
 use crate::entities::{DateFilterConditionPB, DateFilterPB};
-use chrono::NaiveDateTime;
+use crate::services::field::DateCellData;
 
 impl DateFilterPB {
+  /// Evaluates the relative conditions in the local timezone.
   pub fn is_visible<T: Into<Option<i64>>>(&self, cell_timestamp: T) -> bool {
-    match cell_timestamp.into() {
+    self.is_visible_in_timezone(cell_timestamp, Local)
+  }
+
+  /// The relative conditions, like "this week", compare the date of the cell with the current
+  /// date. Both dates are taken in the given timezone, otherwise a cell saved late in the
+  /// evening might fall on the next day. Each date uses the offset of the timezone at that
+  /// date, so the dates on both sides of a daylight saving change are compared correctly.
+  pub fn is_visible_in_timezone<T: Into<Option<i64>>, Z: TimeZone + Copy>(
+    &self,
+    cell_timestamp: T,
+    timezone: Z,
+  ) -> bool {
+    let today = Utc::now().with_timezone(&timezone).date_naive();
+    self.is_visible_on(cell_timestamp.into(), timezone, today)
+  }
+
+  /// Evaluates the conditions on a date cell that might be a date range. The range conditions
+  /// compare the whole range of the cell, the other conditions only its start.
+  pub fn is_range_visible_in_timezone<Z: TimeZone + Copy>(
+    &self,
+    cell_data: &DateCellData,
+    timezone: Z,
+  ) -> bool {
+    let (start, end) = match cell_data.timestamp {
+      None => return self.is_visible_in_timezone(None, timezone),
+      Some(start) => match cell_data.end_timestamp {
+        Some(end) if cell_data.is_range => (start, end),
+        _ => (start, start),
+      },
+    };
+
+    // The dates are taken in the given timezone, like the relative conditions do.
+    let date = |timestamp: i64| {
+      timezone
+        .timestamp_opt(timestamp, 0)
+        .single()
+        .map(|time| time.date_naive())
+    };
+    match self.condition {
+      DateFilterConditionPB::DateOverlaps => match (self.start, self.end) {
+        (Some(filter_start), Some(filter_end)) => {
+          date(start) <= date(filter_end) && date(end) >= date(filter_start)
+        },
+        _ => true,
+      },
+      DateFilterConditionPB::DateContains => match self.timestamp {
+        Some(timestamp) => date(start) <= date(timestamp) && date(timestamp) <= date(end),
+        None => true,
+      },
+      _ => self.is_visible_in_timezone(start, timezone),
+    }
+  }
+
+  fn is_visible_on<Z: TimeZone + Copy>(
+    &self,
+    cell_timestamp: Option<i64>,
+    timezone: Z,
+    today: NaiveDate,
+  ) -> bool {
+    match cell_timestamp {
       None => DateFilterConditionPB::DateIsEmpty == self.condition,
       Some(timestamp) => {
         match self.condition {
@@ -16,33 +78,40 @@ impl DateFilterPB {
           _ => {},
         }
 
-        let cell_time = NaiveDateTime::from_timestamp_opt(timestamp, 0);
-        let cell_date = cell_time.map(|time| time.date());
-        match self.timestamp {
-          None => {
-            if self.start.is_none() {
-              return true;
-            }
-
-            if self.end.is_none() {
-              return true;
-            }
-
-            let start_time = NaiveDateTime::from_timestamp_opt(*self.start.as_ref().unwrap(), 0);
-            let start_date = start_time.map(|time| time.date());
-
-            let end_time = NaiveDateTime::from_timestamp_opt(*self.end.as_ref().unwrap(), 0);
-            let end_date = end_time.map(|time| time.date());
+        // The dates of the cell and of the filter are taken in the given timezone, so the
+        // absolute conditions match the same dates as the relative ones.
+        let date = |timestamp: i64| {
+          timezone
+            .timestamp_opt(timestamp, 0)
+            .single()
+            .map(|time| time.date_naive())
+        };
+        let cell_date = date(timestamp);
+        if self.condition.is_relative() {
+          return match (cell_date, self.relative_date_range(today)) {
+            (Some(cell_date), Some((start_date, end_date))) => {
+              cell_date >= start_date && cell_date <= end_date
+            },
+            // The "in the last N days" and "in the next N days" conditions don't filter out
+            // anything until a valid N is set.
+            (_, None) => true,
+            (None, _) => false,
+          };
+        }
 
-            cell_date >= start_date && cell_date <= end_date
+        match self.timestamp {
+          None => match (self.start, self.end) {
+            (Some(start), Some(end)) => cell_date >= date(start) && cell_date <= date(end),
+            _ => true,
           },
           Some(timestamp) => {
-            let expected_timestamp = NaiveDateTime::from_timestamp_opt(timestamp, 0);
-            let expected_date = expected_timestamp.map(|time| time.date());
+            let expected_date = date(timestamp);
 
             // We assume that the cell_timestamp doesn't contain hours, just day.
             match self.condition {
-              DateFilterConditionPB::DateIs => cell_date == expected_date,
+              DateFilterConditionPB::DateIs | DateFilterConditionPB::DateContains => {
+                cell_date == expected_date
+              },
               DateFilterConditionPB::DateBefore => cell_date < expected_date,
               DateFilterConditionPB::DateAfter => cell_date > expected_date,
               DateFilterConditionPB::DateOnOrBefore => cell_date <= expected_date,
@@ -54,12 +123,66 @@ impl DateFilterPB {
       },
     }
   }
+
+  /// Returns the first and the last date, both inclusive, of the range that the relative
+  /// condition covers. The weeks start on Monday. Returns None if the N of the "in the last N
+  /// days" and "in the next N days" conditions isn't set or is out of range.
+  fn relative_date_range(&self, today: NaiveDate) -> Option<(NaiveDate, NaiveDate)> {
+    let start_of_week = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+    let start_of_month = today.with_day(1)?;
+    let month_range = |start_of_month: NaiveDate| {
+      let start_of_next_month = start_of_month.checked_add_months(Months::new(1))?;
+      Some((start_of_month, start_of_next_month.pred_opt()?))
+    };
+    match self.condition {
+      DateFilterConditionPB::DateIsToday => Some((today, today)),
+      DateFilterConditionPB::DateInLastDays => {
+        let days = self.number_of_days_in_range()?;
+        Some((today.checked_sub_days(days)?, today))
+      },
+      DateFilterConditionPB::DateInNextDays => {
+        let days = self.number_of_days_in_range()?;
+        Some((today, today.checked_add_days(days)?))
+      },
+      DateFilterConditionPB::DateIsThisWeek => {
+        Some((start_of_week, start_of_week + Duration::days(6)))
+      },
+      DateFilterConditionPB::DateIsLastWeek => {
+        let start_of_last_week = start_of_week - Duration::days(7);
+        Some((start_of_last_week, start_of_last_week + Duration::days(6)))
+      },
+      DateFilterConditionPB::DateIsNextWeek => {
+        let start_of_next_week = start_of_week + Duration::days(7);
+        Some((start_of_next_week, start_of_next_week + Duration::days(6)))
+      },
+      DateFilterConditionPB::DateIsThisMonth => month_range(start_of_month),
+      DateFilterConditionPB::DateIsLastMonth => {
+        month_range(start_of_month.checked_sub_months(Months::new(1))?)
+      },
+      DateFilterConditionPB::DateIsNextMonth => {
+        month_range(start_of_month.checked_add_months(Months::new(1))?)
+      },
+      _ => None,
+    }
+  }
+
+  fn number_of_days_in_range(&self) -> Option<Days> {
+    let days = self.number_of_days?;
+    if !self.is_number_of_days_valid() {
+      return None;
+    }
+    Some(Days::new(days as u64))
+  }
 }
 
 #[cfg(test)]
 mod tests {
   #![allow(clippy::all)]
+  use chrono::{FixedOffset, NaiveDate};
+  use chrono_tz::Tz;
+
   use crate::entities::{DateFilterConditionPB, DateFilterPB};
+  use crate::services::field::DateCellData;
 
   #[test]
   fn date_filter_is_test() {
@@ -68,6 +191,7 @@ mod tests {
       timestamp: Some(1668387885),
       end: None,
       start: None,
+      number_of_days: None,
     };
 
     for (val, visible) in vec![(1668387885, true), (1647251762, false)] {
@@ -81,6 +205,7 @@ mod tests {
       timestamp: Some(1668387885),
       start: None,
       end: None,
+      number_of_days: None,
     };
 
     for (val, visible, msg) in vec![(1668387884, false, "1"), (1647251762, true, "2")] {
@@ -95,6 +220,7 @@ mod tests {
       timestamp: Some(1668387885),
       start: None,
       end: None,
+      number_of_days: None,
     };
 
     for (val, visible) in vec![(1668387884, true), (1668387885, true)] {
@@ -108,6 +234,7 @@ mod tests {
       timestamp: Some(1668387885),
       start: None,
       end: None,
+      number_of_days: None,
     };
 
     for (val, visible) in vec![(1668387888, false), (1668531885, true), (0, false)] {
@@ -122,6 +249,7 @@ mod tests {
       start: Some(1668272685), // 11/13
       end: Some(1668618285),   // 11/17
       timestamp: None,
+      number_of_days: None,
     };
 
     for (val, visible, _msg) in vec![
@@ -133,6 +261,22 @@ mod tests {
     }
   }
 
+  #[test]
+  fn date_filter_is_in_timezone_test() {
+    // 11/16 17:00 UTC is 11/17 01:00 in UTC+8, 11/17 02:00 UTC is 11/17 10:00 in UTC+8
+    let filter = DateFilterPB {
+      condition: DateFilterConditionPB::DateIs,
+      timestamp: Some(1668650400),
+      start: None,
+      end: None,
+      number_of_days: None,
+    };
+    let utc = FixedOffset::east_opt(0).unwrap();
+    let utc_8 = FixedOffset::east_opt(8 * 3600).unwrap();
+    assert!(!filter.is_visible_in_timezone(1668618000, utc));
+    assert!(filter.is_visible_in_timezone(1668618000, utc_8));
+  }
+
   #[test]
   fn date_filter_is_empty_test() {
     let filter = DateFilterPB {
@@ -140,10 +284,255 @@ mod tests {
       start: None,
       end: None,
       timestamp: None,
+      number_of_days: None,
     };
 
     for (val, visible) in vec![(None, true), (Some(123), false)] {
       assert_eq!(filter.is_visible(val), visible);
     }
   }
+
+  #[test]
+  fn date_filter_relative_test() {
+    // Wednesday, 11/16/2022
+    let today = NaiveDate::from_ymd_opt(2022, 11, 16).unwrap();
+    let utc = FixedOffset::east_opt(0).unwrap();
+    for (condition, number_of_days, val, visible, msg) in vec![
+      (
+        DateFilterConditionPB::DateIsToday,
+        None,
+        1668600000,
+        true,
+        "11/16",
+      ),
+      (
+        DateFilterConditionPB::DateIsToday,
+        None,
+        1668531885,
+        false,
+        "11/15",
+      ),
+      (
+        DateFilterConditionPB::DateInLastDays,
+        Some(3),
+        1668359085,
+        true,
+        "11/13",
+      ),
+      (
+        DateFilterConditionPB::DateInLastDays,
+        Some(3),
+        1668272685,
+        false,
+        "11/12",
+      ),
+      (
+        DateFilterConditionPB::DateInLastDays,
+        None,
+        1647251762,
+        true,
+        "no days",
+      ),
+      (
+        DateFilterConditionPB::DateInNextDays,
+        Some(2),
+        1668704685,
+        true,
+        "11/17",
+      ),
+      // The N out of range doesn't filter out anything
+      (
+        DateFilterConditionPB::DateInLastDays,
+        Some(-3),
+        1668704685,
+        true,
+        "negative days",
+      ),
+      (
+        DateFilterConditionPB::DateInNextDays,
+        Some(i64::MAX),
+        1647251762,
+        true,
+        "too many days",
+      ),
+      (
+        DateFilterConditionPB::DateInLastDays,
+        Some(i64::MIN),
+        1647251762,
+        true,
+        "too few days",
+      ),
+      (
+        DateFilterConditionPB::DateIsThisWeek,
+        None,
+        1668965085,
+        true,
+        "Sun 11/20",
+      ),
+      (
+        DateFilterConditionPB::DateIsThisWeek,
+        None,
+        1668359085,
+        false,
+        "Sun 11/13",
+      ),
+      (
+        DateFilterConditionPB::DateIsLastWeek,
+        None,
+        1668359085,
+        true,
+        "Sun 11/13",
+      ),
+      (
+        DateFilterConditionPB::DateIsNextWeek,
+        None,
+        1669051485,
+        true,
+        "Mon 11/21",
+      ),
+      (
+        DateFilterConditionPB::DateIsThisMonth,
+        None,
+        1669766400,
+        true,
+        "11/30",
+      ),
+      (
+        DateFilterConditionPB::DateIsThisMonth,
+        None,
+        1669852800,
+        false,
+        "12/01",
+      ),
+      (
+        DateFilterConditionPB::DateIsLastMonth,
+        None,
+        1667174400,
+        true,
+        "10/31",
+      ),
+      (
+        DateFilterConditionPB::DateIsNextMonth,
+        None,
+        1669852800,
+        true,
+        "12/01",
+      ),
+    ] {
+      let filter = DateFilterPB {
+        condition,
+        start: None,
+        end: None,
+        timestamp: None,
+        number_of_days,
+      };
+      assert_eq!(
+        filter.is_visible_on(Some(val), utc, today),
+        visible,
+        "{}",
+        msg
+      );
+    }
+  }
+
+  #[test]
+  fn date_filter_relative_timezone_test() {
+    let today = NaiveDate::from_ymd_opt(2022, 11, 16).unwrap();
+    let filter = DateFilterPB {
+      condition: DateFilterConditionPB::DateIsToday,
+      start: None,
+      end: None,
+      timestamp: None,
+      number_of_days: None,
+    };
+
+    // 11/15 22:00 UTC is already 11/16 in UTC+8
+    let val = 1668549600;
+    assert!(!filter.is_visible_on(Some(val), FixedOffset::east_opt(0).unwrap(), today));
+    assert!(filter.is_visible_on(Some(val), FixedOffset::east_opt(8 * 3600).unwrap(), today));
+  }
+
+  #[test]
+  fn date_filter_range_test() {
+    let utc = FixedOffset::east_opt(0).unwrap();
+    // 11/14 - 11/16
+    let range = DateCellData {
+      timestamp: Some(1668384000),
+      end_timestamp: Some(1668556800),
+      is_range: true,
+      ..Default::default()
+    };
+    // 11/14
+    let single_date = DateCellData {
+      timestamp: Some(1668384000),
+      ..Default::default()
+    };
+
+    let overlaps = |start: i64, end: i64| DateFilterPB {
+      condition: DateFilterConditionPB::DateOverlaps,
+      start: Some(start),
+      end: Some(end),
+      timestamp: None,
+      number_of_days: None,
+    };
+    // 11/16 - 11/18
+    assert!(overlaps(1668556800, 1668729600).is_range_visible_in_timezone(&range, utc));
+    assert!(!overlaps(1668556800, 1668729600).is_range_visible_in_timezone(&single_date, utc));
+    // 11/10 - 11/13
+    assert!(!overlaps(1668038400, 1668297600).is_range_visible_in_timezone(&range, utc));
+
+    let contains = |timestamp: i64| DateFilterPB {
+      condition: DateFilterConditionPB::DateContains,
+      start: None,
+      end: None,
+      timestamp: Some(timestamp),
+      number_of_days: None,
+    };
+    // 11/15
+    assert!(contains(1668470400).is_range_visible_in_timezone(&range, utc));
+    assert!(!contains(1668470400).is_range_visible_in_timezone(&single_date, utc));
+    // 11/17
+    assert!(!contains(1668643200).is_range_visible_in_timezone(&range, utc));
+
+    // The other conditions only compare the start of the range.
+    let is = DateFilterPB {
+      condition: DateFilterConditionPB::DateIs,
+      timestamp: Some(1668470400),
+      ..Default::default()
+    };
+    assert!(!is.is_range_visible_in_timezone(&range, utc));
+
+    // 11/14 - 11/16 22:00 UTC, which ends on 11/17 in UTC+8
+    let range = DateCellData {
+      timestamp: Some(1668384000),
+      end_timestamp: Some(1668636000),
+      is_range: true,
+      ..Default::default()
+    };
+    let utc_8 = FixedOffset::east_opt(8 * 3600).unwrap();
+    assert!(!contains(1668643200).is_range_visible_in_timezone(&range, utc));
+    assert!(contains(1668643200).is_range_visible_in_timezone(&range, utc_8));
+    // 11/17 - 11/18
+    assert!(!overlaps(1668643200, 1668729600).is_range_visible_in_timezone(&range, utc));
+    assert!(overlaps(1668643200, 1668729600).is_range_visible_in_timezone(&range, utc_8));
+  }
+
+  #[test]
+  fn date_filter_contains_in_timezone_test() {
+    // 07/01 04:30 UTC is 07/01 00:30 in New York, which observes the daylight saving time
+    let single_date = DateCellData {
+      timestamp: Some(1656649800),
+      ..Default::default()
+    };
+    // 07/01 12:00 UTC
+    let contains = DateFilterPB {
+      condition: DateFilterConditionPB::DateContains,
+      timestamp: Some(1656676800),
+      ..Default::default()
+    };
+    assert!(contains.is_range_visible_in_timezone(&single_date, Tz::America__New_York));
+    // The standard time offset of New York puts the cell on 06/30
+    let standard_offset = FixedOffset::west_opt(5 * 3600).unwrap();
+    assert!(!contains.is_range_visible_in_timezone(&single_date, standard_offset));
+  }
 }
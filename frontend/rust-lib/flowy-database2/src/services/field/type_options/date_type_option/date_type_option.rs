@@ -27,6 +27,9 @@ pub struct DateTypeOption {
   pub date_format: DateFormat,
   pub time_format: TimeFormat,
   pub timezone_id: String,
+  /// Whether the new cells of the field are date ranges, with a start and an end date.
+  #[serde(default)]
+  pub is_range: bool,
 }
 
 impl TypeOption for DateTypeOption {
@@ -47,10 +50,12 @@ impl From<TypeOptionData> for DateTypeOption {
       .map(TimeFormat::from)
       .unwrap_or_default();
     let timezone_id = data.get_str_value("timezone_id").unwrap_or_default();
+    let is_range = data.get_bool_value("is_range").unwrap_or_default();
     Self {
       date_format,
       time_format,
       timezone_id,
+      is_range,
     }
   }
 }
@@ -61,6 +66,7 @@ impl From<DateTypeOption> for TypeOptionData {
       .insert_i64_value("date_format", data.date_format.value())
       .insert_i64_value("time_format", data.time_format.value())
       .insert_str_value("timezone_id", data.timezone_id)
+      .insert_bool_value("is_range", data.is_range)
       .build()
   }
 }
@@ -186,7 +192,7 @@ impl DateTypeOption {
   }
 
   /// returns offset of Tz timezone if provided or of the local timezone otherwise
-  fn get_timezone_offset(&self, date_time: NaiveDateTime) -> FixedOffset {
+  pub fn get_timezone_offset(&self, date_time: NaiveDateTime) -> FixedOffset {
     let current_timezone_offset = Local::now().offset().fix();
     if self.timezone_id.is_empty() {
       current_timezone_offset
@@ -276,7 +282,7 @@ impl CellDataChangeset for DateTypeOption {
             cell_data.reminder_id,
           )
         },
-        None => (None, None, false, false, String::new()),
+        None => (None, None, false, self.is_range, String::new()),
       };
 
     if changeset.clear_flag == Some(true) {
@@ -352,7 +358,12 @@ impl TypeOptionCellDataFilter for DateTypeOption {
       return true;
     }
 
-    filter.is_visible(cell_data.timestamp)
+    // All the conditions are evaluated in the timezone of the field, the local one if it's not
+    // set.
+    match Tz::from_str(&self.timezone_id) {
+      Ok(timezone) => filter.is_range_visible_in_timezone(cell_data, timezone),
+      Err(_) => filter.is_range_visible_in_timezone(cell_data, Local),
+    }
   }
 }
 
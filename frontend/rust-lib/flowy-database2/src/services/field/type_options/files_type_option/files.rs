@@ -0,0 +1,192 @@
+use std::cmp::Ordering;
+
+use bytes::Bytes;
+use collab_database::fields::{Field, TypeOptionData, TypeOptionDataBuilder};
+use collab_database::rows::Cell;
+
+use flowy_error::{internal_error, FlowyResult};
+
+use crate::entities::{FieldType, FilePB, FilesCellDataPB, FilesFilterPB};
+use crate::services::cell::{CellDataChangeset, CellDataDecoder, CellProtobufBlobParser};
+use crate::services::field::{
+  default_order, FilesCellChangeset, FilesCellData, TypeOption, TypeOptionCellDataCompare,
+  TypeOptionCellDataFilter, TypeOptionCellDataSerde, TypeOptionTransform,
+};
+use crate::services::sort::SortCondition;
+
+/// The [FilesTypeOption] attaches files to a row. The files are uploaded to the
+/// `ObjectStorageService` by the [DatabaseEditor](crate::services::database::DatabaseEditor)
+/// before they are inserted into the cell.
+#[derive(Debug, Clone, Default)]
+pub struct FilesTypeOption;
+
+impl TypeOption for FilesTypeOption {
+  type CellData = FilesCellData;
+  type CellChangeset = FilesCellChangeset;
+  type CellProtobufType = FilesCellDataPB;
+  type CellFilter = FilesFilterPB;
+}
+
+impl From<TypeOptionData> for FilesTypeOption {
+  fn from(_data: TypeOptionData) -> Self {
+    Self
+  }
+}
+
+impl From<FilesTypeOption> for TypeOptionData {
+  fn from(_data: FilesTypeOption) -> Self {
+    TypeOptionDataBuilder::new().build()
+  }
+}
+
+impl TypeOptionCellDataSerde for FilesTypeOption {
+  fn protobuf_encode(
+    &self,
+    cell_data: <Self as TypeOption>::CellData,
+  ) -> <Self as TypeOption>::CellProtobufType {
+    FilesCellDataPB {
+      files: cell_data.files.into_iter().map(FilePB::from).collect(),
+    }
+  }
+
+  fn parse_cell(&self, cell: &Cell) -> FlowyResult<<Self as TypeOption>::CellData> {
+    Ok(FilesCellData::from(cell))
+  }
+}
+
+impl CellDataChangeset for FilesTypeOption {
+  fn apply_changeset(
+    &self,
+    changeset: <Self as TypeOption>::CellChangeset,
+    cell: Option<Cell>,
+  ) -> FlowyResult<(Cell, <Self as TypeOption>::CellData)> {
+    let mut cell_data = cell.as_ref().map(FilesCellData::from).unwrap_or_default();
+    cell_data
+      .files
+      .retain(|file| !changeset.removed_file_ids.contains(&file.id));
+    cell_data.files.extend(changeset.inserted_files);
+    Ok((Cell::from(cell_data.clone()), cell_data))
+  }
+}
+
+impl CellDataDecoder for FilesTypeOption {
+  fn decode_cell(
+    &self,
+    cell: &Cell,
+    decoded_field_type: &FieldType,
+    _field: &Field,
+  ) -> FlowyResult<<Self as TypeOption>::CellData> {
+    if !decoded_field_type.is_files() {
+      return Ok(Default::default());
+    }
+
+    self.parse_cell(cell)
+  }
+
+  fn stringify_cell_data(&self, cell_data: <Self as TypeOption>::CellData) -> String {
+    cell_data
+      .files
+      .into_iter()
+      .map(|file| file.name)
+      .collect::<Vec<_>>()
+      .join(", ")
+  }
+
+  fn stringify_cell(&self, cell: &Cell) -> String {
+    let cell_data = FilesCellData::from(cell);
+    self.stringify_cell_data(cell_data)
+  }
+
+  fn numeric_cell(&self, _cell: &Cell) -> Option<f64> {
+    None
+  }
+}
+
+impl TypeOptionCellDataCompare for FilesTypeOption {
+  fn apply_cmp(
+    &self,
+    cell_data: &<Self as TypeOption>::CellData,
+    other_cell_data: &<Self as TypeOption>::CellData,
+    sort_condition: SortCondition,
+  ) -> Ordering {
+    match cell_data.files.len().cmp(&other_cell_data.files.len()) {
+      Ordering::Equal => default_order(),
+      order => sort_condition.evaluate_order(order),
+    }
+  }
+}
+
+impl TypeOptionCellDataFilter for FilesTypeOption {
+  fn apply_filter(
+    &self,
+    filter: &<Self as TypeOption>::CellFilter,
+    field_type: &FieldType,
+    cell_data: &<Self as TypeOption>::CellData,
+  ) -> bool {
+    if !field_type.is_files() {
+      return true;
+    }
+
+    filter.is_visible(cell_data)
+  }
+}
+
+impl TypeOptionTransform for FilesTypeOption {}
+
+pub struct FilesCellDataParser();
+impl CellProtobufBlobParser for FilesCellDataParser {
+  type Object = FilesCellDataPB;
+
+  fn parser(bytes: &Bytes) -> FlowyResult<Self::Object> {
+    FilesCellDataPB::try_from(bytes.as_ref()).map_err(internal_error)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::services::cell::{CellDataChangeset, CellDataDecoder};
+  use crate::services::field::{FileEntry, FilesCellChangeset, FilesTypeOption};
+
+  fn file(name: &str) -> FileEntry {
+    FileEntry::new(
+      name.to_string(),
+      format!("https://appflowy.io/files/{}", name),
+      "application/pdf".to_string(),
+      1024,
+    )
+  }
+
+  #[test]
+  fn files_changeset_test() {
+    let type_option = FilesTypeOption;
+    let invoice = file("invoice.pdf");
+    let screenshot = file("screenshot.png");
+    let changeset = FilesCellChangeset {
+      inserted_files: vec![invoice.clone(), screenshot.clone()],
+      ..Default::default()
+    };
+    let (cell, cell_data) = type_option.apply_changeset(changeset, None).unwrap();
+    assert_eq!(cell_data.files.len(), 2);
+
+    let changeset = FilesCellChangeset {
+      removed_file_ids: vec![invoice.id.clone()],
+      ..Default::default()
+    };
+    let (_, cell_data) = type_option.apply_changeset(changeset, Some(cell)).unwrap();
+    assert_eq!(cell_data.files, vec![screenshot]);
+  }
+
+  #[test]
+  fn files_stringify_test() {
+    let type_option = FilesTypeOption;
+    let changeset = FilesCellChangeset {
+      inserted_files: vec![file("invoice.pdf"), file("screenshot.png")],
+      ..Default::default()
+    };
+    let (cell, _) = type_option.apply_changeset(changeset, None).unwrap();
+    assert_eq!(
+      type_option.stringify_cell(&cell),
+      "invoice.pdf, screenshot.png"
+    );
+  }
+}
@@ -0,0 +1,108 @@
+use collab::core::any_map::AnyMapExtension;
+use collab_database::database::gen_option_id;
+use collab_database::rows::{new_cell_builder, Cell};
+use serde::{Deserialize, Serialize};
+
+use flowy_error::{internal_error, FlowyResult};
+use lib_infra::util::timestamp;
+
+use crate::entities::FieldType;
+use crate::services::cell::{FromCellChangeset, FromCellString, ToCellChangeset};
+use crate::services::field::{TypeOptionCellData, CELL_DATA};
+
+/// A file attached to a files cell. The content of the file is stored in the
+/// `ObjectStorageService`, the cell only keeps the url of the object.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileEntry {
+  pub id: String,
+  pub name: String,
+  pub url: String,
+  pub mime: String,
+  /// The size of the file in bytes.
+  pub size: i64,
+  pub uploaded_at: i64,
+}
+
+impl FileEntry {
+  pub fn new(name: String, url: String, mime: String, size: i64) -> Self {
+    Self {
+      id: gen_option_id(),
+      name,
+      url,
+      mime,
+      size,
+      uploaded_at: timestamp(),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FilesCellData {
+  pub files: Vec<FileEntry>,
+}
+
+impl FilesCellData {
+  pub fn urls(&self) -> impl Iterator<Item = &str> {
+    self.files.iter().map(|file| file.url.as_str())
+  }
+}
+
+impl TypeOptionCellData for FilesCellData {
+  fn is_cell_empty(&self) -> bool {
+    self.files.is_empty()
+  }
+}
+
+impl From<&Cell> for FilesCellData {
+  fn from(cell: &Cell) -> Self {
+    cell
+      .get_str_value(CELL_DATA)
+      .map(|data| serde_json::from_str::<FilesCellData>(&data).unwrap_or_default())
+      .unwrap_or_default()
+  }
+}
+
+impl From<FilesCellData> for Cell {
+  fn from(cell_data: FilesCellData) -> Self {
+    new_cell_builder(FieldType::Files)
+      .insert_str_value(CELL_DATA, cell_data.to_string())
+      .build()
+  }
+}
+
+impl FromCellString for FilesCellData {
+  fn from_cell_str(s: &str) -> FlowyResult<Self>
+  where
+    Self: Sized,
+  {
+    serde_json::from_str::<FilesCellData>(s).map_err(internal_error)
+  }
+}
+
+impl ToString for FilesCellData {
+  fn to_string(&self) -> String {
+    serde_json::to_string(self).unwrap_or_default()
+  }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FilesCellChangeset {
+  /// The files that were uploaded to the storage service.
+  pub inserted_files: Vec<FileEntry>,
+  pub removed_file_ids: Vec<String>,
+}
+
+impl FromCellChangeset for FilesCellChangeset {
+  fn from_changeset(changeset: String) -> FlowyResult<Self>
+  where
+    Self: Sized,
+  {
+    serde_json::from_str::<FilesCellChangeset>(&changeset).map_err(internal_error)
+  }
+}
+
+impl ToCellChangeset for FilesCellChangeset {
+  fn to_cell_changeset_str(&self) -> String {
+    serde_json::to_string(self).unwrap_or_default()
+  }
+}
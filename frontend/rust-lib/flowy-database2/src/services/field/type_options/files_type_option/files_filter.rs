@@ -0,0 +1,79 @@
+use crate::entities::{FilesFilterConditionPB, FilesFilterPB};
+use crate::services::field::FilesCellData;
+
+impl FilesFilterPB {
+  pub fn is_visible(&self, cell_data: &FilesCellData) -> bool {
+    match self.condition {
+      FilesFilterConditionPB::FilesIsEmpty => cell_data.files.is_empty(),
+      FilesFilterConditionPB::FilesIsNotEmpty => !cell_data.files.is_empty(),
+      FilesFilterConditionPB::FileNameContains => {
+        let content = self.content.to_lowercase();
+        cell_data
+          .files
+          .iter()
+          .any(|file| file.name.to_lowercase().contains(&content))
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::entities::{FilesFilterConditionPB, FilesFilterPB};
+  use crate::services::field::{FileEntry, FilesCellData};
+
+  fn cell_data(names: &[&str]) -> FilesCellData {
+    FilesCellData {
+      files: names
+        .iter()
+        .map(|name| {
+          FileEntry::new(
+            name.to_string(),
+            format!("https://appflowy.io/files/{}", name),
+            "image/png".to_string(),
+            0,
+          )
+        })
+        .collect(),
+    }
+  }
+
+  #[test]
+  fn files_filter_is_empty_test() {
+    let filter = FilesFilterPB {
+      condition: FilesFilterConditionPB::FilesIsEmpty,
+      content: "".to_string(),
+    };
+    assert!(filter.is_visible(&cell_data(&[])));
+    assert!(!filter.is_visible(&cell_data(&["a.png"])));
+
+    let filter = FilesFilterPB {
+      condition: FilesFilterConditionPB::FilesIsNotEmpty,
+      content: "".to_string(),
+    };
+    assert!(!filter.is_visible(&cell_data(&[])));
+    assert!(filter.is_visible(&cell_data(&["a.png"])));
+  }
+
+  #[test]
+  fn files_filter_name_contains_test() {
+    let filter = FilesFilterPB {
+      condition: FilesFilterConditionPB::FileNameContains,
+      content: "Invoice".to_string(),
+    };
+
+    for (names, visible) in [
+      (vec!["invoice-2023.pdf"], true),
+      (vec!["screenshot.png", "INVOICE.pdf"], true),
+      (vec!["screenshot.png"], false),
+      (vec![], false),
+    ] {
+      assert_eq!(
+        filter.is_visible(&cell_data(&names)),
+        visible,
+        "{:?}",
+        names
+      );
+    }
+  }
+}
@@ -0,0 +1,7 @@
+#![allow(clippy::module_inception)]
+mod files;
+mod files_entities;
+mod files_filter;
+
+pub use files::*;
+pub use files_entities::*;
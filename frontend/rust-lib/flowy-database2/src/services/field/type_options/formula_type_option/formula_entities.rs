@@ -0,0 +1,211 @@
+use std::cmp::Ordering;
+
+use chrono::{Local, TimeZone, Timelike};
+use collab::core::any_map::AnyMapExtension;
+use collab_database::rows::{new_cell_builder, Cell};
+
+use crate::entities::{FieldType, FormulaResultTypePB};
+use crate::services::field::{TypeOptionCellData, CELL_DATA, CHECK, UNCHECK};
+
+const RESULT_TYPE: &str = "result_type";
+
+/// The typed result of evaluating a formula.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum FormulaValue {
+  #[default]
+  Empty,
+  Number(f64),
+  Text(String),
+  Boolean(bool),
+  /// Timestamp in seconds.
+  Date(i64),
+}
+
+impl FormulaValue {
+  pub fn result_type(&self) -> FormulaResultTypePB {
+    match self {
+      FormulaValue::Empty | FormulaValue::Text(_) => FormulaResultTypePB::Text,
+      FormulaValue::Number(_) => FormulaResultTypePB::Number,
+      FormulaValue::Boolean(_) => FormulaResultTypePB::Boolean,
+      FormulaValue::Date(_) => FormulaResultTypePB::Date,
+    }
+  }
+
+  pub fn is_empty(&self) -> bool {
+    match self {
+      FormulaValue::Empty => true,
+      FormulaValue::Text(s) => s.is_empty(),
+      _ => false,
+    }
+  }
+
+  pub fn as_number(&self) -> Option<f64> {
+    match self {
+      FormulaValue::Empty => Some(0.0),
+      FormulaValue::Number(num) => Some(*num),
+      FormulaValue::Boolean(b) => Some(if *b { 1.0 } else { 0.0 }),
+      FormulaValue::Text(s) => s.trim().parse::<f64>().ok(),
+      FormulaValue::Date(_) => None,
+    }
+  }
+
+  pub fn is_truthy(&self) -> bool {
+    match self {
+      FormulaValue::Empty => false,
+      FormulaValue::Number(num) => *num != 0.0,
+      FormulaValue::Text(s) => !s.is_empty(),
+      FormulaValue::Boolean(b) => *b,
+      FormulaValue::Date(_) => true,
+    }
+  }
+
+  /// Compares two values of the same type. Values of different types are ordered by their type,
+  /// and the empty values are always placed at the end.
+  pub fn compare(&self, other: &FormulaValue) -> Ordering {
+    match (self, other) {
+      (FormulaValue::Number(left), FormulaValue::Number(right)) => {
+        left.partial_cmp(right).unwrap_or(Ordering::Equal)
+      },
+      (FormulaValue::Date(left), FormulaValue::Date(right)) => left.cmp(right),
+      (FormulaValue::Boolean(left), FormulaValue::Boolean(right)) => left.cmp(right),
+      (FormulaValue::Text(left), FormulaValue::Text(right)) => left.cmp(right),
+      _ => self.rank().cmp(&other.rank()),
+    }
+  }
+
+  fn rank(&self) -> u8 {
+    match self {
+      FormulaValue::Number(_) => 0,
+      FormulaValue::Date(_) => 1,
+      FormulaValue::Boolean(_) => 2,
+      FormulaValue::Text(_) => 3,
+      FormulaValue::Empty => 4,
+    }
+  }
+
+  /// The raw string that is stored in the cell.
+  fn to_cell_str(&self) -> String {
+    match self {
+      FormulaValue::Date(timestamp) => timestamp.to_string(),
+      _ => self.to_string(),
+    }
+  }
+
+  fn from_cell_str(s: String, result_type: FormulaResultTypePB) -> Self {
+    if s.is_empty() {
+      return FormulaValue::Empty;
+    }
+    match result_type {
+      FormulaResultTypePB::Text => FormulaValue::Text(s),
+      FormulaResultTypePB::Number => s
+        .parse::<f64>()
+        .map(FormulaValue::Number)
+        .unwrap_or_default(),
+      FormulaResultTypePB::Boolean => FormulaValue::Boolean(s == CHECK),
+      FormulaResultTypePB::Date => s.parse::<i64>().map(FormulaValue::Date).unwrap_or_default(),
+    }
+  }
+}
+
+impl ToString for FormulaValue {
+  fn to_string(&self) -> String {
+    match self {
+      FormulaValue::Empty => "".to_owned(),
+      FormulaValue::Number(num) => format_number(*num),
+      FormulaValue::Text(s) => s.clone(),
+      FormulaValue::Boolean(b) => if *b { CHECK } else { UNCHECK }.to_owned(),
+      FormulaValue::Date(timestamp) => match Local.timestamp_opt(*timestamp, 0).single() {
+        None => "".to_owned(),
+        Some(date_time) => {
+          if date_time.hour() == 0 && date_time.minute() == 0 {
+            date_time.format("%Y/%m/%d").to_string()
+          } else {
+            date_time.format("%Y/%m/%d %H:%M").to_string()
+          }
+        },
+      },
+    }
+  }
+}
+
+/// Formats the number with at most 10 decimal places, and without the trailing zeros.
+fn format_number(num: f64) -> String {
+  if num.fract() == 0.0 && num.abs() < 1e15 {
+    return format!("{}", num as i64);
+  }
+  let s = format!("{:.10}", num);
+  s.trim_end_matches('0').trim_end_matches('.').to_owned()
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FormulaCellData {
+  pub value: FormulaValue,
+}
+
+impl FormulaCellData {
+  pub fn new(value: FormulaValue) -> Self {
+    Self { value }
+  }
+}
+
+impl TypeOptionCellData for FormulaCellData {
+  fn is_cell_empty(&self) -> bool {
+    self.value.is_empty()
+  }
+}
+
+impl From<&Cell> for FormulaCellData {
+  fn from(cell: &Cell) -> Self {
+    let result_type = cell
+      .get_i64_value(RESULT_TYPE)
+      .map(FormulaResultTypePB::from)
+      .unwrap_or_default();
+    let s = cell.get_str_value(CELL_DATA).unwrap_or_default();
+    Self {
+      value: FormulaValue::from_cell_str(s, result_type),
+    }
+  }
+}
+
+impl From<FormulaCellData> for Cell {
+  fn from(data: FormulaCellData) -> Self {
+    new_cell_builder(FieldType::Formula)
+      .insert_str_value(CELL_DATA, data.value.to_cell_str())
+      .insert_i64_value(RESULT_TYPE, data.value.result_type() as i64)
+      .build()
+  }
+}
+
+impl ToString for FormulaCellData {
+  fn to_string(&self) -> String {
+    self.value.to_string()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use collab_database::rows::Cell;
+
+  use crate::services::field::{FormulaCellData, FormulaValue};
+
+  #[test]
+  fn formula_cell_round_trip_test() {
+    for value in [
+      FormulaValue::Number(1.5),
+      FormulaValue::Text("abc".to_owned()),
+      FormulaValue::Boolean(true),
+      FormulaValue::Date(1_700_000_000),
+      FormulaValue::Empty,
+    ] {
+      let cell = Cell::from(FormulaCellData::new(value.clone()));
+      assert_eq!(FormulaCellData::from(&cell).value, value);
+    }
+  }
+
+  #[test]
+  fn formula_number_format_test() {
+    assert_eq!(FormulaValue::Number(3.0).to_string(), "3");
+    assert_eq!(FormulaValue::Number(0.1 + 0.2).to_string(), "0.3");
+    assert_eq!(FormulaValue::Number(-2.25).to_string(), "-2.25");
+  }
+}
@@ -0,0 +1,516 @@
+use std::fmt::Write;
+
+use chrono::{Datelike, Local, Months, NaiveDateTime, TimeZone};
+
+use flowy_error::{ErrorCode, FlowyError, FlowyResult};
+
+use crate::services::field::formula_type_option::formula_parser::{BinaryOp, FormulaExpr, UnaryOp};
+use crate::services::field::FormulaValue;
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Provides the values that a formula refers to.
+pub trait FormulaContext {
+  /// Returns the value of the field with the given name or id of the current row.
+  fn field_value(&self, field: &str) -> FlowyResult<FormulaValue>;
+
+  /// Returns the current timestamp in seconds.
+  fn now(&self) -> i64 {
+    chrono::Utc::now().timestamp()
+  }
+}
+
+pub fn evaluate_formula(expr: &FormulaExpr, ctx: &dyn FormulaContext) -> FlowyResult<FormulaValue> {
+  match expr {
+    FormulaExpr::Number(num) => Ok(FormulaValue::Number(*num)),
+    FormulaExpr::Text(s) => Ok(FormulaValue::Text(s.clone())),
+    FormulaExpr::Boolean(b) => Ok(FormulaValue::Boolean(*b)),
+    FormulaExpr::Field(field) => ctx.field_value(field),
+    FormulaExpr::Unary(op, expr) => {
+      let value = evaluate_formula(expr, ctx)?;
+      match op {
+        UnaryOp::Neg => Ok(FormulaValue::Number(-expect_number(&value)?)),
+        UnaryOp::Not => Ok(FormulaValue::Boolean(!value.is_truthy())),
+      }
+    },
+    FormulaExpr::Binary(op, left, right) => match op {
+      // Short circuit
+      BinaryOp::And => {
+        let left = evaluate_formula(left, ctx)?;
+        if !left.is_truthy() {
+          return Ok(FormulaValue::Boolean(false));
+        }
+        let right = evaluate_formula(right, ctx)?;
+        Ok(FormulaValue::Boolean(right.is_truthy()))
+      },
+      BinaryOp::Or => {
+        let left = evaluate_formula(left, ctx)?;
+        if left.is_truthy() {
+          return Ok(FormulaValue::Boolean(true));
+        }
+        let right = evaluate_formula(right, ctx)?;
+        Ok(FormulaValue::Boolean(right.is_truthy()))
+      },
+      _ => {
+        let left = evaluate_formula(left, ctx)?;
+        let right = evaluate_formula(right, ctx)?;
+        evaluate_binary(*op, left, right)
+      },
+    },
+    FormulaExpr::Call(name, args) => call_function(name, args, ctx),
+  }
+}
+
+fn error<T: ToString>(msg: T) -> FlowyError {
+  FlowyError::new(ErrorCode::InvalidFormula, msg)
+}
+
+fn expect_number(value: &FormulaValue) -> FlowyResult<f64> {
+  value
+    .as_number()
+    .ok_or_else(|| error(format!("Expected a number, found {:?}", value)))
+}
+
+fn expect_date(value: &FormulaValue) -> FlowyResult<Option<i64>> {
+  match value {
+    FormulaValue::Date(timestamp) => Ok(Some(*timestamp)),
+    FormulaValue::Empty => Ok(None),
+    _ => Err(error(format!("Expected a date, found {:?}", value))),
+  }
+}
+
+/// Returns the timestamp shifted by the given number of seconds, or an error if the result is out
+/// of the range of the timestamps.
+fn add_seconds(timestamp: i64, seconds: f64) -> FlowyResult<i64> {
+  if !seconds.is_finite() || seconds.abs() >= i64::MAX as f64 {
+    return Err(error("Date out of range"));
+  }
+  timestamp
+    .checked_add(seconds as i64)
+    .ok_or_else(|| error("Date out of range"))
+}
+
+fn seconds_between(left: i64, right: i64) -> FlowyResult<f64> {
+  left
+    .checked_sub(right)
+    .map(|seconds| seconds as f64)
+    .ok_or_else(|| error("Date out of range"))
+}
+
+/// Arithmetic on dates uses days as the unit: `date + 1` is the next day, and `date - date`
+/// returns the number of days in between. The comparisons with an empty value are false, like
+/// the comparisons in the filters.
+fn evaluate_binary(
+  op: BinaryOp,
+  left: FormulaValue,
+  right: FormulaValue,
+) -> FlowyResult<FormulaValue> {
+  use FormulaValue::*;
+  let value = match op {
+    BinaryOp::Add => match (&left, &right) {
+      (Text(_), _) | (_, Text(_)) => Text(format!("{}{}", left.to_string(), right.to_string())),
+      (Date(timestamp), other) | (other, Date(timestamp)) => Date(add_seconds(
+        *timestamp,
+        expect_number(other)? * SECONDS_PER_DAY as f64,
+      )?),
+      _ => Number(expect_number(&left)? + expect_number(&right)?),
+    },
+    BinaryOp::Sub => match (&left, &right) {
+      (Date(left), Date(right)) => Number(seconds_between(*left, *right)? / SECONDS_PER_DAY as f64),
+      (Date(timestamp), other) => Date(add_seconds(
+        *timestamp,
+        -expect_number(other)? * SECONDS_PER_DAY as f64,
+      )?),
+      _ => Number(expect_number(&left)? - expect_number(&right)?),
+    },
+    BinaryOp::Mul => Number(expect_number(&left)? * expect_number(&right)?),
+    BinaryOp::Div => {
+      let divisor = expect_number(&right)?;
+      if divisor == 0.0 {
+        return Err(error("Division by zero"));
+      }
+      Number(expect_number(&left)? / divisor)
+    },
+    BinaryOp::Rem => {
+      let divisor = expect_number(&right)?;
+      if divisor == 0.0 {
+        return Err(error("Division by zero"));
+      }
+      Number(expect_number(&left)? % divisor)
+    },
+    BinaryOp::Eq => Boolean(is_equal(&left, &right)),
+    BinaryOp::NotEq => Boolean(!is_equal(&left, &right)),
+    BinaryOp::Lt | BinaryOp::LtEq | BinaryOp::Gt | BinaryOp::GtEq
+      if left.is_empty() || right.is_empty() =>
+    {
+      Boolean(false)
+    },
+    BinaryOp::Lt => Boolean(left.compare(&right).is_lt()),
+    BinaryOp::LtEq => Boolean(left.compare(&right).is_le()),
+    BinaryOp::Gt => Boolean(left.compare(&right).is_gt()),
+    BinaryOp::GtEq => Boolean(left.compare(&right).is_ge()),
+    BinaryOp::And => Boolean(left.is_truthy() && right.is_truthy()),
+    BinaryOp::Or => Boolean(left.is_truthy() || right.is_truthy()),
+  };
+  Ok(value)
+}
+
+fn is_equal(left: &FormulaValue, right: &FormulaValue) -> bool {
+  match (left, right) {
+    (FormulaValue::Number(_), _) | (_, FormulaValue::Number(_)) => {
+      match (left.as_number(), right.as_number()) {
+        (Some(left), Some(right)) => left == right,
+        _ => false,
+      }
+    },
+    _ => (left.is_empty() && right.is_empty()) || left.to_string() == right.to_string(),
+  }
+}
+
+fn check_args(name: &str, args: &[FormulaExpr], min: usize, max: usize) -> FlowyResult<()> {
+  if args.len() < min || args.len() > max {
+    return Err(error(format!(
+      "{} expects {} to {} arguments, found {}",
+      name,
+      min,
+      max,
+      args.len()
+    )));
+  }
+  Ok(())
+}
+
+fn call_function(
+  name: &str,
+  args: &[FormulaExpr],
+  ctx: &dyn FormulaContext,
+) -> FlowyResult<FormulaValue> {
+  // The branches of the if function are evaluated lazily.
+  if name.eq_ignore_ascii_case("if") {
+    check_args(name, args, 2, 3)?;
+    let condition = evaluate_formula(&args[0], ctx)?;
+    return if condition.is_truthy() {
+      evaluate_formula(&args[1], ctx)
+    } else if let Some(otherwise) = args.get(2) {
+      evaluate_formula(otherwise, ctx)
+    } else {
+      Ok(FormulaValue::Empty)
+    };
+  }
+
+  let values = args
+    .iter()
+    .map(|arg| evaluate_formula(arg, ctx))
+    .collect::<FlowyResult<Vec<_>>>()?;
+
+  let value = match name.to_lowercase().as_str() {
+    "prop" => {
+      check_args(name, args, 1, 1)?;
+      ctx.field_value(&values[0].to_string())?
+    },
+    "now" => {
+      check_args(name, args, 0, 0)?;
+      FormulaValue::Date(ctx.now())
+    },
+    "today" => {
+      check_args(name, args, 0, 0)?;
+      let now = ctx.now();
+      FormulaValue::Date(start_of_day(now).unwrap_or(now))
+    },
+    "datebetween" => {
+      check_args(name, args, 3, 3)?;
+      match (expect_date(&values[0])?, expect_date(&values[1])?) {
+        (Some(left), Some(right)) => {
+          FormulaValue::Number(date_between(left, right, &values[2].to_string())?)
+        },
+        _ => FormulaValue::Empty,
+      }
+    },
+    "dateadd" | "datesubtract" => {
+      check_args(name, args, 3, 3)?;
+      let mut amount = expect_number(&values[1])?;
+      if name.eq_ignore_ascii_case("datesubtract") {
+        amount = -amount;
+      }
+      match expect_date(&values[0])? {
+        None => FormulaValue::Empty,
+        Some(timestamp) => FormulaValue::Date(date_add(timestamp, amount, &values[2].to_string())?),
+      }
+    },
+    "formatdate" => {
+      check_args(name, args, 2, 2)?;
+      match expect_date(&values[0])? {
+        None => FormulaValue::Empty,
+        Some(timestamp) => {
+          let date_time = local_date_time(timestamp)?;
+          let format = values[1].to_string();
+          // The format comes from the user, an invalid one fails to write instead of panicking
+          let mut text = String::new();
+          write!(text, "{}", date_time.format(&format))
+            .map_err(|_| error(format!("Invalid date format: {}", format)))?;
+          FormulaValue::Text(text)
+        },
+      }
+    },
+    "concat" => FormulaValue::Text(values.iter().map(|value| value.to_string()).collect()),
+    "length" => {
+      check_args(name, args, 1, 1)?;
+      FormulaValue::Number(values[0].to_string().chars().count() as f64)
+    },
+    "lower" => {
+      check_args(name, args, 1, 1)?;
+      FormulaValue::Text(values[0].to_string().to_lowercase())
+    },
+    "upper" => {
+      check_args(name, args, 1, 1)?;
+      FormulaValue::Text(values[0].to_string().to_uppercase())
+    },
+    "contains" => {
+      check_args(name, args, 2, 2)?;
+      FormulaValue::Boolean(values[0].to_string().contains(&values[1].to_string()))
+    },
+    "empty" => {
+      check_args(name, args, 1, 1)?;
+      FormulaValue::Boolean(values[0].is_empty())
+    },
+    "tonumber" => {
+      check_args(name, args, 1, 1)?;
+      match values[0].as_number() {
+        None => FormulaValue::Empty,
+        Some(num) => FormulaValue::Number(num),
+      }
+    },
+    "format" => {
+      check_args(name, args, 1, 1)?;
+      FormulaValue::Text(values[0].to_string())
+    },
+    "abs" => {
+      check_args(name, args, 1, 1)?;
+      FormulaValue::Number(expect_number(&values[0])?.abs())
+    },
+    "floor" => {
+      check_args(name, args, 1, 1)?;
+      FormulaValue::Number(expect_number(&values[0])?.floor())
+    },
+    "ceil" => {
+      check_args(name, args, 1, 1)?;
+      FormulaValue::Number(expect_number(&values[0])?.ceil())
+    },
+    "round" => {
+      check_args(name, args, 1, 2)?;
+      let num = expect_number(&values[0])?;
+      let digits = match values.get(1) {
+        None => 0,
+        Some(value) => expect_number(value)? as i32,
+      };
+      let factor = 10_f64.powi(digits);
+      FormulaValue::Number((num * factor).round() / factor)
+    },
+    "min" | "max" | "sum" => {
+      let numbers = values
+        .iter()
+        .filter(|value| !value.is_empty())
+        .map(expect_number)
+        .collect::<FlowyResult<Vec<f64>>>()?;
+      if numbers.is_empty() {
+        FormulaValue::Empty
+      } else {
+        let num = match name.to_lowercase().as_str() {
+          "min" => numbers.iter().cloned().fold(f64::INFINITY, f64::min),
+          "max" => numbers.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+          _ => numbers.iter().sum(),
+        };
+        FormulaValue::Number(num)
+      }
+    },
+    _ => return Err(error(format!("Unknown function: {}", name))),
+  };
+  Ok(value)
+}
+
+fn local_date_time(timestamp: i64) -> FlowyResult<NaiveDateTime> {
+  Local
+    .timestamp_opt(timestamp, 0)
+    .single()
+    .map(|date_time| date_time.naive_local())
+    .ok_or_else(|| error(format!("Invalid timestamp: {}", timestamp)))
+}
+
+fn start_of_day(timestamp: i64) -> Option<i64> {
+  let date = local_date_time(timestamp).ok()?.date();
+  let start = date.and_hms_opt(0, 0, 0)?;
+  Local
+    .from_local_datetime(&start)
+    .single()
+    .map(|date_time| date_time.timestamp())
+}
+
+/// Returns `left - right` in the given unit. Months and years are counted as whole calendar
+/// months, the other units can be fractional.
+fn date_between(left: i64, right: i64, unit: &str) -> FlowyResult<f64> {
+  let seconds = seconds_between(left, right)?;
+  let value = match unit.to_lowercase().as_str() {
+    "years" | "year" => (months_between(left, right)? / 12) as f64,
+    "months" | "month" => months_between(left, right)? as f64,
+    "weeks" | "week" => seconds / (7 * SECONDS_PER_DAY) as f64,
+    "days" | "day" => seconds / SECONDS_PER_DAY as f64,
+    "hours" | "hour" => seconds / 3600.0,
+    "minutes" | "minute" => seconds / 60.0,
+    "seconds" | "second" => seconds,
+    _ => return Err(error(format!("Unknown date unit: {}", unit))),
+  };
+  Ok(value.trunc())
+}
+
+fn months_between(left: i64, right: i64) -> FlowyResult<i64> {
+  let (left, right) = (local_date_time(left)?, local_date_time(right)?);
+  let mut months =
+    (left.year() - right.year()) as i64 * 12 + left.month() as i64 - right.month() as i64;
+  // Only count the month if it's completed
+  if months > 0 && (left.day(), left.time()) < (right.day(), right.time()) {
+    months -= 1;
+  } else if months < 0 && (left.day(), left.time()) > (right.day(), right.time()) {
+    months += 1;
+  }
+  Ok(months)
+}
+
+fn date_add(timestamp: i64, amount: f64, unit: &str) -> FlowyResult<i64> {
+  let seconds = match unit.to_lowercase().as_str() {
+    "years" | "year" | "months" | "month" => {
+      let months = if unit.to_lowercase().starts_with("year") {
+        (amount as i64).checked_mul(12)
+      } else {
+        Some(amount as i64)
+      }
+      .and_then(|months| i32::try_from(months).ok())
+      .ok_or_else(|| error("Date out of range"))?;
+      let date_time = local_date_time(timestamp)?;
+      let shifted = if months >= 0 {
+        date_time.checked_add_months(Months::new(months.unsigned_abs()))
+      } else {
+        date_time.checked_sub_months(Months::new(months.unsigned_abs()))
+      }
+      .ok_or_else(|| error("Date out of range"))?;
+      return Local
+        .from_local_datetime(&shifted)
+        .earliest()
+        .map(|date_time| date_time.timestamp())
+        .ok_or_else(|| error("Date out of range"));
+    },
+    "weeks" | "week" => amount * (7 * SECONDS_PER_DAY) as f64,
+    "days" | "day" => amount * SECONDS_PER_DAY as f64,
+    "hours" | "hour" => amount * 3600.0,
+    "minutes" | "minute" => amount * 60.0,
+    "seconds" | "second" => amount,
+    _ => return Err(error(format!("Unknown date unit: {}", unit))),
+  };
+  add_seconds(timestamp, seconds)
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::HashMap;
+
+  use flowy_error::FlowyResult;
+
+  use crate::services::field::formula_type_option::formula_evaluator::{
+    evaluate_formula, FormulaContext,
+  };
+  use crate::services::field::formula_type_option::formula_parser::parse_formula;
+  use crate::services::field::FormulaValue;
+
+  struct MockContext(HashMap<&'static str, FormulaValue>);
+
+  impl FormulaContext for MockContext {
+    fn field_value(&self, field: &str) -> FlowyResult<FormulaValue> {
+      Ok(self.0.get(field).cloned().unwrap_or_default())
+    }
+
+    fn now(&self) -> i64 {
+      1_700_000_000
+    }
+  }
+
+  fn eval(formula: &str) -> FormulaValue {
+    try_eval(formula).unwrap()
+  }
+
+  fn try_eval(formula: &str) -> FlowyResult<FormulaValue> {
+    let ctx = MockContext(HashMap::from([
+      ("Price", FormulaValue::Number(2.5)),
+      ("Quantity", FormulaValue::Number(4.0)),
+      ("Name", FormulaValue::Text("AppFlowy".to_owned())),
+      ("Done", FormulaValue::Boolean(true)),
+      ("Start", FormulaValue::Date(1_700_000_000)),
+    ]));
+    let expr = parse_formula(formula)?;
+    evaluate_formula(&expr, &ctx)
+  }
+
+  #[test]
+  fn formula_arithmetic_test() {
+    assert_eq!(eval("{Price} * {Quantity}"), FormulaValue::Number(10.0));
+    assert_eq!(eval("(1 + 2) * 3 - 4 / 2"), FormulaValue::Number(7.0));
+    assert_eq!(eval("-{Price} + {Missing}"), FormulaValue::Number(-2.5));
+    assert_eq!(eval("7 % 4"), FormulaValue::Number(3.0));
+  }
+
+  #[test]
+  fn formula_text_test() {
+    assert_eq!(
+      eval(r#"{Name} + " " + 1"#),
+      FormulaValue::Text("AppFlowy 1".to_owned())
+    );
+    assert_eq!(
+      eval(r#"concat(upper({Name}), "-", length({Name}))"#),
+      FormulaValue::Text("APPFLOWY-8".to_owned())
+    );
+  }
+
+  #[test]
+  fn formula_if_test() {
+    assert_eq!(
+      eval(r#"if({Price} > 2 and {Done}, "Yes", "No")"#),
+      FormulaValue::Text("Yes".to_owned())
+    );
+    assert_eq!(
+      eval(r#"if(not {Done}, 1 / 0, 2)"#),
+      FormulaValue::Number(2.0)
+    );
+    assert_eq!(eval(r#"if({Missing}, 1)"#), FormulaValue::Empty);
+  }
+
+  #[test]
+  fn formula_compare_empty_test() {
+    assert_eq!(eval("{Missing} > 10"), FormulaValue::Boolean(false));
+    assert_eq!(eval("{Missing} <= 10"), FormulaValue::Boolean(false));
+    assert_eq!(eval("10 < {Missing}"), FormulaValue::Boolean(false));
+    assert_eq!(eval("{Price} > 2"), FormulaValue::Boolean(true));
+  }
+
+  #[test]
+  fn formula_date_test() {
+    assert_eq!(eval("{Start} + 1"), FormulaValue::Date(1_700_086_400));
+    assert_eq!(
+      eval(r#"dateBetween(dateAdd({Start}, 3, "days"), now(), "days")"#),
+      FormulaValue::Number(3.0)
+    );
+    assert_eq!(
+      eval(r#"dateBetween(dateAdd({Start}, 2, "months"), {Start}, "months")"#),
+      FormulaValue::Number(2.0)
+    );
+    assert_eq!(
+      eval(r#"dateBetween({Start}, dateSubtract({Start}, 36, "hours"), "days")"#),
+      FormulaValue::Number(1.0)
+    );
+  }
+
+  #[test]
+  fn formula_invalid_date_test() {
+    assert!(try_eval(r#"formatDate(now(), "%Q")"#).is_err());
+    assert!(try_eval("{Start} + 100000000000000000000").is_err());
+    assert!(try_eval(r#"dateAdd({Start}, 100000000000000000000, "seconds")"#).is_err());
+    assert!(try_eval(r#"dateAdd({Start}, 9223372036854775807, "years")"#).is_err());
+  }
+}
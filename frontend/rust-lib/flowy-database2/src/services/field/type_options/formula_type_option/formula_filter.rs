@@ -0,0 +1,109 @@
+use crate::entities::{FormulaFilterPB, FormulaResultTypePB};
+use crate::services::field::{
+  CheckboxCellData, FormulaCellData, FormulaValue, NumberCellFormat, NumberFormat,
+};
+
+impl FormulaFilterPB {
+  pub fn is_visible(&self, cell_data: &FormulaCellData) -> bool {
+    let value = &cell_data.value;
+    match self.result_type {
+      FormulaResultTypePB::Text => match &self.text_filter {
+        None => true,
+        Some(filter) => filter.is_visible(value.to_string()),
+      },
+      FormulaResultTypePB::Number => match &self.number_filter {
+        None => true,
+        Some(filter) => {
+          let num_cell_data =
+            NumberCellFormat::from_format_str(&value.to_string(), &NumberFormat::Num)
+              .unwrap_or_default();
+          filter.is_visible(&num_cell_data)
+        },
+      },
+      FormulaResultTypePB::Date => match &self.date_filter {
+        None => true,
+        Some(filter) => match value {
+          FormulaValue::Date(timestamp) => filter.is_visible(*timestamp),
+          _ => filter.is_visible(None::<i64>),
+        },
+      },
+      FormulaResultTypePB::Boolean => match &self.checkbox_filter {
+        None => true,
+        Some(filter) => {
+          let checkbox = value
+            .to_string()
+            .parse::<CheckboxCellData>()
+            .unwrap_or_default();
+          filter.is_visible(&checkbox)
+        },
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::entities::{
+    CheckboxFilterConditionPB, CheckboxFilterPB, FieldType, FormulaFilterPB, FormulaResultTypePB,
+    NumberFilterConditionPB, NumberFilterPB,
+  };
+  use crate::services::field::{FormulaCellData, FormulaValue};
+  use crate::services::filter::{Filter, FilterType};
+
+  #[test]
+  fn formula_number_filter_test() {
+    let filter = FormulaFilterPB {
+      result_type: FormulaResultTypePB::Number,
+      number_filter: Some(NumberFilterPB {
+        condition: NumberFilterConditionPB::GreaterThan,
+        content: "10".to_owned(),
+      }),
+      ..Default::default()
+    };
+
+    for (value, visible) in [
+      (FormulaValue::Number(12.0), true),
+      (FormulaValue::Number(10.0), false),
+      (FormulaValue::Empty, false),
+    ] {
+      let cell_data = FormulaCellData::new(value.clone());
+      assert_eq!(filter.is_visible(&cell_data), visible, "{:?}", value);
+    }
+  }
+
+  #[test]
+  fn formula_checkbox_filter_test() {
+    let filter = FormulaFilterPB {
+      result_type: FormulaResultTypePB::Boolean,
+      checkbox_filter: Some(CheckboxFilterPB {
+        condition: CheckboxFilterConditionPB::IsChecked,
+      }),
+      ..Default::default()
+    };
+    assert!(filter.is_visible(&FormulaCellData::new(FormulaValue::Boolean(true))));
+    assert!(!filter.is_visible(&FormulaCellData::new(FormulaValue::Boolean(false))));
+  }
+
+  #[test]
+  fn formula_filter_serde_test() {
+    let filter = FormulaFilterPB {
+      result_type: FormulaResultTypePB::Number,
+      number_filter: Some(NumberFilterPB {
+        condition: NumberFilterConditionPB::LessThan,
+        content: "3".to_owned(),
+      }),
+      ..Default::default()
+    };
+    let (condition, content) = filter.to_condition_and_content();
+    let saved_filter = Filter {
+      id: "id".to_owned(),
+      parent_id: None,
+      filter_type: FilterType::Data,
+      field_id: "field_id".to_owned(),
+      field_type: FieldType::Formula,
+      condition: condition as i64,
+      content,
+    };
+    assert_eq!(FormulaFilterPB::from(&saved_filter), filter);
+  }
+}
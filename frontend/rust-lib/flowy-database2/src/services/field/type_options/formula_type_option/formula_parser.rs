@@ -0,0 +1,538 @@
+use std::ops::Range;
+
+use flowy_error::{ErrorCode, FlowyError, FlowyResult};
+
+/// The syntax tree of a formula.
+///
+/// ```text
+/// if({Estimate} > 10, "Big", "Small")
+/// {Price} * {Quantity}
+/// dateBetween(now(), {Due date}, "days")
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormulaExpr {
+  Number(f64),
+  Text(String),
+  Boolean(bool),
+  /// Refers to the cell of the field with the given name or id of the current row.
+  Field(String),
+  Unary(UnaryOp, Box<FormulaExpr>),
+  Binary(BinaryOp, Box<FormulaExpr>, Box<FormulaExpr>),
+  Call(String, Vec<FormulaExpr>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+  Neg,
+  Not,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+  Add,
+  Sub,
+  Mul,
+  Div,
+  Rem,
+  Eq,
+  NotEq,
+  Lt,
+  LtEq,
+  Gt,
+  GtEq,
+  And,
+  Or,
+}
+
+impl BinaryOp {
+  /// Returns the binding power of the operator. The higher one binds tighter.
+  fn precedence(&self) -> u8 {
+    match self {
+      BinaryOp::Or => 1,
+      BinaryOp::And => 2,
+      BinaryOp::Eq | BinaryOp::NotEq => 3,
+      BinaryOp::Lt | BinaryOp::LtEq | BinaryOp::Gt | BinaryOp::GtEq => 4,
+      BinaryOp::Add | BinaryOp::Sub => 5,
+      BinaryOp::Mul | BinaryOp::Div | BinaryOp::Rem => 6,
+    }
+  }
+}
+
+impl FormulaExpr {
+  /// Returns the names or ids of the fields that are referenced by the formula.
+  pub fn referenced_fields(&self) -> Vec<String> {
+    let mut fields = vec![];
+    self.collect_referenced_fields(&mut fields);
+    fields
+  }
+
+  /// Returns true if the result depends on the current time, i.e. `now()` or `today()` is called.
+  pub fn is_time_dependent(&self) -> bool {
+    match self {
+      FormulaExpr::Unary(_, expr) => expr.is_time_dependent(),
+      FormulaExpr::Binary(_, left, right) => left.is_time_dependent() || right.is_time_dependent(),
+      FormulaExpr::Call(name, args) => {
+        name.eq_ignore_ascii_case("now")
+          || name.eq_ignore_ascii_case("today")
+          || args.iter().any(|arg| arg.is_time_dependent())
+      },
+      FormulaExpr::Number(_)
+      | FormulaExpr::Text(_)
+      | FormulaExpr::Boolean(_)
+      | FormulaExpr::Field(_) => false,
+    }
+  }
+
+  fn collect_referenced_fields(&self, fields: &mut Vec<String>) {
+    match self {
+      FormulaExpr::Field(field) => {
+        if !fields.contains(field) {
+          fields.push(field.clone());
+        }
+      },
+      FormulaExpr::Unary(_, expr) => expr.collect_referenced_fields(fields),
+      FormulaExpr::Binary(_, left, right) => {
+        left.collect_referenced_fields(fields);
+        right.collect_referenced_fields(fields);
+      },
+      FormulaExpr::Call(name, args) => {
+        // prop("Name") is the same as {Name}
+        if name.eq_ignore_ascii_case("prop") {
+          if let Some(FormulaExpr::Text(field)) = args.first() {
+            if !fields.contains(field) {
+              fields.push(field.clone());
+            }
+          }
+        }
+        args
+          .iter()
+          .for_each(|arg| arg.collect_referenced_fields(fields));
+      },
+      FormulaExpr::Number(_) | FormulaExpr::Text(_) | FormulaExpr::Boolean(_) => {},
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+  Number(f64),
+  Text(String),
+  Ident(String),
+  Field(String),
+  Op(&'static str),
+  LParen,
+  RParen,
+  Comma,
+}
+
+pub fn parse_formula(formula: &str) -> FlowyResult<FormulaExpr> {
+  let tokens = tokenize(formula)?;
+  let mut parser = Parser {
+    tokens,
+    pos: 0,
+    nesting: 0,
+  };
+  let (expr, _) = parser.parse_expr(0)?;
+  if parser.pos < parser.tokens.len() {
+    return Err(invalid_formula(format!(
+      "Unexpected token: {:?}",
+      parser.tokens[parser.pos]
+    )));
+  }
+  Ok(expr)
+}
+
+fn invalid_formula<T: ToString>(msg: T) -> FlowyError {
+  FlowyError::new(ErrorCode::InvalidFormula, msg)
+}
+
+const OPERATORS: [&str; 16] = [
+  "==", "!=", "<=", ">=", "&&", "||", "+", "-", "*", "/", "%", "<", ">", "!", "=", "&",
+];
+
+fn tokenize(formula: &str) -> FlowyResult<Vec<Token>> {
+  let tokens = tokenize_with_spans(formula)?;
+  Ok(tokens.into_iter().map(|(token, _)| token).collect())
+}
+
+/// Returns the tokens along with the range of the chars that each token is read from.
+fn tokenize_with_spans(formula: &str) -> FlowyResult<Vec<(Token, Range<usize>)>> {
+  let chars = formula.chars().collect::<Vec<char>>();
+  let mut tokens = vec![];
+  let mut i = 0;
+  while i < chars.len() {
+    let c = chars[i];
+    if c.is_whitespace() {
+      i += 1;
+      continue;
+    }
+
+    let start = i;
+    let token = match c {
+      '(' => {
+        i += 1;
+        Token::LParen
+      },
+      ')' => {
+        i += 1;
+        Token::RParen
+      },
+      ',' => {
+        i += 1;
+        Token::Comma
+      },
+      '"' | '\'' => {
+        let mut text = String::new();
+        i += 1;
+        loop {
+          match chars.get(i) {
+            None => return Err(invalid_formula("Unterminated string")),
+            Some('\\') => {
+              if let Some(next) = chars.get(i + 1) {
+                text.push(*next);
+              }
+              i += 2;
+            },
+            Some(ch) if *ch == c => break,
+            Some(ch) => {
+              text.push(*ch);
+              i += 1;
+            },
+          }
+        }
+        i += 1;
+        Token::Text(text)
+      },
+      '{' => {
+        let end = chars[i..]
+          .iter()
+          .position(|ch| *ch == '}')
+          .ok_or_else(|| invalid_formula("Unterminated field reference"))?;
+        let field = chars[i + 1..i + end].iter().collect::<String>();
+        i += end + 1;
+        Token::Field(field.trim().to_string())
+      },
+      _ if c.is_ascii_digit() || c == '.' => {
+        while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+          i += 1;
+        }
+        let s = chars[start..i].iter().collect::<String>();
+        let num = s
+          .parse::<f64>()
+          .map_err(|_| invalid_formula(format!("Invalid number: {}", s)))?;
+        Token::Number(num)
+      },
+      _ if c.is_alphabetic() || c == '_' => {
+        while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+          i += 1;
+        }
+        Token::Ident(chars[start..i].iter().collect())
+      },
+      _ => {
+        let op = OPERATORS
+          .iter()
+          .find(|op| {
+            let op_chars = op.chars().collect::<Vec<char>>();
+            chars[i..].starts_with(&op_chars)
+          })
+          .ok_or_else(|| invalid_formula(format!("Unexpected character: {}", c)))?;
+        i += op.len();
+        Token::Op(op)
+      },
+    };
+    tokens.push((token, start..i));
+  }
+  Ok(tokens)
+}
+
+/// Rewrites the field references of the formula, `{Name}` and `prop("Name")`, with the given
+/// function. The references that the function returns None for, and the rest of the formula,
+/// are kept as they are.
+pub fn rewrite_field_references<F>(formula: &str, rewrite: F) -> FlowyResult<String>
+where
+  F: Fn(&str) -> Option<String>,
+{
+  let tokens = tokenize_with_spans(formula)?;
+  let mut replacements = vec![];
+  for (index, (token, span)) in tokens.iter().enumerate() {
+    match token {
+      Token::Field(field) => {
+        if let Some(new_field) = rewrite(field) {
+          // The braces can't hold a name that contains '}' or is padded with whitespace.
+          let reference = if new_field.contains('}') || new_field.trim() != new_field {
+            format!("prop({})", quote_text(&new_field))
+          } else {
+            format!("{{{}}}", new_field)
+          };
+          replacements.push((span.clone(), reference));
+        }
+      },
+      Token::Text(field) => {
+        let is_prop = |token: &Token| matches!(token, Token::Ident(ident) if ident.eq_ignore_ascii_case("prop"));
+        let is_prop_argument = index >= 2
+          && is_prop(&tokens[index - 2].0)
+          && tokens[index - 1].0 == Token::LParen
+          && matches!(tokens.get(index + 1), Some((Token::RParen, _)));
+        if is_prop_argument {
+          if let Some(new_field) = rewrite(field) {
+            replacements.push((span.clone(), quote_text(&new_field)));
+          }
+        }
+      },
+      _ => {},
+    }
+  }
+
+  let chars = formula.chars().collect::<Vec<char>>();
+  let mut rewritten = String::with_capacity(formula.len());
+  let mut pos = 0;
+  for (span, replacement) in replacements {
+    rewritten.extend(&chars[pos..span.start]);
+    rewritten.push_str(&replacement);
+    pos = span.end;
+  }
+  rewritten.extend(&chars[pos..]);
+  Ok(rewritten)
+}
+
+fn quote_text(text: &str) -> String {
+  format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// The maximum depth of the syntax tree. The parser and the evaluator are recursive, so a deeper
+/// formula could overflow the stack.
+const MAX_DEPTH: usize = 256;
+
+fn check_depth(depth: usize) -> FlowyResult<usize> {
+  if depth > MAX_DEPTH {
+    return Err(invalid_formula("The formula is nested too deeply"));
+  }
+  Ok(depth)
+}
+
+/// Each parse function returns the expression along with the depth of its syntax tree.
+struct Parser {
+  tokens: Vec<Token>,
+  pos: usize,
+  /// The number of the nested parentheses, function calls and unary operators that are being
+  /// parsed.
+  nesting: usize,
+}
+
+impl Parser {
+  fn peek(&self) -> Option<&Token> {
+    self.tokens.get(self.pos)
+  }
+
+  fn next(&mut self) -> Option<Token> {
+    let token = self.tokens.get(self.pos).cloned();
+    self.pos += 1;
+    token
+  }
+
+  fn expect(&mut self, expected: Token) -> FlowyResult<()> {
+    match self.next() {
+      Some(token) if token == expected => Ok(()),
+      token => Err(invalid_formula(format!(
+        "Expected {:?}, found {:?}",
+        expected, token
+      ))),
+    }
+  }
+
+  fn peek_binary_op(&self) -> Option<BinaryOp> {
+    let op = match self.peek()? {
+      Token::Op(op) => match *op {
+        "+" | "&" => BinaryOp::Add,
+        "-" => BinaryOp::Sub,
+        "*" => BinaryOp::Mul,
+        "/" => BinaryOp::Div,
+        "%" => BinaryOp::Rem,
+        "==" | "=" => BinaryOp::Eq,
+        "!=" => BinaryOp::NotEq,
+        "<" => BinaryOp::Lt,
+        "<=" => BinaryOp::LtEq,
+        ">" => BinaryOp::Gt,
+        ">=" => BinaryOp::GtEq,
+        "&&" => BinaryOp::And,
+        "||" => BinaryOp::Or,
+        _ => return None,
+      },
+      Token::Ident(ident) => match ident.to_lowercase().as_str() {
+        "and" => BinaryOp::And,
+        "or" => BinaryOp::Or,
+        _ => return None,
+      },
+      _ => return None,
+    };
+    Some(op)
+  }
+
+  /// Precedence climbing. All the binary operators are left associative.
+  fn parse_expr(&mut self, min_precedence: u8) -> FlowyResult<(FormulaExpr, usize)> {
+    let (mut left, mut depth) = self.parse_unary()?;
+    while let Some(op) = self.peek_binary_op() {
+      let precedence = op.precedence();
+      if precedence <= min_precedence {
+        break;
+      }
+      self.pos += 1;
+      let (right, right_depth) = self.parse_expr(precedence)?;
+      // The chains of binary operators are built without recursion, so their depth is checked
+      // here instead of by the nesting.
+      depth = check_depth(depth.max(right_depth) + 1)?;
+      left = FormulaExpr::Binary(op, Box::new(left), Box::new(right));
+    }
+    Ok((left, depth))
+  }
+
+  fn parse_unary(&mut self) -> FlowyResult<(FormulaExpr, usize)> {
+    self.nesting = check_depth(self.nesting + 1)?;
+    let result = self.parse_unary_operand();
+    self.nesting -= 1;
+    result
+  }
+
+  fn parse_unary_operand(&mut self) -> FlowyResult<(FormulaExpr, usize)> {
+    let op = match self.peek() {
+      Some(Token::Op("-")) => UnaryOp::Neg,
+      Some(Token::Op("!")) => UnaryOp::Not,
+      Some(Token::Ident(ident)) if ident.eq_ignore_ascii_case("not") => UnaryOp::Not,
+      _ => return self.parse_primary(),
+    };
+    self.pos += 1;
+    let (expr, depth) = self.parse_unary()?;
+    Ok((
+      FormulaExpr::Unary(op, Box::new(expr)),
+      check_depth(depth + 1)?,
+    ))
+  }
+
+  fn parse_primary(&mut self) -> FlowyResult<(FormulaExpr, usize)> {
+    let expr = match self.next() {
+      Some(Token::Number(num)) => FormulaExpr::Number(num),
+      Some(Token::Text(text)) => FormulaExpr::Text(text),
+      Some(Token::Field(field)) => FormulaExpr::Field(field),
+      Some(Token::LParen) => {
+        let expr = self.parse_expr(0)?;
+        self.expect(Token::RParen)?;
+        return Ok(expr);
+      },
+      Some(Token::Ident(ident)) => match ident.to_lowercase().as_str() {
+        "true" => FormulaExpr::Boolean(true),
+        "false" => FormulaExpr::Boolean(false),
+        _ => {
+          self.expect(Token::LParen)?;
+          let mut args = vec![];
+          let mut depth = 0;
+          if self.peek() != Some(&Token::RParen) {
+            loop {
+              let (arg, arg_depth) = self.parse_expr(0)?;
+              args.push(arg);
+              depth = depth.max(arg_depth);
+              if self.peek() == Some(&Token::Comma) {
+                self.pos += 1;
+              } else {
+                break;
+              }
+            }
+          }
+          self.expect(Token::RParen)?;
+          return Ok((FormulaExpr::Call(ident, args), check_depth(depth + 1)?));
+        },
+      },
+      token => return Err(invalid_formula(format!("Unexpected token: {:?}", token))),
+    };
+    Ok((expr, 1))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::services::field::formula_type_option::formula_parser::{
+    parse_formula, rewrite_field_references, BinaryOp, FormulaExpr,
+  };
+
+  #[test]
+  fn parse_precedence_test() {
+    let expr = parse_formula("1 + 2 * 3").unwrap();
+    assert_eq!(
+      expr,
+      FormulaExpr::Binary(
+        BinaryOp::Add,
+        Box::new(FormulaExpr::Number(1.0)),
+        Box::new(FormulaExpr::Binary(
+          BinaryOp::Mul,
+          Box::new(FormulaExpr::Number(2.0)),
+          Box::new(FormulaExpr::Number(3.0)),
+        )),
+      )
+    );
+  }
+
+  #[test]
+  fn parse_function_and_field_test() {
+    let expr = parse_formula(r#"if({Estimate} > 10, "Big", prop("Size"))"#).unwrap();
+    assert_eq!(
+      expr.referenced_fields(),
+      vec!["Estimate".to_string(), "Size".to_string()]
+    );
+    assert!(!expr.is_time_dependent());
+    assert_eq!(
+      parse_formula(r#"PROP("Size") + 1"#)
+        .unwrap()
+        .referenced_fields(),
+      vec!["Size".to_string()]
+    );
+    assert!(parse_formula("dateBetween(Today(), {Due}, \"days\")")
+      .unwrap()
+      .is_time_dependent());
+  }
+
+  #[test]
+  fn parse_invalid_formula_test() {
+    assert!(parse_formula("1 +").is_err());
+    assert!(parse_formula("{Estimate").is_err());
+    assert!(parse_formula(r#""abc"#).is_err());
+    assert!(parse_formula("(1 + 2").is_err());
+    assert!(parse_formula("1 2").is_err());
+  }
+
+  #[test]
+  fn parse_deeply_nested_formula_test() {
+    let nested = format!("{}1{}", "(".repeat(100_000), ")".repeat(100_000));
+    assert!(parse_formula(&nested).is_err());
+    let negated = format!("{}1", "-".repeat(100_000));
+    assert!(parse_formula(&negated).is_err());
+    let sum = vec!["1"; 100_000].join(" + ");
+    assert!(parse_formula(&sum).is_err());
+    assert!(parse_formula(&vec!["1"; 100].join(" + ")).is_ok());
+  }
+
+  #[test]
+  fn rewrite_field_references_test() {
+    let rewrite = |name: &str| match name {
+      "Estimate" => Some("id_1".to_string()),
+      "Size" => Some("id_2".to_string()),
+      "Odd" => Some("a } b".to_string()),
+      _ => None,
+    };
+    assert_eq!(
+      rewrite_field_references(
+        r#"if({Estimate} > 10, "Size", prop( "Size" )) & {Other}"#,
+        rewrite
+      )
+      .unwrap(),
+      r#"if({id_1} > 10, "Size", prop( "id_2" )) & {Other}"#
+    );
+    assert_eq!(
+      rewrite_field_references("{ Odd } * 2", rewrite).unwrap(),
+      r#"prop("a } b") * 2"#
+    );
+    assert_eq!(
+      rewrite_field_references(r#"prop("Odd")"#, |_| Some(r#"a "b""#.to_string())).unwrap(),
+      r#"prop("a \"b\"")"#
+    );
+    assert!(rewrite_field_references("{Estimate", rewrite).is_err());
+  }
+}
@@ -0,0 +1,314 @@
+use std::cmp::Ordering;
+
+use collab::core::any_map::AnyMapExtension;
+use collab_database::fields::{Field, TypeOptionData, TypeOptionDataBuilder};
+use collab_database::rows::{Cell, Row};
+use serde::{Deserialize, Serialize};
+
+use flowy_error::{ErrorCode, FlowyError, FlowyResult};
+
+use crate::entities::{FieldType, FormulaCellDataPB, FormulaFilterPB};
+use crate::services::cell::{stringify_cell_data, CellDataChangeset, CellDataDecoder};
+use crate::services::field::{
+  evaluate_formula, parse_formula, rewrite_field_references, CheckboxCellData, DateCellData,
+  FormulaCellData, FormulaContext, FormulaExpr, FormulaValue, TypeOption,
+  TypeOptionCellDataCompare, TypeOptionCellDataFilter, TypeOptionCellDataSerde, TypeOptionCellExt,
+  TypeOptionTransform,
+};
+use crate::services::sort::SortCondition;
+
+/// The [FormulaTypeOption] computes the cell from the other cells of the same row. The fields
+/// are referenced by `{Field name}` or `prop("Field name")`, the field id can be used as well.
+/// The names are replaced with the ids when the formula is saved, so renaming a field doesn't
+/// break the formulas that refer to it, and the names are rendered back for display.
+///
+/// The cells are recomputed by the [DatabaseEditor](crate::services::database::DatabaseEditor)
+/// whenever one of the referenced cells is changed. The formulas that call `now()` or `today()`
+/// are recomputed when the day rolls over as well.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FormulaTypeOption {
+  pub formula: String,
+}
+
+impl FormulaTypeOption {
+  pub fn parse(&self) -> FlowyResult<FormulaExpr> {
+    parse_formula(&self.formula)
+  }
+
+  /// Returns the ids of the fields that are referenced by the formula.
+  pub fn referenced_field_ids(&self, fields: &[Field]) -> Vec<String> {
+    match self.parse() {
+      Err(_) => vec![],
+      Ok(expr) => expr
+        .referenced_fields()
+        .iter()
+        .flat_map(|name_or_id| find_field(fields, name_or_id))
+        .map(|field| field.id.clone())
+        .collect(),
+    }
+  }
+
+  /// Returns true if the result of the formula depends on the current time.
+  pub fn is_time_dependent(&self) -> bool {
+    self
+      .parse()
+      .map(|expr| expr.is_time_dependent())
+      .unwrap_or(false)
+  }
+
+  /// Replaces the field names that the formula refers to with the field ids. An invalid formula
+  /// is kept as it is.
+  pub fn normalize_field_references(mut self, fields: &[Field]) -> Self {
+    if let Ok(formula) = rewrite_field_references(&self.formula, |name_or_id| {
+      find_field(fields, name_or_id).map(|field| field.id.clone())
+    }) {
+      self.formula = formula;
+    }
+    self
+  }
+
+  /// Replaces the field ids that the formula refers to with the field names, which is how the
+  /// formula is displayed.
+  pub fn display_field_references(mut self, fields: &[Field]) -> Self {
+    if let Ok(formula) = rewrite_field_references(&self.formula, |field_id| {
+      fields
+        .iter()
+        .find(|field| field.id == field_id)
+        .map(|field| field.name.clone())
+    }) {
+      self.formula = formula;
+    }
+    self
+  }
+
+  /// Evaluates the formula against the given row. An invalid formula produces an empty cell.
+  pub fn evaluate(&self, fields: &[Field], row: &Row) -> FormulaCellData {
+    let ctx = RowFormulaContext { fields, row };
+    let value = self
+      .parse()
+      .and_then(|expr| evaluate_formula(&expr, &ctx))
+      .unwrap_or_else(|err| {
+        tracing::trace!("Failed to evaluate the formula: {}, {}", self.formula, err);
+        FormulaValue::Empty
+      });
+    FormulaCellData::new(value)
+  }
+}
+
+fn find_field<'a>(fields: &'a [Field], name_or_id: &str) -> Option<&'a Field> {
+  fields
+    .iter()
+    .find(|field| field.id == name_or_id)
+    .or_else(|| fields.iter().find(|field| field.name == name_or_id))
+}
+
+struct RowFormulaContext<'a> {
+  fields: &'a [Field],
+  row: &'a Row,
+}
+
+impl<'a> FormulaContext for RowFormulaContext<'a> {
+  fn field_value(&self, name_or_id: &str) -> FlowyResult<FormulaValue> {
+    let field = find_field(self.fields, name_or_id).ok_or_else(|| {
+      FlowyError::new(
+        ErrorCode::InvalidFormula,
+        format!("Unknown field: {}", name_or_id),
+      )
+    })?;
+    let field_type = FieldType::from(field.field_type);
+    match field_type {
+      FieldType::CreatedTime => return Ok(FormulaValue::Date(self.row.created_at)),
+      FieldType::LastEditedTime => return Ok(FormulaValue::Date(self.row.modified_at)),
+      _ => {},
+    }
+
+    let cell = match self.row.cells.get(&field.id) {
+      None => return Ok(FormulaValue::Empty),
+      Some(cell) => cell,
+    };
+    let value = match field_type {
+      FieldType::Number | FieldType::Lookup => {
+        TypeOptionCellExt::new_with_cell_data_cache(field, None)
+          .get_type_option_cell_data_handler(&field_type)
+          .and_then(|handler| handler.handle_numeric_cell(cell))
+          .map(FormulaValue::Number)
+          .unwrap_or_default()
+      },
+      FieldType::DateTime => DateCellData::from(cell)
+        .timestamp
+        .map(FormulaValue::Date)
+        .unwrap_or_default(),
+      FieldType::Checkbox => FormulaValue::Boolean(CheckboxCellData::from(cell).is_check()),
+      FieldType::Formula => FormulaCellData::from(cell).value,
+      _ => {
+        let s = stringify_cell_data(cell, &field_type, &field_type, field);
+        if s.is_empty() {
+          FormulaValue::Empty
+        } else {
+          FormulaValue::Text(s)
+        }
+      },
+    };
+    Ok(value)
+  }
+}
+
+impl From<TypeOptionData> for FormulaTypeOption {
+  fn from(value: TypeOptionData) -> Self {
+    let formula = value.get_str_value("formula").unwrap_or_default();
+    Self { formula }
+  }
+}
+
+impl From<FormulaTypeOption> for TypeOptionData {
+  fn from(value: FormulaTypeOption) -> Self {
+    TypeOptionDataBuilder::new()
+      .insert_str_value("formula", value.formula)
+      .build()
+  }
+}
+
+impl TypeOption for FormulaTypeOption {
+  type CellData = FormulaCellData;
+  type CellChangeset = String;
+  type CellProtobufType = FormulaCellDataPB;
+  type CellFilter = FormulaFilterPB;
+}
+
+impl CellDataChangeset for FormulaTypeOption {
+  /// The formula cell is computed, so the changeset is ignored and the cell is kept as it is.
+  fn apply_changeset(
+    &self,
+    _changeset: <Self as TypeOption>::CellChangeset,
+    cell: Option<Cell>,
+  ) -> FlowyResult<(Cell, <Self as TypeOption>::CellData)> {
+    let cell_data = cell.as_ref().map(FormulaCellData::from).unwrap_or_default();
+    Ok((Cell::from(cell_data.clone()), cell_data))
+  }
+}
+
+impl CellDataDecoder for FormulaTypeOption {
+  fn decode_cell(
+    &self,
+    cell: &Cell,
+    decoded_field_type: &FieldType,
+    _field: &Field,
+  ) -> FlowyResult<<Self as TypeOption>::CellData> {
+    if !decoded_field_type.is_formula() {
+      return Ok(Default::default());
+    }
+
+    self.parse_cell(cell)
+  }
+
+  fn stringify_cell_data(&self, cell_data: <Self as TypeOption>::CellData) -> String {
+    cell_data.to_string()
+  }
+
+  fn stringify_cell(&self, cell: &Cell) -> String {
+    let cell_data = FormulaCellData::from(cell);
+    self.stringify_cell_data(cell_data)
+  }
+
+  fn numeric_cell(&self, cell: &Cell) -> Option<f64> {
+    match FormulaCellData::from(cell).value {
+      FormulaValue::Number(num) => Some(num),
+      _ => None,
+    }
+  }
+}
+
+impl TypeOptionCellDataCompare for FormulaTypeOption {
+  fn apply_cmp(
+    &self,
+    cell_data: &<Self as TypeOption>::CellData,
+    other_cell_data: &<Self as TypeOption>::CellData,
+    sort_condition: SortCondition,
+  ) -> Ordering {
+    // The empty cells are always placed at the end.
+    match (cell_data.value.is_empty(), other_cell_data.value.is_empty()) {
+      (true, true) => Ordering::Equal,
+      (true, false) => Ordering::Greater,
+      (false, true) => Ordering::Less,
+      (false, false) => {
+        let order = cell_data.value.compare(&other_cell_data.value);
+        sort_condition.evaluate_order(order)
+      },
+    }
+  }
+}
+
+impl TypeOptionCellDataFilter for FormulaTypeOption {
+  fn apply_filter(
+    &self,
+    filter: &<Self as TypeOption>::CellFilter,
+    field_type: &FieldType,
+    cell_data: &<Self as TypeOption>::CellData,
+  ) -> bool {
+    if !field_type.is_formula() {
+      return true;
+    }
+
+    filter.is_visible(cell_data)
+  }
+}
+
+impl TypeOptionTransform for FormulaTypeOption {}
+
+impl TypeOptionCellDataSerde for FormulaTypeOption {
+  fn protobuf_encode(
+    &self,
+    cell_data: <Self as TypeOption>::CellData,
+  ) -> <Self as TypeOption>::CellProtobufType {
+    cell_data.into()
+  }
+
+  fn parse_cell(&self, cell: &Cell) -> FlowyResult<<Self as TypeOption>::CellData> {
+    Ok(cell.into())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use collab_database::fields::Field;
+
+  use crate::entities::FieldType;
+  use crate::services::field::FormulaTypeOption;
+
+  #[test]
+  fn formula_field_references_round_trip_test() {
+    let fields = vec![
+      Field::new(
+        "id_1".to_owned(),
+        "Price".to_owned(),
+        FieldType::Number.into(),
+        false,
+      ),
+      Field::new(
+        "id_2".to_owned(),
+        "Quantity".to_owned(),
+        FieldType::Number.into(),
+        false,
+      ),
+    ];
+    let type_option = FormulaTypeOption {
+      formula: r#"{Price} * prop("Quantity") + {Unknown}"#.to_owned(),
+    }
+    .normalize_field_references(&fields);
+    assert_eq!(type_option.formula, r#"{id_1} * prop("id_2") + {Unknown}"#);
+    assert_eq!(
+      type_option.referenced_field_ids(&fields),
+      vec!["id_1", "id_2"]
+    );
+
+    // Renaming a field doesn't break the formula, the new name is displayed.
+    let mut renamed_fields = fields.clone();
+    renamed_fields[0].name = "Unit price".to_owned();
+    assert_eq!(
+      type_option
+        .display_field_references(&renamed_fields)
+        .formula,
+      r#"{Unit price} * prop("Quantity") + {Unknown}"#
+    );
+  }
+}
@@ -0,0 +1,11 @@
+#![allow(clippy::module_inception)]
+mod formula_entities;
+mod formula_evaluator;
+mod formula_filter;
+mod formula_parser;
+mod formula_type_option;
+
+pub use formula_entities::*;
+pub use formula_evaluator::*;
+pub use formula_parser::*;
+pub use formula_type_option::*;
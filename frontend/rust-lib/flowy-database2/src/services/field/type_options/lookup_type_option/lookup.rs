@@ -0,0 +1,209 @@
+use std::cmp::Ordering;
+
+use collab::core::any_map::AnyMapExtension;
+use collab_database::fields::{Field, TypeOptionData, TypeOptionDataBuilder};
+use collab_database::rows::{Cell, Row};
+use serde::{Deserialize, Serialize};
+
+use flowy_error::{ErrorCode, FlowyError, FlowyResult};
+
+use crate::entities::{CalculationType, FieldType, LookupCellDataPB, NumberFilterPB};
+use crate::services::calculations::CalculationsService;
+use crate::services::cell::{CellDataChangeset, CellDataDecoder};
+use crate::services::field::{
+  stringify_key_cell, LookupCellData, LookupRelatedRows, RelationCellData, TypeOption,
+  TypeOptionCellDataCompare, TypeOptionCellDataFilter, TypeOptionCellDataSerde,
+  TypeOptionTransform,
+};
+use crate::services::sort::SortCondition;
+
+/// The [LookupTypeOption] aggregates the `target_field_id` of another database over all the rows
+/// whose `related_key_field_id` cell matches the `key_field_id` cell of the current row. It works
+/// like the VLOOKUP/SUMIF of the spreadsheet.
+///
+/// If the key field is a relation field, the linked rows are used instead of comparing the keys,
+/// and the `related_key_field_id` is ignored.
+///
+/// The cells are computed by the [DatabaseEditor](crate::services::database::DatabaseEditor), they
+/// are recomputed whenever the rows of the related database or the key cells are changed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LookupTypeOption {
+  pub database_id: String,
+  pub key_field_id: String,
+  pub related_key_field_id: String,
+  pub target_field_id: String,
+  pub calculation_type: i64,
+}
+
+impl LookupTypeOption {
+  /// Returns true if the value of the lookup field depends on the given field of the current
+  /// database.
+  pub fn depends_on_local_field(&self, field_id: &str) -> bool {
+    self.key_field_id == field_id
+  }
+
+  /// Returns true if the value of the lookup field depends on the given field of the related
+  /// database.
+  pub fn depends_on_related_field(&self, field_id: &str) -> bool {
+    self.related_key_field_id == field_id || self.target_field_id == field_id
+  }
+
+  /// Returns the aggregated value for the given row. The `key_field` is the field of the current
+  /// database that is used to find the matching rows in the `related_rows`.
+  pub fn lookup(&self, row: &Row, key_field: &Field, related_rows: &LookupRelatedRows) -> String {
+    let key_field_type = FieldType::from(key_field.field_type);
+    let row_cells = if key_field_type.is_relation() {
+      let row_ids = row
+        .cells
+        .get(&key_field.id)
+        .map(|cell| RelationCellData::from(cell).row_ids)
+        .unwrap_or_default();
+      related_rows.cells_with_row_ids(&row_ids)
+    } else {
+      match stringify_key_cell(row, key_field) {
+        None => vec![],
+        Some(key) => related_rows.cells_with_key(&key),
+      }
+    };
+
+    if row_cells.is_empty() {
+      return "".to_owned();
+    }
+
+    CalculationsService::new().calculate(
+      &related_rows.target_field,
+      self.calculation_type,
+      row_cells,
+    )
+  }
+}
+
+impl From<TypeOptionData> for LookupTypeOption {
+  fn from(value: TypeOptionData) -> Self {
+    let database_id = value.get_str_value("database_id").unwrap_or_default();
+    let key_field_id = value.get_str_value("key_field_id").unwrap_or_default();
+    let related_key_field_id = value
+      .get_str_value("related_key_field_id")
+      .unwrap_or_default();
+    let target_field_id = value.get_str_value("target_field_id").unwrap_or_default();
+    let calculation_type = value
+      .get_i64_value("calculation_type")
+      .unwrap_or_else(|| CalculationType::Sum.value());
+    Self {
+      database_id,
+      key_field_id,
+      related_key_field_id,
+      target_field_id,
+      calculation_type,
+    }
+  }
+}
+
+impl From<LookupTypeOption> for TypeOptionData {
+  fn from(value: LookupTypeOption) -> Self {
+    TypeOptionDataBuilder::new()
+      .insert_str_value("database_id", value.database_id)
+      .insert_str_value("key_field_id", value.key_field_id)
+      .insert_str_value("related_key_field_id", value.related_key_field_id)
+      .insert_str_value("target_field_id", value.target_field_id)
+      .insert_i64_value("calculation_type", value.calculation_type)
+      .build()
+  }
+}
+
+impl TypeOption for LookupTypeOption {
+  type CellData = LookupCellData;
+  type CellChangeset = String;
+  type CellProtobufType = LookupCellDataPB;
+  type CellFilter = NumberFilterPB;
+}
+
+impl CellDataChangeset for LookupTypeOption {
+  fn apply_changeset(
+    &self,
+    _changeset: <Self as TypeOption>::CellChangeset,
+    _cell: Option<Cell>,
+  ) -> FlowyResult<(Cell, <Self as TypeOption>::CellData)> {
+    Err(FlowyError::new(
+      ErrorCode::FieldInvalidOperation,
+      "Cells of this field type cannot be edited",
+    ))
+  }
+}
+
+impl CellDataDecoder for LookupTypeOption {
+  fn decode_cell(
+    &self,
+    cell: &Cell,
+    decoded_field_type: &FieldType,
+    _field: &Field,
+  ) -> FlowyResult<<Self as TypeOption>::CellData> {
+    if !decoded_field_type.is_lookup() {
+      return Ok(Default::default());
+    }
+
+    self.parse_cell(cell)
+  }
+
+  fn stringify_cell_data(&self, cell_data: <Self as TypeOption>::CellData) -> String {
+    cell_data.to_string()
+  }
+
+  fn stringify_cell(&self, cell: &Cell) -> String {
+    let cell_data = LookupCellData::from(cell);
+    self.stringify_cell_data(cell_data)
+  }
+
+  fn numeric_cell(&self, cell: &Cell) -> Option<f64> {
+    LookupCellData::from(cell).numeric_value()
+  }
+}
+
+impl TypeOptionCellDataCompare for LookupTypeOption {
+  fn apply_cmp(
+    &self,
+    cell_data: &<Self as TypeOption>::CellData,
+    other_cell_data: &<Self as TypeOption>::CellData,
+    sort_condition: SortCondition,
+  ) -> Ordering {
+    match (cell_data.numeric_value(), other_cell_data.numeric_value()) {
+      (Some(left), Some(right)) => {
+        let order = left.partial_cmp(&right).unwrap_or(Ordering::Equal);
+        sort_condition.evaluate_order(order)
+      },
+      (Some(_), None) => Ordering::Less,
+      (None, Some(_)) => Ordering::Greater,
+      (None, None) => Ordering::Equal,
+    }
+  }
+}
+
+impl TypeOptionCellDataFilter for LookupTypeOption {
+  fn apply_filter(
+    &self,
+    filter: &<Self as TypeOption>::CellFilter,
+    field_type: &FieldType,
+    cell_data: &<Self as TypeOption>::CellData,
+  ) -> bool {
+    if !field_type.is_lookup() {
+      return true;
+    }
+
+    filter.is_lookup_visible(cell_data)
+  }
+}
+
+impl TypeOptionTransform for LookupTypeOption {}
+
+impl TypeOptionCellDataSerde for LookupTypeOption {
+  fn protobuf_encode(
+    &self,
+    cell_data: <Self as TypeOption>::CellData,
+  ) -> <Self as TypeOption>::CellProtobufType {
+    cell_data.into()
+  }
+
+  fn parse_cell(&self, cell: &Cell) -> FlowyResult<<Self as TypeOption>::CellData> {
+    Ok(cell.into())
+  }
+}
@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use collab::core::any_map::AnyMapExtension;
+use collab_database::fields::Field;
+use collab_database::rows::{new_cell_builder, Cell, Row, RowCell, RowId};
+
+use flowy_error::FlowyResult;
+
+use crate::entities::FieldType;
+use crate::services::cell::{stringify_cell_data, FromCellString};
+use crate::services::field::{TypeOptionCellData, CELL_DATA};
+
+/// The aggregated value of the lookup field. The value is computed from the rows of the related
+/// database and is stored in the cell, so the cell can be displayed, sorted and filtered without
+/// opening the related database.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LookupCellData(pub String);
+
+impl LookupCellData {
+  pub fn new(value: String) -> Self {
+    Self(value)
+  }
+
+  pub fn numeric_value(&self) -> Option<f64> {
+    self.0.parse::<f64>().ok()
+  }
+}
+
+impl TypeOptionCellData for LookupCellData {
+  fn is_cell_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+}
+
+impl From<&Cell> for LookupCellData {
+  fn from(cell: &Cell) -> Self {
+    Self(cell.get_str_value(CELL_DATA).unwrap_or_default())
+  }
+}
+
+impl From<LookupCellData> for Cell {
+  fn from(data: LookupCellData) -> Self {
+    new_cell_builder(FieldType::Lookup)
+      .insert_str_value(CELL_DATA, data.0)
+      .build()
+  }
+}
+
+impl FromCellString for LookupCellData {
+  fn from_cell_str(s: &str) -> FlowyResult<Self>
+  where
+    Self: Sized,
+  {
+    Ok(Self(s.to_owned()))
+  }
+}
+
+impl ToString for LookupCellData {
+  fn to_string(&self) -> String {
+    self.0.clone()
+  }
+}
+
+impl AsRef<str> for LookupCellData {
+  fn as_ref(&self) -> &str {
+    &self.0
+  }
+}
+
+/// The rows of the related database that a lookup field aggregates over. The target cells are
+/// indexed by the stringified key cell and by the row id, so computing the value of each local
+/// row doesn't need to scan all the related rows.
+pub struct LookupRelatedRows {
+  pub target_field: Field,
+  cells_by_key: HashMap<String, Vec<Arc<RowCell>>>,
+  cell_by_row_id: HashMap<String, Arc<RowCell>>,
+}
+
+impl LookupRelatedRows {
+  pub fn new(key_field: Option<&Field>, target_field: Field, rows: Vec<Row>) -> Self {
+    let mut cells_by_key: HashMap<String, Vec<Arc<RowCell>>> = HashMap::new();
+    let mut cell_by_row_id = HashMap::new();
+    for row in rows {
+      let row_cell = Arc::new(RowCell {
+        row_id: row.id.clone(),
+        cell: row.cells.get(&target_field.id).cloned(),
+      });
+
+      if let Some(key) = key_field.and_then(|field| stringify_key_cell(&row, field)) {
+        cells_by_key.entry(key).or_default().push(row_cell.clone());
+      }
+      cell_by_row_id.insert(row.id.into_inner(), row_cell);
+    }
+
+    Self {
+      target_field,
+      cells_by_key,
+      cell_by_row_id,
+    }
+  }
+
+  /// Returns the target cells of the related rows whose key is equal to the given key.
+  pub fn cells_with_key(&self, key: &str) -> Vec<Arc<RowCell>> {
+    self.cells_by_key.get(key).cloned().unwrap_or_default()
+  }
+
+  /// Returns the target cells of the given related rows.
+  pub fn cells_with_row_ids(&self, row_ids: &[RowId]) -> Vec<Arc<RowCell>> {
+    row_ids
+      .iter()
+      .flat_map(|row_id| self.cell_by_row_id.get(row_id.as_str()).cloned())
+      .collect()
+  }
+}
+
+/// Returns the stringified cell of the key field. Empty keys never match any row.
+pub(crate) fn stringify_key_cell(row: &Row, key_field: &Field) -> Option<String> {
+  let field_type = FieldType::from(key_field.field_type);
+  let cell = row.cells.get(&key_field.id)?;
+  let key = stringify_cell_data(cell, &field_type, &field_type, key_field);
+  if key.is_empty() {
+    None
+  } else {
+    Some(key)
+  }
+}
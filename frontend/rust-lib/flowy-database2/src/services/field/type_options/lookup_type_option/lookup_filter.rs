@@ -0,0 +1,41 @@
+use crate::entities::NumberFilterPB;
+use crate::services::field::{LookupCellData, NumberCellFormat, NumberFormat};
+
+impl NumberFilterPB {
+  /// The lookup cell stores the aggregated value as a plain number, so it's filtered the same
+  /// way as a number cell without any format.
+  pub fn is_lookup_visible(&self, cell_data: &LookupCellData) -> bool {
+    let num_cell_data =
+      NumberCellFormat::from_format_str(cell_data.as_ref(), &NumberFormat::Num).unwrap_or_default();
+    self.is_visible(&num_cell_data)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::entities::{NumberFilterConditionPB, NumberFilterPB};
+  use crate::services::field::LookupCellData;
+
+  #[test]
+  fn lookup_filter_greater_than_test() {
+    let filter = NumberFilterPB {
+      condition: NumberFilterConditionPB::GreaterThan,
+      content: "10".to_owned(),
+    };
+
+    for (value, visible) in [("12.00000", true), ("10.00000", false), ("", false)] {
+      let cell_data = LookupCellData::new(value.to_owned());
+      assert_eq!(filter.is_lookup_visible(&cell_data), visible, "{}", value);
+    }
+  }
+
+  #[test]
+  fn lookup_filter_is_empty_test() {
+    let filter = NumberFilterPB {
+      condition: NumberFilterConditionPB::NumberIsEmpty,
+      content: "".to_owned(),
+    };
+    assert!(filter.is_lookup_visible(&LookupCellData::default()));
+    assert!(!filter.is_lookup_visible(&LookupCellData::new("1.00000".to_owned())));
+  }
+}
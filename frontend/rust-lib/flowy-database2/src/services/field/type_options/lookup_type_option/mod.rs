@@ -0,0 +1,7 @@
+#![allow(clippy::module_inception)]
+mod lookup;
+mod lookup_entities;
+mod lookup_filter;
+
+pub use lookup::*;
+pub use lookup_entities::*;
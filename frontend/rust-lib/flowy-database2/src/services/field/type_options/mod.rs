@@ -1,7 +1,13 @@
+pub mod auto_number_type_option;
 pub mod checkbox_type_option;
 pub mod checklist_type_option;
 pub mod date_type_option;
+pub mod files_type_option;
+pub mod formula_type_option;
+pub mod lookup_type_option;
 pub mod number_type_option;
+pub mod person_type_option;
+pub mod relation_type_option;
 pub mod selection_type_option;
 pub mod text_type_option;
 pub mod timestamp_type_option;
@@ -10,12 +16,19 @@ mod type_option_cell;
 mod url_type_option;
 mod util;
 
+pub use auto_number_type_option::*;
 pub use checkbox_type_option::*;
 pub use date_type_option::*;
+pub use files_type_option::*;
+pub use formula_type_option::*;
+pub use lookup_type_option::*;
 pub use number_type_option::*;
+pub use person_type_option::*;
+pub use relation_type_option::*;
 pub use selection_type_option::*;
 pub use text_type_option::*;
 pub use timestamp_type_option::*;
 pub use type_option::*;
 pub use type_option_cell::*;
 pub use url_type_option::*;
+pub use util::*;
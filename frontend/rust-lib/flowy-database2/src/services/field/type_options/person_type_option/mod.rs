@@ -0,0 +1,7 @@
+#![allow(clippy::module_inception)]
+mod person;
+mod person_entities;
+mod person_filter;
+
+pub use person::*;
+pub use person_entities::*;
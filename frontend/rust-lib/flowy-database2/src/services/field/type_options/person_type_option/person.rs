@@ -0,0 +1,303 @@
+use std::cmp::Ordering;
+
+use bytes::Bytes;
+use collab::core::any_map::AnyMapExtension;
+use collab_database::fields::{Field, TypeOptionData, TypeOptionDataBuilder};
+use collab_database::rows::Cell;
+use serde::{Deserialize, Serialize};
+
+use flowy_error::{internal_error, FlowyResult};
+
+use crate::entities::{FieldType, PersonCellDataPB, PersonFilterPB};
+use crate::services::cell::{CellDataChangeset, CellDataDecoder, CellProtobufBlobParser};
+use crate::services::field::{
+  default_order, Person, PersonCellChangeset, PersonCellData, TypeOption,
+  TypeOptionCellDataCompare, TypeOptionCellDataFilter, TypeOptionCellDataSerde,
+  TypeOptionTransform,
+};
+use crate::services::sort::SortCondition;
+
+/// The [PersonTypeOption] assigns workspace members to a row. The cell only stores the ids of
+/// the members. The type option keeps the name and the email of the members that were assigned,
+/// so the cell can be displayed and exported without fetching the member list.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PersonTypeOption {
+  /// Only one person can be assigned to the cell if it's true.
+  #[serde(default)]
+  pub is_single: bool,
+  #[serde(default)]
+  pub persons: Vec<Person>,
+}
+
+impl PersonTypeOption {
+  pub fn get_person(&self, user_id: &str) -> Option<&Person> {
+    self.persons.iter().find(|person| person.id == user_id)
+  }
+
+  /// Inserts the person or updates the name and the email of the existing one.
+  pub fn insert_person(&mut self, person: Person) {
+    match self
+      .persons
+      .iter_mut()
+      .find(|existing| existing.id == person.id)
+    {
+      None => self.persons.push(person),
+      Some(existing) => *existing = person,
+    }
+  }
+
+  pub fn delete_person(&mut self, user_id: &str) {
+    self.persons.retain(|person| person.id != user_id);
+  }
+
+  fn display_name<'a>(&'a self, user_id: &'a str) -> &'a str {
+    self
+      .get_person(user_id)
+      .map(|person| person.display_name())
+      .unwrap_or(user_id)
+  }
+}
+
+impl From<TypeOptionData> for PersonTypeOption {
+  fn from(data: TypeOptionData) -> Self {
+    data
+      .get_str_value("content")
+      .map(|s| serde_json::from_str::<PersonTypeOption>(&s).unwrap_or_default())
+      .unwrap_or_default()
+  }
+}
+
+impl From<PersonTypeOption> for TypeOptionData {
+  fn from(data: PersonTypeOption) -> Self {
+    let content = serde_json::to_string(&data).unwrap_or_default();
+    TypeOptionDataBuilder::new()
+      .insert_str_value("content", content)
+      .build()
+  }
+}
+
+impl TypeOption for PersonTypeOption {
+  type CellData = PersonCellData;
+  type CellChangeset = PersonCellChangeset;
+  type CellProtobufType = PersonCellDataPB;
+  type CellFilter = PersonFilterPB;
+}
+
+impl CellDataChangeset for PersonTypeOption {
+  fn apply_changeset(
+    &self,
+    changeset: <Self as TypeOption>::CellChangeset,
+    cell: Option<Cell>,
+  ) -> FlowyResult<(Cell, <Self as TypeOption>::CellData)> {
+    let mut cell_data = cell.as_ref().map(PersonCellData::from).unwrap_or_default();
+
+    cell_data
+      .user_ids
+      .retain(|user_id| !changeset.removed_user_ids.contains(user_id));
+
+    if self.is_single {
+      // The last inserted person replaces the current one.
+      if let Some(user_id) = changeset.inserted_user_ids.last() {
+        cell_data.user_ids = vec![user_id.clone()];
+      }
+    } else {
+      for user_id in changeset.inserted_user_ids {
+        if !cell_data.contains(&user_id) {
+          cell_data.user_ids.push(user_id);
+        }
+      }
+    }
+
+    Ok((Cell::from(cell_data.clone()), cell_data))
+  }
+}
+
+impl CellDataDecoder for PersonTypeOption {
+  fn decode_cell(
+    &self,
+    cell: &Cell,
+    decoded_field_type: &FieldType,
+    _field: &Field,
+  ) -> FlowyResult<<Self as TypeOption>::CellData> {
+    if !decoded_field_type.is_person() {
+      return Ok(Default::default());
+    }
+
+    self.parse_cell(cell)
+  }
+
+  fn stringify_cell_data(&self, cell_data: <Self as TypeOption>::CellData) -> String {
+    cell_data
+      .user_ids
+      .iter()
+      .map(|user_id| self.display_name(user_id))
+      .collect::<Vec<_>>()
+      .join(", ")
+  }
+
+  fn stringify_cell(&self, cell: &Cell) -> String {
+    let cell_data = PersonCellData::from(cell);
+    self.stringify_cell_data(cell_data)
+  }
+
+  fn numeric_cell(&self, _cell: &Cell) -> Option<f64> {
+    None
+  }
+}
+
+impl TypeOptionCellDataCompare for PersonTypeOption {
+  fn apply_cmp(
+    &self,
+    cell_data: &<Self as TypeOption>::CellData,
+    other_cell_data: &<Self as TypeOption>::CellData,
+    sort_condition: SortCondition,
+  ) -> Ordering {
+    for (left_id, right_id) in cell_data
+      .user_ids
+      .iter()
+      .zip(other_cell_data.user_ids.iter())
+    {
+      let order = self
+        .display_name(left_id)
+        .to_lowercase()
+        .cmp(&self.display_name(right_id).to_lowercase());
+      if order.is_ne() {
+        return sort_condition.evaluate_order(order);
+      }
+    }
+
+    match cell_data
+      .user_ids
+      .len()
+      .cmp(&other_cell_data.user_ids.len())
+    {
+      Ordering::Equal => default_order(),
+      order => sort_condition.evaluate_order(order),
+    }
+  }
+}
+
+impl TypeOptionCellDataFilter for PersonTypeOption {
+  fn apply_filter(
+    &self,
+    filter: &<Self as TypeOption>::CellFilter,
+    field_type: &FieldType,
+    cell_data: &<Self as TypeOption>::CellData,
+  ) -> bool {
+    if !field_type.is_person() {
+      return true;
+    }
+
+    filter.is_visible(cell_data)
+  }
+}
+
+impl TypeOptionTransform for PersonTypeOption {}
+
+impl TypeOptionCellDataSerde for PersonTypeOption {
+  fn protobuf_encode(
+    &self,
+    cell_data: <Self as TypeOption>::CellData,
+  ) -> <Self as TypeOption>::CellProtobufType {
+    let persons = cell_data
+      .user_ids
+      .iter()
+      .map(|user_id| {
+        self.get_person(user_id).cloned().unwrap_or_else(|| Person {
+          id: user_id.clone(),
+          ..Default::default()
+        })
+      })
+      .collect::<Vec<_>>();
+    PersonCellDataPB {
+      user_ids: cell_data.user_ids,
+      persons: persons.into_iter().map(Into::into).collect(),
+    }
+  }
+
+  fn parse_cell(&self, cell: &Cell) -> FlowyResult<<Self as TypeOption>::CellData> {
+    Ok(cell.into())
+  }
+}
+
+pub struct PersonCellDataParser();
+impl CellProtobufBlobParser for PersonCellDataParser {
+  type Object = PersonCellDataPB;
+
+  fn parser(bytes: &Bytes) -> FlowyResult<Self::Object> {
+    PersonCellDataPB::try_from(bytes.as_ref()).map_err(internal_error)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::services::cell::{CellDataChangeset, CellDataDecoder};
+  use crate::services::field::{Person, PersonCellChangeset, PersonCellData, PersonTypeOption};
+
+  fn type_option(is_single: bool) -> PersonTypeOption {
+    PersonTypeOption {
+      is_single,
+      persons: vec![
+        Person {
+          id: "lucas@appflowy.io".to_string(),
+          name: "Lucas".to_string(),
+          email: "lucas@appflowy.io".to_string(),
+        },
+        Person {
+          id: "nathan@appflowy.io".to_string(),
+          name: "".to_string(),
+          email: "nathan@appflowy.io".to_string(),
+        },
+      ],
+    }
+  }
+
+  #[test]
+  fn person_multiple_changeset_test() {
+    let type_option = type_option(false);
+    let changeset = PersonCellChangeset {
+      inserted_user_ids: vec![
+        "lucas@appflowy.io".to_string(),
+        "nathan@appflowy.io".to_string(),
+      ],
+      ..Default::default()
+    };
+    let (cell, cell_data) = type_option.apply_changeset(changeset, None).unwrap();
+    assert_eq!(cell_data.user_ids.len(), 2);
+
+    let changeset = PersonCellChangeset {
+      removed_user_ids: vec!["lucas@appflowy.io".to_string()],
+      ..Default::default()
+    };
+    let (_, cell_data) = type_option.apply_changeset(changeset, Some(cell)).unwrap();
+    assert_eq!(cell_data.user_ids, vec!["nathan@appflowy.io".to_string()]);
+  }
+
+  #[test]
+  fn person_single_changeset_test() {
+    let type_option = type_option(true);
+    let changeset = PersonCellChangeset {
+      inserted_user_ids: vec!["lucas@appflowy.io".to_string()],
+      ..Default::default()
+    };
+    let (cell, _) = type_option.apply_changeset(changeset, None).unwrap();
+
+    let changeset = PersonCellChangeset {
+      inserted_user_ids: vec!["nathan@appflowy.io".to_string()],
+      ..Default::default()
+    };
+    let (_, cell_data) = type_option.apply_changeset(changeset, Some(cell)).unwrap();
+    assert_eq!(cell_data.user_ids, vec!["nathan@appflowy.io".to_string()]);
+  }
+
+  #[test]
+  fn person_stringify_test() {
+    let type_option = type_option(false);
+    let cell_data =
+      PersonCellData::from("lucas@appflowy.io,nathan@appflowy.io,unknown@appflowy.io".to_string());
+    assert_eq!(
+      type_option.stringify_cell_data(cell_data),
+      "Lucas, nathan@appflowy.io, unknown@appflowy.io"
+    );
+  }
+}
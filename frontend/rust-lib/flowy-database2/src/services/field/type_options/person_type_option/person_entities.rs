@@ -0,0 +1,112 @@
+use collab::core::any_map::AnyMapExtension;
+use collab_database::rows::{new_cell_builder, Cell};
+use serde::{Deserialize, Serialize};
+
+use flowy_error::{internal_error, FlowyResult};
+
+use crate::entities::FieldType;
+use crate::services::cell::{FromCellChangeset, FromCellString, ToCellChangeset};
+use crate::services::field::{TypeOptionCellData, CELL_DATA};
+
+pub const PERSON_IDS_SEPARATOR: &str = ",";
+
+/// A member of the workspace that can be assigned to a person cell. The member list returned by
+/// `GetWorkspaceMember` identifies the members by their email, so the `id` is the member's email.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Person {
+  pub id: String,
+  pub name: String,
+  pub email: String,
+}
+
+impl Person {
+  /// Returns the name of the person. Falls back to the email if the name is empty.
+  pub fn display_name(&self) -> &str {
+    if self.name.is_empty() {
+      &self.email
+    } else {
+      &self.name
+    }
+  }
+}
+
+/// List of the ids of the persons assigned to the cell.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PersonCellData {
+  pub user_ids: Vec<String>,
+}
+
+impl PersonCellData {
+  pub fn contains(&self, user_id: &str) -> bool {
+    self.user_ids.iter().any(|id| id == user_id)
+  }
+}
+
+impl TypeOptionCellData for PersonCellData {
+  fn is_cell_empty(&self) -> bool {
+    self.user_ids.is_empty()
+  }
+}
+
+impl From<&Cell> for PersonCellData {
+  fn from(cell: &Cell) -> Self {
+    let value = cell.get_str_value(CELL_DATA).unwrap_or_default();
+    Self::from(value)
+  }
+}
+
+impl From<String> for PersonCellData {
+  fn from(s: String) -> Self {
+    let user_ids = s
+      .split(PERSON_IDS_SEPARATOR)
+      .map(|id| id.trim())
+      .filter(|id| !id.is_empty())
+      .map(|id| id.to_owned())
+      .collect();
+    Self { user_ids }
+  }
+}
+
+impl From<PersonCellData> for Cell {
+  fn from(data: PersonCellData) -> Self {
+    new_cell_builder(FieldType::Person)
+      .insert_str_value(CELL_DATA, data.to_string())
+      .build()
+  }
+}
+
+impl FromCellString for PersonCellData {
+  fn from_cell_str(s: &str) -> FlowyResult<Self>
+  where
+    Self: Sized,
+  {
+    Ok(Self::from(s.to_owned()))
+  }
+}
+
+impl ToString for PersonCellData {
+  fn to_string(&self) -> String {
+    self.user_ids.join(PERSON_IDS_SEPARATOR)
+  }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PersonCellChangeset {
+  pub inserted_user_ids: Vec<String>,
+  pub removed_user_ids: Vec<String>,
+}
+
+impl FromCellChangeset for PersonCellChangeset {
+  fn from_changeset(changeset: String) -> FlowyResult<Self>
+  where
+    Self: Sized,
+  {
+    serde_json::from_str::<PersonCellChangeset>(&changeset).map_err(internal_error)
+  }
+}
+
+impl ToCellChangeset for PersonCellChangeset {
+  fn to_cell_changeset_str(&self) -> String {
+    serde_json::to_string(self).unwrap_or_default()
+  }
+}
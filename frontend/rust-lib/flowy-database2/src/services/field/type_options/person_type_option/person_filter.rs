@@ -0,0 +1,99 @@
+use crate::entities::{PersonFilterConditionPB, PersonFilterPB};
+use crate::services::field::PersonCellData;
+
+impl PersonFilterPB {
+  /// For [PersonFilterConditionPB::PersonIsMe], the `user_ids` is expected to contain the id of
+  /// the current user, which is filled in by the filter controller.
+  pub fn is_visible(&self, cell_data: &PersonCellData) -> bool {
+    let contains_any = || {
+      self
+        .user_ids
+        .iter()
+        .any(|user_id| cell_data.contains(user_id))
+    };
+
+    match self.condition {
+      PersonFilterConditionPB::PersonIsMe => contains_any(),
+      PersonFilterConditionPB::PersonContains => {
+        if self.user_ids.is_empty() {
+          return true;
+        }
+        contains_any()
+      },
+      PersonFilterConditionPB::PersonDoesNotContain => {
+        if self.user_ids.is_empty() {
+          return true;
+        }
+        !contains_any()
+      },
+      PersonFilterConditionPB::PersonIsEmpty => cell_data.user_ids.is_empty(),
+      PersonFilterConditionPB::PersonIsNotEmpty => !cell_data.user_ids.is_empty(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::entities::{PersonFilterConditionPB, PersonFilterPB};
+  use crate::services::field::PersonCellData;
+
+  #[test]
+  fn person_filter_is_me_test() {
+    let filter = PersonFilterPB {
+      condition: PersonFilterConditionPB::PersonIsMe,
+      user_ids: vec!["me@appflowy.io".to_string()],
+    };
+
+    for (cell_str, visible) in [
+      ("me@appflowy.io", true),
+      ("other@appflowy.io,me@appflowy.io", true),
+      ("other@appflowy.io", false),
+      ("", false),
+    ] {
+      let cell_data = PersonCellData::from(cell_str.to_string());
+      assert_eq!(filter.is_visible(&cell_data), visible, "{}", cell_str);
+    }
+
+    // Nothing matches if the current user is unknown
+    let filter = PersonFilterPB {
+      condition: PersonFilterConditionPB::PersonIsMe,
+      user_ids: vec![],
+    };
+    assert!(!filter.is_visible(&PersonCellData::from("me@appflowy.io".to_string())));
+  }
+
+  #[test]
+  fn person_filter_contains_test() {
+    let filter = PersonFilterPB {
+      condition: PersonFilterConditionPB::PersonContains,
+      user_ids: vec!["a@appflowy.io".to_string(), "b@appflowy.io".to_string()],
+    };
+
+    for (cell_str, visible) in [
+      ("a@appflowy.io", true),
+      ("c@appflowy.io,b@appflowy.io", true),
+      ("c@appflowy.io", false),
+      ("", false),
+    ] {
+      let cell_data = PersonCellData::from(cell_str.to_string());
+      assert_eq!(filter.is_visible(&cell_data), visible, "{}", cell_str);
+    }
+  }
+
+  #[test]
+  fn person_filter_is_empty_test() {
+    let filter = PersonFilterPB {
+      condition: PersonFilterConditionPB::PersonIsEmpty,
+      user_ids: vec![],
+    };
+    assert!(filter.is_visible(&PersonCellData::default()));
+    assert!(!filter.is_visible(&PersonCellData::from("a@appflowy.io".to_string())));
+
+    let filter = PersonFilterPB {
+      condition: PersonFilterConditionPB::PersonIsNotEmpty,
+      user_ids: vec![],
+    };
+    assert!(!filter.is_visible(&PersonCellData::default()));
+    assert!(filter.is_visible(&PersonCellData::from("a@appflowy.io".to_string())));
+  }
+}
@@ -0,0 +1,7 @@
+#![allow(clippy::module_inception)]
+mod relation;
+mod relation_entities;
+mod relation_filter;
+
+pub use relation::*;
+pub use relation_entities::*;
@@ -0,0 +1,144 @@
+use std::cmp::Ordering;
+
+use collab::core::any_map::AnyMapExtension;
+use collab_database::fields::{Field, TypeOptionData, TypeOptionDataBuilder};
+use collab_database::rows::Cell;
+use serde::{Deserialize, Serialize};
+
+use flowy_error::FlowyResult;
+
+use crate::entities::{FieldType, RelationCellDataPB, RelationFilterPB};
+use crate::services::cell::{CellDataChangeset, CellDataDecoder};
+use crate::services::field::{
+  RelationCellChangeset, RelationCellData, TypeOption, TypeOptionCellDataCompare,
+  TypeOptionCellDataFilter, TypeOptionCellDataSerde, TypeOptionTransform,
+};
+use crate::services::sort::SortCondition;
+
+/// The [RelationTypeOption] links the rows of the current database to the rows of another
+/// database. The cell only stores the ids of the linked rows, so renaming a row in the related
+/// database doesn't require touching the cells that refer to it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RelationTypeOption {
+  pub database_id: String,
+}
+
+impl From<TypeOptionData> for RelationTypeOption {
+  fn from(value: TypeOptionData) -> Self {
+    let database_id = value.get_str_value("database_id").unwrap_or_default();
+    Self { database_id }
+  }
+}
+
+impl From<RelationTypeOption> for TypeOptionData {
+  fn from(value: RelationTypeOption) -> Self {
+    TypeOptionDataBuilder::new()
+      .insert_str_value("database_id", value.database_id)
+      .build()
+  }
+}
+
+impl TypeOption for RelationTypeOption {
+  type CellData = RelationCellData;
+  type CellChangeset = RelationCellChangeset;
+  type CellProtobufType = RelationCellDataPB;
+  type CellFilter = RelationFilterPB;
+}
+
+impl CellDataChangeset for RelationTypeOption {
+  fn apply_changeset(
+    &self,
+    changeset: <Self as TypeOption>::CellChangeset,
+    cell: Option<Cell>,
+  ) -> FlowyResult<(Cell, <Self as TypeOption>::CellData)> {
+    let mut cell_data = cell
+      .as_ref()
+      .map(RelationCellData::from)
+      .unwrap_or_default();
+
+    cell_data
+      .row_ids
+      .retain(|row_id| !changeset.removed_row_ids.contains(row_id));
+
+    for row_id in changeset.inserted_row_ids {
+      if !cell_data.contains(&row_id) {
+        cell_data.row_ids.push(row_id);
+      }
+    }
+
+    Ok((Cell::from(cell_data.clone()), cell_data))
+  }
+}
+
+impl CellDataDecoder for RelationTypeOption {
+  fn decode_cell(
+    &self,
+    cell: &Cell,
+    decoded_field_type: &FieldType,
+    _field: &Field,
+  ) -> FlowyResult<<Self as TypeOption>::CellData> {
+    if !decoded_field_type.is_relation() {
+      return Ok(Default::default());
+    }
+
+    self.parse_cell(cell)
+  }
+
+  /// The related database isn't accessible from here, so the cell is the ids of the linked rows.
+  /// The exports write the names of the rows instead, see
+  /// [RelatedRowNames](crate::services::share::RelatedRowNames).
+  fn stringify_cell_data(&self, cell_data: <Self as TypeOption>::CellData) -> String {
+    cell_data.to_string()
+  }
+
+  fn stringify_cell(&self, cell: &Cell) -> String {
+    let cell_data = RelationCellData::from(cell);
+    self.stringify_cell_data(cell_data)
+  }
+
+  fn numeric_cell(&self, _cell: &Cell) -> Option<f64> {
+    None
+  }
+}
+
+impl TypeOptionCellDataCompare for RelationTypeOption {
+  fn apply_cmp(
+    &self,
+    cell_data: &<Self as TypeOption>::CellData,
+    other_cell_data: &<Self as TypeOption>::CellData,
+    sort_condition: SortCondition,
+  ) -> Ordering {
+    let order = cell_data.row_ids.len().cmp(&other_cell_data.row_ids.len());
+    sort_condition.evaluate_order(order)
+  }
+}
+
+impl TypeOptionCellDataFilter for RelationTypeOption {
+  fn apply_filter(
+    &self,
+    filter: &<Self as TypeOption>::CellFilter,
+    field_type: &FieldType,
+    cell_data: &<Self as TypeOption>::CellData,
+  ) -> bool {
+    if !field_type.is_relation() {
+      return true;
+    }
+
+    filter.is_visible(cell_data)
+  }
+}
+
+impl TypeOptionTransform for RelationTypeOption {}
+
+impl TypeOptionCellDataSerde for RelationTypeOption {
+  fn protobuf_encode(
+    &self,
+    cell_data: <Self as TypeOption>::CellData,
+  ) -> <Self as TypeOption>::CellProtobufType {
+    cell_data.into()
+  }
+
+  fn parse_cell(&self, cell: &Cell) -> FlowyResult<<Self as TypeOption>::CellData> {
+    Ok(cell.into())
+  }
+}
@@ -0,0 +1,97 @@
+use collab::core::any_map::AnyMapExtension;
+use collab_database::rows::{new_cell_builder, Cell, RowId};
+use serde::{Deserialize, Serialize};
+
+use flowy_error::{internal_error, FlowyResult};
+
+use crate::entities::FieldType;
+use crate::services::cell::{FromCellChangeset, FromCellString, ToCellChangeset};
+use crate::services::field::{TypeOptionCellData, CELL_DATA};
+
+pub const RELATION_ROW_IDS_SEPARATOR: &str = ",";
+
+/// List of the ids of the rows that are linked from the related database.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RelationCellData {
+  pub row_ids: Vec<RowId>,
+}
+
+impl RelationCellData {
+  pub fn contains(&self, row_id: &RowId) -> bool {
+    self.row_ids.contains(row_id)
+  }
+}
+
+impl TypeOptionCellData for RelationCellData {
+  fn is_cell_empty(&self) -> bool {
+    self.row_ids.is_empty()
+  }
+}
+
+impl From<&Cell> for RelationCellData {
+  fn from(cell: &Cell) -> Self {
+    let value = cell.get_str_value(CELL_DATA).unwrap_or_default();
+    Self::from(value)
+  }
+}
+
+impl From<String> for RelationCellData {
+  fn from(s: String) -> Self {
+    let row_ids = s
+      .split(RELATION_ROW_IDS_SEPARATOR)
+      .map(|id| id.trim())
+      .filter(|id| !id.is_empty())
+      .map(|id| RowId::from(id.to_owned()))
+      .collect();
+    Self { row_ids }
+  }
+}
+
+impl From<RelationCellData> for Cell {
+  fn from(data: RelationCellData) -> Self {
+    new_cell_builder(FieldType::Relation)
+      .insert_str_value(CELL_DATA, data.to_string())
+      .build()
+  }
+}
+
+impl FromCellString for RelationCellData {
+  fn from_cell_str(s: &str) -> FlowyResult<Self>
+  where
+    Self: Sized,
+  {
+    Ok(Self::from(s.to_owned()))
+  }
+}
+
+impl ToString for RelationCellData {
+  fn to_string(&self) -> String {
+    self
+      .row_ids
+      .iter()
+      .map(|row_id| row_id.as_str())
+      .collect::<Vec<_>>()
+      .join(RELATION_ROW_IDS_SEPARATOR)
+  }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RelationCellChangeset {
+  pub inserted_row_ids: Vec<RowId>,
+  pub removed_row_ids: Vec<RowId>,
+}
+
+impl FromCellChangeset for RelationCellChangeset {
+  fn from_changeset(changeset: String) -> FlowyResult<Self>
+  where
+    Self: Sized,
+  {
+    serde_json::from_str::<RelationCellChangeset>(&changeset).map_err(internal_error)
+  }
+}
+
+impl ToCellChangeset for RelationCellChangeset {
+  fn to_cell_changeset_str(&self) -> String {
+    serde_json::to_string(self).unwrap_or_default()
+  }
+}
@@ -0,0 +1,84 @@
+use crate::entities::{RelationFilterConditionPB, RelationFilterPB};
+use crate::services::field::RelationCellData;
+
+impl RelationFilterPB {
+  pub fn is_visible(&self, cell_data: &RelationCellData) -> bool {
+    let contains_any = || {
+      self
+        .row_ids
+        .iter()
+        .any(|row_id| cell_data.row_ids.iter().any(|id| id.as_str() == row_id))
+    };
+
+    match self.condition {
+      RelationFilterConditionPB::RelationContains => {
+        if self.row_ids.is_empty() {
+          return true;
+        }
+        contains_any()
+      },
+      RelationFilterConditionPB::RelationDoesNotContain => {
+        if self.row_ids.is_empty() {
+          return true;
+        }
+        !contains_any()
+      },
+      RelationFilterConditionPB::RelationIsEmpty => cell_data.row_ids.is_empty(),
+      RelationFilterConditionPB::RelationIsNotEmpty => !cell_data.row_ids.is_empty(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::entities::{RelationFilterConditionPB, RelationFilterPB};
+  use crate::services::field::RelationCellData;
+
+  #[test]
+  fn relation_filter_contains_test() {
+    let filter = RelationFilterPB {
+      condition: RelationFilterConditionPB::RelationContains,
+      row_ids: vec!["row_1".to_string()],
+    };
+
+    for (cell_str, visible) in [
+      ("row_1", true),
+      ("row_2,row_1", true),
+      ("row_2", false),
+      ("", false),
+    ] {
+      let cell_data = RelationCellData::from(cell_str.to_string());
+      assert_eq!(filter.is_visible(&cell_data), visible, "{}", cell_str);
+    }
+  }
+
+  #[test]
+  fn relation_filter_does_not_contain_test() {
+    let filter = RelationFilterPB {
+      condition: RelationFilterConditionPB::RelationDoesNotContain,
+      row_ids: vec!["row_1".to_string()],
+    };
+
+    for (cell_str, visible) in [("row_1", false), ("row_2", true), ("", true)] {
+      let cell_data = RelationCellData::from(cell_str.to_string());
+      assert_eq!(filter.is_visible(&cell_data), visible, "{}", cell_str);
+    }
+  }
+
+  #[test]
+  fn relation_filter_is_empty_test() {
+    let filter = RelationFilterPB {
+      condition: RelationFilterConditionPB::RelationIsEmpty,
+      row_ids: vec![],
+    };
+    assert!(filter.is_visible(&RelationCellData::default()));
+    assert!(!filter.is_visible(&RelationCellData::from("row_1".to_string())));
+
+    let filter = RelationFilterPB {
+      condition: RelationFilterConditionPB::RelationIsNotEmpty,
+      row_ids: vec![],
+    };
+    assert!(!filter.is_visible(&RelationCellData::default()));
+    assert!(filter.is_visible(&RelationCellData::from("row_1".to_string())));
+  }
+}
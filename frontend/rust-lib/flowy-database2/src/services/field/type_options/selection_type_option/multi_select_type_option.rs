@@ -178,6 +178,31 @@ impl TypeOptionCellDataCompare for MultiSelectTypeOption {
       order => sort_condition.evaluate_order(order),
     }
   }
+
+  /// Compares the positions of the options one by one, so a cell whose first option comes first
+  /// in the type option is placed first.
+  fn apply_cmp_by_option_position(
+    &self,
+    cell_data: &<Self as TypeOption>::CellData,
+    other_cell_data: &<Self as TypeOption>::CellData,
+    sort_condition: SortCondition,
+  ) -> Ordering {
+    let positions = |cell_data: &SelectOptionIds| {
+      let mut positions = cell_data
+        .iter()
+        .flat_map(|id| self.options.iter().position(|option| &option.id == id))
+        .collect::<Vec<usize>>();
+      positions.sort_unstable();
+      positions
+    };
+    let (left, right) = (positions(cell_data), positions(other_cell_data));
+    match (left.is_empty(), right.is_empty()) {
+      (true, true) => default_order(),
+      (true, false) => Ordering::Greater,
+      (false, true) => Ordering::Less,
+      (false, false) => sort_condition.evaluate_order(left.cmp(&right)),
+    }
+  }
 }
 
 #[cfg(test)]
@@ -151,13 +151,35 @@ impl TypeOptionCellDataCompare for SingleSelectTypeOption {
       (None, None) => default_order(),
     }
   }
+
+  fn apply_cmp_by_option_position(
+    &self,
+    cell_data: &<Self as TypeOption>::CellData,
+    other_cell_data: &<Self as TypeOption>::CellData,
+    sort_condition: SortCondition,
+  ) -> Ordering {
+    let position = |cell_data: &SelectOptionIds| {
+      cell_data
+        .first()
+        .and_then(|id| self.options.iter().position(|option| &option.id == id))
+    };
+    match (position(cell_data), position(other_cell_data)) {
+      (Some(left), Some(right)) => sort_condition.evaluate_order(left.cmp(&right)),
+      (Some(_), None) => Ordering::Less,
+      (None, Some(_)) => Ordering::Greater,
+      (None, None) => default_order(),
+    }
+  }
 }
 
 #[cfg(test)]
 mod tests {
+  use std::cmp::Ordering;
+
   use crate::entities::FieldType;
   use crate::services::cell::CellDataChangeset;
   use crate::services::field::type_options::*;
+  use crate::services::sort::SortCondition;
 
   #[test]
   fn single_select_transform_with_checkbox_type_option_test() {
@@ -225,4 +247,50 @@ mod tests {
     let select_option_ids = single_select.apply_changeset(changeset, None).unwrap().1;
     assert!(select_option_ids.is_cell_empty());
   }
+
+  #[test]
+  fn single_select_cmp_by_option_position_test() {
+    let todo = SelectOption::new("Todo");
+    let in_progress = SelectOption::new("In progress");
+    let done = SelectOption::new("Done");
+    let single_select = SingleSelectTypeOption {
+      options: vec![todo.clone(), in_progress.clone(), done.clone()],
+      disable_color: false,
+    };
+    let cell_data = |option: &SelectOption| SelectOptionIds::from(vec![option.id.clone()]);
+
+    // By name, "Done" comes before "In progress"
+    assert_eq!(
+      single_select.apply_cmp(
+        &cell_data(&done),
+        &cell_data(&in_progress),
+        SortCondition::Ascending
+      ),
+      Ordering::Less
+    );
+    assert_eq!(
+      single_select.apply_cmp_by_option_position(
+        &cell_data(&done),
+        &cell_data(&in_progress),
+        SortCondition::Ascending
+      ),
+      Ordering::Greater
+    );
+    assert_eq!(
+      single_select.apply_cmp_by_option_position(
+        &cell_data(&todo),
+        &cell_data(&done),
+        SortCondition::Descending
+      ),
+      Ordering::Greater
+    );
+    assert_eq!(
+      single_select.apply_cmp_by_option_position(
+        &cell_data(&todo),
+        &SelectOptionIds::new(),
+        SortCondition::Descending
+      ),
+      Ordering::Less
+    );
+  }
 }
@@ -1,22 +1,112 @@
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+
+use fancy_regex::Regex;
+use lazy_static::lazy_static;
+use lru::LruCache;
+use parking_lot::Mutex;
+
 use crate::entities::{TextFilterConditionPB, TextFilterPB};
 
+lazy_static! {
+  /// The filters are applied to every cell of the field, so the compiled regular expressions are
+  /// cached instead of compiling the pattern for each cell. None means the pattern is invalid.
+  static ref REGEX_CACHE: Mutex<LruCache<(String, bool), Option<Arc<Regex>>>> =
+    Mutex::new(LruCache::new(NonZeroUsize::new(64).unwrap()));
+}
+
 impl TextFilterPB {
   pub fn is_visible<T: AsRef<str>>(&self, cell_data: T) -> bool {
-    let cell_data = cell_data.as_ref().to_lowercase();
-    let content = &self.content.to_lowercase();
+    let cell_data = cell_data.as_ref();
+    let (text, content) = if self.case_sensitive {
+      (cell_data.to_owned(), self.content.clone())
+    } else {
+      (cell_data.to_lowercase(), self.content.to_lowercase())
+    };
+    match self.condition {
+      TextFilterConditionPB::Is => text == content,
+      TextFilterConditionPB::IsNot => text != content,
+      TextFilterConditionPB::Contains => text.contains(&content),
+      TextFilterConditionPB::DoesNotContain => !text.contains(&content),
+      TextFilterConditionPB::StartsWith => text.starts_with(&content),
+      TextFilterConditionPB::EndsWith => text.ends_with(&content),
+      TextFilterConditionPB::TextIsEmpty => text.is_empty(),
+      TextFilterConditionPB::TextIsNotEmpty => !text.is_empty(),
+      TextFilterConditionPB::ContainsWord => contains_word(&text, &content),
+      // A row never matches an invalid pattern.
+      TextFilterConditionPB::MatchesRegex => get_regex(&self.content, self.case_sensitive)
+        .map(|regex| regex.is_match(cell_data).unwrap_or(false))
+        .unwrap_or(false),
+    }
+  }
+
+  /// Returns true if a row with the given texts is visible. It's used to search the text of all
+  /// the cells of a row: the row matches if any of its texts matches, but for the negated
+  /// conditions, such as [TextFilterConditionPB::DoesNotContain], all of its texts must match.
+  pub fn is_visible_in_any<I, T>(&self, texts: I) -> bool
+  where
+    I: IntoIterator<Item = T>,
+    T: AsRef<str>,
+  {
+    let is_empty_condition = matches!(
+      self.condition,
+      TextFilterConditionPB::TextIsEmpty | TextFilterConditionPB::TextIsNotEmpty
+    );
+    // An empty search matches every row
+    if self.content.is_empty() && !is_empty_condition {
+      return true;
+    }
+
+    let mut texts = texts.into_iter();
     match self.condition {
-      TextFilterConditionPB::Is => &cell_data == content,
-      TextFilterConditionPB::IsNot => &cell_data != content,
-      TextFilterConditionPB::Contains => cell_data.contains(content),
-      TextFilterConditionPB::DoesNotContain => !cell_data.contains(content),
-      TextFilterConditionPB::StartsWith => cell_data.starts_with(content),
-      TextFilterConditionPB::EndsWith => cell_data.ends_with(content),
-      TextFilterConditionPB::TextIsEmpty => cell_data.is_empty(),
-      TextFilterConditionPB::TextIsNotEmpty => !cell_data.is_empty(),
+      TextFilterConditionPB::IsNot
+      | TextFilterConditionPB::DoesNotContain
+      | TextFilterConditionPB::TextIsEmpty => texts.all(|text| self.is_visible(text)),
+      _ => texts.any(|text| self.is_visible(text)),
     }
   }
 }
 
+fn get_regex(pattern: &str, case_sensitive: bool) -> Option<Arc<Regex>> {
+  let key = (pattern.to_owned(), case_sensitive);
+  let mut cache = REGEX_CACHE.lock();
+  if let Some(regex) = cache.get(&key) {
+    return regex.clone();
+  }
+
+  let regex = if case_sensitive {
+    Regex::new(pattern)
+  } else {
+    Regex::new(&format!("(?i){}", pattern))
+  }
+  .ok()
+  .map(Arc::new);
+  cache.put(key, regex.clone());
+  regex
+}
+
+/// Returns true if the word appears in the text and the characters around it are not a part of
+/// a word.
+fn contains_word(text: &str, word: &str) -> bool {
+  if word.is_empty() {
+    return false;
+  }
+
+  let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+  text.match_indices(word).any(|(start, _)| {
+    let end = start + word.len();
+    let is_start_boundary = text[..start]
+      .chars()
+      .next_back()
+      .map_or(true, |c| !is_word_char(c));
+    let is_end_boundary = text[end..]
+      .chars()
+      .next()
+      .map_or(true, |c| !is_word_char(c));
+    is_start_boundary && is_end_boundary
+  })
+}
+
 #[cfg(test)]
 mod tests {
   #![allow(clippy::all)]
@@ -27,6 +117,7 @@ mod tests {
     let text_filter = TextFilterPB {
       condition: TextFilterConditionPB::Is,
       content: "appflowy".to_owned(),
+      case_sensitive: false,
     };
 
     assert!(text_filter.is_visible("AppFlowy"));
@@ -39,6 +130,7 @@ mod tests {
     let text_filter = TextFilterPB {
       condition: TextFilterConditionPB::StartsWith,
       content: "appflowy".to_owned(),
+      case_sensitive: false,
     };
 
     assert_eq!(text_filter.is_visible("AppFlowy.io"), true);
@@ -51,6 +143,7 @@ mod tests {
     let text_filter = TextFilterPB {
       condition: TextFilterConditionPB::EndsWith,
       content: "appflowy".to_owned(),
+      case_sensitive: false,
     };
 
     assert_eq!(text_filter.is_visible("https://github.com/appflowy"), true);
@@ -62,6 +155,7 @@ mod tests {
     let text_filter = TextFilterPB {
       condition: TextFilterConditionPB::TextIsEmpty,
       content: "appflowy".to_owned(),
+      case_sensitive: false,
     };
 
     assert_eq!(text_filter.is_visible(""), true);
@@ -72,6 +166,7 @@ mod tests {
     let text_filter = TextFilterPB {
       condition: TextFilterConditionPB::Contains,
       content: "appflowy".to_owned(),
+      case_sensitive: false,
     };
 
     assert_eq!(text_filter.is_visible("https://github.com/appflowy"), true);
@@ -80,4 +175,73 @@ mod tests {
     assert_eq!(text_filter.is_visible(""), false);
     assert_eq!(text_filter.is_visible("github"), false);
   }
+
+  #[test]
+  fn text_filter_case_sensitive_test() {
+    let text_filter = TextFilterPB {
+      condition: TextFilterConditionPB::Contains,
+      content: "Flowy".to_owned(),
+      case_sensitive: true,
+    };
+
+    assert_eq!(text_filter.is_visible("AppFlowy"), true);
+    assert_eq!(text_filter.is_visible("appflowy"), false);
+  }
+
+  #[test]
+  fn text_filter_regex_test() {
+    let text_filter = TextFilterPB {
+      condition: TextFilterConditionPB::MatchesRegex,
+      content: r"^v\d+\.\d+$".to_owned(),
+      case_sensitive: false,
+    };
+
+    assert_eq!(text_filter.is_visible("v0.4"), true);
+    assert_eq!(text_filter.is_visible("V10.12"), true);
+    assert_eq!(text_filter.is_visible("v0.4-beta"), false);
+
+    let text_filter = TextFilterPB {
+      condition: TextFilterConditionPB::MatchesRegex,
+      content: "[unclosed".to_owned(),
+      case_sensitive: false,
+    };
+    assert_eq!(text_filter.is_visible("[unclosed"), false);
+  }
+
+  #[test]
+  fn text_filter_contain_word_test() {
+    let text_filter = TextFilterPB {
+      condition: TextFilterConditionPB::ContainsWord,
+      content: "flowy".to_owned(),
+      case_sensitive: false,
+    };
+
+    assert_eq!(text_filter.is_visible("Flowy is open source"), true);
+    assert_eq!(text_filter.is_visible("open source, flowy."), true);
+    assert_eq!(text_filter.is_visible("AppFlowy"), false);
+    assert_eq!(text_filter.is_visible("flowy_io"), false);
+  }
+
+  #[test]
+  fn text_filter_visible_in_any_test() {
+    let texts = vec!["AppFlowy", "Notion"];
+    let text_filter = |condition: TextFilterConditionPB, content: &str| TextFilterPB {
+      condition,
+      content: content.to_owned(),
+      case_sensitive: false,
+    };
+
+    assert!(text_filter(TextFilterConditionPB::Contains, "notion").is_visible_in_any(&texts));
+    assert!(
+      !text_filter(TextFilterConditionPB::DoesNotContain, "notion").is_visible_in_any(&texts)
+    );
+    assert!(
+      text_filter(TextFilterConditionPB::DoesNotContain, "obsidian").is_visible_in_any(&texts)
+    );
+    assert!(!text_filter(TextFilterConditionPB::IsNot, "appflowy").is_visible_in_any(&texts));
+    assert!(text_filter(TextFilterConditionPB::TextIsNotEmpty, "").is_visible_in_any(&texts));
+    assert!(!text_filter(TextFilterConditionPB::TextIsEmpty, "").is_visible_in_any(&texts));
+    assert!(text_filter(TextFilterConditionPB::TextIsEmpty, "").is_visible_in_any(vec!["", ""]));
+    assert!(text_filter(TextFilterConditionPB::Contains, "").is_visible_in_any(&texts));
+  }
 }
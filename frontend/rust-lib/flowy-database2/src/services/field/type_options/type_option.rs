@@ -9,15 +9,17 @@ use protobuf::ProtobufError;
 use flowy_error::FlowyResult;
 
 use crate::entities::{
-  CheckboxTypeOptionPB, ChecklistTypeOptionPB, DateTypeOptionPB, FieldType,
-  MultiSelectTypeOptionPB, NumberTypeOptionPB, RichTextTypeOptionPB, SingleSelectTypeOptionPB,
-  TimestampTypeOptionPB, URLTypeOptionPB,
+  AutoNumberTypeOptionPB, CheckboxTypeOptionPB, ChecklistTypeOptionPB, DateTypeOptionPB, FieldType,
+  FilesTypeOptionPB, FormulaTypeOptionPB, LookupTypeOptionPB, MultiSelectTypeOptionPB,
+  NumberTypeOptionPB, PersonTypeOptionPB, RelationTypeOptionPB, RichTextTypeOptionPB,
+  SingleSelectTypeOptionPB, TimestampTypeOptionPB, URLTypeOptionPB,
 };
 use crate::services::cell::{CellDataDecoder, FromCellChangeset, ToCellChangeset};
 use crate::services::field::checklist_type_option::ChecklistTypeOption;
 use crate::services::field::{
-  CheckboxTypeOption, DateTypeOption, MultiSelectTypeOption, NumberTypeOption, RichTextTypeOption,
-  SingleSelectTypeOption, TimestampTypeOption, URLTypeOption,
+  AutoNumberTypeOption, CheckboxTypeOption, DateTypeOption, FilesTypeOption, FormulaTypeOption,
+  LookupTypeOption, MultiSelectTypeOption, NumberTypeOption, PersonTypeOption, RelationTypeOption,
+  RichTextTypeOption, SingleSelectTypeOption, TimestampTypeOption, URLTypeOption,
 };
 use crate::services::filter::FromFilterString;
 use crate::services::sort::SortCondition;
@@ -160,6 +162,18 @@ pub trait TypeOptionCellDataCompare: TypeOption {
     sort_condition: SortCondition,
   ) -> Ordering;
 
+  /// Compares the cells by the position of their options in the type option instead of the
+  /// names of the options. Only the select option type options have options, the others
+  /// compare the cells as [Self::apply_cmp] does.
+  fn apply_cmp_by_option_position(
+    &self,
+    cell_data: &<Self as TypeOption>::CellData,
+    other_cell_data: &<Self as TypeOption>::CellData,
+    sort_condition: SortCondition,
+  ) -> Ordering {
+    self.apply_cmp(cell_data, other_cell_data, sort_condition)
+  }
+
   /// Compares the two cells where one of the cells is None
   fn apply_cmp_with_uninitialized(
     &self,
@@ -206,6 +220,24 @@ pub fn type_option_data_from_pb<T: Into<Bytes>>(
     FieldType::Checklist => {
       ChecklistTypeOptionPB::try_from(bytes).map(|pb| ChecklistTypeOption::from(pb).into())
     },
+    FieldType::Relation => {
+      RelationTypeOptionPB::try_from(bytes).map(|pb| RelationTypeOption::from(pb).into())
+    },
+    FieldType::Lookup => {
+      LookupTypeOptionPB::try_from(bytes).map(|pb| LookupTypeOption::from(pb).into())
+    },
+    FieldType::Formula => {
+      FormulaTypeOptionPB::try_from(bytes).map(|pb| FormulaTypeOption::from(pb).into())
+    },
+    FieldType::Person => {
+      PersonTypeOptionPB::try_from(bytes).map(|pb| PersonTypeOption::from(pb).into())
+    },
+    FieldType::Files => {
+      FilesTypeOptionPB::try_from(bytes).map(|pb| FilesTypeOption::from(pb).into())
+    },
+    FieldType::AutoNumber => {
+      AutoNumberTypeOptionPB::try_from(bytes).map(|pb| AutoNumberTypeOption::from(pb).into())
+    },
   }
 }
 
@@ -261,6 +293,42 @@ pub fn type_option_to_pb(type_option: TypeOptionData, field_type: &FieldType) ->
         .try_into()
         .unwrap()
     },
+    FieldType::Relation => {
+      let relation_type_option: RelationTypeOption = type_option.into();
+      RelationTypeOptionPB::from(relation_type_option)
+        .try_into()
+        .unwrap()
+    },
+    FieldType::Lookup => {
+      let lookup_type_option: LookupTypeOption = type_option.into();
+      LookupTypeOptionPB::from(lookup_type_option)
+        .try_into()
+        .unwrap()
+    },
+    FieldType::Formula => {
+      let formula_type_option: FormulaTypeOption = type_option.into();
+      FormulaTypeOptionPB::from(formula_type_option)
+        .try_into()
+        .unwrap()
+    },
+    FieldType::Person => {
+      let person_type_option: PersonTypeOption = type_option.into();
+      PersonTypeOptionPB::from(person_type_option)
+        .try_into()
+        .unwrap()
+    },
+    FieldType::Files => {
+      let files_type_option: FilesTypeOption = type_option.into();
+      FilesTypeOptionPB::from(files_type_option)
+        .try_into()
+        .unwrap()
+    },
+    FieldType::AutoNumber => {
+      let auto_number_type_option: AutoNumberTypeOption = type_option.into();
+      AutoNumberTypeOptionPB::from(auto_number_type_option)
+        .try_into()
+        .unwrap()
+    },
   }
 }
 
@@ -280,5 +348,11 @@ pub fn default_type_option_data_from_type(field_type: &FieldType) -> TypeOptionD
     FieldType::Checkbox => CheckboxTypeOption::default().into(),
     FieldType::URL => URLTypeOption::default().into(),
     FieldType::Checklist => ChecklistTypeOption.into(),
+    FieldType::Relation => RelationTypeOption::default().into(),
+    FieldType::Lookup => LookupTypeOption::default().into(),
+    FieldType::Formula => FormulaTypeOption::default().into(),
+    FieldType::Person => PersonTypeOption::default().into(),
+    FieldType::Files => FilesTypeOption.into(),
+    FieldType::AutoNumber => AutoNumberTypeOption::default().into(),
   }
 }
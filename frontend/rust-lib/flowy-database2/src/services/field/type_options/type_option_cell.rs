@@ -15,11 +15,13 @@ use crate::services::cell::{
 };
 use crate::services::field::checklist_type_option::ChecklistTypeOption;
 use crate::services::field::{
-  CheckboxTypeOption, DateTypeOption, MultiSelectTypeOption, NumberTypeOption, RichTextTypeOption,
-  SingleSelectTypeOption, TimestampTypeOption, TypeOption, TypeOptionCellDataCompare,
-  TypeOptionCellDataFilter, TypeOptionCellDataSerde, TypeOptionTransform, URLTypeOption,
+  AutoNumberTypeOption, CheckboxTypeOption, DateTypeOption, FilesTypeOption, FormulaTypeOption,
+  LookupTypeOption, MultiSelectTypeOption, NumberTypeOption, PersonTypeOption, RelationTypeOption,
+  RichTextTypeOption, SingleSelectTypeOption, TimestampTypeOption, TypeOption, TypeOptionCellData,
+  TypeOptionCellDataCompare, TypeOptionCellDataFilter, TypeOptionCellDataSerde,
+  TypeOptionTransform, URLTypeOption,
 };
-use crate::services::sort::SortCondition;
+use crate::services::sort::{EmptyPlacement, SelectOptionOrder, Sort};
 
 pub const CELL_DATA: &str = "data";
 
@@ -51,10 +53,17 @@ pub trait TypeOptionCellDataHandler: Send + Sync + 'static {
     left_cell: Option<&Cell>,
     right_cell: Option<&Cell>,
     field: &Field,
-    sort_condition: SortCondition,
+    sort: &Sort,
   ) -> Ordering;
 
-  fn handle_cell_filter(&self, field_type: &FieldType, field: &Field, cell: &Cell) -> bool;
+  /// Applies the filter identified by `filter_id` to the cell. Returns true if the cell passes.
+  fn handle_cell_filter(
+    &self,
+    filter_id: &str,
+    field_type: &FieldType,
+    field: &Field,
+    cell: &Cell,
+  ) -> bool;
 
   /// Format the cell to string using the passed-in [FieldType] and [Field].
   /// The [Cell] is generic, so we need to know the [FieldType] and [Field] to format the cell.
@@ -66,6 +75,10 @@ pub trait TypeOptionCellDataHandler: Send + Sync + 'static {
 
   fn handle_numeric_cell(&self, cell: &Cell) -> Option<f64>;
 
+  /// Returns true if the decoded cell data is empty. For example, a text cell without content or
+  /// a select option cell without any selected option.
+  fn handle_is_cell_empty(&self, cell: &Cell, field: &Field) -> bool;
+
   /// Format the cell to [BoxCellData] using the passed-in [FieldType] and [Field].
   /// The caller can get the cell data by calling [BoxCellData::unbox_or_none].
   fn get_cell_data(
@@ -248,69 +261,89 @@ where
     Ok(cell)
   }
 
-  /// Compares two cell data values given their optional references, field information, and sorting condition.
+  /// Compares two cell data values given their optional references, field information, and sort.
   ///
   /// This function is designed to handle the comparison of cells that might not be initialized. The cells are
-  /// first decoded based on the provided field type, and then compared according to the specified sort condition.
+  /// first decoded based on the provided field type, and then compared according to the specified sort.
   ///
   /// # Parameters
   /// - `left_cell`: An optional reference to the left cell's data.
   /// - `right_cell`: An optional reference to the right cell's data.
   /// - `field`: A reference to the field information, which includes details about the field type.
-  /// - `sort_condition`: The condition that dictates the sort order based on the results of the comparison.
+  /// - `sort`: The sort that dictates the sort order, where the empty cells are placed and how
+  ///   the select options are compared.
   ///
   /// # Returns
   /// An `Ordering` indicating:
   /// - `Ordering::Equal` if both cells are `None` or if their decoded values are equal.
   /// - `Ordering::Less` or `Ordering::Greater` based on the `apply_cmp_with_uninitialized` or `apply_cmp`
-  ///   method results and the specified `sort_condition`.
+  ///   method results and the specified sort condition.
   ///
   /// # Note
+  /// - If the empty cells are placed first, a `None` or an empty cell is placed before the other cell.
   /// - If only one of the cells is `None`, the other cell is decoded, and the comparison is made using
   ///   the `apply_cmp_with_uninitialized` method.
-  /// - If both cells are present, they are decoded, and the comparison is made using the `apply_cmp` method.
+  /// - If both cells are present, they are decoded, and the comparison is made using the `apply_cmp` method,
+  ///   or the `apply_cmp_by_option_position` method if the options are compared by their position.
   fn handle_cell_compare(
     &self,
     left_cell: Option<&Cell>,
     right_cell: Option<&Cell>,
     field: &Field,
-    sort_condition: SortCondition,
+    sort: &Sort,
   ) -> Ordering {
     let field_type = FieldType::from(field.field_type);
+    let decode = |cell: &Cell| -> <T as TypeOption>::CellData {
+      self
+        .get_decoded_cell_data(cell, &field_type, field)
+        .unwrap_or_default()
+    };
+    let left_cell_data = left_cell.map(&decode);
+    let right_cell_data = right_cell.map(&decode);
+
+    if sort.empty_placement == EmptyPlacement::First {
+      let is_empty = |cell_data: &Option<<T as TypeOption>::CellData>| {
+        cell_data
+          .as_ref()
+          .map_or(true, |cell_data| cell_data.is_cell_empty())
+      };
+      match (is_empty(&left_cell_data), is_empty(&right_cell_data)) {
+        (true, true) => return Ordering::Equal,
+        (true, false) => return Ordering::Less,
+        (false, true) => return Ordering::Greater,
+        (false, false) => {},
+      }
+    }
 
-    match (left_cell, right_cell) {
+    match (left_cell_data, right_cell_data) {
       (None, None) => Ordering::Equal,
-      (None, Some(right_cell)) => {
-        let right_cell_data = self
-          .get_decoded_cell_data(right_cell, &field_type, field)
-          .unwrap_or_default();
-
-        self.apply_cmp_with_uninitialized(None, Some(right_cell_data).as_ref(), sort_condition)
+      (None, Some(right_cell_data)) => {
+        self.apply_cmp_with_uninitialized(None, Some(&right_cell_data), sort.condition)
       },
-      (Some(left_cell), None) => {
-        let left_cell_data = self
-          .get_decoded_cell_data(left_cell, &field_type, field)
-          .unwrap_or_default();
-
-        self.apply_cmp_with_uninitialized(Some(left_cell_data).as_ref(), None, sort_condition)
+      (Some(left_cell_data), None) => {
+        self.apply_cmp_with_uninitialized(Some(&left_cell_data), None, sort.condition)
       },
-      (Some(left_cell), Some(right_cell)) => {
-        let left_cell_data: <T as TypeOption>::CellData = self
-          .get_decoded_cell_data(left_cell, &field_type, field)
-          .unwrap_or_default();
-        let right_cell_data = self
-          .get_decoded_cell_data(right_cell, &field_type, field)
-          .unwrap_or_default();
-
-        self.apply_cmp(&left_cell_data, &right_cell_data, sort_condition)
+      (Some(left_cell_data), Some(right_cell_data)) => match sort.option_order {
+        SelectOptionOrder::Name => {
+          self.apply_cmp(&left_cell_data, &right_cell_data, sort.condition)
+        },
+        SelectOptionOrder::Position => {
+          self.apply_cmp_by_option_position(&left_cell_data, &right_cell_data, sort.condition)
+        },
       },
     }
   }
 
-  fn handle_cell_filter(&self, field_type: &FieldType, field: &Field, cell: &Cell) -> bool {
+  fn handle_cell_filter(
+    &self,
+    filter_id: &str,
+    field_type: &FieldType,
+    field: &Field,
+    cell: &Cell,
+  ) -> bool {
     let perform_filter = || {
       let filter_cache = self.cell_filter_cache.as_ref()?.read();
-      let cell_filter = filter_cache.get::<<Self as TypeOption>::CellFilter>(&field.id)?;
+      let cell_filter = filter_cache.get::<<Self as TypeOption>::CellFilter>(filter_id)?;
       let cell_data = self.get_decoded_cell_data(cell, field_type, field).ok()?;
       Some(self.apply_filter(cell_filter, field_type, &cell_data))
     };
@@ -339,6 +372,13 @@ where
     self.numeric_cell(cell)
   }
 
+  fn handle_is_cell_empty(&self, cell: &Cell, field: &Field) -> bool {
+    let field_type = FieldType::from(field.field_type);
+    self
+      .get_decoded_cell_data(cell, &field_type, field)
+      .map_or(true, |cell_data| cell_data.is_cell_empty())
+  }
+
   fn get_cell_data(
     &self,
     cell: &Cell,
@@ -490,6 +530,66 @@ impl<'a> TypeOptionCellExt<'a> {
             self.cell_data_cache.clone(),
           )
         }),
+      FieldType::Relation => self
+        .field
+        .get_type_option::<RelationTypeOption>(field_type)
+        .map(|type_option| {
+          TypeOptionCellDataHandlerImpl::new_with_boxed(
+            type_option,
+            self.cell_filter_cache.clone(),
+            self.cell_data_cache.clone(),
+          )
+        }),
+      FieldType::Lookup => self
+        .field
+        .get_type_option::<LookupTypeOption>(field_type)
+        .map(|type_option| {
+          TypeOptionCellDataHandlerImpl::new_with_boxed(
+            type_option,
+            self.cell_filter_cache.clone(),
+            self.cell_data_cache.clone(),
+          )
+        }),
+      FieldType::Formula => self
+        .field
+        .get_type_option::<FormulaTypeOption>(field_type)
+        .map(|type_option| {
+          TypeOptionCellDataHandlerImpl::new_with_boxed(
+            type_option,
+            self.cell_filter_cache.clone(),
+            self.cell_data_cache.clone(),
+          )
+        }),
+      FieldType::Person => self
+        .field
+        .get_type_option::<PersonTypeOption>(field_type)
+        .map(|type_option| {
+          TypeOptionCellDataHandlerImpl::new_with_boxed(
+            type_option,
+            self.cell_filter_cache.clone(),
+            self.cell_data_cache.clone(),
+          )
+        }),
+      FieldType::Files => self
+        .field
+        .get_type_option::<FilesTypeOption>(field_type)
+        .map(|type_option| {
+          TypeOptionCellDataHandlerImpl::new_with_boxed(
+            type_option,
+            self.cell_filter_cache.clone(),
+            self.cell_data_cache.clone(),
+          )
+        }),
+      FieldType::AutoNumber => self
+        .field
+        .get_type_option::<AutoNumberTypeOption>(field_type)
+        .map(|type_option| {
+          TypeOptionCellDataHandlerImpl::new_with_boxed(
+            type_option,
+            self.cell_filter_cache.clone(),
+            self.cell_data_cache.clone(),
+          )
+        }),
     }
   }
 }
@@ -568,6 +668,24 @@ fn get_type_option_transform_handler(
     FieldType::Checklist => {
       Box::new(ChecklistTypeOption::from(type_option_data)) as Box<dyn TypeOptionTransformHandler>
     },
+    FieldType::Relation => {
+      Box::new(RelationTypeOption::from(type_option_data)) as Box<dyn TypeOptionTransformHandler>
+    },
+    FieldType::Lookup => {
+      Box::new(LookupTypeOption::from(type_option_data)) as Box<dyn TypeOptionTransformHandler>
+    },
+    FieldType::Formula => {
+      Box::new(FormulaTypeOption::from(type_option_data)) as Box<dyn TypeOptionTransformHandler>
+    },
+    FieldType::Person => {
+      Box::new(PersonTypeOption::from(type_option_data)) as Box<dyn TypeOptionTransformHandler>
+    },
+    FieldType::Files => {
+      Box::new(FilesTypeOption::from(type_option_data)) as Box<dyn TypeOptionTransformHandler>
+    },
+    FieldType::AutoNumber => {
+      Box::new(AutoNumberTypeOption::from(type_option_data)) as Box<dyn TypeOptionTransformHandler>
+    },
   }
 }
 
@@ -622,4 +740,28 @@ impl RowSingleCellData {
     into_check_list_field_cell_data,
     <CheckboxTypeOption as TypeOption>::CellData
   );
+  into_cell_data!(
+    into_relation_field_cell_data,
+    <RelationTypeOption as TypeOption>::CellData
+  );
+  into_cell_data!(
+    into_lookup_field_cell_data,
+    <LookupTypeOption as TypeOption>::CellData
+  );
+  into_cell_data!(
+    into_formula_field_cell_data,
+    <FormulaTypeOption as TypeOption>::CellData
+  );
+  into_cell_data!(
+    into_person_field_cell_data,
+    <PersonTypeOption as TypeOption>::CellData
+  );
+  into_cell_data!(
+    into_files_field_cell_data,
+    <FilesTypeOption as TypeOption>::CellData
+  );
+  into_cell_data!(
+    into_auto_number_field_cell_data,
+    <AutoNumberTypeOption as TypeOption>::CellData
+  );
 }
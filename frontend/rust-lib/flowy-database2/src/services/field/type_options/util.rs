@@ -1,6 +1,10 @@
 use bytes::Bytes;
 use protobuf::ProtobufError;
 
+use flowy_error::FlowyResult;
+
+use crate::services::cell::{CellProtobufBlobParser, DecodedCellData};
+
 #[derive(Default, Debug, Clone)]
 pub struct ProtobufStr(pub String);
 impl std::ops::Deref for ProtobufStr {
@@ -47,3 +51,23 @@ impl AsRef<str> for ProtobufStr {
     self.0.as_str()
   }
 }
+
+impl DecodedCellData for ProtobufStr {
+  type Object = ProtobufStr;
+
+  fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+}
+
+pub struct ProtobufStrParser();
+impl CellProtobufBlobParser for ProtobufStrParser {
+  type Object = ProtobufStr;
+
+  fn parser(bytes: &Bytes) -> FlowyResult<Self::Object> {
+    match String::from_utf8(bytes.to_vec()) {
+      Ok(s) => Ok(ProtobufStr(s)),
+      Err(_) => Ok(ProtobufStr::default()),
+    }
+  }
+}
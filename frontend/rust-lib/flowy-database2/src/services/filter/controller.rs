@@ -2,9 +2,11 @@ use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::Arc;
 
+use chrono::{FixedOffset, Local, Offset, Utc};
 use collab_database::fields::Field;
 use collab_database::rows::{Cell, Row, RowDetail, RowId};
 use dashmap::DashMap;
+use parking_lot::RwLock as SyncRwLock;
 use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 
@@ -14,10 +16,12 @@ use lib_infra::priority_task::{QualityOfService, Task, TaskContent, TaskDispatch
 
 use crate::entities::filter_entities::*;
 use crate::entities::{FieldType, InsertedRowPB, RowMetaPB};
-use crate::services::cell::{CellCache, CellFilterCache};
+use crate::services::cell::{stringify_cell_data, CellCache, CellFilterCache};
 use crate::services::database_view::{DatabaseViewChanged, DatabaseViewChangedNotifier};
 use crate::services::field::*;
-use crate::services::filter::{Filter, FilterChangeset, FilterResult, FilterResultNotification};
+use crate::services::filter::{
+  Filter, FilterChangeset, FilterResult, FilterResultNotification, FilterTree, FilterType,
+};
 use crate::utils::cache::AnyTypeCache;
 
 pub trait FilterDelegate: Send + Sync + 'static {
@@ -26,6 +30,7 @@ pub trait FilterDelegate: Send + Sync + 'static {
   fn get_fields(&self, view_id: &str, field_ids: Option<Vec<String>>) -> Fut<Vec<Arc<Field>>>;
   fn get_rows(&self, view_id: &str) -> Fut<Vec<Arc<RowDetail>>>;
   fn get_row(&self, view_id: &str, rows_id: &RowId) -> Fut<Option<(usize, Arc<RowDetail>)>>;
+  fn get_current_user_email(&self) -> Option<String>;
 }
 
 pub trait FromFilterString {
@@ -39,6 +44,7 @@ pub struct FilterController {
   handler_id: String,
   delegate: Box<dyn FilterDelegate>,
   result_by_row_id: DashMap<RowId, FilterResult>,
+  filter_tree: SyncRwLock<FilterTree>,
   cell_cache: CellCache,
   cell_filter_cache: CellFilterCache,
   task_scheduler: Arc<RwLock<TaskDispatcher>>,
@@ -69,8 +75,9 @@ impl FilterController {
       handler_id: handler_id.to_string(),
       delegate: Box::new(delegate),
       result_by_row_id: DashMap::default(),
+      filter_tree: SyncRwLock::new(FilterTree::default()),
       cell_cache,
-      // Cache by field_id
+      // Cache by filter_id
       cell_filter_cache: AnyTypeCache::<String>::new(),
       task_scheduler,
       notifier,
@@ -104,11 +111,13 @@ impl FilterController {
       return;
     }
     let field_by_field_id = self.get_field_map().await;
+    let filter_tree = self.filter_tree.read().clone();
     rows.iter().for_each(|row_detail| {
       let _ = filter_row(
         &row_detail.row,
         &self.result_by_row_id,
         &field_by_field_id,
+        &filter_tree,
         &self.cell_cache,
         &self.cell_filter_cache,
       );
@@ -152,11 +161,13 @@ impl FilterController {
   async fn filter_row(&self, row_id: RowId) -> FlowyResult<()> {
     if let Some((_, row_detail)) = self.delegate.get_row(&self.view_id, &row_id).await {
       let field_by_field_id = self.get_field_map().await;
+      let filter_tree = self.filter_tree.read().clone();
       let mut notification = FilterResultNotification::new(self.view_id.clone());
       if let Some((row_id, is_visible)) = filter_row(
         &row_detail.row,
         &self.result_by_row_id,
         &field_by_field_id,
+        &filter_tree,
         &self.cell_cache,
         &self.cell_filter_cache,
       ) {
@@ -180,6 +191,7 @@ impl FilterController {
 
   async fn filter_all_rows(&self) -> FlowyResult<()> {
     let field_by_field_id = self.get_field_map().await;
+    let filter_tree = self.filter_tree.read().clone();
     let mut visible_rows = vec![];
     let mut invisible_rows = vec![];
 
@@ -194,6 +206,7 @@ impl FilterController {
         &row_detail.row,
         &self.result_by_row_id,
         &field_by_field_id,
+        &filter_tree,
         &self.cell_cache,
         &self.cell_filter_cache,
       ) {
@@ -218,6 +231,46 @@ impl FilterController {
     Ok(())
   }
 
+  /// Filters the rows again if any filter compares the dates of the cells with the current date,
+  /// which changes when the day rolls over.
+  pub async fn did_change_day(&self) {
+    let has_relative_date_filter = self
+      .filter_tree
+      .read()
+      .data_filters()
+      .any(|filter| is_relative_date_filter(filter));
+    if has_relative_date_filter {
+      self
+        .gen_task(FilterEvent::FilterDidChanged, QualityOfService::Background)
+        .await;
+    }
+  }
+
+  /// Returns the timezones in which the relative date filters are evaluated. The filters of the
+  /// [FieldType::DateTime] fields use the timezone of the field, the others the local timezone.
+  pub fn relative_date_filter_offsets(&self) -> Vec<FixedOffset> {
+    let now = Utc::now().naive_utc();
+    self
+      .filter_tree
+      .read()
+      .data_filters()
+      .filter(|filter| is_relative_date_filter(filter))
+      .map(|filter| {
+        let type_option = match filter.field_type {
+          FieldType::DateTime => self
+            .delegate
+            .get_field(&filter.field_id)
+            .and_then(|field| field.get_type_option::<DateTypeOption>(FieldType::DateTime)),
+          _ => None,
+        };
+        match type_option {
+          Some(type_option) => type_option.get_timezone_offset(now),
+          None => Local::now().offset().fix(),
+        }
+      })
+      .collect()
+  }
+
   pub async fn did_receive_row_changed(&self, row_id: RowId) {
     if !self.cell_filter_cache.read().is_empty() {
       self
@@ -283,16 +336,23 @@ impl FilterController {
     }
 
     if let Some(filter_context) = &changeset.delete_filter {
-      if let Some(filter) = self.filter_from_filter_id(&filter_context.filter_id).await {
+      // Deleting a filter group deletes all the filters in it.
+      let removed_filters = self.filter_tree.write().remove(&filter_context.filter_id);
+      if !removed_filters.is_empty() {
+        let mut cell_filter_cache = self.cell_filter_cache.write();
+        for filter in removed_filters.iter() {
+          cell_filter_cache.remove(&filter.id);
+        }
+        drop(cell_filter_cache);
+
         notification = Some(FilterChangesetNotificationPB::from_delete(
           &self.view_id,
-          vec![filter],
+          removed_filters
+            .iter()
+            .map(|filter| FilterPB::from(filter.as_ref()))
+            .collect(),
         ));
       }
-      self
-        .cell_filter_cache
-        .write()
-        .remove(&filter_context.field_id);
     }
 
     self
@@ -313,50 +373,94 @@ impl FilterController {
   #[tracing::instrument(level = "trace", skip_all)]
   async fn refresh_filters(&self, filters: Vec<Arc<Filter>>) {
     for filter in filters {
-      let field_id = &filter.field_id;
+      self.filter_tree.write().insert(filter.clone());
+      if filter.is_group() {
+        // And/Or filters only combine the results of their children.
+        self.cell_filter_cache.write().remove(&filter.id);
+        continue;
+      }
+      if filter.filter_type == FilterType::Search {
+        self
+          .cell_filter_cache
+          .write()
+          .insert(&filter.id, TextFilterPB::from_filter(filter.as_ref()));
+        continue;
+      }
+
+      let filter_id = &filter.id;
       tracing::trace!("Create filter with type: {:?}", filter.field_type);
       match &filter.field_type {
         FieldType::RichText => {
           self
             .cell_filter_cache
             .write()
-            .insert(field_id, TextFilterPB::from_filter(filter.as_ref()));
+            .insert(filter_id, TextFilterPB::from_filter(filter.as_ref()));
         },
-        FieldType::Number => {
+        FieldType::Number | FieldType::Lookup | FieldType::AutoNumber => {
           self
             .cell_filter_cache
             .write()
-            .insert(field_id, NumberFilterPB::from_filter(filter.as_ref()));
+            .insert(filter_id, NumberFilterPB::from_filter(filter.as_ref()));
         },
         FieldType::DateTime | FieldType::LastEditedTime | FieldType::CreatedTime => {
           self
             .cell_filter_cache
             .write()
-            .insert(field_id, DateFilterPB::from_filter(filter.as_ref()));
+            .insert(filter_id, DateFilterPB::from_filter(filter.as_ref()));
         },
         FieldType::SingleSelect | FieldType::MultiSelect => {
-          self
-            .cell_filter_cache
-            .write()
-            .insert(field_id, SelectOptionFilterPB::from_filter(filter.as_ref()));
+          self.cell_filter_cache.write().insert(
+            filter_id,
+            SelectOptionFilterPB::from_filter(filter.as_ref()),
+          );
         },
         FieldType::Checkbox => {
           self
             .cell_filter_cache
             .write()
-            .insert(field_id, CheckboxFilterPB::from_filter(filter.as_ref()));
+            .insert(filter_id, CheckboxFilterPB::from_filter(filter.as_ref()));
         },
         FieldType::URL => {
           self
             .cell_filter_cache
             .write()
-            .insert(field_id, TextFilterPB::from_filter(filter.as_ref()));
+            .insert(filter_id, TextFilterPB::from_filter(filter.as_ref()));
         },
         FieldType::Checklist => {
           self
             .cell_filter_cache
             .write()
-            .insert(field_id, ChecklistFilterPB::from_filter(filter.as_ref()));
+            .insert(filter_id, ChecklistFilterPB::from_filter(filter.as_ref()));
+        },
+        FieldType::Relation => {
+          self
+            .cell_filter_cache
+            .write()
+            .insert(filter_id, RelationFilterPB::from_filter(filter.as_ref()));
+        },
+        FieldType::Formula => {
+          self
+            .cell_filter_cache
+            .write()
+            .insert(filter_id, FormulaFilterPB::from_filter(filter.as_ref()));
+        },
+        FieldType::Person => {
+          let mut person_filter = PersonFilterPB::from_filter(filter.as_ref());
+          // "Is me" is resolved against the user who opens the view instead of the user who
+          // created the filter.
+          if person_filter.condition == PersonFilterConditionPB::PersonIsMe {
+            person_filter.user_ids = self.delegate.get_current_user_email().into_iter().collect();
+          }
+          self
+            .cell_filter_cache
+            .write()
+            .insert(filter_id, person_filter);
+        },
+        FieldType::Files => {
+          self
+            .cell_filter_cache
+            .write()
+            .insert(filter_id, FilesFilterPB::from_filter(filter.as_ref()));
         },
       }
     }
@@ -369,6 +473,7 @@ fn filter_row(
   row: &Row,
   result_by_row_id: &DashMap<RowId, FilterResult>,
   field_by_field_id: &HashMap<String, Arc<Field>>,
+  filter_tree: &FilterTree,
   cell_data_cache: &CellCache,
   cell_filter_cache: &CellFilterCache,
 ) -> Option<(RowId, bool)> {
@@ -376,27 +481,64 @@ fn filter_row(
   let mut filter_result = result_by_row_id.entry(row.id.clone()).or_default();
   let old_is_visible = filter_result.is_visible();
 
-  // Iterate each cell of the row to check its visibility
-  for (field_id, field) in field_by_field_id {
-    if !cell_filter_cache.read().contains(field_id) {
-      filter_result.visible_by_field_id.remove(field_id);
-      continue;
-    }
+  // Remove the results of the filters that were deleted
+  filter_result
+    .visible_by_filter_id
+    .retain(|filter_id, _| filter_tree.contains(filter_id));
+
+  // Apply each data filter to the corresponding cell of the row
+  for filter in filter_tree.data_filters() {
+    let field = match field_by_field_id.get(&filter.field_id) {
+      Some(field) if cell_filter_cache.read().contains(&filter.id) => field,
+      _ => {
+        filter_result.visible_by_filter_id.remove(&filter.id);
+        continue;
+      },
+    };
 
-    let cell = row.cells.get(field_id).cloned();
+    let cell = row.cells.get(&filter.field_id).cloned();
     let field_type = FieldType::from(field.field_type);
-    // if the visibility of the cell_rew is changed, which means the visibility of the
-    // row is changed too.
-    if let Some(is_visible) =
-      filter_cell(&field_type, field, cell, cell_data_cache, cell_filter_cache)
-    {
+    if let Some(is_visible) = filter_cell(
+      &filter.id,
+      &field_type,
+      field,
+      cell,
+      cell_data_cache,
+      cell_filter_cache,
+    ) {
       filter_result
-        .visible_by_field_id
-        .insert(field_id.to_string(), is_visible);
+        .visible_by_filter_id
+        .insert(filter.id.clone(), is_visible);
     }
   }
 
-  let is_visible = filter_result.is_visible();
+  // Apply each search filter to the text of all the searchable cells of the row
+  for filter in filter_tree.search_filters() {
+    let text_filter = match cell_filter_cache.read().get::<TextFilterPB>(&filter.id) {
+      Some(text_filter) => text_filter.clone(),
+      None => {
+        filter_result.visible_by_filter_id.remove(&filter.id);
+        continue;
+      },
+    };
+    let texts = field_by_field_id.values().filter_map(|field| {
+      let field_type = FieldType::from(field.field_type);
+      if !field_type.is_searchable() {
+        return None;
+      }
+      let cell = row.cells.get(&field.id).cloned().unwrap_or_default();
+      Some(stringify_cell_data(&cell, &field_type, &field_type, field))
+    });
+    let is_visible = text_filter.is_visible_in_any(texts);
+    filter_result
+      .visible_by_filter_id
+      .insert(filter.id.clone(), is_visible);
+  }
+
+  // The visibility of the row is decided by evaluating the tree against the result of each
+  // data and search filter.
+  let is_visible = filter_tree.is_visible(&filter_result.visible_by_filter_id);
+  filter_result.is_visible = Some(is_visible);
   if old_is_visible != is_visible {
     Some((row.id.clone(), is_visible))
   } else {
@@ -404,11 +546,25 @@ fn filter_row(
   }
 }
 
+fn is_relative_date_filter(filter: &Filter) -> bool {
+  match filter.field_type {
+    FieldType::DateTime | FieldType::LastEditedTime | FieldType::CreatedTime => {
+      DateFilterPB::from(filter).condition.is_relative()
+    },
+    FieldType::Formula => FormulaFilterPB::from(filter)
+      .date_filter
+      .map(|date_filter| date_filter.condition.is_relative())
+      .unwrap_or(false),
+    _ => false,
+  }
+}
+
 // Returns None if there is no change in this cell after applying the filter
 // Returns Some if the visibility of the cell is changed
 
 #[tracing::instrument(level = "trace", skip_all, fields(cell_content))]
 fn filter_cell(
+  filter_id: &str,
   field_type: &FieldType,
   field: &Arc<Field>,
   cell: Option<Cell>,
@@ -421,8 +577,12 @@ fn filter_cell(
     Some(cell_filter_cache.clone()),
   )
   .get_type_option_cell_data_handler(field_type)?;
-  let is_visible =
-    handler.handle_cell_filter(field_type, field.as_ref(), &cell.unwrap_or_default());
+  let is_visible = handler.handle_cell_filter(
+    filter_id,
+    field_type,
+    field.as_ref(),
+    &cell.unwrap_or_default(),
+  );
   Some(is_visible)
 }
 
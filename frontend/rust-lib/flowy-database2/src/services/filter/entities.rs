@@ -1,3 +1,6 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
 use anyhow::bail;
 use collab::core::any_map::AnyMapExtension;
 use collab_database::rows::RowId;
@@ -5,16 +8,57 @@ use collab_database::views::{FilterMap, FilterMapBuilder};
 
 use crate::entities::{FieldType, FilterPB, InsertedRowPB};
 
+/// A view's filters form a tree. [FilterType::Data] filters are the leaves that check the cell of
+/// a field, while [FilterType::And] and [FilterType::Or] filters combine their children.
+/// [FilterType::Search] filters are leaves that check the text of all the searchable fields.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FilterType {
+  #[default]
+  Data = 0,
+  And = 1,
+  Or = 2,
+  Search = 3,
+}
+
+impl From<i64> for FilterType {
+  fn from(value: i64) -> Self {
+    match value {
+      1 => FilterType::And,
+      2 => FilterType::Or,
+      3 => FilterType::Search,
+      _ => FilterType::Data,
+    }
+  }
+}
+
+impl From<FilterType> for i64 {
+  fn from(value: FilterType) -> Self {
+    value as i64
+  }
+}
+
 #[derive(Debug, Clone)]
 pub struct Filter {
   pub id: String,
+  /// The id of the And/Or filter this filter belongs to. Filters without a parent are at the
+  /// root of the tree.
+  pub parent_id: Option<String>,
+  pub filter_type: FilterType,
   pub field_id: String,
   pub field_type: FieldType,
   pub condition: i64,
   pub content: String,
 }
 
+impl Filter {
+  pub fn is_group(&self) -> bool {
+    matches!(self.filter_type, FilterType::And | FilterType::Or)
+  }
+}
+
 const FILTER_ID: &str = "id";
+const FILTER_PARENT_ID: &str = "parent_id";
+const FILTER_TYPE: &str = "filter_type";
 const FIELD_ID: &str = "field_id";
 const FIELD_TYPE: &str = "ty";
 const FILTER_CONDITION: &str = "condition";
@@ -22,13 +66,17 @@ const FILTER_CONTENT: &str = "content";
 
 impl From<Filter> for FilterMap {
   fn from(data: Filter) -> Self {
-    FilterMapBuilder::new()
+    let mut builder = FilterMapBuilder::new()
       .insert_str_value(FILTER_ID, data.id)
       .insert_str_value(FIELD_ID, data.field_id)
       .insert_str_value(FILTER_CONTENT, data.content)
       .insert_i64_value(FIELD_TYPE, data.field_type.into())
       .insert_i64_value(FILTER_CONDITION, data.condition)
-      .build()
+      .insert_i64_value(FILTER_TYPE, data.filter_type.into());
+    if let Some(parent_id) = data.parent_id {
+      builder = builder.insert_str_value(FILTER_PARENT_ID, parent_id);
+    }
+    builder.build()
   }
 }
 
@@ -47,8 +95,17 @@ impl TryFrom<FilterMap> for Filter {
           .get_i64_value(FIELD_TYPE)
           .map(FieldType::from)
           .unwrap_or_default();
+        let filter_type = filter
+          .get_i64_value(FILTER_TYPE)
+          .map(FilterType::from)
+          .unwrap_or_default();
+        let parent_id = filter
+          .get_str_value(FILTER_PARENT_ID)
+          .filter(|parent_id| !parent_id.is_empty());
         Ok(Filter {
           id,
+          parent_id,
+          filter_type,
           field_id,
           field_type,
           condition,
@@ -61,6 +118,124 @@ impl TryFrom<FilterMap> for Filter {
     }
   }
 }
+
+/// Evaluates the filters of a view as a tree. The filters at the root are combined with AND,
+/// which keeps the views that were created before filter groups existed working as before.
+#[derive(Debug, Default, Clone)]
+pub struct FilterTree {
+  filter_by_id: HashMap<String, Arc<Filter>>,
+}
+
+impl FilterTree {
+  pub fn new(filters: Vec<Arc<Filter>>) -> Self {
+    let mut tree = Self::default();
+    for filter in filters {
+      tree.insert(filter);
+    }
+    tree
+  }
+
+  pub fn insert(&mut self, filter: Arc<Filter>) {
+    self.filter_by_id.insert(filter.id.clone(), filter);
+  }
+
+  /// Removes the filter and all of its descendants. Returns the removed filters.
+  pub fn remove(&mut self, filter_id: &str) -> Vec<Arc<Filter>> {
+    let mut filter_ids = self.descendant_ids(filter_id);
+    filter_ids.push(filter_id.to_string());
+    filter_ids
+      .iter()
+      .flat_map(|filter_id| self.filter_by_id.remove(filter_id))
+      .collect()
+  }
+
+  pub fn get(&self, filter_id: &str) -> Option<&Arc<Filter>> {
+    self.filter_by_id.get(filter_id)
+  }
+
+  pub fn contains(&self, filter_id: &str) -> bool {
+    self.filter_by_id.contains_key(filter_id)
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.filter_by_id.is_empty()
+  }
+
+  /// Returns the leaf filters, which are the only ones that test cells.
+  pub fn data_filters(&self) -> impl Iterator<Item = &Arc<Filter>> {
+    self
+      .filter_by_id
+      .values()
+      .filter(|filter| filter.filter_type == FilterType::Data)
+  }
+
+  /// Returns the filters that check the text of all the searchable fields.
+  pub fn search_filters(&self) -> impl Iterator<Item = &Arc<Filter>> {
+    self
+      .filter_by_id
+      .values()
+      .filter(|filter| filter.filter_type == FilterType::Search)
+  }
+
+  pub fn descendant_ids(&self, filter_id: &str) -> Vec<String> {
+    let mut descendant_ids = vec![];
+    let mut parent_ids = vec![filter_id.to_string()];
+    while let Some(parent_id) = parent_ids.pop() {
+      for child in self.children(Some(&parent_id)) {
+        if child.id != filter_id && !descendant_ids.contains(&child.id) {
+          descendant_ids.push(child.id.clone());
+          parent_ids.push(child.id.clone());
+        }
+      }
+    }
+    descendant_ids
+  }
+
+  /// Returns true if the row passes the filters. `visible_by_filter_id` holds the result of each
+  /// data and search filter; a filter without a result doesn't hide the row.
+  pub fn is_visible(&self, visible_by_filter_id: &HashMap<String, bool>) -> bool {
+    self
+      .children(None)
+      .into_iter()
+      .all(|filter| self.evaluate(filter, visible_by_filter_id))
+  }
+
+  fn evaluate(&self, filter: &Filter, visible_by_filter_id: &HashMap<String, bool>) -> bool {
+    match filter.filter_type {
+      FilterType::Data | FilterType::Search => visible_by_filter_id
+        .get(&filter.id)
+        .copied()
+        .unwrap_or(true),
+      FilterType::And => self
+        .children(Some(&filter.id))
+        .into_iter()
+        .all(|child| self.evaluate(child, visible_by_filter_id)),
+      FilterType::Or => {
+        let children = self.children(Some(&filter.id));
+        // An empty group doesn't filter out anything.
+        children.is_empty()
+          || children
+            .into_iter()
+            .any(|child| self.evaluate(child, visible_by_filter_id))
+      },
+    }
+  }
+
+  /// Returns the children of the given parent. Filters whose parent no longer exists are treated
+  /// as root filters.
+  fn children(&self, parent_id: Option<&str>) -> Vec<&Arc<Filter>> {
+    self
+      .filter_by_id
+      .values()
+      .filter(|filter| match (filter.parent_id.as_deref(), parent_id) {
+        (Some(filter_parent_id), Some(parent_id)) => filter_parent_id == parent_id,
+        (Some(filter_parent_id), None) => !self.filter_by_id.contains_key(filter_parent_id),
+        (None, parent_id) => parent_id.is_none(),
+      })
+      .collect()
+  }
+}
+
 #[derive(Debug)]
 pub struct FilterChangeset {
   pub(crate) insert_filter: Option<Filter>,
@@ -142,3 +317,109 @@ impl FilterResultNotification {
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use std::collections::HashMap;
+  use std::sync::Arc;
+
+  use crate::entities::FieldType;
+  use crate::services::filter::{Filter, FilterTree, FilterType};
+
+  fn filter(id: &str, parent_id: Option<&str>, filter_type: FilterType) -> Arc<Filter> {
+    Arc::new(Filter {
+      id: id.to_string(),
+      parent_id: parent_id.map(|parent_id| parent_id.to_string()),
+      filter_type,
+      field_id: "".to_string(),
+      field_type: FieldType::RichText,
+      condition: 0,
+      content: "".to_string(),
+    })
+  }
+
+  fn visible_by_filter_id(results: &[(&str, bool)]) -> HashMap<String, bool> {
+    results
+      .iter()
+      .map(|(filter_id, is_visible)| (filter_id.to_string(), *is_visible))
+      .collect()
+  }
+
+  #[test]
+  fn filter_tree_or_group_test() {
+    // a AND (b OR c)
+    let tree = FilterTree::new(vec![
+      filter("a", None, FilterType::Data),
+      filter("or", None, FilterType::Or),
+      filter("b", Some("or"), FilterType::Data),
+      filter("c", Some("or"), FilterType::Data),
+    ]);
+
+    for (results, expected) in [
+      (vec![("a", true), ("b", false), ("c", true)], true),
+      (vec![("a", true), ("b", false), ("c", false)], false),
+      (vec![("a", false), ("b", true), ("c", true)], false),
+    ] {
+      assert_eq!(tree.is_visible(&visible_by_filter_id(&results)), expected);
+    }
+  }
+
+  #[test]
+  fn filter_tree_nested_group_test() {
+    // a OR (b AND c)
+    let tree = FilterTree::new(vec![
+      filter("or", None, FilterType::Or),
+      filter("a", Some("or"), FilterType::Data),
+      filter("and", Some("or"), FilterType::And),
+      filter("b", Some("and"), FilterType::Data),
+      filter("c", Some("and"), FilterType::Data),
+    ]);
+
+    for (results, expected) in [
+      (vec![("a", false), ("b", true), ("c", true)], true),
+      (vec![("a", false), ("b", true), ("c", false)], false),
+      (vec![("a", true), ("b", false), ("c", false)], true),
+    ] {
+      assert_eq!(tree.is_visible(&visible_by_filter_id(&results)), expected);
+    }
+  }
+
+  #[test]
+  fn filter_tree_remove_group_test() {
+    let mut tree = FilterTree::new(vec![
+      filter("a", None, FilterType::Data),
+      filter("or", None, FilterType::Or),
+      filter("and", Some("or"), FilterType::And),
+      filter("b", Some("and"), FilterType::Data),
+    ]);
+
+    let mut removed_ids = tree
+      .remove("or")
+      .into_iter()
+      .map(|filter| filter.id.clone())
+      .collect::<Vec<_>>();
+    removed_ids.sort();
+    assert_eq!(removed_ids, vec!["and", "b", "or"]);
+    assert!(tree.contains("a"));
+    assert_eq!(tree.data_filters().count(), 1);
+  }
+
+  #[test]
+  fn filter_tree_search_filter_test() {
+    // search OR a
+    let tree = FilterTree::new(vec![
+      filter("or", None, FilterType::Or),
+      filter("search", Some("or"), FilterType::Search),
+      filter("a", Some("or"), FilterType::Data),
+    ]);
+
+    assert_eq!(tree.search_filters().count(), 1);
+    assert_eq!(tree.data_filters().count(), 1);
+    for (results, expected) in [
+      (vec![("search", true), ("a", false)], true),
+      (vec![("search", false), ("a", false)], false),
+    ] {
+      assert_eq!(tree.is_visible(&visible_by_filter_id(&results)), expected);
+    }
+  }
+}
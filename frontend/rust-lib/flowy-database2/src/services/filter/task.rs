@@ -40,21 +40,18 @@ impl TaskHandler for FilterTaskHandler {
     })
   }
 }
-/// Refresh the filter according to the field id.
+/// The filter result of a row.
 #[derive(Default)]
 pub(crate) struct FilterResult {
-  pub(crate) visible_by_field_id: HashMap<String, bool>,
+  /// The result of each data filter, keyed by the filter id.
+  pub(crate) visible_by_filter_id: HashMap<String, bool>,
+  /// The visibility of the row computed from [Self::visible_by_filter_id] the last time the row
+  /// was filtered.
+  pub(crate) is_visible: Option<bool>,
 }
 
 impl FilterResult {
   pub(crate) fn is_visible(&self) -> bool {
-    let mut is_visible = true;
-    for visible in self.visible_by_field_id.values() {
-      if !is_visible {
-        break;
-      }
-      is_visible = *visible;
-    }
-    is_visible
+    self.is_visible.unwrap_or(true)
   }
 }
@@ -0,0 +1,216 @@
+use async_trait::async_trait;
+use collab_database::fields::{Field, TypeOptionData};
+use collab_database::rows::{Cell, Cells, Row, RowDetail};
+use serde::{Deserialize, Serialize};
+
+use flowy_error::FlowyResult;
+
+use crate::entities::{GroupPB, GroupRowsNotificationPB, InsertedRowPB, RowMetaPB};
+use crate::services::field::checklist_type_option::{
+  ChecklistCellData, ChecklistCellDataParser, ChecklistTypeOption,
+};
+use crate::services::field::TypeOption;
+use crate::services::group::action::GroupCustomize;
+use crate::services::group::configuration::GroupContext;
+use crate::services::group::controller::{BaseGroupController, GroupController};
+use crate::services::group::{
+  move_group_row, GeneratedGroupConfig, GeneratedGroups, Group, GroupOperationInterceptor,
+  GroupsBuilder, MoveGroupRowContext,
+};
+
+/// The rows whose checklist has some of its items selected.
+pub const IN_PROGRESS: &str = "in_progress";
+/// The rows whose checklist has all of its items selected.
+pub const DONE: &str = "done";
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct ChecklistGroupConfiguration {
+  pub hide_empty: bool,
+}
+
+pub type ChecklistGroupController = BaseGroupController<
+  ChecklistGroupConfiguration,
+  ChecklistTypeOption,
+  ChecklistGroupBuilder,
+  ChecklistCellDataParser,
+  ChecklistGroupOperationInterceptorImpl,
+>;
+
+pub type ChecklistGroupContext = GroupContext<ChecklistGroupConfiguration>;
+
+impl GroupCustomize for ChecklistGroupController {
+  type GroupTypeOption = ChecklistTypeOption;
+
+  fn placeholder_cell(&self) -> Option<Cell> {
+    Some(Cell::from(ChecklistCellData::default()))
+  }
+
+  fn can_group(
+    &self,
+    content: &str,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellData,
+  ) -> bool {
+    group_id(cell_data.selected_options().len(), cell_data.options.len()) == Some(content)
+  }
+
+  fn add_or_remove_row_when_cell_changed(
+    &mut self,
+    row_detail: &RowDetail,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellProtobufType,
+  ) -> Vec<GroupRowsNotificationPB> {
+    let mut changesets = vec![];
+    let group_id = group_id(cell_data.selected_options.len(), cell_data.options.len());
+    self.context.iter_mut_status_groups(|group| {
+      let mut changeset = GroupRowsNotificationPB::new(group.id.clone());
+      if group_id == Some(group.id.as_str()) {
+        if !group.contains_row(&row_detail.row.id) {
+          changeset
+            .inserted_rows
+            .push(InsertedRowPB::new(RowMetaPB::from(row_detail)));
+          group.add_row(row_detail.clone());
+        }
+      } else if group.contains_row(&row_detail.row.id) {
+        group.remove_row(&row_detail.row.id);
+        changeset
+          .deleted_rows
+          .push(row_detail.row.id.clone().into_inner());
+      }
+
+      if !changeset.is_empty() {
+        changesets.push(changeset);
+      }
+    });
+    changesets
+  }
+
+  fn delete_row(
+    &mut self,
+    row: &Row,
+    _cell_data: &<Self::GroupTypeOption as TypeOption>::CellData,
+  ) -> (Option<GroupPB>, Vec<GroupRowsNotificationPB>) {
+    let mut changesets = vec![];
+    self.context.iter_mut_groups(|group| {
+      let mut changeset = GroupRowsNotificationPB::new(group.id.clone());
+      if group.contains_row(&row.id) {
+        changeset.deleted_rows.push(row.id.clone().into_inner());
+        group.remove_row(&row.id);
+      }
+
+      if !changeset.is_empty() {
+        changesets.push(changeset);
+      }
+    });
+    (None, changesets)
+  }
+
+  fn move_row(
+    &mut self,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellProtobufType,
+    mut context: MoveGroupRowContext,
+  ) -> Vec<GroupRowsNotificationPB> {
+    // A row can't be moved to the in progress group, because there is no way to tell which items
+    // should be selected. It can't be moved to the done group either if its checklist is empty.
+    let is_moved_to_other_group = self
+      .context
+      .get_group(context.to_group_id)
+      .map(|(_, group)| !group.contains_row(&context.row_detail.row.id))
+      .unwrap_or(false);
+    if is_moved_to_other_group
+      && (context.to_group_id == IN_PROGRESS
+        || (context.to_group_id == DONE && cell_data.options.is_empty()))
+    {
+      return vec![];
+    }
+
+    let mut group_changeset = vec![];
+    self.context.iter_mut_groups(|group| {
+      if let Some(changeset) = move_group_row(group, &mut context) {
+        group_changeset.push(changeset);
+      }
+    });
+    group_changeset
+  }
+
+  fn delete_group_custom(&mut self, _group_id: &str) -> FlowyResult<Option<TypeOptionData>> {
+    Ok(None)
+  }
+}
+
+impl GroupController for ChecklistGroupController {
+  fn did_update_field_type_option(&mut self, _field: &Field) {}
+
+  fn will_create_row(&mut self, _cells: &mut Cells, _field: &Field, _group_id: &str) {
+    // A new row has no checklist items, so it always starts in the not started group.
+  }
+}
+
+pub struct ChecklistGroupBuilder();
+#[async_trait]
+impl GroupsBuilder for ChecklistGroupBuilder {
+  type Context = ChecklistGroupContext;
+  type GroupTypeOption = ChecklistTypeOption;
+
+  async fn build(
+    field: &Field,
+    _context: &Self::Context,
+    _type_option: &Self::GroupTypeOption,
+  ) -> GeneratedGroups {
+    let in_progress_group = GeneratedGroupConfig {
+      group: Group::new(IN_PROGRESS.to_string(), "In progress".to_string()),
+      filter_content: IN_PROGRESS.to_string(),
+    };
+
+    let done_group = GeneratedGroupConfig {
+      group: Group::new(DONE.to_string(), "Done".to_string()),
+      filter_content: DONE.to_string(),
+    };
+
+    // The rows without any selected item are in the no status group.
+    let not_started_group = Group::new(field.id.clone(), "Not started".to_string());
+    GeneratedGroups {
+      no_status_group: Some(not_started_group),
+      group_configs: vec![in_progress_group, done_group],
+    }
+  }
+}
+
+/// Returns the id of the group of the checklist, or None if none of its items is selected.
+fn group_id(selected_count: usize, count: usize) -> Option<&'static str> {
+  if selected_count == 0 {
+    None
+  } else if selected_count < count {
+    Some(IN_PROGRESS)
+  } else {
+    Some(DONE)
+  }
+}
+
+/// Returns the checklist cell of the `row` once it's moved to the group. All the items are
+/// selected when it's moved to the done group, and none of them when it's moved to the not
+/// started group.
+pub(crate) fn make_moved_checklist_cell(group_id: &str, field: &Field, row: &Row) -> Option<Cell> {
+  let mut cell_data = row
+    .cells
+    .get(&field.id)
+    .map(ChecklistCellData::from)
+    .unwrap_or_default();
+  if group_id == DONE {
+    cell_data.selected_option_ids = cell_data
+      .options
+      .iter()
+      .map(|option| option.id.clone())
+      .collect();
+  } else if group_id == field.id {
+    cell_data.selected_option_ids.clear();
+  } else {
+    return None;
+  }
+  Some(Cell::from(cell_data))
+}
+
+pub struct ChecklistGroupOperationInterceptorImpl {}
+
+#[async_trait]
+impl GroupOperationInterceptor for ChecklistGroupOperationInterceptorImpl {
+  type GroupTypeOption = ChecklistTypeOption;
+}
@@ -0,0 +1,451 @@
+use async_trait::async_trait;
+use collab_database::fields::{Field, TypeOptionData};
+use collab_database::rows::{new_cell_builder, Cell, Cells, Row, RowDetail};
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+
+use flowy_error::FlowyResult;
+
+use crate::entities::{
+  FieldType, GroupPB, GroupRowsNotificationPB, InsertedGroupPB, InsertedRowPB, RowMetaPB,
+};
+use crate::services::cell::apply_cell_changeset;
+use crate::services::field::{
+  NumberCellFormat, NumberFormat, NumberTypeOption, ProtobufStrParser, TypeOption,
+};
+use crate::services::group::action::GroupCustomize;
+use crate::services::group::configuration::GroupContext;
+use crate::services::group::controller::{BaseGroupController, GroupController};
+use crate::services::group::{
+  make_no_status_group, move_group_row, GeneratedGroupConfig, GeneratedGroups, Group,
+  GroupConfigurationContentSerde, GroupOperationInterceptor, GroupsBuilder, MoveGroupRowContext,
+};
+
+/// Groups the numbers into buckets. By default, the buckets are evenly sized and start at `start`.
+/// If the `boundaries` are not empty, they are used instead, e.g. `[0, 100]` makes the
+/// `< 0`, `0 - 100` and `≥ 100` buckets.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct NumberGroupConfiguration {
+  pub hide_empty: bool,
+  pub start: f64,
+  pub bucket_size: f64,
+  pub boundaries: Vec<f64>,
+}
+
+impl Default for NumberGroupConfiguration {
+  fn default() -> Self {
+    Self {
+      hide_empty: false,
+      start: 0.0,
+      bucket_size: 10.0,
+      boundaries: vec![],
+    }
+  }
+}
+
+impl GroupConfigurationContentSerde for NumberGroupConfiguration {
+  fn from_json(s: &str) -> Result<Self, serde_json::Error> {
+    serde_json::from_str(s)
+  }
+  fn to_json(&self) -> Result<String, serde_json::Error> {
+    serde_json::to_string(self)
+  }
+}
+
+impl NumberGroupConfiguration {
+  /// Returns the lower and upper bounds of the bucket that contains the `number`. The lower bound
+  /// is inclusive and the upper bound is exclusive.
+  fn bucket(&self, number: f64) -> (Option<f64>, Option<f64>) {
+    if self.boundaries.is_empty() {
+      let bucket_size = if self.bucket_size > 0.0 {
+        self.bucket_size
+      } else {
+        NumberGroupConfiguration::default().bucket_size
+      };
+      let lower = self.start + ((number - self.start) / bucket_size).floor() * bucket_size;
+      return (Some(lower), Some(lower + bucket_size));
+    }
+
+    let mut boundaries = self.boundaries.clone();
+    boundaries.sort_by(|a, b| a.total_cmp(b));
+    match boundaries.iter().position(|boundary| number < *boundary) {
+      Some(0) => (None, Some(boundaries[0])),
+      Some(index) => (Some(boundaries[index - 1]), Some(boundaries[index])),
+      None => (boundaries.last().copied(), None),
+    }
+  }
+}
+
+pub type NumberGroupController = BaseGroupController<
+  NumberGroupConfiguration,
+  NumberTypeOption,
+  NumberGroupBuilder,
+  ProtobufStrParser,
+  NumberGroupOperationInterceptorImpl,
+>;
+
+pub type NumberGroupContext = GroupContext<NumberGroupConfiguration>;
+
+impl GroupCustomize for NumberGroupController {
+  type GroupTypeOption = NumberTypeOption;
+
+  fn placeholder_cell(&self) -> Option<Cell> {
+    Some(
+      new_cell_builder(FieldType::Number)
+        .insert_str_value("data", "")
+        .build(),
+    )
+  }
+
+  fn can_group(
+    &self,
+    content: &str,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellData,
+  ) -> bool {
+    group_id(&cell_data.0, &self.context.get_setting_content()).as_deref() == Some(content)
+  }
+
+  fn create_or_delete_group_when_cell_changed(
+    &mut self,
+    row_detail: &RowDetail,
+    old_cell_data: Option<&<Self::GroupTypeOption as TypeOption>::CellProtobufType>,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellProtobufType,
+  ) -> FlowyResult<(Option<InsertedGroupPB>, Option<GroupPB>)> {
+    let setting_content = self.context.get_setting_content();
+    let new_group_id = group_id(cell_data, &setting_content);
+    let mut inserted_group = None;
+    if let Some(group_id) = new_group_id.clone() {
+      if self.context.get_group(&group_id).is_none() {
+        let group = make_group_from_id(group_id);
+        let mut new_group = self.context.add_new_group(group)?;
+        new_group.group.rows.push(RowMetaPB::from(row_detail));
+        inserted_group = Some(new_group);
+      }
+    }
+
+    // Delete the old group if the row was the only one in it
+    let deleted_group = match old_cell_data
+      .and_then(|old_cell_data| group_id(old_cell_data, &setting_content))
+      .filter(|old_group_id| Some(old_group_id) != new_group_id.as_ref())
+      .and_then(|old_group_id| self.context.get_group(&old_group_id))
+    {
+      Some((_, group)) if group.rows.len() == 1 => Some(group.clone()),
+      _ => None,
+    };
+
+    let deleted_group = match deleted_group {
+      None => None,
+      Some(group) => {
+        self.context.delete_group(&group.id)?;
+        Some(GroupPB::from(group))
+      },
+    };
+
+    Ok((inserted_group, deleted_group))
+  }
+
+  fn add_or_remove_row_when_cell_changed(
+    &mut self,
+    row_detail: &RowDetail,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellProtobufType,
+  ) -> Vec<GroupRowsNotificationPB> {
+    let mut changesets = vec![];
+    let group_id = group_id(cell_data, &self.context.get_setting_content());
+    self.context.iter_mut_status_groups(|group| {
+      let mut changeset = GroupRowsNotificationPB::new(group.id.clone());
+      if group_id.as_ref() == Some(&group.id) {
+        if !group.contains_row(&row_detail.row.id) {
+          changeset
+            .inserted_rows
+            .push(InsertedRowPB::new(RowMetaPB::from(row_detail)));
+          group.add_row(row_detail.clone());
+        }
+      } else if group.contains_row(&row_detail.row.id) {
+        group.remove_row(&row_detail.row.id);
+        changeset
+          .deleted_rows
+          .push(row_detail.row.id.clone().into_inner());
+      }
+
+      if !changeset.is_empty() {
+        changesets.push(changeset);
+      }
+    });
+    changesets
+  }
+
+  fn delete_row(
+    &mut self,
+    row: &Row,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellData,
+  ) -> (Option<GroupPB>, Vec<GroupRowsNotificationPB>) {
+    let mut changesets = vec![];
+    self.context.iter_mut_groups(|group| {
+      let mut changeset = GroupRowsNotificationPB::new(group.id.clone());
+      if group.contains_row(&row.id) {
+        group.remove_row(&row.id);
+        changeset.deleted_rows.push(row.id.clone().into_inner());
+      }
+
+      if !changeset.is_empty() {
+        changesets.push(changeset);
+      }
+    });
+
+    let deleted_group = match group_id(&cell_data.0, &self.context.get_setting_content())
+      .and_then(|group_id| self.context.get_group(&group_id))
+    {
+      Some((_, group)) if group.rows.len() == 1 => Some(group.clone()),
+      _ => None,
+    };
+
+    let deleted_group = deleted_group.map(|group| {
+      let _ = self.context.delete_group(&group.id);
+      group.into()
+    });
+
+    (deleted_group, changesets)
+  }
+
+  fn move_row(
+    &mut self,
+    _cell_data: &<Self::GroupTypeOption as TypeOption>::CellProtobufType,
+    mut context: MoveGroupRowContext,
+  ) -> Vec<GroupRowsNotificationPB> {
+    let mut group_changeset = vec![];
+    self.context.iter_mut_groups(|group| {
+      if let Some(changeset) = move_group_row(group, &mut context) {
+        group_changeset.push(changeset);
+      }
+    });
+    group_changeset
+  }
+
+  fn delete_group_when_move_row(
+    &mut self,
+    _row: &Row,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellProtobufType,
+  ) -> Option<GroupPB> {
+    let deleted_group = match group_id(cell_data, &self.context.get_setting_content())
+      .and_then(|group_id| self.context.get_group(&group_id))
+    {
+      Some((_, group)) if group.rows.len() == 1 => Some(GroupPB::from(group.clone())),
+      _ => None,
+    };
+    if let Some(deleted_group) = deleted_group.as_ref() {
+      let _ = self.context.delete_group(&deleted_group.group_id);
+    }
+    deleted_group
+  }
+
+  fn delete_group_custom(&mut self, group_id: &str) -> FlowyResult<Option<TypeOptionData>> {
+    self.context.delete_group(group_id)?;
+    Ok(None)
+  }
+}
+
+impl GroupController for NumberGroupController {
+  fn did_update_field_type_option(&mut self, _field: &Field) {}
+
+  fn will_create_row(&mut self, cells: &mut Cells, field: &Field, group_id: &str) {
+    match self.context.get_group(group_id) {
+      None => tracing::warn!("Can not find the group: {}", group_id),
+      Some((_, group)) => {
+        if let Some(cell) = make_number_cell(&group.id, field) {
+          cells.insert(field.id.clone(), cell);
+        }
+      },
+    }
+  }
+}
+
+pub struct NumberGroupBuilder();
+#[async_trait]
+impl GroupsBuilder for NumberGroupBuilder {
+  type Context = NumberGroupContext;
+  type GroupTypeOption = NumberTypeOption;
+
+  async fn build(
+    field: &Field,
+    context: &Self::Context,
+    _type_option: &Self::GroupTypeOption,
+  ) -> GeneratedGroups {
+    // Read all the cells for the grouping field
+    let cells = context.get_all_cells().await;
+    let setting_content = context.get_setting_content();
+
+    // Generate the groups, ordered by their lower bound
+    let mut group_ids = cells
+      .into_iter()
+      .flat_map(|value| value.into_number_field_cell_data())
+      .flat_map(|cell| group_id(&cell.0, &setting_content))
+      .collect::<Vec<String>>();
+    group_ids.sort_by(|a, b| {
+      let (a, b) = (lower_bound(a), lower_bound(b));
+      a.total_cmp(&b)
+    });
+    group_ids.dedup();
+
+    let group_configs = group_ids
+      .into_iter()
+      .map(|group_id| GeneratedGroupConfig {
+        filter_content: group_id.clone(),
+        group: make_group_from_id(group_id),
+      })
+      .collect();
+
+    let no_status_group = Some(make_no_status_group(field));
+    GeneratedGroups {
+      no_status_group,
+      group_configs,
+    }
+  }
+}
+
+/// Returns the id of the group that contains the number, or None if the cell is empty. The id is
+/// made of the bounds of the bucket, e.g. `0..10`, `..0` or `100..`.
+fn group_id(cell_str: &str, setting_content: &str) -> Option<String> {
+  let number = number_from_cell_str(cell_str)?;
+  let config = NumberGroupConfiguration::from_json(setting_content).unwrap_or_default();
+  let (lower, upper) = config.bucket(number);
+  Some(format!(
+    "{}..{}",
+    lower.map(format_bound).unwrap_or_default(),
+    upper.map(format_bound).unwrap_or_default()
+  ))
+}
+
+fn number_from_cell_str(cell_str: &str) -> Option<f64> {
+  let cell_format = NumberCellFormat::from_format_str(cell_str, &NumberFormat::Num).ok()?;
+  cell_format.decimal().as_ref()?.to_f64()
+}
+
+/// Rounds off the floating point errors of the bounds, so that `0.1 * 3` is displayed as `0.3`.
+fn format_bound(bound: f64) -> String {
+  let bound = (bound * 1e10).round() / 1e10;
+  if bound == 0.0 {
+    // Avoid displaying `-0`
+    return "0".to_string();
+  }
+  bound.to_string()
+}
+
+fn bounds_from_group_id(group_id: &str) -> Option<(Option<f64>, Option<f64>)> {
+  let (lower, upper) = group_id.split_once("..")?;
+  let parse = |bound: &str| -> Option<Option<f64>> {
+    if bound.is_empty() {
+      Some(None)
+    } else {
+      bound.parse::<f64>().ok().map(Some)
+    }
+  };
+  Some((parse(lower)?, parse(upper)?))
+}
+
+fn lower_bound(group_id: &str) -> f64 {
+  bounds_from_group_id(group_id)
+    .and_then(|(lower, _)| lower)
+    .unwrap_or(f64::NEG_INFINITY)
+}
+
+fn group_name_from_id(group_id: &str) -> String {
+  match bounds_from_group_id(group_id) {
+    Some((Some(lower), Some(upper))) => {
+      format!("{} - {}", format_bound(lower), format_bound(upper))
+    },
+    Some((None, Some(upper))) => format!("< {}", format_bound(upper)),
+    Some((Some(lower), None)) => format!("≥ {}", format_bound(lower)),
+    _ => group_id.to_string(),
+  }
+}
+
+fn make_group_from_id(group_id: String) -> Group {
+  let group_name = group_name_from_id(&group_id);
+  Group::new(group_id, group_name)
+}
+
+/// Returns the number that is written in the cell when a row is moved to the group. It's the
+/// lower bound of the bucket, or the number just below the upper bound if the bucket has no lower
+/// bound.
+fn number_from_group_id(group_id: &str) -> Option<f64> {
+  match bounds_from_group_id(group_id)? {
+    (Some(lower), _) => Some(lower),
+    (None, Some(upper)) => Some(upper - 1.0),
+    (None, None) => None,
+  }
+}
+
+/// Returns the cell of the number field for the group. The cell is cleared if the group is the no
+/// status group.
+pub(crate) fn make_number_cell(group_id: &str, field: &Field) -> Option<Cell> {
+  let changeset = if group_id == field.id {
+    String::new()
+  } else {
+    format_bound(number_from_group_id(group_id)?)
+  };
+  apply_cell_changeset(changeset, None, field, None).ok()
+}
+
+pub struct NumberGroupOperationInterceptorImpl {}
+
+#[async_trait]
+impl GroupOperationInterceptor for NumberGroupOperationInterceptorImpl {
+  type GroupTypeOption = NumberTypeOption;
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::services::group::controller_impls::number_controller::{
+    group_id, group_name_from_id, number_from_group_id,
+  };
+
+  #[test]
+  fn number_group_id_name_test() {
+    let tests = vec![
+      ("5", "", Some("0..10"), "0 - 10"),
+      ("10", "", Some("10..20"), "10 - 20"),
+      ("-0.5", "", Some("-10..0"), "-10 - 0"),
+      ("$1,234.5", "", Some("1230..1240"), "1230 - 1240"),
+      (
+        "0.35",
+        r#"{"bucket_size": 0.1}"#,
+        Some("0.3..0.4"),
+        "0.3 - 0.4",
+      ),
+      (
+        "7",
+        r#"{"start": 5, "bucket_size": 5}"#,
+        Some("5..10"),
+        "5 - 10",
+      ),
+      ("-1", r#"{"boundaries": [100, 0]}"#, Some("..0"), "< 0"),
+      (
+        "50",
+        r#"{"boundaries": [100, 0]}"#,
+        Some("0..100"),
+        "0 - 100",
+      ),
+      ("100", r#"{"boundaries": [100, 0]}"#, Some("100.."), "≥ 100"),
+      ("", "", None, ""),
+    ];
+
+    for (i, (cell_str, setting_content, exp_group_id, exp_group_name)) in
+      tests.into_iter().enumerate()
+    {
+      let group_id = group_id(cell_str, setting_content);
+      assert_eq!(group_id.as_deref(), exp_group_id, "test {}", i);
+      if let Some(group_id) = group_id {
+        assert_eq!(group_name_from_id(&group_id), exp_group_name, "test {}", i);
+      }
+    }
+  }
+
+  #[test]
+  fn number_from_group_id_test() {
+    assert_eq!(number_from_group_id("0..10"), Some(0.0));
+    assert_eq!(number_from_group_id("..0"), Some(-1.0));
+    assert_eq!(number_from_group_id("100.."), Some(100.0));
+    assert_eq!(number_from_group_id("abc"), None);
+  }
+}
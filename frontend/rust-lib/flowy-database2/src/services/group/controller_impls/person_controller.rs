@@ -0,0 +1,230 @@
+use async_trait::async_trait;
+use collab_database::fields::{Field, TypeOptionData};
+use collab_database::rows::{new_cell_builder, Cell, Cells, Row, RowDetail};
+use serde::{Deserialize, Serialize};
+
+use flowy_error::FlowyResult;
+
+use crate::entities::{
+  FieldType, GroupPB, GroupRowsNotificationPB, InsertedGroupPB, InsertedRowPB, PersonCellDataPB,
+  RowMetaPB,
+};
+use crate::services::cell::insert_person_cell;
+use crate::services::field::{
+  Person, PersonCellData, PersonCellDataParser, PersonTypeOption, TypeOption,
+};
+use crate::services::group::action::GroupCustomize;
+use crate::services::group::controller::{BaseGroupController, GroupController};
+use crate::services::group::{
+  make_no_status_group, move_group_row, GeneratedGroupConfig, GeneratedGroups, Group,
+  GroupChangeset, GroupContext, GroupData, GroupOperationInterceptor, GroupsBuilder,
+  MoveGroupRowContext,
+};
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct PersonGroupConfiguration {
+  pub hide_empty: bool,
+}
+
+pub type PersonGroupContext = GroupContext<PersonGroupConfiguration>;
+
+/// Groups the rows by the persons assigned to them. Each person saved in the type option is a
+/// group, and a row assigned to several persons shows up in each of their groups.
+pub type PersonGroupController = BaseGroupController<
+  PersonGroupConfiguration,
+  PersonTypeOption,
+  PersonGroupBuilder,
+  PersonCellDataParser,
+  PersonGroupOperationInterceptorImpl,
+>;
+
+impl GroupCustomize for PersonGroupController {
+  type GroupTypeOption = PersonTypeOption;
+
+  fn can_group(
+    &self,
+    content: &str,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellData,
+  ) -> bool {
+    cell_data.contains(content)
+  }
+
+  fn placeholder_cell(&self) -> Option<Cell> {
+    Some(
+      new_cell_builder(FieldType::Person)
+        .insert_str_value("data", "")
+        .build(),
+    )
+  }
+
+  fn add_or_remove_row_when_cell_changed(
+    &mut self,
+    row_detail: &RowDetail,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellProtobufType,
+  ) -> Vec<GroupRowsNotificationPB> {
+    let mut changesets = vec![];
+    self.context.iter_mut_status_groups(|group| {
+      if let Some(changeset) = add_or_remove_person_row(group, cell_data, row_detail) {
+        changesets.push(changeset);
+      }
+    });
+    changesets
+  }
+
+  fn delete_row(
+    &mut self,
+    row: &Row,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellData,
+  ) -> (Option<GroupPB>, Vec<GroupRowsNotificationPB>) {
+    let mut changesets = vec![];
+    self.context.iter_mut_status_groups(|group| {
+      if let Some(changeset) = remove_person_row(group, cell_data, row) {
+        changesets.push(changeset);
+      }
+    });
+    (None, changesets)
+  }
+
+  fn move_row(
+    &mut self,
+    _cell_data: &<Self::GroupTypeOption as TypeOption>::CellProtobufType,
+    mut context: MoveGroupRowContext,
+  ) -> Vec<GroupRowsNotificationPB> {
+    let mut group_changeset = vec![];
+    self.context.iter_mut_groups(|group| {
+      if let Some(changeset) = move_group_row(group, &mut context) {
+        group_changeset.push(changeset);
+      }
+    });
+    group_changeset
+  }
+
+  fn generate_new_group(
+    &mut self,
+    _name: String,
+  ) -> FlowyResult<(Option<TypeOptionData>, Option<InsertedGroupPB>)> {
+    // The groups are the members of the workspace. A new group is created when a member that is
+    // not in the type option yet is assigned to a cell.
+    Ok((None, None))
+  }
+
+  fn delete_group_custom(&mut self, group_id: &str) -> FlowyResult<Option<TypeOptionData>> {
+    if self.type_option.get_person(group_id).is_some() {
+      let mut new_type_option = self.type_option.clone();
+      new_type_option.delete_person(group_id);
+      Ok(Some(new_type_option.into()))
+    } else {
+      Ok(None)
+    }
+  }
+}
+
+impl GroupController for PersonGroupController {
+  fn did_update_field_type_option(&mut self, _field: &Field) {}
+
+  fn will_create_row(&mut self, cells: &mut Cells, field: &Field, group_id: &str) {
+    match self.context.get_group(group_id) {
+      None => tracing::warn!("Can not find the group: {}", group_id),
+      Some((_, group)) => {
+        let cell = insert_person_cell(vec![group.id.clone()], field);
+        cells.insert(field.id.clone(), cell);
+      },
+    }
+  }
+}
+
+pub struct PersonGroupBuilder;
+#[async_trait]
+impl GroupsBuilder for PersonGroupBuilder {
+  type Context = PersonGroupContext;
+  type GroupTypeOption = PersonTypeOption;
+
+  async fn build(
+    field: &Field,
+    _context: &Self::Context,
+    type_option: &Self::GroupTypeOption,
+  ) -> GeneratedGroups {
+    let group_configs = type_option
+      .persons
+      .iter()
+      .map(|person| GeneratedGroupConfig {
+        group: Group::new(person.id.clone(), person.display_name().to_owned()),
+        filter_content: person.id.clone(),
+      })
+      .collect();
+
+    GeneratedGroups {
+      no_status_group: Some(make_no_status_group(field)),
+      group_configs,
+    }
+  }
+}
+
+pub struct PersonGroupOperationInterceptorImpl;
+
+#[async_trait]
+impl GroupOperationInterceptor for PersonGroupOperationInterceptorImpl {
+  type GroupTypeOption = PersonTypeOption;
+
+  #[tracing::instrument(level = "trace", skip_all)]
+  async fn type_option_from_group_changeset(
+    &self,
+    changeset: &GroupChangeset,
+    type_option: &Self::GroupTypeOption,
+    _view_id: &str,
+  ) -> Option<TypeOptionData> {
+    let name = changeset.name.as_ref()?;
+    let person = type_option.get_person(&changeset.group_id)?;
+    let mut new_type_option = type_option.clone();
+    new_type_option.insert_person(Person {
+      name: name.to_owned(),
+      ..person.clone()
+    });
+    Some(new_type_option.into())
+  }
+}
+
+fn add_or_remove_person_row(
+  group: &mut GroupData,
+  cell_data: &PersonCellDataPB,
+  row_detail: &RowDetail,
+) -> Option<GroupRowsNotificationPB> {
+  let mut changeset = GroupRowsNotificationPB::new(group.id.clone());
+  if cell_data.user_ids.contains(&group.id) {
+    if !group.contains_row(&row_detail.row.id) {
+      changeset
+        .inserted_rows
+        .push(InsertedRowPB::new(RowMetaPB::from(row_detail)));
+      group.add_row(row_detail.clone());
+    }
+  } else if group.contains_row(&row_detail.row.id) {
+    group.remove_row(&row_detail.row.id);
+    changeset
+      .deleted_rows
+      .push(row_detail.row.id.clone().into_inner());
+  }
+
+  if changeset.is_empty() {
+    None
+  } else {
+    Some(changeset)
+  }
+}
+
+fn remove_person_row(
+  group: &mut GroupData,
+  cell_data: &PersonCellData,
+  row: &Row,
+) -> Option<GroupRowsNotificationPB> {
+  let mut changeset = GroupRowsNotificationPB::new(group.id.clone());
+  if cell_data.contains(&group.id) && group.contains_row(&row.id) {
+    group.remove_row(&row.id);
+    changeset.deleted_rows.push(row.id.clone().into_inner());
+  }
+
+  if changeset.is_empty() {
+    None
+  } else {
+    Some(changeset)
+  }
+}
@@ -6,10 +6,14 @@ use crate::entities::{
   FieldType, GroupRowsNotificationPB, InsertedRowPB, RowMetaPB, SelectOptionCellDataPB,
 };
 use crate::services::cell::{
-  insert_checkbox_cell, insert_date_cell, insert_select_option_cell, insert_url_cell,
+  insert_checkbox_cell, insert_date_cell, insert_person_cell, insert_select_option_cell,
+  insert_text_cell, insert_url_cell,
+};
+use crate::services::field::{DateCellData, SelectOption, SelectOptionIds, CHECK};
+use crate::services::group::{
+  make_moved_checklist_cell, make_number_cell, GeneratedGroupConfig, Group, GroupData,
+  MoveGroupRowContext,
 };
-use crate::services::field::{SelectOption, SelectOptionIds, CHECK};
-use crate::services::group::{GeneratedGroupConfig, Group, GroupData, MoveGroupRowContext};
 
 pub fn add_or_remove_select_option_row(
   group: &mut GroupData,
@@ -133,7 +137,7 @@ pub fn move_group_row(
     // If the from_index is none which means the row is not belong to this group before and
     // it is moved from other groups.
     if from_index.is_none() {
-      let cell = make_inserted_cell(&group.id, field);
+      let cell = make_moved_cell(&group.id, field, &row_detail.row);
       if let Some(cell) = cell {
         tracing::debug!(
           "Update content of the cell in the row:{} to group:{}",
@@ -179,6 +183,17 @@ pub fn make_inserted_cell(group_id: &str, field: &Field) -> Option<Cell> {
       let cell = insert_date_cell(date.timestamp(), None, field);
       Some(cell)
     },
+    FieldType::Person => {
+      let cell = insert_person_cell(vec![group_id.to_owned()], field);
+      Some(cell)
+    },
+    FieldType::Number => make_number_cell(group_id, field),
+    FieldType::RichText => {
+      // The id of the no status group is the id of the field
+      let text = if group_id == field.id { "" } else { group_id };
+      let cell = insert_text_cell(text.to_owned(), field);
+      Some(cell)
+    },
     _ => {
       tracing::warn!("Unknown field type: {:?}", field_type);
       None
@@ -186,6 +201,38 @@ pub fn make_inserted_cell(group_id: &str, field: &Field) -> Option<Cell> {
   }
 }
 
+/// Returns the cell of the `row` once it's moved to the group. The date ranges are grouped by
+/// their start, so only the start is moved to the date of the group and the range keeps its
+/// duration. The checklists keep their items, only the selected ones change.
+fn make_moved_cell(group_id: &str, field: &Field, row: &Row) -> Option<Cell> {
+  if FieldType::from(field.field_type) == FieldType::Checklist {
+    return make_moved_checklist_cell(group_id, field, row);
+  }
+
+  let cell = make_inserted_cell(group_id, field)?;
+  if FieldType::from(field.field_type) != FieldType::DateTime {
+    return Some(cell);
+  }
+
+  let old_cell_data = row.cells.get(&field.id).map(DateCellData::from);
+  match old_cell_data {
+    Some(old_cell_data) if old_cell_data.is_range => {
+      let start = DateCellData::from(&cell).timestamp;
+      let end_timestamp = match (start, old_cell_data.timestamp, old_cell_data.end_timestamp) {
+        (Some(start), Some(old_start), Some(old_end)) => Some(start + old_end - old_start),
+        _ => start,
+      };
+      let cell_data = DateCellData {
+        timestamp: start,
+        end_timestamp,
+        ..old_cell_data
+      };
+      Some(Cell::from(&cell_data))
+    },
+    _ => Some(cell),
+  }
+}
+
 pub fn generate_select_option_groups(
   _field_id: &str,
   options: &[SelectOption],
@@ -0,0 +1,350 @@
+use async_trait::async_trait;
+use collab_database::fields::{Field, TypeOptionData};
+use collab_database::rows::{new_cell_builder, Cell, Cells, Row, RowDetail};
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+use flowy_error::FlowyResult;
+
+use crate::entities::{
+  FieldType, GroupPB, GroupRowsNotificationPB, InsertedGroupPB, InsertedRowPB, RowMetaPB,
+};
+use crate::services::cell::insert_text_cell;
+use crate::services::field::{ProtobufStrParser, RichTextTypeOption, TypeOption};
+use crate::services::group::action::GroupCustomize;
+use crate::services::group::configuration::GroupContext;
+use crate::services::group::controller::{BaseGroupController, GroupController};
+use crate::services::group::{
+  make_no_status_group, move_group_row, GeneratedGroupConfig, GeneratedGroups, Group,
+  GroupConfigurationContentSerde, GroupOperationInterceptor, GroupsBuilder, MoveGroupRowContext,
+};
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct TextGroupConfiguration {
+  pub hide_empty: bool,
+  pub condition: TextGroupCondition,
+}
+
+impl GroupConfigurationContentSerde for TextGroupConfiguration {
+  fn from_json(s: &str) -> Result<Self, serde_json::Error> {
+    serde_json::from_str(s)
+  }
+  fn to_json(&self) -> Result<String, serde_json::Error> {
+    serde_json::to_string(self)
+  }
+}
+
+#[derive(Default, Eq, PartialEq, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum TextGroupCondition {
+  /// Groups the texts by their first letter. The texts that don't start with a letter are
+  /// grouped in the `#` group.
+  #[default]
+  FirstLetter = 0,
+  /// Groups the texts that are exactly the same.
+  ExactValue = 1,
+}
+
+pub type TextGroupController = BaseGroupController<
+  TextGroupConfiguration,
+  RichTextTypeOption,
+  TextGroupBuilder,
+  ProtobufStrParser,
+  TextGroupOperationInterceptorImpl,
+>;
+
+pub type TextGroupContext = GroupContext<TextGroupConfiguration>;
+
+impl TextGroupController {
+  /// The rows can only be moved between the groups of exact values. Moving a row to a first
+  /// letter group would have to rewrite its text.
+  fn can_move_row(&self) -> bool {
+    let config =
+      TextGroupConfiguration::from_json(&self.context.get_setting_content()).unwrap_or_default();
+    config.condition == TextGroupCondition::ExactValue
+  }
+}
+
+impl GroupCustomize for TextGroupController {
+  type GroupTypeOption = RichTextTypeOption;
+
+  fn placeholder_cell(&self) -> Option<Cell> {
+    Some(
+      new_cell_builder(FieldType::RichText)
+        .insert_str_value("data", "")
+        .build(),
+    )
+  }
+
+  fn can_group(
+    &self,
+    content: &str,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellData,
+  ) -> bool {
+    group_id(&cell_data.0, &self.context.get_setting_content()).as_deref() == Some(content)
+  }
+
+  fn create_or_delete_group_when_cell_changed(
+    &mut self,
+    row_detail: &RowDetail,
+    old_cell_data: Option<&<Self::GroupTypeOption as TypeOption>::CellProtobufType>,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellProtobufType,
+  ) -> FlowyResult<(Option<InsertedGroupPB>, Option<GroupPB>)> {
+    let setting_content = self.context.get_setting_content();
+    let new_group_id = group_id(cell_data, &setting_content);
+    let mut inserted_group = None;
+    if let Some(group_id) = new_group_id.clone() {
+      if self.context.get_group(&group_id).is_none() {
+        let mut new_group = self.context.add_new_group(make_group_from_id(group_id))?;
+        new_group.group.rows.push(RowMetaPB::from(row_detail));
+        inserted_group = Some(new_group);
+      }
+    }
+
+    // Delete the old group if the row was the only one in it
+    let deleted_group = match old_cell_data
+      .and_then(|old_cell_data| group_id(old_cell_data, &setting_content))
+      .filter(|old_group_id| Some(old_group_id) != new_group_id.as_ref())
+      .and_then(|old_group_id| self.context.get_group(&old_group_id))
+    {
+      Some((_, group)) if group.rows.len() == 1 => Some(group.clone()),
+      _ => None,
+    };
+
+    let deleted_group = match deleted_group {
+      None => None,
+      Some(group) => {
+        self.context.delete_group(&group.id)?;
+        Some(GroupPB::from(group))
+      },
+    };
+
+    Ok((inserted_group, deleted_group))
+  }
+
+  fn add_or_remove_row_when_cell_changed(
+    &mut self,
+    row_detail: &RowDetail,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellProtobufType,
+  ) -> Vec<GroupRowsNotificationPB> {
+    let mut changesets = vec![];
+    let group_id = group_id(cell_data, &self.context.get_setting_content());
+    self.context.iter_mut_status_groups(|group| {
+      let mut changeset = GroupRowsNotificationPB::new(group.id.clone());
+      if group_id.as_ref() == Some(&group.id) {
+        if !group.contains_row(&row_detail.row.id) {
+          changeset
+            .inserted_rows
+            .push(InsertedRowPB::new(RowMetaPB::from(row_detail)));
+          group.add_row(row_detail.clone());
+        }
+      } else if group.contains_row(&row_detail.row.id) {
+        group.remove_row(&row_detail.row.id);
+        changeset
+          .deleted_rows
+          .push(row_detail.row.id.clone().into_inner());
+      }
+
+      if !changeset.is_empty() {
+        changesets.push(changeset);
+      }
+    });
+    changesets
+  }
+
+  fn delete_row(
+    &mut self,
+    row: &Row,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellData,
+  ) -> (Option<GroupPB>, Vec<GroupRowsNotificationPB>) {
+    let mut changesets = vec![];
+    self.context.iter_mut_groups(|group| {
+      let mut changeset = GroupRowsNotificationPB::new(group.id.clone());
+      if group.contains_row(&row.id) {
+        group.remove_row(&row.id);
+        changeset.deleted_rows.push(row.id.clone().into_inner());
+      }
+
+      if !changeset.is_empty() {
+        changesets.push(changeset);
+      }
+    });
+
+    let deleted_group = match group_id(&cell_data.0, &self.context.get_setting_content())
+      .and_then(|group_id| self.context.get_group(&group_id))
+    {
+      Some((_, group)) if group.rows.len() == 1 => Some(group.clone()),
+      _ => None,
+    };
+
+    let deleted_group = deleted_group.map(|group| {
+      let _ = self.context.delete_group(&group.id);
+      group.into()
+    });
+
+    (deleted_group, changesets)
+  }
+
+  fn move_row(
+    &mut self,
+    _cell_data: &<Self::GroupTypeOption as TypeOption>::CellProtobufType,
+    mut context: MoveGroupRowContext,
+  ) -> Vec<GroupRowsNotificationPB> {
+    if !self.can_move_row() {
+      return vec![];
+    }
+
+    let mut group_changeset = vec![];
+    self.context.iter_mut_groups(|group| {
+      if let Some(changeset) = move_group_row(group, &mut context) {
+        group_changeset.push(changeset);
+      }
+    });
+    group_changeset
+  }
+
+  fn delete_group_when_move_row(
+    &mut self,
+    _row: &Row,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellProtobufType,
+  ) -> Option<GroupPB> {
+    if !self.can_move_row() {
+      return None;
+    }
+
+    let deleted_group = match group_id(cell_data, &self.context.get_setting_content())
+      .and_then(|group_id| self.context.get_group(&group_id))
+    {
+      Some((_, group)) if group.rows.len() == 1 => Some(GroupPB::from(group.clone())),
+      _ => None,
+    };
+    if let Some(deleted_group) = deleted_group.as_ref() {
+      let _ = self.context.delete_group(&deleted_group.group_id);
+    }
+    deleted_group
+  }
+
+  fn delete_group_custom(&mut self, group_id: &str) -> FlowyResult<Option<TypeOptionData>> {
+    self.context.delete_group(group_id)?;
+    Ok(None)
+  }
+}
+
+impl GroupController for TextGroupController {
+  fn did_update_field_type_option(&mut self, _field: &Field) {}
+
+  fn will_create_row(&mut self, cells: &mut Cells, field: &Field, group_id: &str) {
+    if !self.can_move_row() {
+      return;
+    }
+
+    match self.context.get_group(group_id) {
+      None => tracing::warn!("Can not find the group: {}", group_id),
+      Some((_, group)) => {
+        let cell = insert_text_cell(group.id.clone(), field);
+        cells.insert(field.id.clone(), cell);
+      },
+    }
+  }
+}
+
+pub struct TextGroupBuilder();
+#[async_trait]
+impl GroupsBuilder for TextGroupBuilder {
+  type Context = TextGroupContext;
+  type GroupTypeOption = RichTextTypeOption;
+
+  async fn build(
+    field: &Field,
+    context: &Self::Context,
+    _type_option: &Self::GroupTypeOption,
+  ) -> GeneratedGroups {
+    // Read all the cells for the grouping field
+    let cells = context.get_all_cells().await;
+    let setting_content = context.get_setting_content();
+
+    // Generate the groups in alphabetical order
+    let mut group_ids = cells
+      .into_iter()
+      .flat_map(|value| value.into_text_field_cell_data())
+      .flat_map(|cell| group_id(&cell.0, &setting_content))
+      .collect::<Vec<String>>();
+    group_ids.sort();
+    group_ids.dedup();
+
+    let group_configs = group_ids
+      .into_iter()
+      .map(|group_id| GeneratedGroupConfig {
+        filter_content: group_id.clone(),
+        group: make_group_from_id(group_id),
+      })
+      .collect();
+
+    let no_status_group = Some(make_no_status_group(field));
+    GeneratedGroups {
+      no_status_group,
+      group_configs,
+    }
+  }
+}
+
+/// Returns the id of the group that contains the text, or None if the text is empty.
+fn group_id(text: &str, setting_content: &str) -> Option<String> {
+  if text.trim().is_empty() {
+    return None;
+  }
+
+  let config = TextGroupConfiguration::from_json(setting_content).unwrap_or_default();
+  match config.condition {
+    TextGroupCondition::FirstLetter => {
+      let first_char = text.trim_start().chars().next()?;
+      if first_char.is_alphabetic() {
+        Some(first_char.to_uppercase().collect())
+      } else {
+        Some("#".to_string())
+      }
+    },
+    TextGroupCondition::ExactValue => Some(text.to_string()),
+  }
+}
+
+fn make_group_from_id(group_id: String) -> Group {
+  Group::new(group_id.clone(), group_id)
+}
+
+pub struct TextGroupOperationInterceptorImpl {}
+
+#[async_trait]
+impl GroupOperationInterceptor for TextGroupOperationInterceptorImpl {
+  type GroupTypeOption = RichTextTypeOption;
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::services::group::controller_impls::text_controller::group_id;
+
+  #[test]
+  fn text_group_id_test() {
+    let first_letter = r#"{"condition": 0, "hide_empty": false}"#;
+    let exact_value = r#"{"condition": 1, "hide_empty": false}"#;
+    let tests = vec![
+      ("apple", first_letter, Some("A")),
+      ("  Banana", first_letter, Some("B")),
+      ("élan", first_letter, Some("É")),
+      ("42 apples", first_letter, Some("#")),
+      ("apple", "", Some("A")),
+      ("apple", exact_value, Some("apple")),
+      ("   ", first_letter, None),
+      ("", exact_value, None),
+    ];
+
+    for (i, (text, setting_content, exp_group_id)) in tests.into_iter().enumerate() {
+      assert_eq!(
+        group_id(text, setting_content).as_deref(),
+        exp_group_id,
+        "test {}",
+        i
+      );
+    }
+  }
+}
@@ -12,6 +12,9 @@ pub struct GroupSetting {
   pub field_type: i64,
   pub groups: Vec<Group>,
   pub content: String,
+  /// The second-level grouping of the board, whose groups are displayed as swimlanes across the
+  /// groups of this setting.
+  pub swimlane: Option<Box<GroupSetting>>,
 }
 
 pub struct GroupChangesets {
@@ -40,6 +43,7 @@ impl GroupSetting {
       field_type,
       groups: vec![],
       content,
+      swimlane: None,
     }
   }
 }
@@ -49,6 +53,7 @@ const FIELD_ID: &str = "field_id";
 const FIELD_TYPE: &str = "ty";
 const GROUPS: &str = "groups";
 const CONTENT: &str = "content";
+const SWIMLANE: &str = "swimlane";
 
 impl TryFrom<GroupSettingMap> for GroupSetting {
   type Error = anyhow::Error;
@@ -62,12 +67,18 @@ impl TryFrom<GroupSettingMap> for GroupSetting {
       (Some(id), Some(field_id), Some(field_type)) => {
         let content = value.get_str_value(CONTENT).unwrap_or_default();
         let groups = value.try_get_array(GROUPS);
+        let swimlane = value
+          .try_get_array::<GroupSetting>(SWIMLANE)
+          .into_iter()
+          .next()
+          .map(Box::new);
         Ok(Self {
           id,
           field_id,
           field_type,
           groups,
           content,
+          swimlane,
         })
       },
       _ => {
@@ -79,12 +90,18 @@ impl TryFrom<GroupSettingMap> for GroupSetting {
 
 impl From<GroupSetting> for GroupSettingMap {
   fn from(setting: GroupSetting) -> Self {
+    // The swimlane setting is stored as an array with at most one element.
+    let swimlane = setting
+      .swimlane
+      .map(|swimlane| vec![*swimlane])
+      .unwrap_or_default();
     GroupSettingBuilder::new()
       .insert_str_value(GROUP_ID, setting.id)
       .insert_str_value(FIELD_ID, setting.field_id)
       .insert_i64_value(FIELD_TYPE, setting.field_type)
       .insert_maps(GROUPS, setting.groups)
       .insert_str_value(CONTENT, setting.content)
+      .insert_maps(SWIMLANE, swimlane)
       .build()
   }
 }
@@ -12,12 +12,15 @@ use crate::entities::FieldType;
 use crate::services::field::TypeOption;
 use crate::services::group::{
   CheckboxGroupContext, CheckboxGroupController, CheckboxGroupOperationInterceptorImpl,
+  ChecklistGroupContext, ChecklistGroupController, ChecklistGroupOperationInterceptorImpl,
   DateGroupContext, DateGroupController, DateGroupOperationInterceptorImpl, DefaultGroupController,
   Group, GroupController, GroupSetting, GroupSettingReader, GroupSettingWriter,
   GroupTypeOptionCellOperation, MultiSelectGroupController,
-  MultiSelectGroupOperationInterceptorImpl, MultiSelectOptionGroupContext,
-  SingleSelectGroupController, SingleSelectGroupOperationInterceptorImpl,
-  SingleSelectOptionGroupContext, URLGroupContext, URLGroupController,
+  MultiSelectGroupOperationInterceptorImpl, MultiSelectOptionGroupContext, NumberGroupContext,
+  NumberGroupController, NumberGroupOperationInterceptorImpl, PersonGroupContext,
+  PersonGroupController, PersonGroupOperationInterceptorImpl, SingleSelectGroupController,
+  SingleSelectGroupOperationInterceptorImpl, SingleSelectOptionGroupContext, TextGroupContext,
+  TextGroupController, TextGroupOperationInterceptorImpl, URLGroupContext, URLGroupController,
   URLGroupOperationInterceptorImpl,
 };
 
@@ -185,6 +188,59 @@ where
         DateGroupController::new(&grouping_field, configuration, operation_interceptor).await?;
       group_controller = Box::new(controller);
     },
+    FieldType::Person => {
+      let configuration = PersonGroupContext::new(
+        view_id,
+        grouping_field.clone(),
+        configuration_reader,
+        configuration_writer,
+      )
+      .await?;
+      let operation_interceptor = PersonGroupOperationInterceptorImpl;
+      let controller =
+        PersonGroupController::new(&grouping_field, configuration, operation_interceptor).await?;
+      group_controller = Box::new(controller);
+    },
+    FieldType::Number => {
+      let configuration = NumberGroupContext::new(
+        view_id,
+        grouping_field.clone(),
+        configuration_reader,
+        configuration_writer,
+      )
+      .await?;
+      let operation_interceptor = NumberGroupOperationInterceptorImpl {};
+      let controller =
+        NumberGroupController::new(&grouping_field, configuration, operation_interceptor).await?;
+      group_controller = Box::new(controller);
+    },
+    FieldType::RichText => {
+      let configuration = TextGroupContext::new(
+        view_id,
+        grouping_field.clone(),
+        configuration_reader,
+        configuration_writer,
+      )
+      .await?;
+      let operation_interceptor = TextGroupOperationInterceptorImpl {};
+      let controller =
+        TextGroupController::new(&grouping_field, configuration, operation_interceptor).await?;
+      group_controller = Box::new(controller);
+    },
+    FieldType::Checklist => {
+      let configuration = ChecklistGroupContext::new(
+        view_id,
+        grouping_field.clone(),
+        configuration_reader,
+        configuration_writer,
+      )
+      .await?;
+      let operation_interceptor = ChecklistGroupOperationInterceptorImpl {};
+      let controller =
+        ChecklistGroupController::new(&grouping_field, configuration, operation_interceptor)
+          .await?;
+      group_controller = Box::new(controller);
+    },
     _ => {
       group_controller = Box::new(DefaultGroupController::new(&grouping_field));
     },
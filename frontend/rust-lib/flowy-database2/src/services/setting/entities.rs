@@ -123,3 +123,56 @@ impl From<BoardLayoutSetting> for LayoutSetting {
       .build()
   }
 }
+
+/// The setting of a timeline, which displays each row as a span between two dates.
+///
+/// It's saved as the layout setting of a grid view, see
+/// [DatabaseLayoutPB::Timeline](crate::entities::DatabaseLayoutPB::Timeline).
+#[derive(Debug, Clone, Default)]
+pub struct TimelineLayoutSetting {
+  /// The date field of the start of the spans.
+  pub start_field_id: String,
+  /// The date field of the end of the spans. If it's None, the end of each span is the end date
+  /// of the start field's date range.
+  pub end_field_id: Option<String>,
+  /// The relation field that links each row to the rows it depends on.
+  pub dependency_field_id: Option<String>,
+}
+
+impl TimelineLayoutSetting {
+  pub fn new(start_field_id: String) -> Self {
+    Self {
+      start_field_id,
+      ..Default::default()
+    }
+  }
+
+  /// Returns true if the layout setting is the setting of a timeline.
+  pub fn is_timeline(setting: &LayoutSetting) -> bool {
+    setting.get_str_value("start_field_id").is_some()
+  }
+}
+
+impl From<LayoutSetting> for TimelineLayoutSetting {
+  fn from(setting: LayoutSetting) -> Self {
+    Self {
+      start_field_id: setting.get_str_value("start_field_id").unwrap_or_default(),
+      end_field_id: setting.get_str_value("end_field_id"),
+      dependency_field_id: setting.get_str_value("dependency_field_id"),
+    }
+  }
+}
+
+impl From<TimelineLayoutSetting> for LayoutSetting {
+  fn from(setting: TimelineLayoutSetting) -> Self {
+    let mut builder =
+      LayoutSettingBuilder::new().insert_str_value("start_field_id", setting.start_field_id);
+    if let Some(end_field_id) = setting.end_field_id {
+      builder = builder.insert_str_value("end_field_id", end_field_id);
+    }
+    if let Some(dependency_field_id) = setting.dependency_field_id {
+      builder = builder.insert_str_value("dependency_field_id", dependency_field_id);
+    }
+    builder.build()
+  }
+}
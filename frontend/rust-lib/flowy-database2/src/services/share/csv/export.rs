@@ -1,10 +1,20 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
 use collab_database::database::Database;
+use collab_database::fields::Field;
+use collab_database::rows::Row;
 use indexmap::IndexMap;
 
 use flowy_error::{FlowyError, FlowyResult};
 
 use crate::entities::FieldType;
 use crate::services::cell::stringify_cell_data;
+use crate::services::share::{stringify_export_cell, RelatedRowNames, ViewExportData};
+
+/// The header of the column that contains the name of the group of each row, when the rows of a
+/// grouped view are exported.
+const GROUP_COLUMN_NAME: &str = "Group";
 
 #[derive(Debug, Clone, Copy)]
 pub enum CSVFormat {
@@ -16,12 +26,119 @@ pub enum CSVFormat {
   META,
 }
 
+/// The fields and the rows of the inline view of the database, in the order they are exported.
+/// It lets the CSV be written without holding the lock of the database.
+pub struct CSVExportData {
+  pub fields: Vec<Field>,
+  pub rows: Vec<Row>,
+}
+
+impl CSVExportData {
+  pub fn from_database(database: &Database) -> Self {
+    let inline_view_id = database.get_inline_view_id();
+    let fields = database.get_fields_in_view(&inline_view_id, None);
+    let rows = database
+      .get_row_orders_for_view(&inline_view_id)
+      .into_iter()
+      .filter_map(|row_order| database.get_row_detail(&row_order.id))
+      .map(|row_detail| row_detail.row)
+      .collect();
+    Self { fields, rows }
+  }
+}
+
+/// Exports the database to CSV. In the [CSVFormat::Original] format, the relation cells are the
+/// names of the linked rows, which are looked up in the `related_row_names`.
 pub struct CSVExport;
 impl CSVExport {
-  pub fn export_database(&self, database: &Database, style: CSVFormat) -> FlowyResult<String> {
+  pub fn export_database(
+    &self,
+    database: &Database,
+    style: CSVFormat,
+    related_row_names: &RelatedRowNames,
+  ) -> FlowyResult<String> {
+    let wtr = csv::Writer::from_writer(vec![]);
+    let data = CSVExportData::from_database(database);
+    let data = self.export_data_to_writer(data, style, related_row_names, wtr)?;
+    let csv = String::from_utf8(data).map_err(|e| FlowyError::internal().with_context(e))?;
+    Ok(csv)
+  }
+
+  /// Writes the CSV straight to the file at `path`, so the CSV is never built in memory.
+  pub fn export_data_to_file(
+    &self,
+    data: CSVExportData,
+    style: CSVFormat,
+    related_row_names: &RelatedRowNames,
+    path: &str,
+  ) -> FlowyResult<()> {
+    let file = File::create(path)?;
+    let wtr = csv::Writer::from_writer(BufWriter::new(file));
+    let mut writer = self.export_data_to_writer(data, style, related_row_names, wtr)?;
+    writer.flush()?;
+    Ok(())
+  }
+
+  /// Exports the rows of a view as the user sees them. If the rows are grouped, the first column
+  /// is the name of the group of each row.
+  pub fn export_view(&self, data: &ViewExportData, style: CSVFormat) -> FlowyResult<String> {
     let mut wtr = csv::Writer::from_writer(vec![]);
-    let inline_view_id = database.get_inline_view_id();
-    let fields = database.get_fields_in_view(&inline_view_id, None);
+    let mut field_records = data
+      .fields
+      .iter()
+      .map(|field| match &style {
+        CSVFormat::Original => field.name.clone(),
+        CSVFormat::META => serde_json::to_string(&field).unwrap(),
+      })
+      .collect::<Vec<String>>();
+    if data.is_grouped() {
+      field_records.insert(0, GROUP_COLUMN_NAME.to_string());
+    }
+    wtr
+      .write_record(&field_records)
+      .map_err(|e| FlowyError::internal().with_context(e))?;
+
+    for group in &data.groups {
+      for row_detail in &group.rows {
+        let mut cells = data
+          .fields
+          .iter()
+          .map(|field| match style {
+            CSVFormat::Original => {
+              stringify_export_cell(&row_detail.row, field, &data.related_row_names)
+            },
+            CSVFormat::META => row_detail
+              .row
+              .cells
+              .get(&field.id)
+              .and_then(|cell| serde_json::to_string(cell).ok())
+              .unwrap_or_default(),
+          })
+          .collect::<Vec<String>>();
+        if data.is_grouped() {
+          cells.insert(0, group.name.clone().unwrap_or_default());
+        }
+        if let Err(e) = wtr.write_record(&cells) {
+          tracing::warn!("CSV failed to write record: {}", e);
+        }
+      }
+    }
+
+    let data = wtr
+      .into_inner()
+      .map_err(|e| FlowyError::internal().with_context(e))?;
+    let csv = String::from_utf8(data).map_err(|e| FlowyError::internal().with_context(e))?;
+    Ok(csv)
+  }
+
+  fn export_data_to_writer<W: Write>(
+    &self,
+    data: CSVExportData,
+    style: CSVFormat,
+    related_row_names: &RelatedRowNames,
+    mut wtr: csv::Writer<W>,
+  ) -> FlowyResult<W> {
+    let CSVExportData { fields, rows } = data;
 
     // Write fields
     let field_records = fields
@@ -40,7 +157,6 @@ impl CSVExport {
     fields.into_iter().for_each(|field| {
       field_by_field_id.insert(field.id.clone(), field);
     });
-    let rows = database.get_rows_for_view(&inline_view_id);
     for row in rows {
       let cells = field_by_field_id
         .iter()
@@ -49,6 +165,9 @@ impl CSVExport {
           Some(cell) => {
             let field_type = FieldType::from(field.field_type);
             match style {
+              CSVFormat::Original if field_type.is_relation() => {
+                related_row_names.stringify_cell(cell)
+              },
               CSVFormat::Original => stringify_cell_data(cell, &field_type, &field_type, field),
               CSVFormat::META => serde_json::to_string(cell).unwrap_or_else(|_| "".to_string()),
             }
@@ -57,14 +176,15 @@ impl CSVExport {
         .collect::<Vec<_>>();
 
       if let Err(e) = wtr.write_record(&cells) {
+        if e.is_io_error() {
+          return Err(FlowyError::internal().with_context(e));
+        }
         tracing::warn!("CSV failed to write record: {}", e);
       }
     }
 
-    let data = wtr
+    wtr
       .into_inner()
-      .map_err(|e| FlowyError::internal().with_context(e))?;
-    let csv = String::from_utf8(data).map_err(|e| FlowyError::internal().with_context(e))?;
-    Ok(csv)
+      .map_err(|e| FlowyError::internal().with_context(e))
   }
 }
@@ -1,16 +1,34 @@
+use std::collections::{HashMap, VecDeque};
 use std::{fs::File, io::prelude::*};
 
 use collab_database::database::{gen_database_id, gen_field_id, gen_row_id};
-use collab_database::fields::Field;
+use collab_database::fields::{Field, TypeOptionData};
 use collab_database::rows::{new_cell_builder, Cell, CreateRowParams};
 use collab_database::views::{CreateDatabaseParams, DatabaseLayout};
+use csv::StringRecord;
 
 use flowy_error::{FlowyError, FlowyResult};
 
 use crate::entities::FieldType;
 use crate::services::field::{default_type_option_data_from_type, CELL_DATA};
 use crate::services::field_settings::default_field_settings_for_fields;
-use crate::services::share::csv::CSVFormat;
+use crate::services::share::csv::{
+  infer_field_type, is_inferable_field_type, CSVFormat, ImportColumn, SAMPLE_ROW_COUNT,
+};
+
+/// The number of values of each column that are returned in the [CSVImportPreview].
+const PREVIEW_SAMPLE_COUNT: usize = 5;
+
+/// The key of the import meta that carries the field types chosen by the user after previewing
+/// the CSV. The value is a JSON array of the field types, e.g. "[0,1,3]".
+pub const CSV_FIELD_TYPES_META_KEY: &str = "csv_field_types";
+
+/// Returns the field types saved in the import meta under [CSV_FIELD_TYPES_META_KEY].
+pub fn csv_field_types_from_meta(meta: &HashMap<String, String>) -> Option<Vec<FieldType>> {
+  let value = meta.get(CSV_FIELD_TYPES_META_KEY)?;
+  let field_types = serde_json::from_str::<Vec<i64>>(value).ok()?;
+  Some(field_types.into_iter().map(FieldType::from).collect())
+}
 
 #[derive(Default)]
 pub struct CSVImporter;
@@ -26,10 +44,12 @@ impl CSVImporter {
     let mut content = String::new();
     file.read_to_string(&mut content)?;
     let fields_with_rows = self.get_fields_and_rows(content)?;
-    let database_data = database_from_fields_and_rows(view_id, fields_with_rows, &style);
+    let database_data = database_from_fields_and_rows(view_id, fields_with_rows, &style, None);
     Ok(database_data)
   }
 
+  /// Imports the CSV. The type of each column is inferred from its values if the format is
+  /// [CSVFormat::Original].
   pub fn import_csv_from_string(
     &self,
     view_id: String,
@@ -37,10 +57,73 @@ impl CSVImporter {
     format: CSVFormat,
   ) -> FlowyResult<CreateDatabaseParams> {
     let fields_with_rows = self.get_fields_and_rows(content)?;
-    let database_data = database_from_fields_and_rows(&view_id, fields_with_rows, &format);
+    let database_data = database_from_fields_and_rows(&view_id, fields_with_rows, &format, None);
+    Ok(database_data)
+  }
+
+  /// Imports the CSV with the field types that the user chose after previewing it. The
+  /// `field_types` are the types of the columns, in the order of the columns.
+  pub fn import_csv_from_string_with_field_types(
+    &self,
+    view_id: String,
+    content: String,
+    field_types: Vec<FieldType>,
+  ) -> FlowyResult<CreateDatabaseParams> {
+    let fields_with_rows = self.get_fields_and_rows(content)?;
+    if field_types.len() != fields_with_rows.fields.len() {
+      return Err(FlowyError::invalid_data().with_context(format!(
+        "Expected {} field types, but got {}",
+        fields_with_rows.fields.len(),
+        field_types.len()
+      )));
+    }
+    if let Some(field_type) = field_types
+      .iter()
+      .find(|field_type| !is_inferable_field_type(field_type))
+    {
+      return Err(
+        FlowyError::invalid_data()
+          .with_context(format!("Can't import a CSV column as {:?}", field_type)),
+      );
+    }
+
+    let database_data = database_from_fields_and_rows(
+      &view_id,
+      fields_with_rows,
+      &CSVFormat::Original,
+      Some(field_types),
+    );
     Ok(database_data)
   }
 
+  /// Returns the columns of the CSV with the inferred field types. The user can override the
+  /// types and then import the CSV by calling [Self::import_csv_from_string_with_field_types].
+  pub fn preview_csv_from_string(&self, content: String) -> FlowyResult<CSVImportPreview> {
+    let fields_with_rows = self.get_fields_and_rows(content)?;
+    let columns = fields_with_rows
+      .fields
+      .iter()
+      .enumerate()
+      .map(|(index, name)| {
+        let values = fields_with_rows.column_values(index);
+        CSVColumn {
+          name: name.clone(),
+          field_type: column_field_type(index, &values, None),
+          samples: values
+            .iter()
+            .filter(|value| !value.trim().is_empty())
+            .take(PREVIEW_SAMPLE_COUNT)
+            .map(|value| value.to_string())
+            .collect(),
+        }
+      })
+      .collect();
+    Ok(CSVImportPreview {
+      columns,
+      num_of_rows: fields_with_rows.rows.len(),
+    })
+  }
+
   fn get_fields_and_rows(&self, content: String) -> Result<FieldsRows, FlowyError> {
     let mut fields: Vec<String> = vec![];
     if content.is_empty() {
@@ -71,61 +154,287 @@ impl CSVImporter {
   }
 }
 
+/// Returns the field type of the column. The first column is the primary field, which is always
+/// a [FieldType::RichText] field.
+fn column_field_type(
+  index: usize,
+  values: &[&str],
+  field_types: Option<&[FieldType]>,
+) -> FieldType {
+  if index == 0 {
+    return FieldType::RichText;
+  }
+  match field_types.and_then(|field_types| field_types.get(index)) {
+    Some(field_type) => *field_type,
+    None => infer_field_type(values),
+  }
+}
+
 fn database_from_fields_and_rows(
   view_id: &str,
   fields_and_rows: FieldsRows,
   format: &CSVFormat,
+  field_types: Option<Vec<FieldType>>,
 ) -> CreateDatabaseParams {
-  let (fields, rows) = fields_and_rows.split();
-  let database_id = gen_database_id();
-
-  let fields = fields
-    .into_iter()
-    .enumerate()
-    .map(|(index, field_meta)| match format {
-      CSVFormat::Original => default_field(field_meta, index == 0),
+  let mut row_builder = RowBuilder::new(&fields_and_rows, format, field_types.as_deref());
+  let created_rows = fields_and_rows
+    .rows
+    .iter()
+    .map(|record| row_builder.build_row(record))
+    .collect::<Vec<CreateRowParams>>();
+
+  create_database_params(
+    gen_database_id(),
+    view_id,
+    row_builder.into_fields(),
+    created_rows,
+  )
+}
+
+/// Converts the records of the CSV to rows.
+enum RowBuilder {
+  /// Each column of the CSV has a field with the inferred type, or with the type in
+  /// `field_types` if the user overrode it. The contents are converted to the cells of that type.
+  Typed(Vec<ImportColumn>),
+  /// The headers of the CSV exported with [CSVFormat::META] are the serialized fields and the
+  /// contents are the serialized cells.
+  Meta(Vec<Field>),
+}
+
+impl RowBuilder {
+  /// Builds the fields from the headers of the CSV. The rows of `fields_and_rows` are used to
+  /// infer the type of each column.
+  fn new(
+    fields_and_rows: &FieldsRows,
+    format: &CSVFormat,
+    field_types: Option<&[FieldType]>,
+  ) -> Self {
+    match format {
+      CSVFormat::Original => {
+        let columns = fields_and_rows
+          .fields
+          .iter()
+          .enumerate()
+          .map(|(index, name)| {
+            let values = fields_and_rows.column_values(index);
+            let field_type = column_field_type(index, &values, field_types);
+            ImportColumn::new(name.clone(), field_type, &values, index == 0)
+          })
+          .collect();
+        Self::Typed(columns)
+      },
       CSVFormat::META => {
-        //
-        match serde_json::from_str(&field_meta) {
-          Ok(field) => field,
-          Err(e) => {
-            dbg!(e);
-            default_field(field_meta, index == 0)
-          },
-        }
+        let fields = fields_and_rows
+          .fields
+          .iter()
+          .enumerate()
+          .map(
+            |(index, field_meta)| match serde_json::from_str(field_meta) {
+              Ok(field) => field,
+              Err(e) => {
+                dbg!(e);
+                default_field(field_meta.clone(), index == 0)
+              },
+            },
+          )
+          .collect();
+        Self::Meta(fields)
       },
-    })
-    .collect::<Vec<Field>>();
+    }
+  }
 
-  let field_settings = default_field_settings_for_fields(&fields, DatabaseLayout::Grid);
+  fn fields(&self) -> Vec<Field> {
+    match self {
+      RowBuilder::Typed(columns) => columns.iter().map(|column| column.field.clone()).collect(),
+      RowBuilder::Meta(fields) => fields.clone(),
+    }
+  }
 
-  let created_rows = rows
-    .iter()
-    .map(|cells| {
-      let mut params = CreateRowParams::new(gen_row_id());
-      for (index, cell_content) in cells.iter().enumerate() {
-        if let Some(field) = fields.get(index) {
-          let field_type = FieldType::from(field.field_type);
+  fn into_fields(self) -> Vec<Field> {
+    match self {
+      RowBuilder::Typed(columns) => columns.into_iter().map(|column| column.field).collect(),
+      RowBuilder::Meta(fields) => fields,
+    }
+  }
 
-          // Make the cell based on the style.
-          let cell = match format {
-            CSVFormat::Original => new_cell_builder(field_type)
-              .insert_str_value(CELL_DATA, cell_content.to_string())
+  fn build_row(&mut self, record: &[String]) -> CreateRowParams {
+    let mut params = CreateRowParams::new(gen_row_id());
+    match self {
+      RowBuilder::Typed(columns) => {
+        for (cell_content, column) in record.iter().zip(columns.iter_mut()) {
+          params
+            .cells
+            .insert(column.field.id.clone(), column.cell_from_str(cell_content));
+        }
+      },
+      RowBuilder::Meta(fields) => {
+        for (cell_content, field) in record.iter().zip(fields.iter()) {
+          let field_type = FieldType::from(field.field_type);
+          let cell = match serde_json::from_str::<Cell>(cell_content) {
+            Ok(cell) => cell,
+            Err(_) => new_cell_builder(field_type)
+              .insert_str_value(CELL_DATA, "".to_string())
               .build(),
-            CSVFormat::META => match serde_json::from_str::<Cell>(cell_content) {
-              Ok(cell) => cell,
-              Err(_) => new_cell_builder(field_type)
-                .insert_str_value(CELL_DATA, "".to_string())
-                .build(),
-            },
           };
           params.cells.insert(field.id.clone(), cell);
         }
+      },
+    }
+    params
+  }
+
+  /// Returns the type options of the fields that changed while building the rows, see
+  /// [ImportColumn::updated_type_option].
+  fn updated_type_options(&self) -> Vec<UpdatedTypeOption> {
+    match self {
+      RowBuilder::Typed(columns) => columns
+        .iter()
+        .filter_map(|column| {
+          let (field_type, type_option_data) = column.updated_type_option()?;
+          Some(UpdatedTypeOption {
+            field_id: column.field.id.clone(),
+            field_type,
+            type_option_data,
+          })
+        })
+        .collect(),
+      RowBuilder::Meta(_) => vec![],
+    }
+  }
+}
+
+/// Reads a CSV file in batches of rows, so a large file can be imported without loading the
+/// whole file into memory. The first [SAMPLE_ROW_COUNT] rows are read up front to infer the type
+/// of each column.
+pub struct CSVFileReader {
+  reader: csv::Reader<File>,
+  file_size: u64,
+  row_builder: RowBuilder,
+  sample_records: VecDeque<Vec<String>>,
+  num_of_rows: usize,
+}
+
+impl CSVFileReader {
+  pub fn open(path: &str, format: CSVFormat) -> FlowyResult<Self> {
+    let file = File::open(path)?;
+    let file_size = file.metadata()?.len();
+    let mut reader = csv::Reader::from_reader(file);
+    let fields = match reader.headers() {
+      Ok(headers) => headers
+        .iter()
+        .map(|header| header.to_string())
+        .collect::<Vec<String>>(),
+      Err(_) => return Err(FlowyError::invalid_data().with_context("Header not found")),
+    };
+    if fields.is_empty() {
+      return Err(FlowyError::invalid_data().with_context("Import content is empty"));
+    }
+
+    let mut sample_records = vec![];
+    while sample_records.len() < SAMPLE_ROW_COUNT {
+      match read_record(&mut reader)? {
+        None => break,
+        Some(record) => sample_records.push(record),
       }
-      params
+    }
+    let fields_and_rows = FieldsRows {
+      fields,
+      rows: sample_records,
+    };
+    let row_builder = RowBuilder::new(&fields_and_rows, &format, None);
+    Ok(Self {
+      reader,
+      file_size,
+      row_builder,
+      sample_records: fields_and_rows.rows.into(),
+      num_of_rows: 0,
     })
-    .collect::<Vec<CreateRowParams>>();
+  }
+
+  /// Returns the params to create the database with the first batch of rows. The type options
+  /// of the select fields only contain the options of the sample rows, see
+  /// [Self::updated_type_options].
+  pub fn create_database_params(
+    &self,
+    view_id: &str,
+    created_rows: Vec<CreateRowParams>,
+  ) -> CreateDatabaseParams {
+    create_database_params(
+      gen_database_id(),
+      view_id,
+      self.row_builder.fields(),
+      created_rows,
+    )
+  }
+
+  /// Returns the next `batch_size` rows. An empty batch means the whole file has been read.
+  pub fn next_batch(&mut self, batch_size: usize) -> FlowyResult<Vec<CreateRowParams>> {
+    let mut rows = Vec::with_capacity(batch_size);
+    while rows.len() < batch_size {
+      let record = match self.sample_records.pop_front() {
+        Some(record) => record,
+        None => match read_record(&mut self.reader)? {
+          Some(record) => record,
+          None => break,
+        },
+      };
+      rows.push(self.row_builder.build_row(&record));
+    }
+    self.num_of_rows += rows.len();
+    Ok(rows)
+  }
+
+  /// The number of rows returned by [Self::next_batch] so far.
+  pub fn num_of_rows(&self) -> usize {
+    self.num_of_rows
+  }
+
+  /// The proportion of the file that has been read, between 0.0 and 1.0.
+  pub fn progress(&self) -> f64 {
+    if self.file_size == 0 {
+      return 1.0;
+    }
+    let position = self.reader.position().byte() as f64;
+    (position / self.file_size as f64).min(1.0)
+  }
 
+  /// Returns the type options of the fields that changed after the sample rows: the select
+  /// fields that got new options and the columns that fell back to text. They must be saved once
+  /// all the rows are imported.
+  pub fn updated_type_options(&self) -> Vec<UpdatedTypeOption> {
+    self.row_builder.updated_type_options()
+  }
+}
+
+/// Reads the next record of the CSV. The records that can't be parsed are skipped, as in
+/// [CSVImporter::import_csv_from_string].
+fn read_record(reader: &mut csv::Reader<File>) -> FlowyResult<Option<Vec<String>>> {
+  let mut record = StringRecord::new();
+  loop {
+    match reader.read_record(&mut record) {
+      Ok(true) => return Ok(Some(record.iter().map(|s| s.to_string()).collect())),
+      Ok(false) => return Ok(None),
+      Err(err) if err.is_io_error() => {
+        return Err(FlowyError::internal().with_context(err));
+      },
+      Err(err) => tracing::warn!("CSV failed to read record: {}", err),
+    }
+  }
+}
+
+pub struct UpdatedTypeOption {
+  pub field_id: String,
+  pub field_type: FieldType,
+  pub type_option_data: TypeOptionData,
+}
+
+fn create_database_params(
+  database_id: String,
+  view_id: &str,
+  fields: Vec<Field>,
+  created_rows: Vec<CreateRowParams>,
+) -> CreateDatabaseParams {
+  let field_settings = default_field_settings_for_fields(&fields, DatabaseLayout::Grid);
   CreateDatabaseParams {
     database_id,
     view_id: view_id.to_string(),
@@ -153,11 +462,38 @@ struct FieldsRows {
   rows: Vec<Vec<String>>,
 }
 impl FieldsRows {
-  fn split(self) -> (Vec<String>, Vec<Vec<String>>) {
-    (self.fields, self.rows)
+  /// Returns the values of the column at `index`. A row that is shorter than the header has an
+  /// empty value.
+  fn column_values(&self, index: usize) -> Vec<&str> {
+    self
+      .rows
+      .iter()
+      .map(|row| {
+        row
+          .get(index)
+          .map(|value| value.as_str())
+          .unwrap_or_default()
+      })
+      .collect()
   }
 }
 
+/// The columns of a CSV that is about to be imported. See [CSVImporter::preview_csv_from_string].
+#[derive(Debug, Clone)]
+pub struct CSVImportPreview {
+  pub columns: Vec<CSVColumn>,
+  pub num_of_rows: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct CSVColumn {
+  pub name: String,
+  /// The inferred field type of the column.
+  pub field_type: FieldType,
+  /// The first non-empty values of the column.
+  pub samples: Vec<String>,
+}
+
 pub struct ImportResult {
   pub database_id: String,
   pub view_id: String,
@@ -165,9 +501,13 @@ pub struct ImportResult {
 
 #[cfg(test)]
 mod tests {
+  use collab::core::any_map::AnyMapExtension;
   use collab_database::database::gen_database_view_id;
+  use collab_database::rows::get_field_type_from_cell;
 
-  use crate::services::share::csv::{CSVFormat, CSVImporter};
+  use crate::entities::FieldType;
+  use crate::services::field::CELL_DATA;
+  use crate::services::share::csv::{CSVFileReader, CSVFormat, CSVImporter, SAMPLE_ROW_COUNT};
 
   #[test]
   fn test_import_csv_from_str() {
@@ -193,9 +533,142 @@ mod tests {
     assert_eq!(result.created_rows[1].cells.len(), 6);
     assert_eq!(result.created_rows[2].cells.len(), 6);
 
+    let field_types = result
+      .fields
+      .iter()
+      .map(|field| FieldType::from(field.field_type))
+      .collect::<Vec<FieldType>>();
+    assert_eq!(
+      field_types,
+      vec![
+        FieldType::RichText,
+        FieldType::RichText,
+        FieldType::Number,
+        FieldType::DateTime,
+        FieldType::Checkbox,
+        FieldType::URL,
+      ]
+    );
+
     println!("{:?}", result);
   }
 
+  #[test]
+  fn preview_csv_and_import_with_field_types_test() {
+    let s = r#"Name,Status,Price
+A,Done,$10
+B,Todo,$20
+C,Done,$30
+D,Todo,"$1,000"
+"#;
+    let importer = CSVImporter;
+    let preview = importer.preview_csv_from_string(s.to_string()).unwrap();
+    assert_eq!(preview.num_of_rows, 4);
+    assert_eq!(preview.columns[1].field_type, FieldType::SingleSelect);
+    assert_eq!(
+      preview.columns[1].samples,
+      vec!["Done", "Todo", "Done", "Todo"]
+    );
+    assert_eq!(preview.columns[2].field_type, FieldType::Number);
+
+    let result = importer
+      .import_csv_from_string_with_field_types(
+        gen_database_view_id(),
+        s.to_string(),
+        vec![FieldType::RichText, FieldType::RichText, FieldType::Number],
+      )
+      .unwrap();
+    assert_eq!(result.fields[1].field_type, FieldType::RichText as i64);
+    assert_eq!(result.fields[2].field_type, FieldType::Number as i64);
+
+    // The number of field types must match the number of columns.
+    let result = importer.import_csv_from_string_with_field_types(
+      gen_database_view_id(),
+      s.to_string(),
+      vec![FieldType::RichText],
+    );
+    assert!(result.is_err());
+  }
+
+  #[test]
+  fn read_csv_file_in_batches_test() {
+    // The select options of the rows after the sampled rows are created while reading them.
+    let mut content = "Name,Status\n".to_string();
+    for index in 0..SAMPLE_ROW_COUNT {
+      content.push_str(&format!("{},{}\n", index, ["Todo", "Done"][index % 2]));
+    }
+    content.push_str("last,Doing\n");
+    let file_path = std::env::temp_dir()
+      .join(format!("{}.csv", nanoid::nanoid!(6)))
+      .to_str()
+      .unwrap()
+      .to_string();
+    std::fs::write(&file_path, content).unwrap();
+
+    let mut reader = CSVFileReader::open(&file_path, CSVFormat::Original).unwrap();
+    let params = reader.create_database_params(&gen_database_view_id(), vec![]);
+    assert_eq!(params.fields[1].field_type, FieldType::SingleSelect as i64);
+
+    let mut num_of_batches = 0;
+    loop {
+      let rows = reader.next_batch(30).unwrap();
+      if rows.is_empty() {
+        break;
+      }
+      num_of_batches += 1;
+    }
+    assert_eq!(num_of_batches, 4);
+    assert_eq!(reader.num_of_rows(), SAMPLE_ROW_COUNT + 1);
+    assert_eq!(reader.progress(), 1.0);
+
+    let updated_type_options = reader.updated_type_options();
+    assert_eq!(updated_type_options.len(), 1);
+    assert_eq!(updated_type_options[0].field_id, params.fields[1].id);
+    std::fs::remove_file(file_path).unwrap();
+  }
+
+  #[test]
+  fn read_csv_file_with_invalid_value_after_sampled_rows_test() {
+    // The column is a number column, until a value that isn't a number is read
+    let mut content = "Name,Price\n".to_string();
+    for index in 0..SAMPLE_ROW_COUNT {
+      content.push_str(&format!("{},{}\n", index, index));
+    }
+    content.push_str("last,unknown\n");
+    let file_path = std::env::temp_dir()
+      .join(format!("{}.csv", nanoid::nanoid!(6)))
+      .to_str()
+      .unwrap()
+      .to_string();
+    std::fs::write(&file_path, content).unwrap();
+
+    let mut reader = CSVFileReader::open(&file_path, CSVFormat::Original).unwrap();
+    let params = reader.create_database_params(&gen_database_view_id(), vec![]);
+    assert_eq!(params.fields[1].field_type, FieldType::Number as i64);
+
+    let mut rows = vec![];
+    loop {
+      let batch = reader.next_batch(1000).unwrap();
+      if batch.is_empty() {
+        break;
+      }
+      rows.extend(batch);
+    }
+    let field_id = &params.fields[1].id;
+    let last_cell = rows.last().unwrap().cells.get(field_id).unwrap();
+    assert_eq!(
+      get_field_type_from_cell::<FieldType>(last_cell),
+      Some(FieldType::RichText)
+    );
+    assert_eq!(last_cell.get_str_value(CELL_DATA).unwrap(), "unknown");
+
+    let updated_type_options = reader.updated_type_options();
+    assert_eq!(updated_type_options.len(), 1);
+    assert_eq!(&updated_type_options[0].field_id, field_id);
+    assert_eq!(updated_type_options[0].field_type, FieldType::RichText);
+    std::fs::remove_file(file_path).unwrap();
+  }
+
   #[test]
   fn import_empty_csv_data_test() {
     let s = r#""#;
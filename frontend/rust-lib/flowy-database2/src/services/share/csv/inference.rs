@@ -0,0 +1,575 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone};
+use collab_database::database::gen_field_id;
+use collab_database::fields::{Field, TypeOptionData};
+use collab_database::rows::{new_cell_builder, Cell};
+
+use crate::entities::FieldType;
+use crate::services::cell::{
+  apply_cell_changeset, insert_checkbox_cell, insert_date_cell, insert_url_cell,
+};
+use crate::services::field::{
+  default_type_option_data_from_type, select_type_option_from_field, DateFormat, DateTypeOption,
+  MultiSelectTypeOption, NumberFormat, NumberTypeOption, SelectOption, SelectOptionIds,
+  SelectTypeOptionSharedAction, SingleSelectTypeOption, CELL_DATA,
+};
+
+/// The number of rows that are sampled to infer the type of a column.
+pub(crate) const SAMPLE_ROW_COUNT: usize = 100;
+
+/// A column is a select column only if it has at most this many distinct values.
+const MAX_SELECT_OPTION_COUNT: usize = 10;
+
+/// The separator of the options in a multi-select cell. The CSV export uses the same separator.
+const SELECT_OPTION_SEPARATOR: char = ',';
+
+type DateFormats = &'static [(&'static str, bool)];
+
+/// The recognized date formats, grouped by the [DateFormat] of the field. The bool tells if the
+/// format contains the time. The groups are tried in order, so a date like "05/06/2023" is read
+/// as the 6th of May unless another date of the column is only valid as day/month/year.
+const DATE_FORMATS: [(DateFormat, DateFormats); 5] = [
+  (
+    DateFormat::ISO,
+    &[
+      ("%Y-%m-%d", false),
+      ("%Y-%m-%d %H:%M", true),
+      ("%Y-%m-%d %H:%M:%S", true),
+      ("%Y-%m-%dT%H:%M:%S", true),
+    ],
+  ),
+  (
+    DateFormat::US,
+    &[
+      ("%Y/%m/%d", false),
+      ("%Y/%m/%d %H:%M", true),
+      ("%Y/%m/%d %H:%M:%S", true),
+    ],
+  ),
+  (
+    DateFormat::Local,
+    &[("%m/%d/%Y", false), ("%m/%d/%Y %H:%M", true)],
+  ),
+  (
+    DateFormat::DayMonthYear,
+    &[("%d/%m/%Y", false), ("%d/%m/%Y %H:%M", true)],
+  ),
+  (
+    DateFormat::Friendly,
+    &[
+      ("%b %d, %Y", false),
+      ("%B %d, %Y", false),
+      ("%b %d, %Y %H:%M", true),
+      ("%d %b %Y", false),
+      ("%d %B %Y", false),
+    ],
+  ),
+];
+
+/// Returns the [FieldType] of the column that contains the `values`. Only the first
+/// [SAMPLE_ROW_COUNT] values are sampled and the empty values are ignored. The column is a
+/// [FieldType::RichText] column if no other type matches all the sampled values.
+pub fn infer_field_type(values: &[&str]) -> FieldType {
+  let samples = values
+    .iter()
+    .take(SAMPLE_ROW_COUNT)
+    .map(|value| value.trim())
+    .filter(|value| !value.is_empty())
+    .collect::<Vec<&str>>();
+
+  if samples.is_empty() {
+    FieldType::RichText
+  } else if samples.iter().all(|value| parse_checkbox(value).is_some()) {
+    FieldType::Checkbox
+  } else if samples.iter().all(|value| parse_number(value).is_some()) {
+    FieldType::Number
+  } else if date_formats_of(&samples).is_some() {
+    FieldType::DateTime
+  } else if samples.iter().all(|value| is_url(value)) {
+    FieldType::URL
+  } else if is_low_cardinality(samples.iter().copied()) {
+    FieldType::SingleSelect
+  } else if samples
+    .iter()
+    .any(|value| value.contains(SELECT_OPTION_SEPARATOR))
+    && is_low_cardinality(samples.iter().flat_map(|value| split_options(value)))
+  {
+    FieldType::MultiSelect
+  } else {
+    FieldType::RichText
+  }
+}
+
+/// Returns true if the [FieldType] can be inferred from a CSV column, so it's also one of the
+/// field types that the user can choose when previewing the import.
+pub fn is_inferable_field_type(field_type: &FieldType) -> bool {
+  matches!(
+    field_type,
+    FieldType::RichText
+      | FieldType::Number
+      | FieldType::DateTime
+      | FieldType::Checkbox
+      | FieldType::URL
+      | FieldType::SingleSelect
+      | FieldType::MultiSelect
+  )
+}
+
+/// A column of the imported CSV. It holds the [Field] built for the column and converts the
+/// contents of the column to the cells of the field.
+pub(crate) struct ImportColumn {
+  pub(crate) field: Field,
+  field_type: FieldType,
+  date_formats: DateFormats,
+  select_type_option: Option<Box<dyn SelectTypeOptionSharedAction>>,
+  option_ids_by_name: HashMap<String, String>,
+  has_new_options: bool,
+  /// True if a value couldn't be converted to the type of the column, so the column became a
+  /// [FieldType::RichText] column. See [ImportColumn::cell_from_str].
+  is_fallen_back_to_text: bool,
+}
+
+impl ImportColumn {
+  /// Builds the field of the column. The type option is configured by the `values` of the
+  /// column, e.g. the options of a select field or the format of a number field.
+  pub(crate) fn new(
+    name: String,
+    field_type: FieldType,
+    values: &[&str],
+    is_primary: bool,
+  ) -> Self {
+    let mut date_formats: DateFormats = &[];
+    let mut option_ids_by_name = HashMap::new();
+    let type_option_data: TypeOptionData = match field_type {
+      FieldType::Number => {
+        let mut type_option = NumberTypeOption::new();
+        type_option.set_format(number_format_of(values));
+        type_option.into()
+      },
+      FieldType::DateTime => {
+        let samples = values
+          .iter()
+          .map(|value| value.trim())
+          .filter(|value| !value.is_empty())
+          .collect::<Vec<&str>>();
+        let mut type_option = DateTypeOption::new();
+        if let Some((date_format, formats)) = date_formats_of(&samples) {
+          type_option.date_format = date_format;
+          date_formats = formats;
+        }
+        type_option.into()
+      },
+      FieldType::SingleSelect => {
+        let mut type_option = SingleSelectTypeOption::default();
+        for option in select_options_of(values.iter().map(|value| value.trim()), &type_option) {
+          option_ids_by_name.insert(option.name.clone(), option.id.clone());
+          type_option.options.push(option);
+        }
+        type_option.into()
+      },
+      FieldType::MultiSelect => {
+        let mut type_option = MultiSelectTypeOption::default();
+        for option in select_options_of(
+          values.iter().flat_map(|value| split_options(value)),
+          &type_option,
+        ) {
+          option_ids_by_name.insert(option.name.clone(), option.id.clone());
+          type_option.options.push(option);
+        }
+        type_option.into()
+      },
+      _ => default_type_option_data_from_type(&field_type),
+    };
+
+    let field = Field::new(gen_field_id(), name, field_type.into(), is_primary)
+      .with_type_option_data(field_type, type_option_data);
+    let select_type_option = match field_type {
+      FieldType::SingleSelect | FieldType::MultiSelect => {
+        select_type_option_from_field(&field).ok()
+      },
+      _ => None,
+    };
+    Self {
+      field,
+      field_type,
+      date_formats,
+      select_type_option,
+      option_ids_by_name,
+      has_new_options: false,
+      is_fallen_back_to_text: false,
+    }
+  }
+
+  /// Returns the field type and the type option of the field if they changed after the values
+  /// passed to [ImportColumn::new]. It happens when a large CSV is imported, since only its first
+  /// rows are used to build the fields: the select field gets new options, or the column falls
+  /// back to text.
+  pub(crate) fn updated_type_option(&self) -> Option<(FieldType, TypeOptionData)> {
+    if self.is_fallen_back_to_text {
+      let type_option_data = default_type_option_data_from_type(&FieldType::RichText);
+      return Some((FieldType::RichText, type_option_data));
+    }
+    if !self.has_new_options {
+      return None;
+    }
+    let type_option = self.select_type_option.as_ref()?;
+    Some((self.field_type, type_option.to_type_option_data()))
+  }
+
+  /// Converts the content of the CSV cell to the [Cell] of the field. If the content can't be
+  /// converted, the column falls back to a [FieldType::RichText] column and the content is kept
+  /// as text. The cells converted before keep their type, and are displayed as text by the
+  /// text field.
+  pub(crate) fn cell_from_str(&mut self, content: &str) -> Cell {
+    let content = content.trim();
+    if let Some(cell) = self.typed_cell_from_str(content) {
+      return cell;
+    }
+    if !content.is_empty() && self.field_type != FieldType::RichText {
+      self.fall_back_to_text();
+      if let Some(cell) = self.typed_cell_from_str(content) {
+        return cell;
+      }
+    }
+    new_cell_builder(self.field_type)
+      .insert_str_value(CELL_DATA, "".to_string())
+      .build()
+  }
+
+  /// Keeps the type option of the inferred type, which the cells that were already converted
+  /// need to be displayed as text.
+  fn fall_back_to_text(&mut self) {
+    let field_type = FieldType::RichText;
+    self.field.field_type = field_type.into();
+    self.field.type_options.insert(
+      field_type.to_string(),
+      default_type_option_data_from_type(&field_type),
+    );
+    self.field_type = field_type;
+    self.is_fallen_back_to_text = true;
+  }
+
+  fn typed_cell_from_str(&mut self, content: &str) -> Option<Cell> {
+    match self.field_type {
+      FieldType::RichText => Some(
+        new_cell_builder(self.field_type)
+          .insert_str_value(CELL_DATA, content.to_string())
+          .build(),
+      ),
+      _ if content.is_empty() => None,
+      FieldType::Number => {
+        let (number, _) = parse_number(content)?;
+        apply_cell_changeset(number, None, &self.field, None).ok()
+      },
+      FieldType::DateTime => {
+        // The user may choose the date type for a column that has no recognized date format.
+        let (timestamp, include_time) = parse_date(content, self.date_formats).or_else(|| {
+          DATE_FORMATS
+            .iter()
+            .find_map(|(_, formats)| parse_date(content, formats))
+        })?;
+        Some(insert_date_cell(timestamp, Some(include_time), &self.field))
+      },
+      FieldType::Checkbox => Some(insert_checkbox_cell(parse_checkbox(content)?, &self.field)),
+      FieldType::URL => Some(insert_url_cell(content.to_string(), &self.field)),
+      FieldType::SingleSelect => {
+        let option_id = self.get_or_create_option(content)?;
+        Some(SelectOptionIds::from(vec![option_id]).to_cell_data(self.field_type))
+      },
+      FieldType::MultiSelect => {
+        let option_ids = split_options(content)
+          .filter_map(|name| self.get_or_create_option(name))
+          .collect::<Vec<String>>();
+        Some(SelectOptionIds::from(option_ids).to_cell_data(self.field_type))
+      },
+      _ => None,
+    }
+  }
+
+  fn get_or_create_option(&mut self, name: &str) -> Option<String> {
+    if let Some(option_id) = self.option_ids_by_name.get(name) {
+      return Some(option_id.clone());
+    }
+
+    let type_option = self.select_type_option.as_mut()?;
+    let option = type_option.create_option(name);
+    type_option.mut_options().push(option.clone());
+    self
+      .option_ids_by_name
+      .insert(option.name.clone(), option.id.clone());
+    self.has_new_options = true;
+    Some(option.id)
+  }
+}
+
+fn parse_checkbox(value: &str) -> Option<bool> {
+  match value.to_lowercase().as_str() {
+    "yes" | "true" => Some(true),
+    "no" | "false" => Some(false),
+    _ => None,
+  }
+}
+
+/// Parses the number and returns it without the currency symbol, the percent sign and the
+/// thousands separators, along with the [NumberFormat] that the symbol implies.
+fn parse_number(value: &str) -> Option<(String, NumberFormat)> {
+  let value = value.trim();
+  let (value, negative) = match value.strip_prefix('-') {
+    Some(value) => (value.trim_start(), true),
+    None => (value, false),
+  };
+
+  let (value, format) = if let Some(value) = value.strip_suffix('%') {
+    (value, NumberFormat::Percent)
+  } else if let Some(value) = value.strip_prefix('$') {
+    (value, NumberFormat::USD)
+  } else if let Some(value) = value.strip_prefix('€') {
+    (value, NumberFormat::EUR)
+  } else if let Some(value) = value.strip_prefix('£') {
+    (value, NumberFormat::Pound)
+  } else if let Some(value) = value.strip_prefix('¥') {
+    (value, NumberFormat::Yuan)
+  } else {
+    (value, NumberFormat::Num)
+  };
+
+  let number = strip_thousands_separators(value.trim())?;
+  // Only the plain decimal notation, parse::<f64> also accepts "inf", "NaN" and "1e5"
+  let is_decimal = number.chars().any(|c| c.is_ascii_digit())
+    && number.chars().all(|c| c.is_ascii_digit() || c == '.');
+  if !is_decimal || !number.parse::<f64>().map_or(false, |num| num.is_finite()) {
+    return None;
+  }
+
+  if negative {
+    Some((format!("-{}", number), format))
+  } else {
+    Some((number, format))
+  }
+}
+
+/// Removes the thousands separators of the integer part, as in "1,234.5". Returns None if a comma
+/// isn't a thousands separator, since "1,5" or "1,2" is more likely a list than a number.
+fn strip_thousands_separators(value: &str) -> Option<String> {
+  let (integer, fraction) = match value.split_once('.') {
+    Some((integer, fraction)) => (integer, fraction),
+    None => (value, ""),
+  };
+  if fraction.contains(',') {
+    return None;
+  }
+  if integer.contains(',') {
+    let mut groups = integer.split(',');
+    let first = groups.next()?;
+    if first.is_empty() || first.len() > 3 || groups.any(|group| group.len() != 3) {
+      return None;
+    }
+  }
+  Some(value.replace(',', ""))
+}
+
+/// Returns the [NumberFormat] shared by all the numbers of the column. If the numbers use
+/// different formats, the plain [NumberFormat::Num] is used.
+fn number_format_of(values: &[&str]) -> NumberFormat {
+  let mut formats = values
+    .iter()
+    .filter_map(|value| parse_number(value))
+    .map(|(_, format)| format);
+  match formats.next() {
+    Some(format) if formats.all(|other| other == format) => format,
+    _ => NumberFormat::Num,
+  }
+}
+
+/// Parses the date with the `formats` and returns its timestamp and whether it contains the
+/// time. The dates are in the local timezone unless they carry an offset.
+fn parse_date(value: &str, formats: DateFormats) -> Option<(i64, bool)> {
+  if let Ok(date_time) = DateTime::parse_from_rfc3339(value) {
+    return Some((date_time.timestamp(), true));
+  }
+
+  formats.iter().find_map(|(format, include_time)| {
+    let naive_date_time = if *include_time {
+      NaiveDateTime::parse_from_str(value, format).ok()?
+    } else {
+      NaiveDate::parse_from_str(value, format)
+        .ok()?
+        .and_hms_opt(0, 0, 0)?
+    };
+    let date_time = Local.from_local_datetime(&naive_date_time).earliest()?;
+    Some((date_time.timestamp(), *include_time))
+  })
+}
+
+/// Returns the first group of [DATE_FORMATS] that parses all the dates.
+fn date_formats_of(values: &[&str]) -> Option<(DateFormat, DateFormats)> {
+  if values.is_empty() {
+    return None;
+  }
+
+  DATE_FORMATS
+    .iter()
+    .find(|(_, formats)| {
+      values
+        .iter()
+        .all(|value| parse_date(value, formats).is_some())
+    })
+    .copied()
+}
+
+/// The value is a URL if it has a http(s) scheme or if it looks like a domain, for example,
+/// "appflowy.io".
+fn is_url(value: &str) -> bool {
+  if value.starts_with("http://") || value.starts_with("https://") {
+    return url::Url::parse(value).is_ok();
+  }
+
+  if value.contains(char::is_whitespace) {
+    return false;
+  }
+  match url::Url::parse(&format!("https://{}", value)) {
+    Ok(url) => url.domain().map_or(false, |domain| {
+      domain.contains('.')
+        && domain.rsplit('.').next().map_or(false, |tld| {
+          tld.len() >= 2 && tld.chars().all(char::is_alphabetic)
+        })
+    }),
+    Err(_) => false,
+  }
+}
+
+/// The column has a low cardinality if it has a few distinct values and each of them is used
+/// by two rows on average.
+fn is_low_cardinality<'a>(values: impl Iterator<Item = &'a str>) -> bool {
+  let mut count = 0;
+  let mut distinct_values = HashSet::new();
+  for value in values {
+    count += 1;
+    distinct_values.insert(value);
+  }
+  !distinct_values.is_empty()
+    && distinct_values.len() <= MAX_SELECT_OPTION_COUNT
+    && distinct_values.len() * 2 <= count
+}
+
+fn split_options(value: &str) -> impl Iterator<Item = &str> {
+  value
+    .split(SELECT_OPTION_SEPARATOR)
+    .map(|name| name.trim())
+    .filter(|name| !name.is_empty())
+}
+
+/// Creates an option for each distinct name, in the order of their first appearance.
+fn select_options_of<'a, T>(
+  names: impl Iterator<Item = &'a str>,
+  type_option: &T,
+) -> Vec<SelectOption>
+where
+  T: SelectTypeOptionSharedAction + Clone,
+{
+  let mut type_option = type_option.clone();
+  let mut options: Vec<SelectOption> = vec![];
+  for name in names.filter(|name| !name.is_empty()) {
+    if options.iter().all(|option| option.name != name) {
+      let option = type_option.create_option(name);
+      type_option.mut_options().push(option.clone());
+      options.push(option);
+    }
+  }
+  options
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::entities::FieldType;
+  use crate::services::field::NumberFormat;
+
+  use super::{infer_field_type, parse_number};
+
+  #[test]
+  fn infer_field_type_test() {
+    assert_eq!(infer_field_type(&["1", "2.5", "-3", ""]), FieldType::Number);
+    assert_eq!(
+      infer_field_type(&["Yes", "no", "TRUE"]),
+      FieldType::Checkbox
+    );
+    assert_eq!(
+      infer_field_type(&["May 26, 2023", "May 22, 2023"]),
+      FieldType::DateTime
+    );
+    assert_eq!(
+      infer_field_type(&[
+        "2023-05-26",
+        "2023-05-22 10:30",
+        "2023-05-22T10:30:00+08:00"
+      ]),
+      FieldType::DateTime
+    );
+    assert_eq!(
+      infer_field_type(&["26/05/2023", "05/06/2023"]),
+      FieldType::DateTime
+    );
+    assert_eq!(
+      infer_field_type(&["https://appflowy.io", "appflowy.io"]),
+      FieldType::URL
+    );
+    assert_eq!(
+      infer_field_type(&["Done", "Todo", "Done", "Todo", "Doing", "Done"]),
+      FieldType::SingleSelect
+    );
+    assert_eq!(
+      infer_field_type(&["Google,Facebook", "Google", "Facebook,Twitter", "Twitter"]),
+      FieldType::MultiSelect
+    );
+    assert_eq!(
+      infer_field_type(&["tag 1", "tag 2", "tag 3"]),
+      FieldType::RichText
+    );
+    assert_eq!(infer_field_type(&["", ""]), FieldType::RichText);
+  }
+
+  #[test]
+  fn parse_number_with_format_test() {
+    assert_eq!(
+      parse_number("$1,234.50"),
+      Some(("1234.50".to_string(), NumberFormat::USD))
+    );
+    assert_eq!(
+      parse_number("-€3"),
+      Some(("-3".to_string(), NumberFormat::EUR))
+    );
+    assert_eq!(
+      parse_number("12.5%"),
+      Some(("12.5".to_string(), NumberFormat::Percent))
+    );
+    assert_eq!(parse_number("12 apples"), None);
+  }
+
+  #[test]
+  fn parse_number_with_commas_test() {
+    assert_eq!(
+      parse_number("1,234,567"),
+      Some(("1234567".to_string(), NumberFormat::Num))
+    );
+    assert_eq!(parse_number("1,5"), None);
+    assert_eq!(parse_number("1,2"), None);
+    assert_eq!(parse_number("12,34.5"), None);
+    assert_eq!(parse_number(",123"), None);
+    assert_eq!(parse_number("1.5,000"), None);
+    assert_eq!(
+      infer_field_type(&["1,2", "2,3", "1,3", "1,2"]),
+      FieldType::MultiSelect
+    );
+  }
+
+  #[test]
+  fn parse_number_without_special_values_test() {
+    assert_eq!(parse_number("inf"), None);
+    assert_eq!(parse_number("-infinity"), None);
+    assert_eq!(parse_number("NaN"), None);
+    assert_eq!(parse_number("1e5"), None);
+    assert_eq!(parse_number("."), None);
+    assert_eq!(parse_number(&"9".repeat(400)), None);
+  }
+}
@@ -0,0 +1,65 @@
+use collab_database::database::Database;
+use serde_json::{json, Map, Value};
+
+use flowy_error::{FlowyError, FlowyResult};
+
+use crate::services::share::{ExportValue, RelatedRowNames};
+
+/// Exports the database to JSON. Unlike the CSV export, the cells keep their types: numbers and
+/// checkboxes are JSON numbers and booleans, select options and linked rows are their names and
+/// dates are ISO-8601 strings in the time zone of their field.
+///
+/// The JSON contains the fields of the database and its rows, whose cells are keyed by the id of
+/// the field:
+/// ```json
+/// {
+///   "fields": [{ "id": "a1b2", "name": "Price", "field_type": 1 }],
+///   "rows": [{ "id": "c3d4", "cells": { "a1b2": 1.5 } }]
+/// }
+/// ```
+pub struct JSONExport;
+impl JSONExport {
+  pub fn export_database(
+    &self,
+    database: &Database,
+    related_row_names: &RelatedRowNames,
+  ) -> FlowyResult<String> {
+    let inline_view_id = database.get_inline_view_id();
+    let fields = database.get_fields_in_view(&inline_view_id, None);
+    let field_values = fields
+      .iter()
+      .map(|field| {
+        json!({
+          "id": field.id,
+          "name": field.name,
+          "field_type": field.field_type,
+        })
+      })
+      .collect::<Vec<Value>>();
+
+    let row_values = database
+      .get_row_orders_for_view(&inline_view_id)
+      .into_iter()
+      .filter_map(|row_order| database.get_row_detail(&row_order.id))
+      .map(|row_detail| {
+        let cells = fields
+          .iter()
+          .map(|field| {
+            let value = ExportValue::from_row(&row_detail.row, field, related_row_names);
+            (field.id.clone(), value.to_json())
+          })
+          .collect::<Map<String, Value>>();
+        json!({
+          "id": row_detail.row.id.as_str(),
+          "cells": cells,
+        })
+      })
+      .collect::<Vec<Value>>();
+
+    let value = json!({
+      "fields": field_values,
+      "rows": row_values,
+    });
+    serde_json::to_string_pretty(&value).map_err(|e| FlowyError::internal().with_context(e))
+  }
+}
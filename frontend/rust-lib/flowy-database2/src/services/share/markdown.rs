@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use collab_database::fields::Field;
+use collab_database::rows::RowDetail;
+
+use crate::services::share::{stringify_export_cell, RelatedRowNames, ViewExportData};
+
+/// Exports the rows of a view to Markdown tables.
+pub struct MarkdownExport;
+impl MarkdownExport {
+  /// Exports the rows of the view as the user sees them. If the rows are grouped, each group is a
+  /// heading followed by the table of its rows.
+  pub fn export_view(&self, data: &ViewExportData) -> String {
+    data
+      .groups
+      .iter()
+      .map(|group| {
+        let table = self.export_table(&data.fields, &group.rows, &data.related_row_names);
+        match &group.name {
+          None => table,
+          Some(name) => format!("## {}\n\n{}", name, table),
+        }
+      })
+      .collect::<Vec<String>>()
+      .join("\n")
+  }
+
+  pub fn export_table(
+    &self,
+    fields: &[Field],
+    rows: &[Arc<RowDetail>],
+    related_row_names: &RelatedRowNames,
+  ) -> String {
+    let mut markdown = String::new();
+    let header = fields
+      .iter()
+      .map(|field| escape_table_cell(&field.name))
+      .collect::<Vec<String>>();
+    push_table_row(&mut markdown, &header);
+    push_table_row(&mut markdown, &vec!["---".to_string(); fields.len()]);
+
+    for row_detail in rows {
+      let cells = fields
+        .iter()
+        .map(|field| {
+          escape_table_cell(&stringify_export_cell(
+            &row_detail.row,
+            field,
+            related_row_names,
+          ))
+        })
+        .collect::<Vec<String>>();
+      push_table_row(&mut markdown, &cells);
+    }
+    markdown
+  }
+}
+
+fn push_table_row(markdown: &mut String, cells: &[String]) {
+  markdown.push('|');
+  for cell in cells {
+    markdown.push(' ');
+    markdown.push_str(cell);
+    markdown.push_str(" |");
+  }
+  markdown.push('\n');
+}
+
+/// The pipes would end the cell and the line breaks would end the row.
+fn escape_table_cell(content: &str) -> String {
+  content
+    .replace('|', "\\|")
+    .replace("\r\n", "<br>")
+    .replace('\n', "<br>")
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::services::share::markdown::escape_table_cell;
+
+  #[test]
+  fn escape_table_cell_test() {
+    assert_eq!(escape_table_cell("a|b"), "a\\|b");
+    assert_eq!(escape_table_cell("line 1\nline 2"), "line 1<br>line 2");
+  }
+}
@@ -1 +1,9 @@
 pub mod csv;
+pub mod json;
+pub mod markdown;
+mod value;
+mod view;
+pub mod xlsx;
+
+pub use value::*;
+pub use view::*;
@@ -0,0 +1,285 @@
+use std::collections::HashMap;
+
+use chrono::{FixedOffset, Local, NaiveDateTime, Offset, SecondsFormat, TimeZone};
+use collab_database::fields::Field;
+use collab_database::rows::{Cell, Row};
+use serde_json::{json, Value};
+
+use crate::entities::FieldType;
+use crate::services::cell::stringify_cell_data;
+use crate::services::field::checklist_type_option::ChecklistCellData;
+use crate::services::field::{
+  select_type_option_from_field, CheckboxCellData, DateCellData, DateTypeOption, FormulaCellData,
+  FormulaValue, RelationCellData, SelectOptionIds, TimestampCellData, TimestampTypeOption,
+  TypeOptionCellExt,
+};
+
+/// The primary cell text of the rows that the relation cells link to, keyed by the row id. The
+/// exports write the linked rows by their names, which mean something outside the database.
+#[derive(Debug, Clone, Default)]
+pub struct RelatedRowNames(HashMap<String, String>);
+
+impl RelatedRowNames {
+  pub fn new(names: HashMap<String, String>) -> Self {
+    Self(names)
+  }
+
+  /// Returns the names of the linked rows. The rows that can't be found, e.g. because they were
+  /// deleted, are written by their id.
+  pub fn names(&self, cell_data: &RelationCellData) -> Vec<String> {
+    cell_data
+      .row_ids
+      .iter()
+      .map(|row_id| {
+        self
+          .0
+          .get(row_id.as_str())
+          .cloned()
+          .unwrap_or_else(|| row_id.to_string())
+      })
+      .collect()
+  }
+
+  /// Returns the names of the rows that the relation cell links to, separated by commas.
+  pub fn stringify_cell(&self, cell: &Cell) -> String {
+    self.names(&RelationCellData::from(cell)).join(", ")
+  }
+}
+
+/// The typed value of a cell. Unlike [stringify_cell_data], it keeps the type of the cell, so the
+/// exports that support typed values, e.g. JSON and XLSX, don't turn everything into text.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportValue {
+  Empty,
+  Text(String),
+  Number(f64),
+  Bool(bool),
+  /// Timestamp in seconds. The `offset` is the time zone the date is displayed in, so the date
+  /// is exported as the user sees it.
+  Date {
+    timestamp: i64,
+    include_time: bool,
+    offset: FixedOffset,
+  },
+  List(Vec<String>),
+  /// The name of each task and whether it's completed.
+  Checklist(Vec<(String, bool)>),
+}
+
+impl ExportValue {
+  /// Returns the value of the `field` in the `row`. The relation cells are the names of the
+  /// linked rows, which are looked up in the `related_row_names`.
+  pub fn from_row(row: &Row, field: &Field, related_row_names: &RelatedRowNames) -> Self {
+    let field_type = FieldType::from(field.field_type);
+    let cell = match cell_of_row(row, field) {
+      None => return ExportValue::Empty,
+      Some(cell) => cell,
+    };
+
+    match field_type {
+      FieldType::Number => TypeOptionCellExt::new_with_cell_data_cache(field, None)
+        .get_type_option_cell_data_handler(&field_type)
+        .and_then(|handler| handler.handle_numeric_cell(&cell))
+        .map_or(ExportValue::Empty, ExportValue::Number),
+      FieldType::DateTime => {
+        let cell_data = DateCellData::from(&cell);
+        match cell_data.timestamp {
+          None => ExportValue::Empty,
+          Some(timestamp) => {
+            let type_option = field
+              .get_type_option::<DateTypeOption>(field_type)
+              .unwrap_or_default();
+            let offset = NaiveDateTime::from_timestamp_opt(timestamp, 0)
+              .map(|date_time| type_option.get_timezone_offset(date_time))
+              .unwrap_or_else(local_offset);
+            ExportValue::Date {
+              timestamp,
+              include_time: cell_data.include_time,
+              offset,
+            }
+          },
+        }
+      },
+      FieldType::LastEditedTime | FieldType::CreatedTime => {
+        let type_option = field
+          .get_type_option::<TimestampTypeOption>(field_type)
+          .unwrap_or_default();
+        match TimestampCellData::from(&cell).timestamp {
+          None => ExportValue::Empty,
+          Some(timestamp) => ExportValue::Date {
+            timestamp,
+            include_time: type_option.include_time,
+            offset: local_offset(),
+          },
+        }
+      },
+      FieldType::SingleSelect | FieldType::MultiSelect => {
+        let names = select_type_option_from_field(field)
+          .map(|type_option| {
+            type_option
+              .get_selected_options(SelectOptionIds::from(&cell))
+              .select_options
+              .into_iter()
+              .map(|option| option.name)
+              .collect::<Vec<String>>()
+          })
+          .unwrap_or_default();
+        match field_type {
+          FieldType::SingleSelect => names
+            .into_iter()
+            .next()
+            .map_or(ExportValue::Empty, ExportValue::Text),
+          _ => ExportValue::List(names),
+        }
+      },
+      FieldType::Checkbox => ExportValue::Bool(CheckboxCellData::from(&cell).is_check()),
+      FieldType::Checklist => {
+        let cell_data = ChecklistCellData::from(&cell);
+        let tasks = cell_data
+          .options
+          .iter()
+          .map(|option| {
+            let is_completed = cell_data.selected_option_ids.contains(&option.id);
+            (option.name.clone(), is_completed)
+          })
+          .collect();
+        ExportValue::Checklist(tasks)
+      },
+      FieldType::Relation => {
+        ExportValue::List(related_row_names.names(&RelationCellData::from(&cell)))
+      },
+      FieldType::Formula => match FormulaCellData::from(&cell).value {
+        FormulaValue::Empty => ExportValue::Empty,
+        FormulaValue::Number(value) => ExportValue::Number(value),
+        FormulaValue::Text(value) => ExportValue::Text(value),
+        FormulaValue::Boolean(value) => ExportValue::Bool(value),
+        FormulaValue::Date(timestamp) => ExportValue::Date {
+          timestamp,
+          include_time: true,
+          offset: local_offset(),
+        },
+      },
+      FieldType::RichText
+      | FieldType::URL
+      | FieldType::Lookup
+      | FieldType::Person
+      | FieldType::Files
+      | FieldType::AutoNumber => {
+        let text = stringify_cell_data(&cell, &field_type, &field_type, field);
+        if text.is_empty() {
+          ExportValue::Empty
+        } else {
+          ExportValue::Text(text)
+        }
+      },
+    }
+  }
+
+  pub fn to_json(&self) -> Value {
+    match self {
+      ExportValue::Empty => Value::Null,
+      ExportValue::Text(text) => json!(text),
+      ExportValue::Number(value) => json!(value),
+      ExportValue::Bool(value) => json!(value),
+      ExportValue::Date {
+        timestamp,
+        include_time,
+        offset,
+      } => json!(iso_8601(*timestamp, *include_time, *offset)),
+      ExportValue::List(values) => json!(values),
+      ExportValue::Checklist(tasks) => Value::Array(
+        tasks
+          .iter()
+          .map(|(name, is_completed)| json!({ "name": name, "completed": is_completed }))
+          .collect(),
+      ),
+    }
+  }
+}
+
+/// Returns the cell of the `field` in the `row`. The cells of the [FieldType::LastEditedTime] and
+/// [FieldType::CreatedTime] fields are generated from the timestamps of the row.
+pub fn cell_of_row(row: &Row, field: &Field) -> Option<Cell> {
+  match FieldType::from(field.field_type) {
+    FieldType::LastEditedTime => Some(Cell::from(TimestampCellData::new(row.modified_at))),
+    FieldType::CreatedTime => Some(Cell::from(TimestampCellData::new(row.created_at))),
+    _ => row.cells.get(&field.id).cloned(),
+  }
+}
+
+/// Returns the text of the `field` in the `row`, as it's displayed in the database.
+pub fn stringify_row_cell(row: &Row, field: &Field) -> String {
+  let field_type = FieldType::from(field.field_type);
+  cell_of_row(row, field)
+    .map(|cell| stringify_cell_data(&cell, &field_type, &field_type, field))
+    .unwrap_or_default()
+}
+
+/// The offset of the local time zone, which the timestamps and the formula dates are displayed in.
+fn local_offset() -> FixedOffset {
+  Local::now().offset().fix()
+}
+
+/// Same as [stringify_row_cell], except that the relation cells are the names of the linked rows
+/// instead of their ids.
+pub fn stringify_export_cell(
+  row: &Row,
+  field: &Field,
+  related_row_names: &RelatedRowNames,
+) -> String {
+  if FieldType::from(field.field_type).is_relation() {
+    return row
+      .cells
+      .get(&field.id)
+      .map(|cell| related_row_names.stringify_cell(cell))
+      .unwrap_or_default();
+  }
+  stringify_row_cell(row, field)
+}
+
+/// Formats the timestamp as an ISO-8601 date, or date and time if `include_time` is true, in the
+/// time zone of the `offset`.
+pub fn iso_8601(timestamp: i64, include_time: bool, offset: FixedOffset) -> String {
+  match offset.timestamp_opt(timestamp, 0).single() {
+    None => "".to_string(),
+    Some(date_time) if include_time => date_time.to_rfc3339_opts(SecondsFormat::Secs, true),
+    Some(date_time) => date_time.format("%Y-%m-%d").to_string(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::FixedOffset;
+  use collab_database::rows::RowId;
+
+  use crate::services::field::RelationCellData;
+  use crate::services::share::{iso_8601, RelatedRowNames};
+
+  #[test]
+  fn iso_8601_test() {
+    let utc = FixedOffset::east_opt(0).unwrap();
+    assert_eq!(iso_8601(1647251762, false, utc), "2022-03-14");
+    assert_eq!(iso_8601(1647251762, true, utc), "2022-03-14T09:56:02Z");
+
+    // 2022-03-14 00:00 in UTC+8 is still 2022-03-13 in UTC
+    let utc_8 = FixedOffset::east_opt(8 * 3600).unwrap();
+    assert_eq!(iso_8601(1647187200, false, utc), "2022-03-13");
+    assert_eq!(iso_8601(1647187200, false, utc_8), "2022-03-14");
+    assert_eq!(
+      iso_8601(1647187200, true, utc_8),
+      "2022-03-14T00:00:00+08:00"
+    );
+  }
+
+  #[test]
+  fn related_row_names_test() {
+    let names = RelatedRowNames::new([("row_1".to_string(), "A".to_string())].into());
+    let cell_data = RelationCellData {
+      row_ids: vec![
+        RowId::from("row_1".to_string()),
+        RowId::from("row_2".to_string()),
+      ],
+    };
+    assert_eq!(names.names(&cell_data), vec!["A", "row_2"]);
+  }
+}
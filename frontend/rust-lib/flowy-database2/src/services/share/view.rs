@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use collab_database::fields::Field;
+use collab_database::rows::RowDetail;
+
+use crate::services::share::RelatedRowNames;
+
+/// The fields and rows of a database view as the user sees them.
+pub struct ViewExportData {
+  /// The visible fields, in the order of the view.
+  pub fields: Vec<Field>,
+  /// The rows of the view with the filters and sorts applied. If the rows aren't grouped, there
+  /// is a single group without a name.
+  pub groups: Vec<ExportGroup>,
+  /// The names of the rows that the relation cells link to.
+  pub related_row_names: RelatedRowNames,
+}
+
+impl ViewExportData {
+  /// Returns true if the rows are split by the groups of the view, e.g. the columns of a board.
+  pub fn is_grouped(&self) -> bool {
+    self.groups.iter().any(|group| group.name.is_some())
+  }
+}
+
+pub struct ExportGroup {
+  pub name: Option<String>,
+  pub rows: Vec<Arc<RowDetail>>,
+}
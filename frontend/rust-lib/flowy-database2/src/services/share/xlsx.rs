@@ -0,0 +1,231 @@
+use std::fs::File;
+use std::io::{BufWriter, Seek, Write};
+
+use collab_database::database::Database;
+use collab_database::fields::Field;
+use collab_database::rows::Row;
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use flowy_error::{FlowyError, FlowyResult};
+
+use crate::services::share::{stringify_export_cell, ExportValue, RelatedRowNames};
+
+const CONTENT_TYPES_XML: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/><Override PartName="/xl/worksheets/sheet1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/><Override PartName="/xl/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.styles+xml"/></Types>"#;
+
+const RELS_XML: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/></Relationships>"#;
+
+const WORKBOOK_XML: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets><sheet name="Sheet1" sheetId="1" r:id="rId1"/></sheets></workbook>"#;
+
+const WORKBOOK_RELS_XML: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/></Relationships>"#;
+
+/// The cell formats referenced by the `s` attribute of the cells: 0 is the default format, 1 is
+/// the built-in date format and 2 is the built-in date and time format.
+const STYLES_XML: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<styleSheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><fonts count="1"><font><sz val="11"/><name val="Calibri"/></font></fonts><fills count="2"><fill><patternFill patternType="none"/></fill><fill><patternFill patternType="gray125"/></fill></fills><borders count="1"><border><left/><right/><top/><bottom/><diagonal/></border></borders><cellStyleXfs count="1"><xf numFmtId="0" fontId="0" fillId="0" borderId="0"/></cellStyleXfs><cellXfs count="3"><xf numFmtId="0" fontId="0" fillId="0" borderId="0" xfId="0"/><xf numFmtId="14" fontId="0" fillId="0" borderId="0" xfId="0" applyNumberFormat="1"/><xf numFmtId="22" fontId="0" fillId="0" borderId="0" xfId="0" applyNumberFormat="1"/></cellXfs></styleSheet>"#;
+
+const DATE_STYLE: usize = 1;
+const DATE_TIME_STYLE: usize = 2;
+
+/// The spreadsheet date of 1970-01-01. Spreadsheets store the dates as the number of days since
+/// 1899-12-30.
+const UNIX_EPOCH_SERIAL: f64 = 25569.0;
+const SECONDS_PER_DAY: f64 = 86400.0;
+
+/// Exports the database to an XLSX workbook with a single sheet. The numbers, dates and
+/// checkboxes are written as number, date and boolean cells, the other cells as text. The dates
+/// are written in the time zone they are displayed in, spreadsheets don't have time zones.
+pub struct XLSXExport;
+impl XLSXExport {
+  /// Writes the workbook straight to the file at `path`. The rows are written one at a time, so
+  /// the memory usage doesn't grow with the size of the database.
+  pub fn export_database_to_file(
+    &self,
+    database: &Database,
+    related_row_names: &RelatedRowNames,
+    path: &str,
+  ) -> FlowyResult<()> {
+    let file = File::create(path)?;
+    let mut writer =
+      self.export_database_to_writer(database, related_row_names, BufWriter::new(file))?;
+    writer.flush()?;
+    Ok(())
+  }
+
+  pub fn export_database_to_writer<W: Write + Seek>(
+    &self,
+    database: &Database,
+    related_row_names: &RelatedRowNames,
+    writer: W,
+  ) -> FlowyResult<W> {
+    let mut zip = ZipWriter::new(writer);
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+    for (name, content) in [
+      ("[Content_Types].xml", CONTENT_TYPES_XML),
+      ("_rels/.rels", RELS_XML),
+      ("xl/workbook.xml", WORKBOOK_XML),
+      ("xl/_rels/workbook.xml.rels", WORKBOOK_RELS_XML),
+      ("xl/styles.xml", STYLES_XML),
+    ] {
+      zip.start_file(name, options).map_err(zip_error)?;
+      zip.write_all(content.as_bytes())?;
+    }
+
+    zip
+      .start_file("xl/worksheets/sheet1.xml", options)
+      .map_err(zip_error)?;
+    zip.write_all(
+      br#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData>"#,
+    )?;
+
+    let inline_view_id = database.get_inline_view_id();
+    let fields = database.get_fields_in_view(&inline_view_id, None);
+    let mut sheet_row = String::new();
+    write_header_row(&mut sheet_row, &fields);
+    zip.write_all(sheet_row.as_bytes())?;
+
+    let row_orders = database.get_row_orders_for_view(&inline_view_id);
+    for (index, row_order) in row_orders.iter().enumerate() {
+      if let Some(row_detail) = database.get_row_detail(&row_order.id) {
+        sheet_row.clear();
+        // The first row of the sheet is the header.
+        write_row(
+          &mut sheet_row,
+          index + 2,
+          &row_detail.row,
+          &fields,
+          related_row_names,
+        );
+        zip.write_all(sheet_row.as_bytes())?;
+      }
+    }
+
+    zip.write_all(b"</sheetData></worksheet>")?;
+    zip.finish().map_err(zip_error)
+  }
+}
+
+fn write_header_row(sheet_row: &mut String, fields: &[Field]) {
+  sheet_row.push_str(r#"<row r="1">"#);
+  for (column, field) in fields.iter().enumerate() {
+    push_text_cell(sheet_row, &cell_reference(column, 1), &field.name);
+  }
+  sheet_row.push_str("</row>");
+}
+
+fn write_row(
+  sheet_row: &mut String,
+  row_number: usize,
+  row: &Row,
+  fields: &[Field],
+  related_row_names: &RelatedRowNames,
+) {
+  sheet_row.push_str(&format!(r#"<row r="{}">"#, row_number));
+  for (column, field) in fields.iter().enumerate() {
+    let reference = cell_reference(column, row_number);
+    match ExportValue::from_row(row, field, related_row_names) {
+      ExportValue::Empty => {},
+      ExportValue::Number(value) if value.is_finite() => {
+        sheet_row.push_str(&format!(r#"<c r="{}"><v>{}</v></c>"#, reference, value));
+      },
+      ExportValue::Bool(value) => {
+        sheet_row.push_str(&format!(
+          r#"<c r="{}" t="b"><v>{}</v></c>"#,
+          reference, value as u8
+        ));
+      },
+      ExportValue::Date {
+        timestamp,
+        include_time,
+        offset,
+      } => {
+        let local_timestamp = timestamp + offset.local_minus_utc() as i64;
+        let serial = UNIX_EPOCH_SERIAL + local_timestamp as f64 / SECONDS_PER_DAY;
+        let (serial, style) = if include_time {
+          (serial, DATE_TIME_STYLE)
+        } else {
+          (serial.floor(), DATE_STYLE)
+        };
+        sheet_row.push_str(&format!(
+          r#"<c r="{}" s="{}"><v>{}</v></c>"#,
+          reference, style, serial
+        ));
+      },
+      _ => push_text_cell(
+        sheet_row,
+        &reference,
+        &stringify_export_cell(row, field, related_row_names),
+      ),
+    }
+  }
+  sheet_row.push_str("</row>");
+}
+
+fn push_text_cell(sheet_row: &mut String, reference: &str, text: &str) {
+  sheet_row.push_str(&format!(
+    r#"<c r="{}" t="inlineStr"><is><t xml:space="preserve">{}</t></is></c>"#,
+    reference,
+    escape_xml(text)
+  ));
+}
+
+/// Returns the reference of the cell, e.g. "A1" for the first column of the first row. The
+/// `column` starts at 0 and the `row_number` at 1.
+fn cell_reference(column: usize, row_number: usize) -> String {
+  let mut letters = vec![];
+  let mut column = column + 1;
+  while column > 0 {
+    let remainder = (column - 1) % 26;
+    letters.push((b'A' + remainder as u8) as char);
+    column = (column - 1) / 26;
+  }
+  letters.iter().rev().collect::<String>() + &row_number.to_string()
+}
+
+/// Escapes the XML special characters and removes the control characters that aren't allowed in
+/// XML documents.
+fn escape_xml(text: &str) -> String {
+  let mut escaped = String::with_capacity(text.len());
+  for c in text.chars() {
+    match c {
+      '&' => escaped.push_str("&amp;"),
+      '<' => escaped.push_str("&lt;"),
+      '>' => escaped.push_str("&gt;"),
+      '"' => escaped.push_str("&quot;"),
+      '\'' => escaped.push_str("&apos;"),
+      '\t' | '\n' | '\r' => escaped.push(c),
+      c if c.is_control() => {},
+      c => escaped.push(c),
+    }
+  }
+  escaped
+}
+
+fn zip_error(error: zip::result::ZipError) -> FlowyError {
+  FlowyError::internal().with_context(error)
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::services::share::xlsx::{cell_reference, escape_xml};
+
+  #[test]
+  fn cell_reference_test() {
+    assert_eq!(cell_reference(0, 1), "A1");
+    assert_eq!(cell_reference(25, 2), "Z2");
+    assert_eq!(cell_reference(26, 3), "AA3");
+    assert_eq!(cell_reference(701, 4), "ZZ4");
+    assert_eq!(cell_reference(702, 5), "AAA5");
+  }
+
+  #[test]
+  fn escape_xml_test() {
+    assert_eq!(escape_xml("a < b & c"), "a &lt; b &amp; c");
+    assert_eq!(escape_xml("a\u{1}b"), "ab");
+  }
+}
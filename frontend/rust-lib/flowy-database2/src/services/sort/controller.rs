@@ -20,9 +20,7 @@ use crate::services::database_view::{DatabaseViewChanged, DatabaseViewChangedNot
 use crate::services::field::{
   default_order, TimestampCellData, TimestampCellDataWrapper, TypeOptionCellExt,
 };
-use crate::services::sort::{
-  ReorderAllRowsResult, ReorderSingleRowResult, Sort, SortChangeset, SortCondition,
-};
+use crate::services::sort::{ReorderAllRowsResult, ReorderSingleRowResult, Sort, SortChangeset};
 
 pub trait SortDelegate: Send + Sync {
   fn get_sort(&self, view_id: &str, sort_id: &str) -> Fut<Option<Arc<Sort>>>;
@@ -162,10 +160,15 @@ impl SortController {
     }
 
     let fields = self.delegate.get_fields(&self.view_id, None).await;
-    for sort in self.sorts.iter().rev() {
-      rows
-        .par_sort_by(|left, right| cmp_row(&left.row, &right.row, sort, &fields, &self.cell_cache));
-    }
+    rows.par_sort_by(|left, right| {
+      cmp_rows(
+        &left.row,
+        &right.row,
+        &self.sorts,
+        &fields,
+        &self.cell_cache,
+      )
+    });
     rows.iter().enumerate().for_each(|(index, row_detail)| {
       self
         .row_index_cache
@@ -245,6 +248,21 @@ impl SortController {
   }
 }
 
+/// Compares the rows by the first sort. The next sorts only break the ties of the previous ones.
+fn cmp_rows(
+  left: &Row,
+  right: &Row,
+  sorts: &[Arc<Sort>],
+  fields: &[Arc<Field>],
+  cell_data_cache: &CellCache,
+) -> Ordering {
+  sorts
+    .iter()
+    .map(|sort| cmp_row(left, right, sort, fields, cell_data_cache))
+    .find(|order| order.is_ne())
+    .unwrap_or_else(default_order)
+}
+
 fn cmp_row(
   left: &Row,
   right: &Row,
@@ -252,13 +270,14 @@ fn cmp_row(
   fields: &[Arc<Field>],
   cell_data_cache: &CellCache,
 ) -> Ordering {
-  let field_type = sort.field_type;
   match fields
     .iter()
     .find(|field_rev| field_rev.id == sort.field_id)
   {
     None => default_order(),
     Some(field_rev) => {
+      // The type of the field might have changed since the sort was created.
+      let field_type = FieldType::from(field_rev.field_type);
       let timestamp_cells = match field_type {
         FieldType::LastEditedTime | FieldType::CreatedTime => {
           let (left_cell, right_cell) = if field_type.is_created_time() {
@@ -285,7 +304,7 @@ fn cmp_row(
         field_rev,
         field_type,
         cell_data_cache,
-        sort.condition,
+        sort,
       )
     },
   }
@@ -297,15 +316,13 @@ fn cmp_cell(
   field: &Arc<Field>,
   field_type: FieldType,
   cell_data_cache: &CellCache,
-  sort_condition: SortCondition,
+  sort: &Sort,
 ) -> Ordering {
   match TypeOptionCellExt::new_with_cell_data_cache(field.as_ref(), Some(cell_data_cache.clone()))
     .get_type_option_cell_data_handler(&field_type)
   {
     None => default_order(),
-    Some(handler) => {
-      handler.handle_cell_compare(left_cell, right_cell, field.as_ref(), sort_condition)
-    },
+    Some(handler) => handler.handle_cell_compare(left_cell, right_cell, field.as_ref(), sort),
   }
 }
 
@@ -13,12 +13,16 @@ pub struct Sort {
   pub field_id: String,
   pub field_type: FieldType,
   pub condition: SortCondition,
+  pub empty_placement: EmptyPlacement,
+  pub option_order: SelectOptionOrder,
 }
 
 const SORT_ID: &str = "id";
 const FIELD_ID: &str = "field_id";
 const FIELD_TYPE: &str = "ty";
 const SORT_CONDITION: &str = "condition";
+const SORT_EMPTY_PLACEMENT: &str = "empty_placement";
+const SORT_OPTION_ORDER: &str = "option_order";
 
 impl TryFrom<SortMap> for Sort {
   type Error = anyhow::Error;
@@ -34,11 +38,21 @@ impl TryFrom<SortMap> for Sort {
           .get_i64_value(SORT_CONDITION)
           .map(SortCondition::from)
           .unwrap_or_default();
+        let empty_placement = value
+          .get_i64_value(SORT_EMPTY_PLACEMENT)
+          .map(EmptyPlacement::from)
+          .unwrap_or_default();
+        let option_order = value
+          .get_i64_value(SORT_OPTION_ORDER)
+          .map(SelectOptionOrder::from)
+          .unwrap_or_default();
         Ok(Self {
           id,
           field_id,
           field_type,
           condition,
+          empty_placement,
+          option_order,
         })
       },
       _ => {
@@ -55,6 +69,8 @@ impl From<Sort> for SortMap {
       .insert_str_value(FIELD_ID, data.field_id)
       .insert_i64_value(FIELD_TYPE, data.field_type.into())
       .insert_i64_value(SORT_CONDITION, data.condition.value())
+      .insert_i64_value(SORT_EMPTY_PLACEMENT, data.empty_placement as i64)
+      .insert_i64_value(SORT_OPTION_ORDER, data.option_order as i64)
       .build()
   }
 }
@@ -98,6 +114,45 @@ impl From<i64> for SortCondition {
   }
 }
 
+/// Where the rows whose cell is empty are placed. By default, they are placed after the other
+/// rows whatever the [SortCondition] is.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[repr(u8)]
+pub enum EmptyPlacement {
+  #[default]
+  Last = 0,
+  First = 1,
+}
+
+impl From<i64> for EmptyPlacement {
+  fn from(value: i64) -> Self {
+    match value {
+      1 => EmptyPlacement::First,
+      _ => EmptyPlacement::Last,
+    }
+  }
+}
+
+/// How the options of the select option fields are compared. Comparing them by their position
+/// in the type option keeps the order the user gave to the options, e.g. "Todo", "In progress",
+/// "Done".
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[repr(u8)]
+pub enum SelectOptionOrder {
+  #[default]
+  Name = 0,
+  Position = 1,
+}
+
+impl From<i64> for SelectOptionOrder {
+  fn from(value: i64) -> Self {
+    match value {
+      1 => SelectOptionOrder::Position,
+      _ => SelectOptionOrder::Name,
+    }
+  }
+}
+
 #[derive(Clone)]
 pub struct ReorderAllRowsResult {
   pub view_id: String,
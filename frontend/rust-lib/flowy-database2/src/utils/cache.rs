@@ -58,6 +58,10 @@ where
   pub fn is_empty(&self) -> bool {
     self.0.is_empty()
   }
+
+  pub fn keys(&self) -> Vec<TypeValueKey> {
+    self.0.keys().cloned().collect()
+  }
 }
 
 fn downcast_owned<T: 'static + Send + Sync>(type_value: TypeValue) -> Option<T> {
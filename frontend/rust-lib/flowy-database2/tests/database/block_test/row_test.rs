@@ -1,11 +1,14 @@
 use std::time::Duration;
 
+use collab_database::rows::RowId;
 use flowy_database2::entities::FieldType;
-use flowy_database2::services::field::DateCellData;
+use flowy_database2::services::cell::{insert_auto_number_cell, stringify_cell_data};
+use flowy_database2::services::field::{AutoNumberCellData, AutoNumberTypeOption, DateCellData};
 use lib_infra::util::timestamp;
 
 use crate::database::block_test::script::DatabaseRowTest;
 use crate::database::block_test::script::RowScript::*;
+use crate::database::field_test::util::create_auto_number_field;
 
 // Create a new row at the end of the grid and check the create time is valid.
 #[tokio::test]
@@ -62,3 +65,175 @@ async fn update_at_field_test() {
   let new_updated_at = DateCellData::from(&cell).timestamp.unwrap();
   assert!(old_updated_at < new_updated_at);
 }
+
+async fn add_auto_number_field(test: &DatabaseRowTest) {
+  let (params, _) = create_auto_number_field(&test.view_id);
+  test
+    .editor
+    .create_field_with_type_option(params)
+    .await
+    .unwrap();
+}
+
+async fn get_auto_number(test: &DatabaseRowTest, row_id: &RowId) -> Option<i64> {
+  let field = test.get_first_field(FieldType::AutoNumber);
+  let cell = test.editor.get_cell(&field.id, row_id).await?;
+  AutoNumberCellData::from(&cell).number
+}
+
+// The new row takes the number after the last one and is displayed with the prefix.
+#[tokio::test]
+async fn auto_number_create_row_test() {
+  let mut test = DatabaseRowTest::new().await;
+  add_auto_number_field(&test).await;
+  let row_count = test.row_details.len();
+  test
+    .run_scripts(vec![CreateEmptyRow, AssertRowCount(row_count + 1)])
+    .await;
+
+  let row_detail = test.get_rows().await.last().cloned().unwrap();
+  assert_eq!(
+    get_auto_number(&test, &row_detail.row.id).await,
+    Some(row_count as i64 + 1)
+  );
+
+  let field = test.get_first_field(FieldType::AutoNumber);
+  let cell = test
+    .editor
+    .get_cell(&field.id, &row_detail.row.id)
+    .await
+    .unwrap();
+  let s = stringify_cell_data(
+    &cell,
+    &FieldType::AutoNumber,
+    &FieldType::AutoNumber,
+    &field,
+  );
+  assert_eq!(s, format!("ID-{}", row_count + 1));
+}
+
+async fn set_next_number(test: &DatabaseRowTest, next_number: i64) {
+  let field = test.get_first_field(FieldType::AutoNumber);
+  let type_option = AutoNumberTypeOption {
+    next_number,
+    ..field
+      .get_type_option::<AutoNumberTypeOption>(FieldType::AutoNumber)
+      .unwrap()
+  };
+  test
+    .editor
+    .update_field_type_option(&field.id, type_option.into(), field.clone())
+    .await
+    .unwrap();
+}
+
+// The new row takes the next number of the type option. A next number that is already taken is
+// moved after the last number.
+#[tokio::test]
+async fn auto_number_next_number_test() {
+  let mut test = DatabaseRowTest::new().await;
+  add_auto_number_field(&test).await;
+  let row_count = test.row_details.len();
+
+  set_next_number(&test, 100).await;
+  test
+    .run_scripts(vec![CreateEmptyRow, AssertRowCount(row_count + 1)])
+    .await;
+  let row_detail = test.get_rows().await.last().cloned().unwrap();
+  assert_eq!(get_auto_number(&test, &row_detail.row.id).await, Some(100));
+
+  set_next_number(&test, 2).await;
+  test
+    .run_scripts(vec![CreateEmptyRow, AssertRowCount(row_count + 2)])
+    .await;
+  let row_detail = test.get_rows().await.last().cloned().unwrap();
+  assert_eq!(get_auto_number(&test, &row_detail.row.id).await, Some(101));
+}
+
+// The duplicated row gets its own number, the original row keeps its number.
+#[tokio::test]
+async fn auto_number_duplicate_row_test() {
+  let test = DatabaseRowTest::new().await;
+  add_auto_number_field(&test).await;
+  let row_count = test.row_details.len();
+  let original_row_id = test.row_details[0].row.id.clone();
+  let original_number = get_auto_number(&test, &original_row_id).await;
+  test
+    .editor
+    .duplicate_row(&test.view_id, &original_row_id)
+    .await;
+
+  let rows = test.get_rows().await;
+  assert_eq!(rows.len(), row_count + 1);
+  let duplicated_row = rows
+    .iter()
+    .find(|row_detail| {
+      test
+        .row_details
+        .iter()
+        .all(|original| original.row.id != row_detail.row.id)
+    })
+    .unwrap();
+  assert_eq!(
+    get_auto_number(&test, &original_row_id).await,
+    original_number
+  );
+  assert_eq!(
+    get_auto_number(&test, &duplicated_row.row.id).await,
+    Some(row_count as i64 + 1)
+  );
+}
+
+// Two devices that created a row while offline allocate the same number. Once synced, the
+// pending row of this device is renumbered after the last number and the synced row keeps its
+// number.
+#[tokio::test]
+async fn auto_number_resolve_duplicated_numbers_test() {
+  let mut test = DatabaseRowTest::new().await;
+  add_auto_number_field(&test).await;
+  let row_count = test.row_details.len();
+  test.editor.resolve_auto_numbers(true).await;
+  let mut original_numbers = vec![];
+  for row_detail in test.get_rows().await {
+    let number = get_auto_number(&test, &row_detail.row.id).await;
+    original_numbers.push((row_detail.row.id.clone(), number));
+  }
+  test.run_scripts(vec![CreateEmptyRow, CreateEmptyRow]).await;
+
+  // Simulate the row that another device synced with the number of the pending row.
+  let field = test.get_first_field(FieldType::AutoNumber);
+  let rows = test.get_rows().await;
+  let own_row_id = rows[row_count].row.id.clone();
+  let remote_row_id = rows[row_count + 1].row.id.clone();
+  let own_number = get_auto_number(&test, &own_row_id).await.unwrap();
+  test
+    .editor
+    .update_cell(
+      &test.view_id,
+      remote_row_id.clone(),
+      &field.id,
+      insert_auto_number_cell(own_number),
+    )
+    .await
+    .unwrap();
+
+  test.editor.resolve_auto_numbers(true).await;
+  assert_eq!(
+    get_auto_number(&test, &remote_row_id).await,
+    Some(own_number)
+  );
+  let own_cell = test.editor.get_cell(&field.id, &own_row_id).await.unwrap();
+  let own_cell_data = AutoNumberCellData::from(&own_cell);
+  assert_eq!(own_cell_data.number, Some(row_count as i64 + 3));
+  assert!(own_cell_data.is_pending());
+
+  // The new number is confirmed by the next sync, the synced numbers are never changed.
+  test.editor.resolve_auto_numbers(true).await;
+  let own_cell = test.editor.get_cell(&field.id, &own_row_id).await.unwrap();
+  let own_cell_data = AutoNumberCellData::from(&own_cell);
+  assert_eq!(own_cell_data.number, Some(row_count as i64 + 3));
+  assert!(!own_cell_data.is_pending());
+  for (row_id, number) in original_numbers {
+    assert_eq!(get_auto_number(&test, &row_id).await, number);
+  }
+}
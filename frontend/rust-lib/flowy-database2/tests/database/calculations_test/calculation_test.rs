@@ -85,3 +85,85 @@ async fn calculations_test() {
   ];
   test.run_scripts(scripts).await;
 }
+
+#[tokio::test]
+async fn calculations_count_test() {
+  let mut test = DatabaseCalculationTest::new().await;
+  let view_id = test.view_id.clone();
+  let text_field_id = test.get_first_field(FieldType::RichText).id;
+
+  let calculation_id = "calc_id".to_owned();
+  let mut scripts = vec![];
+  for (calculation_type, expected) in [
+    (CalculationType::Count, "7"),
+    (CalculationType::CountEmpty, "1"),
+    (CalculationType::CountNonEmpty, "6"),
+    (CalculationType::CountUnique, "5"),
+  ] {
+    scripts.push(InsertCalculation {
+      payload: UpdateCalculationChangesetPB {
+        view_id: view_id.clone(),
+        field_id: text_field_id.clone(),
+        calculation_id: Some(calculation_id.clone()),
+        calculation_type,
+      },
+    });
+    scripts.push(AssertCalculationText {
+      field_id: text_field_id.clone(),
+      expected,
+    });
+  }
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn calculations_percent_checked_and_date_test() {
+  let mut test = DatabaseCalculationTest::new().await;
+  let view_id = test.view_id.clone();
+  let checkbox_field_id = test.get_first_field(FieldType::Checkbox).id;
+  let date_field_id = test.get_first_field(FieldType::DateTime).id;
+
+  let scripts = vec![
+    InsertCalculation {
+      payload: UpdateCalculationChangesetPB {
+        view_id: view_id.clone(),
+        field_id: checkbox_field_id.clone(),
+        calculation_id: None,
+        calculation_type: CalculationType::PercentChecked,
+      },
+    },
+    AssertCalculationText {
+      field_id: checkbox_field_id,
+      expected: "42.85714",
+    },
+    InsertCalculation {
+      payload: UpdateCalculationChangesetPB {
+        view_id,
+        field_id: date_field_id.clone(),
+        calculation_id: None,
+        calculation_type: CalculationType::DateRange,
+      },
+    },
+    AssertCalculationText {
+      field_id: date_field_id,
+      expected: "285",
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn calculations_unsupported_field_type_test() {
+  let test = DatabaseCalculationTest::new().await;
+  let text_field_id = test.get_first_field(FieldType::RichText).id;
+  let result = test
+    .editor
+    .update_calculation(UpdateCalculationChangesetPB {
+      view_id: test.view_id.clone(),
+      field_id: text_field_id,
+      calculation_id: None,
+      calculation_type: CalculationType::Sum,
+    })
+    .await;
+  assert!(result.is_err());
+}
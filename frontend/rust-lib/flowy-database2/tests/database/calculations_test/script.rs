@@ -12,6 +12,10 @@ pub enum CalculationScript {
   AssertCalculationValue {
     expected: f64,
   },
+  AssertCalculationText {
+    field_id: String,
+    expected: &'static str,
+  },
 }
 
 pub struct DatabaseCalculationTest {
@@ -55,6 +59,15 @@ impl DatabaseCalculationTest {
         let calculation = calculations.items.first().unwrap();
         assert_eq!(calculation.value, format!("{:.5}", expected));
       },
+      CalculationScript::AssertCalculationText { field_id, expected } => {
+        let calculations = self.editor.get_all_calculations(&self.view_id()).await;
+        let calculation = calculations
+          .items
+          .iter()
+          .find(|calculation| calculation.field_id == field_id)
+          .unwrap();
+        assert_eq!(calculation.value, expected);
+      },
     }
   }
 }
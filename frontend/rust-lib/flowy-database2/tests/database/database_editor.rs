@@ -270,6 +270,15 @@ impl DatabaseEditorTest {
       .unwrap()
   }
 
+  pub async fn import_file(&self, file_path: String, format: CSVFormat) -> ImportResult {
+    self
+      .sdk
+      .database_manager
+      .import_csv_from_file(gen_database_view_id(), file_path, format)
+      .await
+      .unwrap()
+  }
+
   pub async fn get_database(&self, database_id: &str) -> Option<Arc<DatabaseEditor>> {
     self
       .sdk
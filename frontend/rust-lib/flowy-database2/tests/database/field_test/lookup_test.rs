@@ -0,0 +1,227 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use collab_database::views::OrderObjectPosition;
+use event_integration::folder_event::ViewTest;
+use flowy_database2::entities::{CalculationType, CreateFieldParams, FieldType};
+use flowy_database2::services::database::{DatabaseDataChanged, DatabaseEditor};
+use flowy_database2::services::field::{type_option_to_pb, LookupCellData, LookupTypeOption};
+
+use crate::database::database_editor::DatabaseEditorTest;
+use crate::database::mock_data::make_test_grid;
+
+/// Opens another grid with the same rows, whose lookup field counts the rows of the source
+/// database that have the same name. Returns the view id of the other grid and the lookup field id.
+async fn open_grid_with_lookup_field(test: &DatabaseEditorTest) -> (String, String) {
+  let text_field = test.get_first_field(FieldType::RichText);
+  let (view_id, lookup_field_id) =
+    open_grid_with_lookup_field_of(test, &test.view_id, &text_field.id, CalculationType::Count)
+      .await;
+  let editor = get_editor(test, &view_id).await;
+  wait_for_lookup_values(
+    &editor,
+    &view_id,
+    &lookup_field_id,
+    &["1", "", "1", "1", "2", "2", "1"],
+  )
+  .await;
+  (view_id, lookup_field_id)
+}
+
+/// Opens another grid with the same rows, whose lookup field aggregates the `target_field_id` of
+/// the rows of the database of `source_view_id` that have the same name.
+async fn open_grid_with_lookup_field_of(
+  test: &DatabaseEditorTest,
+  source_view_id: &str,
+  target_field_id: &str,
+  calculation_type: CalculationType,
+) -> (String, String) {
+  let database_id = test
+    .sdk
+    .database_manager
+    .get_database_id_with_view_id(source_view_id)
+    .await
+    .unwrap();
+  let source_editor = get_editor(test, source_view_id).await;
+  let source_key_field = source_editor
+    .get_fields(source_view_id, None)
+    .into_iter()
+    .find(|field| field.is_primary)
+    .unwrap();
+
+  let view = ViewTest::new_grid_view(&test.sdk, make_test_grid().to_json_bytes().unwrap()).await;
+  let view_id = view.child_view.id;
+  let editor = get_editor(test, &view_id).await;
+  let key_field = editor
+    .get_fields(&view_id, None)
+    .into_iter()
+    .find(|field| field.is_primary)
+    .unwrap();
+  let type_option = LookupTypeOption {
+    database_id,
+    key_field_id: key_field.id,
+    related_key_field_id: source_key_field.id,
+    target_field_id: target_field_id.to_owned(),
+    calculation_type: calculation_type.value(),
+  };
+  let params = CreateFieldParams {
+    view_id: view_id.clone(),
+    field_name: Some("Lookup".to_owned()),
+    field_type: FieldType::Lookup,
+    type_option_data: Some(type_option_to_pb(type_option.into(), &FieldType::Lookup).to_vec()),
+    position: OrderObjectPosition::default(),
+  };
+  let lookup_field = editor.create_field_with_type_option(params).await.unwrap();
+  (view_id, lookup_field.id)
+}
+
+async fn get_editor(test: &DatabaseEditorTest, view_id: &str) -> Arc<DatabaseEditor> {
+  test
+    .sdk
+    .database_manager
+    .get_database_with_view_id(view_id)
+    .await
+    .unwrap()
+}
+
+/// Returns the lookup values of the rows, in the order of the rows.
+async fn get_lookup_values(editor: &DatabaseEditor, view_id: &str, field_id: &str) -> Vec<String> {
+  let mut values = vec![];
+  for row_detail in editor.get_rows(view_id).await.unwrap() {
+    let value = editor
+      .get_cell(field_id, &row_detail.row.id)
+      .await
+      .map(|cell| LookupCellData::from(&cell).0)
+      .unwrap_or_default();
+    values.push(value);
+  }
+  values
+}
+
+/// Waits until the cells of the lookup field were recomputed to the `expected` values.
+async fn wait_for_lookup_values(
+  editor: &DatabaseEditor,
+  view_id: &str,
+  field_id: &str,
+  expected: &[&str],
+) {
+  let mut data_changed_rx = editor.subscribe_data_changed();
+  tokio::time::timeout(Duration::from_secs(10), async {
+    while get_lookup_values(editor, view_id, field_id).await != expected {
+      loop {
+        match data_changed_rx.recv().await.unwrap() {
+          DatabaseDataChanged::DidUpdateCell {
+            field_id: changed_field_id,
+            ..
+          } if changed_field_id == field_id => break,
+          _ => continue,
+        }
+      }
+    }
+  })
+  .await
+  .unwrap();
+}
+
+// The rows are A, "", C, DA, AE, AE and CB.
+#[tokio::test]
+async fn lookup_field_refresh_after_source_row_changed_test() {
+  let mut test = DatabaseEditorTest::new_grid().await;
+  let (view_id, lookup_field_id) = open_grid_with_lookup_field(&test).await;
+  let editor = get_editor(&test, &view_id).await;
+
+  // Rename C to AE in the source database
+  let text_field = test.get_first_field(FieldType::RichText);
+  let row_id = test.row_details[2].row.id.clone();
+  test
+    .update_cell(&text_field.id, row_id, "AE".to_string())
+    .await
+    .unwrap();
+  wait_for_lookup_values(
+    &editor,
+    &view_id,
+    &lookup_field_id,
+    &["1", "", "", "1", "3", "3", "1"],
+  )
+  .await;
+}
+
+// The third grid looks up the lookup field of the second grid, which looks up the source grid.
+#[tokio::test]
+async fn chained_lookup_field_refresh_after_source_row_changed_test() {
+  let mut test = DatabaseEditorTest::new_grid().await;
+  let (view_id, lookup_field_id) = open_grid_with_lookup_field(&test).await;
+  let (chained_view_id, chained_lookup_field_id) = open_grid_with_lookup_field_of(
+    &test,
+    &view_id,
+    &lookup_field_id,
+    CalculationType::CountNonEmpty,
+  )
+  .await;
+  let chained_editor = get_editor(&test, &chained_view_id).await;
+  wait_for_lookup_values(
+    &chained_editor,
+    &chained_view_id,
+    &chained_lookup_field_id,
+    &["1", "", "1", "1", "2", "2", "1"],
+  )
+  .await;
+
+  // Rename C to AE in the source database. The lookup cell of C in the second grid becomes empty.
+  let text_field = test.get_first_field(FieldType::RichText);
+  let row_id = test.row_details[2].row.id.clone();
+  test
+    .update_cell(&text_field.id, row_id, "AE".to_string())
+    .await
+    .unwrap();
+  wait_for_lookup_values(
+    &chained_editor,
+    &chained_view_id,
+    &chained_lookup_field_id,
+    &["1", "", "0", "1", "2", "2", "1"],
+  )
+  .await;
+}
+
+#[tokio::test]
+async fn lookup_cell_cannot_be_edited_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let (view_id, lookup_field_id) = open_grid_with_lookup_field(&test).await;
+  let editor = get_editor(&test, &view_id).await;
+  let row_id = editor.get_rows(&view_id).await.unwrap()[0].row.id.clone();
+  assert!(editor
+    .update_cell_with_changeset(&view_id, row_id, &lookup_field_id, "100".to_string())
+    .await
+    .is_err());
+  assert_eq!(
+    get_lookup_values(&editor, &view_id, &lookup_field_id).await[0],
+    "1"
+  );
+}
+
+// The source rows are changed while the other database isn't open. Its lookup field is
+// recomputed when it's opened again.
+#[tokio::test]
+async fn lookup_field_refresh_on_open_test() {
+  let mut test = DatabaseEditorTest::new_grid().await;
+  let (view_id, lookup_field_id) = open_grid_with_lookup_field(&test).await;
+
+  // Only the last five databases stay open
+  for _ in 0..5 {
+    let view = ViewTest::new_grid_view(&test.sdk, make_test_grid().to_json_bytes().unwrap()).await;
+    get_editor(&test, &view.child_view.id).await;
+  }
+
+  let text_field = test.get_first_field(FieldType::RichText);
+  let row_id = test.row_details[2].row.id.clone();
+  test
+    .update_cell(&text_field.id, row_id, "AE".to_string())
+    .await
+    .unwrap();
+
+  let editor = get_editor(&test, &view_id).await;
+  assert_eq!(
+    get_lookup_values(&editor, &view_id, &lookup_field_id).await,
+    vec!["1", "", "", "1", "3", "3", "1"]
+  );
+}
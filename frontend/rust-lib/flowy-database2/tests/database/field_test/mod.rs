@@ -1,3 +1,5 @@
+mod lookup_test;
 mod script;
 mod test;
 pub mod util;
+mod validation_test;
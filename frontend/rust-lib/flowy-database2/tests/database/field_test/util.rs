@@ -3,8 +3,9 @@ use collab_database::views::OrderObjectPosition;
 
 use flowy_database2::entities::{CreateFieldParams, FieldType};
 use flowy_database2::services::field::{
-  type_option_to_pb, DateCellChangeset, DateFormat, DateTypeOption, FieldBuilder,
-  RichTextTypeOption, SelectOption, SingleSelectTypeOption, TimeFormat, TimestampTypeOption,
+  type_option_to_pb, AutoNumberTypeOption, DateCellChangeset, DateFormat, DateTypeOption,
+  FieldBuilder, RelationTypeOption, RichTextTypeOption, SelectOption, SingleSelectTypeOption,
+  TimeFormat, TimestampTypeOption,
 };
 
 pub fn create_text_field(grid_id: &str) -> (CreateFieldParams, Field) {
@@ -53,6 +54,7 @@ pub fn create_date_field(grid_id: &str) -> (CreateFieldParams, Field) {
     date_format: DateFormat::US,
     time_format: TimeFormat::TwentyFourHour,
     timezone_id: "Etc/UTC".to_owned(),
+    is_range: false,
   };
 
   let field = FieldBuilder::new(FieldType::DateTime, date_type_option.clone())
@@ -104,6 +106,50 @@ pub fn create_timestamp_field(grid_id: &str, field_type: FieldType) -> (CreateFi
   (params, field)
 }
 
+pub fn create_relation_field(grid_id: &str, database_id: &str) -> (CreateFieldParams, Field) {
+  let field_type = FieldType::Relation;
+  let type_option = RelationTypeOption {
+    database_id: database_id.to_owned(),
+  };
+  let relation_field = FieldBuilder::new(field_type, type_option.clone())
+    .name("Related")
+    .visibility(true)
+    .build();
+
+  let type_option_data = type_option_to_pb(type_option.into(), &field_type).to_vec();
+  let params = CreateFieldParams {
+    view_id: grid_id.to_owned(),
+    field_type,
+    type_option_data: Some(type_option_data),
+    field_name: Some("Related".to_owned()),
+    position: OrderObjectPosition::default(),
+  };
+  (params, relation_field)
+}
+
+/// The existing rows are numbered when the field is created.
+pub fn create_auto_number_field(grid_id: &str) -> (CreateFieldParams, Field) {
+  let field_type = FieldType::AutoNumber;
+  let type_option = AutoNumberTypeOption {
+    prefix: "ID-".to_owned(),
+    next_number: 1,
+  };
+  let auto_number_field = FieldBuilder::new(field_type, type_option.clone())
+    .name("ID")
+    .visibility(true)
+    .build();
+
+  let type_option_data = type_option_to_pb(type_option.into(), &field_type).to_vec();
+  let params = CreateFieldParams {
+    view_id: grid_id.to_owned(),
+    field_type,
+    type_option_data: Some(type_option_data),
+    field_name: Some("ID".to_owned()),
+    position: OrderObjectPosition::default(),
+  };
+  (params, auto_number_field)
+}
+
 //  The grid will contains all existing field types and there are three empty rows in this grid.
 
 pub fn make_date_cell_string(timestamp: i64) -> String {
@@ -0,0 +1,299 @@
+use collab_database::database::{gen_row_id, timestamp};
+use collab_database::rows::CreateRowParams;
+
+use flowy_database2::entities::{FieldType, FieldValidationRulePB, RepeatedFieldValidationErrorPB};
+use flowy_database2::services::cell::{stringify_cell_data, CellBuilder};
+use flowy_database2::services::field::FieldValidation;
+use flowy_error::{ErrorCode, FlowyError};
+
+use crate::database::database_editor::DatabaseEditorTest;
+use crate::database::field_test::util::create_auto_number_field;
+
+fn broken_rules(error: FlowyError) -> Vec<FieldValidationRulePB> {
+  assert_eq!(error.code, ErrorCode::FieldValidationFailed);
+  RepeatedFieldValidationErrorPB::try_from(error.payload.as_slice())
+    .unwrap()
+    .items
+    .into_iter()
+    .map(|error| error.rule)
+    .collect()
+}
+
+#[tokio::test]
+async fn update_cell_with_validation_rules_test() {
+  let mut test = DatabaseEditorTest::new_grid().await;
+  let text_field = test.get_first_field(FieldType::RichText);
+  test
+    .editor
+    .update_field_validation(
+      &text_field.id,
+      FieldValidation {
+        required: true,
+        unique: true,
+        ..Default::default()
+      },
+    )
+    .await
+    .unwrap();
+
+  let row_id = test.row_details[0].row.id.clone();
+  let error = test
+    .update_cell(&text_field.id, row_id.clone(), "".to_string())
+    .await
+    .unwrap_err();
+  assert_eq!(broken_rules(error), vec![FieldValidationRulePB::Required]);
+
+  // The third row's name is C
+  let error = test
+    .update_cell(&text_field.id, row_id.clone(), "C".to_string())
+    .await
+    .unwrap_err();
+  assert_eq!(broken_rules(error), vec![FieldValidationRulePB::Unique]);
+
+  test
+    .update_cell(&text_field.id, row_id.clone(), "Z".to_string())
+    .await
+    .unwrap();
+  let cell = test.editor.get_cell(&text_field.id, &row_id).await.unwrap();
+  assert_eq!(
+    stringify_cell_data(
+      &cell,
+      &FieldType::RichText,
+      &FieldType::RichText,
+      &text_field
+    ),
+    "Z"
+  );
+}
+
+#[tokio::test]
+async fn create_row_with_validation_rules_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let row_count = test.row_details.len();
+  let (params, _) = create_auto_number_field(&test.view_id);
+  test
+    .editor
+    .create_field_with_type_option(params)
+    .await
+    .unwrap();
+  let text_field = test.get_first_field(FieldType::RichText);
+  test
+    .editor
+    .update_field_validation(
+      &text_field.id,
+      FieldValidation {
+        unique: true,
+        ..Default::default()
+      },
+    )
+    .await
+    .unwrap();
+
+  // The third row's name is C
+  let fields = test.get_fields();
+  let mut cell_by_field_id = std::collections::HashMap::new();
+  cell_by_field_id.insert(text_field.id.clone(), "C".to_string());
+  let params = CreateRowParams {
+    id: gen_row_id(),
+    cells: CellBuilder::with_cells(cell_by_field_id, &fields).build(),
+    timestamp: timestamp(),
+    ..Default::default()
+  };
+  let error = test
+    .editor
+    .create_row(&test.view_id, None, params)
+    .await
+    .unwrap_err();
+  assert_eq!(broken_rules(error), vec![FieldValidationRulePB::Unique]);
+  assert_eq!(test.get_rows().await.len(), row_count);
+
+  let mut cell_by_field_id = std::collections::HashMap::new();
+  cell_by_field_id.insert(text_field.id.clone(), "New row".to_string());
+  let params = CreateRowParams {
+    id: gen_row_id(),
+    cells: CellBuilder::with_cells(cell_by_field_id, &fields).build(),
+    timestamp: timestamp(),
+    ..Default::default()
+  };
+  let row_detail = test
+    .editor
+    .create_row(&test.view_id, None, params)
+    .await
+    .unwrap()
+    .unwrap();
+  assert_eq!(test.get_rows().await.len(), row_count + 1);
+
+  // The rejected row didn't use an auto number
+  let auto_number_field = test.get_first_field(FieldType::AutoNumber);
+  let cell = test
+    .editor
+    .get_cell(&auto_number_field.id, &row_detail.row.id)
+    .await
+    .unwrap();
+  assert_eq!(
+    stringify_cell_data(
+      &cell,
+      &FieldType::AutoNumber,
+      &FieldType::AutoNumber,
+      &auto_number_field
+    ),
+    format!("ID-{}", row_count + 1)
+  );
+}
+
+#[tokio::test]
+async fn create_blank_row_with_required_field_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let row_count = test.row_details.len();
+  let text_field = test.get_first_field(FieldType::RichText);
+  test
+    .editor
+    .update_field_validation(
+      &text_field.id,
+      FieldValidation {
+        required: true,
+        unique: true,
+        ..Default::default()
+      },
+    )
+    .await
+    .unwrap();
+
+  // The blank row is created, and the missing name is returned with it
+  let params = CreateRowParams {
+    id: gen_row_id(),
+    timestamp: timestamp(),
+    ..Default::default()
+  };
+  let blank_row = test
+    .editor
+    .create_row(&test.view_id, None, params)
+    .await
+    .unwrap()
+    .unwrap();
+  let errors = test.editor.get_row_validation_errors(&blank_row.row.id);
+  assert_eq!(errors.len(), 1);
+  assert_eq!(errors[0].field_id, text_field.id);
+  assert_eq!(errors[0].rule, FieldValidationRulePB::Required);
+
+  // The name of the duplicated row is unique, so it's not copied
+  let first_row_id = test.row_details[0].row.id.clone();
+  test
+    .editor
+    .duplicate_row(&test.view_id, &first_row_id)
+    .await;
+  let rows = test.get_rows().await;
+  assert_eq!(rows.len(), row_count + 2);
+
+  let duplicated_row = rows
+    .iter()
+    .find(|row| {
+      row.row.id != blank_row.row.id
+        && !test
+          .row_details
+          .iter()
+          .any(|row_detail| row_detail.row.id == row.row.id)
+    })
+    .unwrap();
+
+  // The new rows are reported along with the second row, whose name is empty
+  let mut row_ids = test
+    .editor
+    .get_validation_violations(&test.view_id)
+    .await
+    .into_iter()
+    .filter(|error| error.rule == FieldValidationRulePB::Required)
+    .map(|error| error.row_id)
+    .collect::<Vec<_>>();
+  row_ids.sort();
+  let mut expected_row_ids = vec![
+    test.row_details[1].row.id.to_string(),
+    blank_row.row.id.to_string(),
+    duplicated_row.row.id.to_string(),
+  ];
+  expected_row_ids.sort();
+  assert_eq!(row_ids, expected_row_ids);
+}
+
+#[tokio::test]
+async fn get_validation_violations_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let text_field = test.get_first_field(FieldType::RichText);
+  let number_field = test.get_first_field(FieldType::Number);
+  test
+    .editor
+    .update_field_validation(
+      &text_field.id,
+      FieldValidation {
+        unique: true,
+        ..Default::default()
+      },
+    )
+    .await
+    .unwrap();
+  test
+    .editor
+    .update_field_validation(
+      &number_field.id,
+      FieldValidation {
+        min: Some(2.0),
+        ..Default::default()
+      },
+    )
+    .await
+    .unwrap();
+
+  let violations = test
+    .editor
+    .get_validation_violations(&test.view_id)
+    .await
+    .into_iter()
+    .map(|error| (error.row_id, error.field_id, error.rule))
+    .collect::<Vec<_>>();
+
+  // The first row's number is 1, the fifth and the sixth rows are both named AE
+  let row_id = |index: usize| test.row_details[index].row.id.to_string();
+  assert_eq!(
+    violations,
+    vec![
+      (
+        row_id(0),
+        number_field.id.clone(),
+        FieldValidationRulePB::Min
+      ),
+      (
+        row_id(4),
+        text_field.id.clone(),
+        FieldValidationRulePB::Unique
+      ),
+      (
+        row_id(5),
+        text_field.id.clone(),
+        FieldValidationRulePB::Unique
+      ),
+    ]
+  );
+}
+
+#[tokio::test]
+async fn invalid_validation_rules_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let text_field = test.get_first_field(FieldType::RichText);
+  let error = test
+    .editor
+    .update_field_validation(
+      &text_field.id,
+      FieldValidation {
+        pattern: Some("[a-z".to_string()),
+        ..Default::default()
+      },
+    )
+    .await
+    .unwrap_err();
+  assert_eq!(error.code, ErrorCode::InvalidParams);
+  assert!(test
+    .editor
+    .get_field_validation(&text_field.id)
+    .unwrap()
+    .is_empty());
+}
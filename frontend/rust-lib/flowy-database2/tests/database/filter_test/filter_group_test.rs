@@ -0,0 +1,130 @@
+use flowy_database2::entities::{
+  FieldType, FilterTypePB, NumberFilterConditionPB, NumberFilterPB, TextFilterConditionPB,
+  TextFilterPB, UpdateFilterPayloadPB,
+};
+use flowy_database2::services::filter::FilterContext;
+
+use crate::database::filter_test::script::DatabaseFilterTest;
+use crate::database::filter_test::script::FilterScript::*;
+
+#[tokio::test]
+async fn grid_filter_or_group_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  let view_id = test.view_id();
+  test
+    .run_scripts(vec![InsertFilter {
+      payload: UpdateFilterPayloadPB::new_group(&view_id, FilterTypePB::Or, None),
+    }])
+    .await;
+  let group = test.get_all_filters().await.pop().unwrap();
+
+  // The text is "A" OR the number is 14
+  let text_field = test.get_first_field(FieldType::RichText);
+  let number_field = test.get_first_field(FieldType::Number);
+  let text_filter = TextFilterPB {
+    condition: TextFilterConditionPB::Is,
+    content: "A".to_string(),
+    case_sensitive: false,
+  };
+  let number_filter = NumberFilterPB {
+    condition: NumberFilterConditionPB::Equal,
+    content: "14".to_string(),
+  };
+  let scripts = vec![
+    AssertNumberOfVisibleRows { expected: 7 },
+    InsertFilter {
+      payload: UpdateFilterPayloadPB::new(&view_id, &text_field, text_filter)
+        .with_parent_id(&group.id),
+    },
+    AssertNumberOfVisibleRows { expected: 1 },
+    InsertFilter {
+      payload: UpdateFilterPayloadPB::new(&view_id, &number_field, number_filter)
+        .with_parent_id(&group.id),
+    },
+    AssertFilterCount { count: 3 },
+    AssertNumberOfVisibleRows { expected: 2 },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_filter_root_filter_and_or_group_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  let view_id = test.view_id();
+  let text_field = test.get_first_field(FieldType::RichText);
+  let number_field = test.get_first_field(FieldType::Number);
+
+  // The text contains "A" AND (the number is 1 OR the number is 5)
+  let text_filter = TextFilterPB {
+    condition: TextFilterConditionPB::Contains,
+    content: "A".to_string(),
+    case_sensitive: false,
+  };
+  test
+    .run_scripts(vec![
+      InsertFilter {
+        payload: UpdateFilterPayloadPB::new(&view_id, &text_field, text_filter),
+      },
+      AssertNumberOfVisibleRows { expected: 4 },
+      InsertFilter {
+        payload: UpdateFilterPayloadPB::new_group(&view_id, FilterTypePB::Or, None),
+      },
+    ])
+    .await;
+  let group = test
+    .get_all_filters()
+    .await
+    .into_iter()
+    .find(|filter| filter.filter_type == FilterTypePB::Or)
+    .unwrap();
+
+  let mut scripts = vec![];
+  for content in ["1", "5"] {
+    let number_filter = NumberFilterPB {
+      condition: NumberFilterConditionPB::Equal,
+      content: content.to_string(),
+    };
+    scripts.push(InsertFilter {
+      payload: UpdateFilterPayloadPB::new(&view_id, &number_field, number_filter)
+        .with_parent_id(&group.id),
+    });
+  }
+  scripts.push(AssertFilterCount { count: 4 });
+  scripts.push(AssertNumberOfVisibleRows { expected: 2 });
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_filter_delete_group_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  let view_id = test.view_id();
+  test
+    .run_scripts(vec![InsertFilter {
+      payload: UpdateFilterPayloadPB::new_group(&view_id, FilterTypePB::And, None),
+    }])
+    .await;
+  let group = test.get_all_filters().await.pop().unwrap();
+
+  let text_field = test.get_first_field(FieldType::RichText);
+  let text_filter = TextFilterPB {
+    condition: TextFilterConditionPB::TextIsEmpty,
+    content: "".to_string(),
+    case_sensitive: false,
+  };
+  let scripts = vec![
+    InsertFilter {
+      payload: UpdateFilterPayloadPB::new(&view_id, &text_field, text_filter)
+        .with_parent_id(&group.id),
+    },
+    AssertFilterCount { count: 2 },
+    AssertNumberOfVisibleRows { expected: 1 },
+    // Deleting the group deletes the filters in it
+    DeleteFilter {
+      filter_context: FilterContext::from(&group),
+      changed: None,
+    },
+    AssertFilterCount { count: 0 },
+    AssertNumberOfVisibleRows { expected: 7 },
+  ];
+  test.run_scripts(scripts).await;
+}
@@ -1,6 +1,7 @@
 mod checkbox_filter_test;
 mod checklist_filter_test;
 mod date_filter_test;
+mod filter_group_test;
 mod number_filter_test;
 mod script;
 mod select_option_filter_test;
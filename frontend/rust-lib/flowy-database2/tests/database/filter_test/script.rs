@@ -200,7 +200,11 @@ impl DatabaseFilterTest {
         );
         self.assert_future_changed(changed).await;
         let field = self.get_first_field(FieldType::RichText);
-        let text_filter = TextFilterPB { condition, content };
+        let text_filter = TextFilterPB {
+          condition,
+          content,
+          case_sensitive: false,
+        };
         let payload = UpdateFilterPayloadPB::new(&self.view_id(), &field, text_filter);
         self.insert_filter(payload).await;
       },
@@ -222,6 +226,8 @@ impl DatabaseFilterTest {
           view_id: self.view_id(),
           field_id: filter.field_id,
           filter_id: Some(filter.id),
+          parent_id: filter.parent_id,
+          filter_type: filter.filter_type.into(),
           field_type: filter.field_type,
           condition: condition as i64,
           content,
@@ -281,6 +287,7 @@ impl DatabaseFilterTest {
           start,
           end,
           timestamp,
+          number_of_days: None,
         };
 
         let payload = UpdateFilterPayloadPB::new(&self.view_id(), &field, date_filter);
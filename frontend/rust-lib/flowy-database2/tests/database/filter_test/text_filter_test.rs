@@ -194,6 +194,7 @@ async fn grid_filter_delete_test() {
   let text_filter = TextFilterPB {
     condition: TextFilterConditionPB::TextIsEmpty,
     content: "".to_string(),
+    case_sensitive: false,
   };
   let payload = UpdateFilterPayloadPB::new(&test.view_id(), &field, text_filter);
   let scripts = vec![
@@ -241,3 +242,80 @@ async fn grid_filter_update_empty_text_cell_test() {
   ];
   test.run_scripts(scripts).await;
 }
+
+#[tokio::test]
+async fn grid_filter_search_all_text_fields_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  let view_id = test.view_id();
+  // Only the url of the first row contains "appflowy".
+  let text_filter = TextFilterPB {
+    condition: TextFilterConditionPB::Contains,
+    content: "APPFLOWY".to_string(),
+    case_sensitive: false,
+  };
+  let scripts = vec![
+    InsertFilter {
+      payload: UpdateFilterPayloadPB::new_search(&view_id, text_filter),
+    },
+    AssertFilterCount { count: 1 },
+    AssertNumberOfVisibleRows { expected: 1 },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_filter_search_negated_condition_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  let view_id = test.view_id();
+  // The rows are hidden if any of their text fields contains "appflowy"
+  let text_filter = TextFilterPB {
+    condition: TextFilterConditionPB::DoesNotContain,
+    content: "appflowy".to_string(),
+    case_sensitive: false,
+  };
+  let scripts = vec![
+    InsertFilter {
+      payload: UpdateFilterPayloadPB::new_search(&view_id, text_filter),
+    },
+    AssertNumberOfVisibleRows { expected: 6 },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_filter_search_empty_condition_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  let view_id = test.view_id();
+  // Every row has some text, so none of them is empty
+  let scripts = vec![
+    InsertFilter {
+      payload: UpdateFilterPayloadPB::new_search(
+        &view_id,
+        TextFilterPB {
+          condition: TextFilterConditionPB::TextIsEmpty,
+          content: "".to_string(),
+          case_sensitive: false,
+        },
+      ),
+    },
+    AssertNumberOfVisibleRows { expected: 0 },
+  ];
+  test.run_scripts(scripts).await;
+
+  let mut test = DatabaseFilterTest::new().await;
+  let view_id = test.view_id();
+  let scripts = vec![
+    InsertFilter {
+      payload: UpdateFilterPayloadPB::new_search(
+        &view_id,
+        TextFilterPB {
+          condition: TextFilterConditionPB::TextIsNotEmpty,
+          content: "".to_string(),
+          case_sensitive: false,
+        },
+      ),
+    },
+    AssertNumberOfVisibleRows { expected: 7 },
+  ];
+  test.run_scripts(scripts).await;
+}
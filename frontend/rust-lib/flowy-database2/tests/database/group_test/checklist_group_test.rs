@@ -0,0 +1,85 @@
+use collab_database::rows::RowId;
+use flowy_database2::entities::FieldType;
+use flowy_database2::services::field::checklist_type_option::{
+  ChecklistCellChangeset, ChecklistCellData,
+};
+
+use crate::database::group_test::script::DatabaseGroupTest;
+use crate::database::group_test::script::GroupScript::*;
+
+#[tokio::test]
+async fn group_by_checklist_progress_test() {
+  let mut test = DatabaseGroupTest::new().await;
+  let checklist_field = test.get_field(FieldType::Checklist).await;
+  let scripts = vec![
+    GroupByField {
+      field_id: checklist_field.id.clone(),
+    },
+    AssertGroupCount(3),
+    AssertGroupIDName {
+      group_index: 0,
+      group_id: checklist_field.id.clone(),
+      group_name: "Not started".to_string(),
+    },
+    AssertGroupRowCount {
+      group_index: 0,
+      row_count: 5,
+    },
+  ];
+  test.run_scripts(scripts).await;
+
+  // Selecting one of the two items moves the row to the in progress group
+  let row_id = RowId::from(test.row_at_index(0, 0).await.id);
+  let view_id = test.view_id.clone();
+  test
+    .editor
+    .set_checklist_options(
+      &view_id,
+      row_id.clone(),
+      &checklist_field.id,
+      ChecklistCellChangeset {
+        insert_options: vec!["first".to_string(), "second".to_string()],
+        ..Default::default()
+      },
+    )
+    .await
+    .unwrap();
+  let cell = test
+    .editor
+    .get_cell(&checklist_field.id, &row_id)
+    .await
+    .unwrap();
+  let first_option_id = ChecklistCellData::from(&cell).options[0].id.clone();
+  test
+    .set_checklist_cell(row_id.clone(), vec![first_option_id])
+    .await
+    .unwrap();
+
+  let in_progress = test.group_at_index(1).await;
+  assert_eq!(in_progress.group_id, "in_progress");
+  assert_eq!(in_progress.rows.len(), 1);
+
+  // The row can be moved to the done group, which selects all of its items
+  test
+    .editor
+    .move_group_row(&view_id, "in_progress", "done", row_id.clone(), None, None)
+    .await
+    .unwrap();
+  let scripts = vec![
+    AssertGroupRowCount {
+      group_index: 1,
+      row_count: 0,
+    },
+    AssertGroupRowCount {
+      group_index: 2,
+      row_count: 1,
+    },
+  ];
+  test.run_scripts(scripts).await;
+  let cell = test
+    .editor
+    .get_cell(&checklist_field.id, &row_id)
+    .await
+    .unwrap();
+  assert_eq!(ChecklistCellData::from(&cell).selected_options().len(), 2);
+}
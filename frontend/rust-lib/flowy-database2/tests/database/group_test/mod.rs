@@ -1,4 +1,8 @@
+mod checklist_group_test;
 mod date_group_test;
+mod number_group_test;
 mod script;
+mod swimlane_test;
 mod test;
+mod text_group_test;
 mod url_group_test;
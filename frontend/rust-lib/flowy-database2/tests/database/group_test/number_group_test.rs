@@ -0,0 +1,100 @@
+use collab_database::rows::RowId;
+use flowy_database2::entities::FieldType;
+use flowy_database2::services::field::NumberCellData;
+
+use crate::database::group_test::script::DatabaseGroupTest;
+use crate::database::group_test::script::GroupScript::*;
+
+#[tokio::test]
+async fn group_by_number_test() {
+  let mut test = DatabaseGroupTest::new().await;
+  let number_field = test.get_field(FieldType::Number).await;
+  let scripts = vec![
+    GroupByField {
+      field_id: number_field.id.clone(),
+    },
+    AssertGroupCount(2),
+    // no status group
+    AssertGroupRowCount {
+      group_index: 0,
+      row_count: 1,
+    },
+    AssertGroupIDName {
+      group_index: 1,
+      group_id: "0..10".to_string(),
+      group_name: "0 - 10".to_string(),
+    },
+    AssertGroupRowCount {
+      group_index: 1,
+      row_count: 4,
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn group_by_number_with_bucket_size_test() {
+  let mut test = DatabaseGroupTest::new().await;
+  let number_field = test.get_field(FieldType::Number).await;
+  test
+    .editor
+    .set_group_by_field(
+      &test.view_id,
+      &number_field.id,
+      r#"{"bucket_size": 2}"#.to_string(),
+    )
+    .await
+    .unwrap();
+
+  let scripts = vec![
+    AssertGroupCount(4),
+    AssertGroupIDName {
+      group_index: 1,
+      group_id: "0..2".to_string(),
+      group_name: "0 - 2".to_string(),
+    },
+    AssertGroupRowCount {
+      group_index: 2,
+      row_count: 2,
+    },
+    AssertGroupIDName {
+      group_index: 3,
+      group_id: "4..6".to_string(),
+      group_name: "4 - 6".to_string(),
+    },
+  ];
+  test.run_scripts(scripts).await;
+
+  // Moving the only row of the first bucket to the last one sets its number to the lower bound
+  // of the last bucket, and deletes the empty bucket.
+  let from_group = test.group_at_index(1).await;
+  let to_group = test.group_at_index(3).await;
+  let row_id = RowId::from(from_group.rows[0].id.clone());
+  test
+    .editor
+    .move_group_row(
+      &test.view_id,
+      &from_group.group_id,
+      &to_group.group_id,
+      row_id.clone(),
+      None,
+      None,
+    )
+    .await
+    .unwrap();
+
+  let scripts = vec![
+    AssertGroupCount(3),
+    AssertGroupRowCount {
+      group_index: 2,
+      row_count: 2,
+    },
+  ];
+  test.run_scripts(scripts).await;
+  let cell = test
+    .editor
+    .get_cell(&number_field.id, &row_id)
+    .await
+    .unwrap();
+  assert_eq!(NumberCellData::from(&cell).0, "4");
+}
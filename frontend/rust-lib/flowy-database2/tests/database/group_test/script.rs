@@ -122,6 +122,7 @@ impl DatabaseGroupTest {
             &to_group.group_id,
             from_row,
             Some(to_row),
+            None,
           )
           .await
           .unwrap();
@@ -0,0 +1,117 @@
+use flowy_database2::entities::{FieldType, SwimlanePB};
+use flowy_database2::services::field::{CHECK, UNCHECK};
+
+use crate::database::group_test::script::DatabaseGroupTest;
+
+fn swimlane_with_id<'a>(swimlanes: &'a [SwimlanePB], swimlane_id: &str) -> &'a SwimlanePB {
+  swimlanes
+    .iter()
+    .find(|swimlane| swimlane.swimlane_id == swimlane_id)
+    .unwrap()
+}
+
+#[tokio::test]
+async fn group_by_swimlane_field_test() {
+  let test = DatabaseGroupTest::new().await;
+  let checkbox_field = test.get_field(FieldType::Checkbox).await;
+  test
+    .editor
+    .set_swimlane_field(&test.view_id, Some(&checkbox_field.id))
+    .await
+    .unwrap();
+
+  let setting = test
+    .editor
+    .get_database_view_setting(&test.view_id)
+    .await
+    .unwrap();
+  assert_eq!(
+    setting.group_settings.items[0].swimlane_field_id,
+    Some(checkbox_field.id.clone())
+  );
+
+  let swimlanes = test
+    .editor
+    .get_swimlanes(&test.view_id)
+    .await
+    .unwrap()
+    .items;
+  assert_eq!(swimlanes.len(), 2);
+  // The first two rows are checked and in the first group of the board.
+  let checked = swimlane_with_id(&swimlanes, CHECK);
+  assert_eq!(checked.groups[1].rows.len(), 2);
+  assert_eq!(checked.groups[2].rows.len(), 0);
+  let unchecked = swimlane_with_id(&swimlanes, UNCHECK);
+  assert_eq!(unchecked.groups[1].rows.len(), 0);
+  assert_eq!(unchecked.groups[2].rows.len(), 2);
+  assert_eq!(unchecked.groups[3].rows.len(), 1);
+
+  test
+    .editor
+    .set_swimlane_field(&test.view_id, None)
+    .await
+    .unwrap();
+  assert!(test
+    .editor
+    .get_swimlanes(&test.view_id)
+    .await
+    .unwrap()
+    .items
+    .is_empty());
+}
+
+#[tokio::test]
+async fn move_row_across_groups_and_swimlanes_test() {
+  let test = DatabaseGroupTest::new().await;
+  let checkbox_field = test.get_field(FieldType::Checkbox).await;
+  test
+    .editor
+    .set_swimlane_field(&test.view_id, Some(&checkbox_field.id))
+    .await
+    .unwrap();
+
+  let from_group = test.group_at_index(1).await;
+  let to_group = test.group_at_index(2).await;
+  let row = from_group.rows[0].clone();
+  test
+    .editor
+    .move_group_row(
+      &test.view_id,
+      &from_group.group_id,
+      &to_group.group_id,
+      row.id.clone().into(),
+      None,
+      Some(UNCHECK.to_string()),
+    )
+    .await
+    .unwrap();
+
+  // Both the grouping cell and the swimlane cell of the row are updated.
+  assert_eq!(test.group_at_index(1).await.rows.len(), 1);
+  assert_eq!(test.group_at_index(2).await.rows.len(), 3);
+  let swimlanes = test
+    .editor
+    .get_swimlanes(&test.view_id)
+    .await
+    .unwrap()
+    .items;
+  let checked = swimlane_with_id(&swimlanes, CHECK);
+  assert_eq!(checked.groups[1].rows.len(), 1);
+  let unchecked = swimlane_with_id(&swimlanes, UNCHECK);
+  assert_eq!(unchecked.groups[2].rows.len(), 3);
+  assert!(unchecked.groups[2]
+    .rows
+    .iter()
+    .any(|row_meta| row_meta.id == row.id));
+}
+
+#[tokio::test]
+async fn grouping_field_cannot_be_swimlane_field_test() {
+  let test = DatabaseGroupTest::new().await;
+  let grouping_field = test.get_field(FieldType::SingleSelect).await;
+  assert!(test
+    .editor
+    .set_swimlane_field(&test.view_id, Some(&grouping_field.id))
+    .await
+    .is_err());
+}
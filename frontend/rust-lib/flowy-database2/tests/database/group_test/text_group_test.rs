@@ -0,0 +1,89 @@
+use collab_database::rows::RowId;
+use flowy_database2::entities::FieldType;
+
+use crate::database::group_test::script::DatabaseGroupTest;
+use crate::database::group_test::script::GroupScript::*;
+
+#[tokio::test]
+async fn group_by_first_letter_test() {
+  let mut test = DatabaseGroupTest::new().await;
+  let text_field = test.get_field(FieldType::RichText).await;
+  let scripts = vec![
+    GroupByField {
+      field_id: text_field.id.clone(),
+    },
+    // No status, A, B, C and D
+    AssertGroupCount(5),
+    AssertGroupIDName {
+      group_index: 1,
+      group_id: "A".to_string(),
+      group_name: "A".to_string(),
+    },
+    // A and AE
+    AssertGroupRowCount {
+      group_index: 1,
+      row_count: 2,
+    },
+    // The rows can't be moved between the first letter groups
+    MoveRow {
+      from_group_index: 1,
+      from_row_index: 0,
+      to_group_index: 2,
+      to_row_index: 0,
+    },
+    AssertGroupRowCount {
+      group_index: 1,
+      row_count: 2,
+    },
+    AssertGroupRowCount {
+      group_index: 2,
+      row_count: 1,
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn group_by_exact_value_test() {
+  let mut test = DatabaseGroupTest::new().await;
+  let text_field = test.get_field(FieldType::RichText).await;
+  test
+    .editor
+    .set_group_by_field(
+      &test.view_id,
+      &text_field.id,
+      r#"{"condition": 1}"#.to_string(),
+    )
+    .await
+    .unwrap();
+
+  // No status, A, AE, B, C and DA
+  test.run_scripts(vec![AssertGroupCount(6)]).await;
+
+  // Moving the row of B to A rewrites its text and deletes the B group
+  let from_group = test.group_at_index(3).await;
+  let to_group = test.group_at_index(1).await;
+  assert_eq!(from_group.group_id, "B");
+  let row_id = RowId::from(from_group.rows[0].id.clone());
+  test
+    .editor
+    .move_group_row(
+      &test.view_id,
+      &from_group.group_id,
+      &to_group.group_id,
+      row_id,
+      None,
+      None,
+    )
+    .await
+    .unwrap();
+
+  let scripts = vec![
+    AssertGroupCount(5),
+    AssertGroupRowCount {
+      group_index: 1,
+      row_count: 2,
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
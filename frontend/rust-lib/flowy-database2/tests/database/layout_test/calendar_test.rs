@@ -0,0 +1,42 @@
+use flowy_database2::entities::FieldType;
+use flowy_database2::services::field::DateCellChangeset;
+
+use crate::database::database_editor::DatabaseEditorTest;
+
+const DAY: i64 = 86400;
+
+#[tokio::test]
+async fn calendar_event_spanning_multiple_days_test() {
+  let test = DatabaseEditorTest::new_calendar().await;
+  let date_field = test.get_first_field(FieldType::DateTime);
+  let row_id = test.row_details[0].row.id.clone();
+  let start = 1678090778;
+
+  let changeset = DateCellChangeset {
+    date: Some(start),
+    end_date: Some(start + 2 * DAY),
+    is_range: Some(true),
+    ..Default::default()
+  };
+  test
+    .editor
+    .update_cell_with_changeset(&test.view_id, row_id.clone(), &date_field.id, changeset)
+    .await
+    .unwrap();
+
+  let events = test.editor.get_all_calendar_events(&test.view_id).await;
+  assert_eq!(events[0].timestamp, start);
+  assert_eq!(events[0].end_timestamp, start + 2 * DAY);
+  // The events that aren't date ranges last a single day.
+  assert_eq!(events[1].end_timestamp, events[1].timestamp);
+
+  // Moving the event keeps its duration.
+  test
+    .editor
+    .move_calendar_event(&test.view_id, row_id, &date_field.id, start + DAY)
+    .await
+    .unwrap();
+  let events = test.editor.get_all_calendar_events(&test.view_id).await;
+  assert_eq!(events[0].timestamp, start + DAY);
+  assert_eq!(events[0].end_timestamp, start + 3 * DAY);
+}
@@ -1,2 +1,4 @@
+mod calendar_test;
 mod script;
 mod test;
+mod timeline_test;
@@ -64,7 +64,7 @@ impl DatabaseLayoutTest {
         self
           .database_test
           .editor
-          .update_view_layout(&self.database_test.view_id, layout)
+          .update_view_layout(&self.database_test.view_id, layout.into())
           .await
           .unwrap();
       },
@@ -110,6 +110,7 @@ impl DatabaseLayoutTest {
           layout_type: DatabaseLayout::Board,
           board: Some(new_setting),
           calendar: None,
+          timeline: None,
         };
         self
           .database_test
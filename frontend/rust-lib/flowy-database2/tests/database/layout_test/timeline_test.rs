@@ -0,0 +1,209 @@
+use collab_database::views::DatabaseLayout;
+
+use flowy_database2::entities::{DatabaseLayoutPB, FieldType, LayoutSettingChangeset};
+use flowy_database2::services::setting::TimelineLayoutSetting;
+
+use crate::database::database_editor::DatabaseEditorTest;
+
+const DAY: i64 = 86400;
+
+async fn set_timeline_setting(test: &DatabaseEditorTest, setting: TimelineLayoutSetting) {
+  test
+    .editor
+    .update_view_layout(&test.view_id, DatabaseLayoutPB::Timeline)
+    .await
+    .unwrap();
+  let changeset = LayoutSettingChangeset {
+    view_id: test.view_id.clone(),
+    layout_type: DatabaseLayout::Grid,
+    board: None,
+    calendar: None,
+    timeline: Some(setting),
+  };
+  test
+    .editor
+    .set_layout_setting(&test.view_id, changeset)
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn timeline_get_spans_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let date_field = test.get_first_field(FieldType::DateTime);
+  set_timeline_setting(&test, TimelineLayoutSetting::new(date_field.id.clone())).await;
+
+  let layout_setting = test
+    .editor
+    .get_layout_setting(&test.view_id, DatabaseLayout::Grid)
+    .await
+    .unwrap();
+  assert_eq!(
+    layout_setting.timeline.unwrap().start_field_id,
+    date_field.id
+  );
+
+  let spans = test.editor.get_all_timeline_spans(&test.view_id).await;
+  assert_eq!(spans.len(), 6);
+  assert_eq!(spans[0].title, "A");
+  assert_eq!(spans[0].start, 1647251762);
+  assert_eq!(spans[0].end, 1647251762);
+
+  let no_date_rows = test
+    .editor
+    .get_all_no_date_timeline_rows(&test.view_id)
+    .await;
+  assert_eq!(no_date_rows.len(), 1);
+  assert_eq!(no_date_rows[0].title, "CB");
+}
+
+#[tokio::test]
+async fn timeline_resize_and_move_span_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let date_field = test.get_first_field(FieldType::DateTime);
+  set_timeline_setting(&test, TimelineLayoutSetting::new(date_field.id.clone())).await;
+  let row_id = test.row_details[0].row.id.clone();
+  let start = 1647251762;
+
+  // Without an end field, the end is saved in the date range of the start field.
+  test
+    .editor
+    .resize_timeline_span(&test.view_id, row_id.clone(), start, start + 3 * DAY)
+    .await
+    .unwrap();
+  let spans = test.editor.get_all_timeline_spans(&test.view_id).await;
+  assert_eq!(spans[0].start, start);
+  assert_eq!(spans[0].end, start + 3 * DAY);
+
+  test
+    .editor
+    .move_timeline_span(&test.view_id, row_id, start + DAY)
+    .await
+    .unwrap();
+  let spans = test.editor.get_all_timeline_spans(&test.view_id).await;
+  assert_eq!(spans[0].start, start + DAY);
+  assert_eq!(spans[0].end, start + 4 * DAY);
+}
+
+#[tokio::test]
+async fn timeline_reject_invalid_span_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let date_field = test.get_first_field(FieldType::DateTime);
+  set_timeline_setting(&test, TimelineLayoutSetting::new(date_field.id.clone())).await;
+  let row_id = test.row_details[0].row.id.clone();
+  let start = 1647251762;
+  test
+    .editor
+    .resize_timeline_span(&test.view_id, row_id.clone(), start, start + 3 * DAY)
+    .await
+    .unwrap();
+
+  // The end can't be before the start
+  assert!(test
+    .editor
+    .resize_timeline_span(&test.view_id, row_id.clone(), start, start - DAY)
+    .await
+    .is_err());
+
+  // The end of the moved span would overflow
+  assert!(test
+    .editor
+    .move_timeline_span(&test.view_id, row_id, i64::MAX - DAY)
+    .await
+    .is_err());
+
+  let spans = test.editor.get_all_timeline_spans(&test.view_id).await;
+  assert_eq!(spans[0].start, start);
+  assert_eq!(spans[0].end, start + 3 * DAY);
+}
+
+#[tokio::test]
+async fn update_grid_layout_to_timeline_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let date_field = test.get_first_field(FieldType::DateTime);
+  test
+    .editor
+    .update_view_layout(&test.view_id, DatabaseLayoutPB::Timeline)
+    .await
+    .unwrap();
+
+  // The timeline starts the spans at the first date field by default
+  let database = test.editor.get_database_data(&test.view_id).await.unwrap();
+  assert_eq!(database.layout_type, DatabaseLayoutPB::Timeline);
+  let layout_setting = test
+    .editor
+    .get_layout_setting(&test.view_id, DatabaseLayout::Grid)
+    .await
+    .unwrap();
+  assert_eq!(
+    layout_setting.timeline.unwrap().start_field_id,
+    date_field.id
+  );
+  assert_eq!(
+    test
+      .editor
+      .get_all_timeline_spans(&test.view_id)
+      .await
+      .len(),
+    6
+  );
+
+  // The timeline setting is removed when the view becomes a grid again
+  test
+    .editor
+    .update_view_layout(&test.view_id, DatabaseLayoutPB::Grid)
+    .await
+    .unwrap();
+  let database = test.editor.get_database_data(&test.view_id).await.unwrap();
+  assert_eq!(database.layout_type, DatabaseLayoutPB::Grid);
+  assert!(test
+    .editor
+    .get_all_timeline_spans(&test.view_id)
+    .await
+    .is_empty());
+}
+
+#[tokio::test]
+async fn set_timeline_setting_on_grid_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let date_field = test.get_first_field(FieldType::DateTime);
+  let changeset = LayoutSettingChangeset {
+    view_id: test.view_id.clone(),
+    layout_type: DatabaseLayout::Grid,
+    board: None,
+    calendar: None,
+    timeline: Some(TimelineLayoutSetting::new(date_field.id.clone())),
+  };
+  let result = test
+    .editor
+    .set_layout_setting(&test.view_id, changeset)
+    .await;
+  assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn timeline_with_text_start_field_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  test
+    .editor
+    .update_view_layout(&test.view_id, DatabaseLayoutPB::Timeline)
+    .await
+    .unwrap();
+  let text_field = test.get_first_field(FieldType::RichText);
+  let changeset = LayoutSettingChangeset {
+    view_id: test.view_id.clone(),
+    layout_type: DatabaseLayout::Grid,
+    board: None,
+    calendar: None,
+    timeline: Some(TimelineLayoutSetting::new(text_field.id.clone())),
+  };
+  let result = test
+    .editor
+    .set_layout_setting(&test.view_id, changeset)
+    .await;
+  assert!(result.is_err());
+
+  // The timeline keeps its previous setting
+  let spans = test.editor.get_all_timeline_spans(&test.view_id).await;
+  assert_eq!(spans.len(), 6);
+}
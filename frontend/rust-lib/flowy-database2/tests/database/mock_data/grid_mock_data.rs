@@ -45,6 +45,7 @@ pub fn make_test_grid() -> DatabaseData {
           date_format: DateFormat::US,
           time_format: TimeFormat::TwentyFourHour,
           timezone_id: "Etc/UTC".to_owned(),
+          is_range: false,
         };
         let name = "Time";
         let date_field = FieldBuilder::new(field_type, date_type_option)
@@ -128,6 +129,13 @@ pub fn make_test_grid() -> DatabaseData {
           .build();
         fields.push(checklist_field);
       },
+      // The tests of these field types create the fields they need
+      FieldType::Relation
+      | FieldType::Lookup
+      | FieldType::Formula
+      | FieldType::Person
+      | FieldType::Files
+      | FieldType::AutoNumber => {},
     }
   }
 
@@ -1,9 +1,20 @@
-use flowy_database2::entities::FieldType;
+use std::io::Read;
+
+use collab_database::fields::Field;
+use collab_database::rows::RowId;
+use flowy_database2::entities::{
+  CheckboxFilterConditionPB, CheckboxFilterPB, FieldType, FieldVisibility, UpdateFilterPayloadPB,
+};
 use flowy_database2::services::cell::stringify_cell_data;
-use flowy_database2::services::field::CHECK;
+use flowy_database2::services::field::{
+  DateCellChangeset, DateTypeOption, RelationCellChangeset, CHECK,
+};
+use flowy_database2::services::field_settings::FieldSettingsChangesetParams;
+use flowy_database2::services::filter::UpdateFilterParams;
 use flowy_database2::services::share::csv::CSVFormat;
 
 use crate::database::database_editor::DatabaseEditorTest;
+use crate::database::field_test::util::create_relation_field;
 
 #[tokio::test]
 async fn export_meta_csv_test() {
@@ -99,6 +110,12 @@ async fn export_and_then_import_meta_csv_test() {
           FieldType::Checklist => {},
           FieldType::LastEditedTime => {},
           FieldType::CreatedTime => {},
+          FieldType::Relation => {},
+          FieldType::Lookup => {},
+          FieldType::Formula => {},
+          FieldType::Person => {},
+          FieldType::Files => {},
+          FieldType::AutoNumber => {},
         }
       } else {
         panic!(
@@ -180,6 +197,12 @@ async fn history_database_import_test() {
           FieldType::Checklist => {},
           FieldType::LastEditedTime => {},
           FieldType::CreatedTime => {},
+          FieldType::Relation => {},
+          FieldType::Lookup => {},
+          FieldType::Formula => {},
+          FieldType::Person => {},
+          FieldType::Files => {},
+          FieldType::AutoNumber => {},
         }
       } else {
         panic!(
@@ -190,3 +213,322 @@ async fn history_database_import_test() {
     }
   }
 }
+
+#[tokio::test]
+async fn export_and_then_import_csv_file_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let database = test.editor.clone();
+  let file_path = std::env::temp_dir()
+    .join(format!("{}.csv", nanoid::nanoid!(6)))
+    .to_str()
+    .unwrap()
+    .to_string();
+  database
+    .export_csv_to_file(CSVFormat::META, file_path.clone())
+    .await
+    .unwrap();
+  let csv = database.export_csv(CSVFormat::META).await.unwrap();
+  assert_eq!(std::fs::read_to_string(&file_path).unwrap(), csv);
+
+  let result = test.import_file(file_path.clone(), CSVFormat::META).await;
+  let imported_database = test.get_database(&result.database_id).await.unwrap();
+  let fields = imported_database.get_fields(&result.view_id, None);
+  let rows = imported_database.get_rows(&result.view_id).await.unwrap();
+  assert_eq!(fields.len(), test.get_fields().len());
+  assert_eq!(rows.len(), test.row_details.len());
+  std::fs::remove_file(file_path).unwrap();
+}
+
+#[tokio::test]
+async fn export_json_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let json = test.editor.export_json().await.unwrap();
+  let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+  let rows = value["rows"].as_array().unwrap();
+  assert_eq!(
+    value["fields"].as_array().unwrap().len(),
+    test.get_fields().len()
+  );
+  assert_eq!(rows.len(), test.row_details.len());
+
+  let cells = &rows[0]["cells"];
+  let number_field = test.get_first_field(FieldType::Number);
+  let date_field = test.get_first_field(FieldType::DateTime);
+  let single_select_field = test.get_first_field(FieldType::SingleSelect);
+  let multi_select_field = test.get_first_field(FieldType::MultiSelect);
+  let checkbox_field = test.get_first_field(FieldType::Checkbox);
+  assert_eq!(cells[&number_field.id], serde_json::json!(1.0));
+  assert_eq!(cells[&date_field.id], serde_json::json!("2022-03-14"));
+  assert_eq!(cells[&single_select_field.id], serde_json::Value::Null);
+  assert_eq!(
+    cells[&multi_select_field.id],
+    serde_json::json!(["Google", "Facebook"])
+  );
+  assert_eq!(cells[&checkbox_field.id], serde_json::json!(true));
+}
+
+#[tokio::test]
+async fn export_date_in_field_timezone_test() {
+  let mut test = DatabaseEditorTest::new_grid().await;
+  let date_field = test.get_first_field(FieldType::DateTime);
+  let type_option = DateTypeOption {
+    timezone_id: "Asia/Shanghai".to_owned(),
+    ..date_field
+      .get_type_option::<DateTypeOption>(FieldType::DateTime)
+      .unwrap()
+  };
+  test
+    .editor
+    .update_field_type_option(&date_field.id, type_option.into(), date_field.clone())
+    .await
+    .unwrap();
+
+  // 2022-03-14 00:00 in UTC+8, which is still 2022-03-13 in UTC
+  let row_id = test.row_details[0].row.id.clone();
+  test
+    .update_cell(
+      &date_field.id,
+      row_id,
+      DateCellChangeset {
+        date: Some(1647187200),
+        ..Default::default()
+      },
+    )
+    .await
+    .unwrap();
+
+  let json = test.editor.export_json().await.unwrap();
+  let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+  assert_eq!(
+    value["rows"][0]["cells"][&date_field.id],
+    serde_json::json!("2022-03-14")
+  );
+
+  let file_path = std::env::temp_dir()
+    .join(format!("{}.xlsx", nanoid::nanoid!(6)))
+    .to_str()
+    .unwrap()
+    .to_string();
+  test
+    .editor
+    .export_xlsx_to_file(file_path.clone())
+    .await
+    .unwrap();
+  let file = std::fs::File::open(&file_path).unwrap();
+  let mut archive = zip::ZipArchive::new(file).unwrap();
+  let mut sheet = String::new();
+  archive
+    .by_name("xl/worksheets/sheet1.xml")
+    .unwrap()
+    .read_to_string(&mut sheet)
+    .unwrap();
+  assert!(sheet.contains(r#"<c r="C2" s="1"><v>44634</v></c>"#));
+  std::fs::remove_file(file_path).unwrap();
+}
+
+/// Creates a relation field that refers to its own database, then links the first row to the
+/// third row, whose name is C, and to a row that doesn't exist.
+async fn link_first_row(test: &mut DatabaseEditorTest) -> Field {
+  let database_id = test
+    .sdk
+    .database_manager
+    .get_database_id_with_view_id(&test.view_id)
+    .await
+    .unwrap();
+  let (params, _) = create_relation_field(&test.view_id, &database_id);
+  test
+    .editor
+    .create_field_with_type_option(params)
+    .await
+    .unwrap();
+  let relation_field = test.get_first_field(FieldType::Relation);
+
+  let row_id = test.row_details[0].row.id.clone();
+  let linked_row_id = test.row_details[2].row.id.clone();
+  test
+    .update_cell(
+      &relation_field.id,
+      row_id,
+      RelationCellChangeset {
+        inserted_row_ids: vec![linked_row_id, RowId::from("missing_row".to_string())],
+        ..Default::default()
+      },
+    )
+    .await
+    .unwrap();
+  relation_field
+}
+
+#[tokio::test]
+async fn export_json_with_related_row_names_test() {
+  let mut test = DatabaseEditorTest::new_grid().await;
+  let relation_field = link_first_row(&mut test).await;
+
+  // The missing row is exported by its id
+  let json = test.editor.export_json().await.unwrap();
+  let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+  assert_eq!(
+    value["rows"][0]["cells"][&relation_field.id],
+    serde_json::json!(["C", "missing_row"])
+  );
+}
+
+#[tokio::test]
+async fn export_csv_with_related_row_names_test() {
+  let mut test = DatabaseEditorTest::new_grid().await;
+  let relation_field = link_first_row(&mut test).await;
+
+  let csv = test.editor.export_csv(CSVFormat::Original).await.unwrap();
+  let mut reader = csv::Reader::from_reader(csv.as_bytes());
+  let column = reader
+    .headers()
+    .unwrap()
+    .iter()
+    .position(|header| header == relation_field.name)
+    .unwrap();
+  let record = reader.records().next().unwrap().unwrap();
+  assert_eq!(&record[column], "C, missing_row");
+
+  // The META format keeps the ids, so the relation can be restored
+  let csv = test.editor.export_csv(CSVFormat::META).await.unwrap();
+  assert!(csv.contains(test.row_details[2].row.id.as_str()));
+}
+
+#[tokio::test]
+async fn export_markdown_without_hidden_fields_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let number_field = test.get_first_field(FieldType::Number);
+  test
+    .editor
+    .update_field_settings_with_changeset(FieldSettingsChangesetParams {
+      view_id: test.view_id.clone(),
+      field_id: number_field.id.clone(),
+      visibility: Some(FieldVisibility::AlwaysHidden),
+      width: None,
+    })
+    .await
+    .unwrap();
+
+  let markdown = test
+    .editor
+    .export_markdown(&test.view_id, false)
+    .await
+    .unwrap();
+  let lines = markdown.lines().collect::<Vec<&str>>();
+  assert_eq!(lines.len(), test.row_details.len() + 2);
+  assert!(lines[0].starts_with("| Name | Time | Status | Platform |"));
+  assert!(!lines[0].contains("Price"));
+  assert!(lines[1].starts_with("| --- | --- |"));
+  assert!(lines[2].starts_with("| A | 2022/03/14 |  | Google,Facebook | Yes |"));
+}
+
+#[tokio::test]
+async fn export_xlsx_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let file_path = std::env::temp_dir()
+    .join(format!("{}.xlsx", nanoid::nanoid!(6)))
+    .to_str()
+    .unwrap()
+    .to_string();
+  test
+    .editor
+    .export_xlsx_to_file(file_path.clone())
+    .await
+    .unwrap();
+
+  let file = std::fs::File::open(&file_path).unwrap();
+  let mut archive = zip::ZipArchive::new(file).unwrap();
+  let mut sheet = String::new();
+  archive
+    .by_name("xl/worksheets/sheet1.xml")
+    .unwrap()
+    .read_to_string(&mut sheet)
+    .unwrap();
+  // The price is a number cell, the date is a date cell and the checkbox is a boolean cell.
+  assert!(sheet.contains(r#"<c r="B2"><v>1</v></c>"#));
+  assert!(sheet.contains(r#"<c r="C2" s="1"><v>44634</v></c>"#));
+  assert!(sheet.contains(r#"<c r="F2" t="b"><v>1</v></c>"#));
+  assert!(sheet.contains(r#"<t xml:space="preserve">Google,Facebook</t>"#));
+  std::fs::remove_file(file_path).unwrap();
+}
+
+#[tokio::test]
+async fn export_view_csv_with_filter_and_hidden_field_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let checkbox_field = test.get_first_field(FieldType::Checkbox);
+  let payload = UpdateFilterPayloadPB::new(
+    &test.view_id,
+    &checkbox_field,
+    CheckboxFilterPB {
+      condition: CheckboxFilterConditionPB::IsChecked,
+    },
+  );
+  let params: UpdateFilterParams = payload.try_into().unwrap();
+  test.editor.create_or_update_filter(params).await.unwrap();
+
+  let number_field = test.get_first_field(FieldType::Number);
+  test
+    .editor
+    .update_field_settings_with_changeset(FieldSettingsChangesetParams {
+      view_id: test.view_id.clone(),
+      field_id: number_field.id.clone(),
+      visibility: Some(FieldVisibility::AlwaysHidden),
+      width: None,
+    })
+    .await
+    .unwrap();
+
+  let csv = test
+    .editor
+    .export_view_csv(&test.view_id, CSVFormat::Original, false)
+    .await
+    .unwrap();
+  let mut reader = csv::Reader::from_reader(csv.as_bytes());
+  let headers = reader.headers().unwrap().clone();
+  assert!(!headers.iter().any(|header| header == number_field.name));
+  let checkbox_index = headers
+    .iter()
+    .position(|header| header == checkbox_field.name)
+    .unwrap();
+  let records = reader
+    .records()
+    .map(|record| record.unwrap())
+    .collect::<Vec<_>>();
+  assert_eq!(records.len(), 3);
+  for record in records {
+    assert_eq!(&record[checkbox_index], "Yes");
+  }
+}
+
+#[tokio::test]
+async fn export_board_view_with_group_headers_test() {
+  let test = DatabaseEditorTest::new_board().await;
+  let groups = test
+    .editor
+    .load_groups(&test.view_id)
+    .await
+    .unwrap()
+    .items
+    .into_iter()
+    .filter(|group| group.is_visible)
+    .collect::<Vec<_>>();
+
+  let markdown = test
+    .editor
+    .export_markdown(&test.view_id, true)
+    .await
+    .unwrap();
+  for group in &groups {
+    assert!(markdown.contains(&format!("## {}\n", group.group_name)));
+  }
+
+  let csv = test
+    .editor
+    .export_view_csv(&test.view_id, CSVFormat::Original, true)
+    .await
+    .unwrap();
+  let mut reader = csv::Reader::from_reader(csv.as_bytes());
+  assert_eq!(&reader.headers().unwrap()[0], "Group");
+  let num_of_rows = groups.iter().map(|group| group.rows.len()).sum::<usize>();
+  assert_eq!(reader.records().count(), num_of_rows);
+}
@@ -0,0 +1,105 @@
+use std::time::Duration;
+
+use collab_database::database::gen_database_view_id;
+use flowy_database2::entities::CSVImportProgressPB;
+use flowy_database2::notification::DatabaseNotification;
+use flowy_database2::services::share::csv::CSVFormat;
+use flowy_error::ErrorCode;
+use tokio::time::timeout;
+
+use crate::database::database_editor::DatabaseEditorTest;
+
+/// Writes a CSV file with a name and an amount column and `num_of_rows` rows.
+fn write_csv_file(num_of_rows: usize) -> String {
+  let mut csv = "Name,Amount\n".to_string();
+  for i in 0..num_of_rows {
+    csv.push_str(&format!("Row {},{}\n", i, i));
+  }
+  let file_path = std::env::temp_dir()
+    .join(format!("{}.csv", nanoid::nanoid!(6)))
+    .to_str()
+    .unwrap()
+    .to_string();
+  std::fs::write(&file_path, csv).unwrap();
+  file_path
+}
+
+#[tokio::test]
+async fn import_csv_file_in_batches_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let file_path = write_csv_file(2500);
+  let view_id = gen_database_view_id();
+  let mut rx = test
+    .sdk
+    .notification_sender
+    .subscribe::<CSVImportProgressPB>(&view_id, DatabaseNotification::DidUpdateCSVImportProgress);
+
+  let result = test
+    .sdk
+    .database_manager
+    .import_csv_from_file(view_id.clone(), file_path.clone(), CSVFormat::Original)
+    .await
+    .unwrap();
+  let database = test.get_database(&result.database_id).await.unwrap();
+  let rows = database.get_rows(&result.view_id).await.unwrap();
+  assert_eq!(rows.len(), 2500);
+
+  // One progress notification for each batch of 1000 rows.
+  let mut num_of_rows = vec![];
+  let mut progress = 0.0;
+  for _ in 0..3 {
+    let notification = timeout(Duration::from_secs(5), rx.recv())
+      .await
+      .unwrap()
+      .unwrap();
+    assert!(notification.progress >= progress);
+    progress = notification.progress;
+    num_of_rows.push(notification.num_of_rows);
+  }
+  assert_eq!(num_of_rows, vec![1000, 2000, 2500]);
+  assert_eq!(progress, 1.0);
+  std::fs::remove_file(file_path).unwrap();
+}
+
+#[tokio::test]
+async fn cancel_csv_file_import_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let file_path = write_csv_file(20000);
+  let view_id = gen_database_view_id();
+  let mut rx = test
+    .sdk
+    .notification_sender
+    .subscribe::<CSVImportProgressPB>(&view_id, DatabaseNotification::DidUpdateCSVImportProgress);
+
+  let database_manager = test.sdk.database_manager.clone();
+  let cloned_view_id = view_id.clone();
+  let cloned_file_path = file_path.clone();
+  let handle = tokio::spawn(async move {
+    database_manager
+      .import_csv_from_file(cloned_view_id, cloned_file_path, CSVFormat::Original)
+      .await
+  });
+
+  // Cancels the import after the first batch is imported.
+  timeout(Duration::from_secs(5), rx.recv())
+    .await
+    .unwrap()
+    .unwrap();
+  test.sdk.database_manager.cancel_csv_import(&view_id);
+
+  let error = handle.await.unwrap().unwrap_err();
+  assert_eq!(error.code, ErrorCode::ImportCancelled);
+
+  // The view of the partially imported database is deleted.
+  let is_deleted = match test
+    .sdk
+    .database_manager
+    .get_database_with_view_id(&view_id)
+    .await
+  {
+    Ok(database) => database.get_layout_type_pb(&view_id).is_err(),
+    Err(_) => true,
+  };
+  assert!(is_deleted);
+  std::fs::remove_file(file_path).unwrap();
+}
@@ -12,7 +12,7 @@ use flowy_database2::entities::{
 };
 use flowy_database2::services::cell::stringify_cell_data;
 use flowy_database2::services::database_view::DatabaseViewChanged;
-use flowy_database2::services::sort::SortCondition;
+use flowy_database2::services::sort::{EmptyPlacement, SelectOptionOrder, SortCondition};
 
 use crate::database::database_editor::DatabaseEditorTest;
 
@@ -21,6 +21,12 @@ pub enum SortScript {
     field: Field,
     condition: SortCondition,
   },
+  InsertSortWithOptions {
+    field: Field,
+    condition: SortCondition,
+    empty_placement: EmptyPlacement,
+    option_order: SelectOptionOrder,
+  },
   ReorderSort {
     from_sort_id: String,
     to_sort_id: String,
@@ -64,24 +70,53 @@ impl DatabaseSortTest {
     }
   }
 
+  async fn insert_sort(
+    &mut self,
+    field: Field,
+    condition: SortCondition,
+    empty_placement: EmptyPlacement,
+    option_order: SelectOptionOrder,
+  ) {
+    self.recv = Some(
+      self
+        .editor
+        .subscribe_view_changed(&self.view_id)
+        .await
+        .unwrap(),
+    );
+    let params = UpdateSortPayloadPB {
+      view_id: self.view_id.clone(),
+      field_id: field.id.clone(),
+      sort_id: None,
+      field_type: FieldType::from(field.field_type),
+      condition: condition.into(),
+      empty_placement: empty_placement.into(),
+      option_order: option_order.into(),
+    };
+    let _ = self.editor.create_or_update_sort(params).await.unwrap();
+  }
+
   pub async fn run_script(&mut self, script: SortScript) {
     match script {
       SortScript::InsertSort { condition, field } => {
-        self.recv = Some(
-          self
-            .editor
-            .subscribe_view_changed(&self.view_id)
-            .await
-            .unwrap(),
-        );
-        let params = UpdateSortPayloadPB {
-          view_id: self.view_id.clone(),
-          field_id: field.id.clone(),
-          sort_id: None,
-          field_type: FieldType::from(field.field_type),
-          condition: condition.into(),
-        };
-        let _ = self.editor.create_or_update_sort(params).await.unwrap();
+        self
+          .insert_sort(
+            field,
+            condition,
+            EmptyPlacement::default(),
+            SelectOptionOrder::default(),
+          )
+          .await;
+      },
+      SortScript::InsertSortWithOptions {
+        field,
+        condition,
+        empty_placement,
+        option_order,
+      } => {
+        self
+          .insert_sort(field, condition, empty_placement, option_order)
+          .await;
       },
       SortScript::ReorderSort {
         from_sort_id,
@@ -1,5 +1,5 @@
 use flowy_database2::entities::FieldType;
-use flowy_database2::services::sort::SortCondition;
+use flowy_database2::services::sort::{EmptyPlacement, SelectOptionOrder, SortCondition};
 
 use crate::database::sort_test::script::{DatabaseSortTest, SortScript::*};
 
@@ -381,3 +381,22 @@ async fn sort_multi_select_by_descending_test() {
   ];
   test.run_scripts(scripts).await;
 }
+
+#[tokio::test]
+async fn sort_single_select_with_empty_first_test() {
+  let mut test = DatabaseSortTest::new().await;
+  let single_select = test.get_first_field(FieldType::SingleSelect);
+  let scripts = vec![
+    InsertSortWithOptions {
+      field: single_select.clone(),
+      condition: SortCondition::Descending,
+      empty_placement: EmptyPlacement::First,
+      option_order: SelectOptionOrder::Position,
+    },
+    AssertCellContentOrder {
+      field_id: single_select.id.clone(),
+      orders: vec!["", "", "", "Planned", "Planned", "Completed", "Completed"],
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
@@ -7,6 +7,7 @@ use collab::core::collab::MutexCollab;
 use collab_document::{blocks::DocumentData, document::Document};
 use futures::StreamExt;
 use parking_lot::Mutex;
+use tokio::sync::broadcast;
 
 use flowy_error::FlowyResult;
 use lib_dispatch::prelude::af_spawn;
@@ -22,13 +23,18 @@ impl MutexDocument {
   /// Open a document with the given collab.
   /// # Arguments
   /// * `collab` - the identifier of the collaboration instance
+  /// * `changed_notifier` - receives the id of the document whenever the document was changed
   ///
   /// # Returns
   /// * `Result<Document, FlowyError>` - a Result containing either a new Document object or an Error if the document creation failed
-  pub fn open(doc_id: &str, collab: Arc<MutexCollab>) -> FlowyResult<Self> {
+  pub fn open(
+    doc_id: &str,
+    collab: Arc<MutexCollab>,
+    changed_notifier: broadcast::Sender<String>,
+  ) -> FlowyResult<Self> {
     #[allow(clippy::arc_with_non_send_sync)]
     let document = Document::open(collab.clone()).map(|inner| Self(Arc::new(Mutex::new(inner))))?;
-    subscribe_document_changed(doc_id, &document);
+    subscribe_document_changed(doc_id, &document, changed_notifier);
     subscribe_document_snapshot_state(&collab);
     subscribe_document_sync_state(&collab);
     Ok(document)
@@ -49,7 +55,11 @@ impl MutexDocument {
   }
 }
 
-fn subscribe_document_changed(doc_id: &str, document: &MutexDocument) {
+fn subscribe_document_changed(
+  doc_id: &str,
+  document: &MutexDocument,
+  changed_notifier: broadcast::Sender<String>,
+) {
   let doc_id = doc_id.to_string();
   document
     .lock()
@@ -58,6 +68,7 @@ fn subscribe_document_changed(doc_id: &str, document: &MutexDocument) {
       send_notification(&doc_id, DocumentNotification::DidReceiveUpdate)
         .payload::<DocEventPB>((events, is_remote).into())
         .send();
+      let _ = changed_notifier.send(doc_id.clone());
     });
 }
 
@@ -11,10 +11,11 @@ use collab_document::document::Document;
 use collab_document::document_data::default_document_data;
 use collab_entity::CollabType;
 use collab_plugins::CollabKVDB;
-use flowy_storage::object_from_disk;
+use flowy_storage::upload_file_from_disk;
 use lru::LruCache;
 use parking_lot::Mutex;
 use tokio::io::AsyncWriteExt;
+use tokio::sync::broadcast;
 use tracing::error;
 use tracing::info;
 use tracing::warn;
@@ -54,6 +55,8 @@ pub struct DocumentManager {
   cloud_service: Arc<dyn DocumentCloudService>,
   storage_service: Weak<dyn ObjectStorageService>,
   snapshot_service: Arc<dyn DocumentSnapshotService>,
+  /// Sends the id of the opened document that was changed, locally or remotely.
+  document_changed_notifier: broadcast::Sender<String>,
 }
 
 impl DocumentManager {
@@ -65,6 +68,7 @@ impl DocumentManager {
     snapshot_service: Arc<dyn DocumentSnapshotService>,
   ) -> Self {
     let documents = Arc::new(Mutex::new(LruCache::new(NonZeroUsize::new(10).unwrap())));
+    let (document_changed_notifier, _) = broadcast::channel(100);
     Self {
       user_service,
       collab_builder,
@@ -72,9 +76,16 @@ impl DocumentManager {
       cloud_service,
       storage_service,
       snapshot_service,
+      document_changed_notifier,
     }
   }
 
+  /// Subscribe to the changes of the opened documents. The receiver gets the id of the document
+  /// that was changed.
+  pub fn subscribe_document_changed(&self) -> broadcast::Receiver<String> {
+    self.document_changed_notifier.subscribe()
+  }
+
   pub async fn initialize(&self, _uid: i64, _workspace_id: String) -> FlowyResult<()> {
     self.documents.lock().clear();
     Ok(())
@@ -156,7 +167,11 @@ impl DocumentManager {
     let collab = self
       .collab_for_document(uid, doc_id, doc_state, true)
       .await?;
-    let document = Arc::new(MutexDocument::open(doc_id, collab)?);
+    let document = Arc::new(MutexDocument::open(
+      doc_id,
+      collab,
+      self.document_changed_notifier.clone(),
+    )?);
 
     // save the document to the memory and read it from the memory if we open the same document again.
     // and we don't want to subscribe to the document changes if we open the same document again.
@@ -256,24 +271,15 @@ impl DocumentManager {
     local_file_path: &str,
     is_async: bool,
   ) -> FlowyResult<String> {
-    let (object_identity, object_value) = object_from_disk(&workspace_id, local_file_path).await?;
     let storage_service = self.storage_service_upgrade()?;
-    let url = storage_service.get_object_url(object_identity).await?;
-
-    let clone_url = url.clone();
-
-    match is_async {
-      false => storage_service.put_object(clone_url, object_value).await?,
-      true => {
-        // let the upload happen in the background
-        af_spawn(async move {
-          if let Err(e) = storage_service.put_object(clone_url, object_value).await {
-            error!("upload file failed: {}", e);
-          }
-        });
-      },
-    }
-    Ok(url)
+    upload_file_from_disk(
+      storage_service,
+      &workspace_id,
+      local_file_path,
+      None,
+      is_async,
+    )
+    .await
   }
 
   pub async fn download_file(&self, local_file_path: String, url: String) -> FlowyResult<()> {
@@ -13,6 +13,13 @@ pub const URL: &str = "url";
 pub const CAPTION: &str = "caption";
 pub const ALIGN: &str = "align";
 
+pub const ROWS_LEN: &str = "rowsLen";
+pub const COLS_LEN: &str = "colsLen";
+pub const ROW_POSITION: &str = "rowPosition";
+pub const COL_POSITION: &str = "colPosition";
+pub const COL_DEFAULT_WIDTH: &str = "colDefaultWidth";
+pub const WIDTH: &str = "width";
+
 pub const PAGE: &str = "page";
 pub const HEADING: &str = "heading";
 pub const PARAGRAPH: &str = "paragraph";
@@ -25,6 +32,8 @@ pub const CALLOUT: &str = "callout";
 pub const IMAGE: &str = "image";
 pub const DIVIDER: &str = "divider";
 pub const MATH_EQUATION: &str = "math_equation";
+pub const TABLE: &str = "table";
+pub const TABLE_CELL: &str = "table/cell";
 pub const BOLD: &str = "bold";
 pub const ITALIC: &str = "italic";
 pub const STRIKETHROUGH: &str = "strikethrough";
@@ -35,6 +44,9 @@ pub const BG_COLOR: &str = "bg_color";
 
 pub const FORMULA: &str = "formula";
 pub const MENTION: &str = "mention";
+pub const MENTION_TYPE: &str = "type";
+pub const MENTION_TYPE_PAGE: &str = "page";
+pub const MENTION_PAGE_ID: &str = "page_id";
 
 pub const TEXT_DIRECTION: &str = "text_direction";
 
@@ -100,6 +112,17 @@ pub const MENU_TAG_NAME: &str = "menu";
 
 pub const MARK_TAG_NAME: &str = "mark";
 
+pub const TABLE_TAG_NAME: &str = "table";
+pub const THEAD_TAG_NAME: &str = "thead";
+pub const TBODY_TAG_NAME: &str = "tbody";
+pub const TFOOT_TAG_NAME: &str = "tfoot";
+pub const TR_TAG_NAME: &str = "tr";
+pub const TH_TAG_NAME: &str = "th";
+pub const TD_TAG_NAME: &str = "td";
+pub const COLGROUP_TAG_NAME: &str = "colgroup";
+pub const COL_TAG_NAME: &str = "col";
+pub const COLSPAN: &str = "colspan";
+
 pub const FONT_WEIGHT: &str = "font-weight";
 pub const FONT_STYLE: &str = "font-style";
 pub const TEXT_DECORATION: &str = "text-decoration";
@@ -1,10 +1,12 @@
 use crate::parser::constant::DELTA;
-use crate::parser::parser_entities::{ConvertBlockToHtmlParams, InsertDelta, NestedBlock, Range};
-use crate::parser::utils::{get_delta_for_block, get_delta_for_selection};
+use crate::parser::parser_entities::{
+  ConvertBlockToHtmlParams, ConvertBlockToMarkdownParams, InsertDelta, NestedBlock, Range,
+};
+use crate::parser::utils::{delta_to_text, get_delta_for_block, get_delta_for_selection};
 use collab_document::blocks::DocumentData;
 use std::sync::Arc;
 
-/// DocumentDataParser is a struct for parsing a document's data and converting it to JSON, HTML, or text.
+/// DocumentDataParser is a struct for parsing a document's data and converting it to JSON, HTML, text, or Markdown.
 pub struct DocumentDataParser {
   /// The document data to parse.
   pub document_data: Arc<DocumentData>,
@@ -43,6 +45,20 @@ impl DocumentDataParser {
     }
   }
 
+  /// Converts the JSON to a CommonMark document with the GitHub Flavored Markdown task lists,
+  /// tables and strikethrough.
+  pub fn to_markdown_with_json(&self, json: &Option<NestedBlock>) -> String {
+    if let Some(json) = json {
+      let mut markdown = json.convert_to_markdown(ConvertBlockToMarkdownParams { list_number: 1 });
+      if !markdown.is_empty() {
+        markdown.push('\n');
+      }
+      markdown
+    } else {
+      String::new()
+    }
+  }
+
   /// Converts the document data to HTML.
   pub fn to_html(&self) -> String {
     let json = self.to_json();
@@ -55,6 +71,12 @@ impl DocumentDataParser {
     self.to_text_with_json(&json)
   }
 
+  /// Converts the document data to Markdown.
+  pub fn to_markdown(&self) -> String {
+    let json = self.to_json();
+    self.to_markdown_with_json(&json)
+  }
+
   /// Converts the document data to a nested JSON structure, considering the optional range.
   pub fn to_json(&self) -> Option<NestedBlock> {
     let root_id = &self.document_data.page_id;
@@ -70,6 +92,29 @@ impl DocumentDataParser {
       })
   }
 
+  /// Returns the id and the plain text of the blocks that have text, in the order they appear in
+  /// the document. The range is ignored.
+  pub fn to_block_texts(&self) -> Vec<(String, String)> {
+    let mut block_texts = vec![];
+    let mut block_ids = vec![self.document_data.page_id.clone()];
+    while let Some(block_id) = block_ids.pop() {
+      let block = match self.document_data.blocks.get(&block_id) {
+        None => continue,
+        Some(block) => block,
+      };
+      if let Some(delta) = get_delta_for_block(&block_id, &self.document_data) {
+        let text = delta_to_text(&delta);
+        if !text.trim().is_empty() {
+          block_texts.push((block_id.clone(), text));
+        }
+      }
+      if let Some(children_ids) = self.document_data.meta.children_map.get(&block.children) {
+        block_ids.extend(children_ids.iter().rev().cloned());
+      }
+    }
+    block_texts
+  }
+
   fn block_to_nested_block(
     &self,
     block_id: &str,
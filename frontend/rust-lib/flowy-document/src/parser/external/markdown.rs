@@ -0,0 +1,573 @@
+use crate::parser::constant::*;
+use crate::parser::parser_entities::{InsertDelta, NestedBlock};
+use crate::parser::utils::convert_insert_delta_from_json;
+use percent_encoding::percent_decode_str;
+use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Parse markdown to nested block.
+///
+/// The GitHub Flavored Markdown tables, task lists and strikethrough are supported. The images
+/// are moved out of their paragraph, because an image is a block in the document.
+pub fn parse_markdown_to_nested_block(markdown: &str) -> Option<NestedBlock> {
+  let options = Options::ENABLE_TABLES | Options::ENABLE_TASKLISTS | Options::ENABLE_STRIKETHROUGH;
+  let mut builder = MarkdownBlockBuilder::new();
+  for event in Parser::new_ext(markdown, options) {
+    builder.handle_event(event);
+  }
+  builder.build()
+}
+
+/// A block that is being parsed. The text of the block is collected in the delta, and the image
+/// blocks found in the text are inserted after the block once it's closed.
+struct OpenBlock {
+  block: NestedBlock,
+  delta: Vec<InsertDelta>,
+  trailing_blocks: Vec<NestedBlock>,
+}
+
+impl OpenBlock {
+  fn new(ty: &str) -> Self {
+    Self {
+      block: NestedBlock {
+        ty: ty.to_string(),
+        ..Default::default()
+      },
+      delta: vec![],
+      trailing_blocks: vec![],
+    }
+  }
+
+  fn with_data(mut self, key: &str, value: Value) -> Self {
+    self.block.data.insert(key.to_string(), value);
+    self
+  }
+
+  fn push_text(&mut self, text: &str, attributes: Option<HashMap<String, Value>>) {
+    match self.delta.last_mut() {
+      Some(last) if last.attributes == attributes => last.insert.push_str(text),
+      _ => self.delta.push(InsertDelta {
+        insert: text.to_string(),
+        attributes,
+      }),
+    }
+  }
+
+  /// The first paragraph of a list item or a quote is the text of the block itself.
+  fn can_merge_paragraph(&self) -> bool {
+    matches!(
+      self.block.ty.as_str(),
+      BULLETED_LIST | NUMBERED_LIST | TODO_LIST | QUOTE
+    ) && self.delta.is_empty()
+      && self.block.children.is_empty()
+  }
+}
+
+struct ListContext {
+  start: Option<u64>,
+  is_first_item: bool,
+}
+
+struct MarkdownBlockBuilder {
+  blocks: Vec<OpenBlock>,
+  /// Whether each open paragraph was merged into its parent block.
+  paragraphs: Vec<bool>,
+  lists: Vec<ListContext>,
+  attributes: Vec<(&'static str, Value)>,
+  /// The url and the alt text of the image that is being parsed.
+  image: Option<(String, String)>,
+  /// The row and the column of the table cell that is being parsed.
+  table_position: (usize, usize),
+}
+
+impl MarkdownBlockBuilder {
+  fn new() -> Self {
+    Self {
+      blocks: vec![OpenBlock::new(PAGE)],
+      paragraphs: vec![],
+      lists: vec![],
+      attributes: vec![],
+      image: None,
+      table_position: (0, 0),
+    }
+  }
+
+  fn build(mut self) -> Option<NestedBlock> {
+    // The page is the only block left at the end of the markdown
+    while self.blocks.len() > 1 {
+      self.close_block();
+    }
+    let page = self.blocks.pop()?.block;
+    if page.children.is_empty() {
+      return None;
+    }
+    Some(page)
+  }
+
+  fn current(&mut self) -> &mut OpenBlock {
+    // The page is only closed by `build`, so there is always an open block
+    self.blocks.last_mut().unwrap()
+  }
+
+  fn current_attributes(&self) -> Option<HashMap<String, Value>> {
+    if self.attributes.is_empty() {
+      return None;
+    }
+    Some(
+      self
+        .attributes
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.clone()))
+        .collect(),
+    )
+  }
+
+  fn handle_event(&mut self, event: Event) {
+    match event {
+      Event::Start(tag) => self.start_tag(tag),
+      Event::End(tag) => self.end_tag(tag),
+      Event::Text(text) => self.push_text(&text),
+      Event::Code(code) => {
+        self.attributes.push((CODE, Value::Bool(true)));
+        self.push_text(&code);
+        self.attributes.pop();
+      },
+      Event::Html(html) => self.push_text(&html),
+      Event::FootnoteReference(name) => self.push_text(&format!("[^{}]", name)),
+      Event::SoftBreak => self.push_text(" "),
+      Event::HardBreak => self.push_text("\n"),
+      Event::Rule => self
+        .current()
+        .block
+        .children
+        .push(OpenBlock::new(DIVIDER).block),
+      Event::TaskListMarker(checked) => {
+        let current = self.current();
+        current.block.ty = TODO_LIST.to_string();
+        current
+          .block
+          .data
+          .insert(CHECKED.to_string(), Value::Bool(checked));
+      },
+    }
+  }
+
+  fn start_tag(&mut self, tag: Tag) {
+    match tag {
+      Tag::Paragraph => {
+        let is_merged = self.current().can_merge_paragraph();
+        if !is_merged {
+          self.blocks.push(OpenBlock::new(PARAGRAPH));
+        }
+        self.paragraphs.push(is_merged);
+      },
+      Tag::Heading(level, _, _) => {
+        let block = OpenBlock::new(HEADING).with_data(LEVEL, Value::from(level as u64));
+        self.blocks.push(block);
+      },
+      Tag::BlockQuote => self.blocks.push(OpenBlock::new(QUOTE)),
+      Tag::CodeBlock(kind) => {
+        let language = match kind {
+          CodeBlockKind::Fenced(info) => info.split_whitespace().next().unwrap_or("").to_string(),
+          CodeBlockKind::Indented => String::new(),
+        };
+        let block = OpenBlock::new(CODE).with_data(LANGUAGE, Value::String(language));
+        self.blocks.push(block);
+      },
+      Tag::List(start) => self.lists.push(ListContext {
+        start,
+        is_first_item: true,
+      }),
+      Tag::Item => {
+        let mut block = OpenBlock::new(BULLETED_LIST);
+        if let Some(list) = self.lists.last_mut() {
+          if let Some(start) = list.start {
+            block = OpenBlock::new(NUMBERED_LIST);
+            // The number of the other items follows the first one
+            if list.is_first_item && start != 1 {
+              block = block.with_data(NUMBER, Value::from(start));
+            }
+          }
+          list.is_first_item = false;
+        }
+        self.blocks.push(block);
+      },
+      Tag::Table(alignments) => {
+        let block = OpenBlock::new(TABLE).with_data(COLS_LEN, Value::from(alignments.len()));
+        self.blocks.push(block);
+        self.table_position = (0, 0);
+      },
+      Tag::TableHead | Tag::TableRow => self.table_position.1 = 0,
+      Tag::TableCell => {
+        let (row, col) = self.table_position;
+        let block = OpenBlock::new(TABLE_CELL)
+          .with_data(ROW_POSITION, Value::from(row))
+          .with_data(COL_POSITION, Value::from(col));
+        self.blocks.push(block);
+      },
+      Tag::Emphasis => self.attributes.push((ITALIC, Value::Bool(true))),
+      Tag::Strong => self.attributes.push((BOLD, Value::Bool(true))),
+      Tag::Strikethrough => self.attributes.push((STRIKETHROUGH, Value::Bool(true))),
+      Tag::Link(_, url, _) => self.attributes.push((HREF, Value::String(url.to_string()))),
+      Tag::Image(_, url, _) => self.image = Some((url.to_string(), String::new())),
+      Tag::FootnoteDefinition(_) => self.blocks.push(OpenBlock::new(PARAGRAPH)),
+    }
+  }
+
+  fn end_tag(&mut self, tag: Tag) {
+    match tag {
+      Tag::Paragraph => {
+        if !self.paragraphs.pop().unwrap_or(true) {
+          self.close_block();
+        }
+      },
+      Tag::Heading(..)
+      | Tag::BlockQuote
+      | Tag::CodeBlock(_)
+      | Tag::Item
+      | Tag::FootnoteDefinition(_) => self.close_block(),
+      Tag::List(_) => {
+        self.lists.pop();
+      },
+      Tag::Table(_) => {
+        let rows_len = self.table_position.0;
+        self
+          .current()
+          .block
+          .data
+          .insert(ROWS_LEN.to_string(), Value::from(rows_len));
+        self.close_block();
+      },
+      Tag::TableHead | Tag::TableRow => self.table_position.0 += 1,
+      Tag::TableCell => {
+        self.close_block();
+        self.table_position.1 += 1;
+      },
+      Tag::Emphasis | Tag::Strong | Tag::Strikethrough | Tag::Link(..) => {
+        self.attributes.pop();
+      },
+      Tag::Image(..) => {
+        if let Some((url, alt)) = self.image.take() {
+          let mut block = OpenBlock::new(IMAGE).with_data(URL, Value::String(url));
+          if !alt.is_empty() {
+            block = block.with_data(CAPTION, Value::String(alt));
+          }
+          self.current().trailing_blocks.push(block.block);
+        }
+      },
+    }
+  }
+
+  fn push_text(&mut self, text: &str) {
+    if let Some((_, alt)) = self.image.as_mut() {
+      alt.push_str(text);
+      return;
+    }
+
+    let attributes = self.current_attributes();
+    let current = self.current();
+    if current.block.ty == CODE {
+      current.push_text(text, None);
+    } else {
+      current.push_text(text, attributes);
+    }
+  }
+
+  /// Closes the current block and inserts it into its parent.
+  fn close_block(&mut self) {
+    let OpenBlock {
+      mut block,
+      mut delta,
+      trailing_blocks,
+    } = match self.blocks.pop() {
+      Some(open_block) => open_block,
+      None => return,
+    };
+
+    if block.ty == CODE {
+      // The code of a fenced code block always ends with a line break
+      if let Some(last) = delta.last_mut() {
+        if last.insert.ends_with('\n') {
+          last.insert.pop();
+        }
+      }
+    }
+
+    if block.ty == PARAGRAPH {
+      if let Some(formula) = math_equation_formula(&delta) {
+        block.ty = MATH_EQUATION.to_string();
+        block
+          .data
+          .insert(FORMULA.to_string(), Value::String(formula));
+        delta.clear();
+      }
+    }
+
+    let is_empty = delta.is_empty() && block.children.is_empty();
+    if block.ty == TABLE_CELL {
+      // The text of a cell is kept in its paragraph
+      let mut paragraph = OpenBlock::new(PARAGRAPH).block;
+      if let Ok(value) = serde_json::to_value(&delta) {
+        paragraph.data.insert(DELTA.to_string(), value);
+      }
+      block.children.push(paragraph);
+    } else if !delta.is_empty() {
+      if let Ok(value) = serde_json::to_value(&delta) {
+        block.data.insert(DELTA.to_string(), value);
+      }
+    }
+
+    let parent = match self.blocks.last_mut() {
+      Some(parent) => parent,
+      None => return,
+    };
+
+    // The paragraphs that only contained images are removed
+    if !(block.ty == PARAGRAPH && is_empty) {
+      parent.block.children.push(block);
+    }
+    parent.block.children.extend(trailing_blocks);
+  }
+}
+
+/// Returns the canonical path of the local file that a link in a markdown file points to. The
+/// `base_dir` is the directory of the markdown file. Remote urls and anchors don't have a path.
+///
+/// The `root_dir` is the canonical path of the imported directory. The links to the files outside
+/// of it, such as absolute paths or `../` paths that escape it, don't have a path either, so an
+/// imported file can't make the import read other local files.
+pub fn resolve_markdown_link(root_dir: &Path, base_dir: &Path, link: &str) -> Option<PathBuf> {
+  let is_remote = link.contains("://") || link.starts_with("mailto:") || link.starts_with("data:");
+  if link.is_empty() || link.starts_with('#') || is_remote {
+    return None;
+  }
+  let link = link.split(['#', '?']).next()?;
+  let link = percent_decode_str(link).decode_utf8().ok()?;
+  let path = base_dir.join(link.as_ref()).canonicalize().ok()?;
+  path.starts_with(root_dir).then_some(path)
+}
+
+/// Returns the urls of the image blocks.
+pub fn markdown_image_urls(block: &NestedBlock) -> Vec<String> {
+  let mut urls = vec![];
+  if block.ty == IMAGE {
+    if let Some(url) = block.data.get(URL).and_then(|url| url.as_str()) {
+      urls.push(url.to_string());
+    }
+  }
+  for child in &block.children {
+    urls.extend(markdown_image_urls(child));
+  }
+  urls
+}
+
+/// Rewrites the links of the blocks that are parsed from markdown.
+///
+/// A link that `page_id_of_link` returns a page id for becomes a mention of that page, and the url
+/// of an image block is replaced with the url returned by `new_image_url`.
+pub fn rewrite_markdown_links<P, I>(block: &mut NestedBlock, page_id_of_link: &P, new_image_url: &I)
+where
+  P: Fn(&str) -> Option<String>,
+  I: Fn(&str) -> Option<String>,
+{
+  if block.ty == IMAGE {
+    let url = block
+      .data
+      .get(URL)
+      .and_then(|url| url.as_str())
+      .and_then(new_image_url);
+    if let Some(url) = url {
+      block.data.insert(URL.to_string(), Value::String(url));
+    }
+  }
+
+  let delta = block
+    .data
+    .get(DELTA)
+    .and_then(convert_insert_delta_from_json);
+  if let Some(delta) = delta {
+    let mut new_delta: Vec<InsertDelta> = vec![];
+    let mut last_link: Option<String> = None;
+    for insert in delta {
+      let link = insert
+        .attributes
+        .as_ref()
+        .and_then(|attributes| attributes.get(HREF))
+        .and_then(|href| href.as_str())
+        .map(|href| href.to_string());
+      let page_id = link.as_deref().and_then(page_id_of_link);
+      match page_id {
+        Some(page_id) => {
+          // The text of a link is split into several inserts when it has different styles
+          if last_link != link {
+            let mention = json!({
+              MENTION_TYPE: MENTION_TYPE_PAGE,
+              MENTION_PAGE_ID: page_id,
+            });
+            new_delta.push(InsertDelta {
+              insert: "$".to_string(),
+              attributes: Some(HashMap::from([(MENTION.to_string(), mention)])),
+            });
+          }
+          last_link = link;
+        },
+        None => {
+          new_delta.push(insert);
+          last_link = None;
+        },
+      }
+    }
+    if let Ok(value) = serde_json::to_value(&new_delta) {
+      block.data.insert(DELTA.to_string(), value);
+    }
+  }
+
+  for child in block.children.iter_mut() {
+    rewrite_markdown_links(child, page_id_of_link, new_image_url);
+  }
+}
+
+/// Returns the formula if the paragraph is a `$$` math block.
+fn math_equation_formula(delta: &[InsertDelta]) -> Option<String> {
+  if delta.iter().any(|d| d.attributes.is_some()) {
+    return None;
+  }
+  let text = delta.iter().map(|d| d.insert.as_str()).collect::<String>();
+  let text = text.trim();
+  if text.len() > 4 && text.starts_with("$$") && text.ends_with("$$") {
+    Some(text[2..text.len() - 2].trim().to_string())
+  } else {
+    None
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::parser::constant::*;
+  use crate::parser::external::markdown::{
+    parse_markdown_to_nested_block, resolve_markdown_link, rewrite_markdown_links,
+  };
+  use crate::parser::parser_entities::NestedBlock;
+  use crate::parser::utils::convert_insert_delta_from_json;
+
+  fn text(block: &NestedBlock) -> String {
+    block
+      .data
+      .get(DELTA)
+      .and_then(convert_insert_delta_from_json)
+      .map(|delta| delta.into_iter().map(|d| d.insert).collect())
+      .unwrap_or_default()
+  }
+
+  #[test]
+  fn markdown_list_test() {
+    let markdown = "- [x] done\n- [ ] todo\n  1. first\n  2. second\n";
+    let page = parse_markdown_to_nested_block(markdown).unwrap();
+    assert_eq!(page.children.len(), 2);
+    assert_eq!(page.children[0].ty, TODO_LIST);
+    assert_eq!(page.children[0].data.get(CHECKED).unwrap(), true);
+    assert_eq!(text(&page.children[0]), "done");
+
+    let nested = &page.children[1].children;
+    assert_eq!(nested.len(), 2);
+    assert_eq!(nested[1].ty, NUMBERED_LIST);
+    assert_eq!(text(&nested[1]), "second");
+  }
+
+  #[test]
+  fn markdown_image_and_math_test() {
+    let markdown = "Logo ![AppFlowy](https://appflowy.io/logo.png)\n\n$$\nE = MC^2\n$$\n";
+    let page = parse_markdown_to_nested_block(markdown).unwrap();
+    let types = page
+      .children
+      .iter()
+      .map(|block| block.ty.as_str())
+      .collect::<Vec<&str>>();
+    assert_eq!(types, vec![PARAGRAPH, IMAGE, MATH_EQUATION]);
+    assert_eq!(page.children[1].data.get(CAPTION).unwrap(), "AppFlowy");
+    assert_eq!(page.children[2].data.get(FORMULA).unwrap(), "E = MC^2");
+  }
+
+  #[test]
+  fn markdown_table_test() {
+    let markdown = "| Name | Status |\n| --- | --- |\n| AppFlowy | **Done** |\n";
+    let page = parse_markdown_to_nested_block(markdown).unwrap();
+    let table = &page.children[0];
+    assert_eq!(table.ty, TABLE);
+    assert_eq!(table.data.get(ROWS_LEN).unwrap(), 2);
+    assert_eq!(table.data.get(COLS_LEN).unwrap(), 2);
+    assert_eq!(table.children.len(), 4);
+
+    let cell = &table.children[3];
+    assert_eq!(cell.data.get(ROW_POSITION).unwrap(), 1);
+    assert_eq!(cell.data.get(COL_POSITION).unwrap(), 1);
+    assert_eq!(text(&cell.children[0]), "Done");
+  }
+
+  #[test]
+  fn rewrite_markdown_links_test() {
+    let markdown = "See [**Page** one](Page%20one.md) and [AppFlowy](https://appflowy.io)\n\n![logo](logo.png)\n";
+    let mut page = parse_markdown_to_nested_block(markdown).unwrap();
+    rewrite_markdown_links(
+      &mut page,
+      &|link| (link == "Page%20one.md").then(|| "page_1".to_string()),
+      &|url| Some(format!("https://files.appflowy.io/{}", url)),
+    );
+
+    let delta = page.children[0]
+      .data
+      .get(DELTA)
+      .and_then(convert_insert_delta_from_json)
+      .unwrap();
+    let inserts = delta
+      .iter()
+      .map(|d| d.insert.as_str())
+      .collect::<Vec<&str>>();
+    assert_eq!(inserts, vec!["See ", "$", " and ", "AppFlowy"]);
+    let mention = delta[1].attributes.as_ref().unwrap().get(MENTION).unwrap();
+    assert_eq!(mention.get(MENTION_PAGE_ID).unwrap(), "page_1");
+    assert_eq!(
+      page.children[1].data.get(URL).unwrap(),
+      "https://files.appflowy.io/logo.png"
+    );
+  }
+
+  #[test]
+  fn resolve_markdown_link_test() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let root_dir = temp_dir.path().join("import");
+    let pages_dir = root_dir.join("pages");
+    std::fs::create_dir_all(&pages_dir).unwrap();
+    std::fs::write(root_dir.join("logo.png"), "logo").unwrap();
+    std::fs::write(pages_dir.join("Page one.md"), "# Page one").unwrap();
+    std::fs::write(temp_dir.path().join("secret.txt"), "secret").unwrap();
+    let root_dir = root_dir.canonicalize().unwrap();
+    let pages_dir = pages_dir.canonicalize().unwrap();
+
+    assert_eq!(
+      resolve_markdown_link(&root_dir, &pages_dir, "Page%20one.md#title"),
+      Some(pages_dir.join("Page one.md"))
+    );
+    assert_eq!(
+      resolve_markdown_link(&root_dir, &pages_dir, "../logo.png"),
+      Some(root_dir.join("logo.png"))
+    );
+    assert_eq!(
+      resolve_markdown_link(&root_dir, &pages_dir, "https://appflowy.io/logo.png"),
+      None
+    );
+
+    // The links that escape the imported directory are not resolved
+    assert_eq!(
+      resolve_markdown_link(&root_dir, &pages_dir, "../../secret.txt"),
+      None
+    );
+    let secret_path = temp_dir.path().join("secret.txt").canonicalize().unwrap();
+    assert_eq!(
+      resolve_markdown_link(&root_dir, &pages_dir, &secret_path.to_string_lossy()),
+      None
+    );
+  }
+}
@@ -1,2 +1,3 @@
+pub mod markdown;
 pub mod parser;
 mod utils;
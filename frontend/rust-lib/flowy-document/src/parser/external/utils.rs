@@ -90,29 +90,84 @@ pub fn parse_plaintext_to_nested_block(plaintext: &str) -> Option<NestedBlock> {
     ..Default::default()
   };
 
-  for line in lines {
-    let mut data = HashMap::new();
+  let mut index = 0;
+  while index < lines.len() {
+    // The consecutive lines that have the same number of tab separated columns are a table,
+    // which is how the spreadsheets copy their cells
+    let cols_len = lines[index].split('\t').count();
+    let rows_len = lines[index..]
+      .iter()
+      .take_while(|line| line.split('\t').count() == cols_len)
+      .count();
+    if cols_len > 1 && rows_len > 1 {
+      let rows = lines[index..index + rows_len]
+        .iter()
+        .map(|line| line.split('\t').collect())
+        .collect::<Vec<Vec<&str>>>();
+      current_block.children.push(table_block_from_rows(&rows));
+      index += rows_len;
+      continue;
+    }
+
+    // Create a new block for each non-empty line
+    current_block
+      .children
+      .push(plaintext_paragraph_block(lines[index]));
+    index += 1;
+  }
+
+  if current_block.children.is_empty() {
+    return None;
+  }
+  Some(current_block)
+}
+
+fn plaintext_paragraph_block(text: &str) -> NestedBlock {
+  let mut data = HashMap::new();
 
-    // Insert plaintext into delta
+  // Insert plaintext into delta
+  if !text.is_empty() {
     if let Ok(delta) = serde_json::to_value(vec![InsertDelta {
-      insert: line.to_string(),
+      insert: text.to_string(),
       attributes: None,
     }]) {
       data.insert(DELTA.to_string(), delta);
     }
+  }
 
-    // Create a new block for each non-empty line
-    current_block.children.push(NestedBlock {
-      ty: PARAGRAPH.to_string(),
-      data,
-      children: Default::default(),
-    });
+  NestedBlock {
+    ty: PARAGRAPH.to_string(),
+    data,
+    children: Default::default(),
   }
+}
 
-  if current_block.children.is_empty() {
-    return None;
+fn table_block_from_rows(rows: &[Vec<&str>]) -> NestedBlock {
+  let mut children = vec![];
+  for (row_position, row) in rows.iter().enumerate() {
+    for (col_position, text) in row.iter().enumerate() {
+      let mut data = HashMap::new();
+      data.insert(ROW_POSITION.to_string(), Value::from(row_position));
+      data.insert(COL_POSITION.to_string(), Value::from(col_position));
+      children.push(NestedBlock {
+        ty: TABLE_CELL.to_string(),
+        children: vec![plaintext_paragraph_block(text.trim())],
+        data,
+      });
+    }
+  }
+
+  let mut data = HashMap::new();
+  data.insert(ROWS_LEN.to_string(), Value::from(rows.len()));
+  data.insert(
+    COLS_LEN.to_string(),
+    Value::from(rows.first().map(|row| row.len()).unwrap_or(0)),
+  );
+  NestedBlock {
+    ty: TABLE.to_string(),
+    children,
+    data,
   }
-  Some(current_block)
 }
 
 fn flatten_element_to_json(
@@ -152,6 +207,7 @@ fn flatten_element_to_json(
     },
     PRE_TAG_NAME => process_code_element(node),
     IMG_TAG_NAME => process_image_element(node),
+    TABLE_TAG_NAME => process_table_element(node),
     B_TAG_NAME => {
       // Compatible with Google Docs, <b id=xxx> is the document top level tag, so we need to process it's children
       let id = find_attribute_value(node.to_owned(), "id");
@@ -230,6 +286,143 @@ fn process_code_element(node: ElementRef) -> Option<JSONResult> {
   }))
 }
 
+// process <table> element, each cell of the table contains its nested blocks
+// <table>
+//   <colgroup><col style="width: 120px"></colgroup>
+//   <tr><th>Name</th></tr>
+//   <tr><td><p>AppFlowy</p></td></tr>
+// </table>
+fn process_table_element(node: ElementRef) -> Option<JSONResult> {
+  let mut widths: Vec<Option<f64>> = vec![];
+  let mut rows = vec![];
+  for child in node.children().filter_map(ElementRef::wrap) {
+    match get_tag_name(child).as_str() {
+      COLGROUP_TAG_NAME => {
+        for col in child.children().filter_map(ElementRef::wrap) {
+          let span = find_attribute_value(col, "span")
+            .and_then(|span| span.parse::<usize>().ok())
+            .unwrap_or(1);
+          widths.extend(vec![find_width(col); span.max(1)]);
+        }
+      },
+      THEAD_TAG_NAME | TBODY_TAG_NAME | TFOOT_TAG_NAME => rows.extend(
+        child
+          .children()
+          .filter_map(ElementRef::wrap)
+          .filter(|row| get_tag_name(*row) == TR_TAG_NAME),
+      ),
+      TR_TAG_NAME => rows.push(child),
+      _ => {},
+    }
+  }
+
+  let mut cells_by_row = vec![];
+  for row in rows {
+    let mut cells = vec![];
+    for cell in row.children().filter_map(ElementRef::wrap) {
+      let tag_name = get_tag_name(cell);
+      if tag_name != TD_TAG_NAME && tag_name != TH_TAG_NAME {
+        continue;
+      }
+      let (delta, mut children) = process_node_children(cell, &None, None);
+      if !delta.is_empty() {
+        let mut data = HashMap::new();
+        data.insert(DELTA.to_string(), delta_to_json(&delta));
+        children.insert(
+          0,
+          NestedBlock {
+            ty: PARAGRAPH.to_string(),
+            children: Default::default(),
+            data,
+          },
+        );
+      }
+      cells.push((children, find_width(cell)));
+      // The merged cells are split, the content is kept in the first one
+      let colspan = find_attribute_value(cell, COLSPAN)
+        .and_then(|colspan| colspan.parse::<usize>().ok())
+        .unwrap_or(1);
+      for _ in 1..colspan {
+        cells.push((vec![], None));
+      }
+    }
+    if !cells.is_empty() {
+      cells_by_row.push(cells);
+    }
+  }
+
+  let cols_len = cells_by_row.iter().map(|cells| cells.len()).max()?;
+  let rows_len = cells_by_row.len();
+  // The width of a column comes from its <col> element, or from its first cell that has one
+  let widths = (0..cols_len)
+    .map(|col| {
+      widths.get(col).copied().flatten().or_else(|| {
+        cells_by_row
+          .iter()
+          .find_map(|cells| cells.get(col).and_then(|(_, width)| *width))
+      })
+    })
+    .collect::<Vec<Option<f64>>>();
+  let mut table_children = vec![];
+  for (row_position, mut cells) in cells_by_row.into_iter().enumerate() {
+    cells.resize_with(cols_len, || (vec![], None));
+    for (col_position, (mut children, _)) in cells.into_iter().enumerate() {
+      // A table cell always has a paragraph, so the empty cell can be edited
+      if children.is_empty() {
+        children.push(NestedBlock {
+          ty: PARAGRAPH.to_string(),
+          ..Default::default()
+        });
+      }
+      let mut data = HashMap::new();
+      data.insert(ROW_POSITION.to_string(), Value::from(row_position));
+      data.insert(COL_POSITION.to_string(), Value::from(col_position));
+      if let Some(width) = widths[col_position] {
+        data.insert(WIDTH.to_string(), Value::from(width));
+      }
+      table_children.push(NestedBlock {
+        ty: TABLE_CELL.to_string(),
+        children,
+        data,
+      });
+    }
+  }
+
+  let mut data = HashMap::new();
+  data.insert(ROWS_LEN.to_string(), Value::from(rows_len));
+  data.insert(COLS_LEN.to_string(), Value::from(cols_len));
+  Some(JSONResult::Block(NestedBlock {
+    ty: TABLE.to_string(),
+    children: table_children,
+    data,
+  }))
+}
+
+// get the width in pixels from the width attribute or the style of the element
+// input <col width="120"> or <td style="width: 120px">
+// export 120.0
+fn find_width(node: ElementRef) -> Option<f64> {
+  let parse_pixels = |value: &str| {
+    value
+      .trim()
+      .trim_end_matches("px")
+      .trim()
+      .parse::<f64>()
+      .ok()
+  };
+  let style_width = find_attribute_value(node, STYLE).and_then(|style| {
+    style.split(';').find_map(|property| {
+      let (key, value) = property.split_once(':')?;
+      if key.trim() == WIDTH {
+        parse_pixels(value)
+      } else {
+        None
+      }
+    })
+  });
+  style_width.or_else(|| find_attribute_value(node, WIDTH).and_then(|width| parse_pixels(&width)))
+}
+
 // process "ul" | "ol" | "dl" | "menu" element
 fn process_nested_element(node: ElementRef) -> Option<JSONResult> {
   let tag_name = get_tag_name(node.to_owned());
@@ -1,8 +1,11 @@
 use crate::parse::NotEmptyStr;
 use crate::parser::constant::*;
 use crate::parser::utils::{
-  convert_insert_delta_from_json, convert_nested_block_children_to_html, delta_to_html,
-  delta_to_text, required_not_empty_str, serialize_color_attribute,
+  block_text_to_markdown, convert_insert_delta_from_json, convert_nested_block_children_to_html,
+  convert_nested_block_children_to_markdown, delta_to_html, delta_to_markdown, delta_to_text,
+  escape_markdown, escape_markdown_block_start, indent_markdown, quote_markdown,
+  required_not_empty_str, serialize_color_attribute, table_block_column_widths, table_block_rows,
+  table_cell_to_markdown, table_cell_to_text, wrap_markdown_code, wrap_markdown_code_block,
 };
 use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
 use flowy_error::ErrorCode;
@@ -38,6 +41,7 @@ pub struct RangePB {
  * @field json: bool // export json data
  * @field html: bool // export html data
  * @field text: bool // export text data
+ * @field markdown: bool // export markdown data
  */
 #[derive(Default, ProtoBuf, Debug, Clone)]
 pub struct ParseTypePB {
@@ -49,6 +53,9 @@ pub struct ParseTypePB {
 
   #[pb(index = 3)]
   pub text: bool,
+
+  #[pb(index = 4)]
+  pub markdown: bool,
 }
 /**
 * ConvertDocumentPayloadPB
@@ -76,6 +83,8 @@ pub struct ConvertDocumentResponsePB {
   pub html: Option<String>,
   #[pb(index = 3, one_of)]
   pub text: Option<String>,
+  #[pb(index = 4, one_of)]
+  pub markdown: Option<String>,
 }
 
 pub struct Selection {
@@ -93,6 +102,7 @@ pub struct ParseType {
   pub json: bool,
   pub html: bool,
   pub text: bool,
+  pub markdown: bool,
 }
 
 pub struct ConvertDocumentParams {
@@ -103,7 +113,7 @@ pub struct ConvertDocumentParams {
 
 impl ParseType {
   pub fn any_enabled(&self) -> bool {
-    self.json || self.html || self.text
+    self.json || self.html || self.text || self.markdown
   }
 }
 
@@ -132,6 +142,7 @@ impl From<ParseTypePB> for ParseType {
       json: data.json,
       html: data.html,
       text: data.text,
+      markdown: data.markdown,
     }
   }
 }
@@ -243,6 +254,54 @@ impl InsertDelta {
     }
     html
   }
+
+  pub fn to_markdown(&self) -> String {
+    let attrs = match &self.attributes {
+      Some(attrs) => attrs,
+      None => return escape_markdown(&self.insert),
+    };
+    let is_enabled = |name: &str| {
+      attrs
+        .get(name)
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false)
+    };
+
+    // The emphasis markers can't be next to a whitespace, so the leading and trailing whitespaces
+    // are kept outside of them.
+    let content = self.insert.trim();
+    if content.is_empty() {
+      return self.insert.clone();
+    }
+    let leading = &self.insert[..self.insert.len() - self.insert.trim_start().len()];
+    let trailing = &self.insert[self.insert.trim_end().len()..];
+
+    let mut markdown = if is_enabled(CODE) {
+      wrap_markdown_code(content)
+    } else if is_enabled(FORMULA) {
+      format!("${}$", content)
+    } else {
+      escape_markdown(content)
+    };
+    if is_enabled(ITALIC) {
+      markdown = format!("*{}*", markdown);
+    }
+    if is_enabled(BOLD) {
+      markdown = format!("**{}**", markdown);
+    }
+    if is_enabled(STRIKETHROUGH) {
+      markdown = format!("~~{}~~", markdown);
+    }
+    if let Some(href) = attrs.get(HREF).and_then(|value| value.as_str()) {
+      // The destination must be wrapped in angle brackets if it contains a space or a parenthesis
+      if href.contains(|c: char| c.is_whitespace() || c == '(' || c == ')') {
+        markdown = format!("[{}](<{}>)", markdown, href);
+      } else {
+        markdown = format!("[{}]({})", markdown, href);
+      }
+    }
+    format!("{}{}{}", leading, markdown, trailing)
+  }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -426,6 +485,36 @@ impl NestedBlock {
           html.push_str(&format!("</{}>", BLOCKQUOTE_TAG_NAME));
         }
       },
+      // <table><colgroup><col style="width: 120px" /></colgroup><tbody><tr><td><p>Hello</p></td></tr></tbody></table>
+      TABLE => {
+        html.push_str(&format!("<{}>", TABLE_TAG_NAME));
+        let widths = table_block_column_widths(self);
+        if widths.iter().any(|width| width.is_some()) {
+          html.push_str(&format!("<{}>", COLGROUP_TAG_NAME));
+          for width in widths {
+            match width {
+              Some(width) => html.push_str(&format!(
+                "<{} {}=\"{}: {}px\" />",
+                COL_TAG_NAME, STYLE, WIDTH, width
+              )),
+              None => html.push_str(&format!("<{} />", COL_TAG_NAME)),
+            }
+          }
+          html.push_str(&format!("</{}>", COLGROUP_TAG_NAME));
+        }
+        html.push_str(&format!("<{}>", TBODY_TAG_NAME));
+        for row in table_block_rows(self) {
+          html.push_str(&format!("<{}>", TR_TAG_NAME));
+          for cell in row {
+            let cell_html = cell
+              .map(|cell| convert_nested_block_children_to_html(Arc::new(cell.to_owned())))
+              .unwrap_or_default();
+            html.push_str(&format!("<{}>{}</{}>", TD_TAG_NAME, cell_html, TD_TAG_NAME));
+          }
+          html.push_str(&format!("</{}>", TR_TAG_NAME));
+        }
+        html.push_str(&format!("</{}></{}>", TBODY_TAG_NAME, TABLE_TAG_NAME));
+      },
       // <p>Hello</p>
       PAGE => {
         if !text_html.is_empty() {
@@ -447,6 +536,159 @@ impl NestedBlock {
     html
   }
 
+  pub fn convert_to_markdown(&self, params: ConvertBlockToMarkdownParams) -> String {
+    let delta = self
+      .data
+      .get(DELTA)
+      .and_then(convert_insert_delta_from_json)
+      .unwrap_or_default();
+    let text_markdown = block_text_to_markdown(&delta_to_markdown(&delta));
+    let children_markdown = convert_nested_block_children_to_markdown(&self.children);
+    let join = |first: String, second: String| match (first.is_empty(), second.is_empty()) {
+      (_, true) => first,
+      (true, false) => second,
+      (false, false) => format!("{}\n\n{}", first, second),
+    };
+
+    match self.ty.as_str() {
+      // ## Hello
+      HEADING => {
+        let level = self
+          .data
+          .get(LEVEL)
+          .and_then(|level| level.as_u64())
+          .unwrap_or(1)
+          .clamp(1, 6);
+        let heading = format!("{} {}", "#".repeat(level as usize), text_markdown);
+        join(heading, children_markdown)
+      },
+      // > 😁 Hello
+      CALLOUT => {
+        let icon = self
+          .data
+          .get(ICON)
+          .and_then(|icon| icon.as_str())
+          .unwrap_or_default();
+        let callout = format!("{} {}", icon, text_markdown).trim().to_string();
+        quote_markdown(&join(callout, children_markdown))
+      },
+      // ![caption](https://appflowy.io/image.png)
+      IMAGE => {
+        let url = self
+          .data
+          .get(URL)
+          .and_then(|url| url.as_str())
+          .unwrap_or_default();
+        if url.is_empty() {
+          return String::new();
+        }
+        let caption = self
+          .data
+          .get(CAPTION)
+          .and_then(|caption| caption.as_str())
+          .unwrap_or_default();
+        format!("![{}]({})", escape_markdown(caption), url)
+      },
+      // ---
+      DIVIDER => "---".to_string(),
+      // $$
+      // E = MC^2
+      // $$
+      MATH_EQUATION => {
+        let formula = self
+          .data
+          .get(FORMULA)
+          .and_then(|formula| formula.as_str())
+          .unwrap_or_default();
+        format!("$$\n{}\n$$", formula)
+      },
+      // ```rust
+      // fn main() {}
+      // ```
+      CODE => {
+        let language = self
+          .data
+          .get(LANGUAGE)
+          .and_then(|language| language.as_str())
+          .unwrap_or_default();
+        wrap_markdown_code_block(&delta_to_text(&delta), language)
+      },
+      // - Hello
+      // 1. Hello
+      // - [x] Hello
+      BULLETED_LIST | NUMBERED_LIST | TODO_LIST | TOGGLE_LIST => {
+        let marker = match self.ty.as_str() {
+          NUMBERED_LIST => format!("{}.", params.list_number),
+          TODO_LIST => {
+            let checked = self
+              .data
+              .get(CHECKED)
+              .and_then(|checked| checked.as_bool())
+              .unwrap_or_default();
+            if checked {
+              "- [x]".to_string()
+            } else {
+              "- [ ]".to_string()
+            }
+          },
+          _ => "-".to_string(),
+        };
+        // The children are aligned with the content of the list item.
+        let width = if self.ty == NUMBERED_LIST {
+          marker.len() + 1
+        } else {
+          2
+        };
+        let is_first_child_list = self
+          .children
+          .first()
+          .map(|child| is_markdown_list(&child.ty))
+          .unwrap_or(false);
+        let content = if children_markdown.is_empty() {
+          text_markdown
+        } else if is_first_child_list {
+          format!("{}\n{}", text_markdown, children_markdown)
+        } else {
+          format!("{}\n\n{}", text_markdown, children_markdown)
+        };
+        format!("{} {}", marker, indent_markdown(&content, width))
+          .trim_end()
+          .to_string()
+      },
+      // > Hello
+      QUOTE => quote_markdown(&join(text_markdown, children_markdown)),
+      // | Name | Status |
+      // | --- | --- |
+      // | AppFlowy | Done |
+      TABLE => {
+        let rows = table_block_rows(self);
+        let cols_len = rows.first().map(|row| row.len()).unwrap_or(0);
+        if cols_len == 0 {
+          return String::new();
+        }
+        let mut lines = rows
+          .iter()
+          .map(|row| {
+            let cells = row
+              .iter()
+              .map(|cell| cell.map(table_cell_to_markdown).unwrap_or_default())
+              .collect::<Vec<String>>();
+            format!("| {} |", cells.join(" | "))
+          })
+          .collect::<Vec<String>>();
+        // The first row is the header row of the Markdown table
+        lines.insert(1, format!("|{}", " --- |".repeat(cols_len)));
+        lines.join("\n")
+      },
+      PAGE => join(text_markdown, children_markdown),
+      // Hello
+      _ => join(
+        escape_markdown_block_start(&text_markdown),
+        children_markdown,
+      ),
+    }
+  }
+
   pub fn convert_to_text(&self) -> String {
     let mut text = String::new();
 
@@ -474,6 +716,16 @@ impl NestedBlock {
         let formula = self.data.get(FORMULA).unwrap_or(&Value::Null);
         text.push_str(&format!("{}\n", formula.to_string().trim_matches('\"')));
       },
+      // The cells of a row are separated by tabs
+      TABLE => {
+        for row in table_block_rows(self) {
+          let cells = row
+            .iter()
+            .map(|cell| cell.map(table_cell_to_text).unwrap_or_default())
+            .collect::<Vec<String>>();
+          text.push_str(&format!("{}\n", cells.join("\t")));
+        }
+      },
       PAGE => {
         if !delta_text.is_empty() {
           text.push_str(&format!("{}\n", delta_text));
@@ -498,11 +750,22 @@ pub struct ConvertBlockToHtmlParams {
   pub next_block_ty: Option<String>,
 }
 
+pub struct ConvertBlockToMarkdownParams {
+  /// The number of the block if it's a numbered list.
+  pub list_number: u64,
+}
+
+/// Returns true if the consecutive blocks of the type are rendered as one Markdown list.
+pub fn is_markdown_list(ty: &str) -> bool {
+  matches!(ty, BULLETED_LIST | NUMBERED_LIST | TODO_LIST | TOGGLE_LIST)
+}
+
 #[derive(PartialEq, Eq, Debug, ProtoBuf_Enum, Clone, Default)]
 pub enum InputType {
   #[default]
   Html = 0,
   PlainText = 1,
+  Markdown = 2,
 }
 
 #[derive(Default, ProtoBuf, Debug, Validate)]
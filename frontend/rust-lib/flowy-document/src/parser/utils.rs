@@ -1,5 +1,10 @@
+use crate::parser::constant::{
+  COLS_LEN, COL_DEFAULT_WIDTH, COL_POSITION, NUMBER, NUMBERED_LIST, ROWS_LEN, ROW_POSITION,
+  TABLE_CELL, WIDTH,
+};
 use crate::parser::parser_entities::{
-  ConvertBlockToHtmlParams, InsertDelta, NestedBlock, Selection,
+  is_markdown_list, ConvertBlockToHtmlParams, ConvertBlockToMarkdownParams, InsertDelta,
+  NestedBlock, Selection,
 };
 use collab_document::blocks::DocumentData;
 use serde_json::Value;
@@ -71,6 +76,149 @@ pub fn delta_to_html(delta: &Vec<InsertDelta>) -> String {
   result
 }
 
+/// Converts the delta to Markdown. The consecutive inserts with the same attributes are merged
+/// first, so their markers are not closed and opened again in the middle of the text.
+pub fn delta_to_markdown(delta: &[InsertDelta]) -> String {
+  let mut merged: Vec<InsertDelta> = vec![];
+  for d in delta {
+    match merged.last_mut() {
+      Some(last) if last.attributes == d.attributes => last.insert.push_str(&d.insert),
+      _ => merged.push(d.clone()),
+    }
+  }
+
+  let mut result = String::new();
+  for d in merged {
+    result.push_str(d.to_markdown().as_str());
+  }
+  result
+}
+
+/// Converts the line breaks of a block's text to Markdown hard line breaks. The line breaks at
+/// the start and the end of the text are removed.
+pub fn block_text_to_markdown(text: &str) -> String {
+  text.trim_matches('\n').replace('\n', "\\\n")
+}
+
+/// Escapes the characters that would be parsed as inline Markdown.
+pub fn escape_markdown(text: &str) -> String {
+  let mut result = String::with_capacity(text.len());
+  for c in text.chars() {
+    if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '~') {
+      result.push('\\');
+    }
+    result.push(c);
+  }
+  result
+}
+
+/// Escapes the start of a paragraph that would be parsed as a heading, a quote or a list.
+pub fn escape_markdown_block_start(text: &str) -> String {
+  let digits = text.chars().take_while(|c| c.is_ascii_digit()).count();
+  let is_ordered_list = digits > 0 && matches!(text[digits..].chars().next(), Some('.' | ')'));
+  if is_ordered_list {
+    format!("{}\\{}", &text[..digits], &text[digits..])
+  } else if text.starts_with(['#', '>', '-', '+']) {
+    format!("\\{}", text)
+  } else {
+    text.to_string()
+  }
+}
+
+/// Indents all the lines except the first one by the width. The empty lines are not indented.
+pub fn indent_markdown(markdown: &str, width: usize) -> String {
+  markdown
+    .lines()
+    .enumerate()
+    .map(|(i, line)| {
+      if i == 0 || line.is_empty() {
+        line.to_string()
+      } else {
+        format!("{}{}", " ".repeat(width), line)
+      }
+    })
+    .collect::<Vec<String>>()
+    .join("\n")
+}
+
+/// Prefixes all the lines with the blockquote marker.
+pub fn quote_markdown(markdown: &str) -> String {
+  markdown
+    .lines()
+    .map(|line| {
+      if line.is_empty() {
+        ">".to_string()
+      } else {
+        format!("> {}", line)
+      }
+    })
+    .collect::<Vec<String>>()
+    .join("\n")
+}
+
+/// Wraps the text in an inline code span. The span uses more backticks than the text, so the
+/// backticks of the text don't close it.
+pub fn wrap_markdown_code(text: &str) -> String {
+  let fence = "`".repeat(longest_backtick_run(text) + 1);
+  if text.contains('`') {
+    format!("{} {} {}", fence, text, fence)
+  } else {
+    format!("{}{}{}", fence, text, fence)
+  }
+}
+
+/// Wraps the code in a fenced code block. The fence is longer than the backticks of the code.
+pub fn wrap_markdown_code_block(code: &str, language: &str) -> String {
+  let fence = "`".repeat(std::cmp::max(3, longest_backtick_run(code) + 1));
+  format!("{}{}\n{}\n{}", fence, language, code, fence)
+}
+
+fn longest_backtick_run(text: &str) -> usize {
+  text
+    .split(|c| c != '`')
+    .map(|run| run.len())
+    .max()
+    .unwrap_or(0)
+}
+
+/// Converts the children to Markdown. The blocks are separated by a blank line, except the items
+/// of the same list. The numbered lists continue the number of the previous item.
+pub fn convert_nested_block_children_to_markdown(children: &[NestedBlock]) -> String {
+  let mut markdown = String::new();
+  let mut list_number = 1;
+  let mut prev_block_ty: Option<&str> = None;
+
+  for child in children {
+    if child.ty == NUMBERED_LIST {
+      list_number = if prev_block_ty == Some(NUMBERED_LIST) {
+        list_number + 1
+      } else {
+        child
+          .data
+          .get(NUMBER)
+          .and_then(|number| number.as_u64())
+          .unwrap_or(1)
+      };
+    }
+
+    let child_markdown = child.convert_to_markdown(ConvertBlockToMarkdownParams { list_number });
+    if child_markdown.is_empty() {
+      continue;
+    }
+
+    if !markdown.is_empty() {
+      if prev_block_ty == Some(child.ty.as_str()) && is_markdown_list(&child.ty) {
+        markdown.push('\n');
+      } else {
+        markdown.push_str("\n\n");
+      }
+    }
+    markdown.push_str(&child_markdown);
+    prev_block_ty = Some(child.ty.as_str());
+  }
+  markdown
+}
+
 pub fn convert_nested_block_children_to_html(block: Arc<NestedBlock>) -> String {
   let children = &block.children;
   let mut html = String::new();
@@ -99,6 +247,81 @@ pub fn convert_nested_block_children_to_html(block: Arc<NestedBlock>) -> String
   html
 }
 
+/// Returns the cells of the table block by rows. The cells are placed by their positions, so a
+/// missing cell is `None`.
+pub fn table_block_rows(table: &NestedBlock) -> Vec<Vec<Option<&NestedBlock>>> {
+  let cells = table
+    .children
+    .iter()
+    .filter(|cell| cell.ty == TABLE_CELL)
+    .filter_map(|cell| {
+      let row = cell.data.get(ROW_POSITION)?.as_u64()? as usize;
+      let col = cell.data.get(COL_POSITION)?.as_u64()? as usize;
+      Some((row, col, cell))
+    })
+    .collect::<Vec<_>>();
+  let len_of = |key: &str, max_position: usize| {
+    table
+      .data
+      .get(key)
+      .and_then(|len| len.as_u64())
+      .map(|len| len as usize)
+      .unwrap_or(max_position)
+  };
+  let rows_len = len_of(
+    ROWS_LEN,
+    cells.iter().map(|(row, _, _)| row + 1).max().unwrap_or(0),
+  );
+  let cols_len = len_of(
+    COLS_LEN,
+    cells.iter().map(|(_, col, _)| col + 1).max().unwrap_or(0),
+  );
+
+  let mut rows = vec![vec![None; cols_len]; rows_len];
+  for (row, col, cell) in cells {
+    if row < rows_len && col < cols_len {
+      rows[row][col] = Some(cell);
+    }
+  }
+  rows
+}
+
+/// Returns the width of each column of the table block. The width of a column is the width of
+/// its first cell that has one, or the default width of the table.
+pub fn table_block_column_widths(table: &NestedBlock) -> Vec<Option<f64>> {
+  let default_width = table.data.get(COL_DEFAULT_WIDTH).and_then(|w| w.as_f64());
+  let rows = table_block_rows(table);
+  let cols_len = rows.first().map(|row| row.len()).unwrap_or(0);
+  (0..cols_len)
+    .map(|col| {
+      rows
+        .iter()
+        .filter_map(|row| row[col])
+        .find_map(|cell| cell.data.get(WIDTH).and_then(|w| w.as_f64()))
+        .or(default_width)
+    })
+    .collect()
+}
+
+/// Returns the text of a table cell in one line.
+pub fn table_cell_to_text(cell: &NestedBlock) -> String {
+  let text = cell
+    .children
+    .iter()
+    .map(|child| child.convert_to_text())
+    .collect::<String>();
+  text.trim_end().replace(['\n', '\t'], " ")
+}
+
+/// Returns the Markdown of a table cell in one line, the line breaks become `<br>`.
+pub fn table_cell_to_markdown(cell: &NestedBlock) -> String {
+  convert_nested_block_children_to_markdown(&cell.children)
+    .replace("\\\n", "<br>")
+    .replace("\n\n", "<br>")
+    .replace('\n', " ")
+    .replace('|', "\\|")
+}
+
 pub fn convert_insert_delta_from_json(delta_value: &Value) -> Option<Vec<InsertDelta>> {
   serde_json::from_value::<Vec<InsertDelta>>(delta_value.to_owned()).ok()
 }
@@ -16,6 +16,38 @@ async fn document_data_parse_json_test() {
   assert_eq!(read_me_json, json);
 }
 
+#[tokio::test]
+async fn document_data_to_block_texts_test() {
+  let initial_json_str = include_str!("../assets/json/initial_document.json");
+  let document_data: Arc<DocumentData> = Arc::new(
+    JsonToDocumentParser::json_str_to_document(initial_json_str)
+      .unwrap()
+      .into(),
+  );
+  let parser = DocumentDataParser::new(document_data.clone(), None);
+  let block_texts = parser.to_block_texts();
+  let texts = block_texts
+    .iter()
+    .map(|(_, text)| text.as_str())
+    .collect::<Vec<&str>>();
+  assert_eq!(
+    texts[0..5],
+    [
+      "Welcome to AppFlowy!",
+      "Here are the basics",
+      "Here is H3",
+      "Click anywhere and just start typing.",
+      "Click Enter to create a new line.",
+    ]
+  );
+  // The blocks without text, such as the dividers, are skipped.
+  for (block_id, text) in &block_texts {
+    let block = document_data.blocks.get(block_id).unwrap();
+    assert_ne!(block.ty, "divider");
+    assert!(!text.trim().is_empty());
+  }
+}
+
 // range_1 is a range from the 2nd block to the 8th block
 #[tokio::test]
 async fn document_data_to_json_with_range_1_test() {
@@ -103,3 +135,41 @@ async fn document_data_to_json_with_range_2_test() {
   let part_2_json = serde_json::from_str::<NestedBlock>(part_2).unwrap();
   assert_eq!(part_2_json, json);
 }
+
+// The Markdown of range_1 only contains the selected part of the document
+#[tokio::test]
+async fn document_data_to_markdown_with_range_1_test() {
+  let initial_json_str = include_str!("../assets/json/initial_document.json");
+  let document_data: DocumentData = JsonToDocumentParser::json_str_to_document(initial_json_str)
+    .unwrap()
+    .into();
+
+  let children_map = &document_data.meta.children_map;
+  let page_block_id = &document_data.page_id;
+  let blocks = &document_data.blocks;
+  let page_block = blocks.get(page_block_id).unwrap();
+  let children = children_map.get(page_block.children.as_str()).unwrap();
+
+  let range = Range {
+    start: Selection {
+      block_id: children.get(1).unwrap().to_string(),
+      index: 4,
+      length: 15,
+    },
+    end: Selection {
+      block_id: children.get(7).unwrap().to_string(),
+      index: 0,
+      length: 11,
+    },
+  };
+  let parser = DocumentDataParser::new(Arc::new(document_data), Some(range));
+  let markdown = parser.to_markdown();
+  assert!(markdown.starts_with(
+    "##  are the basics\n\n### Here is H3\n\n- [ ] Click anywhere and just start typing."
+  ));
+  assert!(markdown.ends_with("- [x] Click `+ New`\n"));
+
+  let part_1 = include_str!("../assets/json/range_1.json");
+  let part_1_json = serde_json::from_str::<NestedBlock>(part_1).unwrap();
+  assert_eq!(markdown, parser.to_markdown_with_json(&Some(part_1_json)));
+}
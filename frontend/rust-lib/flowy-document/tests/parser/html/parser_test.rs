@@ -43,3 +43,58 @@ async fn plain_text_to_document_test() {
   let expect_block = serde_json::from_str::<NestedBlock>(expect_json).unwrap();
   assert_eq!(block, expect_block);
 }
+
+/// test convert data to json
+/// - input markdown: # Hello World!
+#[tokio::test]
+async fn markdown_to_document_test() {
+  let markdown = include_str!("../../assets/markdown/github_flavored.md");
+  let parser = ExternalDataToNestedJSONParser::new(markdown.to_string(), InputType::Markdown);
+  let block = parser.to_nested_block();
+  assert!(block.is_some());
+  let block = block.unwrap();
+  let expect_json = include_str!("../../assets/json/github_flavored.json");
+  let expect_block = serde_json::from_str::<NestedBlock>(expect_json).unwrap();
+  assert_eq!(block, expect_block);
+}
+
+/// test convert data to json
+/// - input html: <table><tr><td>Hello</td></tr></table>
+#[tokio::test]
+async fn html_table_to_document_test() {
+  let html = include_str!("../../assets/html/pasted_table.html");
+  let parser = ExternalDataToNestedJSONParser::new(html.to_string(), InputType::Html);
+  let block = parser.to_nested_block();
+  assert!(block.is_some());
+  let block = block.unwrap();
+  let expect_json = include_str!("../../assets/json/pasted_table.json");
+  let expect_block = serde_json::from_str::<NestedBlock>(expect_json).unwrap();
+  assert_eq!(block, expect_block);
+}
+
+/// test convert data to json
+/// - input plain text: the tab separated cells copied from a spreadsheet
+#[tokio::test]
+async fn plain_text_table_to_document_test() {
+  let plain_text = "Tasks\nName\tStatus\nAppFlowy\tDone\n";
+  let parser = ExternalDataToNestedJSONParser::new(plain_text.to_string(), InputType::PlainText);
+  let block = parser.to_nested_block().unwrap();
+  let types = block
+    .children
+    .iter()
+    .map(|child| child.ty.as_str())
+    .collect::<Vec<&str>>();
+  assert_eq!(types, vec!["paragraph", "table"]);
+
+  let table = &block.children[1];
+  assert_eq!(table.data.get("rowsLen").unwrap(), 2);
+  assert_eq!(table.data.get("colsLen").unwrap(), 2);
+  let cell = &table.children[3];
+  assert_eq!(cell.data.get("rowPosition").unwrap(), 1);
+  assert_eq!(cell.data.get("colPosition").unwrap(), 1);
+  let expect_paragraph = serde_json::from_str::<NestedBlock>(
+    r#"{ "type": "paragraph", "data": { "delta": [{ "insert": "Done" }] } }"#,
+  )
+  .unwrap();
+  assert_eq!(cell.children[0], expect_paragraph);
+}
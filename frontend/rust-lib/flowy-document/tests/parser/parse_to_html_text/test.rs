@@ -1,4 +1,6 @@
-use crate::parser::parse_to_html_text::utils::{assert_document_html_eq, assert_document_text_eq};
+use crate::parser::parse_to_html_text::utils::{
+  assert_document_html_eq, assert_document_markdown_eq, assert_document_text_eq,
+};
 
 macro_rules! generate_test_cases {
     ($($block_ty:ident),*) => {
@@ -8,6 +10,7 @@ macro_rules! generate_test_cases {
                     include_str!(concat!("../../assets/json/", stringify!($block_ty), ".json")),
                     include_str!(concat!("../../assets/html/", stringify!($block_ty), ".html")),
                     include_str!(concat!("../../assets/text/", stringify!($block_ty), ".txt")),
+                    include_str!(concat!("../../assets/markdown/", stringify!($block_ty), ".md")),
                 )
             ),*
         ]
@@ -28,10 +31,12 @@ async fn block_tests() {
     numbered_list,
     todo_list,
     toggle_list,
-    quote
+    quote,
+    table
   );
-  for (json_data, expect_html, expect_text) in test_cases.iter() {
+  for (json_data, expect_html, expect_text, expect_markdown) in test_cases.iter() {
     assert_document_html_eq(json_data, expect_html);
     assert_document_text_eq(json_data, expect_text);
+    assert_document_markdown_eq(json_data, expect_markdown);
   }
 }
@@ -259,6 +259,21 @@ pub enum ErrorCode {
 
   #[error("Cloud request payload too large")]
   CloudRequestPayloadTooLarge = 90,
+
+  #[error("Invalid formula")]
+  InvalidFormula = 91,
+
+  #[error("The file exceeds the maximum file size")]
+  FileTooLarge = 92,
+
+  #[error("The import was cancelled")]
+  ImportCancelled = 93,
+
+  #[error("Only the date type can be used for the dates of the timeline")]
+  UnexpectedTimelineFieldType = 94,
+
+  #[error("The cell doesn't satisfy the validation rules of the field")]
+  FieldValidationFailed = 95,
 }
 
 impl ErrorCode {
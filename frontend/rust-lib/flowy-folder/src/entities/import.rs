@@ -1,6 +1,8 @@
+use std::collections::HashMap;
+
 use crate::entities::parser::empty_str::NotEmptyStr;
-use crate::entities::ViewLayoutPB;
-use crate::share::{ImportParams, ImportType};
+use crate::entities::{ViewLayoutPB, ViewPB};
+use crate::share::{ImportDirectoryParams, ImportParams, ImportType};
 use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
 use flowy_error::FlowyError;
 
@@ -10,6 +12,7 @@ pub enum ImportTypePB {
   HistoryDatabase = 1,
   RawDatabase = 2,
   CSV = 3,
+  Markdown = 4,
 }
 
 impl From<ImportTypePB> for ImportType {
@@ -19,6 +22,7 @@ impl From<ImportTypePB> for ImportType {
       ImportTypePB::HistoryDatabase => ImportType::HistoryDatabase,
       ImportTypePB::RawDatabase => ImportType::RawDatabase,
       ImportTypePB::CSV => ImportType::CSV,
+      ImportTypePB::Markdown => ImportType::Markdown,
     }
   }
 }
@@ -48,6 +52,11 @@ pub struct ImportPB {
 
   #[pb(index = 6)]
   pub import_type: ImportTypePB,
+
+  /// Use to carry extra information to the handler of the view layout. For example, the
+  /// database uses it to carry the field types of the imported CSV columns.
+  #[pb(index = 7)]
+  pub meta: HashMap<String, String>,
 }
 
 impl TryInto<ImportParams> for ImportPB {
@@ -80,6 +89,61 @@ impl TryInto<ImportParams> for ImportPB {
       file_path,
       view_layout: self.view_layout.into(),
       import_type: self.import_type.into(),
+      meta: self.meta,
+    })
+  }
+}
+
+/// Imports a directory tree, such as an exported Notion workspace, into the parent view. Each
+/// sub-directory becomes a nested view, the `.md` files become documents and the `.csv` files
+/// become grids.
+#[derive(Clone, Debug, ProtoBuf, Default)]
+pub struct ImportDirectoryPB {
+  #[pb(index = 1)]
+  pub parent_view_id: String,
+
+  /// The path of the directory, or of a zip file that contains the directory.
+  #[pb(index = 2)]
+  pub path: String,
+}
+
+impl TryInto<ImportDirectoryParams> for ImportDirectoryPB {
+  type Error = FlowyError;
+
+  fn try_into(self) -> Result<ImportDirectoryParams, Self::Error> {
+    let parent_view_id = NotEmptyStr::parse(self.parent_view_id)
+      .map_err(|_| FlowyError::invalid_view_id())?
+      .0;
+
+    let path = NotEmptyStr::parse(self.path)
+      .map_err(|_| FlowyError::invalid_data().with_context("The import directory path is empty"))?
+      .0;
+
+    Ok(ImportDirectoryParams {
+      parent_view_id,
+      path,
     })
   }
 }
+
+/// A file of the imported directory that couldn't be imported. The other files are imported
+/// anyway, and the views nested in the view of the file are moved to its parent.
+#[derive(Clone, Debug, ProtoBuf, Default)]
+pub struct ImportFailedFilePB {
+  /// The path of the file, relative to the imported directory.
+  #[pb(index = 1)]
+  pub file_path: String,
+
+  #[pb(index = 2)]
+  pub error: String,
+}
+
+#[derive(Clone, Debug, ProtoBuf, Default)]
+pub struct ImportDirectoryResultPB {
+  /// The imported views, the parent view of each view comes before it.
+  #[pb(index = 1)]
+  pub views: Vec<ViewPB>,
+
+  #[pb(index = 2)]
+  pub failed_files: Vec<ImportFailedFilePB>,
+}
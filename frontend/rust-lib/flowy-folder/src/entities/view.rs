@@ -96,13 +96,18 @@ pub enum ViewLayoutPB {
   Grid = 1,
   Board = 2,
   Calendar = 3,
+  /// The folder stores the timeline as a [ViewLayout::Grid], the database keeps the timeline
+  /// layout of the view. See [FolderOperationHandler::get_view_layout].
+  ///
+  /// [FolderOperationHandler::get_view_layout]: crate::view_operation::FolderOperationHandler::get_view_layout
+  Timeline = 4,
 }
 
 impl ViewLayoutPB {
   pub fn is_database(&self) -> bool {
     matches!(
       self,
-      ViewLayoutPB::Grid | ViewLayoutPB::Board | ViewLayoutPB::Calendar
+      ViewLayoutPB::Grid | ViewLayoutPB::Board | ViewLayoutPB::Calendar | ViewLayoutPB::Timeline
     )
   }
 }
@@ -321,7 +326,7 @@ pub struct UpdateViewParams {
   pub name: Option<String>,
   pub desc: Option<String>,
   pub thumbnail: Option<String>,
-  pub layout: Option<ViewLayout>,
+  pub layout: Option<ViewLayoutPB>,
   pub is_favorite: Option<bool>,
 }
 
@@ -349,7 +354,7 @@ impl TryInto<UpdateViewParams> for UpdateViewPayloadPB {
       desc: self.desc,
       thumbnail,
       is_favorite,
-      layout: self.layout.map(|ty| ty.into()),
+      layout: self.layout,
     })
   }
 }
@@ -5,7 +5,7 @@ use lib_dispatch::prelude::{data_result_ok, AFPluginData, AFPluginState, DataRes
 
 use crate::entities::*;
 use crate::manager::FolderManager;
-use crate::share::ImportParams;
+use crate::share::{ImportDirectoryParams, ImportParams};
 
 fn upgrade_folder(
   folder_manager: AFPluginState<Weak<FolderManager>>,
@@ -81,11 +81,14 @@ pub(crate) async fn create_view_handler(
   let folder = upgrade_folder(folder)?;
   let params: CreateViewParams = data.into_inner().try_into()?;
   let set_as_current = params.set_as_current;
+  let layout = params.layout.clone();
   let view = folder.create_view_with_params(params).await?;
   if set_as_current {
     let _ = folder.set_current_view(&view.id).await;
   }
-  data_result_ok(view_pb_without_child_views(Arc::new(view)))
+  let mut view_pb = view_pb_without_child_views(Arc::new(view));
+  view_pb.layout = layout;
+  data_result_ok(view_pb)
 }
 
 pub(crate) async fn create_orphan_view_handler(
@@ -320,6 +323,17 @@ pub(crate) async fn import_data_handler(
   Ok(())
 }
 
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn import_directory_handler(
+  data: AFPluginData<ImportDirectoryPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<ImportDirectoryResultPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params: ImportDirectoryParams = data.into_inner().try_into()?;
+  let result = folder.import_directory(params).await?;
+  data_result_ok(result)
+}
+
 #[tracing::instrument(level = "debug", skip(folder), err)]
 pub(crate) async fn get_folder_snapshots_handler(
   data: AFPluginData<WorkspaceIdPB>,
@@ -38,6 +38,7 @@ pub fn init(folder: Weak<FolderManager>) -> AFPlugin {
     .event(FolderEvent::ToggleFavorite, toggle_favorites_handler)
     .event(FolderEvent::UpdateRecentViews, update_recent_views_handler)
     .event(FolderEvent::ReloadWorkspace, reload_workspace_handler)
+    .event(FolderEvent::ImportDirectory, import_directory_handler)
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Display, Hash, ProtoBuf_Enum, Flowy_Event)]
@@ -156,4 +157,9 @@ pub enum FolderEvent {
 
   #[event()]
   ReloadWorkspace = 38,
+
+  /// Imports a directory tree into a folder hierarchy. Returns the views that were created and the
+  /// files that couldn't be imported.
+  #[event(input = "ImportDirectoryPB", output = "ImportDirectoryResultPB")]
+  ImportDirectory = 39,
 }
@@ -1,4 +1,4 @@
-pub use collab_folder::ViewLayout;
+pub use collab_folder::{ViewChange, ViewLayout};
 
 pub mod entities;
 pub mod event_handler;
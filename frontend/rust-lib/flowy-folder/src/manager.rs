@@ -1,13 +1,17 @@
+use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 use std::ops::Deref;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Weak};
 
 use collab::core::collab::{CollabDocState, MutexCollab};
 use collab_entity::CollabType;
 use collab_folder::{
-  Folder, FolderData, Section, SectionItem, TrashInfo, View, ViewLayout, ViewUpdate, Workspace,
+  Folder, FolderData, Section, SectionItem, TrashInfo, View, ViewChange, ViewLayout, ViewUpdate,
+  Workspace,
 };
 use parking_lot::{Mutex, RwLock};
+use tokio::sync::broadcast;
 use tracing::{error, info, instrument};
 
 use collab_integrate::collab_builder::{AppFlowyCollabBuilder, CollabBuilderConfig};
@@ -20,8 +24,8 @@ use lib_infra::conditional_send_sync_trait;
 use crate::entities::icon::UpdateViewIconParams;
 use crate::entities::{
   view_pb_with_child_views, view_pb_without_child_views, CreateViewParams, CreateWorkspaceParams,
-  DeletedViewPB, FolderSnapshotPB, RepeatedTrashPB, RepeatedViewIdPB, RepeatedViewPB,
-  UpdateViewParams, ViewPB, WorkspacePB, WorkspaceSettingPB,
+  DeletedViewPB, FolderSnapshotPB, ImportDirectoryResultPB, ImportFailedFilePB, RepeatedTrashPB,
+  RepeatedViewIdPB, RepeatedViewPB, UpdateViewParams, ViewPB, WorkspacePB, WorkspaceSettingPB,
 };
 use crate::manager_observer::{
   notify_child_views_changed, notify_parent_view_did_change, ChildViewChangeReason,
@@ -29,7 +33,10 @@ use crate::manager_observer::{
 use crate::notification::{
   send_notification, send_workspace_setting_notification, FolderNotification,
 };
-use crate::share::ImportParams;
+use crate::share::{
+  scan_import_directory, ImportDirectoryItem, ImportDirectoryParams, ImportParams, ImportType,
+  IMPORT_FILE_PATH, IMPORT_ROOT_DIR, IMPORT_VIEW_LINKS, IMPORT_WORKSPACE_ID,
+};
 use crate::util::{
   folder_not_init_error, insert_parent_child_views, workspace_data_not_sync_error,
 };
@@ -50,6 +57,9 @@ pub struct FolderManager {
   pub(crate) user: Arc<dyn FolderUser>,
   pub(crate) operation_handlers: FolderOperationHandlers,
   pub cloud_service: Arc<dyn FolderCloudService>,
+  /// Sends the [ViewChange]s of the current workspace. It outlives the folder, so the subscribers
+  /// keep receiving the changes after the workspace was switched.
+  pub(crate) view_changed_notifier: broadcast::Sender<ViewChange>,
 }
 
 impl FolderManager {
@@ -60,6 +70,7 @@ impl FolderManager {
     cloud_service: Arc<dyn FolderCloudService>,
   ) -> FlowyResult<Self> {
     let mutex_folder = Arc::new(MutexFolder::default());
+    let (view_changed_notifier, _) = broadcast::channel(100);
     let manager = Self {
       user,
       mutex_folder,
@@ -67,6 +78,7 @@ impl FolderManager {
       operation_handlers,
       cloud_service,
       workspace_id: Default::default(),
+      view_changed_notifier,
     };
 
     Ok(manager)
@@ -390,7 +402,12 @@ impl FolderManager {
     if meta.is_empty() && params.initial_data.is_empty() {
       tracing::trace!("Create view with build-in data");
       handler
-        .create_built_in_view(user_id, &params.view_id, &params.name, view_layout.clone())
+        .create_built_in_view(
+          user_id,
+          &params.view_id,
+          &params.name,
+          params.layout.clone(),
+        )
         .await?;
     } else {
       tracing::trace!("Create view with view data");
@@ -400,7 +417,7 @@ impl FolderManager {
           &params.view_id,
           &params.name,
           params.initial_data.clone(),
-          view_layout.clone(),
+          params.layout.clone(),
           meta,
         )
         .await?;
@@ -430,7 +447,12 @@ impl FolderManager {
     let handler = self.get_handler(&view_layout)?;
     let user_id = self.user.user_id()?;
     handler
-      .create_built_in_view(user_id, &params.view_id, &params.name, view_layout.clone())
+      .create_built_in_view(
+        user_id,
+        &params.view_id,
+        &params.name,
+        params.layout.clone(),
+      )
       .await?;
 
     let view = create_view(self.user.user_id()?, params, view_layout);
@@ -457,38 +479,50 @@ impl FolderManager {
   /// child view, you need to call this method again.
   #[tracing::instrument(level = "debug", skip(self))]
   pub async fn get_view_pb(&self, view_id: &str) -> FlowyResult<ViewPB> {
-    let view_id = view_id.to_string();
-    let folder = self.mutex_folder.lock();
-    let folder = folder.as_ref().ok_or_else(folder_not_init_error)?;
-    let trash_ids = folder
-      .get_all_trash()
-      .into_iter()
-      .map(|trash| trash.id)
-      .collect::<Vec<String>>();
+    let (view_layout, mut view_pb) = {
+      let view_id = view_id.to_string();
+      let folder = self.mutex_folder.lock();
+      let folder = folder.as_ref().ok_or_else(folder_not_init_error)?;
+      let trash_ids = folder
+        .get_all_trash()
+        .into_iter()
+        .map(|trash| trash.id)
+        .collect::<Vec<String>>();
 
-    if trash_ids.contains(&view_id) {
-      return Err(FlowyError::new(
-        ErrorCode::RecordNotFound,
-        format!("View:{} is in trash", view_id),
-      ));
-    }
+      if trash_ids.contains(&view_id) {
+        return Err(FlowyError::new(
+          ErrorCode::RecordNotFound,
+          format!("View:{} is in trash", view_id),
+        ));
+      }
 
-    match folder.views.get_view(&view_id) {
-      None => {
-        error!("Can't find the view with id: {}", view_id);
-        Err(FlowyError::record_not_found())
-      },
-      Some(view) => {
-        let child_views = folder
-          .views
-          .get_views_belong_to(&view.id)
-          .into_iter()
-          .filter(|view| !trash_ids.contains(&view.id))
-          .collect::<Vec<_>>();
-        let view_pb = view_pb_with_child_views(view, child_views);
-        Ok(view_pb)
-      },
+      match folder.views.get_view(&view_id) {
+        None => {
+          error!("Can't find the view with id: {}", view_id);
+          return Err(FlowyError::record_not_found());
+        },
+        Some(view) => {
+          let child_views = folder
+            .views
+            .get_views_belong_to(&view.id)
+            .into_iter()
+            .filter(|view| !trash_ids.contains(&view.id))
+            .collect::<Vec<_>>();
+          (
+            view.layout.clone(),
+            view_pb_with_child_views(view, child_views),
+          )
+        },
+      }
+    };
+
+    // The folder can't represent every layout, e.g. the timeline is stored as a grid.
+    if let Ok(handler) = self.get_handler(&view_layout) {
+      if let Some(layout) = handler.get_view_layout(view_id).await? {
+        view_pb.layout = layout;
+      }
     }
+    Ok(view_pb)
   }
 
   /// Move the view to trash. If the view is the current view, then set the current view to empty.
@@ -634,6 +668,28 @@ impl FolderManager {
     Ok(())
   }
 
+  /// Subscribe to the views that were created, updated or deleted in the current workspace.
+  pub fn subscribe_view_changed(&self) -> broadcast::Receiver<ViewChange> {
+    self.view_changed_notifier.subscribe()
+  }
+
+  /// Return all the views of the current workspace, including the nested child views and the
+  /// views in the trash. The workspace itself is not included.
+  pub async fn get_all_views(&self) -> FlowyResult<Vec<Arc<View>>> {
+    let views = self.with_folder(Vec::new, |folder| {
+      let mut views = vec![];
+      let mut parent_view_ids = vec![folder.get_workspace_id()];
+      while let Some(parent_view_id) = parent_view_ids.pop() {
+        for view in folder.views.get_views_belong_to(&parent_view_id) {
+          parent_view_ids.push(view.id.clone());
+          views.push(view);
+        }
+      }
+      views
+    });
+    Ok(views)
+  }
+
   /// Return a list of views that belong to the given parent view id.
   #[tracing::instrument(level = "debug", skip(self, parent_view_id), err)]
   pub async fn get_views_belong_to(&self, parent_view_id: &str) -> FlowyResult<Vec<Arc<View>>> {
@@ -646,12 +702,23 @@ impl FolderManager {
   /// Update the view with the given params.
   #[tracing::instrument(level = "trace", skip(self), err)]
   pub async fn update_view_with_params(&self, params: UpdateViewParams) -> FlowyResult<()> {
+    if let Some(layout) = params.layout.clone() {
+      let old_view = self.with_folder(|| None, |folder| folder.views.get_view(&params.view_id));
+      if let Some(old_view) = old_view {
+        if let Ok(handler) = self.get_handler(&old_view.layout) {
+          handler
+            .will_update_view_layout(&params.view_id, layout)
+            .await?;
+        }
+      }
+    }
+
     self
       .update_view(&params.view_id, |update| {
         update
           .set_name_if_not_none(params.name)
           .set_desc_if_not_none(params.desc)
-          .set_layout_if_not_none(params.layout)
+          .set_layout_if_not_none(params.layout.map(ViewLayout::from))
           .set_favorite_if_not_none(params.is_favorite)
           .done()
       })
@@ -810,7 +877,7 @@ impl FolderManager {
   }
 
   #[tracing::instrument(level = "trace", skip(self))]
-  pub(crate) async fn get_all_trash(&self) -> Vec<TrashInfo> {
+  pub async fn get_all_trash(&self) -> Vec<TrashInfo> {
     self.with_folder(Vec::new, |folder| folder.get_all_trash())
   }
 
@@ -887,23 +954,223 @@ impl FolderManager {
           uid,
           &view_id,
           &import_data.name,
-          import_data.import_type,
+          import_data.import_type.clone(),
           data,
+          import_data.meta,
         )
         .await?;
     }
 
     if let Some(file_path) = import_data.file_path {
       handler
-        .import_from_file_path(&view_id, &import_data.name, file_path)
+        .import_from_file_path(
+          &view_id,
+          &import_data.name,
+          import_data.import_type,
+          file_path,
+        )
         .await?;
     }
 
+    let view = self.insert_imported_view(
+      uid,
+      import_data.parent_view_id,
+      import_data.name,
+      view_id,
+      import_data.view_layout,
+    );
+    notify_parent_view_did_change(self.mutex_folder.clone(), vec![view.parent_view_id.clone()]);
+    Ok(view)
+  }
+
+  /// Imports a directory tree. The views are created in the order that [scan_import_directory]
+  /// returns them, so the parent view of each view already exists when it's inserted.
+  pub(crate) async fn import_directory(
+    &self,
+    params: ImportDirectoryParams,
+  ) -> FlowyResult<ImportDirectoryResultPB> {
+    let path = PathBuf::from(&params.path);
+    let is_zip = path.is_file()
+      && path
+        .extension()
+        .map(|ext| ext.eq_ignore_ascii_case("zip"))
+        .unwrap_or(false);
+    let dir = if is_zip {
+      let dir = std::env::temp_dir().join(format!("import_{}", gen_view_id()));
+      unzip_import_directory(&path, &dir)?;
+      dir
+    } else if path.is_dir() {
+      path
+    } else {
+      return Err(FlowyError::new(
+        ErrorCode::InvalidParams,
+        "The import path is not a directory or a zip file",
+      ));
+    };
+
+    let result = self
+      .import_directory_items(&params.parent_view_id, &dir)
+      .await;
+    if is_zip {
+      if let Err(err) = std::fs::remove_dir_all(&dir) {
+        error!("Failed to remove the unzipped directory: {:?}", err);
+      }
+    }
+    let (views, failed_files) = result?;
+    notify_parent_view_did_change(self.mutex_folder.clone(), vec![params.parent_view_id]);
+    Ok(ImportDirectoryResultPB {
+      views: views
+        .into_iter()
+        .map(|view| view_pb_without_child_views(Arc::new(view)))
+        .collect(),
+      failed_files,
+    })
+  }
+
+  /// Imports the files of the directory. A file that can't be imported is skipped and reported,
+  /// the views nested in its view are moved to its parent.
+  async fn import_directory_items(
+    &self,
+    parent_view_id: &str,
+    dir: &Path,
+  ) -> FlowyResult<(Vec<View>, Vec<ImportFailedFilePB>)> {
+    // The links between the imported files are resolved with the canonical paths
+    let dir = dir.canonicalize()?;
+    let items = scan_import_directory(&dir, parent_view_id, &|| gen_view_id().to_string())?;
+    if items.is_empty() {
+      return Err(FlowyError::new(
+        ErrorCode::InvalidParams,
+        "There is no markdown or csv file in the import directory",
+      ));
+    }
+
+    let view_links = items
+      .iter()
+      .filter_map(|item| {
+        let file_path = item.file_path.as_ref()?;
+        Some((
+          file_path.to_string_lossy().to_string(),
+          item.view_id.clone(),
+        ))
+      })
+      .collect::<HashMap<String, String>>();
+    let view_links = serde_json::to_string(&view_links)?;
+    let workspace_id = self.get_current_workspace_id().await?;
+    let uid = self.user.user_id()?;
+
+    let mut views = vec![];
+    let mut failed_files = vec![];
+    // The parent of each skipped view. The parents come first, so they are already resolved.
+    let mut parent_by_skipped_view_id = HashMap::<String, String>::new();
+    for mut item in items {
+      if let Some(parent_view_id) = parent_by_skipped_view_id.get(&item.parent_view_id) {
+        item.parent_view_id = parent_view_id.clone();
+      }
+
+      if let Err(err) = self
+        .import_directory_item(uid, &dir, &workspace_id, &view_links, &item)
+        .await
+      {
+        let file_path = item
+          .file_path
+          .as_ref()
+          .map(|file_path| {
+            file_path
+              .strip_prefix(&dir)
+              .unwrap_or(file_path)
+              .to_string_lossy()
+              .to_string()
+          })
+          .unwrap_or_else(|| item.name.clone());
+        error!("Failed to import {}: {}", file_path, err);
+        failed_files.push(ImportFailedFilePB {
+          file_path,
+          error: err.msg,
+        });
+        parent_by_skipped_view_id.insert(item.view_id, item.parent_view_id);
+        continue;
+      }
+
+      let view = self.insert_imported_view(
+        uid,
+        item.parent_view_id,
+        item.name,
+        item.view_id,
+        item.view_layout,
+      );
+      views.push(view);
+    }
+    Ok((views, failed_files))
+  }
+
+  /// Creates the content of the view from the file of the item. The view itself is inserted by
+  /// [Self::insert_imported_view] once the content was created.
+  async fn import_directory_item(
+    &self,
+    uid: i64,
+    dir: &Path,
+    workspace_id: &str,
+    view_links: &str,
+    item: &ImportDirectoryItem,
+  ) -> FlowyResult<()> {
+    let handler = self.get_handler(&item.view_layout)?;
+    match (&item.import_type, &item.file_path) {
+      (ImportType::CSV, Some(file_path)) => {
+        handler
+          .import_from_file_path(
+            &item.view_id,
+            &item.name,
+            item.import_type.clone(),
+            file_path.to_string_lossy().to_string(),
+          )
+          .await?;
+      },
+      (_, file_path) => {
+        let mut meta = HashMap::new();
+        let data = match file_path {
+          None => vec![],
+          Some(file_path) => {
+            meta.insert(
+              IMPORT_FILE_PATH.to_string(),
+              file_path.to_string_lossy().to_string(),
+            );
+            meta.insert(
+              IMPORT_ROOT_DIR.to_string(),
+              dir.to_string_lossy().to_string(),
+            );
+            meta.insert(IMPORT_WORKSPACE_ID.to_string(), workspace_id.to_string());
+            meta.insert(IMPORT_VIEW_LINKS.to_string(), view_links.to_string());
+            std::fs::read(file_path)?
+          },
+        };
+        handler
+          .import_from_bytes(
+            uid,
+            &item.view_id,
+            &item.name,
+            item.import_type.clone(),
+            data,
+            meta,
+          )
+          .await?;
+      },
+    }
+    Ok(())
+  }
+
+  fn insert_imported_view(
+    &self,
+    uid: i64,
+    parent_view_id: String,
+    name: String,
+    view_id: String,
+    view_layout: ViewLayout,
+  ) -> View {
     let params = CreateViewParams {
-      parent_view_id: import_data.parent_view_id,
-      name: import_data.name,
+      parent_view_id,
+      name,
       desc: "".to_string(),
-      layout: import_data.view_layout.clone().into(),
+      layout: view_layout.clone().into(),
       initial_data: vec![],
       view_id,
       meta: Default::default(),
@@ -911,15 +1178,14 @@ impl FolderManager {
       index: None,
     };
 
-    let view = create_view(self.user.user_id()?, params, import_data.view_layout);
+    let view = create_view(uid, params, view_layout);
     self.with_folder(
       || (),
       |folder| {
         folder.insert_view(view.clone(), None);
       },
     );
-    notify_parent_view_did_change(self.mutex_folder.clone(), vec![view.parent_view_id.clone()]);
-    Ok(view)
+    view
   }
 
   /// Update the view with the provided view_id using the specified function.
@@ -1082,6 +1348,17 @@ pub(crate) fn get_workspace_view_pbs(_workspace_id: &str, folder: &Folder) -> Ve
     .collect()
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+fn unzip_import_directory(zip_path: &Path, dir: &Path) -> FlowyResult<()> {
+  lib_infra::file_util::unzip_and_replace(zip_path, dir)
+    .map_err(|err| FlowyError::invalid_data().with_context(err))
+}
+
+#[cfg(target_arch = "wasm32")]
+fn unzip_import_directory(_zip_path: &Path, _dir: &Path) -> FlowyResult<()> {
+  Err(FlowyError::not_support().with_context("Importing a zip file is not supported"))
+}
+
 #[derive(Clone, Default)]
 pub struct MutexFolder(Arc<Mutex<Option<Folder>>>);
 impl Deref for MutexFolder {
@@ -107,7 +107,11 @@ impl FolderManager {
     subscribe_folder_sync_state_changed(workspace_id.clone(), folder_state_rx, &weak_mutex_folder);
     subscribe_folder_snapshot_state_changed(workspace_id, &weak_mutex_folder);
     subscribe_folder_trash_changed(section_change_rx, &weak_mutex_folder);
-    subscribe_folder_view_changed(view_rx, &weak_mutex_folder);
+    subscribe_folder_view_changed(
+      view_rx,
+      &weak_mutex_folder,
+      self.view_changed_notifier.clone(),
+    );
     Ok(())
   }
 
@@ -6,6 +6,7 @@ use collab_folder::{
   Folder, SectionChange, SectionChangeReceiver, TrashSectionChange, View, ViewChange,
   ViewChangeReceiver,
 };
+use tokio::sync::broadcast;
 use tokio_stream::wrappers::WatchStream;
 use tokio_stream::StreamExt;
 use tracing::{event, Level};
@@ -19,14 +20,17 @@ use crate::entities::{
 use crate::manager::{get_workspace_view_pbs, MutexFolder};
 use crate::notification::{send_notification, FolderNotification};
 
-/// Listen on the [ViewChange] after create/delete/update events happened
+/// Listen on the [ViewChange] after create/delete/update events happened, and forward them to the
+/// `changed_notifier`.
 pub(crate) fn subscribe_folder_view_changed(
   mut rx: ViewChangeReceiver,
   weak_mutex_folder: &Weak<MutexFolder>,
+  changed_notifier: broadcast::Sender<ViewChange>,
 ) {
   let weak_mutex_folder = weak_mutex_folder.clone();
   af_spawn(async move {
     while let Ok(value) = rx.recv().await {
+      let _ = changed_notifier.send(value.clone());
       if let Some(folder) = weak_mutex_folder.upgrade() {
         tracing::trace!("Did receive view change: {:?}", value);
         match value {
@@ -1,3 +1,8 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
 use collab_folder::ViewLayout;
 
 #[derive(Clone, Debug)]
@@ -6,6 +11,7 @@ pub enum ImportType {
   HistoryDatabase = 1,
   RawDatabase = 2,
   CSV = 3,
+  Markdown = 4,
 }
 
 #[derive(Clone, Debug)]
@@ -16,4 +22,173 @@ pub struct ImportParams {
   pub file_path: Option<String>,
   pub view_layout: ViewLayout,
   pub import_type: ImportType,
+  pub meta: HashMap<String, String>,
+}
+
+/// The keys of the meta that is passed to the [FolderOperationHandler] of the views that are
+/// created by [ImportDirectoryParams].
+///
+/// [FolderOperationHandler]: crate::view_operation::FolderOperationHandler
+pub const IMPORT_FILE_PATH: &str = "import_file_path";
+/// The canonical path of the imported directory. The links of the imported files that point
+/// outside of it are not followed.
+pub const IMPORT_ROOT_DIR: &str = "import_root_dir";
+pub const IMPORT_WORKSPACE_ID: &str = "import_workspace_id";
+/// The json map from the path of each imported file to the id of its view. The handler uses it
+/// to rewrite the relative links between the imported files to view links.
+pub const IMPORT_VIEW_LINKS: &str = "import_view_links";
+
+#[derive(Clone, Debug)]
+pub struct ImportDirectoryParams {
+  pub parent_view_id: String,
+  /// The path of a directory, or of a zip file that contains the directory.
+  pub path: String,
+}
+
+/// A view that is created from a file or a sub-directory of the imported directory.
+#[derive(Clone, Debug)]
+pub struct ImportDirectoryItem {
+  pub view_id: String,
+  pub parent_view_id: String,
+  pub name: String,
+  pub view_layout: ViewLayout,
+  pub import_type: ImportType,
+  /// The file that contains the data of the view. A sub-directory without a page of its own
+  /// becomes an empty document, so it doesn't have a file.
+  pub file_path: Option<PathBuf>,
+}
+
+/// Walks the directory and returns the views that should be created for it. The `.md` files
+/// become documents and the `.csv` files become grids. The parent view of each view comes before
+/// it in the returned list.
+///
+/// The content of a sub-directory is nested in the page that has the same name, which is how
+/// Notion exports the sub-pages of a page. Otherwise, the sub-directory becomes a page itself.
+pub fn scan_import_directory<F>(
+  dir: &Path,
+  parent_view_id: &str,
+  gen_view_id: &F,
+) -> io::Result<Vec<ImportDirectoryItem>>
+where
+  F: Fn() -> String,
+{
+  let mut dirs = vec![];
+  let mut files = vec![];
+  for entry in fs::read_dir(dir)? {
+    let entry = entry?;
+    let path = entry.path();
+    let is_hidden = path
+      .file_name()
+      .and_then(|name| name.to_str())
+      .map(|name| name.starts_with('.') || name == "__MACOSX")
+      .unwrap_or(true);
+    if is_hidden {
+      continue;
+    }
+    // The symlinks are skipped, a link to one of its parent directories would be scanned forever
+    let file_type = entry.file_type()?;
+    if file_type.is_symlink() {
+      continue;
+    }
+    if file_type.is_dir() {
+      dirs.push(path);
+    } else {
+      files.push(path);
+    }
+  }
+  dirs.sort();
+  files.sort();
+
+  let csv_stems = files
+    .iter()
+    .filter(|path| extension_of(path) == "csv")
+    .map(|path| file_stem_of(path))
+    .collect::<HashSet<String>>();
+
+  let mut items = vec![];
+  // The id of the page that the content of the sub-directory with the same name is nested in
+  let mut page_by_stem = HashMap::new();
+  for path in files {
+    let stem = file_stem_of(&path);
+    let (view_layout, import_type) = match extension_of(&path).as_str() {
+      "md" | "markdown" => (ViewLayout::Document, ImportType::Markdown),
+      "csv" => {
+        // Notion exports each database twice, the `_all` file also contains the hidden rows
+        if let Some(name) = stem.strip_suffix("_all") {
+          if csv_stems.contains(name) {
+            continue;
+          }
+        }
+        (ViewLayout::Grid, ImportType::CSV)
+      },
+      _ => continue,
+    };
+    let view_id = gen_view_id();
+    page_by_stem.insert(stem.clone(), view_id.clone());
+    items.push(ImportDirectoryItem {
+      view_id,
+      parent_view_id: parent_view_id.to_string(),
+      name: view_name_from_file_stem(&stem),
+      view_layout,
+      import_type,
+      file_path: Some(path),
+    });
+  }
+
+  for path in dirs {
+    let stem = path
+      .file_name()
+      .map(|name| name.to_string_lossy().to_string())
+      .unwrap_or_default();
+    match page_by_stem.get(&stem) {
+      Some(view_id) => items.extend(scan_import_directory(&path, view_id, gen_view_id)?),
+      None => {
+        let view_id = gen_view_id();
+        let children = scan_import_directory(&path, &view_id, gen_view_id)?;
+        // The directories that only contain other files, like images, are skipped
+        if children.is_empty() {
+          continue;
+        }
+        items.push(ImportDirectoryItem {
+          view_id,
+          parent_view_id: parent_view_id.to_string(),
+          name: view_name_from_file_stem(&stem),
+          view_layout: ViewLayout::Document,
+          import_type: ImportType::Markdown,
+          file_path: None,
+        });
+        items.extend(children);
+      },
+    }
+  }
+  Ok(items)
+}
+
+/// Removes the id that Notion appends to the names of the exported files.
+/// For example: `Getting Started 0c9a1f3a1e6d4c2a9b8e7f6d5c4b3a21` => `Getting Started`
+pub fn view_name_from_file_stem(stem: &str) -> String {
+  let name = match stem.rsplit_once(' ') {
+    Some((name, id)) if id.len() == 32 && id.chars().all(|c| c.is_ascii_hexdigit()) => name,
+    _ => stem,
+  };
+  let name = name.trim();
+  if name.is_empty() {
+    "Untitled".to_string()
+  } else {
+    name.to_string()
+  }
+}
+
+fn file_stem_of(path: &Path) -> String {
+  path
+    .file_stem()
+    .map(|stem| stem.to_string_lossy().to_string())
+    .unwrap_or_default()
+}
+
+fn extension_of(path: &Path) -> String {
+  path
+    .extension()
+    .map(|ext| ext.to_string_lossy().to_lowercase())
+    .unwrap_or_default()
 }
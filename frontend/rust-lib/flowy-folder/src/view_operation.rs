@@ -61,7 +61,7 @@ pub trait FolderOperationHandler {
     view_id: &str,
     name: &str,
     data: Vec<u8>,
-    layout: ViewLayout,
+    layout: ViewLayoutPB,
     meta: HashMap<String, String>,
   ) -> FutureResult<(), FlowyError>;
 
@@ -73,10 +73,13 @@ pub trait FolderOperationHandler {
     user_id: i64,
     view_id: &str,
     name: &str,
-    layout: ViewLayout,
+    layout: ViewLayoutPB,
   ) -> FutureResult<(), FlowyError>;
 
   /// Create a view by importing data
+  /// * `meta`: use to carry extra information. For example, the database uses this to carry
+  /// the field types of the imported CSV columns, and the document uses the `IMPORT_*` keys to
+  /// resolve the links of a file that is imported with its directory.
   fn import_from_bytes(
     &self,
     uid: i64,
@@ -84,6 +87,7 @@ pub trait FolderOperationHandler {
     name: &str,
     import_type: ImportType,
     bytes: Vec<u8>,
+    meta: HashMap<String, String>,
   ) -> FutureResult<(), FlowyError>;
 
   /// Create a view by importing data from a file
@@ -91,6 +95,7 @@ pub trait FolderOperationHandler {
     &self,
     view_id: &str,
     name: &str,
+    import_type: ImportType,
     path: String,
   ) -> FutureResult<(), FlowyError>;
 
@@ -98,6 +103,23 @@ pub trait FolderOperationHandler {
   fn did_update_view(&self, _old: &View, _new: &View) -> FutureResult<(), FlowyError> {
     FutureResult::new(async move { Ok(()) })
   }
+
+  /// Called before the layout of the view is updated. The handler is the registered handler of
+  /// the current layout. The [ViewLayout] of the folder can't tell some layouts apart, e.g. the
+  /// timeline is stored as a grid, so the handler receives the requested layout.
+  fn will_update_view_layout(
+    &self,
+    _view_id: &str,
+    _layout: ViewLayoutPB,
+  ) -> FutureResult<(), FlowyError> {
+    FutureResult::new(async move { Ok(()) })
+  }
+
+  /// Returns the layout of the view if the [ViewLayout] of the folder can't represent it, e.g.
+  /// the timeline that is stored as a grid.
+  fn get_view_layout(&self, _view_id: &str) -> FutureResult<Option<ViewLayoutPB>, FlowyError> {
+    FutureResult::new(async move { Ok(None) })
+  }
 }
 
 pub type FolderOperationHandlers =
@@ -110,6 +132,7 @@ impl From<ViewLayoutPB> for ViewLayout {
       ViewLayoutPB::Grid => ViewLayout::Grid,
       ViewLayoutPB::Board => ViewLayout::Board,
       ViewLayoutPB::Calendar => ViewLayout::Calendar,
+      ViewLayoutPB::Timeline => ViewLayout::Grid,
     }
   }
 }
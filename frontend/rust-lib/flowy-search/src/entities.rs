@@ -0,0 +1,79 @@
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+
+/// The number of results that are returned if the [SearchQueryPB] doesn't set the limit.
+pub const DEFAULT_SEARCH_LIMIT: i64 = 20;
+
+#[derive(ProtoBuf, Debug, Default, Clone)]
+pub struct SearchQueryPB {
+  #[pb(index = 1)]
+  pub query: String,
+
+  /// The maximum number of results. The [DEFAULT_SEARCH_LIMIT] is used if it's not set.
+  #[pb(index = 2, one_of)]
+  pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ProtoBuf_Enum)]
+pub enum SearchItemTypePB {
+  /// The name of a view.
+  View = 0,
+  /// The text of a block of a document.
+  Document = 1,
+  /// The cells of a row of a database.
+  DatabaseRow = 2,
+}
+
+impl Default for SearchItemTypePB {
+  fn default() -> Self {
+    Self::View
+  }
+}
+
+impl std::convert::From<i32> for SearchItemTypePB {
+  fn from(value: i32) -> Self {
+    match value {
+      1 => SearchItemTypePB::Document,
+      2 => SearchItemTypePB::DatabaseRow,
+      _ => SearchItemTypePB::View,
+    }
+  }
+}
+
+#[derive(ProtoBuf, Debug, Default, Clone)]
+pub struct SearchResultPB {
+  #[pb(index = 1)]
+  pub item_type: SearchItemTypePB,
+
+  /// The view to open to show the result. For the database rows, it's the inline view of the
+  /// database.
+  #[pb(index = 2)]
+  pub view_id: String,
+
+  /// The id of the view, the block or the row that matches the query.
+  #[pb(index = 3)]
+  pub object_id: String,
+
+  /// The matched part of the content, escaped as HTML. The matched terms are wrapped in `<b>` and
+  /// `</b>`.
+  #[pb(index = 4)]
+  pub snippet: String,
+
+  /// The relevance of the result. The higher the score, the more relevant the result.
+  #[pb(index = 5)]
+  pub score: f64,
+}
+
+#[derive(ProtoBuf, Debug, Default, Clone)]
+pub struct RepeatedSearchResultPB {
+  #[pb(index = 1)]
+  pub items: Vec<SearchResultPB>,
+}
+
+/// A view name, a document block or a database row that is written to the search index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchIndexItem {
+  pub item_type: SearchItemTypePB,
+  pub view_id: String,
+  pub object_id: String,
+  pub content: String,
+}
@@ -0,0 +1,38 @@
+use std::sync::{Arc, Weak};
+
+use flowy_error::{FlowyError, FlowyResult};
+use lib_dispatch::prelude::{data_result_ok, AFPluginData, AFPluginState, DataResult};
+
+use crate::entities::*;
+use crate::manager::SearchManager;
+
+fn upgrade_manager(
+  search_manager: AFPluginState<Weak<SearchManager>>,
+) -> FlowyResult<Arc<SearchManager>> {
+  let manager = search_manager
+    .upgrade()
+    .ok_or(FlowyError::internal().with_context("The search manager is already dropped"))?;
+  Ok(manager)
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn search_handler(
+  data: AFPluginData<SearchQueryPB>,
+  manager: AFPluginState<Weak<SearchManager>>,
+) -> DataResult<RepeatedSearchResultPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let query = data.into_inner();
+  let items = manager
+    .search(&query.query, query.limit.unwrap_or(DEFAULT_SEARCH_LIMIT))
+    .await?;
+  data_result_ok(RepeatedSearchResultPB { items })
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn rebuild_search_index_handler(
+  manager: AFPluginState<Weak<SearchManager>>,
+) -> FlowyResult<()> {
+  let manager = upgrade_manager(manager)?;
+  manager.rebuild_index().await?;
+  Ok(())
+}
@@ -0,0 +1,32 @@
+use std::sync::Weak;
+
+use strum_macros::Display;
+
+use flowy_derive::{Flowy_Event, ProtoBuf_Enum};
+use lib_dispatch::prelude::AFPlugin;
+
+use crate::event_handler::*;
+use crate::manager::SearchManager;
+
+pub fn init(search_manager: Weak<SearchManager>) -> AFPlugin {
+  AFPlugin::new()
+    .name(env!("CARGO_PKG_NAME"))
+    .state(search_manager)
+    .event(SearchEvent::Search, search_handler)
+    .event(
+      SearchEvent::RebuildSearchIndex,
+      rebuild_search_index_handler,
+    )
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Display, ProtoBuf_Enum, Flowy_Event)]
+#[event_err = "FlowyError"]
+pub enum SearchEvent {
+  /// Search the view names, the documents and the database rows of the current workspace.
+  #[event(input = "SearchQueryPB", output = "RepeatedSearchResultPB")]
+  Search = 0,
+
+  /// Rebuild the search index of the current workspace from its views, documents and databases.
+  #[event()]
+  RebuildSearchIndex = 1,
+}
@@ -0,0 +1,6 @@
+pub mod entities;
+pub mod event_handler;
+pub mod event_map;
+pub mod manager;
+pub mod protobuf;
+mod search_sql;
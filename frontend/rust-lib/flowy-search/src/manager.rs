@@ -0,0 +1,529 @@
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use flowy_error::{FlowyError, FlowyResult};
+use flowy_sqlite::DBConnection;
+use lib_dispatch::prelude::af_spawn;
+use lib_infra::future::FutureResult;
+use tokio::sync::Mutex;
+use tracing::{error, info, instrument};
+
+use crate::entities::{SearchIndexItem, SearchItemTypePB, SearchResultPB};
+use crate::search_sql::SearchIndexSql;
+
+pub trait SearchUser: Send + Sync {
+  fn user_id(&self) -> Result<i64, FlowyError>;
+  fn workspace_id(&self) -> Result<String, FlowyError>;
+  fn sqlite_connection(&self, uid: i64) -> Result<DBConnection, FlowyError>;
+}
+
+/// Provides the content of the current workspace. It's used to rebuild the index of the
+/// workspaces that were created before the index existed, and to leave the views in the trash
+/// out of the search results.
+pub trait SearchIndexSource: Send + Sync {
+  /// Returns the view names, the document blocks and the database rows of the current workspace.
+  fn get_all_items(&self) -> FutureResult<Vec<SearchIndexItem>, FlowyError>;
+
+  /// Returns the ids of the views in the trash of the current workspace.
+  fn get_trash_view_ids(&self) -> FutureResult<Vec<String>, FlowyError>;
+}
+
+/// The version of the content of the index. Bump it when the indexed content changes, so the
+/// index of the existing workspaces is rebuilt.
+const SEARCH_INDEX_VERSION: i32 = 1;
+
+/// Keeps a full-text index of the view names, the document blocks and the database rows of each
+/// workspace in the user's sqlite database. The index is updated incrementally by calling the
+/// `index_*` and `remove_*` methods when the content changes.
+pub struct SearchManager {
+  user: Arc<dyn SearchUser>,
+  source: Arc<dyn SearchIndexSource>,
+  /// Only one rebuild runs at a time.
+  rebuild_lock: Arc<Mutex<()>>,
+  /// True if a rebuild is scheduled but hasn't started yet. The requests made in the meantime are
+  /// served by that rebuild.
+  is_rebuild_scheduled: Arc<AtomicBool>,
+}
+
+impl SearchManager {
+  pub fn new(user: Arc<dyn SearchUser>, source: Arc<dyn SearchIndexSource>) -> Self {
+    Self {
+      user,
+      source,
+      rebuild_lock: Arc::new(Mutex::new(())),
+      is_rebuild_scheduled: Arc::new(AtomicBool::new(false)),
+    }
+  }
+
+  /// Builds the index of the workspace in the background unless the index of the current version
+  /// was completely built. It happens when the workspace was created before the index existed, or
+  /// when the last build was interrupted.
+  pub async fn initialize(&self, uid: i64, workspace_id: String) -> FlowyResult<()> {
+    let mut conn = self.user.sqlite_connection(uid)?;
+    if SearchIndexSql::get_index_version(&workspace_id, &mut conn)? == Some(SEARCH_INDEX_VERSION) {
+      return Ok(());
+    }
+
+    self.spawn_rebuild_index(uid, workspace_id);
+    Ok(())
+  }
+
+  /// Rebuilds the index of the current workspace in the background. It's used when some changes
+  /// of the content were missed, so the index can't be updated incrementally.
+  pub fn schedule_rebuild_index(&self) {
+    let (uid, workspace_id) = match (self.user.user_id(), self.user.workspace_id()) {
+      (Ok(uid), Ok(workspace_id)) => (uid, workspace_id),
+      _ => return,
+    };
+    // The index is stale until the rebuild finishes, even if the application is closed before.
+    if let Err(err) = self
+      .user
+      .sqlite_connection(uid)
+      .and_then(|mut conn| SearchIndexSql::delete_index_state(&workspace_id, &mut conn))
+    {
+      error!("Failed to invalidate the search index: {}", err);
+    }
+    self.spawn_rebuild_index(uid, workspace_id);
+  }
+
+  fn spawn_rebuild_index(&self, uid: i64, workspace_id: String) {
+    if self.is_rebuild_scheduled.swap(true, Ordering::SeqCst) {
+      return;
+    }
+
+    let user = self.user.clone();
+    let source = self.source.clone();
+    let rebuild_lock = self.rebuild_lock.clone();
+    let is_rebuild_scheduled = self.is_rebuild_scheduled.clone();
+    af_spawn(async move {
+      let _guard = rebuild_lock.lock().await;
+      is_rebuild_scheduled.store(false, Ordering::SeqCst);
+      info!("Build the search index of workspace: {}", workspace_id);
+      if let Err(err) = rebuild_index(user.as_ref(), source.as_ref(), uid, &workspace_id).await {
+        error!("Failed to build the search index: {}", err);
+      }
+    });
+  }
+
+  /// Returns the items of the current workspace that match all the words of the query, ordered
+  /// by their relevance. The last word also matches the words it's a prefix of, so the results
+  /// can be updated while the user is typing.
+  #[instrument(level = "debug", skip(self), err)]
+  pub async fn search(&self, query: &str, limit: i64) -> FlowyResult<Vec<SearchResultPB>> {
+    let match_query = match fts5_match_query(query) {
+      None => return Ok(vec![]),
+      Some(match_query) => match_query,
+    };
+    let limit = limit.max(1);
+    let trash_view_ids = self
+      .source
+      .get_trash_view_ids()
+      .await?
+      .into_iter()
+      .collect::<HashSet<String>>();
+
+    let (workspace_id, mut conn) = self.workspace_connection()?;
+    let results = SearchIndexSql::search(
+      &workspace_id,
+      &match_query,
+      limit + trash_view_ids.len() as i64,
+      &mut conn,
+    )?;
+    Ok(
+      results
+        .into_iter()
+        .filter(|result| !trash_view_ids.contains(&result.view_id))
+        .take(limit as usize)
+        .collect(),
+    )
+  }
+
+  /// Writes the items to the index, replacing the ones that were indexed before.
+  pub fn index_items(&self, items: Vec<SearchIndexItem>) -> FlowyResult<()> {
+    let (workspace_id, mut conn) = self.workspace_connection()?;
+    SearchIndexSql::upsert_items(&workspace_id, &items, &mut conn)
+  }
+
+  /// Replaces the items of the view that have the given type. For example, all the blocks of a
+  /// document are replaced after the document was changed.
+  pub fn index_view_items(
+    &self,
+    view_id: &str,
+    item_type: SearchItemTypePB,
+    items: Vec<SearchIndexItem>,
+  ) -> FlowyResult<()> {
+    let (workspace_id, mut conn) = self.workspace_connection()?;
+    SearchIndexSql::replace_view_items(&workspace_id, view_id, item_type, &items, &mut conn)
+  }
+
+  pub fn remove_items(
+    &self,
+    item_type: SearchItemTypePB,
+    object_ids: Vec<String>,
+  ) -> FlowyResult<()> {
+    let (workspace_id, mut conn) = self.workspace_connection()?;
+    SearchIndexSql::delete_items(&workspace_id, item_type, &object_ids, &mut conn)
+  }
+
+  /// Removes the views and all their items, such as the blocks of the documents and the rows of
+  /// the databases.
+  pub fn remove_views(&self, view_ids: Vec<String>) -> FlowyResult<()> {
+    let (workspace_id, mut conn) = self.workspace_connection()?;
+    SearchIndexSql::delete_view_items(&workspace_id, &view_ids, &mut conn)
+  }
+
+  /// Replaces the index of the current workspace with the items of the [SearchIndexSource].
+  #[instrument(level = "info", skip(self), err)]
+  pub async fn rebuild_index(&self) -> FlowyResult<()> {
+    let uid = self.user.user_id()?;
+    let workspace_id = self.user.workspace_id()?;
+    let _guard = self.rebuild_lock.lock().await;
+    rebuild_index(self.user.as_ref(), self.source.as_ref(), uid, &workspace_id).await
+  }
+
+  fn workspace_connection(&self) -> FlowyResult<(String, DBConnection)> {
+    let uid = self.user.user_id()?;
+    let workspace_id = self.user.workspace_id()?;
+    let conn = self.user.sqlite_connection(uid)?;
+    Ok((workspace_id, conn))
+  }
+}
+
+async fn rebuild_index(
+  user: &dyn SearchUser,
+  source: &dyn SearchIndexSource,
+  uid: i64,
+  workspace_id: &str,
+) -> FlowyResult<()> {
+  let items = source.get_all_items().await?;
+  let mut conn = user.sqlite_connection(uid)?;
+  SearchIndexSql::replace_workspace_items(workspace_id, &items, SEARCH_INDEX_VERSION, &mut conn)?;
+  info!(
+    "Did index {} items of workspace: {}",
+    items.len(),
+    workspace_id
+  );
+  Ok(())
+}
+
+/// Converts the query to an FTS5 query that matches the items containing all the words of the
+/// query. Each word is quoted, so the FTS5 operators in the query are treated as plain text, and
+/// the last word is used as a prefix. Returns None if the query doesn't have any words.
+pub(crate) fn fts5_match_query(query: &str) -> Option<String> {
+  let words = query.split_whitespace().collect::<Vec<&str>>();
+  let (last, words) = words.split_last()?;
+  let mut match_query = words
+    .iter()
+    .map(|word| format!("\"{}\"", word.replace('"', "\"\"")))
+    .collect::<Vec<String>>();
+  match_query.push(format!("\"{}\"*", last.replace('"', "\"\"")));
+  Some(match_query.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+
+  use flowy_error::FlowyError;
+  use flowy_sqlite::{DBConnection, Database};
+  use lib_infra::future::FutureResult;
+  use tempfile::TempDir;
+
+  use crate::entities::{SearchIndexItem, SearchItemTypePB};
+  use crate::manager::{
+    fts5_match_query, SearchIndexSource, SearchManager, SearchUser, SEARCH_INDEX_VERSION,
+  };
+  use crate::search_sql::SearchIndexSql;
+
+  struct TestUser {
+    database: Database,
+    workspace_id: String,
+  }
+
+  impl SearchUser for TestUser {
+    fn user_id(&self) -> Result<i64, FlowyError> {
+      Ok(1)
+    }
+
+    fn workspace_id(&self) -> Result<String, FlowyError> {
+      Ok(self.workspace_id.clone())
+    }
+
+    fn sqlite_connection(&self, _uid: i64) -> Result<DBConnection, FlowyError> {
+      self
+        .database
+        .get_connection()
+        .map_err(|err| FlowyError::internal().with_context(err))
+    }
+  }
+
+  struct TestSource {
+    items: Vec<SearchIndexItem>,
+    trash_view_ids: Vec<String>,
+  }
+
+  impl SearchIndexSource for TestSource {
+    fn get_all_items(&self) -> FutureResult<Vec<SearchIndexItem>, FlowyError> {
+      let items = self.items.clone();
+      FutureResult::new(async move { Ok(items) })
+    }
+
+    fn get_trash_view_ids(&self) -> FutureResult<Vec<String>, FlowyError> {
+      let trash_view_ids = self.trash_view_ids.clone();
+      FutureResult::new(async move { Ok(trash_view_ids) })
+    }
+  }
+
+  fn item(
+    item_type: SearchItemTypePB,
+    view_id: &str,
+    object_id: &str,
+    content: &str,
+  ) -> SearchIndexItem {
+    SearchIndexItem {
+      item_type,
+      view_id: view_id.to_string(),
+      object_id: object_id.to_string(),
+      content: content.to_string(),
+    }
+  }
+
+  fn search_manager(
+    dir: &TempDir,
+    workspace_id: &str,
+    source_items: Vec<SearchIndexItem>,
+    trash_view_ids: Vec<&str>,
+  ) -> SearchManager {
+    let database = flowy_sqlite::init(dir.path()).unwrap();
+    let user = TestUser {
+      database,
+      workspace_id: workspace_id.to_string(),
+    };
+    let source = TestSource {
+      items: source_items,
+      trash_view_ids: trash_view_ids
+        .into_iter()
+        .map(|id| id.to_string())
+        .collect(),
+    };
+    SearchManager::new(Arc::new(user), Arc::new(source))
+  }
+
+  #[test]
+  fn fts5_match_query_test() {
+    assert_eq!(fts5_match_query("   "), None);
+    assert_eq!(fts5_match_query("quick"), Some("\"quick\"*".to_string()));
+    assert_eq!(
+      fts5_match_query(" quick  brown fo"),
+      Some("\"quick\" \"brown\" \"fo\"*".to_string())
+    );
+    assert_eq!(
+      fts5_match_query("say \"hi\" OR"),
+      Some("\"say\" \"\"\"hi\"\"\" \"OR\"*".to_string())
+    );
+  }
+
+  #[tokio::test]
+  async fn search_ranked_results_with_snippets_test() {
+    let dir = tempfile::tempdir().unwrap();
+    let manager = search_manager(&dir, "w1", vec![], vec![]);
+    manager
+      .index_items(vec![
+        item(SearchItemTypePB::View, "v1", "v1", "Meeting notes"),
+        item(
+          SearchItemTypePB::Document,
+          "v1",
+          "b1",
+          "The quick brown fox jumps",
+        ),
+        item(
+          SearchItemTypePB::Document,
+          "v1",
+          "b2",
+          "A fox, a fox and another fox",
+        ),
+        item(
+          SearchItemTypePB::DatabaseRow,
+          "v2",
+          "r1",
+          "Buy a lamp\nTodo",
+        ),
+      ])
+      .unwrap();
+
+    let results = manager.search("fox", 10).await.unwrap();
+    assert_eq!(
+      results
+        .iter()
+        .map(|result| result.object_id.as_str())
+        .collect::<Vec<_>>(),
+      vec!["b2", "b1"]
+    );
+    assert!(results[0].score > results[1].score);
+    assert_eq!(results[1].view_id, "v1");
+    assert_eq!(results[1].item_type, SearchItemTypePB::Document);
+    assert_eq!(results[1].snippet, "The quick brown <b>fox</b> jumps");
+
+    // The last word is used as a prefix.
+    let results = manager.search("meet", 10).await.unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].item_type, SearchItemTypePB::View);
+
+    // All the words must match.
+    let results = manager.search("lamp todo", 10).await.unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].object_id, "r1");
+    assert!(manager.search("lamp fox", 10).await.unwrap().is_empty());
+    assert_eq!(manager.search("fox", 1).await.unwrap().len(), 1);
+  }
+
+  #[tokio::test]
+  async fn update_and_remove_indexed_items_test() {
+    let dir = tempfile::tempdir().unwrap();
+    let manager = search_manager(&dir, "w1", vec![], vec![]);
+    manager
+      .index_items(vec![
+        item(SearchItemTypePB::View, "v1", "v1", "Travel"),
+        item(SearchItemTypePB::Document, "v1", "b1", "Paris"),
+        item(SearchItemTypePB::Document, "v1", "b2", "Rome"),
+        item(SearchItemTypePB::DatabaseRow, "v2", "r1", "Tokyo"),
+      ])
+      .unwrap();
+
+    manager
+      .index_items(vec![item(
+        SearchItemTypePB::DatabaseRow,
+        "v2",
+        "r1",
+        "Kyoto",
+      )])
+      .unwrap();
+    assert!(manager.search("tokyo", 10).await.unwrap().is_empty());
+    assert_eq!(manager.search("kyoto", 10).await.unwrap().len(), 1);
+
+    // The blocks that were removed from the document are removed from the index.
+    manager
+      .index_view_items(
+        "v1",
+        SearchItemTypePB::Document,
+        vec![item(
+          SearchItemTypePB::Document,
+          "v1",
+          "b2",
+          "Rome and Milan",
+        )],
+      )
+      .unwrap();
+    assert!(manager.search("paris", 10).await.unwrap().is_empty());
+    assert_eq!(manager.search("milan", 10).await.unwrap().len(), 1);
+    assert_eq!(manager.search("travel", 10).await.unwrap().len(), 1);
+
+    manager
+      .remove_items(SearchItemTypePB::DatabaseRow, vec!["r1".to_string()])
+      .unwrap();
+    assert!(manager.search("kyoto", 10).await.unwrap().is_empty());
+
+    manager.remove_views(vec!["v1".to_string()]).unwrap();
+    assert!(manager.search("travel", 10).await.unwrap().is_empty());
+    assert!(manager.search("rome", 10).await.unwrap().is_empty());
+  }
+
+  #[tokio::test]
+  async fn rebuild_index_test() {
+    let dir = tempfile::tempdir().unwrap();
+    let manager = search_manager(
+      &dir,
+      "w1",
+      vec![
+        item(SearchItemTypePB::View, "v1", "v1", "Recipes"),
+        item(
+          SearchItemTypePB::Document,
+          "v1",
+          "b1",
+          "Pancakes with syrup",
+        ),
+        item(SearchItemTypePB::View, "v2", "v2", "Old recipes"),
+      ],
+      vec!["v2"],
+    );
+    manager
+      .index_items(vec![item(
+        SearchItemTypePB::View,
+        "v3",
+        "v3",
+        "Stale recipes",
+      )])
+      .unwrap();
+
+    manager.rebuild_index().await.unwrap();
+    // The views in the trash are not returned.
+    let results = manager.search("recipes", 10).await.unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].object_id, "v1");
+    assert_eq!(manager.search("syrup", 10).await.unwrap().len(), 1);
+
+    // The index of the other workspaces is kept.
+    let other_manager = search_manager(&dir, "w2", vec![], vec![]);
+    assert!(other_manager
+      .search("recipes", 10)
+      .await
+      .unwrap()
+      .is_empty());
+    other_manager
+      .index_items(vec![item(SearchItemTypePB::View, "v4", "v4", "Recipes")])
+      .unwrap();
+    manager.rebuild_index().await.unwrap();
+    assert_eq!(other_manager.search("recipes", 10).await.unwrap().len(), 1);
+  }
+
+  #[tokio::test]
+  async fn escape_snippet_test() {
+    let dir = tempfile::tempdir().unwrap();
+    let manager = search_manager(&dir, "w1", vec![], vec![]);
+    manager
+      .index_items(vec![item(
+        SearchItemTypePB::Document,
+        "v1",
+        "b1",
+        "a <b>tag</b> & the fox\u{E000} jumps",
+      )])
+      .unwrap();
+
+    let results = manager.search("fox", 10).await.unwrap();
+    assert_eq!(
+      results[0].snippet,
+      "a &lt;b&gt;tag&lt;/b&gt; &amp; the <b>fox</b> jumps"
+    );
+  }
+
+  #[tokio::test]
+  async fn index_version_test() {
+    let dir = tempfile::tempdir().unwrap();
+    let manager = search_manager(
+      &dir,
+      "w1",
+      vec![item(SearchItemTypePB::View, "v1", "v1", "Recipes")],
+      vec![],
+    );
+    let version = || {
+      let mut conn = manager.user.sqlite_connection(1).unwrap();
+      SearchIndexSql::get_index_version("w1", &mut conn).unwrap()
+    };
+
+    // The items indexed incrementally don't mean that the index was built.
+    manager
+      .index_items(vec![item(SearchItemTypePB::View, "v2", "v2", "Notes")])
+      .unwrap();
+    assert_eq!(version(), None);
+
+    manager.rebuild_index().await.unwrap();
+    assert_eq!(version(), Some(SEARCH_INDEX_VERSION));
+
+    // The index is stale until the scheduled rebuild finishes.
+    manager.schedule_rebuild_index();
+    assert_eq!(version(), None);
+    manager.rebuild_index().await.unwrap();
+    assert_eq!(version(), Some(SEARCH_INDEX_VERSION));
+  }
+}
@@ -0,0 +1,247 @@
+use diesel::sql_types::{BigInt, Double, Integer, Text};
+use diesel::{
+  delete, insert_or_ignore_into, replace_into, sql_query, OptionalExtension, SqliteConnection,
+};
+use flowy_error::FlowyError;
+use flowy_sqlite::{
+  prelude::*,
+  schema::{search_index_item, search_index_item::dsl, search_index_state},
+  Error,
+};
+use lib_infra::util::timestamp;
+
+use crate::entities::{SearchIndexItem, SearchItemTypePB, SearchResultPB};
+
+/// Wraps the matched terms of the snippets. Keep it in sync with the doc of [SearchResultPB].
+const SNIPPET_HIGHLIGHT_START: &str = "<b>";
+const SNIPPET_HIGHLIGHT_END: &str = "</b>";
+/// The matched terms are marked with these private use characters by FTS5, then replaced with the
+/// highlight tags once the content of the snippet is escaped. They are removed from the content
+/// when it's indexed, so they can't be confused with the content.
+const SNIPPET_MARK_START: char = '\u{E000}';
+const SNIPPET_MARK_END: char = '\u{E001}';
+const SNIPPET_ELLIPSIS: &str = "...";
+/// The maximum number of tokens of a snippet.
+const SNIPPET_MAX_TOKENS: i32 = 16;
+
+#[derive(QueryableByName)]
+struct SearchResultRow {
+  #[diesel(sql_type = Integer)]
+  item_type: i32,
+  #[diesel(sql_type = Text)]
+  view_id: String,
+  #[diesel(sql_type = Text)]
+  object_id: String,
+  #[diesel(sql_type = Text)]
+  snippet: String,
+  #[diesel(sql_type = Double)]
+  score: f64,
+}
+
+impl From<SearchResultRow> for SearchResultPB {
+  fn from(row: SearchResultRow) -> Self {
+    SearchResultPB {
+      item_type: SearchItemTypePB::from(row.item_type),
+      view_id: row.view_id,
+      object_id: row.object_id,
+      snippet: highlight_snippet(&row.snippet),
+      score: row.score,
+    }
+  }
+}
+
+pub(crate) struct SearchIndexSql;
+impl SearchIndexSql {
+  /// Writes the items to the index. The items that were indexed before are replaced.
+  pub(crate) fn upsert_items(
+    workspace_id: &str,
+    items: &[SearchIndexItem],
+    conn: &mut SqliteConnection,
+  ) -> Result<(), FlowyError> {
+    conn.immediate_transaction::<_, Error, _>(|conn| {
+      for item in items {
+        delete(
+          dsl::search_index_item
+            .filter(dsl::workspace_id.eq(workspace_id))
+            .filter(dsl::item_type.eq(item.item_type as i32))
+            .filter(dsl::object_id.eq(&item.object_id)),
+        )
+        .execute(conn)?;
+        insert_item(workspace_id, item, conn)?;
+      }
+      Ok(())
+    })?;
+    Ok(())
+  }
+
+  /// Replaces the items of the view that have the given type with the given items.
+  pub(crate) fn replace_view_items(
+    workspace_id: &str,
+    view_id: &str,
+    item_type: SearchItemTypePB,
+    items: &[SearchIndexItem],
+    conn: &mut SqliteConnection,
+  ) -> Result<(), FlowyError> {
+    conn.immediate_transaction::<_, Error, _>(|conn| {
+      delete(
+        dsl::search_index_item
+          .filter(dsl::workspace_id.eq(workspace_id))
+          .filter(dsl::view_id.eq(view_id))
+          .filter(dsl::item_type.eq(item_type as i32)),
+      )
+      .execute(conn)?;
+      for item in items {
+        insert_item(workspace_id, item, conn)?;
+      }
+      Ok(())
+    })?;
+    Ok(())
+  }
+
+  /// Replaces all the items of the workspace with the given items, and records that the index of
+  /// the given version was completely built.
+  pub(crate) fn replace_workspace_items(
+    workspace_id: &str,
+    items: &[SearchIndexItem],
+    version: i32,
+    conn: &mut SqliteConnection,
+  ) -> Result<(), FlowyError> {
+    conn.immediate_transaction::<_, Error, _>(|conn| {
+      delete(dsl::search_index_item.filter(dsl::workspace_id.eq(workspace_id))).execute(conn)?;
+      for item in items {
+        insert_item(workspace_id, item, conn)?;
+      }
+      replace_into(search_index_state::table)
+        .values((
+          search_index_state::workspace_id.eq(workspace_id),
+          search_index_state::version.eq(version),
+          search_index_state::indexed_at.eq(timestamp()),
+        ))
+        .execute(conn)?;
+      Ok(())
+    })?;
+    Ok(())
+  }
+
+  pub(crate) fn delete_items(
+    workspace_id: &str,
+    item_type: SearchItemTypePB,
+    object_ids: &[String],
+    conn: &mut SqliteConnection,
+  ) -> Result<(), FlowyError> {
+    delete(
+      dsl::search_index_item
+        .filter(dsl::workspace_id.eq(workspace_id))
+        .filter(dsl::item_type.eq(item_type as i32))
+        .filter(dsl::object_id.eq_any(object_ids)),
+    )
+    .execute(conn)?;
+    Ok(())
+  }
+
+  /// Deletes the items of the views, including the blocks of the documents and the rows of the
+  /// databases.
+  pub(crate) fn delete_view_items(
+    workspace_id: &str,
+    view_ids: &[String],
+    conn: &mut SqliteConnection,
+  ) -> Result<(), FlowyError> {
+    delete(
+      dsl::search_index_item
+        .filter(dsl::workspace_id.eq(workspace_id))
+        .filter(dsl::view_id.eq_any(view_ids)),
+    )
+    .execute(conn)?;
+    Ok(())
+  }
+
+  /// Returns the version of the index that was completely built for the workspace, or None if
+  /// the index was never built or the build was interrupted.
+  pub(crate) fn get_index_version(
+    workspace_id: &str,
+    conn: &mut SqliteConnection,
+  ) -> Result<Option<i32>, FlowyError> {
+    let version = search_index_state::table
+      .filter(search_index_state::workspace_id.eq(workspace_id))
+      .select(search_index_state::version)
+      .first::<i32>(conn)
+      .optional()?;
+    Ok(version)
+  }
+
+  /// Marks the index of the workspace as incomplete, so it's built again the next time the
+  /// workspace is opened unless the build finishes before.
+  pub(crate) fn delete_index_state(
+    workspace_id: &str,
+    conn: &mut SqliteConnection,
+  ) -> Result<(), FlowyError> {
+    delete(search_index_state::table.filter(search_index_state::workspace_id.eq(workspace_id)))
+      .execute(conn)?;
+    Ok(())
+  }
+
+  /// Returns the items that match the full-text query, ordered by their relevance. The query must
+  /// be a valid FTS5 query, see [fts5_match_query](crate::manager::fts5_match_query).
+  pub(crate) fn search(
+    workspace_id: &str,
+    match_query: &str,
+    limit: i64,
+    conn: &mut SqliteConnection,
+  ) -> Result<Vec<SearchResultPB>, FlowyError> {
+    let sql = format!(
+      "SELECT item.item_type, item.view_id, item.object_id, \
+         snippet(search_index, 0, '{}', '{}', '{}', {}) AS snippet, \
+         -bm25(search_index) AS score \
+       FROM search_index JOIN search_index_item AS item ON item.id = search_index.rowid \
+       WHERE search_index MATCH ? AND item.workspace_id = ? \
+       ORDER BY rank LIMIT ?",
+      SNIPPET_MARK_START, SNIPPET_MARK_END, SNIPPET_ELLIPSIS, SNIPPET_MAX_TOKENS
+    );
+    let rows = sql_query(sql)
+      .bind::<Text, _>(match_query)
+      .bind::<Text, _>(workspace_id)
+      .bind::<BigInt, _>(limit)
+      .load::<SearchResultRow>(conn)?;
+    Ok(rows.into_iter().map(SearchResultPB::from).collect())
+  }
+}
+
+/// Inserts the item. It's ignored if an item with the same type and id was already inserted.
+fn insert_item(
+  workspace_id: &str,
+  item: &SearchIndexItem,
+  conn: &mut SqliteConnection,
+) -> Result<(), Error> {
+  let content = item
+    .content
+    .replace([SNIPPET_MARK_START, SNIPPET_MARK_END], "");
+  insert_or_ignore_into(search_index_item::table)
+    .values((
+      dsl::workspace_id.eq(workspace_id),
+      dsl::item_type.eq(item.item_type as i32),
+      dsl::view_id.eq(&item.view_id),
+      dsl::object_id.eq(&item.object_id),
+      dsl::content.eq(content),
+    ))
+    .execute(conn)?;
+  Ok(())
+}
+
+/// Escapes the content of the snippet, so it can be displayed as HTML, then wraps the matched
+/// terms in the highlight tags.
+fn highlight_snippet(snippet: &str) -> String {
+  let mut highlighted = String::with_capacity(snippet.len());
+  for c in snippet.chars() {
+    match c {
+      SNIPPET_MARK_START => highlighted.push_str(SNIPPET_HIGHLIGHT_START),
+      SNIPPET_MARK_END => highlighted.push_str(SNIPPET_HIGHLIGHT_END),
+      '&' => highlighted.push_str("&amp;"),
+      '<' => highlighted.push_str("&lt;"),
+      '>' => highlighted.push_str("&gt;"),
+      '"' => highlighted.push_str("&quot;"),
+      '\'' => highlighted.push_str("&#39;"),
+      c => highlighted.push(c),
+    }
+  }
+  highlighted
+}
@@ -9,7 +9,7 @@ use client_api::ws::{
   ConnectState, WSClient, WSClientConfig, WSConnectStateReceiver, WebSocketChannel,
 };
 use client_api::{Client, ClientConfiguration};
-use flowy_storage::ObjectStorageService;
+use flowy_storage::{FileStoragePlan, ObjectStorageService};
 use tokio::sync::watch;
 use tokio_stream::wrappers::WatchStream;
 use tracing::{error, event, info, warn};
@@ -29,7 +29,7 @@ use crate::af_cloud::impls::{
   AFCloudDatabaseCloudServiceImpl, AFCloudDocumentCloudServiceImpl, AFCloudFileStorageServiceImpl,
   AFCloudFolderCloudServiceImpl, AFCloudUserAuthServiceImpl,
 };
-use crate::AppFlowyServer;
+use crate::{AppFlowyServer, FileSizeLimitPlan};
 
 pub(crate) type AFCloudClient = Client;
 
@@ -41,6 +41,7 @@ pub struct AppFlowyCloudServer {
   network_reachable: Arc<AtomicBool>,
   pub device_id: String,
   ws_client: Arc<WSClient>,
+  file_storage_plan: Arc<FileSizeLimitPlan>,
 }
 
 impl AppFlowyCloudServer {
@@ -49,6 +50,7 @@ impl AppFlowyCloudServer {
     enable_sync: bool,
     mut device_id: String,
     app_version: &str,
+    maximum_file_size: u64,
   ) -> Self {
     // The device id can't be empty, so we generate a new one if it is.
     if device_id.is_empty() {
@@ -88,6 +90,7 @@ impl AppFlowyCloudServer {
       network_reachable,
       device_id,
       ws_client,
+      file_storage_plan: Arc::new(FileSizeLimitPlan::new(maximum_file_size)),
     }
   }
 
@@ -232,6 +235,10 @@ impl AppFlowyServer for AppFlowyCloudServer {
     };
     Some(Arc::new(AFCloudFileStorageServiceImpl::new(client)))
   }
+
+  fn file_storage_plan(&self) -> Option<Arc<dyn FileStoragePlan>> {
+    Some(self.file_storage_plan.clone())
+  }
 }
 
 /// Spawns a new asynchronous task to handle WebSocket connections based on token state.
@@ -1,4 +1,4 @@
-use flowy_storage::ObjectStorageService;
+use flowy_storage::{FileStoragePlan, ObjectStorageService};
 use std::sync::Arc;
 
 use parking_lot::RwLock;
@@ -18,7 +18,7 @@ use crate::local_server::impls::{
   LocalServerDatabaseCloudServiceImpl, LocalServerDocumentCloudServiceImpl,
   LocalServerFolderCloudServiceImpl, LocalServerUserAuthServiceImpl,
 };
-use crate::AppFlowyServer;
+use crate::{AppFlowyServer, FileSizeLimitPlan};
 
 pub trait LocalServerDB: Send + Sync + 'static {
   fn get_user_profile(&self, uid: i64) -> Result<UserProfile, FlowyError>;
@@ -28,13 +28,15 @@ pub trait LocalServerDB: Send + Sync + 'static {
 pub struct LocalServer {
   local_db: Arc<dyn LocalServerDB>,
   stop_tx: RwLock<Option<mpsc::Sender<()>>>,
+  file_storage_plan: Arc<FileSizeLimitPlan>,
 }
 
 impl LocalServer {
-  pub fn new(local_db: Arc<dyn LocalServerDB>) -> Self {
+  pub fn new(local_db: Arc<dyn LocalServerDB>, maximum_file_size: u64) -> Self {
     Self {
       local_db,
       stop_tx: Default::default(),
+      file_storage_plan: Arc::new(FileSizeLimitPlan::new(maximum_file_size)),
     }
   }
 
@@ -70,4 +72,8 @@ impl AppFlowyServer for LocalServer {
   fn file_storage(&self) -> Option<Arc<dyn ObjectStorageService>> {
     None
   }
+
+  fn file_storage_plan(&self) -> Option<Arc<dyn FileStoragePlan>> {
+    Some(self.file_storage_plan.clone())
+  }
 }
@@ -1,7 +1,7 @@
 use client_api::ws::ConnectState;
 use client_api::ws::WSConnectStateReceiver;
 use client_api::ws::WebSocketChannel;
-use flowy_storage::ObjectStorageService;
+use flowy_storage::{FileStoragePlan, ObjectStorageService, StorageObject};
 use std::sync::Arc;
 
 use anyhow::Error;
@@ -13,6 +13,7 @@ use {collab_entity::CollabObject, collab_plugins::cloud_storage::RemoteCollabSto
 
 use flowy_database_pub::cloud::DatabaseCloudService;
 use flowy_document_pub::cloud::DocumentCloudService;
+use flowy_error::{ErrorCode, FlowyError};
 use flowy_folder_pub::cloud::FolderCloudService;
 use flowy_user_pub::cloud::UserCloudService;
 use flowy_user_pub::entities::UserTokenState;
@@ -135,6 +136,12 @@ pub trait AppFlowyServer: Send + Sync + 'static {
   }
 
   fn file_storage(&self) -> Option<Arc<dyn ObjectStorageService>>;
+
+  /// The plan that limits the size of the files uploaded to the [ObjectStorageService]. Returns
+  /// None if the server doesn't limit the uploads on the client side.
+  fn file_storage_plan(&self) -> Option<Arc<dyn FileStoragePlan>> {
+    None
+  }
 }
 
 pub struct EncryptionImpl {
@@ -158,3 +165,52 @@ impl AppFlowyEncryption for EncryptionImpl {
     *self.secret.write() = Some(secret);
   }
 }
+
+/// Limits the size of each uploaded file on the client side, for the servers that don't report
+/// their own limits. The total storage isn't limited.
+pub struct FileSizeLimitPlan {
+  maximum_file_size: u64,
+}
+
+impl FileSizeLimitPlan {
+  /// 10 MB
+  pub const DEFAULT_MAXIMUM_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+  pub fn new(maximum_file_size: u64) -> Self {
+    Self { maximum_file_size }
+  }
+}
+
+impl Default for FileSizeLimitPlan {
+  fn default() -> Self {
+    Self::new(Self::DEFAULT_MAXIMUM_FILE_SIZE)
+  }
+}
+
+impl FileStoragePlan for FileSizeLimitPlan {
+  fn storage_size(&self) -> FutureResult<u64, FlowyError> {
+    FutureResult::new(async { Ok(u64::MAX) })
+  }
+
+  fn maximum_file_size(&self) -> FutureResult<u64, FlowyError> {
+    let maximum_file_size = self.maximum_file_size;
+    FutureResult::new(async move { Ok(maximum_file_size) })
+  }
+
+  fn check_upload_object(&self, object: &StorageObject) -> FutureResult<(), FlowyError> {
+    let file_size = object.file_size();
+    let maximum_file_size = self.maximum_file_size;
+    FutureResult::new(async move {
+      if file_size > maximum_file_size {
+        return Err(FlowyError::new(
+          ErrorCode::FileTooLarge,
+          format!(
+            "The file size {} exceeds the maximum file size {}",
+            file_size, maximum_file_size
+          ),
+        ));
+      }
+      Ok(())
+    })
+  }
+}
@@ -22,7 +22,6 @@ pub struct SupabaseFileStorage {
   client: Client,
   #[allow(dead_code)]
   encryption: ObjectEncryption,
-  #[allow(dead_code)]
   storage_plan: Arc<dyn FileStoragePlan>,
 }
 
@@ -132,6 +131,10 @@ impl SupabaseFileStorage {
     })
   }
 
+  pub fn storage_plan(&self) -> Arc<dyn FileStoragePlan> {
+    self.storage_plan.clone()
+  }
+
   pub fn storage(&self) -> StorageRequestBuilder {
     StorageRequestBuilder::new(self.url.clone(), self.headers.clone(), self.client.clone())
   }
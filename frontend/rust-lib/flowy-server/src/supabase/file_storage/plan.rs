@@ -2,7 +2,7 @@ use std::sync::Weak;
 
 use parking_lot::RwLock;
 
-use flowy_error::FlowyError;
+use flowy_error::{ErrorCode, FlowyError};
 use flowy_storage::{FileStoragePlan, StorageObject};
 use lib_infra::future::FutureResult;
 
@@ -36,7 +36,21 @@ impl FileStoragePlan for FileStoragePlanImpl {
     FutureResult::new(async { Ok(5 * 1024 * 1024) })
   }
 
-  fn check_upload_object(&self, _object: &StorageObject) -> FutureResult<(), FlowyError> {
-    FutureResult::new(async { Ok(()) })
+  fn check_upload_object(&self, object: &StorageObject) -> FutureResult<(), FlowyError> {
+    let file_size = object.file_size();
+    let maximum_file_size = self.maximum_file_size();
+    FutureResult::new(async move {
+      let maximum_file_size = maximum_file_size.await?;
+      if file_size > maximum_file_size {
+        return Err(FlowyError::new(
+          ErrorCode::FileTooLarge,
+          format!(
+            "The file size {} exceeds the maximum file size {}",
+            file_size, maximum_file_size
+          ),
+        ));
+      }
+      Ok(())
+    })
   }
 }
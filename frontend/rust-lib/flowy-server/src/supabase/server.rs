@@ -1,4 +1,4 @@
-use flowy_storage::ObjectStorageService;
+use flowy_storage::{FileStoragePlan, ObjectStorageService};
 use std::collections::HashMap;
 use std::sync::{Arc, Weak};
 
@@ -194,4 +194,12 @@ impl AppFlowyServer for SupabaseServer {
       .clone()
       .map(|s| s as Arc<dyn ObjectStorageService>)
   }
+
+  fn file_storage_plan(&self) -> Option<Arc<dyn FileStoragePlan>> {
+    self
+      .file_storage
+      .read()
+      .as_ref()
+      .map(|file_storage| file_storage.storage_plan())
+  }
 }
@@ -6,6 +6,7 @@ use uuid::Uuid;
 
 use flowy_server::af_cloud::AppFlowyCloudServer;
 use flowy_server::supabase::define::{USER_DEVICE_ID, USER_SIGN_IN_URL};
+use flowy_server::FileSizeLimitPlan;
 use flowy_server_pub::af_cloud_config::AFCloudConfiguration;
 
 use crate::setup_log;
@@ -31,6 +32,7 @@ pub fn af_cloud_server(config: AFCloudConfiguration) -> Arc<AppFlowyCloudServer>
     true,
     fake_device_id,
     "flowy-server-test",
+    FileSizeLimitPlan::DEFAULT_MAXIMUM_FILE_SIZE,
   ))
 }
 
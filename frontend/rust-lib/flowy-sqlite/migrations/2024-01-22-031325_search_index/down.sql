@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+DROP TRIGGER IF EXISTS search_index_item_update;
+DROP TRIGGER IF EXISTS search_index_item_delete;
+DROP TRIGGER IF EXISTS search_index_item_insert;
+DROP TABLE IF EXISTS search_index;
+DROP TABLE IF EXISTS search_index_item;
@@ -0,0 +1,35 @@
+-- Your SQL goes here
+-- The view names, document blocks and database rows that can be searched. The item_type is the
+-- value of the SearchItemTypePB and the object_id is the id of the view, the block or the row.
+CREATE TABLE search_index_item (
+  id INTEGER NOT NULL PRIMARY KEY,
+  workspace_id TEXT NOT NULL DEFAULT '',
+  item_type INTEGER NOT NULL DEFAULT 0,
+  view_id TEXT NOT NULL DEFAULT '',
+  object_id TEXT NOT NULL DEFAULT '',
+  content TEXT NOT NULL DEFAULT '',
+  UNIQUE (workspace_id, item_type, object_id)
+);
+CREATE INDEX search_index_item_view_id ON search_index_item (workspace_id, view_id);
+
+-- The full-text index of the content of the search_index_item table. The triggers keep it in sync
+-- with the table.
+CREATE VIRTUAL TABLE search_index USING fts5(
+  content,
+  content = 'search_index_item',
+  content_rowid = 'id',
+  tokenize = 'unicode61 remove_diacritics 2'
+);
+
+CREATE TRIGGER search_index_item_insert AFTER INSERT ON search_index_item BEGIN
+  INSERT INTO search_index (rowid, content) VALUES (new.id, new.content);
+END;
+
+CREATE TRIGGER search_index_item_delete AFTER DELETE ON search_index_item BEGIN
+  INSERT INTO search_index (search_index, rowid, content) VALUES ('delete', old.id, old.content);
+END;
+
+CREATE TRIGGER search_index_item_update AFTER UPDATE ON search_index_item BEGIN
+  INSERT INTO search_index (search_index, rowid, content) VALUES ('delete', old.id, old.content);
+  INSERT INTO search_index (rowid, content) VALUES (new.id, new.content);
+END;
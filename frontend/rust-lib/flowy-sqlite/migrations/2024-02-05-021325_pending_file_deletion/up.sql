@@ -0,0 +1,9 @@
+-- Your SQL goes here
+-- The objects of the files cells that are no longer referenced by any cell. They are deleted once
+-- the grace period after deleted_at is over, unless a cell references them again.
+CREATE TABLE pending_file_deletion (
+  url TEXT NOT NULL PRIMARY KEY,
+  database_id TEXT NOT NULL DEFAULT '',
+  deleted_at BIGINT NOT NULL DEFAULT 0
+);
+CREATE INDEX pending_file_deletion_database_id ON pending_file_deletion (database_id);
@@ -0,0 +1,8 @@
+-- Your SQL goes here
+-- The workspaces whose search index was completely built. The version is bumped when the content
+-- of the index changes, so the index built by an older version is rebuilt.
+CREATE TABLE search_index_state (
+  workspace_id TEXT NOT NULL PRIMARY KEY,
+  version INTEGER NOT NULL DEFAULT 0,
+  indexed_at BIGINT NOT NULL DEFAULT 0
+);
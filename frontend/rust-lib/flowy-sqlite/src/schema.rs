@@ -12,6 +12,33 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    pending_file_deletion (url) {
+        url -> Text,
+        database_id -> Text,
+        deleted_at -> BigInt,
+    }
+}
+
+diesel::table! {
+    search_index_item (id) {
+        id -> Integer,
+        workspace_id -> Text,
+        item_type -> Integer,
+        view_id -> Text,
+        object_id -> Text,
+        content -> Text,
+    }
+}
+
+diesel::table! {
+    search_index_state (workspace_id) {
+        workspace_id -> Text,
+        version -> Integer,
+        indexed_at -> BigInt,
+    }
+}
+
 diesel::table! {
     user_data_migration_records (id) {
         id -> Integer,
@@ -48,6 +75,9 @@ diesel::table! {
 
 diesel::allow_tables_to_appear_in_same_query!(
   collab_snapshot,
+  pending_file_deletion,
+  search_index_item,
+  search_index_state,
   user_data_migration_records,
   user_table,
   user_workspace_table,
@@ -1,8 +1,42 @@
-use crate::{ObjectIdentity, ObjectValue};
+use crate::{ObjectIdentity, ObjectStorageService, ObjectValue};
 use flowy_error::FlowyError;
 use std::path::Path;
+use std::sync::Arc;
 use tokio::io::AsyncReadExt;
-use tracing::info;
+use tracing::{error, info};
+
+/// Uploads the file on the user's disk and returns the url of the object. The id of the object is
+/// the hash of the file content, prefixed with the `file_id_prefix` if any.
+///
+/// If `is_async` is true, the url is returned right away and the file is uploaded in the
+/// background.
+pub async fn upload_file_from_disk(
+  storage_service: Arc<dyn ObjectStorageService>,
+  workspace_id: &str,
+  local_file_path: &str,
+  file_id_prefix: Option<&str>,
+  is_async: bool,
+) -> Result<String, FlowyError> {
+  let (mut object_identity, object_value) = object_from_disk(workspace_id, local_file_path).await?;
+  if let Some(file_id_prefix) = file_id_prefix {
+    object_identity.file_id = format!("{}_{}", file_id_prefix, object_identity.file_id);
+  }
+  let url = storage_service.get_object_url(object_identity).await?;
+
+  let clone_url = url.clone();
+  match is_async {
+    false => storage_service.put_object(clone_url, object_value).await?,
+    true => {
+      // let the upload happen in the background
+      tokio::spawn(async move {
+        if let Err(e) = storage_service.put_object(clone_url, object_value).await {
+          error!("upload file failed: {}", e);
+        }
+      });
+    },
+  }
+  Ok(url)
+}
 
 pub async fn object_from_disk(
   workspace_id: &str,
@@ -1,12 +1,13 @@
 use crate::migrations::session_migration::migrate_session_with_user_uuid;
 use crate::services::db::UserDB;
 use crate::services::entities::{UserConfig, UserPaths};
-use crate::services::sqlite_sql::user_sql::vacuum_database;
+use crate::services::sqlite_sql::user_sql::{select_user_profile, vacuum_database};
 use collab_integrate::CollabKVDB;
 
 use flowy_error::{internal_error, ErrorCode, FlowyError, FlowyResult};
 use flowy_sqlite::kv::StorePreferences;
 use flowy_sqlite::DBConnection;
+use flowy_user_pub::entities::UserProfile;
 use flowy_user_pub::session::Session;
 use std::sync::{Arc, Weak};
 use tracing::{debug, error, info};
@@ -61,6 +62,15 @@ impl AuthenticateUser {
     Ok(session.user_workspace.id)
   }
 
+  pub fn device_id(&self) -> String {
+    self.user_config.device_id.clone()
+  }
+
+  pub fn get_user_profile(&self) -> FlowyResult<UserProfile> {
+    let uid = self.user_id()?;
+    select_user_profile(uid, self.get_sqlite_connection(uid)?)
+  }
+
   pub fn get_collab_db(&self, uid: i64) -> FlowyResult<Weak<CollabKVDB>> {
     self
       .database